@@ -0,0 +1,95 @@
+//! Timing comparisons for indicator implementations, kept out of the unit
+//! suite because wall-clock assertions are flaky on loaded machines.
+//!
+//! Run with `cargo bench --bench indicators`.
+
+use perptrix::indicators::structure::donchian::DonchianChannel;
+use perptrix::indicators::trend::ema::EmaRibbon;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+fn fastest<F: FnMut()>(runs: usize, mut f: F) -> Duration {
+    (0..runs)
+        .map(|_| {
+            let start = Instant::now();
+            f();
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+/// Deterministic zig-zag series so both implementations see the same input
+fn series(len: usize) -> Vec<(f64, f64, f64)> {
+    (0..len)
+        .map(|i| {
+            let base = 100.0 + (i as f64 * 0.37).sin() * 20.0 + (i % 17) as f64;
+            (base + 1.5, base - 1.5, base)
+        })
+        .collect()
+}
+
+/// Reference O(period) rescan of the window for every candle
+fn naive_channel(candles: &[(f64, f64, f64)], period: usize) -> Vec<(f64, f64)> {
+    (period..=candles.len())
+        .map(|end| {
+            let window = &candles[end - period..end];
+            let upper = window.iter().map(|c| c.0).fold(f64::MIN, f64::max);
+            let lower = window.iter().map(|c| c.1).fold(f64::MAX, f64::min);
+            (upper, lower)
+        })
+        .collect()
+}
+
+fn deque_channel(candles: &[(f64, f64, f64)], period: usize) -> Vec<(f64, f64)> {
+    let mut donchian = DonchianChannel::new(period);
+    candles
+        .iter()
+        .filter_map(|&(high, low, close)| donchian.update(high, low, close))
+        .map(|(upper, _, lower, _)| (upper, lower))
+        .collect()
+}
+
+/// With a wide window the naive scan touches every candle in it, while the
+/// deque does constant amortized work per update
+fn donchian_deque_vs_naive_scan() {
+    let candles = series(1000);
+    let period = 250;
+
+    let deque = fastest(5, || {
+        black_box(deque_channel(black_box(&candles), period));
+    });
+    let naive = fastest(5, || {
+        black_box(naive_channel(black_box(&candles), period));
+    });
+    println!(
+        "donchian, 1000 candles, period {}: deque {:?}, naive {:?}",
+        period, deque, naive
+    );
+}
+
+fn feed_ribbon(count: usize) -> Option<f64> {
+    let mut ribbon = EmaRibbon::default();
+    for i in 0..count {
+        let price = 100.0 + (i as f64 * 0.01).sin() * 10.0;
+        black_box(ribbon.update(black_box(price)));
+    }
+    ribbon.score()
+}
+
+/// Each update touches a fixed number of EMAs, so ten times the candles
+/// should take about ten times as long
+fn ribbon_scaling() {
+    let small = fastest(3, || {
+        black_box(feed_ribbon(100_000));
+    });
+    let large = fastest(3, || {
+        black_box(feed_ribbon(1_000_000));
+    });
+    println!("EMA ribbon: 100k candles {:?}, 1M candles {:?}", small, large);
+}
+
+fn main() {
+    donchian_deque_vs_naive_scan();
+    ribbon_scaling();
+}
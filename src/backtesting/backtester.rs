@@ -0,0 +1,250 @@
+//! Replays historical candles through a strategy and simulates its trades.
+
+use crate::db::QuestDatabase;
+use crate::models::indicators::Candle;
+use crate::models::signal::{SignalDirection, SignalOutput};
+use crate::models::strategy::Strategy;
+use crate::strategies::evaluator::MIN_CANDLES;
+use crate::strategies::StrategyEvaluator;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Why a simulated trade was closed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub enum ExitReason {
+    StopLoss,
+    TakeProfit,
+    /// Still open on the last candle; closed at its close
+    EndOfData,
+}
+
+/// A simulated trade opened on a strategy signal
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TradeRecord {
+    /// Signal that opened the trade, timestamped with its candle
+    pub signal: SignalOutput,
+    pub entry_time: DateTime<Utc>,
+    pub entry_price: f64,
+    pub stop_loss_price: f64,
+    pub take_profit_price: f64,
+    pub exit_time: DateTime<Utc>,
+    pub exit_price: f64,
+    pub exit_reason: ExitReason,
+    /// Return on the position in percent; positive for winning trades
+    pub pnl_pct: f64,
+}
+
+/// Summary of a backtest run
+#[derive(Debug, Clone, Default, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct BacktestReport {
+    /// Long and short signals generated, including those skipped while a trade was open
+    pub total_signals: usize,
+    pub total_trades: usize,
+    /// Fraction of trades with positive P&L (0.0-1.0)
+    pub win_rate: f64,
+    /// Mean trade P&L in percent
+    pub average_pnl_pct: f64,
+    /// Largest peak-to-trough drop of cumulative trade P&L, in percentage points
+    pub max_drawdown_pct: f64,
+    /// Trades in the order they were opened
+    pub trades: Vec<TradeRecord>,
+}
+
+/// Runs a strategy over historical candles.
+///
+/// A window of `MIN_CANDLES` candles slides forward one candle at a time and
+/// the strategy is evaluated on each window. A long or short signal opens a
+/// position at the window's last close when no position is open; the position
+/// is held until a later candle touches its stop loss or take profit. When a
+/// candle touches both, the stop loss is assumed to have been hit first.
+#[derive(Debug, Clone)]
+pub struct Backtester {
+    strategy: Strategy,
+}
+
+struct OpenPosition {
+    signal: SignalOutput,
+    stop_loss_price: f64,
+    take_profit_price: f64,
+}
+
+impl OpenPosition {
+    /// Open a position on a signal, or `None` if it has no SL/TP to exit on
+    fn open(signal: SignalOutput) -> Option<Self> {
+        if signal.recommended_sl_pct <= 0.0 || signal.recommended_tp_pct <= 0.0 {
+            return None;
+        }
+
+        let entry = signal.price;
+        let sl_offset = entry * signal.recommended_sl_pct / 100.0;
+        let tp_offset = entry * signal.recommended_tp_pct / 100.0;
+        let (stop_loss_price, take_profit_price) = match signal.direction {
+            SignalDirection::Long => (entry - sl_offset, entry + tp_offset),
+            SignalDirection::Short => (entry + sl_offset, entry - tp_offset),
+            SignalDirection::Neutral => return None,
+        };
+
+        Some(Self {
+            signal,
+            stop_loss_price,
+            take_profit_price,
+        })
+    }
+
+    /// Exit price and reason if `candle` touches the stop loss or take profit
+    fn exit_on(&self, candle: &Candle) -> Option<(f64, ExitReason)> {
+        let (stop_hit, target_hit) = match self.signal.direction {
+            SignalDirection::Long => (
+                candle.low <= self.stop_loss_price,
+                candle.high >= self.take_profit_price,
+            ),
+            _ => (
+                candle.high >= self.stop_loss_price,
+                candle.low <= self.take_profit_price,
+            ),
+        };
+
+        if stop_hit {
+            Some((self.stop_loss_price, ExitReason::StopLoss))
+        } else if target_hit {
+            Some((self.take_profit_price, ExitReason::TakeProfit))
+        } else {
+            None
+        }
+    }
+
+    fn close(
+        self,
+        exit_time: DateTime<Utc>,
+        exit_price: f64,
+        exit_reason: ExitReason,
+    ) -> TradeRecord {
+        let entry_price = self.signal.price;
+        let change_pct = (exit_price - entry_price) / entry_price * 100.0;
+        let pnl_pct = match self.signal.direction {
+            SignalDirection::Short => -change_pct,
+            _ => change_pct,
+        };
+
+        TradeRecord {
+            entry_time: self.signal.timestamp,
+            entry_price,
+            stop_loss_price: self.stop_loss_price,
+            take_profit_price: self.take_profit_price,
+            signal: self.signal,
+            exit_time,
+            exit_price,
+            exit_reason,
+            pnl_pct,
+        }
+    }
+}
+
+impl Backtester {
+    pub fn new(strategy: Strategy) -> Self {
+        Self { strategy }
+    }
+
+    /// Backtest against candles (oldest first)
+    pub fn run(&self, candles: &[Candle]) -> BacktestReport {
+        let mut total_signals = 0;
+        let mut trades = Vec::new();
+        let mut position: Option<OpenPosition> = None;
+
+        for end in MIN_CANDLES..=candles.len() {
+            let candle = &candles[end - 1];
+
+            if let Some((exit_price, reason)) = position.as_ref().and_then(|p| p.exit_on(candle)) {
+                if let Some(open) = position.take() {
+                    trades.push(open.close(candle.timestamp, exit_price, reason));
+                }
+            }
+
+            let window = &candles[end - MIN_CANDLES..end];
+            // Evaluate as of the window's last candle so time filters match
+            // the history being replayed rather than the wall clock
+            let Some(mut signal) =
+                StrategyEvaluator::evaluate_strategy_at(&self.strategy, window, candle.timestamp)
+            else {
+                continue;
+            };
+            if signal.direction == SignalDirection::Neutral {
+                continue;
+            }
+
+            signal.timestamp = candle.timestamp;
+            total_signals += 1;
+            if position.is_none() {
+                position = OpenPosition::open(signal);
+            }
+        }
+
+        if let (Some(open), Some(last)) = (position, candles.last()) {
+            trades.push(open.close(last.timestamp, last.close, ExitReason::EndOfData));
+        }
+
+        BacktestReport::new(total_signals, trades)
+    }
+
+    /// Backtest against stored candles with `from <= timestamp < to`.
+    ///
+    /// The first `MIN_CANDLES - 1` candles of the range only warm up the window.
+    pub async fn run_range(
+        &self,
+        database: &QuestDatabase,
+        symbol: &str,
+        interval: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<BacktestReport, Box<dyn std::error::Error + Send + Sync>> {
+        let candles = database
+            .get_candles_between(symbol, interval, from, to)
+            .await?;
+
+        // Replaying a long range is CPU-bound, keep it off the async workers
+        let backtester = self.clone();
+        let report = tokio::task::spawn_blocking(move || backtester.run(&candles))
+            .await
+            .map_err(|e| {
+                Box::new(std::io::Error::other(format!(
+                    "Backtest task failed: {}",
+                    e
+                ))) as Box<dyn std::error::Error + Send + Sync>
+            })?;
+
+        Ok(report)
+    }
+}
+
+impl BacktestReport {
+    fn new(total_signals: usize, trades: Vec<TradeRecord>) -> Self {
+        let total_trades = trades.len();
+        if total_trades == 0 {
+            return Self {
+                total_signals,
+                ..Self::default()
+            };
+        }
+
+        let wins = trades.iter().filter(|t| t.pnl_pct > 0.0).count();
+        let total_pnl: f64 = trades.iter().map(|t| t.pnl_pct).sum();
+
+        let mut cumulative = 0.0_f64;
+        let mut peak = 0.0_f64;
+        let mut max_drawdown_pct = 0.0_f64;
+        for trade in &trades {
+            cumulative += trade.pnl_pct;
+            peak = peak.max(cumulative);
+            max_drawdown_pct = max_drawdown_pct.max(peak - cumulative);
+        }
+
+        Self {
+            total_signals,
+            total_trades,
+            win_rate: wins as f64 / total_trades as f64,
+            average_pnl_pct: total_pnl / total_trades as f64,
+            max_drawdown_pct,
+            trades,
+        }
+    }
+}
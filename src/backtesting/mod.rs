@@ -0,0 +1,5 @@
+//! Strategy backtesting against historical candles.
+
+pub mod backtester;
+
+pub use backtester::{BacktestReport, Backtester, ExitReason, TradeRecord};
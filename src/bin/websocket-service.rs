@@ -9,7 +9,9 @@ use perptrix::cache::RedisCache;
 use perptrix::db::QuestDatabase;
 use perptrix::logging;
 use perptrix::metrics::Metrics;
-use perptrix::services::hyperliquid::HyperliquidMarketDataProvider;
+use perptrix::services::hyperliquid::{
+    HyperliquidClient, HyperliquidMarketDataProvider, DEFAULT_CONNECTION_POLL_INTERVAL,
+};
 use perptrix::services::websocket::WebSocketService;
 use std::env;
 use std::sync::Arc;
@@ -48,7 +50,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         Ok(db) => {
             info!("QuestDB connected");
             metrics.database_connected.set(1.0);
-            Some(Arc::new(db))
+            Some(Arc::new(db.with_metrics(metrics.clone())))
         }
         Err(e) => {
             warn!(error = %e, "Failed to connect to QuestDB");
@@ -76,7 +78,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Initialize WebSocket Service (long-lived, maintains connection)
     info!("Initializing WebSocket service...");
-    let mut ws_provider = HyperliquidMarketDataProvider::new();
+    let hyperliquid_client = HyperliquidClient::new().with_metrics(metrics.clone());
+    let mut ws_provider = HyperliquidMarketDataProvider::with_websocket_client(Arc::new(hyperliquid_client))
+        .with_metrics(metrics.clone());
     if let Some(ref db) = database {
         ws_provider = ws_provider.with_database(db.clone());
     }
@@ -84,13 +88,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         ws_provider = ws_provider.with_cache(c.clone());
     }
 
-    let ws_service = WebSocketService::new(ws_provider);
+    // The API server reports the connection on /health from the heartbeat
+    let mut ws_service = WebSocketService::new(ws_provider);
+    if let Some(ref c) = cache {
+        ws_service = ws_service.with_cache(c.clone());
+    }
     ws_service.start().await.map_err(|e| format!("Failed to start WebSocket service: {}", e))?;
 
     // Wait for connection to establish (with timeout)
     info!("Waiting for WebSocket connection...");
     let ws_client = ws_service.get_provider().client();
-    if ws_client.wait_for_connection(Duration::from_secs(10)).await {
+    if ws_client
+        .wait_for_connection(Duration::from_secs(10), DEFAULT_CONNECTION_POLL_INTERVAL)
+        .await
+    {
         info!("WebSocket connected");
         metrics.websocket_connected.set(1.0);
     } else {
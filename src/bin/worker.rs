@@ -5,21 +5,29 @@
 
 use dotenvy::dotenv;
 use perptrix::cache::RedisCache;
-use perptrix::core::runtime::{RuntimeConfig, SignalRuntime};
+use perptrix::core::runtime::{
+    priority_fetch_storage, RetryPolicyConfig, RuntimeConfig, SignalRuntime,
+};
 use perptrix::core::scheduler::JobScheduler;
 use perptrix::db::QuestDatabase;
 use perptrix::jobs::context::JobContext;
+use perptrix::jobs::handlers;
 use perptrix::jobs::types::{EvaluateSignalJob, FetchCandlesJob, StoreSignalJob};
 use perptrix::logging;
 use perptrix::metrics::Metrics;
+use perptrix::output::NotificationConfig;
 use perptrix::services::hyperliquid::HyperliquidMarketDataProvider;
 use perptrix::services::market_data::MarketDataProvider;
 use apalis_redis::RedisStorage;
 use std::env;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::signal;
 use tracing::{info, warn};
 
+/// How often the worker checks QuestDB and refreshes cached strategies
+const DATABASE_HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Load environment variables from .env if present
@@ -50,7 +58,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         Ok(db) => {
             info!("QuestDB connected");
             metrics.database_connected.set(1.0);
-            Some(Arc::new(db))
+            Some(Arc::new(db.with_metrics(metrics.clone())))
         }
         Err(e) => {
             warn!(error = %e, "Failed to connect to QuestDB");
@@ -134,6 +142,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let conn = apalis_redis::connect(redis_url.clone()).await?;
     let fetch_storage: Arc<RedisStorage<FetchCandlesJob>> =
         Arc::new(RedisStorage::new(conn.clone()));
+    // Confident symbols are re-evaluated from their own queue, drained first
+    let priority_fetch_storage = Arc::new(priority_fetch_storage(conn.clone()));
     let eval_storage: Arc<RedisStorage<EvaluateSignalJob>> =
         Arc::new(RedisStorage::new(conn.clone()));
     let store_storage: Arc<RedisStorage<StoreSignalJob>> =
@@ -141,11 +151,60 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     info!("Apalis Redis storage initialized");
 
     // Create job context
-    let job_context = Arc::new(JobContext::new(
+    let mut job_context = JobContext::new(
         read_only_provider,
         database.clone(),
         Some(metrics.clone()),
-    ));
+    )
+    .with_eval_interval(eval_interval)
+    .with_retry_policies(RetryPolicyConfig::from_env());
+    if let Some(ref c) = cache {
+        job_context = job_context.with_cache(c.clone());
+    }
+    let notifications = NotificationConfig::from_env();
+    if let Some(ref discord) = notifications.discord {
+        info!(
+            min_confidence = discord.min_confidence(),
+            "Posting signals to Discord"
+        );
+    }
+    if let Some(ref telegram) = notifications.telegram {
+        info!(
+            min_confidence = telegram.min_confidence(),
+            "Posting signals to Telegram"
+        );
+    }
+    if let Some(ref webhook) = notifications.webhook {
+        info!(signed = webhook.is_signed(), "Posting signals to webhook");
+    }
+    job_context = job_context.with_notifications(notifications);
+    let job_context = Arc::new(job_context);
+
+    // Cache strategies in Redis so evaluation continues if QuestDB goes down
+    if let Err(e) = job_context.refresh_strategy_cache(&symbols).await {
+        warn!(error = %e, "Failed to populate strategy cache");
+    }
+    let strategy_cache_refresh = {
+        let job_context = job_context.clone();
+        let symbols = symbols.clone();
+        tokio::spawn(async move {
+            refresh_strategy_cache_while_healthy(job_context, symbols).await;
+        })
+    };
+
+    let cluster_signals = {
+        let job_context = job_context.clone();
+        let symbols = symbols.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(handlers::CLUSTER_SIGNALS_INTERVAL);
+            loop {
+                interval.tick().await;
+                handlers::handle_cluster_signals(&job_context, &symbols).await;
+            }
+        })
+    };
+
+    let database = job_context.database.clone();
 
     // Initialize and start job runtime (workers)
     info!("Starting Apalis workers...");
@@ -156,13 +215,23 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         eval_storage.clone(),
         store_storage.clone(),
     )
+    .with_priority_storage(priority_fetch_storage.clone())
     .with_concurrency(concurrency);
     let worker_handles = runtime.start_workers().await.map_err(|e| format!("Failed to start workers: {}", e))?;
 
     // Initialize and start scheduler
     info!("Starting job scheduler...");
-    let scheduler = JobScheduler::new(fetch_storage, symbols.clone(), eval_interval)
+    let mut scheduler = JobScheduler::new(fetch_storage, symbols.clone(), eval_interval)
         .map_err(|e| format!("Failed to create scheduler: {}", e))?;
+    if let Some(ref c) = cache {
+        scheduler = scheduler.with_cache(c.clone());
+    }
+    if let Some(database) = database {
+        scheduler = scheduler.with_database(database);
+    }
+    scheduler = scheduler
+        .with_priority_storage(priority_fetch_storage)
+        .with_metrics(metrics.clone());
     scheduler.start().await.map_err(|e| format!("Failed to start scheduler: {}", e))?;
 
     // Graceful shutdown
@@ -171,6 +240,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         _ = signal::ctrl_c() => {
             info!("Shutting down worker...");
             scheduler.stop().await;
+            strategy_cache_refresh.abort();
+            cluster_signals.abort();
             for handle in worker_handles {
                 handle.abort();
             }
@@ -181,3 +252,34 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Periodically check QuestDB, reconnecting broken connections, and refresh
+/// the strategy cache whenever it is healthy
+async fn refresh_strategy_cache_while_healthy(job_context: Arc<JobContext>, symbols: Vec<String>) {
+    let Some(db) = job_context.database.clone() else {
+        return;
+    };
+    let mut healthy = true;
+    let mut interval = tokio::time::interval(DATABASE_HEALTH_CHECK_INTERVAL);
+    interval.tick().await;
+    loop {
+        interval.tick().await;
+        let was_healthy = std::mem::replace(&mut healthy, db.is_healthy().await);
+        if let Some(ref metrics) = job_context.metrics {
+            metrics
+                .database_connected
+                .set(if healthy { 1.0 } else { 0.0 });
+        }
+        if !healthy {
+            if was_healthy {
+                warn!("QuestDB unavailable, evaluating cached strategies");
+            }
+            continue;
+        }
+        if !was_healthy {
+            info!("QuestDB reconnected, refreshing strategy cache");
+        }
+        if let Err(e) = job_context.refresh_strategy_cache(&symbols).await {
+            warn!(error = %e, "Failed to refresh strategy cache");
+        }
+    }
+}
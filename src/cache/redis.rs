@@ -1,101 +1,188 @@
 //! Redis cache for candles
 
 use crate::config;
+use crate::error::KryptexError;
 use crate::models::indicators::Candle;
-use redis::AsyncCommands;
+use futures_util::{Stream, StreamExt};
+use redis::{AsyncCommands, ExistenceCheck, SetExpiry, SetOptions};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
-const CANDLE_CACHE_TTL: i64 = 3600; // 1 hour in seconds
 const CACHE_KEY_PREFIX: &str = "candles";
+const LATEST_SIGNAL_KEY_PREFIX: &str = "kryptex:signal:latest";
+const STRATEGY_STATS_KEY_PREFIX: &str = "kryptex:strategy:stats";
+const SIGNAL_STATS_KEY_PREFIX: &str = "kryptex:signal:stats";
+const SIGNAL_CLUSTER_KEY_PREFIX: &str = "kryptex:signal:cluster";
+const SCHEDULER_LAST_TICK_KEY_PREFIX: &str = "kryptex:scheduler:last_tick";
+const LATEST_SPREAD_KEY_PREFIX: &str = "kryptex:spread:latest";
+const LATEST_BUY_SELL_RATIO_KEY_PREFIX: &str = "kryptex:buy_sell_ratio:latest";
+const FETCH_LOCK_KEY_PREFIX: &str = "lock:fetch";
+const STRATEGIES_KEY_PREFIX: &str = "strategies";
+/// Keys `SCAN` checks per round trip
+const SCAN_COUNT: usize = 100;
+
+/// Pub/sub channel each stored signal is published on, for API servers
+/// streaming `/api/signals/stream`
+pub const SIGNALS_CHANNEL: &str = "kryptex:signals";
+
+/// Key holding every strategy last loaded by the scheduler
+pub const ACTIVE_STRATEGIES_KEY: &str = "active_strategies";
+
+/// Key the WebSocket service refreshes with its connection state, read by
+/// API servers for `/health`
+pub const WEBSOCKET_HEARTBEAT_KEY: &str = "kryptex:websocket:heartbeat";
+
+/// Sorted set holding cached candles for a symbol and interval, scored by UNIX timestamp
+pub fn candles_key(symbol: &str, interval: &str) -> String {
+    format!("{}:{}:{}", CACHE_KEY_PREFIX, symbol, interval)
+}
+
+/// Build the cache key holding the latest signal for a symbol
+pub fn latest_signal_key(symbol: &str) -> String {
+    format!("{}:{}", LATEST_SIGNAL_KEY_PREFIX, symbol)
+}
+
+/// Build the cache key holding the latest signal cluster analysis for a symbol
+pub fn signal_cluster_key(symbol: &str) -> String {
+    format!("{}:{}", SIGNAL_CLUSTER_KEY_PREFIX, symbol)
+}
+
+/// Build the cache key holding strategy stats for a time window
+pub fn strategy_stats_key(window: &str) -> String {
+    format!("{}:{}", STRATEGY_STATS_KEY_PREFIX, window)
+}
+
+/// Build the cache key holding signal stats for a symbol (or "all") and a
+/// time window
+pub fn signal_stats_key(symbol: &str, window: &str) -> String {
+    format!("{}:{}:{}", SIGNAL_STATS_KEY_PREFIX, symbol, window)
+}
+
+/// Build the cache key holding the latest `(best_bid, best_ask)` for a symbol
+pub fn latest_spread_key(symbol: &str) -> String {
+    format!("{}:{}", LATEST_SPREAD_KEY_PREFIX, symbol)
+}
+
+/// Build the cache key holding the latest buy/sell volume ratio for a symbol
+pub fn latest_buy_sell_ratio_key(symbol: &str) -> String {
+    format!("{}:{}", LATEST_BUY_SELL_RATIO_KEY_PREFIX, symbol)
+}
+
+/// Redis key holding the time of the last scheduler tick that enqueued `symbol`
+pub fn scheduler_last_tick_key(symbol: &str) -> String {
+    format!("{}:{}", SCHEDULER_LAST_TICK_KEY_PREFIX, symbol)
+}
+
+/// Redis key held while a `FetchCandlesJob` for `symbol` is queued for the
+/// current interval
+pub fn fetch_lock_key(symbol: &str) -> String {
+    format!("{}:{}", FETCH_LOCK_KEY_PREFIX, symbol)
+}
+
+/// Build the cache key holding the strategies configured for a symbol
+pub fn strategies_key(symbol: &str) -> String {
+    format!("{}:{}", STRATEGIES_KEY_PREFIX, symbol)
+}
 
 pub struct RedisCache {
     client: Arc<RwLock<Option<redis::aio::ConnectionManager>>>,
+    max_candles_per_symbol: usize,
 }
 
 impl RedisCache {
-    pub async fn new() -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+    pub async fn new() -> Result<Self, KryptexError> {
         let redis_url = config::get_redis_url();
-        let client = redis::Client::open(redis_url.as_str()).map_err(|e| {
-            Box::new(std::io::Error::new(
-                std::io::ErrorKind::ConnectionRefused,
-                format!("Failed to create Redis client: {}", e),
-            )) as Box<dyn std::error::Error + Send + Sync>
-        })?;
-
-        let connection = client.get_connection_manager().await.map_err(|e| {
-            Box::new(std::io::Error::new(
-                std::io::ErrorKind::ConnectionRefused,
-                format!("Failed to connect to Redis: {}", e),
-            )) as Box<dyn std::error::Error + Send + Sync>
-        })?;
+        let client = redis::Client::open(redis_url.as_str())?;
+
+        let connection = client.get_connection_manager().await?;
 
         Ok(Self {
             client: Arc::new(RwLock::new(Some(connection))),
+            max_candles_per_symbol: config::get_max_cached_candles_per_symbol(),
         })
     }
 
-    /// Cache candles for a symbol and interval
-    pub async fn cache_candles(
+    /// Limit how many candles are kept per symbol and interval
+    pub fn with_max_candles_per_symbol(mut self, max_candles: usize) -> Self {
+        self.max_candles_per_symbol = max_candles.max(1);
+        self
+    }
+
+    /// Add candles to the sorted set for a symbol and interval.
+    ///
+    /// Each candle is scored by its UNIX timestamp, replacing any cached
+    /// candle with the same timestamp. The set is trimmed to the newest
+    /// `max_candles_per_symbol` and its TTL reset to `ttl_seconds`
+    /// (0 keeps the key without expiry).
+    pub async fn store_candles(
         &self,
         symbol: &str,
         interval: &str,
         candles: &[Candle],
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        ttl_seconds: u64,
+    ) -> Result<(), KryptexError> {
+        if candles.is_empty() {
+            return Ok(());
+        }
+
         let mut conn = self.client.write().await;
         if let Some(ref mut c) = *conn {
-            let key = format!("{}:{}:{}", CACHE_KEY_PREFIX, symbol, interval);
-            let json = serde_json::to_string(candles).map_err(|e| {
-                Box::new(std::io::Error::new(
-                    std::io::ErrorKind::InvalidData,
-                    format!("Failed to serialize candles: {}", e),
-                )) as Box<dyn std::error::Error + Send + Sync>
-            })?;
-
-            c.set::<_, _, ()>(&key, &json).await.map_err(|e| {
-                Box::new(std::io::Error::other(format!("Failed to set cache: {}", e)))
-                    as Box<dyn std::error::Error + Send + Sync>
-            })?;
-
-            c.expire::<_, ()>(&key, CANDLE_CACHE_TTL)
-                .await
-                .map_err(|e| {
-                    Box::new(std::io::Error::other(format!(
-                        "Failed to set cache TTL: {}",
-                        e
-                    ))) as Box<dyn std::error::Error + Send + Sync>
-                })?;
+            let key = candles_key(symbol, interval);
+            let mut pipe = redis::pipe();
+            pipe.atomic();
+            for candle in candles {
+                let score = candle.timestamp.timestamp();
+                let json = serde_json::to_string(candle)?;
+                pipe.zrembyscore(&key, score, score).ignore();
+                pipe.zadd(&key, json, score).ignore();
+            }
+            pipe.zremrangebyrank(&key, 0, -(self.max_candles_per_symbol as isize) - 1)
+                .ignore();
+            if ttl_seconds > 0 {
+                pipe.expire(&key, ttl_seconds as i64).ignore();
+            }
+
+            pipe.query_async::<()>(c).await?;
         }
 
         Ok(())
     }
 
-    /// Get cached candles for a symbol and interval
-    pub async fn get_cached_candles(
+    /// Get up to `limit` of the newest cached candles, oldest first.
+    ///
+    /// Returns an empty list on a cache miss. The set is trimmed to
+    /// `max_candles_per_symbol` first to keep memory bounded.
+    pub async fn get_candles(
         &self,
         symbol: &str,
         interval: &str,
-    ) -> Result<Option<Vec<Candle>>, Box<dyn std::error::Error + Send + Sync>> {
+        limit: usize,
+    ) -> Result<Vec<Candle>, KryptexError> {
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+
         let mut conn = self.client.write().await;
         if let Some(ref mut c) = *conn {
-            let key = format!("{}:{}:{}", CACHE_KEY_PREFIX, symbol, interval);
-            let json: Option<String> = c.get(&key).await.map_err(|e| {
-                Box::new(std::io::Error::other(format!("Failed to get cache: {}", e)))
-                    as Box<dyn std::error::Error + Send + Sync>
-            })?;
+            let key = candles_key(symbol, interval);
+            let (members,): (Vec<String>,) = redis::pipe()
+                .zremrangebyrank(&key, 0, -(self.max_candles_per_symbol as isize) - 1)
+                .ignore()
+                .zrevrange(&key, 0, limit as isize - 1)
+                .query_async(c)
+                .await?;
 
-            if let Some(json_str) = json {
-                let candles: Vec<Candle> = serde_json::from_str(&json_str).map_err(|e| {
-                    Box::new(std::io::Error::new(
-                        std::io::ErrorKind::InvalidData,
-                        format!("Failed to deserialize candles: {}", e),
-                    )) as Box<dyn std::error::Error + Send + Sync>
-                })?;
-                return Ok(Some(candles));
-            }
+            let mut candles = members
+                .iter()
+                .map(|json| serde_json::from_str::<Candle>(json))
+                .collect::<Result<Vec<_>, _>>()?;
+            candles.reverse();
+            return Ok(candles);
         }
 
-        Ok(None)
+        Ok(Vec::new())
     }
 
     /// Invalidate cache for a symbol and interval
@@ -103,18 +190,156 @@ impl RedisCache {
         &self,
         symbol: &str,
         interval: &str,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    ) -> Result<(), KryptexError> {
+        let mut conn = self.client.write().await;
+        if let Some(ref mut c) = *conn {
+            let key = candles_key(symbol, interval);
+            c.del::<_, ()>(&key).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Serialize a value as JSON and store it under `key` with a TTL
+    ///
+    /// A `ttl_seconds` of 0 stores the value without expiry.
+    pub async fn set_json<T: Serialize>(
+        &self,
+        key: &str,
+        value: &T,
+        ttl_seconds: u64,
+    ) -> Result<(), KryptexError> {
+        let mut conn = self.client.write().await;
+        if let Some(ref mut c) = *conn {
+            let json = serde_json::to_string(value)?;
+
+            let result = if ttl_seconds == 0 {
+                c.set::<_, _, ()>(key, &json).await
+            } else {
+                c.set_ex::<_, _, ()>(key, &json, ttl_seconds).await
+            };
+            result?;
+        }
+
+        Ok(())
+    }
+
+    /// Get a JSON value stored under `key`, returning `None` if it is missing or expired
+    pub async fn get_json<T: DeserializeOwned>(
+        &self,
+        key: &str,
+    ) -> Result<Option<T>, KryptexError> {
         let mut conn = self.client.write().await;
         if let Some(ref mut c) = *conn {
-            let key = format!("{}:{}:{}", CACHE_KEY_PREFIX, symbol, interval);
-            c.del::<_, ()>(&key).await.map_err(|e| {
-                Box::new(std::io::Error::other(format!(
-                    "Failed to delete cache: {}",
-                    e
-                ))) as Box<dyn std::error::Error + Send + Sync>
-            })?;
+            let json: Option<String> = c.get(key).await?;
+
+            if let Some(json_str) = json {
+                let value: T = serde_json::from_str(&json_str)?;
+                return Ok(Some(value));
+            }
         }
 
+        Ok(None)
+    }
+
+    /// Delete `key`, returning whether it existed
+    pub async fn delete(&self, key: &str) -> Result<bool, KryptexError> {
+        let mut conn = self.client.write().await;
+        if let Some(ref mut c) = *conn {
+            let removed: usize = c.del(key).await?;
+            return Ok(removed > 0);
+        }
+
+        Ok(false)
+    }
+
+    /// Keys matching a glob `pattern`, found with `SCAN` so large keyspaces
+    /// do not block the server
+    pub async fn scan_keys(&self, pattern: &str) -> Result<Vec<String>, KryptexError> {
+        let mut conn = self.client.write().await;
+        let mut keys = Vec::new();
+        if let Some(ref mut c) = *conn {
+            let mut cursor = 0u64;
+            loop {
+                let (next, batch): (u64, Vec<String>) = redis::cmd("SCAN")
+                    .arg(cursor)
+                    .arg("MATCH")
+                    .arg(pattern)
+                    .arg("COUNT")
+                    .arg(SCAN_COUNT)
+                    .query_async(c)
+                    .await?;
+                keys.extend(batch);
+                if next == 0 {
+                    break;
+                }
+                cursor = next;
+            }
+        }
+
+        Ok(keys)
+    }
+
+    /// Store `value` under `key` with a TTL unless the key already exists
+    /// (`SET key value NX EX ttl`).
+    ///
+    /// Returns whether the value was stored. The check and the write are a
+    /// single command, so concurrent callers cannot both succeed.
+    pub async fn set_nx_ex(
+        &self,
+        key: &str,
+        value: &str,
+        ttl_seconds: u64,
+    ) -> Result<bool, KryptexError> {
+        let mut conn = self.client.write().await;
+        let Some(ref mut c) = *conn else {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::NotConnected,
+                "Redis not connected",
+            )
+            .into());
+        };
+
+        let options = SetOptions::default()
+            .conditional_set(ExistenceCheck::NX)
+            .with_expiration(SetExpiry::EX(ttl_seconds.max(1)));
+        let reply: Option<String> = c.set_options(key, value, options).await?;
+
+        Ok(reply.is_some())
+    }
+
+    /// Publish a value as JSON on a pub/sub `channel`, returning how many
+    /// subscribers received it
+    pub async fn publish_json<T: Serialize>(
+        &self,
+        channel: &str,
+        value: &T,
+    ) -> Result<usize, KryptexError> {
+        let mut conn = self.client.write().await;
+        let Some(ref mut c) = *conn else {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::NotConnected,
+                "Redis not connected",
+            )
+            .into());
+        };
+
+        let json = serde_json::to_string(value)?;
+        Ok(c.publish(channel, json).await?)
+    }
+
+    /// Send `PING`, failing when Redis is not connected or does not answer
+    pub async fn ping(&self) -> Result<(), KryptexError> {
+        let mut conn = self.client.write().await;
+        let Some(ref mut c) = *conn else {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::NotConnected,
+                "Redis not connected",
+            )
+            .into());
+        };
+
+        redis::cmd("PING").query_async::<()>(c).await?;
         Ok(())
     }
 
@@ -124,3 +349,28 @@ impl RedisCache {
         conn.is_some()
     }
 }
+
+/// Subscribe to a pub/sub `channel` on the configured Redis server, yielding
+/// each message decoded from JSON.
+///
+/// Pub/sub needs a dedicated connection, so this opens one instead of using a
+/// [`RedisCache`]. Messages that fail to decode are skipped; the stream ends
+/// when the connection drops.
+pub async fn subscribe_json<T: DeserializeOwned>(
+    channel: &str,
+) -> Result<impl Stream<Item = T>, KryptexError> {
+    let client = redis::Client::open(config::get_redis_url().as_str())?;
+    let mut pubsub = client.get_async_pubsub().await?;
+    pubsub.subscribe(channel).await?;
+
+    Ok(pubsub.into_on_message().filter_map(|message| async move {
+        let payload: String = message.get_payload().ok()?;
+        match serde_json::from_str(&payload) {
+            Ok(value) => Some(value),
+            Err(e) => {
+                tracing::warn!(channel = %message.get_channel_name(), error = %e, "Skipping undecodable pub/sub message");
+                None
+            }
+        }
+    }))
+}
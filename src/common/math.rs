@@ -1,5 +1,7 @@
 //! Mathematical utilities for indicator calculations.
 
+use crate::metrics::MATH_NAN_GUARD_TOTAL;
+
 /// Calculate Simple Moving Average (SMA)
 pub fn sma(values: &[f64], period: usize) -> Option<f64> {
     if values.len() < period {
@@ -56,10 +58,85 @@ pub fn standard_deviation(values: &[f64], period: usize) -> Option<f64> {
     Some(variance.sqrt())
 }
 
+/// Least-squares fit of a series against its index
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinearRegression {
+    pub slope: f64,
+    pub intercept: f64,
+    /// Coefficient of determination in [0, 1]; 1.0 is a perfect line
+    pub r_squared: f64,
+}
+
+/// Fit a line through `values`, using each value's index as x
+///
+/// Returns `None` for fewer than two points or non-finite input. A flat series
+/// has an `r_squared` of 0.0 since there is no variance to explain.
+pub fn linear_regression(values: &[f64]) -> Option<LinearRegression> {
+    if values.len() < 2 || values.iter().any(|v| !v.is_finite()) {
+        return None;
+    }
+
+    let n = values.len() as f64;
+    let mean_x = (n - 1.0) / 2.0;
+    let mean_y = values.iter().sum::<f64>() / n;
+
+    let mut ss_xy = 0.0;
+    let mut ss_xx = 0.0;
+    let mut ss_yy = 0.0;
+    for (i, &y) in values.iter().enumerate() {
+        let dx = i as f64 - mean_x;
+        let dy = y - mean_y;
+        ss_xy += dx * dy;
+        ss_xx += dx * dx;
+        ss_yy += dy * dy;
+    }
+
+    let slope = ss_xy / ss_xx;
+    let r_squared = if ss_yy > 0.0 {
+        ((ss_xy * ss_xy) / (ss_xx * ss_yy)).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+
+    Some(LinearRegression {
+        slope,
+        intercept: mean_y - slope * mean_x,
+        r_squared,
+    })
+}
+
 /// Calculate True Range (TR) for a single candle
+///
+/// Returns 0.0 for non-finite inputs, `high < low`, or an overflowing result so
+/// a corrupt candle cannot propagate NaN through ATR.
 pub fn true_range(current_high: f64, current_low: f64, previous_close: f64) -> f64 {
+    if !current_high.is_finite()
+        || !current_low.is_finite()
+        || !previous_close.is_finite()
+        || current_high < current_low
+    {
+        return nan_guard(
+            "true_range",
+            format_args!(
+                "high={} low={} prev_close={}",
+                current_high, current_low, previous_close
+            ),
+        );
+    }
+
     let hl = current_high - current_low;
     let hc = (current_high - previous_close).abs();
     let lc = (current_low - previous_close).abs();
-    hl.max(hc).max(lc)
+    let tr = hl.max(hc).max(lc);
+    if !tr.is_finite() {
+        return nan_guard("true_range", format_args!("result overflowed: {}", tr));
+    }
+    tr
+}
+
+/// Record a guarded math result and return the fallback value (0.0)
+fn nan_guard(function: &str, details: std::fmt::Arguments<'_>) -> f64 {
+    tracing::debug!(function = function, "NaN guard fired: {}", details);
+    MATH_NAN_GUARD_TOTAL.with_label_values(&[function]).inc();
+    0.0
 }
@@ -0,0 +1,349 @@
+//! Hot reload of strategy definitions from JSON files in `STRATEGY_DIR`
+
+use crate::db::QuestDatabase;
+use crate::jobs::types::FetchCandlesJob;
+use crate::models::strategy::{Strategy, StrategyConfig};
+use crate::strategies::validate_strategy_config;
+use apalis::prelude::*;
+use apalis_redis::RedisStorage;
+use chrono::{DateTime, Utc};
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::{mpsc, RwLock};
+use tracing::{error, info, warn};
+use utoipa::ToSchema;
+
+/// A strategy file: a [`StrategyConfig`] plus the symbol it trades.
+///
+/// `name` defaults to the file stem. The stored strategy is identified by the
+/// file name, so renaming the strategy inside the file updates it in place.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategyFile {
+    #[serde(default)]
+    pub name: Option<String>,
+    pub symbol: String,
+    #[serde(flatten)]
+    pub config: StrategyConfig,
+}
+
+/// Failure to apply one strategy file
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ReloadError {
+    pub path: String,
+    pub message: String,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// State of strategy hot reload, as reported by the API
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct ReloadStatus {
+    /// Watched directory; `None` when hot reload is disabled
+    pub strategy_dir: Option<String>,
+    /// When a file was last applied to the database
+    pub last_reload_at: Option<DateTime<Utc>>,
+    /// Files whose latest version failed to apply
+    pub errors: Vec<ReloadError>,
+}
+
+impl ReloadStatus {
+    fn record_success(&mut self, path: &Path) {
+        let path = path.display().to_string();
+        self.errors.retain(|error| error.path != path);
+        self.last_reload_at = Some(Utc::now());
+    }
+
+    fn record_error(&mut self, path: &Path, message: String) {
+        let path = path.display().to_string();
+        self.errors.retain(|error| error.path != path);
+        self.errors.push(ReloadError {
+            path,
+            message,
+            occurred_at: Utc::now(),
+        });
+    }
+}
+
+/// Strategy applied from a file, remembered so deletes and repeated events
+/// for an unchanged file can be resolved without another lookup
+struct LoadedFile {
+    strategy_id: i64,
+    contents: String,
+}
+
+/// Keeps the `strategies` table in sync with the JSON files in a directory.
+///
+/// Created and modified files are upserted by file name and trigger a
+/// `FetchCandlesJob` for the symbol; deleted files delete their strategy.
+/// Only strategies whose `source_file` matches are touched, so strategies
+/// created through the API are never overwritten or deleted by a file.
+/// Each file's latest failure is kept in [`ReloadStatus`] until it loads.
+pub struct StrategyWatcher {
+    dir: PathBuf,
+    database: Arc<QuestDatabase>,
+    fetch_queue: Option<RedisStorage<FetchCandlesJob>>,
+    status: Arc<RwLock<ReloadStatus>>,
+    loaded: HashMap<PathBuf, LoadedFile>,
+}
+
+impl StrategyWatcher {
+    pub fn new(
+        dir: impl Into<PathBuf>,
+        database: Arc<QuestDatabase>,
+        status: Arc<RwLock<ReloadStatus>>,
+    ) -> Self {
+        Self {
+            dir: dir.into(),
+            database,
+            fetch_queue: None,
+            status,
+            loaded: HashMap::new(),
+        }
+    }
+
+    /// Enqueue a `FetchCandlesJob` for each created or updated strategy
+    pub fn with_fetch_queue(mut self, fetch_queue: RedisStorage<FetchCandlesJob>) -> Self {
+        self.fetch_queue = Some(fetch_queue);
+        self
+    }
+
+    /// Load every file in the directory, then apply changes as they happen.
+    ///
+    /// The returned task owns the file watcher; aborting it stops watching.
+    pub async fn watch(
+        mut self,
+    ) -> Result<tokio::task::JoinHandle<()>, Box<dyn std::error::Error + Send + Sync>> {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut watcher: RecommendedWatcher =
+            notify::recommended_watcher(move |result: notify::Result<Event>| {
+                // The receiver only goes away when the task is aborted
+                let _ = tx.send(result);
+            })?;
+        watcher.watch(&self.dir, RecursiveMode::NonRecursive)?;
+
+        self.status.write().await.strategy_dir = Some(self.dir.display().to_string());
+        info!(dir = %self.dir.display(), "StrategyWatcher: watching for strategy files");
+        self.reload_all().await;
+
+        Ok(tokio::spawn(async move {
+            let _watcher = watcher;
+            while let Some(result) = rx.recv().await {
+                match result {
+                    Ok(event) => {
+                        for path in event.paths {
+                            self.handle_path(&path).await;
+                        }
+                    }
+                    Err(e) => {
+                        error!(error = %e, dir = %self.dir.display(), "StrategyWatcher: watch error")
+                    }
+                }
+            }
+        }))
+    }
+
+    /// Apply every `.json` file currently in the directory
+    pub async fn reload_all(&mut self) {
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                let message = format!("Failed to read strategy directory: {}", e);
+                error!(dir = %self.dir.display(), error = %e, "StrategyWatcher: {}", message);
+                let dir = self.dir.clone();
+                self.status.write().await.record_error(&dir, message);
+                return;
+            }
+        };
+
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| is_strategy_file(path))
+            .collect();
+        paths.sort();
+        for path in paths {
+            self.handle_path(&path).await;
+        }
+    }
+
+    /// Upsert the strategy in `path`, or delete it if the file is gone.
+    ///
+    /// Paths that are not `.json` files are ignored.
+    pub async fn handle_path(&mut self, path: &Path) {
+        if !is_strategy_file(path) {
+            return;
+        }
+
+        let result = if path.exists() {
+            self.apply_file(path).await
+        } else {
+            self.remove_file(path).await
+        };
+
+        let mut status = self.status.write().await;
+        match result {
+            Ok(()) => status.record_success(path),
+            Err(e) => {
+                error!(path = %path.display(), error = %e, "StrategyWatcher: failed to apply strategy file");
+                status.record_error(path, e.to_string());
+            }
+        }
+    }
+
+    async fn apply_file(
+        &mut self,
+        path: &Path,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let contents = std::fs::read_to_string(path)?;
+        if self
+            .loaded
+            .get(path)
+            .is_some_and(|loaded| loaded.contents == contents)
+        {
+            return Ok(());
+        }
+
+        let mut strategy = parse_strategy_file(path, &contents)?;
+        let existing = self.find_managed(path).await?;
+
+        let strategy_id = match existing {
+            Some(stored) => {
+                let id = stored.id.unwrap_or_default();
+                strategy.created_at = stored.created_at;
+                // Enabling and disabling is done through the API, not the file
+                strategy.enabled = stored.enabled;
+                self.database.update_strategy(id, &strategy).await?;
+                info!(
+                    path = %path.display(),
+                    strategy_id = id,
+                    strategy = %strategy.name,
+                    symbol = %strategy.symbol,
+                    "StrategyWatcher: updated strategy"
+                );
+                id
+            }
+            None => {
+                let id = self.database.create_strategy(&strategy).await?;
+                info!(
+                    path = %path.display(),
+                    strategy_id = id,
+                    strategy = %strategy.name,
+                    symbol = %strategy.symbol,
+                    "StrategyWatcher: created strategy"
+                );
+                id
+            }
+        };
+        self.loaded.insert(
+            path.to_path_buf(),
+            LoadedFile {
+                strategy_id,
+                contents,
+            },
+        );
+
+        if let Some(ref queue) = self.fetch_queue {
+            let job = FetchCandlesJob::new(strategy.symbol.clone());
+            match queue.clone().push(job).await {
+                Ok(_) => info!(
+                    symbol = %strategy.symbol,
+                    "StrategyWatcher: enqueued FetchCandlesJob"
+                ),
+                Err(e) => warn!(
+                    symbol = %strategy.symbol,
+                    error = %e,
+                    "StrategyWatcher: failed to enqueue FetchCandlesJob"
+                ),
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn remove_file(
+        &mut self,
+        path: &Path,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let strategy_id = match self.loaded.remove(path) {
+            Some(loaded) => loaded.strategy_id,
+            // Deleted before this process loaded it
+            None => match self.find_managed(path).await?.and_then(|stored| stored.id) {
+                Some(id) => id,
+                None => return Ok(()),
+            },
+        };
+
+        self.database.delete_strategy(strategy_id).await?;
+        info!(
+            path = %path.display(),
+            strategy_id = strategy_id,
+            "StrategyWatcher: deleted strategy"
+        );
+        Ok(())
+    }
+
+    /// The stored strategy managed by `path`, if any
+    async fn find_managed(
+        &self,
+        path: &Path,
+    ) -> Result<Option<Strategy>, Box<dyn std::error::Error + Send + Sync>> {
+        let source_file = file_name(path);
+        Ok(self
+            .database
+            .get_strategies(None)
+            .await?
+            .into_iter()
+            .find(|stored| stored.source_file.as_deref() == Some(source_file.as_str())))
+    }
+}
+
+/// Parse and validate a strategy file's contents
+pub fn parse_strategy_file(
+    path: &Path,
+    contents: &str,
+) -> Result<Strategy, Box<dyn std::error::Error + Send + Sync>> {
+    let file: StrategyFile = serde_json::from_str(contents).map_err(|e| {
+        Box::new(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("Invalid strategy file: {}", e),
+        )) as Box<dyn std::error::Error + Send + Sync>
+    })?;
+    let errors = validate_strategy_config(&file.config);
+    if !errors.is_empty() {
+        let reasons: Vec<String> = errors.iter().map(ToString::to_string).collect();
+        return Err(Box::new(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("Invalid strategy config: {}", reasons.join("; ")),
+        )));
+    }
+
+    let now = Utc::now();
+    Ok(Strategy {
+        id: None,
+        name: file.name.unwrap_or_else(|| file_stem(path)),
+        symbol: file.symbol,
+        config: file.config,
+        enabled: true,
+        source_file: Some(file_name(path)),
+        created_at: now,
+        updated_at: now,
+    })
+}
+
+fn is_strategy_file(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension == "json")
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
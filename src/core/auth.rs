@@ -0,0 +1,222 @@
+//! API key authentication for the HTTP server
+//!
+//! Requests to `/api/*` must carry a key from `API_KEYS` in the `X-API-Key`
+//! header. Everything else (`/health`, `/metrics`, `/docs`) stays public.
+
+use axum::{
+    extract::Request,
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+use futures_util::future::BoxFuture;
+use prometheus::Counter;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+use utoipa::openapi::security::{
+    ApiKey as ApiKeyScheme, ApiKeyValue, SecurityRequirement, SecurityScheme,
+};
+use utoipa::Modify;
+
+use crate::core::http::ErrorResponse;
+
+/// Header carrying the API key
+pub const API_KEY_HEADER: &str = "x-api-key";
+/// Routes under this prefix require a key
+const PROTECTED_PREFIX: &str = "/api/";
+/// Name of the security scheme in the OpenAPI document
+const SECURITY_SCHEME: &str = "api_key";
+
+/// Identity of the key that authenticated a request, stored in the request
+/// extensions for downstream handlers and middleware
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiKeyIdentity {
+    /// The key's name when configured as `name:key`, otherwise a masked prefix
+    pub key_id: String,
+}
+
+#[derive(Debug, Clone)]
+struct ApiKey {
+    id: String,
+    secret: String,
+}
+
+impl ApiKey {
+    /// Parse an `API_KEYS` entry, either `key` or `name:key`
+    fn parse(entry: &str) -> Option<Self> {
+        let entry = entry.trim();
+        let (id, secret) = match entry.split_once(':') {
+            Some((name, secret)) if !name.trim().is_empty() => {
+                (name.trim().to_string(), secret.trim())
+            }
+            _ => (mask(entry), entry),
+        };
+        if secret.is_empty() {
+            return None;
+        }
+        Some(Self {
+            id,
+            secret: secret.to_string(),
+        })
+    }
+}
+
+/// Layer that rejects `/api/*` requests without a valid `X-API-Key`.
+///
+/// With no keys configured authentication is disabled and every request
+/// passes through, so local setups keep working without `API_KEYS`.
+#[derive(Clone)]
+pub struct ApiKeyAuthLayer {
+    keys: Arc<Vec<ApiKey>>,
+    failures: Counter,
+}
+
+impl ApiKeyAuthLayer {
+    /// Build from raw `API_KEYS` entries; blank entries are ignored.
+    /// `failures` is incremented for every rejected request.
+    pub fn new<I, S>(keys: I, failures: Counter) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let keys = keys
+            .into_iter()
+            .filter_map(|entry| ApiKey::parse(entry.as_ref()))
+            .collect();
+        Self {
+            keys: Arc::new(keys),
+            failures,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.keys.is_empty()
+    }
+
+    fn authenticate(&self, presented: &str) -> Option<ApiKeyIdentity> {
+        // Check every key so the time taken doesn't reveal which one matched
+        let mut identity = None;
+        for key in self.keys.iter() {
+            if constant_time_eq(key.secret.as_bytes(), presented.as_bytes()) {
+                identity = Some(ApiKeyIdentity {
+                    key_id: key.id.clone(),
+                });
+            }
+        }
+        identity
+    }
+}
+
+impl<S> Layer<S> for ApiKeyAuthLayer {
+    type Service = ApiKeyAuth<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ApiKeyAuth {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+/// Service produced by [`ApiKeyAuthLayer`]
+#[derive(Clone)]
+pub struct ApiKeyAuth<S> {
+    inner: S,
+    layer: ApiKeyAuthLayer,
+}
+
+impl<S> Service<Request> for ApiKeyAuth<S>
+where
+    S: Service<Request, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request) -> Self::Future {
+        if !self.layer.is_enabled() || !request.uri().path().starts_with(PROTECTED_PREFIX) {
+            return Box::pin(self.inner.call(request));
+        }
+
+        let identity = request
+            .headers()
+            .get(API_KEY_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|key| self.layer.authenticate(key));
+
+        match identity {
+            Some(identity) => {
+                request.extensions_mut().insert(identity);
+                Box::pin(self.inner.call(request))
+            }
+            None => {
+                self.layer.failures.inc();
+                tracing::warn!(path = %request.uri().path(), "Rejected request without a valid API key");
+                Box::pin(async { Ok(unauthorized()) })
+            }
+        }
+    }
+}
+
+/// Documents the `X-API-Key` scheme and marks every `/api/*` operation as requiring it
+pub(crate) struct ApiKeySecurity;
+
+impl Modify for ApiKeySecurity {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        openapi
+            .components
+            .get_or_insert_with(Default::default)
+            .add_security_scheme(
+                SECURITY_SCHEME,
+                SecurityScheme::ApiKey(ApiKeyScheme::Header(ApiKeyValue::new("X-API-Key"))),
+            );
+
+        for (path, item) in openapi.paths.paths.iter_mut() {
+            if !path.starts_with(PROTECTED_PREFIX) {
+                continue;
+            }
+            let operations = [
+                &mut item.get,
+                &mut item.put,
+                &mut item.post,
+                &mut item.delete,
+                &mut item.patch,
+            ];
+            for operation in operations.into_iter().flatten() {
+                operation.security = Some(vec![SecurityRequirement::new(
+                    SECURITY_SCHEME,
+                    Vec::<String>::new(),
+                )]);
+            }
+        }
+    }
+}
+
+fn unauthorized() -> Response {
+    (
+        StatusCode::UNAUTHORIZED,
+        Json(ErrorResponse {
+            error: "unauthorized".to_string(),
+            message: "A valid X-API-Key header is required".to_string(),
+        }),
+    )
+        .into_response()
+}
+
+/// Short, non-secret label for keys configured without a name
+fn mask(key: &str) -> String {
+    let prefix: String = key.chars().take(4).collect();
+    format!("{}****", prefix)
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
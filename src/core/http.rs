@@ -1,29 +1,93 @@
 //! HTTP endpoint server using Axum
 
 use axum::{
-    extract::{Path, Query, Request, State},
-    http::StatusCode,
+    extract::{DefaultBodyLimit, Extension, Path, Query, Request, State},
+    http::{HeaderMap, StatusCode},
     middleware::Next,
-    response::{Json, Response},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Json, Response,
+    },
     routing::{delete, get, post, put},
     Router,
 };
+use futures_util::{stream, Stream, StreamExt};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast::{self, error::RecvError};
 use tokio::sync::RwLock;
 use tower::ServiceBuilder;
 use tower_http::{
     cors::CorsLayer,
+    limit::RequestBodyLimitLayer,
     trace::{DefaultMakeSpan, DefaultOnRequest, DefaultOnResponse, TraceLayer},
 };
-use tracing::{error, info, Level};
+use tracing::{error, info, warn, Level};
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
+use crate::backtesting::{BacktestReport, Backtester};
+use crate::cache::redis::{
+    latest_signal_key, signal_cluster_key, signal_stats_key, strategy_stats_key, subscribe_json,
+    SIGNALS_CHANNEL, WEBSOCKET_HEARTBEAT_KEY,
+};
+use crate::cache::RedisCache;
+use crate::config::get_health_check_timeout_ms;
+use crate::config::watcher::{ReloadError, ReloadStatus, StrategyWatcher};
+use crate::core::auth::{ApiKeyAuthLayer, ApiKeyIdentity, ApiKeySecurity};
+use crate::core::rate_limit::RateLimitLayer;
 use crate::db::QuestDatabase;
+use crate::error::KryptexError;
+use crate::jobs::replay::{self, JobQueues};
 use crate::metrics::Metrics;
-use crate::models::strategy::{Strategy, StrategyConfig};
+use crate::models::audit_log::AuditLogEntry;
+use crate::models::dead_letter::{DeadLetter, DeadLetterStats};
+use crate::models::signal::{SignalDirection, SignalFilter, SignalOutput, SignalStats};
+use crate::models::strategy::{
+    EvaluationTrace, IndicatorSnapshot, Strategy, StrategyConfig, StrategyStats,
+};
+use crate::positions::PositionTracker;
+use crate::services::hyperliquid::client::WebSocketClient;
+use crate::services::hyperliquid::HyperliquidMarketDataProvider;
+use crate::services::market_data::MarketDataProvider;
+use crate::services::websocket::WebSocketHeartbeat;
+use crate::signals::clustering::{
+    ClusterAnalysis, SignalCluster, DEFAULT_CLUSTER_SIGNAL_LIMIT, DEFAULT_CLUSTER_WINDOW_SECONDS,
+};
+use crate::strategies::evaluator::MIN_CANDLES;
+use crate::strategies::{
+    validate_strategy_config, LiveMarketData, StrategyEvaluator, StrategyTemplate, Templates,
+};
+
+/// How long strategy stats stay cached in Redis
+const STRATEGY_STATS_CACHE_TTL: u64 = 300;
+/// How long signal stats stay cached in Redis
+const SIGNAL_STATS_CACHE_TTL: u64 = 60;
+/// Signals per page returned by `/api/signals` when no page size is given
+const DEFAULT_SIGNAL_PAGE_SIZE: u32 = 50;
+/// Maximum page size accepted by `/api/signals`
+const MAX_SIGNAL_PAGE_SIZE: u32 = 500;
+/// Signals buffered for `/api/signals/stream` before a slow client starts missing them
+pub const SIGNAL_STREAM_CAPACITY: usize = 256;
+/// How often `/api/signals/stream` sends a keep-alive comment
+const SIGNAL_STREAM_KEEP_ALIVE: Duration = Duration::from_secs(30);
+/// Most stored signals replayed to a client reconnecting with `Last-Event-ID`
+const SIGNAL_STREAM_REPLAY_LIMIT: u32 = 500;
+/// Delay before resubscribing to published signals after the subscription fails
+const SIGNAL_RELAY_RETRY_DELAY: Duration = Duration::from_secs(5);
+/// Maximum number of dead letters returned per request
+const DEAD_LETTER_LIMIT: usize = 200;
+/// Maximum number of audit log entries returned per request
+const AUDIT_LOG_LIMIT: usize = 200;
+/// Most recent candles replayed through a strategy by `/api/strategies/{id}/trace`
+const TRACE_CANDLE_COUNT: usize = 250;
+/// Candle interval a trace is evaluated on
+const TRACE_INTERVAL: &str = "1m";
+/// Most recent candles evaluated by `/api/strategies/{id}/live-evaluate`
+const LIVE_EVALUATE_CANDLE_COUNT: usize = 250;
+/// Candle interval a live evaluation uses when none is given
+const DEFAULT_LIVE_EVALUATE_INTERVAL: &str = "1m";
 
 #[derive(Clone)]
 pub struct AppState {
@@ -31,47 +95,551 @@ pub struct AppState {
     pub metrics: Arc<Metrics>,
     pub start_time: Arc<Instant>,
     pub database: Option<Arc<QuestDatabase>>,
+    pub cache: Option<Arc<RedisCache>>,
+    /// Accepted `X-API-Key` values; empty disables authentication
+    pub api_keys: Arc<Vec<String>>,
+    /// Worker queues, used to replay dead letters
+    pub job_queues: Option<JobQueues>,
+    /// Strategy file hot-reload state, updated by [`StrategyWatcher`]
+    pub reload_status: Arc<RwLock<ReloadStatus>>,
+    /// New signals, streamed to `/api/signals/stream` clients
+    pub signal_stream: broadcast::Sender<SignalOutput>,
+    /// WebSocket connection checked by `/health`, when one runs in this process
+    pub websocket_client: Option<Arc<dyn WebSocketClient>>,
+    /// Check the WebSocket service's heartbeat in `cache` on `/health` when
+    /// no `websocket_client` runs in this process
+    pub websocket_heartbeat: bool,
+    /// Live spread and trade readings for live evaluations
+    pub market_data: Option<Arc<dyn MarketDataProvider + Send + Sync>>,
+}
+
+/// Builder for [`AppState`]. Everything except health and start time is optional.
+#[derive(Default)]
+pub struct AppStateBuilder {
+    metrics: Option<Arc<Metrics>>,
+    database: Option<Arc<QuestDatabase>>,
+    cache: Option<Arc<RedisCache>>,
+    api_keys: Vec<String>,
+    job_queues: Option<JobQueues>,
+    reload_status: Option<Arc<RwLock<ReloadStatus>>>,
+    signal_stream: Option<broadcast::Sender<SignalOutput>>,
+    websocket_client: Option<Arc<dyn WebSocketClient>>,
+    websocket_heartbeat: bool,
+    market_data: Option<Arc<dyn MarketDataProvider + Send + Sync>>,
+}
+
+impl AppStateBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_metrics(mut self, metrics: Arc<Metrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    pub fn with_database(mut self, database: Arc<QuestDatabase>) -> Self {
+        self.database = Some(database);
+        self
+    }
+
+    pub fn with_cache(mut self, cache: Arc<RedisCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Require one of these keys (`key` or `name:key`) on `/api/*` routes
+    pub fn with_api_keys(mut self, api_keys: Vec<String>) -> Self {
+        self.api_keys = api_keys;
+        self
+    }
+
+    pub fn with_job_queues(mut self, job_queues: JobQueues) -> Self {
+        self.job_queues = Some(job_queues);
+        self
+    }
+
+    /// Share hot-reload state with a running [`StrategyWatcher`]
+    pub fn with_reload_status(mut self, reload_status: Arc<RwLock<ReloadStatus>>) -> Self {
+        self.reload_status = Some(reload_status);
+        self
+    }
+
+    /// Stream signals sent on `signal_stream`, e.g. by a worker's
+    /// [`JobContext`](crate::jobs::context::JobContext) in the same process
+    pub fn with_signal_stream(mut self, signal_stream: broadcast::Sender<SignalOutput>) -> Self {
+        self.signal_stream = Some(signal_stream);
+        self
+    }
+
+    /// Report the health of `websocket_client` on `/health`
+    pub fn with_websocket_client(mut self, websocket_client: Arc<dyn WebSocketClient>) -> Self {
+        self.websocket_client = Some(websocket_client);
+        self
+    }
+
+    /// Report the connection of the WebSocket service running in another
+    /// process on `/health`, from the
+    /// [`WebSocketHeartbeat`](crate::services::websocket::WebSocketHeartbeat)
+    /// it publishes to the cache
+    pub fn with_websocket_heartbeat(mut self) -> Self {
+        self.websocket_heartbeat = true;
+        self
+    }
+
+    /// Read `Spread` and `BuySellRatio` inputs for live evaluations from
+    /// `market_data`; without it those conditions fail
+    pub fn with_market_data(
+        mut self,
+        market_data: Arc<dyn MarketDataProvider + Send + Sync>,
+    ) -> Self {
+        self.market_data = Some(market_data);
+        self
+    }
+
+    /// Build the state, creating fresh metrics if none were provided
+    pub fn build(self) -> AppState {
+        let health = HealthStatus {
+            database_status: if self.database.is_some() {
+                DATABASE_CONNECTED.to_string()
+            } else {
+                DATABASE_UNAVAILABLE.to_string()
+            },
+            ..HealthStatus::default()
+        };
+
+        AppState {
+            health: Arc::new(RwLock::new(health)),
+            metrics: self.metrics.unwrap_or_default(),
+            start_time: Arc::new(Instant::now()),
+            database: self.database,
+            cache: self.cache,
+            api_keys: Arc::new(self.api_keys),
+            job_queues: self.job_queues,
+            reload_status: self.reload_status.unwrap_or_default(),
+            signal_stream: self
+                .signal_stream
+                .unwrap_or_else(|| broadcast::channel(SIGNAL_STREAM_CAPACITY).0),
+            websocket_client: self.websocket_client,
+            websocket_heartbeat: self.websocket_heartbeat,
+            market_data: self.market_data,
+        }
+    }
+}
+
+impl AppState {
+    pub fn builder() -> AppStateBuilder {
+        AppStateBuilder::new()
+    }
+
+    /// The database handle, or a `database_unavailable` error for handlers that need it
+    fn require_database(&self) -> Result<&Arc<QuestDatabase>, ApiError> {
+        self.database.as_ref().ok_or(ApiError::DatabaseUnavailable)
+    }
+
+    /// The worker queues, or a `job_queue_unavailable` error for handlers that need them
+    fn require_job_queues(&self) -> Result<&JobQueues, ApiError> {
+        self.job_queues.as_ref().ok_or(ApiError::JobQueueUnavailable)
+    }
+
+    /// The Redis cache, or a `cache_unavailable` error for handlers that need it
+    fn require_cache(&self) -> Result<&Arc<RedisCache>, ApiError> {
+        self.cache.as_ref().ok_or(ApiError::CacheUnavailable)
+    }
+
+    /// Current spread and buy/sell ratio for `symbol`, empty without a
+    /// market data provider
+    async fn live_market_data(&self, symbol: &str) -> LiveMarketData {
+        match self.market_data {
+            Some(ref provider) => LiveMarketData {
+                top_of_book: provider.get_spread(symbol).await,
+                buy_sell_ratio: provider.get_buy_sell_ratio(symbol).await,
+            },
+            None => LiveMarketData::default(),
+        }
+    }
 }
 
+const DATABASE_CONNECTED: &str = "connected";
+const DATABASE_UNAVAILABLE: &str = "unavailable";
+
 #[derive(Clone, Debug, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct HealthStatus {
     pub status: String,
+    /// QuestDB connection state: "connected" or "unavailable"
+    pub database_status: String,
 }
 
 #[derive(Serialize, Deserialize, utoipa::ToSchema)]
 pub struct HealthResponse {
+    /// "healthy", "degraded" or "unhealthy"
     pub status: String,
     pub uptime_seconds: u64,
     pub service: String,
+    /// QuestDB connection state: "connected" or "unavailable"
+    pub database_status: String,
+    pub services: DetailedHealth,
 }
 
 impl Default for HealthStatus {
     fn default() -> Self {
         Self {
             status: "healthy".to_string(),
+            database_status: DATABASE_UNAVAILABLE.to_string(),
+        }
+    }
+}
+
+/// State of an external service
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub enum ServiceStatus {
+    Up,
+    /// Reachable but not fully working, e.g. some pooled connections broken
+    Degraded,
+    Down,
+    /// Not used by this process, so not checked
+    Disabled,
+}
+
+/// Result of checking one external service
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ServiceHealth {
+    pub status: ServiceStatus,
+    /// How long the check took; `None` when the service was not checked
+    pub latency_ms: Option<u64>,
+    pub last_error: Option<String>,
+}
+
+impl ServiceHealth {
+    pub fn up(latency: Duration) -> Self {
+        Self {
+            status: ServiceStatus::Up,
+            latency_ms: Some(latency.as_millis() as u64),
+            last_error: None,
+        }
+    }
+
+    pub fn degraded(latency: Duration, error: impl Into<String>) -> Self {
+        Self {
+            status: ServiceStatus::Degraded,
+            latency_ms: Some(latency.as_millis() as u64),
+            last_error: Some(error.into()),
+        }
+    }
+
+    pub fn down(latency: Duration, error: impl Into<String>) -> Self {
+        Self {
+            status: ServiceStatus::Down,
+            latency_ms: Some(latency.as_millis() as u64),
+            last_error: Some(error.into()),
+        }
+    }
+
+    pub fn disabled() -> Self {
+        Self {
+            status: ServiceStatus::Disabled,
+            latency_ms: None,
+            last_error: None,
+        }
+    }
+}
+
+/// Health of each external service
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct DetailedHealth {
+    /// QuestDB, without which most endpoints fail
+    pub database: ServiceHealth,
+    pub cache: ServiceHealth,
+    pub websocket: ServiceHealth,
+}
+
+impl DetailedHealth {
+    /// 503 when the database is down, 207 when any other service is down or
+    /// any service is degraded, 200 otherwise. Disabled services are ignored.
+    pub fn status_code(&self) -> StatusCode {
+        if self.database.status == ServiceStatus::Down {
+            return StatusCode::SERVICE_UNAVAILABLE;
+        }
+        let services = [&self.database, &self.cache, &self.websocket];
+        if services
+            .iter()
+            .any(|s| matches!(s.status, ServiceStatus::Degraded | ServiceStatus::Down))
+        {
+            return StatusCode::MULTI_STATUS;
+        }
+        StatusCode::OK
+    }
+
+    /// Overall status reported by `/health` for [`Self::status_code`]
+    pub fn status(&self) -> &'static str {
+        match self.status_code() {
+            StatusCode::OK => "healthy",
+            StatusCode::MULTI_STATUS => "degraded",
+            _ => "unhealthy",
+        }
+    }
+}
+
+impl HealthStatus {
+    /// Check every configured service concurrently, giving each
+    /// `HEALTH_CHECK_TIMEOUT_MS` to answer; services passed as `None` are
+    /// reported as disabled.
+    ///
+    /// The WebSocket is checked through `websocket_provider` when it runs in
+    /// this process, and otherwise through the heartbeat the WebSocket
+    /// service publishes to `websocket_heartbeat`.
+    pub async fn check_all(
+        database: Option<&QuestDatabase>,
+        cache: Option<&RedisCache>,
+        websocket_provider: Option<&dyn WebSocketClient>,
+        websocket_heartbeat: Option<&RedisCache>,
+    ) -> DetailedHealth {
+        let timeout = Duration::from_millis(get_health_check_timeout_ms());
+        let (database, cache, websocket) = tokio::join!(
+            Self::check_database(database, timeout),
+            Self::check_cache(cache, timeout),
+            Self::check_websocket(websocket_provider, websocket_heartbeat, timeout),
+        );
+        DetailedHealth {
+            database,
+            cache,
+            websocket,
+        }
+    }
+
+    async fn check_database(database: Option<&QuestDatabase>, timeout: Duration) -> ServiceHealth {
+        let Some(database) = database else {
+            return ServiceHealth::disabled();
+        };
+        let start = Instant::now();
+        match tokio::time::timeout(timeout, database.is_healthy()).await {
+            Ok(true) => ServiceHealth::up(start.elapsed()),
+            Ok(false) if database.pool().is_available() => ServiceHealth::degraded(
+                start.elapsed(),
+                "Some pooled QuestDB connections are broken",
+            ),
+            Ok(false) => ServiceHealth::down(start.elapsed(), "QuestDB is not reachable"),
+            Err(_) => ServiceHealth::down(start.elapsed(), timed_out(timeout)),
+        }
+    }
+
+    async fn check_cache(cache: Option<&RedisCache>, timeout: Duration) -> ServiceHealth {
+        let Some(cache) = cache else {
+            return ServiceHealth::disabled();
+        };
+        let start = Instant::now();
+        match tokio::time::timeout(timeout, cache.ping()).await {
+            Ok(Ok(())) => ServiceHealth::up(start.elapsed()),
+            Ok(Err(e)) => ServiceHealth::down(start.elapsed(), e.to_string()),
+            Err(_) => ServiceHealth::down(start.elapsed(), timed_out(timeout)),
+        }
+    }
+
+    async fn check_websocket(
+        websocket_provider: Option<&dyn WebSocketClient>,
+        websocket_heartbeat: Option<&RedisCache>,
+        timeout: Duration,
+    ) -> ServiceHealth {
+        let Some(websocket) = websocket_provider else {
+            return match websocket_heartbeat {
+                Some(cache) => Self::check_websocket_heartbeat(cache, timeout).await,
+                None => ServiceHealth::disabled(),
+            };
+        };
+        let start = Instant::now();
+        match tokio::time::timeout(timeout, websocket.is_connected()).await {
+            Ok(true) => ServiceHealth::up(start.elapsed()),
+            Ok(false) => ServiceHealth::down(start.elapsed(), "WebSocket is not connected"),
+            Err(_) => ServiceHealth::down(start.elapsed(), timed_out(timeout)),
+        }
+    }
+
+    async fn check_websocket_heartbeat(cache: &RedisCache, timeout: Duration) -> ServiceHealth {
+        let start = Instant::now();
+        let heartbeat = cache.get_json::<WebSocketHeartbeat>(WEBSOCKET_HEARTBEAT_KEY);
+        match tokio::time::timeout(timeout, heartbeat).await {
+            Ok(Ok(Some(heartbeat))) if heartbeat.connected => ServiceHealth::up(start.elapsed()),
+            Ok(Ok(Some(_))) => ServiceHealth::down(start.elapsed(), "WebSocket is not connected"),
+            Ok(Ok(None)) => ServiceHealth::down(
+                start.elapsed(),
+                "No heartbeat from the WebSocket service",
+            ),
+            Ok(Err(e)) => ServiceHealth::down(start.elapsed(), e.to_string()),
+            Err(_) => ServiceHealth::down(start.elapsed(), timed_out(timeout)),
+        }
+    }
+}
+
+fn timed_out(timeout: Duration) -> String {
+    format!("No answer within {} ms", timeout.as_millis())
+}
+
+/// JSON error body returned by API endpoints
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ErrorResponse {
+    /// Machine-readable error code
+    pub error: String,
+    /// Human-readable description
+    pub message: String,
+}
+
+/// JSON error body returned when a request body exceeds the size limit
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct PayloadTooLargeResponse {
+    /// Always "request_too_large"
+    pub error: String,
+    /// Maximum accepted body size in bytes
+    pub max_bytes: usize,
+}
+
+/// JSON error body returned when too few candles are stored to evaluate a strategy
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct InsufficientCandlesResponse {
+    /// Always "insufficient_candles"
+    pub error: String,
+    pub message: String,
+    /// Candles needed for an evaluation
+    pub required: usize,
+    /// Candles currently stored
+    pub available: usize,
+    /// `required - available`
+    pub deficit: usize,
+}
+
+/// Errors returned by API handlers
+#[derive(Debug)]
+pub enum ApiError {
+    /// The handler needs QuestDB but it is not connected
+    DatabaseUnavailable,
+    /// The handler needs the Redis job queues but they are not connected
+    JobQueueUnavailable,
+    /// The handler needs the Redis cache but it is not connected
+    CacheUnavailable,
+    /// The submitted strategy config failed validation
+    InvalidStrategy(String),
+    /// The request parameters are inconsistent
+    InvalidRequest(String),
+    /// Fewer than `required` candles are stored for `symbol` on `interval`
+    InsufficientCandles {
+        symbol: String,
+        interval: String,
+        required: usize,
+        available: usize,
+    },
+    /// Any other failure, returned as a bare status code
+    Status(StatusCode),
+}
+
+impl From<StatusCode> for ApiError {
+    fn from(status: StatusCode) -> Self {
+        ApiError::Status(status)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        match self {
+            ApiError::DatabaseUnavailable => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(ErrorResponse {
+                    error: "database_unavailable".to_string(),
+                    message: "QuestDB is not connected".to_string(),
+                }),
+            )
+                .into_response(),
+            ApiError::JobQueueUnavailable => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(ErrorResponse {
+                    error: "job_queue_unavailable".to_string(),
+                    message: "Redis job queues are not connected".to_string(),
+                }),
+            )
+                .into_response(),
+            ApiError::CacheUnavailable => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(ErrorResponse {
+                    error: "cache_unavailable".to_string(),
+                    message: "Redis is not connected".to_string(),
+                }),
+            )
+                .into_response(),
+            ApiError::InvalidStrategy(message) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(ErrorResponse {
+                    error: "invalid_strategy".to_string(),
+                    message,
+                }),
+            )
+                .into_response(),
+            ApiError::InvalidRequest(message) => (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    error: "invalid_request".to_string(),
+                    message,
+                }),
+            )
+                .into_response(),
+            ApiError::InsufficientCandles {
+                symbol,
+                interval,
+                required,
+                available,
+            } => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(InsufficientCandlesResponse {
+                    error: "insufficient_candles".to_string(),
+                    message: format!(
+                        "{} of {} {} candles stored for {}",
+                        available, required, interval, symbol
+                    ),
+                    required,
+                    available,
+                    deficit: required.saturating_sub(available),
+                }),
+            )
+                .into_response(),
+            ApiError::Status(status) => status.into_response(),
         }
     }
 }
 
 /// Health check endpoint
 ///
-/// Returns the health status and uptime of the service
+/// Returns the uptime of the service and the health of QuestDB, Redis and the
+/// WebSocket connection, checked concurrently
 #[utoipa::path(
     get,
     path = "/health",
     tag = "Health",
     responses(
-        (status = 200, description = "Service is healthy", body = HealthResponse)
+        (status = 200, description = "Every configured service is up", body = HealthResponse),
+        (status = 207, description = "A service is degraded, or a non-critical one is down", body = HealthResponse),
+        (status = 503, description = "The database is down", body = HealthResponse)
     )
 )]
-pub async fn health_check(State(state): State<AppState>) -> Result<Json<HealthResponse>, StatusCode> {
+pub async fn health_check(State(state): State<AppState>) -> (StatusCode, Json<HealthResponse>) {
+    let services = HealthStatus::check_all(
+        state.database.as_deref(),
+        state.cache.as_deref(),
+        state.websocket_client.as_deref(),
+        state
+            .cache
+            .as_deref()
+            .filter(|_| state.websocket_heartbeat),
+    )
+    .await;
     let health = state.health.read().await;
     let uptime_seconds = state.start_time.elapsed().as_secs();
-    Ok(Json(HealthResponse {
-        status: health.status.clone(),
-        uptime_seconds,
-        service: "perptrix-signal-engine".to_string(),
-    }))
+    (
+        services.status_code(),
+        Json(HealthResponse {
+            status: services.status().to_string(),
+            uptime_seconds,
+            service: "perptrix-signal-engine".to_string(),
+            database_status: health.database_status.clone(),
+            services,
+        }),
+    )
 }
 
 /// Prometheus metrics endpoint
@@ -106,8 +674,27 @@ struct CreateStrategyRequest {
     name: String,
     /// Trading symbol (e.g., "BTC-USD")
     symbol: String,
-    /// Strategy configuration
-    config: StrategyConfig,
+    /// Strategy configuration; required unless `from_template` is set
+    #[serde(default)]
+    config: Option<StrategyConfig>,
+    /// Name of a template from `/api/strategy-templates` to take the config
+    /// from when `config` is omitted
+    #[serde(default)]
+    from_template: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+struct StrategyValidationResponse {
+    /// Whether the config would be accepted
+    valid: bool,
+    /// Every problem found; empty when valid
+    errors: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+struct CreateDefaultStrategyRequest {
+    /// Trading symbol (e.g., "BTC")
+    symbol: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
@@ -130,6 +717,8 @@ struct StrategyResponse {
     symbol: String,
     /// Strategy configuration
     config: StrategyConfig,
+    /// Whether the worker evaluates the strategy
+    enabled: bool,
     /// Creation timestamp
     created_at: chrono::DateTime<chrono::Utc>,
     /// Last update timestamp
@@ -143,6 +732,7 @@ impl From<Strategy> for StrategyResponse {
             name: strategy.name,
             symbol: strategy.symbol,
             config: strategy.config,
+            enabled: strategy.enabled,
             created_at: strategy.created_at,
             updated_at: strategy.updated_at,
         }
@@ -157,17 +747,14 @@ impl From<Strategy> for StrategyResponse {
     params(StrategyQuery),
     responses(
         (status = 200, description = "List of strategies", body = Vec<StrategyResponse>),
-        (status = 503, description = "Database unavailable")
+        (status = 503, description = "Database unavailable", body = ErrorResponse)
     )
 )]
 async fn list_strategies(
     State(state): State<AppState>,
     Query(params): Query<StrategyQuery>,
-) -> Result<Json<Vec<StrategyResponse>>, StatusCode> {
-    let db = state
-        .database
-        .as_ref()
-        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+) -> Result<Json<Vec<StrategyResponse>>, ApiError> {
+    let db = state.require_database()?;
 
     let strategies = db
         .get_strategies(params.symbol.as_deref())
@@ -192,21 +779,18 @@ async fn list_strategies(
     responses(
         (status = 200, description = "Strategy found", body = StrategyResponse),
         (status = 404, description = "Strategy not found"),
-        (status = 503, description = "Database unavailable")
+        (status = 503, description = "Database unavailable", body = ErrorResponse)
     )
 )]
 async fn get_strategy(
     State(state): State<AppState>,
     Path(id): Path<i64>,
-) -> Result<Json<StrategyResponse>, StatusCode> {
-    let db = state
-        .database
-        .as_ref()
-        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+) -> Result<Json<StrategyResponse>, ApiError> {
+    let db = state.require_database()?;
 
     let strategy = db.get_strategy(id).await.map_err(|e| {
         error!(error = %e, strategy_id = id, "Failed to load strategy");
-        if e.to_string().contains("not found") {
+        if matches!(e, KryptexError::NotFound(_)) {
             StatusCode::NOT_FOUND
         } else {
             StatusCode::INTERNAL_SERVER_ERROR
@@ -224,29 +808,151 @@ async fn get_strategy(
     request_body = CreateStrategyRequest,
     responses(
         (status = 200, description = "Strategy created", body = StrategyResponse),
-        (status = 503, description = "Database unavailable")
+        (status = 400, description = "Missing config or unknown template", body = ErrorResponse),
+        (status = 413, description = "Request body too large", body = PayloadTooLargeResponse),
+        (status = 422, description = "Invalid strategy config", body = ErrorResponse),
+        (status = 503, description = "Database unavailable", body = ErrorResponse)
     )
 )]
 async fn create_strategy(
     State(state): State<AppState>,
+    identity: Option<Extension<ApiKeyIdentity>>,
     Json(request): Json<CreateStrategyRequest>,
-) -> Result<Json<StrategyResponse>, StatusCode> {
-    let db = state
-        .database
-        .as_ref()
-        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+) -> Result<Json<StrategyResponse>, ApiError> {
+    let config = request_config(request.config.clone(), request.from_template.clone())?;
+    check_strategy_config(&config)?;
+    let db = state.require_database()?;
 
     let now = chrono::Utc::now();
     let strategy = Strategy {
         id: None,
-        name: request.name,
-        symbol: request.symbol,
-        config: request.config,
+        name: request.name.clone(),
+        symbol: request.symbol.clone(),
+        config,
+        enabled: true,
+        source_file: None,
         created_at: now,
         updated_at: now,
     };
 
-    let id = db.create_strategy(&strategy).await.map_err(|e| {
+    let created = store_strategy(db, &strategy).await?;
+    let entry = AuditLogEntry::new("create", created.id, &created.name)
+        .with_operator_key(operator_key(identity))
+        .with_request(&request);
+    record_audit_log(db, entry).await;
+
+    Ok(created)
+}
+
+/// Validate a strategy without saving it
+///
+/// Runs the same checks as creating a strategy and lists every problem found
+#[utoipa::path(
+    post,
+    path = "/api/strategies/validate",
+    tag = "Strategies",
+    request_body = CreateStrategyRequest,
+    responses(
+        (status = 200, description = "Validation result", body = StrategyValidationResponse),
+        (status = 400, description = "Missing config or unknown template", body = ErrorResponse),
+        (status = 413, description = "Request body too large", body = PayloadTooLargeResponse)
+    )
+)]
+async fn validate_strategy(
+    Json(request): Json<CreateStrategyRequest>,
+) -> Result<Json<StrategyValidationResponse>, ApiError> {
+    let config = request_config(request.config, request.from_template)?;
+    let errors: Vec<String> = validate_strategy_config(&config)
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+
+    Ok(Json(StrategyValidationResponse {
+        valid: errors.is_empty(),
+        errors,
+    }))
+}
+
+/// The submitted config, or the named template's when no config is given
+fn request_config(
+    config: Option<StrategyConfig>,
+    from_template: Option<String>,
+) -> Result<StrategyConfig, ApiError> {
+    match (config, from_template) {
+        (Some(config), _) => Ok(config),
+        (None, Some(template)) => Templates::get(&template).ok_or_else(|| {
+            ApiError::InvalidRequest(format!("Unknown strategy template '{}'", template))
+        }),
+        (None, None) => Err(ApiError::InvalidRequest(
+            "Either `config` or `from_template` is required".to_string(),
+        )),
+    }
+}
+
+/// Reject `config` with every validation error joined into one message
+fn check_strategy_config(config: &StrategyConfig) -> Result<(), ApiError> {
+    let errors = validate_strategy_config(config);
+    if errors.is_empty() {
+        return Ok(());
+    }
+    let reasons: Vec<String> = errors.iter().map(ToString::to_string).collect();
+    Err(ApiError::InvalidStrategy(reasons.join("; ")))
+}
+
+/// List strategy templates
+///
+/// Returns the names and descriptions of the pre-built configs that can be
+/// passed as `from_template` when creating a strategy
+#[utoipa::path(
+    get,
+    path = "/api/strategy-templates",
+    tag = "Strategies",
+    responses(
+        (status = 200, description = "Available templates", body = Vec<StrategyTemplate>)
+    )
+)]
+async fn list_strategy_templates() -> Json<Vec<StrategyTemplate>> {
+    Json(Templates::list())
+}
+
+/// Create the default RSI+MACD starter strategy for a symbol
+#[utoipa::path(
+    post,
+    path = "/api/strategies/default",
+    tag = "Strategies",
+    request_body = CreateDefaultStrategyRequest,
+    responses(
+        (status = 200, description = "Default strategy created", body = StrategyResponse),
+        (status = 503, description = "Database unavailable", body = ErrorResponse)
+    )
+)]
+async fn create_default_strategy(
+    State(state): State<AppState>,
+    identity: Option<Extension<ApiKeyIdentity>>,
+    Json(request): Json<CreateDefaultStrategyRequest>,
+) -> Result<Json<StrategyResponse>, ApiError> {
+    let db = state.require_database()?;
+
+    let strategy = Strategy {
+        symbol: request.symbol.clone(),
+        ..Strategy::default()
+    };
+
+    let created = store_strategy(db, &strategy).await?;
+    let entry = AuditLogEntry::new("create", created.id, &created.name)
+        .with_operator_key(operator_key(identity))
+        .with_request(&request);
+    record_audit_log(db, entry).await;
+
+    Ok(created)
+}
+
+/// Persist a new strategy and return it as stored
+async fn store_strategy(
+    db: &QuestDatabase,
+    strategy: &Strategy,
+) -> Result<Json<StrategyResponse>, ApiError> {
+    let id = db.create_strategy(strategy).await.map_err(|e| {
         error!(error = %e, "Failed to create strategy");
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
@@ -259,6 +965,25 @@ async fn create_strategy(
     Ok(Json(created_strategy.into()))
 }
 
+/// Name of the API key that authenticated the request, if authentication
+/// is enabled
+fn operator_key(identity: Option<Extension<ApiKeyIdentity>>) -> Option<String> {
+    identity.map(|Extension(identity)| identity.key_id)
+}
+
+/// Append a strategy change to the audit log. The change has already been
+/// applied, so a failed write is logged rather than failing the request.
+async fn record_audit_log(db: &QuestDatabase, entry: AuditLogEntry) {
+    if let Err(e) = db.insert_audit_log(&entry).await {
+        error!(
+            error = %e,
+            strategy_id = entry.strategy_id,
+            operation = %entry.operation,
+            "Failed to record audit log"
+        );
+    }
+}
+
 /// Update a strategy
 #[utoipa::path(
     put,
@@ -270,23 +995,26 @@ async fn create_strategy(
     request_body = UpdateStrategyRequest,
     responses(
         (status = 200, description = "Strategy updated", body = StrategyResponse),
+        (status = 413, description = "Request body too large", body = PayloadTooLargeResponse),
+        (status = 422, description = "Invalid strategy config", body = ErrorResponse),
         (status = 404, description = "Strategy not found"),
-        (status = 503, description = "Database unavailable")
+        (status = 503, description = "Database unavailable", body = ErrorResponse)
     )
 )]
 async fn update_strategy(
     State(state): State<AppState>,
     Path(id): Path<i64>,
+    identity: Option<Extension<ApiKeyIdentity>>,
     Json(request): Json<UpdateStrategyRequest>,
-) -> Result<Json<StrategyResponse>, StatusCode> {
-    let db = state
-        .database
-        .as_ref()
-        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+) -> Result<Json<StrategyResponse>, ApiError> {
+    if let Some(ref config) = request.config {
+        check_strategy_config(config)?;
+    }
+    let db = state.require_database()?;
 
     let mut strategy = db.get_strategy(id).await.map_err(|e| {
         error!(error = %e, strategy_id = id, "Failed to load strategy");
-        if e.to_string().contains("not found") {
+        if matches!(e, KryptexError::NotFound(_)) {
             StatusCode::NOT_FOUND
         } else {
             StatusCode::INTERNAL_SERVER_ERROR
@@ -294,14 +1022,14 @@ async fn update_strategy(
     })?;
 
     // Update fields if provided
-    if let Some(name) = request.name {
-        strategy.name = name;
+    if let Some(ref name) = request.name {
+        strategy.name = name.clone();
     }
-    if let Some(symbol) = request.symbol {
-        strategy.symbol = symbol;
+    if let Some(ref symbol) = request.symbol {
+        strategy.symbol = symbol.clone();
     }
-    if let Some(config) = request.config {
-        strategy.config = config;
+    if let Some(ref config) = request.config {
+        strategy.config = config.clone();
     }
     strategy.updated_at = chrono::Utc::now();
 
@@ -310,6 +1038,12 @@ async fn update_strategy(
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
 
+    // The request only carries the fields that changed
+    let entry = AuditLogEntry::new("update", id, &strategy.name)
+        .with_operator_key(operator_key(identity))
+        .with_request(&request);
+    record_audit_log(db, entry).await;
+
     Ok(Json(strategy.into()))
 }
 
@@ -324,93 +1058,1335 @@ async fn update_strategy(
     responses(
         (status = 204, description = "Strategy deleted"),
         (status = 404, description = "Strategy not found"),
-        (status = 503, description = "Database unavailable")
+        (status = 503, description = "Database unavailable", body = ErrorResponse)
     )
 )]
 async fn delete_strategy(
     State(state): State<AppState>,
     Path(id): Path<i64>,
-) -> Result<StatusCode, StatusCode> {
-    let db = state
-        .database
-        .as_ref()
-        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    identity: Option<Extension<ApiKeyIdentity>>,
+) -> Result<StatusCode, ApiError> {
+    let db = state.require_database()?;
+
+    // Loaded first so the audit log can record the deleted strategy's name
+    let strategy = db.get_strategy(id).await.map_err(|e| {
+        error!(error = %e, strategy_id = id, "Failed to load strategy");
+        if matches!(e, KryptexError::NotFound(_)) {
+            StatusCode::NOT_FOUND
+        } else {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    })?;
 
     db.delete_strategy(id).await.map_err(|e| {
         error!(error = %e, strategy_id = id, "Failed to delete strategy");
-        if e.to_string().contains("not found") {
+        if matches!(e, KryptexError::NotFound(_)) {
             StatusCode::NOT_FOUND
         } else {
             StatusCode::INTERNAL_SERVER_ERROR
         }
     })?;
 
+    let entry =
+        AuditLogEntry::new("delete", id, &strategy.name).with_operator_key(operator_key(identity));
+    record_audit_log(db, entry).await;
+
     Ok(StatusCode::NO_CONTENT)
 }
 
-#[derive(OpenApi)]
-#[openapi(
-    paths(
-        health_check,
-        metrics_handler,
-        list_strategies,
-        get_strategy,
-        create_strategy,
-        update_strategy,
-        delete_strategy
-    ),
-    components(schemas(
-        HealthResponse,
-        StrategyResponse,
-        CreateStrategyRequest,
-        UpdateStrategyRequest,
-        StrategyConfig,
-        StrategyQuery,
-        crate::models::strategy::Rule,
-        crate::models::strategy::RuleType,
-        crate::models::strategy::Condition,
-        crate::models::strategy::IndicatorType,
-        crate::models::strategy::Comparison,
-        crate::models::strategy::LogicalOperator,
-        crate::models::strategy::AggregationConfig,
-        crate::models::strategy::AggregationMethod,
-        crate::models::strategy::SignalThresholds
-    )),
-    tags(
-        (name = "Health", description = "Health check endpoints"),
-        (name = "Metrics", description = "Metrics endpoints"),
-        (name = "Strategies", description = "Strategy management endpoints")
+/// Enable a strategy
+///
+/// The worker resumes evaluating it once its strategy cache is refreshed
+#[utoipa::path(
+    put,
+    path = "/api/strategies/{id}/enable",
+    tag = "Strategies",
+    params(
+        ("id" = i64, Path, description = "Strategy ID")
     ),
-    info(
-        title = "Perptrix API",
-        description = "API for the Perptrix signal engine - a trading strategy evaluation system",
-        version = "0.1.0"
+    responses(
+        (status = 200, description = "Strategy enabled", body = StrategyResponse),
+        (status = 404, description = "Strategy not found"),
+        (status = 503, description = "Database unavailable", body = ErrorResponse)
     )
 )]
-struct ApiDoc;
-
-/// Middleware to track HTTP request metrics
-async fn metrics_middleware(
+async fn enable_strategy(
     State(state): State<AppState>,
-    request: Request,
-    next: Next,
-) -> Response {
-    // Skip metrics tracking for the /metrics endpoint to avoid potential recursion issues
-    let path = request.uri().path();
-    if path == "/metrics" {
-        return next.run(request).await;
-    }
-
-    let start = Instant::now();
-    let method = request.method().clone();
-    let path = path.to_string();
-
-    // Increment in-flight requests
-    state.metrics.http_requests_in_flight.inc();
+    Path(id): Path<i64>,
+    identity: Option<Extension<ApiKeyIdentity>>,
+) -> Result<Json<StrategyResponse>, ApiError> {
+    set_strategy_enabled(&state, id, true, operator_key(identity)).await
+}
 
-    // Process request
-    let response = next.run(request).await;
-    let status = response.status();
+/// Disable a strategy
+///
+/// The strategy is kept but no longer evaluated, and symbols whose strategies
+/// are all disabled are no longer scheduled
+#[utoipa::path(
+    put,
+    path = "/api/strategies/{id}/disable",
+    tag = "Strategies",
+    params(
+        ("id" = i64, Path, description = "Strategy ID")
+    ),
+    responses(
+        (status = 200, description = "Strategy disabled", body = StrategyResponse),
+        (status = 404, description = "Strategy not found"),
+        (status = 503, description = "Database unavailable", body = ErrorResponse)
+    )
+)]
+async fn disable_strategy(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+    identity: Option<Extension<ApiKeyIdentity>>,
+) -> Result<Json<StrategyResponse>, ApiError> {
+    set_strategy_enabled(&state, id, false, operator_key(identity)).await
+}
+
+async fn set_strategy_enabled(
+    state: &AppState,
+    id: i64,
+    enabled: bool,
+    operator_key: Option<String>,
+) -> Result<Json<StrategyResponse>, ApiError> {
+    let db = state.require_database()?;
+
+    let mut strategy = db.get_strategy(id).await.map_err(|e| {
+        error!(error = %e, strategy_id = id, "Failed to load strategy");
+        if matches!(e, KryptexError::NotFound(_)) {
+            StatusCode::NOT_FOUND
+        } else {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    })?;
+
+    if strategy.enabled != enabled {
+        strategy.enabled = enabled;
+        strategy.updated_at = chrono::Utc::now();
+        db.update_strategy(id, &strategy).await.map_err(|e| {
+            error!(error = %e, strategy_id = id, "Failed to update strategy");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+        let operation = if enabled { "enable" } else { "disable" };
+        let entry =
+            AuditLogEntry::new(operation, id, &strategy.name).with_operator_key(operator_key);
+        record_audit_log(db, entry).await;
+    }
+
+    Ok(Json(strategy.into()))
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, utoipa::ToSchema)]
+struct CloneStrategyRequest {
+    /// Name for the clone (optional, defaults to "{original name} (copy)")
+    name: Option<String>,
+    /// Trading symbol for the clone (optional, defaults to the original's)
+    symbol: Option<String>,
+}
+
+/// Clone a strategy
+///
+/// Stores a copy of the strategy under a new ID, optionally with a different
+/// name or symbol. The body may be omitted.
+#[utoipa::path(
+    post,
+    path = "/api/strategies/{id}/clone",
+    tag = "Strategies",
+    params(
+        ("id" = i64, Path, description = "ID of the strategy to clone")
+    ),
+    request_body(content = Option<CloneStrategyRequest>),
+    responses(
+        (status = 200, description = "Strategy cloned", body = StrategyResponse),
+        (status = 404, description = "Strategy not found"),
+        (status = 503, description = "Database unavailable", body = ErrorResponse)
+    )
+)]
+async fn clone_strategy(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+    identity: Option<Extension<ApiKeyIdentity>>,
+    request: Option<Json<CloneStrategyRequest>>,
+) -> Result<Json<StrategyResponse>, ApiError> {
+    let request = request.map(|Json(request)| request).unwrap_or_default();
+    let db = state.require_database()?;
+
+    let original = db.get_strategy(id).await.map_err(|e| {
+        error!(error = %e, strategy_id = id, "Failed to load strategy");
+        if matches!(e, KryptexError::NotFound(_)) {
+            StatusCode::NOT_FOUND
+        } else {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    })?;
+
+    let now = chrono::Utc::now();
+    let clone = Strategy {
+        id: None,
+        name: request
+            .name
+            .clone()
+            .unwrap_or_else(|| format!("{} (copy)", original.name)),
+        symbol: request.symbol.clone().unwrap_or(original.symbol),
+        config: original.config,
+        enabled: original.enabled,
+        // The copy is API-managed even when the original came from a file
+        source_file: None,
+        created_at: now,
+        updated_at: now,
+    };
+
+    let created = store_strategy(db, &clone).await?;
+    let entry = AuditLogEntry::new("create", created.id, &created.name)
+        .with_operator_key(operator_key(identity))
+        .with_request(&request);
+    record_audit_log(db, entry).await;
+
+    Ok(created)
+}
+
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+struct BacktestRequest {
+    /// Symbol to replay candles for (e.g., "BTC")
+    symbol: String,
+    /// Candle interval (e.g., "1m")
+    interval: String,
+    /// Start of the range, inclusive (ISO 8601)
+    from: chrono::DateTime<chrono::Utc>,
+    /// End of the range, exclusive (ISO 8601)
+    to: chrono::DateTime<chrono::Utc>,
+}
+
+/// Backtest a strategy against stored candles
+///
+/// Replays candles in `[from, to)` through the strategy and simulates each
+/// signal as a position held until its recommended SL or TP is hit.
+#[utoipa::path(
+    post,
+    path = "/api/strategies/{id}/backtest",
+    tag = "Strategies",
+    params(
+        ("id" = i64, Path, description = "Strategy ID")
+    ),
+    request_body = BacktestRequest,
+    responses(
+        (status = 200, description = "Backtest report", body = BacktestReport),
+        (status = 400, description = "Invalid time range", body = ErrorResponse),
+        (status = 404, description = "Strategy not found"),
+        (status = 503, description = "Database unavailable", body = ErrorResponse)
+    )
+)]
+async fn backtest_strategy(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+    Json(request): Json<BacktestRequest>,
+) -> Result<Json<BacktestReport>, ApiError> {
+    if request.from >= request.to {
+        return Err(ApiError::InvalidRequest("`from` must be before `to`".to_string()));
+    }
+    let db = state.require_database()?;
+
+    let strategy = db.get_strategy(id).await.map_err(|e| {
+        error!(error = %e, strategy_id = id, "Failed to load strategy");
+        if matches!(e, KryptexError::NotFound(_)) {
+            StatusCode::NOT_FOUND
+        } else {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    })?;
+
+    let report = Backtester::new(strategy)
+        .run_range(db, &request.symbol, &request.interval, request.from, request.to)
+        .await
+        .map_err(|e| {
+            error!(error = %e, strategy_id = id, "Failed to backtest strategy");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(report))
+}
+
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+struct TraceRequest {
+    /// Symbol whose stored candles are evaluated (e.g., "BTC")
+    symbol: String,
+}
+
+/// Trace a strategy evaluation without storing a signal
+///
+/// Evaluates the strategy against the last 250 stored 1m candles for the
+/// symbol and returns every top-level rule with its indicator value, whether
+/// it passed and what it added to the aggregated score.
+#[utoipa::path(
+    post,
+    path = "/api/strategies/{id}/trace",
+    tag = "Strategies",
+    params(
+        ("id" = i64, Path, description = "Strategy ID")
+    ),
+    request_body = TraceRequest,
+    responses(
+        (status = 200, description = "Rule-by-rule evaluation trace", body = EvaluationTrace),
+        (status = 400, description = "Not enough candles to evaluate", body = ErrorResponse),
+        (status = 404, description = "Strategy not found"),
+        (status = 503, description = "Database unavailable", body = ErrorResponse)
+    )
+)]
+async fn trace_strategy(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+    Json(request): Json<TraceRequest>,
+) -> Result<Json<EvaluationTrace>, ApiError> {
+    let db = state.require_database()?;
+
+    let strategy = db.get_strategy(id).await.map_err(|e| {
+        error!(error = %e, strategy_id = id, "Failed to load strategy");
+        if matches!(e, KryptexError::NotFound(_)) {
+            StatusCode::NOT_FOUND
+        } else {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    })?;
+
+    let candles = db
+        .get_candles(&request.symbol, TRACE_INTERVAL, Some(TRACE_CANDLE_COUNT))
+        .await
+        .map_err(|e| {
+            error!(error = %e, symbol = %request.symbol, "Failed to load candles for trace");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let (_, trace) = StrategyEvaluator::evaluate_with_trace(
+        &strategy,
+        &candles,
+        &LiveMarketData::default(),
+        chrono::Utc::now(),
+    )
+    .ok_or_else(|| {
+        ApiError::InvalidRequest(format!(
+            "Not enough usable {} candles for {} ({} stored)",
+            TRACE_INTERVAL,
+            request.symbol,
+            candles.len()
+        ))
+    })?;
+
+    Ok(Json(trace))
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams, utoipa::ToSchema)]
+#[into_params(parameter_in = Query)]
+struct LiveEvaluateQuery {
+    /// Candle interval to evaluate on (defaults to "1m")
+    interval: Option<String>,
+}
+
+/// Result of evaluating a strategy against the latest stored candles
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+struct LiveEvaluation {
+    /// Signal the strategy produces right now; not stored
+    signal: SignalOutput,
+    /// Indicator readings on the last candle
+    indicators: IndicatorSnapshot,
+    interval: String,
+    /// Number of candles evaluated
+    candles: usize,
+}
+
+/// Evaluate a strategy against the latest market data without storing a signal
+///
+/// Evaluates the strategy against the last 250 stored candles for its symbol
+/// on the given interval, with the current spread and buy/sell ratio, and
+/// returns the signal and indicator readings. Unlike a backtest this is a
+/// single point-in-time evaluation.
+#[utoipa::path(
+    post,
+    path = "/api/strategies/{id}/live-evaluate",
+    tag = "Strategies",
+    params(
+        ("id" = i64, Path, description = "Strategy ID"),
+        LiveEvaluateQuery
+    ),
+    responses(
+        (status = 200, description = "Current signal and indicator readings", body = LiveEvaluation),
+        (status = 400, description = "Candles too broken to evaluate or strategy without rules", body = ErrorResponse),
+        (status = 404, description = "Strategy not found"),
+        (status = 422, description = "Not enough candles stored", body = InsufficientCandlesResponse),
+        (status = 503, description = "Database unavailable", body = ErrorResponse)
+    )
+)]
+async fn live_evaluate_strategy(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+    Query(params): Query<LiveEvaluateQuery>,
+) -> Result<Json<LiveEvaluation>, ApiError> {
+    let interval = params
+        .interval
+        .unwrap_or_else(|| DEFAULT_LIVE_EVALUATE_INTERVAL.to_string());
+    let db = state.require_database()?;
+
+    let strategy = db.get_strategy(id).await.map_err(|e| {
+        error!(error = %e, strategy_id = id, "Failed to load strategy");
+        if matches!(e, KryptexError::NotFound(_)) {
+            StatusCode::NOT_FOUND
+        } else {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    })?;
+
+    let candles = db
+        .get_candles(&strategy.symbol, &interval, Some(LIVE_EVALUATE_CANDLE_COUNT))
+        .await
+        .map_err(|e| {
+            error!(error = %e, symbol = %strategy.symbol, "Failed to load candles for live evaluation");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    if candles.len() < MIN_CANDLES {
+        return Err(ApiError::InsufficientCandles {
+            symbol: strategy.symbol,
+            interval,
+            required: MIN_CANDLES,
+            available: candles.len(),
+        });
+    }
+
+    let market_data = state.live_market_data(&strategy.symbol).await;
+    let (signal, indicators) = StrategyEvaluator::evaluate_with_indicators(
+        &strategy,
+        &candles,
+        &market_data,
+        chrono::Utc::now(),
+    )
+    .ok_or_else(|| {
+        ApiError::InvalidRequest(format!(
+            "Strategy produced no signal from the stored {} candles for {}",
+            interval, strategy.symbol
+        ))
+    })?;
+
+    Ok(Json(LiveEvaluation {
+        signal,
+        indicators: indicators.snapshot(),
+        interval,
+        candles: candles.len(),
+    }))
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams, utoipa::ToSchema)]
+#[into_params(parameter_in = Query)]
+struct StrategyStatsQuery {
+    /// Only count signals at or after this time (ISO 8601, defaults to 24 hours ago)
+    since: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Get signal statistics per strategy
+///
+/// Returns signal counts by direction and average confidence for each strategy.
+/// Results are cached in Redis for 5 minutes.
+#[utoipa::path(
+    get,
+    path = "/api/strategies/stats",
+    tag = "Strategies",
+    params(StrategyStatsQuery),
+    responses(
+        (status = 200, description = "Per-strategy signal statistics", body = Vec<StrategyStats>),
+        (status = 503, description = "Database unavailable", body = ErrorResponse)
+    )
+)]
+async fn get_strategy_stats(
+    State(state): State<AppState>,
+    Query(params): Query<StrategyStatsQuery>,
+) -> Result<Json<Vec<StrategyStats>>, ApiError> {
+    let db = state.require_database()?;
+
+    // The default window moves with every request, so cache it under a fixed key
+    let (since, cache_key) = match params.since {
+        Some(since) => (since, strategy_stats_key(&since.timestamp().to_string())),
+        None => (
+            chrono::Utc::now() - chrono::Duration::hours(24),
+            strategy_stats_key("24h"),
+        ),
+    };
+
+    if let Some(ref cache) = state.cache {
+        match cache.get_json::<Vec<StrategyStats>>(&cache_key).await {
+            Ok(Some(stats)) => {
+                state.metrics.cache_hits_total.inc();
+                return Ok(Json(stats));
+            }
+            Ok(None) => state.metrics.cache_misses_total.inc(),
+            Err(e) => {
+                warn!(error = %e, "Failed to read strategy stats from cache");
+                state.metrics.cache_misses_total.inc();
+            }
+        }
+    }
+
+    let stats = db.get_signal_stats_by_strategy(since).await.map_err(|e| {
+        error!(error = %e, "Failed to load strategy stats");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    if let Some(ref cache) = state.cache {
+        if let Err(e) = cache
+            .set_json(&cache_key, &stats, STRATEGY_STATS_CACHE_TTL)
+            .await
+        {
+            warn!(error = %e, "Failed to cache strategy stats");
+        }
+    }
+
+    Ok(Json(stats))
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams, utoipa::ToSchema)]
+#[into_params(parameter_in = Query)]
+struct LatestSignalQuery {
+    /// Symbol to fetch the latest signal for
+    symbol: String,
+}
+
+/// Get the latest signal for a symbol
+///
+/// Reads from the Redis cache first and falls back to QuestDB
+#[utoipa::path(
+    get,
+    path = "/api/signals/latest",
+    tag = "Signals",
+    params(LatestSignalQuery),
+    responses(
+        (status = 200, description = "Latest signal", body = SignalOutput),
+        (status = 404, description = "No signal found for symbol"),
+        (status = 503, description = "Database unavailable", body = ErrorResponse)
+    )
+)]
+async fn get_latest_signal(
+    State(state): State<AppState>,
+    Query(params): Query<LatestSignalQuery>,
+) -> Result<Json<SignalOutput>, ApiError> {
+    if let Some(ref cache) = state.cache {
+        match cache
+            .get_json::<SignalOutput>(&latest_signal_key(&params.symbol))
+            .await
+        {
+            Ok(Some(signal)) => {
+                state.metrics.cache_hits_total.inc();
+                return Ok(Json(signal));
+            }
+            Ok(None) => state.metrics.cache_misses_total.inc(),
+            Err(e) => {
+                warn!(error = %e, symbol = %params.symbol, "Failed to read latest signal from cache");
+                state.metrics.cache_misses_total.inc();
+            }
+        }
+    }
+
+    let db = state.require_database()?;
+
+    let signals = db
+        .get_signals(Some(&params.symbol), Some(1))
+        .await
+        .map_err(|e| {
+            error!(error = %e, symbol = %params.symbol, "Failed to load latest signal");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    signals
+        .into_iter()
+        .next()
+        .map(Json)
+        .ok_or(ApiError::Status(StatusCode::NOT_FOUND))
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams, utoipa::ToSchema)]
+#[into_params(parameter_in = Query)]
+struct SignalsQuery {
+    /// Only return signals for this symbol
+    symbol: Option<String>,
+    /// Only return signals in this direction
+    direction: Option<SignalDirection>,
+    /// Only return signals at or after this time (ISO-8601)
+    from: Option<chrono::DateTime<chrono::Utc>>,
+    /// Only return signals before this time (ISO-8601)
+    to: Option<chrono::DateTime<chrono::Utc>>,
+    /// Only return signals with at least this (stored) confidence
+    min_confidence: Option<f64>,
+    /// 1-based page number (default 1)
+    page: Option<u32>,
+    /// Signals per page (default 50, max 500)
+    page_size: Option<u32>,
+}
+
+/// A stored signal with its confidence decayed to the time of the request
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+struct SignalResponse {
+    #[serde(flatten)]
+    signal: SignalOutput,
+    /// Confidence after decay at query time
+    effective_confidence: f64,
+    /// Whether the decayed confidence is below the strategy's display threshold
+    is_expired: bool,
+}
+
+/// One page of signals with the total number of matches
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+struct SignalPage {
+    data: Vec<SignalResponse>,
+    /// Signals matching the filters across all pages
+    total: u64,
+    page: u32,
+    page_size: u32,
+}
+
+/// List stored signals
+///
+/// Signals are filtered by the query parameters and returned newest first,
+/// one page at a time. Confidence is decayed using the generating strategy's
+/// `decay_model`; signals from strategies without one never expire.
+#[utoipa::path(
+    get,
+    path = "/api/signals",
+    tag = "Signals",
+    params(SignalsQuery),
+    responses(
+        (status = 200, description = "A page of signals with effective confidence", body = SignalPage),
+        (status = 400, description = "Invalid page or time range", body = ErrorResponse),
+        (status = 503, description = "Database unavailable", body = ErrorResponse)
+    )
+)]
+async fn list_signals(
+    State(state): State<AppState>,
+    Query(params): Query<SignalsQuery>,
+) -> Result<Json<SignalPage>, ApiError> {
+    let page = params.page.unwrap_or(1);
+    let page_size = params
+        .page_size
+        .unwrap_or(DEFAULT_SIGNAL_PAGE_SIZE)
+        .min(MAX_SIGNAL_PAGE_SIZE);
+    if page == 0 || page_size == 0 {
+        return Err(ApiError::InvalidRequest(
+            "`page` and `page_size` must be at least 1".to_string(),
+        ));
+    }
+    if let (Some(from), Some(to)) = (params.from, params.to) {
+        if from >= to {
+            return Err(ApiError::InvalidRequest("`from` must be before `to`".to_string()));
+        }
+    }
+    let db = state.require_database()?;
+
+    let filter = SignalFilter {
+        symbol: params.symbol,
+        direction: params.direction,
+        from: params.from,
+        to: params.to,
+        min_confidence: params.min_confidence,
+    };
+    let (signals, total) = db
+        .query_signals_paginated(&filter, page, page_size)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to load signals");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let now = chrono::Utc::now();
+    let data = signals
+        .into_iter()
+        .map(|signal| SignalResponse {
+            effective_confidence: signal.effective_confidence(now),
+            is_expired: signal.is_expired(now),
+            signal,
+        })
+        .collect();
+
+    Ok(Json(SignalPage {
+        data,
+        total,
+        page,
+        page_size,
+    }))
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams, utoipa::ToSchema)]
+#[into_params(parameter_in = Query)]
+struct SignalStreamQuery {
+    /// Only stream signals for this symbol
+    symbol: Option<String>,
+}
+
+/// Stream new signals as Server-Sent Events
+///
+/// Each event carries a JSON-encoded signal, with the signal timestamp in
+/// milliseconds as its `id`; a keep-alive comment is sent every 30 seconds.
+/// A client that reconnects with a `Last-Event-ID` header first receives the
+/// stored signals newer than that id (at most 500, oldest first, and only
+/// when QuestDB is available), then new ones.
+#[utoipa::path(
+    get,
+    path = "/api/signals/stream",
+    tag = "Signals",
+    params(
+        SignalStreamQuery,
+        ("Last-Event-ID" = Option<i64>, Header, description = "Id of the last event received, to resume after reconnecting")
+    ),
+    responses(
+        (status = 200, description = "Stream of signal events", body = SignalOutput, content_type = "text/event-stream")
+    )
+)]
+async fn stream_signals(
+    State(state): State<AppState>,
+    Query(params): Query<SignalStreamQuery>,
+    headers: HeaderMap,
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+    // Subscribe before replaying so no signal falls between the two
+    let receiver = state.signal_stream.subscribe();
+
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<i64>().ok());
+    let replayed = match (last_event_id, &state.database) {
+        (Some(last_event_id), Some(db)) => {
+            replay_signals(db, params.symbol.as_deref(), last_event_id).await
+        }
+        _ => Vec::new(),
+    };
+    // Live signals already covered by the replay are skipped
+    let replayed_up_to = replayed.last().map(signal_event_id);
+
+    let live = stream::unfold(receiver, |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(signal) => return Some((signal, receiver)),
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!(
+                        skipped,
+                        "Signal stream client fell behind, skipping signals"
+                    );
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    })
+    .filter(move |signal| {
+        let is_new = replayed_up_to.is_none_or(|up_to| signal_event_id(signal) > up_to);
+        std::future::ready(is_new)
+    });
+
+    let symbol = params.symbol;
+    let events = stream::iter(replayed)
+        .chain(live)
+        .filter(move |signal| {
+            let matches = symbol
+                .as_ref()
+                .is_none_or(|symbol| &signal.symbol == symbol);
+            std::future::ready(matches)
+        })
+        .map(|signal| {
+            Event::default()
+                .id(signal_event_id(&signal).to_string())
+                .json_data(signal)
+        });
+
+    Sse::new(events).keep_alive(KeepAlive::new().interval(SIGNAL_STREAM_KEEP_ALIVE))
+}
+
+/// SSE event id of a signal: its timestamp in milliseconds, which is also its
+/// id in QuestDB
+fn signal_event_id(signal: &SignalOutput) -> i64 {
+    signal.timestamp.timestamp_millis()
+}
+
+/// Stored signals newer than `last_event_id`, oldest first
+async fn replay_signals(
+    db: &QuestDatabase,
+    symbol: Option<&str>,
+    last_event_id: i64,
+) -> Vec<SignalOutput> {
+    let filter = SignalFilter {
+        symbol: symbol.map(str::to_string),
+        from: chrono::DateTime::from_timestamp_millis(last_event_id.saturating_add(1)),
+        ..SignalFilter::default()
+    };
+    match db
+        .query_signals_paginated(&filter, 1, SIGNAL_STREAM_REPLAY_LIMIT)
+        .await
+    {
+        Ok((mut signals, _)) => {
+            signals.reverse();
+            signals
+        }
+        Err(e) => {
+            warn!(error = %e, last_event_id, "Failed to replay signals to stream client");
+            Vec::new()
+        }
+    }
+}
+
+/// Forward signals published to Redis by workers into `signal_stream`,
+/// resubscribing whenever the subscription fails or ends
+pub async fn relay_published_signals(signal_stream: broadcast::Sender<SignalOutput>) {
+    loop {
+        match subscribe_json::<SignalOutput>(SIGNALS_CHANNEL).await {
+            Ok(signals) => {
+                info!("Relaying published signals to /api/signals/stream");
+                let mut signals = std::pin::pin!(signals);
+                while let Some(signal) = signals.next().await {
+                    // Sending only fails when no client is listening
+                    let _ = signal_stream.send(signal);
+                }
+                warn!("Published signal subscription ended, resubscribing");
+            }
+            Err(e) => {
+                warn!(error = %e, "Failed to subscribe to published signals");
+            }
+        }
+        tokio::time::sleep(SIGNAL_RELAY_RETRY_DELAY).await;
+    }
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams, utoipa::ToSchema)]
+#[into_params(parameter_in = Query)]
+struct SignalStatsQuery {
+    /// Only aggregate signals for this symbol
+    symbol: Option<String>,
+    /// Start of the range, inclusive (ISO 8601, defaults to 7 days before `to`)
+    from: Option<chrono::DateTime<chrono::Utc>>,
+    /// End of the range, exclusive (ISO 8601, defaults to now)
+    to: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Get aggregate signal statistics
+///
+/// Returns, per symbol and strategy, the signal count, direction distribution,
+/// confidence mean and percentiles, average SL/TP and recent signal counts.
+/// Results are cached in Redis for 60 seconds.
+#[utoipa::path(
+    get,
+    path = "/api/signals/stats",
+    tag = "Signals",
+    params(SignalStatsQuery),
+    responses(
+        (status = 200, description = "Signal statistics per symbol and strategy", body = SignalStats),
+        (status = 400, description = "Invalid time range", body = ErrorResponse),
+        (status = 503, description = "Database unavailable", body = ErrorResponse)
+    )
+)]
+async fn get_signal_stats(
+    State(state): State<AppState>,
+    Query(params): Query<SignalStatsQuery>,
+) -> Result<Json<SignalStats>, ApiError> {
+    let to = params.to.unwrap_or_else(chrono::Utc::now);
+    let from = params.from.unwrap_or(to - chrono::Duration::days(7));
+    if from >= to {
+        return Err(ApiError::InvalidRequest("`from` must be before `to`".to_string()));
+    }
+    let db = state.require_database()?;
+
+    // The default window moves with every request, so cache it under a fixed key
+    let window = match (params.from, params.to) {
+        (None, None) => "7d".to_string(),
+        _ => format!("{}-{}", from.timestamp(), to.timestamp()),
+    };
+    let cache_key = signal_stats_key(params.symbol.as_deref().unwrap_or("all"), &window);
+
+    if let Some(ref cache) = state.cache {
+        match cache.get_json::<SignalStats>(&cache_key).await {
+            Ok(Some(stats)) => {
+                state.metrics.cache_hits_total.inc();
+                return Ok(Json(stats));
+            }
+            Ok(None) => state.metrics.cache_misses_total.inc(),
+            Err(e) => {
+                warn!(error = %e, "Failed to read signal stats from cache");
+                state.metrics.cache_misses_total.inc();
+            }
+        }
+    }
+
+    let stats = db
+        .get_signal_stats(params.symbol.as_deref(), from, to)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to load signal stats");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    if let Some(ref cache) = state.cache {
+        if let Err(e) = cache
+            .set_json(&cache_key, &stats, SIGNAL_STATS_CACHE_TTL)
+            .await
+        {
+            warn!(error = %e, "Failed to cache signal stats");
+        }
+    }
+
+    Ok(Json(stats))
+}
+
+/// Get the signal cluster analysis for a symbol
+///
+/// Reports whether the symbol's recent signals agree on a direction. Reads the
+/// analysis workers cache in Redis every 5 minutes and falls back to
+/// clustering the latest signals in QuestDB.
+#[utoipa::path(
+    get,
+    path = "/api/signals/{symbol}/cluster",
+    tag = "Signals",
+    params(
+        ("symbol" = String, Path, description = "Trading symbol")
+    ),
+    responses(
+        (status = 200, description = "Cluster analysis of recent signals", body = ClusterAnalysis),
+        (status = 404, description = "No signal found for symbol"),
+        (status = 503, description = "Database unavailable", body = ErrorResponse)
+    )
+)]
+async fn get_signal_cluster(
+    State(state): State<AppState>,
+    Path(symbol): Path<String>,
+) -> Result<Json<ClusterAnalysis>, ApiError> {
+    if let Some(ref cache) = state.cache {
+        match cache
+            .get_json::<ClusterAnalysis>(&signal_cluster_key(&symbol))
+            .await
+        {
+            Ok(Some(analysis)) => {
+                state.metrics.cache_hits_total.inc();
+                return Ok(Json(analysis));
+            }
+            Ok(None) => state.metrics.cache_misses_total.inc(),
+            Err(e) => {
+                warn!(error = %e, symbol = %symbol, "Failed to read signal cluster from cache");
+                state.metrics.cache_misses_total.inc();
+            }
+        }
+    }
+
+    let db = state.require_database()?;
+
+    let signals = db
+        .get_signals(Some(&symbol), Some(DEFAULT_CLUSTER_SIGNAL_LIMIT))
+        .await
+        .map_err(|e| {
+            error!(error = %e, symbol = %symbol, "Failed to load signals to cluster");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    if signals.is_empty() {
+        return Err(ApiError::Status(StatusCode::NOT_FOUND));
+    }
+
+    Ok(Json(SignalCluster::analyze(
+        &signals,
+        DEFAULT_CLUSTER_WINDOW_SECONDS,
+    )))
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams, utoipa::ToSchema)]
+#[into_params(parameter_in = Query)]
+struct DeadLetterQuery {
+    /// Only return failures for this symbol
+    symbol: Option<String>,
+    /// Only return failures of this job type ("fetch_candles", "evaluate_signal", "store_signal")
+    job_type: Option<String>,
+}
+
+/// List recent job failures
+///
+/// Returns the last 200 failed jobs, newest first
+#[utoipa::path(
+    get,
+    path = "/api/jobs/dead-letters",
+    tag = "Jobs",
+    params(DeadLetterQuery),
+    responses(
+        (status = 200, description = "Recent job failures", body = Vec<DeadLetter>),
+        (status = 503, description = "Database unavailable", body = ErrorResponse)
+    )
+)]
+async fn list_dead_letters(
+    State(state): State<AppState>,
+    Query(params): Query<DeadLetterQuery>,
+) -> Result<Json<Vec<DeadLetter>>, ApiError> {
+    let db = state.require_database()?;
+
+    let dead_letters = db
+        .get_dead_letters(
+            params.symbol.as_deref(),
+            params.job_type.as_deref(),
+            DEAD_LETTER_LIMIT,
+        )
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to load dead letters");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(dead_letters))
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams, utoipa::ToSchema)]
+#[into_params(parameter_in = Query)]
+struct AuditLogQuery {
+    /// Only return changes to this strategy
+    strategy_id: Option<i64>,
+    /// Only return changes at or after this time (ISO-8601)
+    from: Option<chrono::DateTime<chrono::Utc>>,
+    /// Only return changes before this time (ISO-8601)
+    to: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// List strategy changes
+///
+/// Returns the last 200 strategy creates, updates, deletes, enables and
+/// disables made through the API, newest first
+#[utoipa::path(
+    get,
+    path = "/api/audit-logs",
+    tag = "Strategies",
+    params(AuditLogQuery),
+    responses(
+        (status = 200, description = "Recent strategy changes", body = Vec<AuditLogEntry>),
+        (status = 400, description = "Invalid time range", body = ErrorResponse),
+        (status = 503, description = "Database unavailable", body = ErrorResponse)
+    )
+)]
+async fn list_audit_logs(
+    State(state): State<AppState>,
+    Query(params): Query<AuditLogQuery>,
+) -> Result<Json<Vec<AuditLogEntry>>, ApiError> {
+    if let (Some(from), Some(to)) = (params.from, params.to) {
+        if from >= to {
+            return Err(ApiError::InvalidRequest(
+                "`from` must be before `to`".to_string(),
+            ));
+        }
+    }
+    let db = state.require_database()?;
+
+    let entries = db
+        .get_audit_logs(params.strategy_id, params.from, params.to, AUDIT_LOG_LIMIT)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to load audit logs");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(entries))
+}
+
+/// List open positions
+///
+/// Returns the signal that opened each tracked position, sorted by symbol.
+/// Every Long or Short signal opens a position, and later signals in the
+/// same direction are skipped until it is closed.
+#[utoipa::path(
+    get,
+    path = "/api/positions",
+    tag = "Positions",
+    responses(
+        (status = 200, description = "Open positions", body = Vec<SignalOutput>),
+        (status = 503, description = "Redis unavailable", body = ErrorResponse)
+    )
+)]
+async fn list_positions(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<SignalOutput>>, ApiError> {
+    let positions = PositionTracker::new(state.require_cache()?.clone());
+
+    let open = positions.list().await.map_err(|e| {
+        error!(error = %e, "Failed to load open positions");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(open))
+}
+
+/// Close a position
+///
+/// Stops tracking the open position for a symbol, so its next signal is sent
+/// whatever its direction
+#[utoipa::path(
+    post,
+    path = "/api/positions/{symbol}/close",
+    tag = "Positions",
+    params(
+        ("symbol" = String, Path, description = "Trading symbol")
+    ),
+    responses(
+        (status = 204, description = "Position closed"),
+        (status = 404, description = "No open position for the symbol"),
+        (status = 503, description = "Redis unavailable", body = ErrorResponse)
+    )
+)]
+async fn close_position(
+    State(state): State<AppState>,
+    Path(symbol): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let positions = PositionTracker::new(state.require_cache()?.clone());
+
+    let closed = positions.close(&symbol).await.map_err(|e| {
+        error!(error = %e, symbol = %symbol, "Failed to close position");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    if closed {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(StatusCode::NOT_FOUND.into())
+    }
+}
+
+/// Replay a failed job
+///
+/// Rebuilds the job recorded in the dead letter, pushes it back onto its
+/// worker queue and marks the dead letter as replayed
+#[utoipa::path(
+    post,
+    path = "/api/jobs/dead-letters/{id}/replay",
+    tag = "Jobs",
+    params(
+        ("id" = i64, Path, description = "Dead-letter ID")
+    ),
+    responses(
+        (status = 200, description = "Job re-enqueued", body = DeadLetter),
+        (status = 400, description = "Dead letter cannot be turned back into a job", body = ErrorResponse),
+        (status = 404, description = "Dead letter not found"),
+        (status = 409, description = "Dead letter was already replayed"),
+        (status = 503, description = "Database or job queues unavailable", body = ErrorResponse)
+    )
+)]
+async fn replay_dead_letter(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+) -> Result<Json<DeadLetter>, ApiError> {
+    let db = state.require_database()?;
+    let queues = state.require_job_queues()?;
+
+    let mut dead_letter = db.get_dead_letter(id).await.map_err(|e| {
+        error!(error = %e, dead_letter_id = id, "Failed to load dead letter");
+        if matches!(e, KryptexError::NotFound(_)) {
+            StatusCode::NOT_FOUND
+        } else {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    })?;
+    if dead_letter.is_replayed() {
+        return Err(StatusCode::CONFLICT.into());
+    }
+
+    replay::replay_dead_letter(&dead_letter, queues).await.map_err(|e| {
+        let invalid = e
+            .downcast_ref::<std::io::Error>()
+            .is_some_and(|e| e.kind() == std::io::ErrorKind::InvalidData);
+        if invalid {
+            ApiError::InvalidRequest(e.to_string())
+        } else {
+            error!(error = %e, dead_letter_id = id, "Failed to replay dead letter");
+            ApiError::Status(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    })?;
+
+    let replayed_at = chrono::Utc::now();
+    db.mark_dead_letter_replayed(id, replayed_at)
+        .await
+        .map_err(|e| {
+            error!(error = %e, dead_letter_id = id, "Failed to mark dead letter replayed");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    dead_letter.replayed_at = Some(replayed_at);
+
+    info!(
+        dead_letter_id = id,
+        job_type = %dead_letter.job_type,
+        symbol = %dead_letter.symbol,
+        "Replayed {} job for {}",
+        dead_letter.job_type,
+        dead_letter.symbol
+    );
+
+    Ok(Json(dead_letter))
+}
+
+/// Count job failures
+///
+/// Returns dead-letter counts grouped by job type and replay state
+#[utoipa::path(
+    get,
+    path = "/api/jobs/dead-letters/stats",
+    tag = "Jobs",
+    responses(
+        (status = 200, description = "Dead-letter counts", body = Vec<DeadLetterStats>),
+        (status = 503, description = "Database unavailable", body = ErrorResponse)
+    )
+)]
+async fn get_dead_letter_stats(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<DeadLetterStats>>, ApiError> {
+    let db = state.require_database()?;
+
+    let stats = db.get_dead_letter_stats().await.map_err(|e| {
+        error!(error = %e, "Failed to load dead letter stats");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(stats))
+}
+
+/// Strategy file hot-reload status
+///
+/// Returns the watched directory, when a strategy file was last applied and
+/// the files whose latest version failed to load
+#[utoipa::path(
+    get,
+    path = "/api/config/reload-status",
+    tag = "Config",
+    responses(
+        (status = 200, description = "Hot-reload status", body = ReloadStatus)
+    )
+)]
+async fn get_reload_status(State(state): State<AppState>) -> Json<ReloadStatus> {
+    Json(state.reload_status.read().await.clone())
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        health_check,
+        metrics_handler,
+        list_strategies,
+        get_strategy,
+        create_strategy,
+        validate_strategy,
+        create_default_strategy,
+        list_strategy_templates,
+        update_strategy,
+        delete_strategy,
+        enable_strategy,
+        disable_strategy,
+        clone_strategy,
+        backtest_strategy,
+        trace_strategy,
+        live_evaluate_strategy,
+        get_strategy_stats,
+        get_latest_signal,
+        list_signals,
+        get_signal_stats,
+        get_signal_cluster,
+        stream_signals,
+        list_dead_letters,
+        replay_dead_letter,
+        get_dead_letter_stats,
+        list_audit_logs,
+        list_positions,
+        close_position,
+        get_reload_status
+    ),
+    components(schemas(
+        HealthResponse,
+        DetailedHealth,
+        ServiceHealth,
+        ServiceStatus,
+        ErrorResponse,
+        PayloadTooLargeResponse,
+        InsufficientCandlesResponse,
+        StrategyResponse,
+        CreateStrategyRequest,
+        StrategyValidationResponse,
+        CreateDefaultStrategyRequest,
+        StrategyTemplate,
+        UpdateStrategyRequest,
+        StrategyConfig,
+        StrategyQuery,
+        CloneStrategyRequest,
+        crate::models::strategy::Rule,
+        crate::models::strategy::RuleType,
+        crate::models::strategy::Condition,
+        crate::models::strategy::IndicatorType,
+        crate::models::strategy::Comparison,
+        crate::models::strategy::LogicalOperator,
+        crate::models::strategy::AggregationConfig,
+        crate::models::strategy::AggregationMethod,
+        crate::models::strategy::SignalThresholds,
+        crate::models::strategy::DecayConfig,
+        crate::models::strategy::PositionSizeModel,
+        crate::models::strategy::SlTpModel,
+        BacktestRequest,
+        BacktestReport,
+        crate::backtesting::TradeRecord,
+        crate::backtesting::ExitReason,
+        TraceRequest,
+        EvaluationTrace,
+        crate::models::strategy::RuleResult,
+        LiveEvaluateQuery,
+        LiveEvaluation,
+        IndicatorSnapshot,
+        StrategyStatsQuery,
+        StrategyStats,
+        LatestSignalQuery,
+        SignalOutput,
+        crate::models::signal::SignalDirection,
+        crate::models::signal::SignalReason,
+        SignalsQuery,
+        SignalResponse,
+        SignalPage,
+        SignalStatsQuery,
+        SignalStats,
+        ClusterAnalysis,
+        SignalStreamQuery,
+        crate::models::signal::SignalGroupStats,
+        crate::models::signal::ConfidencePercentiles,
+        DeadLetterQuery,
+        DeadLetter,
+        DeadLetterStats,
+        AuditLogQuery,
+        AuditLogEntry,
+        ReloadStatus,
+        ReloadError
+    )),
+    tags(
+        (name = "Health", description = "Health check endpoints"),
+        (name = "Metrics", description = "Metrics endpoints"),
+        (name = "Strategies", description = "Strategy management endpoints"),
+        (name = "Signals", description = "Signal query endpoints"),
+        (name = "Jobs", description = "Job queue inspection endpoints"),
+        (name = "Positions", description = "Open position tracking endpoints"),
+        (name = "Config", description = "Runtime configuration endpoints")
+    ),
+    modifiers(&ApiKeySecurity),
+    info(
+        title = "Perptrix API",
+        description = "API for the Perptrix signal engine - a trading strategy evaluation system",
+        version = "0.1.0"
+    )
+)]
+struct ApiDoc;
+
+/// Middleware to track HTTP request metrics
+async fn metrics_middleware(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    // Skip metrics tracking for the /metrics endpoint to avoid potential recursion issues
+    let path = request.uri().path();
+    if path == "/metrics" {
+        return next.run(request).await;
+    }
+
+    let start = Instant::now();
+    let method = request.method().clone();
+    let path = path.to_string();
+
+    // Increment in-flight requests
+    state.metrics.http_requests_in_flight.inc();
+
+    // Process request
+    let response = next.run(request).await;
+    let status = response.status();
     let duration = start.elapsed();
 
     // Decrement in-flight requests
@@ -437,7 +2413,37 @@ async fn metrics_middleware(
     response
 }
 
+/// Replace the plain-text 413 from the body limit with a JSON error
+async fn payload_too_large_middleware(
+    State(max_bytes): State<usize>,
+    response: Response,
+) -> Response {
+    if response.status() != StatusCode::PAYLOAD_TOO_LARGE {
+        return response;
+    }
+
+    (
+        StatusCode::PAYLOAD_TOO_LARGE,
+        Json(PayloadTooLargeResponse {
+            error: "request_too_large".to_string(),
+            max_bytes,
+        }),
+    )
+        .into_response()
+}
+
 pub fn create_router(state: AppState) -> Router {
+    let max_body_bytes = crate::config::get_max_request_body_bytes();
+    let auth = ApiKeyAuthLayer::new(
+        state.api_keys.iter(),
+        state.metrics.auth_failures_total.clone(),
+    );
+    let rate_limit = RateLimitLayer::new(
+        crate::config::get_api_rate_limit_per_minute(),
+        crate::config::get_api_rate_limit_burst(),
+        state.metrics.api_rate_limit_hits_total.clone(),
+    );
+
     Router::new()
         .merge(
             SwaggerUi::new("/docs")
@@ -447,9 +2453,44 @@ pub fn create_router(state: AppState) -> Router {
         .route("/metrics", get(metrics_handler))
         .route("/api/strategies", get(list_strategies))
         .route("/api/strategies", post(create_strategy))
+        .route("/api/strategies/validate", post(validate_strategy))
+        .route("/api/strategies/default", post(create_default_strategy))
+        .route("/api/strategy-templates", get(list_strategy_templates))
+        .route("/api/strategies/stats", get(get_strategy_stats))
         .route("/api/strategies/{id}", get(get_strategy))
         .route("/api/strategies/{id}", put(update_strategy))
         .route("/api/strategies/{id}", delete(delete_strategy))
+        .route("/api/strategies/{id}/enable", put(enable_strategy))
+        .route("/api/strategies/{id}/disable", put(disable_strategy))
+        .route("/api/strategies/{id}/clone", post(clone_strategy))
+        .route("/api/strategies/{id}/backtest", post(backtest_strategy))
+        .route("/api/strategies/{id}/trace", post(trace_strategy))
+        .route(
+            "/api/strategies/{id}/live-evaluate",
+            post(live_evaluate_strategy),
+        )
+        .route("/api/signals", get(list_signals))
+        .route("/api/signals/latest", get(get_latest_signal))
+        .route("/api/signals/stats", get(get_signal_stats))
+        .route("/api/signals/stream", get(stream_signals))
+        .route("/api/signals/{symbol}/cluster", get(get_signal_cluster))
+        .route("/api/jobs/dead-letters", get(list_dead_letters))
+        .route("/api/jobs/dead-letters/stats", get(get_dead_letter_stats))
+        .route(
+            "/api/jobs/dead-letters/{id}/replay",
+            post(replay_dead_letter),
+        )
+        .route("/api/audit-logs", get(list_audit_logs))
+        .route("/api/positions", get(list_positions))
+        .route("/api/positions/{symbol}/close", post(close_position))
+        .route("/api/config/reload-status", get(get_reload_status))
+        .layer(RequestBodyLimitLayer::new(max_body_bytes))
+        // The limit above replaces axum's built-in 2MB extractor limit
+        .layer(DefaultBodyLimit::disable())
+        // Runs inside the metrics and CORS layers so rejections are still counted
+        .layer(auth)
+        // Outside authentication so guessing API keys is limited too
+        .layer(rate_limit)
         .layer(
             ServiceBuilder::new()
                 .layer(
@@ -462,20 +2503,23 @@ pub fn create_router(state: AppState) -> Router {
                     state.clone(),
                     metrics_middleware,
                 ))
-                .layer(CorsLayer::permissive()),
+                .layer(CorsLayer::permissive())
+                .layer(axum::middleware::map_response_with_state(
+                    max_body_bytes,
+                    payload_too_large_middleware,
+                )),
         )
         .with_state(state)
 }
 
 pub async fn start_server(port: u16) -> Result<(), Box<dyn std::error::Error>> {
     let metrics = Arc::new(Metrics::new()?);
-    let start_time = Arc::new(Instant::now());
     
     // Initialize database connection (optional - API works without it but strategy endpoints won't)
     let database = match crate::db::QuestDatabase::new().await {
         Ok(db) => {
             info!("QuestDB connected for API server");
-            Some(Arc::new(db))
+            Some(Arc::new(db.with_metrics(metrics.clone())))
         }
         Err(e) => {
             tracing::warn!(error = %e, "Failed to connect to QuestDB for API server - strategy endpoints will be unavailable");
@@ -483,12 +2527,80 @@ pub async fn start_server(port: u16) -> Result<(), Box<dyn std::error::Error>> {
         }
     };
     
-    let state = AppState {
-        health: Arc::new(RwLock::new(HealthStatus::default())),
-        metrics: metrics.clone(),
-        start_time: start_time.clone(),
-        database,
+    // Initialize Redis cache (optional - latest signal lookups fall back to QuestDB)
+    let cache = match crate::cache::RedisCache::new().await {
+        Ok(c) => {
+            info!("Redis connected for API server");
+            metrics.cache_connected.set(1.0);
+            Some(Arc::new(c))
+        }
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to connect to Redis for API server - signal lookups will use QuestDB only");
+            None
+        }
+    };
+    
+    // Connect to the worker queues (optional - dead letters can't be replayed without them)
+    let job_queues = match apalis_redis::connect(crate::config::get_redis_url()).await {
+        Ok(conn) => Some(JobQueues {
+            fetch: apalis_redis::RedisStorage::new(conn.clone()),
+            evaluate: apalis_redis::RedisStorage::new(conn.clone()),
+            store: apalis_redis::RedisStorage::new(conn),
+        }),
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to connect to Redis job queues for API server - dead-letter replay will be unavailable");
+            None
+        }
     };
+
+    let api_keys = crate::config::get_api_keys();
+    if api_keys.is_empty() {
+        warn!("API_KEYS is not set - /api routes are unauthenticated");
+    }
+
+    // Watch STRATEGY_DIR for strategy files (optional - needs the database)
+    let reload_status = Arc::new(RwLock::new(ReloadStatus::default()));
+    match (crate::config::get_strategy_dir(), &database) {
+        (Some(dir), Some(database)) => {
+            let mut watcher = StrategyWatcher::new(dir, database.clone(), reload_status.clone());
+            if let Some(ref job_queues) = job_queues {
+                watcher = watcher.with_fetch_queue(job_queues.fetch.clone());
+            }
+            if let Err(e) = watcher.watch().await {
+                tracing::warn!(error = %e, "Failed to watch STRATEGY_DIR - strategy hot reload disabled");
+            }
+        }
+        (Some(_), None) => {
+            tracing::warn!("STRATEGY_DIR is set but QuestDB is unavailable - strategy hot reload disabled");
+        }
+        (None, _) => {}
+    }
+
+    let mut builder = AppState::builder()
+        .with_metrics(metrics)
+        .with_api_keys(api_keys)
+        .with_reload_status(reload_status);
+    if let Some(database) = database {
+        builder = builder.with_database(database);
+    }
+    let has_cache = cache.is_some();
+    if let Some(cache) = cache {
+        // The WebSocket runs in websocket-service, which reports through Redis
+        // and caches the spreads and trades live evaluations read
+        let market_data = HyperliquidMarketDataProvider::new().with_cache(cache.clone());
+        builder = builder
+            .with_cache(cache)
+            .with_websocket_heartbeat()
+            .with_market_data(Arc::new(market_data));
+    }
+    if let Some(job_queues) = job_queues {
+        builder = builder.with_job_queues(job_queues);
+    }
+    let state = builder.build();
+    // Workers publish stored signals to Redis; relay them to stream clients
+    if has_cache {
+        tokio::spawn(relay_published_signals(state.signal_stream.clone()));
+    }
     let app = create_router(state);
     let listener = tokio::net::TcpListener::bind(format!("0.0.0.0:{}", port)).await?;
 
@@ -501,7 +2613,12 @@ pub async fn start_server(port: u16) -> Result<(), Box<dyn std::error::Error>> {
         "API documentation available at http://0.0.0.0:{}/docs",
         port
     );
-    axum::serve(listener, app).await?;
+    // The rate limiter identifies clients by their peer address
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+    )
+    .await?;
 
     Ok(())
 }
@@ -1,10 +1,14 @@
 //! Core application primitives (engines, orchestrators)
 
+pub mod auth;
 pub mod http;
+pub mod rate_limit;
 pub mod runtime;
 pub mod scheduler;
 pub mod bootstrap {}
 
+pub use auth::*;
 pub use http::*;
+pub use rate_limit::*;
 pub use runtime::*;
 pub use scheduler::*;
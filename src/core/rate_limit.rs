@@ -0,0 +1,206 @@
+//! Per-client rate limiting for the HTTP server
+//!
+//! Each client IP gets a token bucket holding up to `burst` requests that
+//! refills at `per_minute` requests per minute. `/health` and `/metrics` are
+//! never limited so probes and scrapers keep working under load.
+
+use axum::{
+    extract::{ConnectInfo, MatchedPath, Request},
+    http::{header, HeaderValue, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use dashmap::DashMap;
+use futures_util::future::BoxFuture;
+use prometheus::CounterVec;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tower::{Layer, Service};
+
+use crate::core::http::ErrorResponse;
+
+/// Routes that bypass rate limiting
+const UNLIMITED_PATHS: [&str; 2] = ["/health", "/metrics"];
+/// `hits` label for requests that matched no route
+const UNMATCHED_PATH: &str = "unmatched";
+/// Tracked clients above which idle (full) buckets are dropped
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+/// Token bucket for a single client
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    /// A full bucket of `capacity` tokens
+    pub fn new(capacity: f64, now: Instant) -> Self {
+        Self {
+            tokens: capacity,
+            refilled_at: now,
+        }
+    }
+
+    /// Take one token, refilling at `per_second` up to `capacity` first.
+    ///
+    /// On an empty bucket returns how long until a token is available.
+    pub fn try_acquire(
+        &mut self,
+        capacity: f64,
+        per_second: f64,
+        now: Instant,
+    ) -> Result<(), Duration> {
+        self.refill(capacity, per_second, now);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - self.tokens) / per_second))
+        }
+    }
+
+    /// Whether the bucket has refilled completely, i.e. the client is idle
+    pub fn is_full(&mut self, capacity: f64, per_second: f64, now: Instant) -> bool {
+        self.refill(capacity, per_second, now);
+        self.tokens >= capacity
+    }
+
+    fn refill(&mut self, capacity: f64, per_second: f64, now: Instant) {
+        let elapsed = now
+            .saturating_duration_since(self.refilled_at)
+            .as_secs_f64();
+        self.tokens = (self.tokens + elapsed * per_second).min(capacity);
+        self.refilled_at = now;
+    }
+}
+
+/// Layer that answers 429 once a client IP exceeds its request budget.
+///
+/// Clients are identified by the peer address from [`ConnectInfo`], so the
+/// router must be served with `into_make_service_with_connect_info`; requests
+/// without one pass through. A `per_minute` of 0 disables limiting.
+#[derive(Clone)]
+pub struct RateLimitLayer {
+    per_minute: u32,
+    burst: u32,
+    buckets: Arc<DashMap<IpAddr, TokenBucket>>,
+    hits: CounterVec,
+}
+
+impl RateLimitLayer {
+    /// `hits` is incremented, labeled by path, for every rejected request
+    pub fn new(per_minute: u32, burst: u32, hits: CounterVec) -> Self {
+        Self {
+            per_minute,
+            burst: burst.max(1),
+            buckets: Arc::new(DashMap::new()),
+            hits,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.per_minute > 0
+    }
+
+    fn per_second(&self) -> f64 {
+        f64::from(self.per_minute) / 60.0
+    }
+
+    /// Take a token for `ip`, or the time until one is available
+    fn check(&self, ip: IpAddr, now: Instant) -> Result<(), Duration> {
+        let (capacity, per_second) = (f64::from(self.burst), self.per_second());
+        if self.buckets.len() > MAX_TRACKED_CLIENTS {
+            self.buckets
+                .retain(|_, bucket| !bucket.is_full(capacity, per_second, now));
+        }
+
+        self.buckets
+            .entry(ip)
+            .or_insert_with(|| TokenBucket::new(capacity, now))
+            .try_acquire(capacity, per_second, now)
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimit<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimit {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+/// Service produced by [`RateLimitLayer`]
+#[derive(Clone)]
+pub struct RateLimit<S> {
+    inner: S,
+    layer: RateLimitLayer,
+}
+
+impl<S> Service<Request> for RateLimit<S>
+where
+    S: Service<Request, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        // Use the route template: raw paths would create a `hits` series per
+        // resource ID, and unrouted variants of `/health` could skip the limit
+        let path = request.extensions().get::<MatchedPath>().cloned();
+        let path = path.as_ref().map(MatchedPath::as_str);
+        if !self.layer.is_enabled() || path.is_some_and(|path| UNLIMITED_PATHS.contains(&path)) {
+            return Box::pin(self.inner.call(request));
+        }
+        let Some(ConnectInfo(addr)) = request
+            .extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .copied()
+        else {
+            return Box::pin(self.inner.call(request));
+        };
+        let ip = addr.ip();
+
+        match self.layer.check(ip, Instant::now()) {
+            Ok(()) => Box::pin(self.inner.call(request)),
+            Err(retry_after) => {
+                let path = path.unwrap_or(UNMATCHED_PATH);
+                self.layer.hits.with_label_values(&[path]).inc();
+                tracing::warn!(client = %ip, path = %path, "Rate limited API request");
+                let response = too_many_requests(retry_after);
+                Box::pin(async { Ok(response) })
+            }
+        }
+    }
+}
+
+/// Seconds a client should wait, rounded up so retrying on time succeeds
+pub fn retry_after_secs(retry_after: Duration) -> u64 {
+    (retry_after.as_secs_f64().ceil() as u64).max(1)
+}
+
+fn too_many_requests(retry_after: Duration) -> Response {
+    let secs = retry_after_secs(retry_after);
+    let mut response = (
+        StatusCode::TOO_MANY_REQUESTS,
+        Json(ErrorResponse {
+            error: "rate_limited".to_string(),
+            message: format!("Too many requests, retry after {} seconds", secs),
+        }),
+    )
+        .into_response();
+    response
+        .headers_mut()
+        .insert(header::RETRY_AFTER, HeaderValue::from(secs));
+    response
+}
@@ -1,11 +1,20 @@
 //! Apalis worker setup for signal evaluation jobs
 
+use crate::error::KryptexError;
 use crate::jobs::context::JobContext;
+use crate::config::{
+    get_eval_max_retries, get_fetch_max_retries, get_job_retry_backoff_seconds,
+    get_store_max_retries,
+};
 use crate::jobs::handlers;
-use crate::jobs::types::{EvaluateSignalJob, FetchCandlesJob, StoreSignalJob};
+use crate::jobs::types::{EvaluateSignalJob, FetchCandlesJob, JobType, StoreSignalJob};
+use apalis::layers::retry::backoff::Backoff;
+use apalis::layers::retry::{BackoffRetryPolicy, Policy, RetryPolicy};
+use apalis::layers::WorkerBuilderExt;
 use apalis::prelude::*;
-use apalis_redis::RedisStorage;
+use apalis_redis::{Config, ConnectionManager, RedisStorage};
 use std::sync::Arc;
+use std::time::Duration;
 use tracing::info;
 
 /// Configuration for the job runtime
@@ -24,11 +33,134 @@ impl Default for RuntimeConfig {
     }
 }
 
+/// How often a failed job of one type is retried
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct JobRetryPolicy {
+    /// Retries after the first attempt; 0 never retries
+    pub max_retries: u32,
+    /// Wait before each retry
+    pub backoff_seconds: u64,
+}
+
+impl JobRetryPolicy {
+    pub fn new(max_retries: u32, backoff_seconds: u64) -> Self {
+        Self {
+            max_retries,
+            backoff_seconds,
+        }
+    }
+
+    /// Whether a job that failed on `attempt` (1 on the first run, as counted
+    /// by Apalis) is retried by the worker's retry layer
+    pub fn should_retry(&self, attempt: usize) -> bool {
+        attempt >= 1 && attempt <= self.max_retries as usize
+    }
+
+    pub fn backoff(&self) -> Duration {
+        Duration::from_secs(self.backoff_seconds)
+    }
+}
+
+/// Retry policy for each job type.
+///
+/// The default never retries, like a worker without a retry layer;
+/// [`RetryPolicyConfig::from_env`] reads the policies the worker runs with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RetryPolicyConfig {
+    pub fetch_candles: JobRetryPolicy,
+    pub evaluate_signal: JobRetryPolicy,
+    pub store_signal: JobRetryPolicy,
+}
+
+impl RetryPolicyConfig {
+    /// Read `FETCH_MAX_RETRIES`, `EVAL_MAX_RETRIES`, `STORE_MAX_RETRIES` and
+    /// `JOB_RETRY_BACKOFF_SECONDS`
+    pub fn from_env() -> Self {
+        let backoff_seconds = get_job_retry_backoff_seconds();
+        Self {
+            fetch_candles: JobRetryPolicy::new(get_fetch_max_retries(), backoff_seconds),
+            evaluate_signal: JobRetryPolicy::new(get_eval_max_retries(), backoff_seconds),
+            store_signal: JobRetryPolicy::new(get_store_max_retries(), backoff_seconds),
+        }
+    }
+
+    pub fn for_job(&self, job_type: JobType) -> JobRetryPolicy {
+        match job_type {
+            JobType::FetchCandles => self.fetch_candles,
+            JobType::EvaluateSignal => self.evaluate_signal,
+            JobType::StoreSignal => self.store_signal,
+        }
+    }
+}
+
+/// Waits the same delay before every retry
+#[derive(Debug, Clone, Copy)]
+struct FixedBackoff(Duration);
+
+impl Backoff for FixedBackoff {
+    type Future = tokio::time::Sleep;
+
+    fn next_backoff(&mut self) -> Self::Future {
+        tokio::time::sleep(self.0)
+    }
+}
+
+/// Retries like the wrapped policy, except that a handler's
+/// [`KryptexError`] that is not [transient](KryptexError::is_transient)
+/// aborts the job at once
+#[derive(Debug, Clone)]
+struct TransientRetryPolicy(BackoffRetryPolicy<FixedBackoff>);
+
+impl<T, Res, Ctx> Policy<Request<T, Ctx>, Res, Error> for TransientRetryPolicy
+where
+    T: Clone,
+    Ctx: Clone,
+{
+    type Future = <BackoffRetryPolicy<FixedBackoff> as Policy<Request<T, Ctx>, Res, Error>>::Future;
+
+    fn retry(
+        &mut self,
+        req: &mut Request<T, Ctx>,
+        result: &mut Result<Res, Error>,
+    ) -> Option<Self::Future> {
+        if let Err(Error::Failed(e)) = result {
+            let permanent = e
+                .downcast_ref::<KryptexError>()
+                .is_some_and(|e| !e.is_transient());
+            if permanent {
+                *result = Err(Error::Abort(e.clone()));
+                return None;
+            }
+        }
+        self.0.retry(req, result)
+    }
+
+    fn clone_request(&mut self, req: &Request<T, Ctx>) -> Option<Request<T, Ctx>> {
+        Some(req.clone())
+    }
+}
+
+/// Apalis retry layer for `policy`
+fn retry_layer(policy: JobRetryPolicy) -> TransientRetryPolicy {
+    TransientRetryPolicy(
+        RetryPolicy::retries(policy.max_retries as usize)
+            .with_backoff(FixedBackoff(policy.backoff())),
+    )
+}
+
+/// Storage for high-priority `FetchCandlesJob`s, kept apart from the default
+/// queue by its Redis namespace
+pub fn priority_fetch_storage(conn: ConnectionManager) -> RedisStorage<FetchCandlesJob> {
+    let namespace = format!("{}:high", std::any::type_name::<FetchCandlesJob>());
+    RedisStorage::new_with_config(conn, Config::default().set_namespace(&namespace))
+}
+
 /// Signal runtime that sets up Apalis workers
 pub struct SignalRuntime {
     _config: RuntimeConfig,
     job_context: Arc<JobContext>,
     fetch_storage: Arc<RedisStorage<FetchCandlesJob>>,
+    priority_fetch_storage: Option<Arc<RedisStorage<FetchCandlesJob>>>,
     eval_storage: Arc<RedisStorage<EvaluateSignalJob>>,
     store_storage: Arc<RedisStorage<StoreSignalJob>>,
     concurrency: usize,
@@ -48,23 +180,34 @@ impl SignalRuntime {
             _config: config,
             job_context,
             fetch_storage,
+            priority_fetch_storage: None,
             eval_storage,
             store_storage,
             concurrency,
         }
     }
 
+    /// Drain `storage` ahead of the default `FetchCandlesJob` queue
+    pub fn with_priority_storage(mut self, storage: Arc<RedisStorage<FetchCandlesJob>>) -> Self {
+        self.priority_fetch_storage = Some(storage);
+        self
+    }
+
     /// Set custom concurrency (default is number of symbols)
     pub fn with_concurrency(mut self, concurrency: usize) -> Self {
         self.concurrency = concurrency;
         self
     }
 
-    /// Start all workers and return handles for graceful shutdown
+    /// Start all workers and return handles for graceful shutdown.
+    ///
+    /// Each worker retries failed jobs per the job context's
+    /// [`RetryPolicyConfig`].
     pub async fn start_workers(
         &self,
     ) -> Result<Vec<tokio::task::JoinHandle<()>>, Box<dyn std::error::Error + Send + Sync>> {
         let mut handles = Vec::new();
+        let retry_policies = self.job_context.retry_policies;
 
         info!(
             concurrency = self.concurrency,
@@ -76,16 +219,43 @@ impl SignalRuntime {
         let fetch_storage = (*self.fetch_storage).clone();
         let eval_storage = self.eval_storage.clone();
         let job_context = self.job_context.clone();
-        let fetch_handle = tokio::spawn(async move {
-            let worker = WorkerBuilder::new("fetch-candles-worker")
-                .data(job_context.clone())
-                .data(eval_storage.clone())
-                .backend(fetch_storage)
-                .build_fn(handlers::handle_fetch_candles);
-
-            info!("SignalRuntime: FetchCandlesJob worker started");
-            worker.run().await;
-        });
+        let fetch_handle = match self.priority_fetch_storage.clone() {
+            Some(priority_storage) => tokio::spawn(async move {
+                let priority_worker = WorkerBuilder::new("fetch-candles-priority-worker")
+                    .retry(retry_layer(retry_policies.fetch_candles))
+                    .data(job_context.clone())
+                    .data(eval_storage.clone())
+                    .backend((*priority_storage).clone())
+                    .build_fn(handlers::handle_fetch_candles);
+                // Default-queue jobs wait for the high-priority queue to drain
+                let worker = WorkerBuilder::new("fetch-candles-worker")
+                    .retry(retry_layer(retry_policies.fetch_candles))
+                    .data(job_context.clone())
+                    .data(eval_storage.clone())
+                    .data((*priority_storage).clone())
+                    .backend(fetch_storage)
+                    .build_fn(handlers::handle_deferred_fetch_candles);
+
+                info!("SignalRuntime: FetchCandlesJob workers started with a high-priority queue");
+                // Biased so the high-priority worker is always polled first
+                tokio::select! {
+                    biased;
+                    _ = priority_worker.run() => {}
+                    _ = worker.run() => {}
+                }
+            }),
+            None => tokio::spawn(async move {
+                let worker = WorkerBuilder::new("fetch-candles-worker")
+                    .retry(retry_layer(retry_policies.fetch_candles))
+                    .data(job_context.clone())
+                    .data(eval_storage.clone())
+                    .backend(fetch_storage)
+                    .build_fn(handlers::handle_fetch_candles);
+
+                info!("SignalRuntime: FetchCandlesJob worker started");
+                worker.run().await;
+            }),
+        };
         handles.push(fetch_handle);
 
         // Worker for EvaluateSignalJob
@@ -94,6 +264,7 @@ impl SignalRuntime {
         let job_context_eval = self.job_context.clone();
         let eval_handle = tokio::spawn(async move {
             let worker = WorkerBuilder::new("evaluate-signal-worker")
+                .retry(retry_layer(retry_policies.evaluate_signal))
                 .data(job_context_eval.clone())
                 .data(store_storage.clone())
                 .backend(eval_storage_worker)
@@ -109,6 +280,7 @@ impl SignalRuntime {
         let job_context_store = self.job_context.clone();
         let store_handle = tokio::spawn(async move {
             let worker = WorkerBuilder::new("store-signal-worker")
+                .retry(retry_layer(retry_policies.store_signal))
                 .data(job_context_store.clone())
                 .backend(store_storage_worker)
                 .build_fn(handlers::handle_store_signal);
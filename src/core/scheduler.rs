@@ -1,20 +1,135 @@
 //! Cron-based scheduler for enqueuing signal evaluation jobs
 
-use crate::jobs::types::FetchCandlesJob;
+use crate::cache::redis::{
+    fetch_lock_key, latest_signal_key, scheduler_last_tick_key, ACTIVE_STRATEGIES_KEY,
+};
+use crate::cache::RedisCache;
+use crate::config;
+use crate::db::QuestDatabase;
+use crate::error::KryptexError;
+use crate::jobs::types::{FetchCandlesJob, JobPriority};
+use crate::metrics::{Metrics, SIGNAL_CONFIDENCE_THRESHOLD};
+use crate::models::signal::{SignalDirection, SignalOutput};
+use crate::models::strategy::Strategy;
 use apalis::prelude::*;
 use apalis_redis::RedisStorage;
+use chrono::{DateTime, Utc};
 use cron::Schedule;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
+
+/// Redis key holding the persisted symbol -> interval map
+const SCHEDULER_SYMBOLS_KEY: &str = "scheduler:symbols";
+/// How much sooner than the evaluation interval a fetch lock expires
+const FETCH_LOCK_TTL_MARGIN_SECS: u64 = 1;
+
+/// TTL of the fetch lock for a symbol evaluated every `interval_seconds`.
+///
+/// Slightly shorter than the interval, so the lock taken on one tick has
+/// expired by the next one.
+pub fn fetch_lock_ttl(interval_seconds: u64) -> u64 {
+    interval_seconds
+        .saturating_sub(FETCH_LOCK_TTL_MARGIN_SECS)
+        .max(1)
+}
+
+/// Priority for a symbol's next `FetchCandlesJob`.
+///
+/// A Long or Short previous signal with confidence of at least
+/// [`SIGNAL_CONFIDENCE_THRESHOLD`] is re-evaluated at `High` priority;
+/// otherwise the job keeps `base`.
+pub fn job_priority(previous_signal: Option<&SignalOutput>, base: JobPriority) -> JobPriority {
+    match previous_signal {
+        Some(signal)
+            if signal.direction != SignalDirection::Neutral
+                && signal.confidence >= SIGNAL_CONFIDENCE_THRESHOLD =>
+        {
+            JobPriority::High
+        }
+        _ => base,
+    }
+}
+
+/// Enabled and disabled strategy counts across all symbols
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StrategyAvailability {
+    pub enabled: usize,
+    pub disabled: usize,
+    /// Symbols with at least one strategy, all of them disabled
+    pub disabled_symbols: HashSet<String>,
+}
+
+impl StrategyAvailability {
+    pub fn from_strategies(strategies: &[Strategy]) -> Self {
+        let enabled_symbols: HashSet<&str> = strategies
+            .iter()
+            .filter(|strategy| strategy.enabled)
+            .map(|strategy| strategy.symbol.as_str())
+            .collect();
+        let disabled_symbols = strategies
+            .iter()
+            .filter(|strategy| !enabled_symbols.contains(strategy.symbol.as_str()))
+            .map(|strategy| strategy.symbol.clone())
+            .collect();
+        let enabled = strategies
+            .iter()
+            .filter(|strategy| strategy.enabled)
+            .count();
+        Self {
+            enabled,
+            disabled: strategies.len() - enabled,
+            disabled_symbols,
+        }
+    }
+}
+
+/// Content hash of a strategy list, used to detect changes between polls.
+///
+/// Strategies hash by their JSON form, so any change to a strategy's name,
+/// symbol, config or enabled flag changes the hash.
+pub fn strategies_hash(strategies: &[Strategy]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for strategy in strategies {
+        serde_json::to_string(strategy)
+            .unwrap_or_default()
+            .hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Result of trying to enqueue a `FetchCandlesJob`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnqueueOutcome {
+    Enqueued,
+    /// A job for the symbol was already enqueued this interval
+    Deduplicated,
+    Failed,
+}
 
 /// Scheduler that periodically enqueues FetchCandlesJob for each symbol
 pub struct JobScheduler {
     storage: Arc<RedisStorage<FetchCandlesJob>>,
-    symbols: Vec<String>,
+    /// Separate queue for `High` priority jobs, drained before `storage`
+    priority_storage: Option<Arc<RedisStorage<FetchCandlesJob>>>,
+    symbols: Arc<RwLock<Vec<String>>>,
+    /// Per-symbol evaluation interval; symbols are enqueued on the first tick
+    /// after their interval has elapsed
+    symbol_intervals: Arc<RwLock<HashMap<String, u64>>>,
+    interval_seconds: u64,
     schedule: Schedule,
+    cache: Option<Arc<RedisCache>>,
+    database: Option<Arc<QuestDatabase>>,
+    metrics: Option<Arc<Metrics>>,
+    /// Seconds between polls of `database` for strategy changes (0 = disabled)
+    strategy_refresh_interval_seconds: u64,
     handle: Arc<RwLock<Option<tokio::task::JoinHandle<()>>>>,
+    refresh_handle: Arc<RwLock<Option<tokio::task::JoinHandle<()>>>>,
 }
 
 impl JobScheduler {
@@ -60,23 +175,271 @@ impl JobScheduler {
             cron_expr
         );
 
+        let symbol_intervals = symbols
+            .iter()
+            .map(|symbol| (symbol.clone(), interval_seconds))
+            .collect();
+
         Ok(Self {
             storage,
-            symbols,
+            priority_storage: None,
+            symbols: Arc::new(RwLock::new(symbols)),
+            symbol_intervals: Arc::new(RwLock::new(symbol_intervals)),
+            interval_seconds,
             schedule,
+            cache: None,
+            database: None,
+            metrics: None,
+            strategy_refresh_interval_seconds: config::get_strategy_refresh_interval_secs(),
             handle: Arc::new(RwLock::new(None)),
+            refresh_handle: Arc::new(RwLock::new(None)),
         })
     }
 
+    /// Persist the symbol list to Redis so it survives scheduler restarts
+    pub fn with_cache(mut self, cache: Arc<RedisCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Push `High` priority jobs to `storage` instead of the default queue.
+    ///
+    /// Priorities are decided from the latest signal in the cache, so this
+    /// has no effect without [`Self::with_cache`].
+    pub fn with_priority_storage(mut self, storage: Arc<RedisStorage<FetchCandlesJob>>) -> Self {
+        self.priority_storage = Some(storage);
+        self
+    }
+
+    /// Skip symbols whose strategies in `database` are all disabled
+    pub fn with_database(mut self, database: Arc<QuestDatabase>) -> Self {
+        self.database = Some(database);
+        self
+    }
+
+    /// Poll the database for strategy changes every `interval_seconds`
+    /// instead of `STRATEGY_REFRESH_INTERVAL_SECS` (0 = disabled)
+    pub fn with_strategy_refresh_interval(mut self, interval_seconds: u64) -> Self {
+        self.strategy_refresh_interval_seconds = interval_seconds;
+        self
+    }
+
+    /// Record recovered missed ticks and strategy counts in `metrics`
+    pub fn with_metrics(mut self, metrics: Arc<Metrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Add a symbol to the schedule, or update its interval if already present
+    ///
+    /// The symbol is picked up on the next cron tick. Intervals shorter than
+    /// the scheduler interval are evaluated on every tick.
+    pub async fn add_symbol(&self, symbol: String, interval_seconds: u64) {
+        {
+            let mut symbols = self.symbols.write().await;
+            if !symbols.contains(&symbol) {
+                symbols.push(symbol.clone());
+            }
+        }
+        self.symbol_intervals
+            .write()
+            .await
+            .insert(symbol.clone(), interval_seconds);
+
+        info!(
+            symbol = %symbol,
+            interval = interval_seconds,
+            "JobScheduler: added symbol {} (interval {}s)",
+            symbol,
+            interval_seconds
+        );
+        self.persist_symbols().await;
+    }
+
+    /// Remove a symbol from the schedule
+    pub async fn remove_symbol(&self, symbol: &str) {
+        self.symbols.write().await.retain(|s| s != symbol);
+        self.symbol_intervals.write().await.remove(symbol);
+
+        info!(symbol = %symbol, "JobScheduler: removed symbol {}", symbol);
+        self.persist_symbols().await;
+    }
+
+    /// List the currently scheduled symbols
+    pub async fn list_symbols(&self) -> Vec<String> {
+        self.symbols.read().await.clone()
+    }
+
+    async fn persist_symbols(&self) {
+        persist_symbols(self.cache.as_deref(), &self.symbol_intervals).await;
+    }
+
+    /// Merge symbols persisted by a previous run into the schedule
+    async fn restore_symbols(&self) {
+        let Some(ref cache) = self.cache else {
+            return;
+        };
+
+        match cache
+            .get_json::<HashMap<String, u64>>(SCHEDULER_SYMBOLS_KEY)
+            .await
+        {
+            Ok(Some(persisted)) => {
+                let mut symbols = self.symbols.write().await;
+                let mut intervals = self.symbol_intervals.write().await;
+                for (symbol, interval) in persisted {
+                    if !symbols.contains(&symbol) {
+                        symbols.push(symbol.clone());
+                    }
+                    intervals.entry(symbol).or_insert(interval);
+                }
+                info!(
+                    symbol_count = symbols.len(),
+                    "JobScheduler: restored persisted symbols"
+                );
+            }
+            Ok(None) => {}
+            Err(e) => {
+                warn!(error = %e, "JobScheduler: failed to restore persisted symbols");
+            }
+        }
+    }
+
+    /// Most recent tick recorded for any scheduled symbol by a previous run
+    async fn last_processed_tick(&self) -> Option<DateTime<Utc>> {
+        let cache = self.cache.as_ref()?;
+        let mut last_tick = None;
+        for symbol in self.symbols.read().await.iter() {
+            match cache
+                .get_json::<DateTime<Utc>>(&scheduler_last_tick_key(symbol))
+                .await
+            {
+                Ok(Some(tick)) => last_tick = last_tick.max(Some(tick)),
+                Ok(None) => {}
+                Err(e) => {
+                    warn!(symbol = %symbol, error = %e, "JobScheduler: failed to read last tick for {}", symbol);
+                }
+            }
+        }
+        last_tick
+    }
+
+    /// Enqueue a `FetchCandlesJob` for `symbol` unless one was already
+    /// enqueued within its evaluation interval.
+    ///
+    /// The `lock:fetch:{symbol}` key is claimed with `SET NX EX` before
+    /// pushing, so jobs piling up after a restart or from overlapping
+    /// schedulers are skipped. Without a cache every call enqueues.
+    pub async fn enqueue_deduplicated(&self, symbol: &str) -> EnqueueOutcome {
+        self.enqueue_deduplicated_with_priority(symbol, JobPriority::Normal)
+            .await
+    }
+
+    async fn enqueue_deduplicated_with_priority(
+        &self,
+        symbol: &str,
+        base_priority: JobPriority,
+    ) -> EnqueueOutcome {
+        let interval = self
+            .symbol_intervals
+            .read()
+            .await
+            .get(symbol)
+            .copied()
+            .unwrap_or(self.interval_seconds);
+        enqueue_deduplicated_fetch_job(
+            &self.storage,
+            self.priority_storage.as_deref(),
+            self.cache.as_deref(),
+            self.metrics.as_deref(),
+            symbol,
+            interval,
+            base_priority,
+        )
+        .await
+    }
+
+    /// Enqueue one catch-up job per symbol if any ticks were missed since `since`
+    ///
+    /// Only the latest candles matter, so a single `FetchCandlesJob` covers all
+    /// missed ticks for a symbol. Catch-up jobs are `Low` priority unless the
+    /// symbol's last signal was confident. Returns the symbols that were
+    /// enqueued.
+    pub async fn recover_missed_ticks(&self, since: DateTime<Utc>) -> Vec<String> {
+        let now = Utc::now();
+        let missed = self
+            .schedule
+            .after(&since)
+            .take_while(|tick| *tick <= now)
+            .count();
+        if missed == 0 {
+            return Vec::new();
+        }
+
+        let disabled_symbols =
+            strategy_availability(self.database.as_deref(), self.metrics.as_deref())
+                .await
+                .disabled_symbols;
+        let mut symbols = self.symbols.read().await.clone();
+        symbols.retain(|symbol| !disabled_symbols.contains(symbol));
+        info!(
+            missed_ticks = missed,
+            since = %since,
+            symbol_count = symbols.len(),
+            "JobScheduler: {} ticks missed since {}, enqueuing catch-up jobs",
+            missed,
+            since
+        );
+
+        let mut recovered = Vec::new();
+        for symbol in symbols {
+            if self
+                .enqueue_deduplicated_with_priority(&symbol, JobPriority::Low)
+                .await
+                != EnqueueOutcome::Failed
+            {
+                recovered.push(symbol);
+            }
+        }
+
+        if let Some(ref metrics) = self.metrics {
+            if !recovered.is_empty() {
+                metrics
+                    .scheduler_missed_ticks_recovered_total
+                    .inc_by(missed as f64);
+            }
+        }
+
+        recovered
+    }
+
     /// Start the scheduler
     pub async fn start(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.restore_symbols().await;
+        self.persist_symbols().await;
+
+        let recovered = match self.last_processed_tick().await {
+            Some(since) => self.recover_missed_ticks(since).await,
+            None => Vec::new(),
+        };
+
         let storage = self.storage.clone();
+        let priority_storage = self.priority_storage.clone();
+        let cache = self.cache.clone();
+        let database = self.database.clone();
+        let metrics = self.metrics.clone();
         let symbols = self.symbols.clone();
+        let symbol_intervals = self.symbol_intervals.clone();
+        let default_interval = self.interval_seconds;
         let schedule = self.schedule.clone();
         let handle_arc = self.handle.clone();
 
         let handle = tokio::spawn(async move {
             info!("JobScheduler: started, waiting for cron schedule...");
+            let mut last_enqueued: HashMap<String, Instant> = recovered
+                .into_iter()
+                .map(|symbol| (symbol, Instant::now()))
+                .collect();
 
             loop {
                 // Get the next scheduled time
@@ -93,6 +456,15 @@ impl JobScheduler {
                     continue;
                 }
 
+                // Read the current list on each tick so add/remove take effect live
+                let symbols = symbols.read().await.clone();
+                let intervals = symbol_intervals.read().await.clone();
+                last_enqueued.retain(|symbol, _| symbols.contains(symbol));
+                let disabled_symbols =
+                    strategy_availability(database.as_deref(), metrics.as_deref())
+                        .await
+                        .disabled_symbols;
+
                 info!(
                     symbol_count = symbols.len(),
                     "JobScheduler: cron tick, enqueuing FetchCandlesJob for {} symbols",
@@ -100,24 +472,34 @@ impl JobScheduler {
                 );
 
                 for symbol in &symbols {
-                    let job = FetchCandlesJob {
-                        symbol: symbol.clone(),
-                    };
-
-                    let mut storage_clone = (*storage).clone();
-                    match storage_clone.push(job).await {
-                        Ok(_) => {
-                            debug!(symbol = %symbol, "JobScheduler: enqueued FetchCandlesJob for {}", symbol);
-                        }
-                        Err(e) => {
-                            error!(
-                                symbol = %symbol,
-                                error = %e,
-                                "JobScheduler: failed to enqueue FetchCandlesJob for {}",
-                                symbol
-                            );
+                    if disabled_symbols.contains(symbol) {
+                        debug!(symbol = %symbol, "JobScheduler: all strategies for {} are disabled, skipping", symbol);
+                        continue;
+                    }
+                    let interval = intervals.get(symbol).copied().unwrap_or(default_interval);
+                    if let Some(last) = last_enqueued.get(symbol) {
+                        // Allow a little slack so a tick landing just early isn't skipped
+                        let due = Duration::from_secs(interval)
+                            .saturating_sub(Duration::from_millis(500));
+                        if last.elapsed() < due {
+                            debug!(symbol = %symbol, "JobScheduler: {} not due yet, skipping", symbol);
+                            continue;
                         }
                     }
+
+                    let outcome = enqueue_deduplicated_fetch_job(
+                        &storage,
+                        priority_storage.as_deref(),
+                        cache.as_deref(),
+                        metrics.as_deref(),
+                        symbol,
+                        interval,
+                        JobPriority::Normal,
+                    )
+                    .await;
+                    if outcome != EnqueueOutcome::Failed {
+                        last_enqueued.insert(symbol.clone(), Instant::now());
+                    }
                 }
             }
         });
@@ -127,6 +509,31 @@ impl JobScheduler {
             *h = Some(handle);
         }
 
+        if let (Some(database), true) = (
+            self.database.clone(),
+            self.strategy_refresh_interval_seconds > 0,
+        ) {
+            let mut refresh = StrategyRefresh {
+                database,
+                cache: self.cache.clone(),
+                metrics: self.metrics.clone(),
+                symbols: self.symbols.clone(),
+                symbol_intervals: self.symbol_intervals.clone(),
+                default_interval: self.interval_seconds,
+                last_hash: None,
+                discovered_symbols: HashSet::new(),
+            };
+            let refresh_interval = Duration::from_secs(self.strategy_refresh_interval_seconds);
+            let refresh_handle = tokio::spawn(async move {
+                let mut interval = tokio::time::interval(refresh_interval);
+                loop {
+                    interval.tick().await;
+                    refresh.poll().await;
+                }
+            });
+            *self.refresh_handle.write().await = Some(refresh_handle);
+        }
+
         info!("JobScheduler: started successfully");
         Ok(())
     }
@@ -138,6 +545,9 @@ impl JobScheduler {
             h.abort();
             info!("JobScheduler: stopped");
         }
+        if let Some(h) = self.refresh_handle.write().await.take() {
+            h.abort();
+        }
     }
 
     /// Check if the scheduler is running
@@ -146,3 +556,271 @@ impl JobScheduler {
         handle.is_some()
     }
 }
+
+/// Background poll keeping the schedule in line with the strategies in
+/// QuestDB.
+///
+/// Symbols gaining a strategy are scheduled at the default interval, and
+/// symbols it scheduled are dropped again once their last strategy is
+/// deleted. Symbols scheduled at startup or through
+/// [`JobScheduler::add_symbol`] are never removed.
+struct StrategyRefresh {
+    database: Arc<QuestDatabase>,
+    cache: Option<Arc<RedisCache>>,
+    metrics: Option<Arc<Metrics>>,
+    symbols: Arc<RwLock<Vec<String>>>,
+    symbol_intervals: Arc<RwLock<HashMap<String, u64>>>,
+    default_interval: u64,
+    /// Hash of the strategies loaded by the last successful poll
+    last_hash: Option<u64>,
+    /// Symbols scheduled because a strategy was found for them
+    discovered_symbols: HashSet<String>,
+}
+
+impl StrategyRefresh {
+    async fn poll(&mut self) {
+        // An unavailable database reads as having no strategies, which would
+        // unschedule every discovered symbol
+        let loaded = if self.database.is_available().await {
+            self.database.get_strategies(None).await
+        } else {
+            Err(KryptexError::Io(std::io::Error::new(
+                std::io::ErrorKind::NotConnected,
+                "QuestDB unavailable",
+            )))
+        };
+        let strategies = match loaded {
+            Ok(strategies) => strategies,
+            Err(e) => {
+                warn!(error = %e, "JobScheduler: failed to refresh strategies");
+                if let Some(ref metrics) = self.metrics {
+                    metrics.strategy_refresh_errors_total.inc();
+                }
+                return;
+            }
+        };
+
+        let hash = strategies_hash(&strategies);
+        if self.last_hash == Some(hash) {
+            debug!("JobScheduler: strategies unchanged");
+            return;
+        }
+        self.last_hash = Some(hash);
+
+        if let Some(ref cache) = self.cache {
+            if let Err(e) = cache.set_json(ACTIVE_STRATEGIES_KEY, &strategies, 0).await {
+                warn!(error = %e, "JobScheduler: failed to cache active strategies");
+            }
+        }
+
+        let strategy_symbols: HashSet<String> = strategies
+            .iter()
+            .map(|strategy| strategy.symbol.clone())
+            .collect();
+        let (added, removed) = {
+            let mut symbols = self.symbols.write().await;
+            let mut intervals = self.symbol_intervals.write().await;
+
+            let added: Vec<String> = strategy_symbols
+                .iter()
+                .filter(|symbol| !symbols.contains(symbol))
+                .cloned()
+                .collect();
+            for symbol in &added {
+                symbols.push(symbol.clone());
+                intervals.insert(symbol.clone(), self.default_interval);
+                self.discovered_symbols.insert(symbol.clone());
+            }
+
+            let removed: Vec<String> = self
+                .discovered_symbols
+                .iter()
+                .filter(|symbol| !strategy_symbols.contains(*symbol))
+                .cloned()
+                .collect();
+            for symbol in &removed {
+                symbols.retain(|s| s != symbol);
+                intervals.remove(symbol);
+                self.discovered_symbols.remove(symbol);
+            }
+            (added, removed)
+        };
+
+        info!(
+            strategy_count = strategies.len(),
+            added = ?added,
+            removed = ?removed,
+            "JobScheduler: strategies changed, {} loaded",
+            strategies.len()
+        );
+        if !added.is_empty() || !removed.is_empty() {
+            persist_symbols(self.cache.as_deref(), &self.symbol_intervals).await;
+        }
+    }
+}
+
+/// Save the symbol -> interval map to Redis
+async fn persist_symbols(
+    cache: Option<&RedisCache>,
+    symbol_intervals: &RwLock<HashMap<String, u64>>,
+) {
+    if let Some(cache) = cache {
+        let intervals = symbol_intervals.read().await.clone();
+        if let Err(e) = cache.set_json(SCHEDULER_SYMBOLS_KEY, &intervals, 0).await {
+            warn!(error = %e, "JobScheduler: failed to persist symbol list");
+        }
+    }
+}
+
+/// Count enabled and disabled strategies in `database`, recording the counts
+/// in `metrics`.
+///
+/// Without a database, or when it cannot be read, nothing is disabled so
+/// every symbol stays scheduled.
+async fn strategy_availability(
+    database: Option<&QuestDatabase>,
+    metrics: Option<&Metrics>,
+) -> StrategyAvailability {
+    let Some(database) = database else {
+        return StrategyAvailability::default();
+    };
+    // An unavailable database reads as having no strategies
+    if !database.is_available().await {
+        return StrategyAvailability::default();
+    }
+    let strategies = match database.get_strategies(None).await {
+        Ok(strategies) => strategies,
+        Err(e) => {
+            warn!(error = %e, "JobScheduler: failed to load strategies, scheduling all symbols");
+            return StrategyAvailability::default();
+        }
+    };
+
+    let availability = StrategyAvailability::from_strategies(&strategies);
+    if let Some(metrics) = metrics {
+        metrics
+            .strategies_enabled_total
+            .set(availability.enabled as f64);
+        metrics
+            .strategies_disabled_total
+            .set(availability.disabled as f64);
+    }
+    availability
+}
+
+/// See [`JobScheduler::enqueue_deduplicated`]. If the lock cannot be
+/// claimed because Redis errors, the job is enqueued anyway. If the push
+/// fails the lock is released so the next tick can retry.
+async fn enqueue_deduplicated_fetch_job(
+    storage: &RedisStorage<FetchCandlesJob>,
+    priority_storage: Option<&RedisStorage<FetchCandlesJob>>,
+    cache: Option<&RedisCache>,
+    metrics: Option<&Metrics>,
+    symbol: &str,
+    interval_seconds: u64,
+    base_priority: JobPriority,
+) -> EnqueueOutcome {
+    let mut lock_claimed = false;
+    if let Some(cache) = cache {
+        match cache
+            .set_nx_ex(
+                &fetch_lock_key(symbol),
+                &Utc::now().to_rfc3339(),
+                fetch_lock_ttl(interval_seconds),
+            )
+            .await
+        {
+            Ok(true) => lock_claimed = true,
+            Ok(false) => {
+                debug!(
+                    symbol = %symbol,
+                    "JobScheduler: FetchCandlesJob for {} already enqueued this interval, skipping",
+                    symbol
+                );
+                if let Some(metrics) = metrics {
+                    metrics.jobs_deduplicated_total.inc();
+                }
+                return EnqueueOutcome::Deduplicated;
+            }
+            Err(e) => {
+                warn!(symbol = %symbol, error = %e, "JobScheduler: failed to claim fetch lock for {}, enqueuing anyway", symbol);
+            }
+        }
+    }
+
+    let priority = job_priority(
+        previous_signal(cache, symbol).await.as_ref(),
+        base_priority,
+    );
+    let storage = match priority_storage {
+        Some(priority_storage) if priority == JobPriority::High => priority_storage,
+        _ => storage,
+    };
+    let job = FetchCandlesJob::new(symbol).with_priority(priority);
+    if enqueue_fetch_job(storage, cache, job).await {
+        if let Some(metrics) = metrics {
+            metrics
+                .jobs_by_priority_total
+                .with_label_values(&[priority.as_str()])
+                .inc();
+        }
+        EnqueueOutcome::Enqueued
+    } else {
+        if let Some(cache) = cache.filter(|_| lock_claimed) {
+            if let Err(e) = cache.delete(&fetch_lock_key(symbol)).await {
+                warn!(symbol = %symbol, error = %e, "JobScheduler: failed to release fetch lock for {}", symbol);
+            }
+        }
+        EnqueueOutcome::Failed
+    }
+}
+
+/// Latest signal cached for `symbol`, if it has not expired
+async fn previous_signal(cache: Option<&RedisCache>, symbol: &str) -> Option<SignalOutput> {
+    match cache?.get_json(&latest_signal_key(symbol)).await {
+        Ok(signal) => signal,
+        Err(e) => {
+            warn!(symbol = %symbol, error = %e, "JobScheduler: failed to read latest signal for {}", symbol);
+            None
+        }
+    }
+}
+
+/// Enqueue a `FetchCandlesJob` and record the tick in Redis
+///
+/// Returns whether the job was enqueued.
+async fn enqueue_fetch_job(
+    storage: &RedisStorage<FetchCandlesJob>,
+    cache: Option<&RedisCache>,
+    job: FetchCandlesJob,
+) -> bool {
+    let symbol = job.symbol.clone();
+    let priority = job.priority;
+    let mut storage = storage.clone();
+    if let Err(e) = storage.push(job).await {
+        error!(
+            symbol = %symbol,
+            error = %e,
+            "JobScheduler: failed to enqueue FetchCandlesJob for {}",
+            symbol
+        );
+        return false;
+    }
+    debug!(
+        symbol = %symbol,
+        priority = priority.as_str(),
+        "JobScheduler: enqueued {} priority FetchCandlesJob for {}",
+        priority.as_str(),
+        symbol
+    );
+
+    if let Some(cache) = cache {
+        if let Err(e) = cache
+            .set_json(&scheduler_last_tick_key(&symbol), &Utc::now(), 0)
+            .await
+        {
+            warn!(symbol = %symbol, error = %e, "JobScheduler: failed to record last tick for {}", symbol);
+        }
+    }
+    true
+}
@@ -0,0 +1,208 @@
+//! Versioned QuestDB schema migrations
+
+use crate::db::questdb::QuestDatabase;
+use crate::error::KryptexError;
+use chrono::Utc;
+use std::collections::HashSet;
+use tracing::{error, info};
+
+/// A numbered schema change, applied at most once per database
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Migration {
+    pub version: i32,
+    pub description: &'static str,
+    pub sql: &'static str,
+}
+
+/// All migrations, in the order they are applied.
+///
+/// Append new migrations with the next version; never edit or renumber an
+/// applied one. Each statement must be safe to run twice (`IF NOT EXISTS`),
+/// since services starting together may race to apply the same version.
+pub fn migrations() -> Vec<Migration> {
+    vec![
+        Migration {
+            version: 1,
+            description: "Create candles table",
+            sql: "CREATE TABLE IF NOT EXISTS candles (
+                    timestamp TIMESTAMP,
+                    symbol SYMBOL,
+                    interval SYMBOL,
+                    open DOUBLE,
+                    high DOUBLE,
+                    low DOUBLE,
+                    close DOUBLE,
+                    volume DOUBLE,
+                    open_interest DOUBLE,
+                    funding_rate DOUBLE
+                ) TIMESTAMP(timestamp) PARTITION BY DAY",
+        },
+        Migration {
+            version: 2,
+            description: "Create strategies table",
+            sql: "CREATE TABLE IF NOT EXISTS strategies (
+                    id LONG,
+                    name STRING,
+                    symbol SYMBOL,
+                    created_at TIMESTAMP,
+                    updated_at TIMESTAMP,
+                    config_json STRING
+                )",
+        },
+        Migration {
+            version: 3,
+            description: "Create signals table",
+            sql: "CREATE TABLE IF NOT EXISTS signals (
+                    timestamp TIMESTAMP,
+                    id LONG,
+                    symbol SYMBOL,
+                    strategy_id LONG,
+                    direction SYMBOL,
+                    confidence DOUBLE,
+                    sl_pct DOUBLE,
+                    tp_pct DOUBLE,
+                    price DOUBLE,
+                    reasons_json STRING
+                ) TIMESTAMP(timestamp) PARTITION BY DAY",
+        },
+        Migration {
+            version: 4,
+            description: "Add strategy_name to signals",
+            sql: "ALTER TABLE signals ADD COLUMN IF NOT EXISTS strategy_name STRING",
+        },
+        Migration {
+            version: 5,
+            description: "Add interval to signals",
+            sql: "ALTER TABLE signals ADD COLUMN IF NOT EXISTS interval SYMBOL",
+        },
+        Migration {
+            version: 6,
+            description: "Create dead_letters table for failed jobs",
+            sql: "CREATE TABLE IF NOT EXISTS dead_letters (
+                    timestamp TIMESTAMP,
+                    job_type SYMBOL,
+                    symbol SYMBOL,
+                    strategy_id LONG,
+                    error_message STRING,
+                    retry_count INT
+                ) TIMESTAMP(timestamp) PARTITION BY DAY",
+        },
+        Migration {
+            version: 7,
+            description: "Add id to dead_letters",
+            sql: "ALTER TABLE dead_letters ADD COLUMN IF NOT EXISTS id LONG",
+        },
+        Migration {
+            version: 8,
+            description: "Add payload_json to dead_letters",
+            sql: "ALTER TABLE dead_letters ADD COLUMN IF NOT EXISTS payload_json STRING",
+        },
+        Migration {
+            version: 9,
+            description: "Add replayed_at to dead_letters",
+            sql: "ALTER TABLE dead_letters ADD COLUMN IF NOT EXISTS replayed_at TIMESTAMP",
+        },
+        Migration {
+            version: 10,
+            // Stored inverted: existing rows read a new BOOLEAN column as
+            // false, which leaves them enabled
+            description: "Add disabled to strategies",
+            sql: "ALTER TABLE strategies ADD COLUMN IF NOT EXISTS disabled BOOLEAN",
+        },
+        Migration {
+            version: 11,
+            description: "Create audit_logs table for strategy changes",
+            sql: "CREATE TABLE IF NOT EXISTS audit_logs (
+                    timestamp TIMESTAMP,
+                    operation SYMBOL,
+                    strategy_id LONG,
+                    strategy_name STRING,
+                    operator_key SYMBOL,
+                    request_json STRING
+                ) TIMESTAMP(timestamp) PARTITION BY DAY",
+        },
+        Migration {
+            version: 12,
+            description: "Add source_file to strategies",
+            sql: "ALTER TABLE strategies ADD COLUMN IF NOT EXISTS source_file STRING",
+        },
+    ]
+}
+
+/// Brings a database up to the latest schema, recording each applied
+/// version in the `migrations` table
+pub struct MigrationRunner;
+
+impl MigrationRunner {
+    /// Apply every migration not yet recorded, in version order; returns how
+    /// many were applied. Stops at the first failure, leaving it unrecorded
+    /// so the next start retries it.
+    pub async fn run(db: &QuestDatabase) -> Result<usize, KryptexError> {
+        let client = db.pool().get().await;
+        let Some(ref c) = *client else {
+            return Ok(0);
+        };
+
+        c.execute(
+            "CREATE TABLE IF NOT EXISTS migrations (
+                version INT,
+                applied_at TIMESTAMP,
+                description STRING
+            )",
+            &[],
+        )
+        .await?;
+
+        let applied = Self::applied_versions(db).await?;
+        let all = migrations();
+        let pending = Self::pending(&all, &applied);
+        for migration in &pending {
+            c.execute(migration.sql, &[]).await.inspect_err(|e| {
+                error!(
+                    version = migration.version,
+                    error = %e,
+                    "QuestDB migration {} ({}) failed",
+                    migration.version,
+                    migration.description
+                );
+            })?;
+
+            let applied_at = Utc::now().naive_utc();
+            c.execute(
+                "INSERT INTO migrations (version, applied_at, description) VALUES ($1, $2, $3)",
+                &[&migration.version, &applied_at, &migration.description],
+            )
+            .await?;
+            info!(
+                version = migration.version,
+                description = migration.description,
+                "Applied QuestDB migration {}: {}",
+                migration.version,
+                migration.description
+            );
+        }
+
+        Ok(pending.len())
+    }
+
+    /// Versions recorded in the `migrations` table
+    pub async fn applied_versions(db: &QuestDatabase) -> Result<HashSet<i32>, KryptexError> {
+        let client = db.pool().get().await;
+        let Some(ref c) = *client else {
+            return Ok(HashSet::new());
+        };
+
+        let rows = c.query("SELECT version FROM migrations", &[]).await?;
+        Ok(rows.iter().map(|row| row.get(0)).collect())
+    }
+
+    /// Migrations whose version is not in `applied`, lowest version first
+    pub fn pending<'a>(migrations: &'a [Migration], applied: &HashSet<i32>) -> Vec<&'a Migration> {
+        let mut pending: Vec<_> = migrations
+            .iter()
+            .filter(|migration| !applied.contains(&migration.version))
+            .collect();
+        pending.sort_by_key(|migration| migration.version);
+        pending
+    }
+}
@@ -1,5 +1,9 @@
+pub mod migrations;
+pub mod pool;
 pub mod questdb;
 
+pub use migrations::{Migration, MigrationRunner};
+pub use pool::QuestDbPool;
 pub use questdb::QuestDatabase;
 
 // Type alias for backward compatibility
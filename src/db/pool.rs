@@ -0,0 +1,321 @@
+//! Fixed-size pool of QuestDB connections
+
+use crate::error::KryptexError;
+use crate::metrics::Metrics;
+use std::ops::Deref;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::sync::{RwLock, RwLockReadGuard};
+use tokio::time::{Duration, Instant};
+use tokio_postgres::{Client, NoTls};
+use tracing::{error, info, warn};
+
+/// Delay before the second connection attempt; doubles on each retry
+const CONNECT_INITIAL_DELAY: Duration = Duration::from_millis(250);
+/// Upper bound on the delay between connection attempts
+const CONNECT_MAX_DELAY: Duration = Duration::from_secs(5);
+
+struct Slot {
+    client: Arc<RwLock<Option<Client>>>,
+    /// Generation of the live connection, or 0 when disconnected. Cleared by
+    /// the connection task when its connection ends, unless it was replaced.
+    live: Arc<AtomicUsize>,
+    generations: AtomicUsize,
+    in_flight: AtomicUsize,
+}
+
+impl Slot {
+    fn empty() -> Self {
+        Self {
+            client: Arc::new(RwLock::new(None)),
+            live: Arc::new(AtomicUsize::new(0)),
+            generations: AtomicUsize::new(0),
+            in_flight: AtomicUsize::new(0),
+        }
+    }
+
+    fn is_connected(&self) -> bool {
+        self.live.load(Ordering::Relaxed) != 0
+    }
+
+    /// Open a connection into this slot, replacing any broken one
+    async fn connect(&self, url: &str) -> Result<(), tokio_postgres::Error> {
+        let (client, connection) = tokio_postgres::connect(url, NoTls).await?;
+        let generation = self.generations.fetch_add(1, Ordering::Relaxed) + 1;
+        let live = self.live.clone();
+        tokio::spawn(async move {
+            if let Err(e) = connection.await {
+                error!(error = %e, "QuestDB connection error");
+            }
+            let _ = live.compare_exchange(generation, 0, Ordering::Relaxed, Ordering::Relaxed);
+        });
+
+        *self.client.write().await = Some(client);
+        self.live.store(generation, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Whether the connection answers a trivial query
+    async fn ping(&self) -> bool {
+        let client = self.client.read().await;
+        match *client {
+            Some(ref c) if !c.is_closed() => c.simple_query("SELECT 1").await.is_ok(),
+            _ => false,
+        }
+    }
+}
+
+/// Connection usage at a point in time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolStats {
+    /// Connected with at least one query in flight
+    pub active: usize,
+    /// Connected with nothing in flight
+    pub idle: usize,
+    /// Not connected
+    pub broken: usize,
+}
+
+/// QuestDB connections shared by every query.
+///
+/// `tokio_postgres` clients pipeline concurrent queries, so a connection is
+/// never held exclusively; [`Self::get`] hands out the connected slot with the
+/// fewest queries in flight. Checkouts beyond the pool size share connections
+/// rather than waiting. Broken connections are skipped until
+/// [`Self::is_healthy`] reconnects them.
+pub struct QuestDbPool {
+    url: String,
+    slots: Vec<Slot>,
+    metrics: Option<Arc<Metrics>>,
+}
+
+impl QuestDbPool {
+    /// Open `size` connections to `url`.
+    ///
+    /// Failed connections are retried with exponential backoff until
+    /// `connect_timeout` has elapsed. The pool is returned as long as at least
+    /// one connection succeeded; the rest stay broken until a health check.
+    pub async fn connect(
+        url: impl Into<String>,
+        size: usize,
+        connect_timeout: Duration,
+    ) -> Result<Self, KryptexError> {
+        let pool = Self {
+            url: url.into(),
+            slots: (0..size.max(1)).map(|_| Slot::empty()).collect(),
+            metrics: None,
+        };
+
+        let deadline = Instant::now() + connect_timeout;
+        let mut delay = CONNECT_INITIAL_DELAY;
+        let mut attempts = 0u32;
+        loop {
+            attempts += 1;
+            let mut last_error = None;
+            for slot in pool.slots.iter().filter(|slot| !slot.is_connected()) {
+                if let Err(e) = slot.connect(&pool.url).await {
+                    last_error = Some(e);
+                }
+            }
+
+            let Some(e) = last_error else {
+                info!(size = pool.slots.len(), attempts, "QuestDB pool connected");
+                return Ok(pool);
+            };
+
+            let now = Instant::now();
+            if now >= deadline {
+                let stats = pool.stats();
+                if stats.broken < pool.slots.len() {
+                    warn!(
+                        connected = pool.slots.len() - stats.broken,
+                        size = pool.slots.len(),
+                        error = %e,
+                        "QuestDB pool partially connected"
+                    );
+                    return Ok(pool);
+                }
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::ConnectionRefused,
+                    format!(
+                        "Failed to connect to QuestDB after {} attempts: {}",
+                        attempts, e
+                    ),
+                )
+                .into());
+            }
+
+            warn!(
+                error = %e,
+                attempt = attempts,
+                retry_in_ms = delay.as_millis() as u64,
+                "QuestDB connection failed, retrying"
+            );
+            tokio::time::sleep(delay.min(deadline - now)).await;
+            delay = (delay * 2).min(CONNECT_MAX_DELAY);
+        }
+    }
+
+    /// Report active and idle connections to `metrics`
+    pub fn with_metrics(mut self, metrics: Arc<Metrics>) -> Self {
+        self.metrics = Some(metrics);
+        self.record_metrics();
+        self
+    }
+
+    pub fn size(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Check out the least-loaded connection.
+    ///
+    /// When no connection is up the guard holds `None` (or a closed client),
+    /// so callers see the same "no database" state as before pooling.
+    pub async fn get(&self) -> PooledClient<'_> {
+        let connected: Vec<bool> = self.slots.iter().map(Slot::is_connected).collect();
+        let in_flight: Vec<usize> = self
+            .slots
+            .iter()
+            .map(|slot| slot.in_flight.load(Ordering::Relaxed))
+            .collect();
+        let index = least_loaded(&connected, &in_flight).unwrap_or(0);
+
+        let slot = &self.slots[index];
+        slot.in_flight.fetch_add(1, Ordering::Relaxed);
+        self.record_metrics();
+        PooledClient {
+            guard: slot.client.read().await,
+            pool: self,
+            index,
+        }
+    }
+
+    /// Ping every connection and reconnect the ones that do not answer.
+    ///
+    /// Returns `true` when every connection is usable afterwards.
+    pub async fn is_healthy(&self) -> bool {
+        let mut healthy = true;
+        for (index, slot) in self.slots.iter().enumerate() {
+            if slot.ping().await {
+                continue;
+            }
+            slot.live.store(0, Ordering::Relaxed);
+            match slot.connect(&self.url).await {
+                Ok(()) => info!(slot = index, "Reconnected pooled QuestDB connection"),
+                Err(e) => {
+                    warn!(slot = index, error = %e, "Failed to reconnect pooled QuestDB connection");
+                    healthy = false;
+                }
+            }
+        }
+        self.record_metrics();
+        healthy
+    }
+
+    /// Whether any connection is up
+    pub fn is_available(&self) -> bool {
+        self.slots.iter().any(Slot::is_connected)
+    }
+
+    pub fn stats(&self) -> PoolStats {
+        let mut stats = PoolStats {
+            active: 0,
+            idle: 0,
+            broken: 0,
+        };
+        for slot in &self.slots {
+            if !slot.is_connected() {
+                stats.broken += 1;
+            } else if slot.in_flight.load(Ordering::Relaxed) > 0 {
+                stats.active += 1;
+            } else {
+                stats.idle += 1;
+            }
+        }
+        stats
+    }
+
+    fn record_metrics(&self) {
+        if let Some(ref metrics) = self.metrics {
+            let stats = self.stats();
+            metrics
+                .database_pool_active_connections
+                .set(stats.active as f64);
+            metrics
+                .database_pool_idle_connections
+                .set(stats.idle as f64);
+        }
+    }
+}
+
+/// Index of the connected slot with the fewest queries in flight, lowest
+/// index first on ties; `None` when nothing is connected
+pub(crate) fn least_loaded(connected: &[bool], in_flight: &[usize]) -> Option<usize> {
+    connected
+        .iter()
+        .zip(in_flight)
+        .enumerate()
+        .filter(|(_, (&connected, _))| connected)
+        .min_by_key(|&(index, (_, &load))| (load, index))
+        .map(|(index, _)| index)
+}
+
+/// A checked-out connection; returned to the pool when dropped
+pub struct PooledClient<'a> {
+    guard: RwLockReadGuard<'a, Option<Client>>,
+    pool: &'a QuestDbPool,
+    index: usize,
+}
+
+impl Deref for PooledClient<'_> {
+    type Target = Option<Client>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.guard
+    }
+}
+
+impl Drop for PooledClient<'_> {
+    fn drop(&mut self) {
+        self.pool.slots[self.index]
+            .in_flight
+            .fetch_sub(1, Ordering::Relaxed);
+        self.pool.record_metrics();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checkout_prefers_least_loaded_connection() {
+        let connected = [true; 4];
+        assert_eq!(least_loaded(&connected, &[0, 0, 0, 0]), Some(0));
+        assert_eq!(least_loaded(&connected, &[2, 1, 0, 1]), Some(2));
+
+        // More checkouts than connections keep spreading evenly instead of waiting
+        let mut in_flight = [0; 4];
+        for _ in 0..10 {
+            let index = least_loaded(&connected, &in_flight).unwrap();
+            in_flight[index] += 1;
+        }
+        assert_eq!(in_flight, [3, 3, 2, 2]);
+    }
+
+    #[test]
+    fn checkout_skips_broken_connections() {
+        let connected = [false, true, false, true];
+        assert_eq!(least_loaded(&connected, &[0, 5, 0, 7]), Some(1));
+
+        let mut in_flight = [0; 4];
+        for _ in 0..6 {
+            let index = least_loaded(&connected, &in_flight).unwrap();
+            in_flight[index] += 1;
+        }
+        assert_eq!(in_flight, [0, 3, 0, 3]);
+
+        assert_eq!(least_loaded(&[false, false], &[0, 0]), None);
+        assert_eq!(least_loaded(&[], &[]), None);
+    }
+}
@@ -1,133 +1,86 @@
 //! QuestDB database operations for candles and signals
 
 use crate::config;
+use crate::db::migrations::MigrationRunner;
+use crate::db::pool::QuestDbPool;
+use crate::error::KryptexError;
+use crate::metrics::Metrics;
+use crate::models::audit_log::AuditLogEntry;
+use crate::models::dead_letter::{DeadLetter, DeadLetterStats};
 use crate::models::indicators::Candle;
-use crate::models::signal::{SignalDirection, SignalOutput};
-use crate::models::strategy::Strategy;
+use crate::models::signal::{
+    ConfidencePercentiles, SignalDirection, SignalFilter, SignalGroupStats, SignalOutput,
+    SignalStats,
+};
+use crate::models::strategy::{DecayConfig, Strategy, StrategyStats};
 use chrono::{DateTime, Utc};
 use serde_json;
+use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
-use tokio_postgres::{Client, NoTls};
+use tokio::time::Duration;
+use tokio_postgres::types::ToSql;
+use tokio_postgres::Row;
 
 pub struct QuestDatabase {
-    client: Arc<RwLock<Option<Client>>>,
+    pool: QuestDbPool,
 }
 
 impl QuestDatabase {
-    pub async fn new() -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
-        let questdb_url = config::get_questdb_url();
-        let (client, connection) =
-            tokio_postgres::connect(&questdb_url, NoTls)
-                .await
-                .map_err(|e| {
-                    Box::new(std::io::Error::new(
-                        std::io::ErrorKind::ConnectionRefused,
-                        format!("Failed to connect to QuestDB: {}", e),
-                    )) as Box<dyn std::error::Error + Send + Sync>
-                })?;
-
-        // Spawn connection task
-        tokio::spawn(async move {
-            if let Err(e) = connection.await {
-                tracing::error!(error = %e, "QuestDB connection error");
-            }
-        });
+    /// Connect a pool of `QUESTDB_POOL_SIZE` connections, retrying for up to
+    /// `QUESTDB_CONNECT_TIMEOUT_SECS`, and apply pending schema migrations
+    pub async fn new() -> Result<Self, KryptexError> {
+        Self::connect(Duration::from_secs(
+            config::get_questdb_connect_timeout_secs(),
+        ))
+        .await
+    }
 
-        let db = Self {
-            client: Arc::new(RwLock::new(Some(client))),
-        };
+    /// Like [`Self::new`], retrying for up to `connect_timeout` instead; zero
+    /// makes a single attempt
+    pub async fn connect(connect_timeout: Duration) -> Result<Self, KryptexError> {
+        let pool = QuestDbPool::connect(
+            config::get_questdb_url(),
+            config::get_questdb_pool_size(),
+            connect_timeout,
+        )
+        .await?;
+
+        let db = Self { pool };
 
-        // Initialize schema
-        db.init_schema().await?;
+        MigrationRunner::run(&db).await?;
 
         Ok(db)
     }
 
-    async fn init_schema(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let client = self.client.read().await;
-        if let Some(ref c) = *client {
-            // Create candles table (time-series optimized)
-            // QuestDB syntax: TIMESTAMP must be first, PARTITION BY comes after
-            c.execute(
-                "CREATE TABLE IF NOT EXISTS candles (
-                    timestamp TIMESTAMP,
-                    symbol SYMBOL,
-                    interval SYMBOL,
-                    open DOUBLE,
-                    high DOUBLE,
-                    low DOUBLE,
-                    close DOUBLE,
-                    volume DOUBLE,
-                    open_interest DOUBLE,
-                    funding_rate DOUBLE
-                ) TIMESTAMP(timestamp) PARTITION BY DAY",
-                &[],
-            )
-            .await
-            .map_err(|e| {
-                Box::new(std::io::Error::other(format!(
-                    "Failed to create candles table: {}",
-                    e
-                ))) as Box<dyn std::error::Error + Send + Sync>
-            })?;
-
-            // Create strategies table
-            c.execute(
-                "CREATE TABLE IF NOT EXISTS strategies (
-                    id LONG,
-                    name STRING,
-                    symbol SYMBOL,
-                    created_at TIMESTAMP,
-                    updated_at TIMESTAMP,
-                    config_json STRING
-                )",
-                &[],
-            )
-            .await
-            .map_err(|e| {
-                Box::new(std::io::Error::other(format!(
-                    "Failed to create strategies table: {}",
-                    e
-                ))) as Box<dyn std::error::Error + Send + Sync>
-            })?;
-
-            // Create signals table
-            c.execute(
-                "CREATE TABLE IF NOT EXISTS signals (
-                    timestamp TIMESTAMP,
-                    id LONG,
-                    symbol SYMBOL,
-                    strategy_id LONG,
-                    direction SYMBOL,
-                    confidence DOUBLE,
-                    sl_pct DOUBLE,
-                    tp_pct DOUBLE,
-                    price DOUBLE,
-                    reasons_json STRING
-                ) TIMESTAMP(timestamp) PARTITION BY DAY",
-                &[],
-            )
-            .await
-            .map_err(|e| {
-                Box::new(std::io::Error::other(format!(
-                    "Failed to create signals table: {}",
-                    e
-                ))) as Box<dyn std::error::Error + Send + Sync>
-            })?;
-        }
+    /// Report pool usage to `metrics`
+    pub fn with_metrics(mut self, metrics: Arc<Metrics>) -> Self {
+        self.pool = self.pool.with_metrics(metrics);
+        self
+    }
 
-        Ok(())
+    pub fn pool(&self) -> &QuestDbPool {
+        &self.pool
+    }
+
+    /// Ping every pooled connection, reconnecting broken ones; see
+    /// [`QuestDbPool::is_healthy`]
+    pub async fn is_healthy(&self) -> bool {
+        self.pool.is_healthy().await
     }
 
-    /// Store a candle in QuestDB
+    /// Store a candle in QuestDB; synthetic gap-fill candles are skipped
     pub async fn store_candle(
         &self,
         symbol: &str,
         interval: &str,
         candle: &Candle,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let client = self.client.read().await;
+    ) -> Result<(), KryptexError> {
+        // Interpolated candles only fill in-memory gaps
+        if candle.is_synthetic {
+            return Ok(());
+        }
+
+        let client = self.pool.get().await;
         if let Some(ref c) = *client {
             // QuestDB expects timestamps - use NaiveDateTime for compatibility
             let timestamp_naive = candle.timestamp.naive_utc();
@@ -148,11 +101,7 @@ impl QuestDatabase {
                     &candle.funding_rate.unwrap_or(0.0),
                 ],
             )
-            .await
-            .map_err(|e| {
-                Box::new(std::io::Error::other(format!("Failed to store candle: {}", e)))
-                    as Box<dyn std::error::Error + Send + Sync>
-            })?;
+            .await?;
         }
 
         Ok(())
@@ -164,7 +113,7 @@ impl QuestDatabase {
         symbol: &str,
         interval: &str,
         candles: &[Candle],
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    ) -> Result<(), KryptexError> {
         // For now, just store candles one by one
         // TODO: Optimize with batch insert when QuestDB supports it better
         for candle in candles {
@@ -181,8 +130,8 @@ impl QuestDatabase {
         symbol: &str,
         interval: &str,
         limit: Option<usize>,
-    ) -> Result<Vec<Candle>, Box<dyn std::error::Error + Send + Sync>> {
-        let client = self.client.read().await;
+    ) -> Result<Vec<Candle>, KryptexError> {
+        let client = self.pool.get().await;
         if let Some(ref c) = *client {
             let query = if let Some(limit) = limit {
                 format!(
@@ -201,35 +150,9 @@ impl QuestDatabase {
                     .to_string()
             };
 
-            let rows = c.query(&query, &[&symbol, &interval]).await.map_err(|e| {
-                Box::new(std::io::Error::other(format!(
-                    "Failed to query candles: {}",
-                    e
-                ))) as Box<dyn std::error::Error + Send + Sync>
-            })?;
-
-            let mut candles = Vec::new();
-            for row in rows {
-                let timestamp_naive: chrono::NaiveDateTime = row.get(0);
-                let timestamp = DateTime::from_naive_utc_and_offset(timestamp_naive, Utc);
-                let open: f64 = row.get(1);
-                let high: f64 = row.get(2);
-                let low: f64 = row.get(3);
-                let close: f64 = row.get(4);
-                let volume: f64 = row.get(5);
-                let open_interest: Option<f64> = row.get(6);
-                let funding_rate: Option<f64> = row.get(7);
-
-                let mut candle = Candle::new(open, high, low, close, volume, timestamp);
-                if let Some(oi) = open_interest {
-                    candle = candle.with_open_interest(oi);
-                }
-                if let Some(fr) = funding_rate {
-                    candle = candle.with_funding_rate(fr);
-                }
+            let rows = c.query(&query, &[&symbol, &interval]).await?;
 
-                candles.push(candle);
-            }
+            let mut candles: Vec<Candle> = rows.iter().map(candle_from_row).collect();
 
             // Reverse to get oldest first
             candles.reverse();
@@ -240,26 +163,130 @@ impl QuestDatabase {
         }
     }
 
+    /// Get candles for a symbol and interval with `from <= timestamp < to`, oldest first
+    pub async fn get_candles_between(
+        &self,
+        symbol: &str,
+        interval: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Candle>, KryptexError> {
+        let client = self.pool.get().await;
+        if let Some(ref c) = *client {
+            let from_naive = from.naive_utc();
+            let to_naive = to.naive_utc();
+            let rows = c
+                .query(
+                    "SELECT timestamp, open, high, low, close, volume, open_interest, funding_rate
+                     FROM candles
+                     WHERE symbol = $1 AND interval = $2 AND timestamp >= $3 AND timestamp < $4
+                     ORDER BY timestamp ASC",
+                    &[&symbol, &interval, &from_naive, &to_naive],
+                )
+                .await?;
+
+            Ok(rows.iter().map(candle_from_row).collect())
+        } else {
+            Ok(Vec::new())
+        }
+    }
+
+    /// Get candles for a symbol and interval with `from <= timestamp <= to`,
+    /// oldest first; unlike [`Self::get_candles_between`], both ends are
+    /// inclusive
+    pub async fn get_candles_range(
+        &self,
+        symbol: &str,
+        interval: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Candle>, KryptexError> {
+        let client = self.pool.get().await;
+        if let Some(ref c) = *client {
+            let from_naive = from.naive_utc();
+            let to_naive = to.naive_utc();
+            // `candles` is TIMESTAMP(timestamp) PARTITION BY DAY, so a range on
+            // the designated timestamp only scans the partitions it covers and
+            // rows within them are already in timestamp order
+            let rows = c
+                .query(
+                    "SELECT timestamp, open, high, low, close, volume, open_interest, funding_rate
+                     FROM candles
+                     WHERE symbol = $1 AND interval = $2 AND timestamp BETWEEN $3 AND $4
+                     ORDER BY timestamp ASC",
+                    &[&symbol, &interval, &from_naive, &to_naive],
+                )
+                .await?;
+
+            Ok(rows.iter().map(candle_from_row).collect())
+        } else {
+            Ok(Vec::new())
+        }
+    }
+
+    /// Number of stored candles for a symbol and interval
+    pub async fn get_candle_count(
+        &self,
+        symbol: &str,
+        interval: &str,
+    ) -> Result<u64, KryptexError> {
+        let client = self.pool.get().await;
+        if let Some(ref c) = *client {
+            // No timestamp filter, so every partition is scanned; `symbol` and
+            // `interval` are SYMBOL columns, compared as integer keys
+            let count: i64 = c
+                .query_one(
+                    "SELECT count() FROM candles WHERE symbol = $1 AND interval = $2",
+                    &[&symbol, &interval],
+                )
+                .await?
+                .get(0);
+            Ok(count.max(0) as u64)
+        } else {
+            Ok(0)
+        }
+    }
+
+    /// Timestamp of the newest stored candle for a symbol and interval, or
+    /// `None` if there are none
+    pub async fn get_latest_candle_timestamp(
+        &self,
+        symbol: &str,
+        interval: &str,
+    ) -> Result<Option<DateTime<Utc>>, KryptexError> {
+        let client = self.pool.get().await;
+        if let Some(ref c) = *client {
+            // Descending order on the designated timestamp reads the newest
+            // partitions first, so LIMIT 1 stops early
+            let row = c
+                .query_opt(
+                    "SELECT timestamp FROM candles
+                     WHERE symbol = $1 AND interval = $2
+                     ORDER BY timestamp DESC
+                     LIMIT 1",
+                    &[&symbol, &interval],
+                )
+                .await?;
+            Ok(row.map(|row| {
+                let timestamp: chrono::NaiveDateTime = row.get(0);
+                DateTime::from_naive_utc_and_offset(timestamp, Utc)
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+
     /// Store a signal in QuestDB
     pub async fn store_signal(
         &self,
         signal: &SignalOutput,
         strategy_id: i64,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let client = self.client.read().await;
+    ) -> Result<(), KryptexError> {
+        let client = self.pool.get().await;
         if let Some(ref c) = *client {
-            let direction_str = match signal.direction {
-                SignalDirection::Long => "Long",
-                SignalDirection::Short => "Short",
-                SignalDirection::Neutral => "Neutral",
-            };
+            let direction_str = signal.direction.as_str();
 
-            let reasons_json = serde_json::to_string(&signal.reasons).map_err(|e| {
-                Box::new(std::io::Error::new(
-                    std::io::ErrorKind::InvalidData,
-                    format!("Failed to serialize reasons: {}", e),
-                )) as Box<dyn std::error::Error + Send + Sync>
-            })?;
+            let reasons_json = serde_json::to_string(&signal.reasons)?;
 
             // Generate ID from timestamp (QuestDB doesn't have auto-increment)
             let id = signal.timestamp.timestamp_millis();
@@ -282,27 +309,25 @@ impl QuestDatabase {
                     &reasons_json,
                 ],
             )
-            .await
-            .map_err(|e| {
-                Box::new(std::io::Error::other(format!("Failed to store signal: {}", e)))
-                    as Box<dyn std::error::Error + Send + Sync>
-            })?;
+            .await?;
         }
 
         Ok(())
     }
 
     /// Get signals for a symbol, ordered by timestamp (newest first)
+    ///
+    /// Each signal carries the decay model of the strategy that generated it.
     pub async fn get_signals(
         &self,
         symbol: Option<&str>,
         limit: Option<usize>,
-    ) -> Result<Vec<SignalOutput>, Box<dyn std::error::Error + Send + Sync>> {
-        let client = self.client.read().await;
+    ) -> Result<Vec<SignalOutput>, KryptexError> {
+        let client = self.pool.get().await;
         if let Some(ref c) = *client {
             let query = match (symbol, limit) {
                 (Some(_), Some(limit)) => format!(
-                    "SELECT symbol, direction, confidence, sl_pct, tp_pct, price, timestamp, reasons_json
+                    "SELECT symbol, direction, confidence, sl_pct, tp_pct, price, timestamp, reasons_json, strategy_id
                      FROM signals
                      WHERE symbol = $1
                      ORDER BY timestamp DESC
@@ -310,21 +335,21 @@ impl QuestDatabase {
                     limit
                 ),
                 (Some(_), None) => {
-                    "SELECT symbol, direction, confidence, sl_pct, tp_pct, price, timestamp, reasons_json
+                    "SELECT symbol, direction, confidence, sl_pct, tp_pct, price, timestamp, reasons_json, strategy_id
                      FROM signals
                      WHERE symbol = $1
                      ORDER BY timestamp DESC"
                         .to_string()
                 }
                 (None, Some(limit)) => format!(
-                    "SELECT symbol, direction, confidence, sl_pct, tp_pct, price, timestamp, reasons_json
+                    "SELECT symbol, direction, confidence, sl_pct, tp_pct, price, timestamp, reasons_json, strategy_id
                      FROM signals
                      ORDER BY timestamp DESC
                      LIMIT {}",
                     limit
                 ),
                 (None, None) => {
-                    "SELECT symbol, direction, confidence, sl_pct, tp_pct, price, timestamp, reasons_json
+                    "SELECT symbol, direction, confidence, sl_pct, tp_pct, price, timestamp, reasons_json, strategy_id
                      FROM signals
                      ORDER BY timestamp DESC"
                         .to_string()
@@ -335,84 +360,521 @@ impl QuestDatabase {
                 c.query(&query, &[&sym]).await
             } else {
                 c.query(&query, &[]).await
-            }
-            .map_err(|e| {
-                Box::new(std::io::Error::other(format!(
-                    "Failed to query signals: {}",
-                    e
-                ))) as Box<dyn std::error::Error + Send + Sync>
-            })?;
+            }?;
 
             let mut signals = Vec::new();
-            for row in rows {
-                let symbol: String = row.get(0);
-                let direction_str: String = row.get(1);
-                let direction = match direction_str.as_str() {
-                    "Long" => SignalDirection::Long,
-                    "Short" => SignalDirection::Short,
-                    _ => SignalDirection::Neutral,
-                };
-                let confidence: f64 = row.get(2);
-                let sl_pct: f64 = row.get(3);
-                let tp_pct: f64 = row.get(4);
-                let price: f64 = row.get(5);
-                let timestamp_naive: chrono::NaiveDateTime = row.get(6);
-                let timestamp = DateTime::from_naive_utc_and_offset(timestamp_naive, Utc);
-                let reasons_json: String = row.get(7);
-
-                let reasons: Vec<crate::models::signal::SignalReason> =
-                    serde_json::from_str(&reasons_json).map_err(|e| {
-                        Box::new(std::io::Error::new(
-                            std::io::ErrorKind::InvalidData,
-                            format!("Failed to deserialize reasons: {}", e),
-                        )) as Box<dyn std::error::Error + Send + Sync>
-                    })?;
-
-                signals.push(SignalOutput {
-                    symbol,
-                    direction,
-                    confidence,
-                    recommended_sl_pct: sl_pct,
-                    recommended_tp_pct: tp_pct,
-                    price,
-                    timestamp,
-                    reasons,
-                });
+            let mut strategy_ids = Vec::new();
+            for row in &rows {
+                let (signal, strategy_id) = signal_from_row(row)?;
+                signals.push(signal);
+                strategy_ids.push(strategy_id);
             }
+            drop(client);
 
+            self.attach_decay_models(symbol, &mut signals, &strategy_ids)
+                .await;
             Ok(signals)
         } else {
             Ok(Vec::new())
         }
     }
 
+    /// Query one page of signals matching `filter`, newest first.
+    ///
+    /// `page` is 1-based. Returns the page together with the total number of
+    /// matching signals.
+    pub async fn query_signals_paginated(
+        &self,
+        filter: &SignalFilter,
+        page: u32,
+        page_size: u32,
+    ) -> Result<(Vec<SignalOutput>, u64), KryptexError> {
+        let client = self.pool.get().await;
+        if let Some(ref c) = *client {
+            let direction = filter.direction.map(|d| d.as_str());
+            let from = filter.from.map(|from| from.naive_utc());
+            let to = filter.to.map(|to| to.naive_utc());
+
+            let mut filters = Vec::new();
+            let mut params: Vec<&(dyn ToSql + Sync)> = Vec::new();
+            if let Some(ref symbol) = filter.symbol {
+                params.push(symbol);
+                filters.push(format!("symbol = ${}", params.len()));
+            }
+            if let Some(ref direction) = direction {
+                params.push(direction);
+                filters.push(format!("direction = ${}", params.len()));
+            }
+            if let Some(ref from) = from {
+                params.push(from);
+                filters.push(format!("timestamp >= ${}", params.len()));
+            }
+            if let Some(ref to) = to {
+                params.push(to);
+                filters.push(format!("timestamp < ${}", params.len()));
+            }
+            if let Some(ref min_confidence) = filter.min_confidence {
+                params.push(min_confidence);
+                filters.push(format!("confidence >= ${}", params.len()));
+            }
+            let where_clause = if filters.is_empty() {
+                String::new()
+            } else {
+                format!("WHERE {}", filters.join(" AND "))
+            };
+
+            let count_query = format!("SELECT count() FROM signals {}", where_clause);
+            let total: i64 = c.query_one(&count_query, &params).await?.get(0);
+
+            // QuestDB has no OFFSET; `LIMIT lo, hi` returns rows lo+1 through hi
+            let offset = u64::from(page.saturating_sub(1)) * u64::from(page_size);
+            let query = format!(
+                "SELECT symbol, direction, confidence, sl_pct, tp_pct, price, timestamp, reasons_json, strategy_id
+                 FROM signals
+                 {}
+                 ORDER BY timestamp DESC
+                 LIMIT {}, {}",
+                where_clause,
+                offset,
+                offset + u64::from(page_size)
+            );
+            let rows = c.query(&query, &params).await?;
+
+            let mut signals = Vec::new();
+            let mut strategy_ids = Vec::new();
+            for row in &rows {
+                let (signal, strategy_id) = signal_from_row(row)?;
+                signals.push(signal);
+                strategy_ids.push(strategy_id);
+            }
+            drop(client);
+
+            self.attach_decay_models(filter.symbol.as_deref(), &mut signals, &strategy_ids)
+                .await;
+            Ok((signals, total.max(0) as u64))
+        } else {
+            Ok((Vec::new(), 0))
+        }
+    }
+
+    /// Fill in each signal's decay model from its strategy. Signals whose
+    /// strategy cannot be loaded are left without decay.
+    async fn attach_decay_models(
+        &self,
+        symbol: Option<&str>,
+        signals: &mut [SignalOutput],
+        strategy_ids: &[Option<i64>],
+    ) {
+        let strategies = match self.get_strategies(symbol).await {
+            Ok(strategies) => strategies,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to load strategies for signal decay");
+                return;
+            }
+        };
+        let decay_by_strategy: HashMap<i64, DecayConfig> = strategies
+            .into_iter()
+            .filter_map(|strategy| Some((strategy.id?, strategy.config.decay_model?)))
+            .collect();
+
+        for (signal, strategy_id) in signals.iter_mut().zip(strategy_ids) {
+            signal.decay = strategy_id.and_then(|id| decay_by_strategy.get(&id).copied());
+        }
+    }
+
+    /// Record a failed job in the dead-letter table and return its ID
+    pub async fn store_dead_letter(&self, dead_letter: &DeadLetter) -> Result<i64, KryptexError> {
+        // Generate ID from timestamp (QuestDB doesn't have auto-increment);
+        // microseconds since failures can land in the same millisecond
+        let id = dead_letter
+            .id
+            .unwrap_or_else(|| dead_letter.timestamp.timestamp_micros());
+        let client = self.pool.get().await;
+        if let Some(ref c) = *client {
+            let timestamp_naive = dead_letter.timestamp.naive_utc();
+            let replayed_at_naive = dead_letter.replayed_at.map(|at| at.naive_utc());
+            c.execute(
+                "INSERT INTO dead_letters (timestamp, id, job_type, symbol, strategy_id, error_message, retry_count, payload_json, replayed_at)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+                &[
+                    &timestamp_naive,
+                    &id,
+                    &dead_letter.job_type,
+                    &dead_letter.symbol,
+                    &dead_letter.strategy_id,
+                    &dead_letter.error_message,
+                    &dead_letter.retry_count,
+                    &dead_letter.payload,
+                    &replayed_at_naive,
+                ],
+            )
+            .await?;
+        }
+
+        Ok(id)
+    }
+
+    /// Get a single dead letter, including its job payload
+    pub async fn get_dead_letter(&self, id: i64) -> Result<DeadLetter, KryptexError> {
+        let client = self.pool.get().await;
+        if let Some(ref c) = *client {
+            let rows = c
+                .query(
+                    &format!("SELECT {} FROM dead_letters WHERE id = $1", DEAD_LETTER_COLUMNS),
+                    &[&id],
+                )
+                .await?;
+
+            rows.first().map(dead_letter_from_row).ok_or_else(|| {
+                KryptexError::NotFound(format!("Dead letter with id {} not found", id))
+            })
+        } else {
+            Err(
+                std::io::Error::new(std::io::ErrorKind::NotConnected, "Database not connected")
+                    .into(),
+            )
+        }
+    }
+
+    /// Record that a dead letter's job was pushed back onto its queue
+    pub async fn mark_dead_letter_replayed(
+        &self,
+        id: i64,
+        replayed_at: DateTime<Utc>,
+    ) -> Result<(), KryptexError> {
+        let client = self.pool.get().await;
+        if let Some(ref c) = *client {
+            let replayed_at_naive = replayed_at.naive_utc();
+            c.execute(
+                "UPDATE dead_letters SET replayed_at = $1 WHERE id = $2",
+                &[&replayed_at_naive, &id],
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Count dead letters by job type and whether they have been replayed
+    pub async fn get_dead_letter_stats(&self) -> Result<Vec<DeadLetterStats>, KryptexError> {
+        let client = self.pool.get().await;
+        if let Some(ref c) = *client {
+            let rows = c
+                .query(
+                    "SELECT job_type, is_replayed, count() AS count
+                     FROM (
+                         SELECT job_type, replayed_at IS NOT NULL AS is_replayed
+                         FROM dead_letters
+                     )
+                     GROUP BY job_type, is_replayed
+                     ORDER BY job_type, is_replayed",
+                    &[],
+                )
+                .await?;
+
+            let stats = rows
+                .iter()
+                .map(|row| DeadLetterStats {
+                    job_type: row.get(0),
+                    is_replayed: row.get(1),
+                    count: row.get(2),
+                })
+                .collect();
+
+            Ok(stats)
+        } else {
+            Ok(Vec::new())
+        }
+    }
+
+    /// Get the most recent failed jobs, newest first, optionally filtered by
+    /// symbol and job type
+    pub async fn get_dead_letters(
+        &self,
+        symbol: Option<&str>,
+        job_type: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<DeadLetter>, KryptexError> {
+        let client = self.pool.get().await;
+        if let Some(ref c) = *client {
+            let mut filters = Vec::new();
+            let mut params: Vec<&(dyn ToSql + Sync)> = Vec::new();
+            if let Some(ref symbol) = symbol {
+                params.push(symbol);
+                filters.push(format!("symbol = ${}", params.len()));
+            }
+            if let Some(ref job_type) = job_type {
+                params.push(job_type);
+                filters.push(format!("job_type = ${}", params.len()));
+            }
+            let where_clause = if filters.is_empty() {
+                String::new()
+            } else {
+                format!("WHERE {}", filters.join(" AND "))
+            };
+
+            let query = format!(
+                "SELECT {}
+                 FROM dead_letters
+                 {}
+                 ORDER BY timestamp DESC
+                 LIMIT {}",
+                DEAD_LETTER_COLUMNS, where_clause, limit
+            );
+
+            let rows = c.query(&query, &params).await?;
+
+            let dead_letters = rows.iter().map(dead_letter_from_row).collect();
+
+            Ok(dead_letters)
+        } else {
+            Ok(Vec::new())
+        }
+    }
+
+    /// Append a strategy change to the audit log
+    pub async fn insert_audit_log(&self, entry: &AuditLogEntry) -> Result<(), KryptexError> {
+        let client = self.pool.get().await;
+        if let Some(ref c) = *client {
+            let timestamp_naive = entry.timestamp.naive_utc();
+            c.execute(
+                "INSERT INTO audit_logs (timestamp, operation, strategy_id, strategy_name, operator_key, request_json)
+                 VALUES ($1, $2, $3, $4, $5, $6)",
+                &[
+                    &timestamp_naive,
+                    &entry.operation,
+                    &entry.strategy_id,
+                    &entry.strategy_name,
+                    &entry.operator_key,
+                    &entry.request_json,
+                ],
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Get the most recent strategy changes in `[from, to)`, newest first,
+    /// optionally for a single strategy
+    pub async fn get_audit_logs(
+        &self,
+        strategy_id: Option<i64>,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: usize,
+    ) -> Result<Vec<AuditLogEntry>, KryptexError> {
+        let client = self.pool.get().await;
+        if let Some(ref c) = *client {
+            let from = from.map(|from| from.naive_utc());
+            let to = to.map(|to| to.naive_utc());
+
+            let mut filters = Vec::new();
+            let mut params: Vec<&(dyn ToSql + Sync)> = Vec::new();
+            if let Some(ref strategy_id) = strategy_id {
+                params.push(strategy_id);
+                filters.push(format!("strategy_id = ${}", params.len()));
+            }
+            if let Some(ref from) = from {
+                params.push(from);
+                filters.push(format!("timestamp >= ${}", params.len()));
+            }
+            if let Some(ref to) = to {
+                params.push(to);
+                filters.push(format!("timestamp < ${}", params.len()));
+            }
+            let where_clause = if filters.is_empty() {
+                String::new()
+            } else {
+                format!("WHERE {}", filters.join(" AND "))
+            };
+
+            let query = format!(
+                "SELECT timestamp, operation, strategy_id, strategy_name, operator_key, request_json
+                 FROM audit_logs
+                 {}
+                 ORDER BY timestamp DESC
+                 LIMIT {}",
+                where_clause, limit
+            );
+
+            let rows = c.query(&query, &params).await?;
+
+            let entries = rows
+                .iter()
+                .map(|row| {
+                    let timestamp_naive: chrono::NaiveDateTime = row.get(0);
+                    AuditLogEntry {
+                        timestamp: DateTime::from_naive_utc_and_offset(timestamp_naive, Utc),
+                        operation: row.get(1),
+                        strategy_id: row.get(2),
+                        strategy_name: row.get(3),
+                        operator_key: row.get(4),
+                        request_json: row.get(5),
+                    }
+                })
+                .collect();
+
+            Ok(entries)
+        } else {
+            Ok(Vec::new())
+        }
+    }
+
+    /// Get per-strategy signal counts and average confidence since a point in time
+    pub async fn get_signal_stats_by_strategy(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<StrategyStats>, KryptexError> {
+        let client = self.pool.get().await;
+        if let Some(ref c) = *client {
+            let since_naive = since.naive_utc();
+            let rows = c
+                .query(
+                    "SELECT s.strategy_id, st.name, st.symbol,
+                            count() AS total_signals,
+                            sum(CASE WHEN s.direction = 'Long' THEN 1L ELSE 0L END) AS long_count,
+                            sum(CASE WHEN s.direction = 'Short' THEN 1L ELSE 0L END) AS short_count,
+                            sum(CASE WHEN s.direction = 'Neutral' THEN 1L ELSE 0L END) AS neutral_count,
+                            avg(s.confidence) AS avg_confidence
+                     FROM signals s
+                     LEFT JOIN strategies st ON s.strategy_id = st.id
+                     WHERE s.timestamp >= $1
+                     GROUP BY s.strategy_id, st.name, st.symbol
+                     ORDER BY total_signals DESC",
+                    &[&since_naive],
+                )
+                .await?;
+
+            let stats = rows
+                .iter()
+                .map(|row| StrategyStats {
+                    strategy_id: row.get(0),
+                    strategy_name: row.get(1),
+                    symbol: row.get(2),
+                    total_signals: row.get(3),
+                    long_count: row.get(4),
+                    short_count: row.get(5),
+                    neutral_count: row.get(6),
+                    avg_confidence: row.get(7),
+                })
+                .collect();
+
+            Ok(stats)
+        } else {
+            Ok(Vec::new())
+        }
+    }
+
+    /// Aggregate signal statistics per symbol and strategy over `[from, to)`,
+    /// optionally for a single symbol
+    ///
+    /// Confidence percentiles are approximate (3 significant digits).
+    pub async fn get_signal_stats(
+        &self,
+        symbol: Option<&str>,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<SignalStats, KryptexError> {
+        let mut stats = SignalStats {
+            from,
+            to,
+            groups: Vec::new(),
+        };
+        let client = self.pool.get().await;
+        let Some(ref c) = *client else {
+            return Ok(stats);
+        };
+
+        let from_naive = from.naive_utc();
+        let to_naive = to.naive_utc();
+        let last_1h = (to - chrono::Duration::hours(1)).naive_utc();
+        let last_24h = (to - chrono::Duration::hours(24)).naive_utc();
+        let last_7d = (to - chrono::Duration::days(7)).naive_utc();
+        let mut params: Vec<&(dyn ToSql + Sync)> =
+            vec![&from_naive, &to_naive, &last_1h, &last_24h, &last_7d];
+        let symbol_filter = match symbol {
+            Some(ref symbol) => {
+                params.push(symbol);
+                format!("AND symbol = ${}", params.len())
+            }
+            None => String::new(),
+        };
+
+        let query = format!(
+            "SELECT symbol, strategy_id,
+                    count() AS count,
+                    sum(CASE WHEN direction = 'Long' THEN 1L ELSE 0L END) AS long_count,
+                    sum(CASE WHEN direction = 'Short' THEN 1L ELSE 0L END) AS short_count,
+                    sum(CASE WHEN direction = 'Neutral' THEN 1L ELSE 0L END) AS neutral_count,
+                    avg(confidence) AS mean_confidence,
+                    approx_percentile(confidence, 0.25, 3) AS p25,
+                    approx_percentile(confidence, 0.5, 3) AS p50,
+                    approx_percentile(confidence, 0.75, 3) AS p75,
+                    approx_percentile(confidence, 0.9, 3) AS p90,
+                    avg(sl_pct) AS avg_sl_pct,
+                    avg(tp_pct) AS avg_tp_pct,
+                    sum(CASE WHEN timestamp >= $3 THEN 1L ELSE 0L END) AS last_1h,
+                    sum(CASE WHEN timestamp >= $4 THEN 1L ELSE 0L END) AS last_24h,
+                    sum(CASE WHEN timestamp >= $5 THEN 1L ELSE 0L END) AS last_7d
+             FROM signals
+             WHERE timestamp >= $1 AND timestamp < $2 {}
+             GROUP BY symbol, strategy_id
+             ORDER BY symbol, strategy_id",
+            symbol_filter
+        );
+        let rows = c.query(&query, &params).await?;
+
+        stats.groups = rows
+            .iter()
+            .map(|row| {
+                let count: i64 = row.get(2);
+                let pct = |n: i64| 100.0 * n as f64 / count.max(1) as f64;
+                SignalGroupStats {
+                    symbol: row.get(0),
+                    strategy_id: row.get(1),
+                    count,
+                    long_pct: pct(row.get(3)),
+                    short_pct: pct(row.get(4)),
+                    neutral_pct: pct(row.get(5)),
+                    mean_confidence: row.get(6),
+                    confidence_percentiles: ConfidencePercentiles {
+                        p25: row.get(7),
+                        p50: row.get(8),
+                        p75: row.get(9),
+                        p90: row.get(10),
+                    },
+                    avg_sl_pct: row.get(11),
+                    avg_tp_pct: row.get(12),
+                    last_1h: row.get(13),
+                    last_24h: row.get(14),
+                    last_7d: row.get(15),
+                }
+            })
+            .collect();
+
+        Ok(stats)
+    }
+
     /// Check if QuestDB connection is available
     pub async fn is_available(&self) -> bool {
-        let client = self.client.read().await;
-        client.is_some()
+        self.pool.is_available()
     }
 
     /// Create a new strategy
-    pub async fn create_strategy(
-        &self,
-        strategy: &Strategy,
-    ) -> Result<i64, Box<dyn std::error::Error + Send + Sync>> {
-        let client = self.client.read().await;
+    pub async fn create_strategy(&self, strategy: &Strategy) -> Result<i64, KryptexError> {
+        let client = self.pool.get().await;
         if let Some(ref c) = *client {
-            let config_json = serde_json::to_string(&strategy.config).map_err(|e| {
-                Box::new(std::io::Error::new(
-                    std::io::ErrorKind::InvalidData,
-                    format!("Failed to serialize strategy config: {}", e),
-                )) as Box<dyn std::error::Error + Send + Sync>
-            })?;
+            let config_json = serde_json::to_string(&strategy.config)?;
 
             let id = strategy.created_at.timestamp_millis();
             let created_at_naive = strategy.created_at.naive_utc();
             let updated_at_naive = strategy.updated_at.naive_utc();
+            let disabled = !strategy.enabled;
 
             c.execute(
-                "INSERT INTO strategies (id, name, symbol, created_at, updated_at, config_json)
-                 VALUES ($1, $2, $3, $4, $5, $6)",
+                "INSERT INTO strategies (id, name, symbol, created_at, updated_at, config_json, disabled, source_file)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
                 &[
                     &id,
                     &strategy.name,
@@ -420,51 +882,39 @@ impl QuestDatabase {
                     &created_at_naive,
                     &updated_at_naive,
                     &config_json,
+                    &disabled,
+                    &strategy.source_file,
                 ],
             )
-            .await
-            .map_err(|e| {
-                Box::new(std::io::Error::other(format!(
-                    "Failed to create strategy: {}",
-                    e
-                ))) as Box<dyn std::error::Error + Send + Sync>
-            })?;
+            .await?;
 
             Ok(id)
         } else {
-            Err(Box::new(std::io::Error::new(
+            Err(std::io::Error::new(
                 std::io::ErrorKind::NotConnected,
                 "Database connection not available",
-            )))
+            )
+            .into())
         }
     }
 
     /// Get a strategy by ID
-    pub async fn get_strategy(
-        &self,
-        id: i64,
-    ) -> Result<Strategy, Box<dyn std::error::Error + Send + Sync>> {
-        let client = self.client.read().await;
+    pub async fn get_strategy(&self, id: i64) -> Result<Strategy, KryptexError> {
+        let client = self.pool.get().await;
         if let Some(ref c) = *client {
             let rows = c
                 .query(
-                    "SELECT id, name, symbol, created_at, updated_at, config_json
+                    "SELECT id, name, symbol, created_at, updated_at, config_json, disabled, source_file
                      FROM strategies
                      WHERE id = $1",
                     &[&id],
                 )
-                .await
-                .map_err(|e| {
-                    Box::new(std::io::Error::other(format!(
-                        "Failed to query strategy: {}",
-                        e
-                    ))) as Box<dyn std::error::Error + Send + Sync>
-                })?;
+                .await?;
 
             if rows.is_empty() {
-                return Err(Box::new(std::io::Error::new(
-                    std::io::ErrorKind::NotFound,
-                    format!("Strategy with id {} not found", id),
+                return Err(KryptexError::NotFound(format!(
+                    "Strategy with id {} not found",
+                    id
                 )));
             }
 
@@ -475,31 +925,31 @@ impl QuestDatabase {
             let created_at_naive: chrono::NaiveDateTime = row.get(3);
             let updated_at_naive: chrono::NaiveDateTime = row.get(4);
             let config_json: String = row.get(5);
+            let disabled: bool = row.get(6);
+            let source_file: Option<String> = row.get(7);
 
             let created_at = DateTime::from_naive_utc_and_offset(created_at_naive, Utc);
             let updated_at = DateTime::from_naive_utc_and_offset(updated_at_naive, Utc);
 
             let config: crate::models::strategy::StrategyConfig =
-                serde_json::from_str(&config_json).map_err(|e| {
-                    Box::new(std::io::Error::new(
-                        std::io::ErrorKind::InvalidData,
-                        format!("Failed to deserialize strategy config: {}", e),
-                    )) as Box<dyn std::error::Error + Send + Sync>
-                })?;
+                serde_json::from_str(&config_json)?;
 
             Ok(Strategy {
                 id: Some(id),
                 name,
                 symbol,
                 config,
+                enabled: !disabled,
+                source_file,
                 created_at,
                 updated_at,
             })
         } else {
-            Err(Box::new(std::io::Error::new(
+            Err(std::io::Error::new(
                 std::io::ErrorKind::NotConnected,
                 "Database connection not available",
-            )))
+            )
+            .into())
         }
     }
 
@@ -507,16 +957,16 @@ impl QuestDatabase {
     pub async fn get_strategies(
         &self,
         symbol: Option<&str>,
-    ) -> Result<Vec<Strategy>, Box<dyn std::error::Error + Send + Sync>> {
-        let client = self.client.read().await;
+    ) -> Result<Vec<Strategy>, KryptexError> {
+        let client = self.pool.get().await;
         if let Some(ref c) = *client {
             let query = if let Some(_sym) = symbol {
-                "SELECT id, name, symbol, created_at, updated_at, config_json
+                "SELECT id, name, symbol, created_at, updated_at, config_json, disabled, source_file
                  FROM strategies
                  WHERE symbol = $1
                  ORDER BY created_at DESC"
             } else {
-                "SELECT id, name, symbol, created_at, updated_at, config_json
+                "SELECT id, name, symbol, created_at, updated_at, config_json, disabled, source_file
                  FROM strategies
                  ORDER BY created_at DESC"
             };
@@ -525,13 +975,7 @@ impl QuestDatabase {
                 c.query(query, &[&sym]).await
             } else {
                 c.query(query, &[]).await
-            }
-            .map_err(|e| {
-                Box::new(std::io::Error::other(format!(
-                    "Failed to query strategies: {}",
-                    e
-                ))) as Box<dyn std::error::Error + Send + Sync>
-            })?;
+            }?;
 
             let mut strategies = Vec::new();
             for row in rows {
@@ -541,23 +985,22 @@ impl QuestDatabase {
                 let created_at_naive: chrono::NaiveDateTime = row.get(3);
                 let updated_at_naive: chrono::NaiveDateTime = row.get(4);
                 let config_json: String = row.get(5);
+                let disabled: bool = row.get(6);
+                let source_file: Option<String> = row.get(7);
 
                 let created_at = DateTime::from_naive_utc_and_offset(created_at_naive, Utc);
                 let updated_at = DateTime::from_naive_utc_and_offset(updated_at_naive, Utc);
 
                 let config: crate::models::strategy::StrategyConfig =
-                    serde_json::from_str(&config_json).map_err(|e| {
-                        Box::new(std::io::Error::new(
-                            std::io::ErrorKind::InvalidData,
-                            format!("Failed to deserialize strategy config: {}", e),
-                        )) as Box<dyn std::error::Error + Send + Sync>
-                    })?;
+                    serde_json::from_str(&config_json)?;
 
                 strategies.push(Strategy {
                     id: Some(id),
                     name,
                     symbol,
                     config,
+                    enabled: !disabled,
+                    source_file,
                     created_at,
                     updated_at,
                 });
@@ -570,89 +1013,140 @@ impl QuestDatabase {
     }
 
     /// Update a strategy
-    pub async fn update_strategy(
-        &self,
-        id: i64,
-        strategy: &Strategy,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let client = self.client.read().await;
+    pub async fn update_strategy(&self, id: i64, strategy: &Strategy) -> Result<(), KryptexError> {
+        let client = self.pool.get().await;
         if let Some(ref c) = *client {
-            let config_json = serde_json::to_string(&strategy.config).map_err(|e| {
-                Box::new(std::io::Error::new(
-                    std::io::ErrorKind::InvalidData,
-                    format!("Failed to serialize strategy config: {}", e),
-                )) as Box<dyn std::error::Error + Send + Sync>
-            })?;
+            let config_json = serde_json::to_string(&strategy.config)?;
 
             let updated_at_naive = strategy.updated_at.naive_utc();
+            let disabled = !strategy.enabled;
 
             let rows_affected = c
                 .execute(
                     "UPDATE strategies
-                     SET name = $1, symbol = $2, updated_at = $3, config_json = $4
-                     WHERE id = $5",
+                     SET name = $1, symbol = $2, updated_at = $3, config_json = $4, disabled = $5
+                     WHERE id = $6",
                     &[
                         &strategy.name,
                         &strategy.symbol,
                         &updated_at_naive,
                         &config_json,
+                        &disabled,
                         &id,
                     ],
                 )
-                .await
-                .map_err(|e| {
-                    Box::new(std::io::Error::other(format!(
-                        "Failed to update strategy: {}",
-                        e
-                    ))) as Box<dyn std::error::Error + Send + Sync>
-                })?;
+                .await?;
 
             if rows_affected == 0 {
-                return Err(Box::new(std::io::Error::new(
-                    std::io::ErrorKind::NotFound,
-                    format!("Strategy with id {} not found", id),
+                return Err(KryptexError::NotFound(format!(
+                    "Strategy with id {} not found",
+                    id
                 )));
             }
 
             Ok(())
         } else {
-            Err(Box::new(std::io::Error::new(
+            Err(std::io::Error::new(
                 std::io::ErrorKind::NotConnected,
                 "Database connection not available",
-            )))
+            )
+            .into())
         }
     }
 
     /// Delete a strategy
-    pub async fn delete_strategy(
-        &self,
-        id: i64,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let client = self.client.read().await;
+    pub async fn delete_strategy(&self, id: i64) -> Result<(), KryptexError> {
+        let client = self.pool.get().await;
         if let Some(ref c) = *client {
             let rows_affected = c
                 .execute("DELETE FROM strategies WHERE id = $1", &[&id])
-                .await
-                .map_err(|e| {
-                    Box::new(std::io::Error::other(format!(
-                        "Failed to delete strategy: {}",
-                        e
-                    ))) as Box<dyn std::error::Error + Send + Sync>
-                })?;
+                .await?;
 
             if rows_affected == 0 {
-                return Err(Box::new(std::io::Error::new(
-                    std::io::ErrorKind::NotFound,
-                    format!("Strategy with id {} not found", id),
+                return Err(KryptexError::NotFound(format!(
+                    "Strategy with id {} not found",
+                    id
                 )));
             }
 
             Ok(())
         } else {
-            Err(Box::new(std::io::Error::new(
+            Err(std::io::Error::new(
                 std::io::ErrorKind::NotConnected,
                 "Database connection not available",
-            )))
+            )
+            .into())
         }
     }
 }
+
+/// Columns selected for dead letters, in the order `dead_letter_from_row` reads them
+const DEAD_LETTER_COLUMNS: &str =
+    "timestamp, id, job_type, symbol, strategy_id, error_message, retry_count, payload_json, replayed_at";
+
+fn dead_letter_from_row(row: &Row) -> DeadLetter {
+    let timestamp_naive: chrono::NaiveDateTime = row.get(0);
+    let replayed_at_naive: Option<chrono::NaiveDateTime> = row.get(8);
+    DeadLetter {
+        id: row.get(1),
+        timestamp: DateTime::from_naive_utc_and_offset(timestamp_naive, Utc),
+        job_type: row.get(2),
+        symbol: row.get(3),
+        strategy_id: row.get(4),
+        error_message: row.get(5),
+        retry_count: row.get(6),
+        payload: row.get(7),
+        replayed_at: replayed_at_naive.map(|at| DateTime::from_naive_utc_and_offset(at, Utc)),
+    }
+}
+
+/// Build a signal and its strategy id from a row selected as
+/// `symbol, direction, confidence, sl_pct, tp_pct, price, timestamp, reasons_json, strategy_id`
+fn signal_from_row(row: &Row) -> Result<(SignalOutput, Option<i64>), KryptexError> {
+    let direction_str: String = row.get(1);
+    let direction = match direction_str.as_str() {
+        "Long" => SignalDirection::Long,
+        "Short" => SignalDirection::Short,
+        _ => SignalDirection::Neutral,
+    };
+    let timestamp_naive: chrono::NaiveDateTime = row.get(6);
+    let reasons_json: String = row.get(7);
+    let reasons: Vec<crate::models::signal::SignalReason> = serde_json::from_str(&reasons_json)?;
+
+    let signal = SignalOutput {
+        symbol: row.get(0),
+        direction,
+        confidence: row.get(2),
+        recommended_sl_pct: row.get(3),
+        recommended_tp_pct: row.get(4),
+        recommended_position_size_pct: None,
+        price: row.get(5),
+        timestamp: DateTime::from_naive_utc_and_offset(timestamp_naive, Utc),
+        reasons,
+        decay: None,
+    };
+    Ok((signal, row.get(8)))
+}
+
+/// Build a candle from a row selected as
+/// `timestamp, open, high, low, close, volume, open_interest, funding_rate`
+fn candle_from_row(row: &Row) -> Candle {
+    let timestamp_naive: chrono::NaiveDateTime = row.get(0);
+    let timestamp = DateTime::from_naive_utc_and_offset(timestamp_naive, Utc);
+    let open: f64 = row.get(1);
+    let high: f64 = row.get(2);
+    let low: f64 = row.get(3);
+    let close: f64 = row.get(4);
+    let volume: f64 = row.get(5);
+    let open_interest: Option<f64> = row.get(6);
+    let funding_rate: Option<f64> = row.get(7);
+
+    let mut candle = Candle::new(open, high, low, close, volume, timestamp);
+    if let Some(oi) = open_interest {
+        candle = candle.with_open_interest(oi);
+    }
+    if let Some(fr) = funding_rate {
+        candle = candle.with_funding_rate(fr);
+    }
+    candle
+}
@@ -4,6 +4,10 @@ use crate::indicators::perp::{funding_rate, open_interest};
 use crate::indicators::trend::{ema, supertrend};
 use crate::indicators::volatility::{atr, bollinger};
 use crate::indicators::volume::{obv, volume_profile};
+use crate::signals::scoring::open_interest_confirmation;
+
+/// Maximum share of confidence open interest confirmation can add or remove
+const OI_CONFIDENCE_ADJUSTMENT: f64 = 0.2;
 
 pub struct SignalAggregator;
 
@@ -25,8 +29,21 @@ impl SignalAggregator {
             trend_score + momentum_score + volatility_score + volume_score + perp_score;
         let bias = MarketBias::from_score(total_score);
         let position = bias.to_position();
-        let confidence =
+        let mut confidence =
             self.calculate_confidence(trend_score, momentum_score, volume_score, perp_score);
+        if let Some(oi_score) = signals.oi_score {
+            let oi_confirmation = open_interest_confirmation(oi_score, trend_score as f64);
+            if oi_confirmation >= 0.5 {
+                reasons.push("Rising open interest confirms trend".into());
+            } else if oi_confirmation <= -0.5 {
+                reasons.push("Open interest diverges from trend".into());
+            }
+            confidence = ((confidence * (1.0 + OI_CONFIDENCE_ADJUSTMENT * oi_confirmation))
+                .clamp(0.0, 1.0)
+                * 10000.0)
+                .round()
+                / 10000.0;
+        }
         let risk_level = self.assess_risk(&signals, total_score);
 
         TradingSignal {
@@ -361,5 +378,7 @@ pub struct IndicatorSignals {
     pub obv_signal: obv::OBVSignal,
     pub volume_profile_signal: volume_profile::VolumeProfileSignal,
     pub oi_signal: open_interest::OpenInterestSignal,
+    /// Normalized OI deviation from its moving average, if open interest is available
+    pub oi_score: Option<f64>,
     pub funding_signal: funding_rate::FundingSignal,
 }
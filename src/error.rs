@@ -0,0 +1,151 @@
+//! Crate-wide error type
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+use tokio_postgres::error::SqlState;
+
+/// Errors from storage, caching, and job processing
+#[derive(Debug)]
+pub enum KryptexError {
+    /// A QuestDB query or connection failed
+    Database(tokio_postgres::Error),
+    /// A Redis command or connection failed
+    Cache(redis::RedisError),
+    /// A WebSocket stream failed or sent something unexpected
+    WebSocket(String),
+    /// The market data provider could not serve a request
+    MarketData(String),
+    /// Input that can never succeed as given, e.g. too few candles
+    InvalidInput(String),
+    /// A requested record does not exist
+    NotFound(String),
+    /// A value could not be (de)serialized as JSON
+    Serialization(serde_json::Error),
+    /// Any other I/O failure, including missing connections
+    Io(io::Error),
+}
+
+impl KryptexError {
+    /// Whether retrying the same operation later may succeed, e.g. after a
+    /// dropped connection or timeout, as opposed to bad input or missing data
+    pub fn is_transient(&self) -> bool {
+        match self {
+            KryptexError::Database(e) => {
+                if e.is_closed() {
+                    return true;
+                }
+                match e.code() {
+                    Some(code) => is_transient_sql_state(code),
+                    // Without a server error code, only connection-level I/O failures are transient
+                    None => e
+                        .source()
+                        .and_then(|source| source.downcast_ref::<io::Error>())
+                        .is_some_and(is_transient_io),
+                }
+            }
+            KryptexError::Cache(e) => {
+                e.is_timeout()
+                    || e.is_connection_dropped()
+                    || e.is_connection_refusal()
+                    || matches!(
+                        e.kind(),
+                        redis::ErrorKind::TryAgain
+                            | redis::ErrorKind::BusyLoadingError
+                            | redis::ErrorKind::ClusterDown
+                            | redis::ErrorKind::MasterDown
+                    )
+            }
+            KryptexError::WebSocket(_) | KryptexError::MarketData(_) => true,
+            KryptexError::Io(e) => is_transient_io(e),
+            KryptexError::InvalidInput(_)
+            | KryptexError::NotFound(_)
+            | KryptexError::Serialization(_) => false,
+        }
+    }
+}
+
+/// Connection exceptions, insufficient resources, server shutdowns, and
+/// serialization failures or deadlocks
+fn is_transient_sql_state(code: &SqlState) -> bool {
+    let code = code.code();
+    code.starts_with("08")
+        || code.starts_with("53")
+        || code.starts_with("57P")
+        || code == "40001"
+        || code == "40P01"
+}
+
+fn is_transient_io(e: &io::Error) -> bool {
+    matches!(
+        e.kind(),
+        io::ErrorKind::ConnectionRefused
+            | io::ErrorKind::ConnectionReset
+            | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::NotConnected
+            | io::ErrorKind::BrokenPipe
+            | io::ErrorKind::TimedOut
+            | io::ErrorKind::Interrupted
+            | io::ErrorKind::WouldBlock
+            | io::ErrorKind::UnexpectedEof
+    )
+}
+
+impl fmt::Display for KryptexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KryptexError::Database(e) => write!(f, "Database error: {}", e),
+            KryptexError::Cache(e) => write!(f, "Cache error: {}", e),
+            KryptexError::WebSocket(msg) => write!(f, "WebSocket error: {}", msg),
+            KryptexError::MarketData(msg) => write!(f, "Market data error: {}", msg),
+            KryptexError::InvalidInput(msg) | KryptexError::NotFound(msg) => f.write_str(msg),
+            KryptexError::Serialization(e) => write!(f, "Serialization error: {}", e),
+            KryptexError::Io(e) => write!(f, "I/O error: {}", e),
+        }
+    }
+}
+
+impl Error for KryptexError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            KryptexError::Database(e) => Some(e),
+            KryptexError::Cache(e) => Some(e),
+            KryptexError::Serialization(e) => Some(e),
+            KryptexError::Io(e) => Some(e),
+            KryptexError::WebSocket(_)
+            | KryptexError::MarketData(_)
+            | KryptexError::InvalidInput(_)
+            | KryptexError::NotFound(_) => None,
+        }
+    }
+}
+
+impl From<tokio_postgres::Error> for KryptexError {
+    fn from(e: tokio_postgres::Error) -> Self {
+        KryptexError::Database(e)
+    }
+}
+
+impl From<redis::RedisError> for KryptexError {
+    fn from(e: redis::RedisError) -> Self {
+        KryptexError::Cache(e)
+    }
+}
+
+impl From<tokio_tungstenite::tungstenite::Error> for KryptexError {
+    fn from(e: tokio_tungstenite::tungstenite::Error) -> Self {
+        KryptexError::WebSocket(e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for KryptexError {
+    fn from(e: serde_json::Error) -> Self {
+        KryptexError::Serialization(e)
+    }
+}
+
+impl From<io::Error> for KryptexError {
+    fn from(e: io::Error) -> Self {
+        KryptexError::Io(e)
+    }
+}
@@ -5,6 +5,7 @@ pub mod validation;
 
 pub mod momentum;
 pub mod perp;
+pub mod structure;
 pub mod trend;
 pub mod volatility;
 pub mod volume;
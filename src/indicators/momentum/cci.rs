@@ -0,0 +1,108 @@
+//! Commodity Channel Index (CCI) indicator implementation.
+
+use std::collections::VecDeque;
+
+pub const CCI_DEFAULT_PERIOD: usize = 20;
+/// CCI level above which price is overbought
+pub const CCI_OVERBOUGHT: f64 = 100.0;
+/// CCI level below which price is oversold
+pub const CCI_OVERSOLD: f64 = -100.0;
+/// Lambert's constant, scaling most readings into [-100, 100]
+const CCI_SCALE: f64 = 0.015;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CCISignal {
+    Overbought,
+    Oversold,
+    /// CCI crossed from negative to positive
+    BullishZeroCross,
+    /// CCI crossed from positive to negative
+    BearishZeroCross,
+    Neutral,
+}
+
+/// Streaming Commodity Channel Index.
+///
+/// CCI is `(TP - SMA(TP)) / (0.015 × mean deviation)` over the last `period`
+/// typical prices `(high + low + close) / 3`. A window of identical typical
+/// prices has no deviation, so its CCI is reported as 0.
+#[derive(Debug, Clone)]
+pub struct CCI {
+    period: usize,
+    typical_prices: VecDeque<f64>,
+    prev_value: Option<f64>,
+}
+
+impl CCI {
+    pub fn new(period: usize) -> Self {
+        let period = period.max(1);
+        Self {
+            period,
+            typical_prices: VecDeque::with_capacity(period),
+            prev_value: None,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.typical_prices.clear();
+        self.prev_value = None;
+    }
+
+    /// Candles needed before the first reading
+    pub fn lookback(&self) -> usize {
+        self.period
+    }
+
+    /// Feed the next candle; returns `(cci, signal)` once `period` candles
+    /// have been seen.
+    pub fn update(&mut self, high: f64, low: f64, close: f64) -> Option<(f64, CCISignal)> {
+        let typical_price = (high + low + close) / 3.0;
+        self.typical_prices.push_back(typical_price);
+        if self.typical_prices.len() > self.period {
+            self.typical_prices.pop_front();
+        }
+        if self.typical_prices.len() < self.period {
+            return None;
+        }
+
+        let count = self.typical_prices.len() as f64;
+        let mean = self.typical_prices.iter().sum::<f64>() / count;
+        let mean_deviation = self
+            .typical_prices
+            .iter()
+            .map(|tp| (tp - mean).abs())
+            .sum::<f64>()
+            / count;
+
+        let value = if mean_deviation > f64::EPSILON {
+            (typical_price - mean) / (CCI_SCALE * mean_deviation)
+        } else {
+            0.0
+        };
+        let signal = Self::get_signal(value, self.prev_value);
+        self.prev_value = Some(value);
+
+        Some((value, signal))
+    }
+
+    /// Classify a CCI reading; the extreme zones take priority over zero crosses
+    pub fn get_signal(value: f64, prev_value: Option<f64>) -> CCISignal {
+        if value > CCI_OVERBOUGHT {
+            CCISignal::Overbought
+        } else if value < CCI_OVERSOLD {
+            CCISignal::Oversold
+        } else {
+            match prev_value {
+                Some(prev) if prev < 0.0 && value > 0.0 => CCISignal::BullishZeroCross,
+                Some(prev) if prev > 0.0 && value < 0.0 => CCISignal::BearishZeroCross,
+                _ => CCISignal::Neutral,
+            }
+        }
+    }
+}
+
+impl Default for CCI {
+    fn default() -> Self {
+        Self::new(CCI_DEFAULT_PERIOD)
+    }
+}
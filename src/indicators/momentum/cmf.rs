@@ -0,0 +1,115 @@
+//! Chaikin Money Flow (CMF) indicator implementation.
+
+pub const CMF_DEFAULT_PERIOD: usize = 20;
+/// CMF level above which buying pressure dominates
+pub const CMF_BULLISH_THRESHOLD: f64 = 0.05;
+/// CMF level below which selling pressure dominates
+pub const CMF_BEARISH_THRESHOLD: f64 = -0.05;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CMFSignal {
+    Bullish,
+    Bearish,
+    Neutral,
+}
+
+/// Streaming Chaikin Money Flow.
+///
+/// Each candle's money flow volume is `volume × (2C - H - L) / (H - L)`: the
+/// full volume when it closes on its high, minus the full volume when it
+/// closes on its low. A candle with `H == L` has no range to place the close
+/// in and contributes zero flow (its volume still counts). CMF is
+/// `sum(MFV) / sum(volume)` over the last `period` candles, kept in a circular
+/// buffer with running sums, and always lies in `[-1, 1]`.
+#[derive(Debug, Clone)]
+pub struct CMF {
+    period: usize,
+    /// `(money_flow_volume, volume)` per candle
+    flows: Vec<(f64, f64)>,
+    next: usize,
+    flow_sum: f64,
+    volume_sum: f64,
+}
+
+impl CMF {
+    pub fn new(period: usize) -> Self {
+        let period = period.max(1);
+        Self {
+            period,
+            flows: Vec::with_capacity(period),
+            next: 0,
+            flow_sum: 0.0,
+            volume_sum: 0.0,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.flows.clear();
+        self.next = 0;
+        self.flow_sum = 0.0;
+        self.volume_sum = 0.0;
+    }
+
+    /// Candles needed before the first reading
+    pub fn lookback(&self) -> usize {
+        self.period
+    }
+
+    /// Feed the next candle; returns `(cmf, signal)` once `period` candles
+    /// have been seen.
+    pub fn update(
+        &mut self,
+        high: f64,
+        low: f64,
+        close: f64,
+        volume: f64,
+    ) -> Option<(f64, CMFSignal)> {
+        let range = high - low;
+        let money_flow_volume = if range > f64::EPSILON {
+            volume * ((close - low) - (high - close)) / range
+        } else {
+            0.0
+        };
+        let flow = (money_flow_volume, volume);
+
+        if self.flows.len() < self.period {
+            self.flows.push(flow);
+        } else {
+            let (old_flow, old_volume) = std::mem::replace(&mut self.flows[self.next], flow);
+            self.flow_sum -= old_flow;
+            self.volume_sum -= old_volume;
+        }
+        self.next = (self.next + 1) % self.period;
+        self.flow_sum += flow.0;
+        self.volume_sum += flow.1;
+
+        if self.flows.len() < self.period {
+            return None;
+        }
+
+        // Running sums can drift slightly after many evictions
+        let value = if self.volume_sum > f64::EPSILON {
+            (self.flow_sum / self.volume_sum).clamp(-1.0, 1.0)
+        } else {
+            0.0
+        };
+
+        Some((value, Self::get_signal(value)))
+    }
+
+    pub fn get_signal(value: f64) -> CMFSignal {
+        if value > CMF_BULLISH_THRESHOLD {
+            CMFSignal::Bullish
+        } else if value < CMF_BEARISH_THRESHOLD {
+            CMFSignal::Bearish
+        } else {
+            CMFSignal::Neutral
+        }
+    }
+}
+
+impl Default for CMF {
+    fn default() -> Self {
+        Self::new(CMF_DEFAULT_PERIOD)
+    }
+}
@@ -1,7 +1,15 @@
 //! MACD (Moving Average Convergence Divergence) indicator implementations.
 
-use super::super::trend::ema::EMA;
+use crate::common::math::ema_from_previous;
 use crate::models::indicators::{Candle, MacdIndicator};
+use std::collections::VecDeque;
+
+/// Fast EMA period used when a strategy condition does not set `fast_period`
+pub const MACD_DEFAULT_FAST_PERIOD: u32 = 12;
+/// Slow EMA period used when a strategy condition does not set `slow_period`
+pub const MACD_DEFAULT_SLOW_PERIOD: u32 = 26;
+/// Signal line period used when a strategy condition does not set `signal_period`
+pub const MACD_DEFAULT_SIGNAL_PERIOD: u32 = 9;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum MACDSignal {
@@ -12,11 +20,21 @@ pub enum MACDSignal {
     Neutral,
 }
 
+/// Streaming MACD with O(1) updates.
+///
+/// The fast and slow EMAs are seeded with the first close. The signal line is
+/// seeded with the SMA of the first `signal_period` MACD values; until then the
+/// running mean of the MACD values seen so far is reported as the signal.
 #[derive(Debug, Clone)]
 pub struct MACD {
-    ema_fast: EMA,
-    ema_slow: EMA,
-    signal_line: EMA,
+    fast_period: usize,
+    slow_period: usize,
+    signal_period: usize,
+    fast_ema: Option<f64>,
+    slow_ema: Option<f64>,
+    macd_history: VecDeque<f64>,
+    signal_ema: Option<f64>,
+    signal_period_count: usize,
     prev_macd: Option<f64>,
     prev_signal: Option<f64>,
 }
@@ -24,20 +42,58 @@ pub struct MACD {
 impl MACD {
     pub fn new(fast_period: usize, slow_period: usize, signal_period: usize) -> Self {
         Self {
-            ema_fast: EMA::new(fast_period),
-            ema_slow: EMA::new(slow_period),
-            signal_line: EMA::new(signal_period),
+            fast_period,
+            slow_period,
+            signal_period,
+            fast_ema: None,
+            slow_ema: None,
+            macd_history: VecDeque::with_capacity(signal_period),
+            signal_ema: None,
+            signal_period_count: 0,
             prev_macd: None,
             prev_signal: None,
         }
     }
 
+    pub fn reset(&mut self) {
+        self.fast_ema = None;
+        self.slow_ema = None;
+        self.macd_history.clear();
+        self.signal_ema = None;
+        self.signal_period_count = 0;
+        self.prev_macd = None;
+        self.prev_signal = None;
+    }
+
     pub fn update(&mut self, close: f64) -> (f64, f64, f64, MACDSignal) {
-        let fast = self.ema_fast.update(close);
-        let slow = self.ema_slow.update(close);
+        let fast = match self.fast_ema {
+            Some(prev) => ema_from_previous(close, prev, self.fast_period),
+            None => close,
+        };
+        let slow = match self.slow_ema {
+            Some(prev) => ema_from_previous(close, prev, self.slow_period),
+            None => close,
+        };
+        self.fast_ema = Some(fast);
+        self.slow_ema = Some(slow);
         let macd = fast - slow;
 
-        let signal = self.signal_line.update(macd);
+        self.macd_history.push_back(macd);
+        if self.macd_history.len() > self.signal_period.max(1) {
+            self.macd_history.pop_front();
+        }
+
+        let signal = match self.signal_ema {
+            Some(prev) => ema_from_previous(macd, prev, self.signal_period),
+            None => {
+                self.signal_period_count += 1;
+                self.macd_history.iter().sum::<f64>() / self.macd_history.len() as f64
+            }
+        };
+        // Once `signal_period` values are buffered the running mean becomes the SMA seed
+        if self.signal_ema.is_some() || self.signal_period_count >= self.signal_period {
+            self.signal_ema = Some(signal);
+        }
         let histogram = macd - signal;
 
         let macd_signal =
@@ -61,6 +117,16 @@ impl MACD {
         self.prev_signal = Some(signal);
         (macd, signal, histogram, macd_signal)
     }
+
+    /// Whether the signal line has been seeded with a full `signal_period` of MACD values
+    pub fn is_ready(&self) -> bool {
+        self.signal_ema.is_some()
+    }
+
+    /// Most recent MACD values (at most `signal_period` entries)
+    pub fn macd_history(&self) -> &VecDeque<f64> {
+        &self.macd_history
+    }
 }
 
 pub fn calculate_macd(
@@ -93,5 +159,10 @@ pub fn calculate_macd(
 }
 
 pub fn calculate_macd_default(candles: &[Candle]) -> Option<MacdIndicator> {
-    calculate_macd(candles, 12, 26, 9)
+    calculate_macd(
+        candles,
+        MACD_DEFAULT_FAST_PERIOD,
+        MACD_DEFAULT_SLOW_PERIOD,
+        MACD_DEFAULT_SIGNAL_PERIOD,
+    )
 }
@@ -0,0 +1,126 @@
+//! Money Flow Index (MFI) indicator implementation.
+
+pub const MFI_DEFAULT_PERIOD: usize = 14;
+/// MFI level above which price is overbought
+pub const MFI_OVERBOUGHT: f64 = 80.0;
+/// MFI level below which price is oversold
+pub const MFI_OVERSOLD: f64 = 20.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MFISignal {
+    Oversold,
+    Overbought,
+    Neutral,
+}
+
+/// Streaming Money Flow Index, a volume-weighted RSI.
+///
+/// Each candle's raw money flow is its typical price `(high + low + close) / 3`
+/// times volume, counted as positive when the typical price rose from the
+/// previous candle and negative when it fell. MFI is
+/// `100 - 100 / (1 + positive / negative)` over the last `period` flows, kept
+/// in a circular buffer with running sums. A window with no negative flow
+/// reads 100 (or 50 if there was no flow at all).
+#[derive(Debug, Clone)]
+pub struct MFI {
+    period: usize,
+    /// `(positive, negative)` flow per candle
+    flows: Vec<(f64, f64)>,
+    next: usize,
+    positive_sum: f64,
+    negative_sum: f64,
+    prev_typical_price: Option<f64>,
+}
+
+impl MFI {
+    pub fn new(period: usize) -> Self {
+        let period = period.max(1);
+        Self {
+            period,
+            flows: Vec::with_capacity(period),
+            next: 0,
+            positive_sum: 0.0,
+            negative_sum: 0.0,
+            prev_typical_price: None,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.flows.clear();
+        self.next = 0;
+        self.positive_sum = 0.0;
+        self.negative_sum = 0.0;
+        self.prev_typical_price = None;
+    }
+
+    /// Candles needed before the first reading
+    pub fn lookback(&self) -> usize {
+        self.period + 1
+    }
+
+    /// Feed the next candle; returns `(mfi, signal)` once `period` money
+    /// flows (`period + 1` candles) have been seen.
+    pub fn update(
+        &mut self,
+        high: f64,
+        low: f64,
+        close: f64,
+        volume: f64,
+    ) -> Option<(f64, MFISignal)> {
+        let typical_price = (high + low + close) / 3.0;
+        let prev_typical_price = self.prev_typical_price.replace(typical_price)?;
+
+        let raw_flow = typical_price * volume;
+        let flow = if typical_price > prev_typical_price {
+            (raw_flow, 0.0)
+        } else if typical_price < prev_typical_price {
+            (0.0, raw_flow)
+        } else {
+            (0.0, 0.0)
+        };
+
+        if self.flows.len() < self.period {
+            self.flows.push(flow);
+        } else {
+            let (old_positive, old_negative) = std::mem::replace(&mut self.flows[self.next], flow);
+            self.positive_sum -= old_positive;
+            self.negative_sum -= old_negative;
+        }
+        self.next = (self.next + 1) % self.period;
+        self.positive_sum += flow.0;
+        self.negative_sum += flow.1;
+
+        if self.flows.len() < self.period {
+            return None;
+        }
+
+        // Running sums can drift just below zero after many evictions
+        let positive = self.positive_sum.max(0.0);
+        let negative = self.negative_sum.max(0.0);
+        let value = if negative > f64::EPSILON {
+            100.0 - 100.0 / (1.0 + positive / negative)
+        } else if positive > f64::EPSILON {
+            100.0
+        } else {
+            50.0
+        };
+
+        Some((value, Self::get_signal(value)))
+    }
+
+    pub fn get_signal(value: f64) -> MFISignal {
+        if value < MFI_OVERSOLD {
+            MFISignal::Oversold
+        } else if value > MFI_OVERBOUGHT {
+            MFISignal::Overbought
+        } else {
+            MFISignal::Neutral
+        }
+    }
+}
+
+impl Default for MFI {
+    fn default() -> Self {
+        Self::new(MFI_DEFAULT_PERIOD)
+    }
+}
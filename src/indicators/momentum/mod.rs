@@ -1,7 +1,17 @@
-//! Momentum indicators: MACD, RSI
+//! Momentum indicators: MACD, RSI, Stochastic RSI, Williams %R, CCI, MFI, CMF
 
+pub mod cci;
+pub mod cmf;
 pub mod macd;
+pub mod mfi;
 pub mod rsi;
+pub mod stochastic_rsi;
+pub mod williams_r;
 
+pub use cci::*;
+pub use cmf::*;
 pub use macd::*;
+pub use mfi::*;
 pub use rsi::*;
+pub use stochastic_rsi::*;
+pub use williams_r::*;
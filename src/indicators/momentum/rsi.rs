@@ -2,6 +2,18 @@
 
 use crate::models::indicators::{Candle, RsiIndicator};
 
+/// Lookback used when a strategy condition does not set `period`
+pub const RSI_DEFAULT_PERIOD: usize = 14;
+/// Default RSI level above which the market is overbought
+pub const DEFAULT_OVERBOUGHT: f64 = 70.0;
+/// Default RSI level below which the market is oversold
+pub const DEFAULT_OVERSOLD: f64 = 30.0;
+/// Readings searched for swings by [`detect_rsi_divergence`] in strategy
+/// evaluation
+pub const RSI_DIVERGENCE_LOOKBACK: usize = 30;
+/// Bars on each side a swing must exceed in divergence detection
+pub const RSI_DIVERGENCE_SWING_BARS: usize = 3;
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum RSISignal {
     Oversold,
@@ -11,6 +23,87 @@ pub enum RSISignal {
     Neutral,
 }
 
+/// Divergence between price swings and RSI swings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DivergenceType {
+    /// Price made a lower low while RSI made a higher low
+    Bullish,
+    /// Price made a higher high while RSI made a lower high
+    Bearish,
+}
+
+/// Compare the latest two price swings with the RSI swings beside them.
+///
+/// A swing high is a reading above the `swing_lookback` readings before it
+/// and not below the `swing_lookback` after it (swing lows mirror this), so
+/// a swing is only confirmed `swing_lookback` readings later. Each of the
+/// last two price swings is paired with the nearest RSI swing of the same
+/// kind at most `swing_lookback` readings away, since the oscillator often
+/// turns a bar or two before or after price. Both series are aligned at
+/// their newest entry.
+///
+/// Returns the divergence of the most recent swing pair, or `None` when
+/// fewer than two swings pair up or the swings agree.
+pub fn detect_rsi_divergence(
+    prices: &[f64],
+    rsi_values: &[f64],
+    swing_lookback: usize,
+) -> Option<DivergenceType> {
+    let len = prices.len().min(rsi_values.len());
+    let prices = &prices[prices.len() - len..];
+    let rsi_values = &rsi_values[rsi_values.len() - len..];
+    let swing_lookback = swing_lookback.max(1);
+
+    // Swing lows are the swing highs of the negated series, where a lower
+    // low in price and a higher low in RSI become the bearish pattern
+    let negate = |values: &[f64]| values.iter().map(|v| -v).collect::<Vec<_>>();
+    let bearish = diverging_swing(prices, rsi_values, swing_lookback);
+    let bullish = diverging_swing(&negate(prices), &negate(rsi_values), swing_lookback);
+
+    match (bullish, bearish) {
+        (Some(low), Some(high)) if low > high => Some(DivergenceType::Bullish),
+        (Some(low), Some(high)) if high > low => Some(DivergenceType::Bearish),
+        (Some(_), None) => Some(DivergenceType::Bullish),
+        (None, Some(_)) => Some(DivergenceType::Bearish),
+        _ => None,
+    }
+}
+
+/// Index of the latest price swing high when it is a higher high than the
+/// one before while the paired RSI swing high is lower
+fn diverging_swing(prices: &[f64], rsi_values: &[f64], swing_lookback: usize) -> Option<usize> {
+    let price_swings = swing_highs(prices, swing_lookback);
+    let rsi_swings = swing_highs(rsi_values, swing_lookback);
+    let paired_rsi = |i: usize| {
+        rsi_swings
+            .iter()
+            .copied()
+            .filter(|j| j.abs_diff(i) <= swing_lookback)
+            .min_by_key(|j| j.abs_diff(i))
+            .map(|j| rsi_values[j])
+    };
+
+    let [.., earlier, latest] = price_swings[..] else {
+        return None;
+    };
+    let (earlier_rsi, latest_rsi) = (paired_rsi(earlier)?, paired_rsi(latest)?);
+    (prices[latest] > prices[earlier] && latest_rsi < earlier_rsi).then_some(latest)
+}
+
+/// Indices of confirmed swing highs, oldest first
+fn swing_highs(values: &[f64], swing_lookback: usize) -> Vec<usize> {
+    let n = swing_lookback;
+    if values.len() < 2 * n + 1 {
+        return Vec::new();
+    }
+    (n..values.len() - n)
+        .filter(|&i| {
+            values[i - n..i].iter().all(|&v| v < values[i])
+                && values[i + 1..=i + n].iter().all(|&v| v <= values[i])
+        })
+        .collect()
+}
+
 #[derive(Debug, Clone)]
 pub struct RSI {
     period: usize,
@@ -35,6 +128,20 @@ impl RSI {
         }
     }
 
+    pub fn reset(&mut self) {
+        self.gains.clear();
+        self.losses.clear();
+        self.avg_gain = None;
+        self.avg_loss = None;
+        self.prev_close = None;
+        self.prev_rsi = None;
+    }
+
+    /// Candles needed before the first reading
+    pub fn lookback(&self) -> usize {
+        self.period + 1
+    }
+
     pub fn update(&mut self, close: f64) -> Option<f64> {
         if let Some(prev) = self.prev_close {
             let change = close - prev;
@@ -84,23 +191,36 @@ impl RSI {
         None
     }
 
-    pub fn get_signal(&self, rsi: f64, price_change: f64) -> RSISignal {
-        if rsi < 30.0 {
-            if let Some(prev_rsi) = self.prev_rsi {
-                if price_change < 0.0 && rsi > prev_rsi {
-                    return RSISignal::BullishDivergence;
-                }
-            }
-            RSISignal::Oversold
-        } else if rsi > 70.0 {
-            if let Some(prev_rsi) = self.prev_rsi {
-                if price_change > 0.0 && rsi < prev_rsi {
-                    return RSISignal::BearishDivergence;
-                }
-            }
-            RSISignal::Overbought
-        } else {
-            RSISignal::Neutral
+    /// Signal for `rsi`, the latest reading, given recent closes and RSI
+    /// readings (newest last) for divergence detection
+    pub fn get_signal(
+        &self,
+        rsi: f64,
+        prices: &[f64],
+        rsi_values: &[f64],
+        overbought: f64,
+        oversold: f64,
+    ) -> RSISignal {
+        let divergence = detect_rsi_divergence(prices, rsi_values, RSI_DIVERGENCE_SWING_BARS);
+        Self::classify(rsi, divergence, overbought, oversold)
+    }
+
+    /// Classify an RSI reading against overbought/oversold thresholds
+    ///
+    /// A swing divergence from [`detect_rsi_divergence`] takes precedence
+    /// over the thresholds.
+    pub fn classify(
+        rsi: f64,
+        divergence: Option<DivergenceType>,
+        overbought: f64,
+        oversold: f64,
+    ) -> RSISignal {
+        match divergence {
+            Some(DivergenceType::Bullish) => RSISignal::BullishDivergence,
+            Some(DivergenceType::Bearish) => RSISignal::BearishDivergence,
+            None if rsi < oversold => RSISignal::Oversold,
+            None if rsi > overbought => RSISignal::Overbought,
+            None => RSISignal::Neutral,
         }
     }
 
@@ -0,0 +1,164 @@
+//! Stochastic RSI indicator implementations.
+
+use std::collections::VecDeque;
+
+/// %K level below which Stochastic RSI is oversold
+pub const STOCH_RSI_OVERSOLD: f64 = 20.0;
+/// %K level above which Stochastic RSI is overbought
+pub const STOCH_RSI_OVERBOUGHT: f64 = 80.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StochRsiSignal {
+    Oversold,
+    Overbought,
+    /// %K crossed above %D from below the oversold level
+    BullishCross,
+    /// %K crossed below %D from above the overbought level
+    BearishCross,
+    Neutral,
+}
+
+/// Streaming Stochastic RSI over an RSI series.
+///
+/// The raw stochastic is the position of the latest RSI within the highest and
+/// lowest RSI of the last `stoch_period` values, scaled to 0-100. %K is its
+/// SMA over `k_period` and %D is the SMA of %K over `d_period`. A flat RSI
+/// window has no range, so its raw stochastic is reported as 50.
+#[derive(Debug, Clone)]
+pub struct StochasticRsi {
+    stoch_period: usize,
+    k_period: usize,
+    d_period: usize,
+    rsi_history: VecDeque<f64>,
+    raw_history: VecDeque<f64>,
+    k_history: VecDeque<f64>,
+    prev_k: Option<f64>,
+    prev_d: Option<f64>,
+}
+
+impl StochasticRsi {
+    pub fn new(stoch_period: usize, k_period: usize, d_period: usize) -> Self {
+        let stoch_period = stoch_period.max(1);
+        let k_period = k_period.max(1);
+        let d_period = d_period.max(1);
+        Self {
+            stoch_period,
+            k_period,
+            d_period,
+            rsi_history: VecDeque::with_capacity(stoch_period),
+            raw_history: VecDeque::with_capacity(k_period),
+            k_history: VecDeque::with_capacity(d_period),
+            prev_k: None,
+            prev_d: None,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.rsi_history.clear();
+        self.raw_history.clear();
+        self.k_history.clear();
+        self.prev_k = None;
+        self.prev_d = None;
+    }
+
+    /// RSI values needed before the first reading, to fill the stochastic,
+    /// %K and %D windows
+    pub fn lookback(&self) -> usize {
+        self.stoch_period + self.k_period + self.d_period - 2
+    }
+
+    /// Feed the next RSI value; returns `(k, d, signal)` once enough RSI
+    /// values have been seen to fill the stochastic, %K and %D windows
+    pub fn update(&mut self, rsi: f64) -> Option<(f64, f64, StochRsiSignal)> {
+        push_window(&mut self.rsi_history, rsi, self.stoch_period);
+        if self.rsi_history.len() < self.stoch_period {
+            return None;
+        }
+
+        let (lowest, highest) = self
+            .rsi_history
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &v| {
+                (lo.min(v), hi.max(v))
+            });
+        let range = highest - lowest;
+        let raw = if range > f64::EPSILON {
+            (rsi - lowest) / range * 100.0
+        } else {
+            50.0
+        };
+
+        push_window(&mut self.raw_history, raw, self.k_period);
+        if self.raw_history.len() < self.k_period {
+            return None;
+        }
+        let k = mean(&self.raw_history);
+
+        push_window(&mut self.k_history, k, self.d_period);
+        if self.k_history.len() < self.d_period {
+            return None;
+        }
+        let d = mean(&self.k_history);
+
+        let signal = self.get_signal(k, d);
+        self.prev_k = Some(k);
+        self.prev_d = Some(d);
+        Some((k, d, signal))
+    }
+
+    fn get_signal(&self, k: f64, d: f64) -> StochRsiSignal {
+        if let (Some(prev_k), Some(prev_d)) = (self.prev_k, self.prev_d) {
+            if prev_k <= prev_d && k > d && prev_d < STOCH_RSI_OVERSOLD {
+                return StochRsiSignal::BullishCross;
+            }
+            if prev_k >= prev_d && k < d && prev_d > STOCH_RSI_OVERBOUGHT {
+                return StochRsiSignal::BearishCross;
+            }
+        }
+
+        if k < STOCH_RSI_OVERSOLD {
+            StochRsiSignal::Oversold
+        } else if k > STOCH_RSI_OVERBOUGHT {
+            StochRsiSignal::Overbought
+        } else {
+            StochRsiSignal::Neutral
+        }
+    }
+
+    /// Latest `(k, d)`, if the indicator is warmed up
+    pub fn last(&self) -> Option<(f64, f64)> {
+        self.prev_k.zip(self.prev_d)
+    }
+}
+
+impl Default for StochasticRsi {
+    fn default() -> Self {
+        Self::new(14, 3, 3)
+    }
+}
+
+/// Stochastic RSI `(k, d)` for a complete RSI series, or `None` if the
+/// series is too short
+pub fn calculate_stoch_rsi(
+    rsi_values: &[f64],
+    stoch_period: usize,
+    k_period: usize,
+    d_period: usize,
+) -> Option<(f64, f64)> {
+    let mut stoch_rsi = StochasticRsi::new(stoch_period, k_period, d_period);
+    for &rsi in rsi_values {
+        stoch_rsi.update(rsi);
+    }
+    stoch_rsi.last()
+}
+
+fn push_window(window: &mut VecDeque<f64>, value: f64, period: usize) {
+    window.push_back(value);
+    if window.len() > period {
+        window.pop_front();
+    }
+}
+
+fn mean(values: &VecDeque<f64>) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
@@ -0,0 +1,73 @@
+//! Williams %R indicator implementation.
+
+use crate::indicators::structure::donchian::DonchianChannel;
+
+pub const WILLIAMS_R_DEFAULT_PERIOD: usize = 14;
+/// %R level at or above which price is overbought
+pub const WILLIAMS_R_OVERBOUGHT: f64 = -20.0;
+/// %R level at or below which price is oversold
+pub const WILLIAMS_R_OVERSOLD: f64 = -80.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WilliamsRSignal {
+    Overbought,
+    Oversold,
+    Neutral,
+}
+
+/// Streaming Williams %R.
+///
+/// %R is where the close sits within the highest high and lowest low of the
+/// last `period` candles, scaled to `[-100, 0]` (0 at the high, -100 at the
+/// low). The rolling high/low comes from a [`DonchianChannel`]. A flat window
+/// has no range, so its %R is reported as -50.
+#[derive(Debug, Clone)]
+pub struct WilliamsR {
+    channel: DonchianChannel,
+}
+
+impl WilliamsR {
+    pub fn new(period: usize) -> Self {
+        Self {
+            channel: DonchianChannel::new(period),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.channel.reset();
+    }
+
+    /// Candles needed before the first reading
+    pub fn lookback(&self) -> usize {
+        self.channel.lookback()
+    }
+
+    /// Feed the next candle; returns `(%R, signal)` once `period` candles
+    /// have been seen.
+    pub fn update(&mut self, high: f64, low: f64, close: f64) -> Option<(f64, WilliamsRSignal)> {
+        let (highest, _, lowest, _) = self.channel.update(high, low, close)?;
+        let range = highest - lowest;
+        let value = if range > f64::EPSILON {
+            (-100.0 * (highest - close) / range).clamp(-100.0, 0.0)
+        } else {
+            -50.0
+        };
+        Some((value, Self::get_signal(value)))
+    }
+
+    pub fn get_signal(value: f64) -> WilliamsRSignal {
+        if value >= WILLIAMS_R_OVERBOUGHT {
+            WilliamsRSignal::Overbought
+        } else if value <= WILLIAMS_R_OVERSOLD {
+            WilliamsRSignal::Oversold
+        } else {
+            WilliamsRSignal::Neutral
+        }
+    }
+}
+
+impl Default for WilliamsR {
+    fn default() -> Self {
+        Self::new(WILLIAMS_R_DEFAULT_PERIOD)
+    }
+}
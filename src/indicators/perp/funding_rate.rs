@@ -25,6 +25,10 @@ impl FundingRate {
         }
     }
 
+    pub fn reset(&mut self) {
+        self.funding_history.clear();
+    }
+
     pub fn update(&mut self, funding_rate: f64) -> (FundingSignal, f64) {
         self.funding_history.push(funding_rate);
         if self.funding_history.len() > self.lookback {
@@ -1,7 +1,9 @@
-//! Perpetual-specific indicators (open interest, funding rate)
+//! Perpetual-specific indicators (open interest, funding rate, bid-ask spread)
 
 pub mod funding_rate;
 pub mod open_interest;
+pub mod spread;
 
 pub use funding_rate::*;
 pub use open_interest::*;
+pub use spread::*;
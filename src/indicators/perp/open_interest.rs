@@ -1,5 +1,14 @@
 //! Open interest trend detector for perp markets.
 
+use crate::common::math::linear_regression;
+use std::collections::VecDeque;
+
+const DEFAULT_TREND_PERIOD: usize = 20;
+/// Minimum R² for a slope to count as a strong trend
+const STRONG_TREND_R_SQUARED: f64 = 0.7;
+/// Below this R² the OI series is too choppy to call a direction
+const CONSOLIDATION_R_SQUARED: f64 = 0.3;
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum OpenInterestSignal {
     BullishExpansion,
@@ -9,22 +18,57 @@ pub enum OpenInterestSignal {
     Neutral,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OITrendSignal {
+    StrongIncrease,
+    StrongDecrease,
+    WeakIncrease,
+    WeakDecrease,
+    Consolidating,
+}
+
+/// Linear trend of open interest over the trend window
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OpenInterestTrend {
+    /// OI change per update from the least-squares fit
+    pub slope: f64,
+    /// How linear the OI series is; choppy OI gives a low, less reliable value
+    pub r_squared: f64,
+    pub signal: OITrendSignal,
+}
+
 #[derive(Debug, Clone)]
 pub struct OpenInterest {
     prev_oi: Option<f64>,
     prev_price: Option<f64>,
     oi_ema: Option<f64>,
+    history: VecDeque<f64>,
+    trend_period: usize,
 }
 
 impl OpenInterest {
     pub fn new() -> Self {
+        Self::with_trend_period(DEFAULT_TREND_PERIOD)
+    }
+
+    /// Create a detector whose OI trend is fit over the last `trend_period` values
+    pub fn with_trend_period(trend_period: usize) -> Self {
         Self {
             prev_oi: None,
             prev_price: None,
             oi_ema: None,
+            history: VecDeque::with_capacity(trend_period),
+            trend_period: trend_period.max(2),
         }
     }
 
+    pub fn reset(&mut self) {
+        self.prev_oi = None;
+        self.prev_price = None;
+        self.oi_ema = None;
+        self.history.clear();
+    }
+
     pub fn update(&mut self, current_oi: f64, price: f64) -> OpenInterestSignal {
         let oi_ema = match self.oi_ema {
             Some(prev) => prev * 0.8 + current_oi * 0.2,
@@ -32,6 +76,11 @@ impl OpenInterest {
         };
         self.oi_ema = Some(oi_ema);
 
+        self.history.push_back(current_oi);
+        if self.history.len() > self.trend_period {
+            self.history.pop_front();
+        }
+
         let signal = if let (Some(prev_oi), Some(prev_price)) = (self.prev_oi, self.prev_price) {
             let oi_change = current_oi - prev_oi;
             let price_change = price - prev_price;
@@ -72,6 +121,35 @@ impl OpenInterest {
     pub fn smoothed(&self) -> Option<f64> {
         self.oi_ema
     }
+
+    /// OI trend over the window, or `None` until the window is full
+    pub fn trend(&self) -> Option<OpenInterestTrend> {
+        if self.history.len() < self.trend_period {
+            return None;
+        }
+
+        let values: Vec<f64> = self.history.iter().copied().collect();
+        let regression = linear_regression(&values)?;
+        let signal = if regression.r_squared < CONSOLIDATION_R_SQUARED || regression.slope == 0.0 {
+            OITrendSignal::Consolidating
+        } else if regression.r_squared > STRONG_TREND_R_SQUARED {
+            if regression.slope > 0.0 {
+                OITrendSignal::StrongIncrease
+            } else {
+                OITrendSignal::StrongDecrease
+            }
+        } else if regression.slope > 0.0 {
+            OITrendSignal::WeakIncrease
+        } else {
+            OITrendSignal::WeakDecrease
+        };
+
+        Some(OpenInterestTrend {
+            slope: regression.slope,
+            r_squared: regression.r_squared,
+            signal,
+        })
+    }
 }
 
 impl Default for OpenInterest {
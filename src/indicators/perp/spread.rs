@@ -0,0 +1,16 @@
+//! Bid-ask spread from the top of the order book.
+
+/// Basis points in one percent
+pub const BPS_PER_PCT: f64 = 100.0;
+
+/// Spread as a percentage of the mid price, `(ask - bid) / mid × 100`.
+///
+/// Returns `None` for a missing side, non-positive prices, or a crossed book
+/// (`ask < bid`), none of which describe a tradable spread.
+pub fn bid_ask_spread_pct(best_bid: f64, best_ask: f64) -> Option<f64> {
+    if !best_bid.is_finite() || !best_ask.is_finite() || best_bid <= 0.0 || best_ask < best_bid {
+        return None;
+    }
+    let mid = (best_bid + best_ask) / 2.0;
+    Some((best_ask - best_bid) / mid * 100.0)
+}
@@ -1,6 +1,7 @@
 //! Indicator registry and trait system
 
 use crate::config::CategoryWeights;
+use crate::indicators::error::IndicatorError;
 
 /// Indicator category
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -39,6 +40,12 @@ impl IndicatorRegistry {
         Self { weights }
     }
 
+    /// Create a new registry with custom weights, rejecting invalid ones
+    pub fn try_with_weights(weights: CategoryWeights) -> Result<Self, IndicatorError> {
+        weights.validate()?;
+        Ok(Self { weights })
+    }
+
     /// Get category weight (as percentage)
     pub fn category_weight(&self, category: IndicatorCategory) -> f64 {
         match category {
@@ -0,0 +1,74 @@
+//! Candle aggregation: synthesize higher-timeframe candles from a lower one.
+
+use crate::models::indicators::Candle;
+
+/// Minutes in an interval string such as "1m", "15m", "4h" or "1d"
+pub fn interval_minutes(interval: &str) -> Option<u32> {
+    let unit = interval.chars().last()?;
+    let count: u32 = interval[..interval.len() - unit.len_utf8()].parse().ok()?;
+    let minutes = match unit {
+        'm' => count,
+        'h' => count.checked_mul(60)?,
+        'd' => count.checked_mul(24 * 60)?,
+        _ => return None,
+    };
+    (minutes > 0).then_some(minutes)
+}
+
+/// Start of the UTC-aligned bucket of `bucket_secs` containing `candle`
+fn bucket_start(candle: &Candle, bucket_secs: i64) -> i64 {
+    candle.timestamp.timestamp().div_euclid(bucket_secs)
+}
+
+/// Merge `candles` into one candle per `target_minutes` bucket.
+///
+/// Buckets are aligned to the UTC epoch, so a 15m bucket always starts at
+/// :00, :15, :30 or :45. Input must be in chronological order. Each output
+/// candle takes the open of its first candle, the highest high, the lowest
+/// low, the close of its last candle, the summed volume, the last known open
+/// interest and the mean funding rate, and is synthetic only if every candle
+/// in the bucket is. It keeps the timestamp of its last candle, matching the
+/// close-time timestamps of the source series. The final bucket may be
+/// partial when the source ends mid-interval.
+pub fn aggregate_candles(candles: &[Candle], target_minutes: u32) -> Vec<Candle> {
+    let bucket_secs = i64::from(target_minutes.max(1)) * 60;
+    let mut aggregated = Vec::new();
+    let mut start = 0;
+
+    while start < candles.len() {
+        let bucket = bucket_start(&candles[start], bucket_secs);
+        let end = candles[start..]
+            .iter()
+            .position(|candle| bucket_start(candle, bucket_secs) != bucket)
+            .map_or(candles.len(), |offset| start + offset);
+        aggregated.push(merge(&candles[start..end]));
+        start = end;
+    }
+
+    aggregated
+}
+
+/// Combine a non-empty run of candles from the same bucket
+fn merge(group: &[Candle]) -> Candle {
+    let first = &group[0];
+    let last = &group[group.len() - 1];
+
+    let funding_rates: Vec<f64> = group.iter().filter_map(|c| c.funding_rate).collect();
+    let funding_rate = if funding_rates.is_empty() {
+        None
+    } else {
+        Some(funding_rates.iter().sum::<f64>() / funding_rates.len() as f64)
+    };
+
+    Candle {
+        open: first.open,
+        high: group.iter().map(|c| c.high).fold(f64::MIN, f64::max),
+        low: group.iter().map(|c| c.low).fold(f64::MAX, f64::min),
+        close: last.close,
+        volume: group.iter().map(|c| c.volume).sum(),
+        timestamp: last.timestamp,
+        open_interest: group.iter().rev().find_map(|c| c.open_interest),
+        funding_rate,
+        is_synthetic: group.iter().all(|c| c.is_synthetic),
+    }
+}
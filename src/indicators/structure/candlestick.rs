@@ -0,0 +1,223 @@
+//! Candlestick patterns for confirming entries: single-candle hammers,
+//! shooting stars and dojis, two-candle engulfing patterns and three-candle
+//! morning and evening stars.
+
+use crate::models::indicators::Candle;
+
+/// Longest body, as a fraction of the candle's range, that counts as a doji
+pub const DOJI_BODY_RATIO: f64 = 0.05;
+/// How many times the body a hammer's or shooting star's long shadow must exceed
+const LONG_SHADOW_BODY_MULTIPLE: f64 = 2.0;
+/// Longest opposite shadow on a hammer or shooting star, as a fraction of the
+/// range, so tick-sized wicks still count as "no shadow"
+const MAX_OPPOSITE_SHADOW_RATIO: f64 = 0.05;
+/// Largest middle body of a morning or evening star, as a fraction of the
+/// first candle's body
+const STAR_BODY_RATIO: f64 = 0.3;
+
+/// Direction a pattern points to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatternSignal {
+    Bullish,
+    Bearish,
+    /// Indecision, e.g. a doji
+    Neutral,
+}
+
+/// Recognized candlestick patterns
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CandlestickPatternKind {
+    Hammer,
+    ShootingStar,
+    BullishEngulfing,
+    BearishEngulfing,
+    Doji,
+    MorningStar,
+    EveningStar,
+}
+
+impl CandlestickPatternKind {
+    /// Name used as a `signal_state` in strategy conditions
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CandlestickPatternKind::Hammer => "Hammer",
+            CandlestickPatternKind::ShootingStar => "ShootingStar",
+            CandlestickPatternKind::BullishEngulfing => "BullishEngulfing",
+            CandlestickPatternKind::BearishEngulfing => "BearishEngulfing",
+            CandlestickPatternKind::Doji => "Doji",
+            CandlestickPatternKind::MorningStar => "MorningStar",
+            CandlestickPatternKind::EveningStar => "EveningStar",
+        }
+    }
+
+    pub fn signal(&self) -> PatternSignal {
+        match self {
+            CandlestickPatternKind::Hammer
+            | CandlestickPatternKind::BullishEngulfing
+            | CandlestickPatternKind::MorningStar => PatternSignal::Bullish,
+            CandlestickPatternKind::ShootingStar
+            | CandlestickPatternKind::BearishEngulfing
+            | CandlestickPatternKind::EveningStar => PatternSignal::Bearish,
+            CandlestickPatternKind::Doji => PatternSignal::Neutral,
+        }
+    }
+
+    /// 1 for single-candle patterns, 2 for engulfing and 3 for stars
+    pub fn strength(&self) -> u8 {
+        match self {
+            CandlestickPatternKind::Hammer
+            | CandlestickPatternKind::ShootingStar
+            | CandlestickPatternKind::Doji => 1,
+            CandlestickPatternKind::BullishEngulfing | CandlestickPatternKind::BearishEngulfing => {
+                2
+            }
+            CandlestickPatternKind::MorningStar | CandlestickPatternKind::EveningStar => 3,
+        }
+    }
+}
+
+/// A pattern completed on the candle at `index`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CandlestickPattern {
+    pub kind: CandlestickPatternKind,
+    pub signal: PatternSignal,
+    /// 1 (single candle) to 3 (three-candle star)
+    pub strength: u8,
+    /// Index of the pattern's last candle in the input
+    pub index: usize,
+}
+
+impl CandlestickPattern {
+    fn new(kind: CandlestickPatternKind, index: usize) -> Self {
+        Self {
+            kind,
+            signal: kind.signal(),
+            strength: kind.strength(),
+            index,
+        }
+    }
+
+    /// Strength signed by direction: positive for bullish, negative for
+    /// bearish and 0 for neutral patterns
+    pub fn score(&self) -> f64 {
+        match self.signal {
+            PatternSignal::Bullish => f64::from(self.strength),
+            PatternSignal::Bearish => -f64::from(self.strength),
+            PatternSignal::Neutral => 0.0,
+        }
+    }
+}
+
+/// Every pattern in `candles`, by the index of its last candle and strongest
+/// first within a candle. A candle can complete at most one pattern of each
+/// strength.
+pub fn detect_patterns(candles: &[Candle]) -> Vec<CandlestickPattern> {
+    (0..candles.len())
+        .flat_map(|index| patterns_at(candles, index))
+        .collect()
+}
+
+/// Strongest pattern completed on the last candle
+pub fn latest_pattern(candles: &[Candle]) -> Option<CandlestickPattern> {
+    let last = candles.len().checked_sub(1)?;
+    patterns_at(candles, last).into_iter().next()
+}
+
+/// Patterns ending on `candles[index]`, strongest first
+fn patterns_at(candles: &[Candle], index: usize) -> Vec<CandlestickPattern> {
+    let mut patterns = Vec::new();
+    if index >= 2 {
+        if let Some(kind) = star(&candles[index - 2], &candles[index - 1], &candles[index]) {
+            patterns.push(CandlestickPattern::new(kind, index));
+        }
+    }
+    if index >= 1 {
+        if let Some(kind) = engulfing(&candles[index - 1], &candles[index]) {
+            patterns.push(CandlestickPattern::new(kind, index));
+        }
+    }
+    if let Some(kind) = single(&candles[index]) {
+        patterns.push(CandlestickPattern::new(kind, index));
+    }
+    patterns
+}
+
+fn body(candle: &Candle) -> f64 {
+    (candle.close - candle.open).abs()
+}
+
+fn upper_shadow(candle: &Candle) -> f64 {
+    candle.high - candle.open.max(candle.close)
+}
+
+fn lower_shadow(candle: &Candle) -> f64 {
+    candle.open.min(candle.close) - candle.low
+}
+
+fn is_bullish(candle: &Candle) -> bool {
+    candle.close > candle.open
+}
+
+fn is_bearish(candle: &Candle) -> bool {
+    candle.close < candle.open
+}
+
+/// Doji, hammer or shooting star. Dojis take precedence, so a long-legged
+/// doji is not also reported as a hammer.
+fn single(candle: &Candle) -> Option<CandlestickPatternKind> {
+    let range = candle.high - candle.low;
+    if range <= 0.0 {
+        return None;
+    }
+    let body = body(candle);
+    if body < DOJI_BODY_RATIO * range {
+        return Some(CandlestickPatternKind::Doji);
+    }
+    let (upper, lower) = (upper_shadow(candle), lower_shadow(candle));
+    let max_opposite = MAX_OPPOSITE_SHADOW_RATIO * range;
+    if lower > LONG_SHADOW_BODY_MULTIPLE * body && upper <= max_opposite {
+        Some(CandlestickPatternKind::Hammer)
+    } else if upper > LONG_SHADOW_BODY_MULTIPLE * body && lower <= max_opposite {
+        Some(CandlestickPatternKind::ShootingStar)
+    } else {
+        None
+    }
+}
+
+/// A candle whose body covers the opposite-colored body before it
+fn engulfing(previous: &Candle, current: &Candle) -> Option<CandlestickPatternKind> {
+    if body(current) <= body(previous) {
+        return None;
+    }
+    if is_bearish(previous)
+        && is_bullish(current)
+        && current.open <= previous.close
+        && current.close >= previous.open
+    {
+        Some(CandlestickPatternKind::BullishEngulfing)
+    } else if is_bullish(previous)
+        && is_bearish(current)
+        && current.open >= previous.close
+        && current.close <= previous.open
+    {
+        Some(CandlestickPatternKind::BearishEngulfing)
+    } else {
+        None
+    }
+}
+
+/// A long candle, a small-bodied one, then a candle the other way closing
+/// past the middle of the first body
+fn star(first: &Candle, middle: &Candle, last: &Candle) -> Option<CandlestickPatternKind> {
+    if body(middle) > STAR_BODY_RATIO * body(first) {
+        return None;
+    }
+    let first_midpoint = (first.open + first.close) / 2.0;
+    if is_bearish(first) && is_bullish(last) && last.close > first_midpoint {
+        Some(CandlestickPatternKind::MorningStar)
+    } else if is_bullish(first) && is_bearish(last) && last.close < first_midpoint {
+        Some(CandlestickPatternKind::EveningStar)
+    } else {
+        None
+    }
+}
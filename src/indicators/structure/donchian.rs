@@ -0,0 +1,122 @@
+//! Donchian Channel: rolling N-period high/low for breakout strategies.
+
+use std::collections::VecDeque;
+
+pub const DEFAULT_PERIOD: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DonchianSignal {
+    /// Close above the highest high of the previous `period` candles
+    UpperBreakout,
+    /// Close below the lowest low of the previous `period` candles
+    LowerBreakdown,
+    /// No breakout; price is inside the channel
+    Midline,
+}
+
+/// Stateful Donchian Channel.
+///
+/// The rolling high and low are kept in monotone deques of `(index, value)`
+/// pairs, so each candle costs amortized O(1) instead of rescanning the window.
+#[derive(Debug, Clone)]
+pub struct DonchianChannel {
+    period: usize,
+    index: usize,
+    highs: VecDeque<(usize, f64)>,
+    lows: VecDeque<(usize, f64)>,
+}
+
+impl DonchianChannel {
+    pub fn new(period: usize) -> Self {
+        let period = period.max(1);
+        Self {
+            period,
+            index: 0,
+            highs: VecDeque::with_capacity(period),
+            lows: VecDeque::with_capacity(period),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.index = 0;
+        self.highs.clear();
+        self.lows.clear();
+    }
+
+    /// Candles needed before the first reading
+    pub fn lookback(&self) -> usize {
+        self.period
+    }
+
+    /// Feed the next candle; returns `(upper, midline, lower, signal)` once
+    /// `period` candles have been seen.
+    ///
+    /// The bands include the current candle, while the signal compares the
+    /// close against the channel formed by the candles before it (a close can
+    /// never break out of a channel that already contains its own high/low).
+    pub fn update(
+        &mut self,
+        high: f64,
+        low: f64,
+        close: f64,
+    ) -> Option<(f64, f64, f64, DonchianSignal)> {
+        let previous = self.upper().zip(self.lower());
+
+        // Drop the candle that falls out of the window
+        let start = (self.index + 1).saturating_sub(self.period);
+        if self.highs.front().is_some_and(|&(i, _)| i < start) {
+            self.highs.pop_front();
+        }
+        if self.lows.front().is_some_and(|&(i, _)| i < start) {
+            self.lows.pop_front();
+        }
+
+        while self.highs.back().is_some_and(|&(_, h)| h <= high) {
+            self.highs.pop_back();
+        }
+        self.highs.push_back((self.index, high));
+        while self.lows.back().is_some_and(|&(_, l)| l >= low) {
+            self.lows.pop_back();
+        }
+        self.lows.push_back((self.index, low));
+        self.index += 1;
+
+        if self.index < self.period {
+            return None;
+        }
+
+        let signal = match previous {
+            Some((prev_upper, _)) if close > prev_upper => DonchianSignal::UpperBreakout,
+            Some((_, prev_lower)) if close < prev_lower => DonchianSignal::LowerBreakdown,
+            _ => DonchianSignal::Midline,
+        };
+        let upper = self.upper()?;
+        let lower = self.lower()?;
+
+        Some((upper, (upper + lower) / 2.0, lower, signal))
+    }
+
+    /// Highest high in the current window
+    pub fn upper(&self) -> Option<f64> {
+        self.highs.front().map(|&(_, high)| high)
+    }
+
+    /// Lowest low in the current window
+    pub fn lower(&self) -> Option<f64> {
+        self.lows.front().map(|&(_, low)| low)
+    }
+
+    pub fn midline(&self) -> Option<f64> {
+        Some((self.upper()? + self.lower()?) / 2.0)
+    }
+
+    pub fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Default for DonchianChannel {
+    fn default() -> Self {
+        Self::new(DEFAULT_PERIOD)
+    }
+}
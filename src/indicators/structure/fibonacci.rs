@@ -0,0 +1,144 @@
+//! Fibonacci retracement levels between the latest swing high and swing low.
+
+use crate::models::indicators::Candle;
+
+/// Standard retracement ratios, from the end of the swing back to its start
+pub const FIB_RATIOS: [f64; 7] = [0.0, 0.236, 0.382, 0.5, 0.618, 0.786, 1.0];
+/// Bars on each side a pivot must exceed to count as a swing point
+pub const DEFAULT_PIVOT_BARS: usize = 5;
+
+/// Direction of the swing the levels retrace
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwingDirection {
+    /// Low then high; levels retrace down from the high
+    Up,
+    /// High then low; levels retrace up from the low
+    Down,
+}
+
+/// Retracement levels for one swing.
+///
+/// `levels[i]` is the price at `FIB_RATIOS[i]`: the 0.0 level sits at the end
+/// of the swing and the 1.0 level at its start.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FibLevels {
+    pub swing_high: f64,
+    pub swing_low: f64,
+    pub direction: SwingDirection,
+    pub levels: [f64; FIB_RATIOS.len()],
+}
+
+impl FibLevels {
+    fn new(swing_high: f64, swing_low: f64, direction: SwingDirection) -> Self {
+        let (swing_high, swing_low) = (swing_high.max(swing_low), swing_high.min(swing_low));
+        let range = swing_high - swing_low;
+        let levels = FIB_RATIOS.map(|ratio| match direction {
+            SwingDirection::Up => swing_high - ratio * range,
+            SwingDirection::Down => swing_low + ratio * range,
+        });
+        Self {
+            swing_high,
+            swing_low,
+            direction,
+            levels,
+        }
+    }
+
+    /// `(price_level, fib_ratio)` pairs, in `FIB_RATIOS` order
+    pub fn iter(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        self.levels.iter().copied().zip(FIB_RATIOS)
+    }
+
+    /// How far `price` has retraced the swing, as a ratio: 0.0 at the end of
+    /// the swing, 1.0 at its start, outside [0, 1] beyond them. `None` for a
+    /// flat swing.
+    pub fn position_ratio(&self, price: f64) -> Option<f64> {
+        let range = self.swing_high - self.swing_low;
+        if range <= f64::EPSILON {
+            return None;
+        }
+        Some(match self.direction {
+            SwingDirection::Up => (self.swing_high - price) / range,
+            SwingDirection::Down => (price - self.swing_low) / range,
+        })
+    }
+}
+
+/// Fibonacci retracements with swings found by `pivot_bars`-bar pivot
+/// detection.
+///
+/// A candle is a pivot high when its high is above the highs of the
+/// `pivot_bars` candles before it and not below those of the `pivot_bars`
+/// after it (pivot lows mirror this), so a pivot is only confirmed
+/// `pivot_bars` candles later.
+#[derive(Debug, Clone)]
+pub struct FibonacciLevels {
+    pivot_bars: usize,
+}
+
+impl FibonacciLevels {
+    pub fn new(pivot_bars: usize) -> Self {
+        Self {
+            pivot_bars: pivot_bars.max(1),
+        }
+    }
+
+    /// Levels for an upswing from `swing_low` to `swing_high`
+    pub fn calculate(swing_high: f64, swing_low: f64) -> FibLevels {
+        FibLevels::new(swing_high, swing_low, SwingDirection::Up)
+    }
+
+    /// Levels for a downswing from `swing_high` to `swing_low`
+    pub fn calculate_downswing(swing_high: f64, swing_low: f64) -> FibLevels {
+        FibLevels::new(swing_high, swing_low, SwingDirection::Down)
+    }
+
+    /// Highest level at or below `price`, as `(price_level, fib_ratio)`
+    pub fn nearest_support(price: f64, levels: &FibLevels) -> Option<(f64, f64)> {
+        levels
+            .iter()
+            .filter(|&(level, _)| level <= price)
+            .max_by(|a, b| a.0.total_cmp(&b.0))
+    }
+
+    /// Lowest level above `price`, as `(price_level, fib_ratio)`
+    pub fn nearest_resistance(price: f64, levels: &FibLevels) -> Option<(f64, f64)> {
+        levels
+            .iter()
+            .filter(|&(level, _)| level > price)
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+    }
+
+    /// Levels for the swing between the latest confirmed pivot high and pivot
+    /// low; the later of the two is the end of the swing. `None` until both
+    /// have been seen.
+    pub fn detect(&self, candles: &[Candle]) -> Option<FibLevels> {
+        let high = self.last_pivot(candles, |c| c.high)?;
+        let low = self.last_pivot(candles, |c| -c.low)?;
+        let (swing_high, swing_low) = (candles[high].high, candles[low].low);
+        Some(if low < high {
+            Self::calculate(swing_high, swing_low)
+        } else {
+            Self::calculate_downswing(swing_high, swing_low)
+        })
+    }
+
+    /// Index of the latest pivot maximum of `value`
+    fn last_pivot(&self, candles: &[Candle], value: impl Fn(&Candle) -> f64) -> Option<usize> {
+        let n = self.pivot_bars;
+        if candles.len() < 2 * n + 1 {
+            return None;
+        }
+        (n..candles.len() - n).rev().find(|&i| {
+            let pivot = value(&candles[i]);
+            candles[i - n..i].iter().all(|c| value(c) < pivot)
+                && candles[i + 1..=i + n].iter().all(|c| value(c) <= pivot)
+        })
+    }
+}
+
+impl Default for FibonacciLevels {
+    fn default() -> Self {
+        Self::new(DEFAULT_PIVOT_BARS)
+    }
+}
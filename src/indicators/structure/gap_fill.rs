@@ -0,0 +1,82 @@
+//! Gap filling: synthesize candles missed during a brief feed outage.
+
+use crate::models::indicators::Candle;
+use chrono::Duration;
+
+/// Most synthetic candles inserted into a single gap unless configured
+/// otherwise; longer gaps are left as they are
+pub const DEFAULT_MAX_GAP_FILL_CANDLES: usize = 10;
+
+/// Fill gaps of up to [`DEFAULT_MAX_GAP_FILL_CANDLES`] missing candles.
+///
+/// See [`fill_candle_gaps_up_to`].
+pub fn fill_candle_gaps(candles: Vec<Candle>, interval_seconds: u64) -> Vec<Candle> {
+    fill_candle_gaps_up_to(candles, interval_seconds, DEFAULT_MAX_GAP_FILL_CANDLES)
+}
+
+/// Insert synthetic candles wherever consecutive timestamps are more than
+/// 1.5 × `interval_seconds` apart.
+///
+/// Input must be in chronological order. Missing candles are spaced one
+/// interval apart from the candle before the gap, and each OHLCV field (and
+/// open interest and funding rate when both sides have one) is linearly
+/// interpolated between the candles either side by time. Synthetic candles
+/// are marked `is_synthetic`. Gaps needing more than `max_gap_fill_candles`
+/// candles are too long to guess at and are left unfilled.
+pub fn fill_candle_gaps_up_to(
+    candles: Vec<Candle>,
+    interval_seconds: u64,
+    max_gap_fill_candles: usize,
+) -> Vec<Candle> {
+    if interval_seconds == 0 || max_gap_fill_candles == 0 || candles.len() < 2 {
+        return candles;
+    }
+    let interval = interval_seconds as f64;
+
+    let mut filled = Vec::with_capacity(candles.len());
+    let mut candles = candles.into_iter();
+    let mut previous = candles.next().expect("at least two candles");
+    for next in candles {
+        let gap_seconds = (next.timestamp - previous.timestamp).num_milliseconds() as f64 / 1000.0;
+        let missing = if gap_seconds > 1.5 * interval {
+            (gap_seconds / interval).round() as usize - 1
+        } else {
+            0
+        };
+
+        filled.push(previous.clone());
+        if (1..=max_gap_fill_candles).contains(&missing) {
+            for step in 1..=missing {
+                let offset_seconds = step as f64 * interval;
+                filled.push(interpolate(
+                    &previous,
+                    &next,
+                    offset_seconds / gap_seconds,
+                    Duration::seconds((step as u64 * interval_seconds) as i64),
+                ));
+            }
+        }
+        previous = next;
+    }
+    filled.push(previous);
+
+    filled
+}
+
+/// Synthetic candle `fraction` of the way from `before` to `after`, at
+/// `offset` past `before`
+fn interpolate(before: &Candle, after: &Candle, fraction: f64, offset: Duration) -> Candle {
+    let lerp = |a: f64, b: f64| a + (b - a) * fraction;
+    let lerp_optional = |a: Option<f64>, b: Option<f64>| Some(lerp(a?, b?));
+    Candle {
+        open: lerp(before.open, after.open),
+        high: lerp(before.high, after.high),
+        low: lerp(before.low, after.low),
+        close: lerp(before.close, after.close),
+        volume: lerp(before.volume, after.volume),
+        timestamp: before.timestamp + offset,
+        open_interest: lerp_optional(before.open_interest, after.open_interest),
+        funding_rate: lerp_optional(before.funding_rate, after.funding_rate),
+        is_synthetic: true,
+    }
+}
@@ -0,0 +1,51 @@
+//! Heikin-Ashi candles: averaged OHLC that smooths out bar-to-bar noise.
+
+use crate::models::indicators::Candle;
+
+/// Transform candles to Heikin-Ashi, keeping open interest and funding rate.
+///
+/// See [`transform_to_heikin_ashi_with`].
+pub fn transform_to_heikin_ashi(candles: &[Candle]) -> Vec<Candle> {
+    transform_to_heikin_ashi_with(candles, true)
+}
+
+/// Transform chronologically ordered candles to Heikin-Ashi.
+///
+/// - HA-Close = (O + H + L + C) / 4
+/// - HA-Open = (previous HA-Open + previous HA-Close) / 2, seeded with
+///   (O + C) / 2 for the first candle
+/// - HA-High = max(H, HA-Open, HA-Close)
+/// - HA-Low = min(L, HA-Open, HA-Close)
+///
+/// Volume, timestamp and `is_synthetic` carry over unchanged. Heikin-Ashi
+/// does not touch open interest or funding rate, so they are kept when
+/// `preserve_open_interest_funding_rate` is set and cleared otherwise.
+pub fn transform_to_heikin_ashi_with(
+    candles: &[Candle],
+    preserve_open_interest_funding_rate: bool,
+) -> Vec<Candle> {
+    let mut transformed: Vec<Candle> = Vec::with_capacity(candles.len());
+    for candle in candles {
+        let close = (candle.open + candle.high + candle.low + candle.close) / 4.0;
+        let open = match transformed.last() {
+            Some(previous) => (previous.open + previous.close) / 2.0,
+            None => (candle.open + candle.close) / 2.0,
+        };
+        transformed.push(Candle {
+            open,
+            high: candle.high.max(open).max(close),
+            low: candle.low.min(open).min(close),
+            close,
+            volume: candle.volume,
+            timestamp: candle.timestamp,
+            open_interest: candle
+                .open_interest
+                .filter(|_| preserve_open_interest_funding_rate),
+            funding_rate: candle
+                .funding_rate
+                .filter(|_| preserve_open_interest_funding_rate),
+            is_synthetic: candle.is_synthetic,
+        });
+    }
+    transformed
+}
@@ -0,0 +1,19 @@
+//! Market structure indicators (VWAP, Donchian Channel, Fibonacci retracements,
+//! candlestick patterns), candle aggregation, gap filling and Heikin-Ashi
+//! candles
+
+pub mod candle_aggregator;
+pub mod candlestick;
+pub mod donchian;
+pub mod fibonacci;
+pub mod gap_fill;
+pub mod heikin_ashi;
+pub mod vwap;
+
+pub use candle_aggregator::*;
+pub use candlestick::*;
+pub use donchian::*;
+pub use fibonacci::*;
+pub use gap_fill::*;
+pub use heikin_ashi::*;
+pub use vwap::*;
@@ -0,0 +1,93 @@
+//! Volume Weighted Average Price with a daily (UTC) session reset.
+
+use chrono::{DateTime, NaiveDate, Utc};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VwapSignal {
+    PriceAbove,
+    PriceBelow,
+    /// Price moved to the other side of VWAP on this update
+    Crossing,
+}
+
+/// Intraday VWAP.
+///
+/// Accumulates `close * volume` and volume for the current UTC day; the first
+/// update with a timestamp on a later day starts a new session. VWAP is
+/// undefined until the session has seen some volume.
+#[derive(Debug, Clone)]
+pub struct VWAP {
+    session: Option<NaiveDate>,
+    cumulative_price_volume: f64,
+    cumulative_volume: f64,
+    prev_side: Option<f64>,
+}
+
+impl VWAP {
+    pub fn new() -> Self {
+        Self {
+            session: None,
+            cumulative_price_volume: 0.0,
+            cumulative_volume: 0.0,
+            prev_side: None,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.session = None;
+        self.cumulative_price_volume = 0.0;
+        self.cumulative_volume = 0.0;
+        self.prev_side = None;
+    }
+
+    pub fn update(
+        &mut self,
+        timestamp: DateTime<Utc>,
+        close: f64,
+        volume: f64,
+    ) -> Option<(f64, VwapSignal)> {
+        let day = timestamp.date_naive();
+        if self.session != Some(day) {
+            self.reset();
+            self.session = Some(day);
+        }
+
+        if close.is_finite() && volume.is_finite() && volume > 0.0 {
+            self.cumulative_price_volume += close * volume;
+            self.cumulative_volume += volume;
+        }
+
+        let vwap = self.value()?;
+        let side = (close - vwap).signum();
+        let signal = match self.prev_side {
+            Some(prev) if prev != side && close != vwap => VwapSignal::Crossing,
+            _ if close >= vwap => VwapSignal::PriceAbove,
+            _ => VwapSignal::PriceBelow,
+        };
+        if close != vwap {
+            self.prev_side = Some(side);
+        }
+
+        Some((vwap, signal))
+    }
+
+    /// VWAP of the current session, if it has seen any volume
+    pub fn value(&self) -> Option<f64> {
+        if self.cumulative_volume > 0.0 {
+            Some(self.cumulative_price_volume / self.cumulative_volume)
+        } else {
+            None
+        }
+    }
+
+    /// UTC day of the current session
+    pub fn session(&self) -> Option<NaiveDate> {
+        self.session
+    }
+}
+
+impl Default for VWAP {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,126 @@
+//! ADX (Average Directional Index) for trend strength, regardless of direction.
+
+use crate::common::math;
+
+pub const ADX_DEFAULT_PERIOD: usize = 14;
+/// ADX above which a market is conventionally considered trending
+pub const ADX_TRENDING_THRESHOLD: f64 = 25.0;
+
+/// Stateful Wilder ADX.
+///
+/// Each candle contributes a true range and a +DM/-DM (the larger of the up
+/// and down moves, if positive). Both are Wilder-smoothed over `period` and
+/// turned into +DI/-DI; DX is their normalized spread and ADX is DX smoothed
+/// again over `period`. The first ADX is available after `2 × period`
+/// candles.
+#[derive(Debug, Clone)]
+pub struct ADX {
+    period: usize,
+    /// `(high, low, close)` of the previous candle
+    prev: Option<(f64, f64, f64)>,
+    /// Candles seen since the first one
+    samples: usize,
+    smoothed_tr: f64,
+    smoothed_plus_dm: f64,
+    smoothed_minus_dm: f64,
+    dx_sum: f64,
+    adx: Option<f64>,
+}
+
+impl ADX {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period: period.max(1),
+            prev: None,
+            samples: 0,
+            smoothed_tr: 0.0,
+            smoothed_plus_dm: 0.0,
+            smoothed_minus_dm: 0.0,
+            dx_sum: 0.0,
+            adx: None,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new(self.period);
+    }
+
+    /// Candles needed before the first reading: DX readings start one
+    /// candle in, and the first ADX averages `period` of them
+    pub fn lookback(&self) -> usize {
+        2 * self.period
+    }
+
+    /// Feed the next candle; returns `(adx, plus_di, minus_di)` once enough
+    /// candles have been seen
+    pub fn update(&mut self, high: f64, low: f64, close: f64) -> Option<(f64, f64, f64)> {
+        let (prev_high, prev_low, prev_close) = self.prev.replace((high, low, close))?;
+
+        let true_range = math::true_range(high, low, prev_close);
+        let up_move = high - prev_high;
+        let down_move = prev_low - low;
+        let plus_dm = if up_move > down_move && up_move > 0.0 {
+            up_move
+        } else {
+            0.0
+        };
+        let minus_dm = if down_move > up_move && down_move > 0.0 {
+            down_move
+        } else {
+            0.0
+        };
+
+        let period = self.period as f64;
+        self.samples += 1;
+        if self.samples <= self.period {
+            // Seed the smoothed sums with plain sums over the first period
+            self.smoothed_tr += true_range;
+            self.smoothed_plus_dm += plus_dm;
+            self.smoothed_minus_dm += minus_dm;
+            if self.samples < self.period {
+                return None;
+            }
+        } else {
+            self.smoothed_tr += true_range - self.smoothed_tr / period;
+            self.smoothed_plus_dm += plus_dm - self.smoothed_plus_dm / period;
+            self.smoothed_minus_dm += minus_dm - self.smoothed_minus_dm / period;
+        }
+
+        let (plus_di, minus_di) = if self.smoothed_tr > f64::EPSILON {
+            (
+                100.0 * self.smoothed_plus_dm / self.smoothed_tr,
+                100.0 * self.smoothed_minus_dm / self.smoothed_tr,
+            )
+        } else {
+            (0.0, 0.0)
+        };
+        let di_sum = plus_di + minus_di;
+        let dx = if di_sum > f64::EPSILON {
+            100.0 * (plus_di - minus_di).abs() / di_sum
+        } else {
+            0.0
+        };
+
+        // DX readings start at sample `period`; the first ADX averages `period` of them
+        let dx_count = self.samples + 1 - self.period;
+        self.adx = match self.adx {
+            Some(adx) => Some((adx * (period - 1.0) + dx) / period),
+            None => {
+                self.dx_sum += dx;
+                (dx_count == self.period).then(|| self.dx_sum / period)
+            }
+        };
+
+        self.adx.map(|adx| (adx, plus_di, minus_di))
+    }
+
+    pub fn value(&self) -> Option<f64> {
+        self.adx
+    }
+}
+
+impl Default for ADX {
+    fn default() -> Self {
+        Self::new(ADX_DEFAULT_PERIOD)
+    }
+}
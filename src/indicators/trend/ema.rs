@@ -4,11 +4,16 @@ use crate::common::math;
 use crate::models::indicators::{Candle, EmaIndicator};
 
 /// Stateful EMA calculator that can be updated tick-by-tick.
+///
+/// The first `period` values seed the EMA with their simple average, matching
+/// `math::ema`; no value is produced until then.
 #[derive(Debug, Clone)]
 pub struct EMA {
     period: usize,
     multiplier: f64,
-    current_ema: Option<f64>,
+    value: Option<f64>,
+    count: usize,
+    seed_sum: f64,
 }
 
 impl EMA {
@@ -18,28 +23,57 @@ impl EMA {
         Self {
             period,
             multiplier,
-            current_ema: None,
+            value: None,
+            count: 0,
+            seed_sum: 0.0,
         }
     }
 
-    /// Update the EMA with the latest price and return the computed value.
-    pub fn update(&mut self, price: f64) -> f64 {
-        match self.current_ema {
-            None => {
-                self.current_ema = Some(price);
-                price
-            }
+    /// Clear the running EMA so the next `period` updates re-seed it.
+    pub fn reset(&mut self) {
+        self.value = None;
+        self.count = 0;
+        self.seed_sum = 0.0;
+    }
+
+    /// Candles needed before the first reading
+    pub fn lookback(&self) -> usize {
+        self.period.max(1)
+    }
+
+    /// Update the EMA with the latest value.
+    ///
+    /// Returns `None` until `period` values have been seen.
+    pub fn update(&mut self, value: f64) -> Option<f64> {
+        self.count += 1;
+        match self.value {
             Some(prev) => {
-                let ema = (price * self.multiplier) + (prev * (1.0 - self.multiplier));
-                self.current_ema = Some(ema);
-                ema
+                let ema = (value * self.multiplier) + (prev * (1.0 - self.multiplier));
+                self.value = Some(ema);
+            }
+            None => {
+                self.seed_sum += value;
+                if self.count >= self.period.max(1) {
+                    self.value = Some(self.seed_sum / self.count as f64);
+                }
             }
         }
+        self.value
     }
 
     /// Get the last computed EMA value.
     pub fn get(&self) -> Option<f64> {
-        self.current_ema
+        self.value
+    }
+
+    /// Number of values seen since creation or the last reset.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Whether the SMA seed period has completed.
+    pub fn is_ready(&self) -> bool {
+        self.value.is_some()
     }
 
     /// Access the configured period.
@@ -53,6 +87,12 @@ impl EMA {
 pub enum EMATrendSignal {
     BullishCross,
     BearishCross,
+    /// Fast EMA crossed above the slow EMA but has not yet held there for
+    /// the confirmation candles
+    PendingBullishCross,
+    /// Fast EMA crossed below the slow EMA but has not yet held there for
+    /// the confirmation candles
+    PendingBearishCross,
     StrongUptrend,
     StrongDowntrend,
     Neutral,
@@ -65,6 +105,10 @@ pub struct EMACrossover {
     ema_slow: EMA,
     prev_fast: Option<f64>,
     prev_slow: Option<f64>,
+    confirmation_candles: u32,
+    /// Direction of an unconfirmed cross (`true` for bullish) and the
+    /// candles it has held since
+    pending: Option<(bool, u32)>,
 }
 
 impl EMACrossover {
@@ -75,19 +119,46 @@ impl EMACrossover {
             ema_slow: EMA::new(slow_period),
             prev_fast: None,
             prev_slow: None,
+            confirmation_candles: 0,
+            pending: None,
         }
     }
 
+    /// Delay cross signals until the fast EMA has stayed on its new side of
+    /// the slow EMA for `n` more candles. The cross candle and those before
+    /// confirmation emit `PendingBullishCross`/`PendingBearishCross`; a cross
+    /// back cancels it. `0` emits crosses immediately.
+    pub fn with_confirmation_candles(mut self, n: u32) -> Self {
+        self.confirmation_candles = n;
+        self
+    }
+
+    /// Reset both EMAs and the previous crossover state.
+    pub fn reset(&mut self) {
+        self.ema_fast.reset();
+        self.ema_slow.reset();
+        self.prev_fast = None;
+        self.prev_slow = None;
+        self.pending = None;
+    }
+
     /// Update both EMAs with the latest price and classify the trend state.
+    ///
+    /// Returns `Neutral` until both EMAs have finished seeding.
     pub fn update(&mut self, price: f64) -> EMATrendSignal {
         let fast = self.ema_fast.update(price);
         let slow = self.ema_slow.update(price);
+        let (Some(fast), Some(slow)) = (fast, slow) else {
+            return EMATrendSignal::Neutral;
+        };
 
         let signal = if let (Some(prev_fast), Some(prev_slow)) = (self.prev_fast, self.prev_slow) {
             if prev_fast <= prev_slow && fast > slow {
-                EMATrendSignal::BullishCross
+                self.start_cross(true)
             } else if prev_fast >= prev_slow && fast < slow {
-                EMATrendSignal::BearishCross
+                self.start_cross(false)
+            } else if let Some(signal) = self.confirm_cross(fast, slow) {
+                signal
             } else if price > fast && fast > slow && (fast - prev_fast) > 0.0 {
                 EMATrendSignal::StrongUptrend
             } else if price < fast && fast < slow && (fast - prev_fast) < 0.0 {
@@ -104,14 +175,196 @@ impl EMACrossover {
         signal
     }
 
+    /// Signal for a fresh cross, which replaces any pending one
+    fn start_cross(&mut self, bullish: bool) -> EMATrendSignal {
+        if self.confirmation_candles == 0 {
+            self.pending = None;
+            return Self::cross_signal(bullish, true);
+        }
+        self.pending = Some((bullish, 0));
+        Self::cross_signal(bullish, false)
+    }
+
+    /// Advance a pending cross while the fast EMA holds its side; `None`
+    /// when there is none or the EMAs have met again
+    fn confirm_cross(&mut self, fast: f64, slow: f64) -> Option<EMATrendSignal> {
+        let (bullish, held) = self.pending.take()?;
+        let holding = if bullish { fast > slow } else { fast < slow };
+        if !holding {
+            return None;
+        }
+        let held = held + 1;
+        if held >= self.confirmation_candles {
+            return Some(Self::cross_signal(bullish, true));
+        }
+        self.pending = Some((bullish, held));
+        Some(Self::cross_signal(bullish, false))
+    }
+
+    fn cross_signal(bullish: bool, confirmed: bool) -> EMATrendSignal {
+        match (bullish, confirmed) {
+            (true, true) => EMATrendSignal::BullishCross,
+            (false, true) => EMATrendSignal::BearishCross,
+            (true, false) => EMATrendSignal::PendingBullishCross,
+            (false, false) => EMATrendSignal::PendingBearishCross,
+        }
+    }
+
     /// Latest fast EMA value.
     pub fn fast(&self) -> Option<f64> {
-        self.prev_fast
+        self.ema_fast.get()
     }
 
     /// Latest slow EMA value.
     pub fn slow(&self) -> Option<f64> {
-        self.prev_slow
+        self.ema_slow.get()
+    }
+
+    /// The underlying fast EMA.
+    pub fn fast_ema(&self) -> &EMA {
+        &self.ema_fast
+    }
+
+    /// The underlying slow EMA.
+    pub fn slow_ema(&self) -> &EMA {
+        &self.ema_slow
+    }
+}
+
+/// Fibonacci EMA periods used by [`EmaRibbon::default`]
+pub const EMA_RIBBON_PERIODS: [usize; 6] = [8, 13, 21, 34, 55, 89];
+/// Change in ribbon width, as a fraction of price, below which the ribbon is flat
+pub const EMA_RIBBON_FLAT_TOLERANCE: f64 = 0.0001;
+
+/// Ribbon state; full alignment takes precedence over the width trend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RibbonSignal {
+    /// Every EMA sits above the next slower one
+    StrongBull,
+    /// Every EMA sits below the next slower one
+    StrongBear,
+    /// Partially aligned and fanning out
+    Expanding,
+    /// Partially aligned and converging
+    Contracting,
+    Flat,
+}
+
+/// Ribbon of EMAs whose ordering scores trend strength.
+///
+/// The alignment score compares each EMA with the next slower one: `+1` for
+/// every pair where the faster EMA is above, `-1` where it is below, averaged
+/// over all pairs. A strong uptrend stacks the EMAs fastest-on-top and scores
+/// `+1.0`, a strong downtrend `-1.0`, and partial alignment falls in between.
+/// Width is the spread between the highest and lowest EMA as a fraction of
+/// price. Each update is O(number of EMAs).
+#[derive(Debug, Clone)]
+pub struct EmaRibbon {
+    /// Sorted fastest first
+    emas: Vec<EMA>,
+    score: Option<f64>,
+    width: Option<f64>,
+}
+
+impl EmaRibbon {
+    /// Ribbon over `periods`, sorted and deduplicated
+    pub fn new(periods: &[usize]) -> Self {
+        let mut periods = periods.to_vec();
+        periods.sort_unstable();
+        periods.dedup();
+        Self {
+            emas: periods.into_iter().map(EMA::new).collect(),
+            score: None,
+            width: None,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.emas.iter_mut().for_each(EMA::reset);
+        self.score = None;
+        self.width = None;
+    }
+
+    /// Candles needed before the first reading
+    pub fn lookback(&self) -> usize {
+        self.emas.iter().map(EMA::lookback).max().unwrap_or(1)
+    }
+
+    /// Update every EMA with the latest price and return `(score, signal)`
+    /// once the slowest EMA has seeded.
+    pub fn update(&mut self, price: f64) -> Option<(f64, RibbonSignal)> {
+        let mut ready = true;
+        for ema in &mut self.emas {
+            ready &= ema.update(price).is_some();
+        }
+        if !ready {
+            return None;
+        }
+
+        let mut aligned = 0.0;
+        let (mut lowest, mut highest) = (f64::INFINITY, f64::NEG_INFINITY);
+        let mut faster: Option<f64> = None;
+        for value in self.emas.iter().filter_map(EMA::get) {
+            if let Some(faster) = faster {
+                aligned += match faster.partial_cmp(&value) {
+                    Some(std::cmp::Ordering::Greater) => 1.0,
+                    Some(std::cmp::Ordering::Less) => -1.0,
+                    _ => 0.0,
+                };
+            }
+            lowest = lowest.min(value);
+            highest = highest.max(value);
+            faster = Some(value);
+        }
+
+        let pairs = self.emas.len().saturating_sub(1);
+        let score = if pairs == 0 {
+            0.0
+        } else {
+            aligned / pairs as f64
+        };
+        let width = if price.abs() > f64::EPSILON {
+            (highest - lowest) / price.abs()
+        } else {
+            0.0
+        };
+
+        let signal = if score >= 1.0 {
+            RibbonSignal::StrongBull
+        } else if score <= -1.0 {
+            RibbonSignal::StrongBear
+        } else {
+            match self.width {
+                Some(prev) if width - prev > EMA_RIBBON_FLAT_TOLERANCE => RibbonSignal::Expanding,
+                Some(prev) if prev - width > EMA_RIBBON_FLAT_TOLERANCE => RibbonSignal::Contracting,
+                _ => RibbonSignal::Flat,
+            }
+        };
+
+        self.score = Some(score);
+        self.width = Some(width);
+        Some((score, signal))
+    }
+
+    /// Latest alignment score in `[-1, 1]`
+    pub fn score(&self) -> Option<f64> {
+        self.score
+    }
+
+    /// Latest spread between the highest and lowest EMA as a fraction of price
+    pub fn width(&self) -> Option<f64> {
+        self.width
+    }
+
+    /// Latest EMA values, fastest first (`None` while seeding)
+    pub fn values(&self) -> Vec<Option<f64>> {
+        self.emas.iter().map(EMA::get).collect()
+    }
+}
+
+impl Default for EmaRibbon {
+    fn default() -> Self {
+        Self::new(&EMA_RIBBON_PERIODS)
     }
 }
 
@@ -0,0 +1,176 @@
+//! Ichimoku Cloud (Ichimoku Kinko Hyo) trend indicator.
+
+use std::collections::VecDeque;
+
+pub const TENKAN_PERIOD: usize = 9;
+pub const KIJUN_PERIOD: usize = 26;
+pub const SENKOU_B_PERIOD: usize = 52;
+/// Bars the Senkou spans are projected forward (and Chikou shifted back)
+pub const DISPLACEMENT: usize = 26;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IchimokuSignal {
+    PriceAboveCloud,
+    PriceBelowCloud,
+    /// Tenkan-sen crossed Kijun-sen on this bar
+    TKCross,
+    /// Senkou Span A crossed Senkou Span B, flipping the projected cloud
+    CloudTwist,
+    /// Price inside the cloud, or no cloud has been projected onto this bar yet
+    Neutral,
+}
+
+/// Ichimoku readings for one bar.
+///
+/// `senkou_span_a`/`senkou_span_b` are the leading spans computed on this bar,
+/// which plot `DISPLACEMENT` bars ahead. The cloud under the current bar is the
+/// pair projected from `DISPLACEMENT` bars ago (`cloud_span_a`/`cloud_span_b`).
+/// `chikou_span` is this bar's close, which plots `DISPLACEMENT` bars back.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IchimokuIndicator {
+    pub tenkan_sen: f64,
+    pub kijun_sen: f64,
+    pub senkou_span_a: f64,
+    pub senkou_span_b: f64,
+    pub chikou_span: f64,
+    pub cloud_span_a: Option<f64>,
+    pub cloud_span_b: Option<f64>,
+    pub signal: IchimokuSignal,
+}
+
+#[derive(Debug, Clone)]
+pub struct Ichimoku {
+    tenkan_period: usize,
+    kijun_period: usize,
+    senkou_b_period: usize,
+    displacement: usize,
+    bars: VecDeque<(f64, f64)>,
+    leading_spans: VecDeque<(f64, f64)>,
+    prev_tk_diff: Option<f64>,
+    prev_span_diff: Option<f64>,
+}
+
+impl Ichimoku {
+    pub fn new(
+        tenkan_period: usize,
+        kijun_period: usize,
+        senkou_b_period: usize,
+        displacement: usize,
+    ) -> Self {
+        let tenkan_period = tenkan_period.max(1);
+        let kijun_period = kijun_period.max(1);
+        let senkou_b_period = senkou_b_period.max(1);
+        let lookback = tenkan_period.max(kijun_period).max(senkou_b_period);
+        Self {
+            tenkan_period,
+            kijun_period,
+            senkou_b_period,
+            displacement,
+            bars: VecDeque::with_capacity(lookback),
+            leading_spans: VecDeque::with_capacity(displacement + 1),
+            prev_tk_diff: None,
+            prev_span_diff: None,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.bars.clear();
+        self.leading_spans.clear();
+        self.prev_tk_diff = None;
+        self.prev_span_diff = None;
+    }
+
+    /// Feed the next candle; returns `None` until the longest period is filled
+    pub fn update(&mut self, high: f64, low: f64, close: f64) -> Option<IchimokuIndicator> {
+        let lookback = self.lookback();
+        self.bars.push_back((high, low));
+        if self.bars.len() > lookback {
+            self.bars.pop_front();
+        }
+        if self.bars.len() < lookback {
+            return None;
+        }
+
+        let tenkan_sen = self.midpoint(self.tenkan_period);
+        let kijun_sen = self.midpoint(self.kijun_period);
+        let senkou_span_a = (tenkan_sen + kijun_sen) / 2.0;
+        let senkou_span_b = self.midpoint(self.senkou_b_period);
+
+        self.leading_spans.push_back((senkou_span_a, senkou_span_b));
+        if self.leading_spans.len() > self.displacement + 1 {
+            self.leading_spans.pop_front();
+        }
+        let cloud = self.cloud();
+
+        let tk_diff = tenkan_sen - kijun_sen;
+        let span_diff = senkou_span_a - senkou_span_b;
+        let signal = if crossed(self.prev_span_diff, span_diff) {
+            IchimokuSignal::CloudTwist
+        } else if crossed(self.prev_tk_diff, tk_diff) {
+            IchimokuSignal::TKCross
+        } else {
+            match cloud {
+                Some((a, b)) if close > a.max(b) => IchimokuSignal::PriceAboveCloud,
+                Some((a, b)) if close < a.min(b) => IchimokuSignal::PriceBelowCloud,
+                _ => IchimokuSignal::Neutral,
+            }
+        };
+        self.prev_tk_diff = Some(tk_diff);
+        self.prev_span_diff = Some(span_diff);
+
+        Some(IchimokuIndicator {
+            tenkan_sen,
+            kijun_sen,
+            senkou_span_a,
+            senkou_span_b,
+            chikou_span: close,
+            cloud_span_a: cloud.map(|(a, _)| a),
+            cloud_span_b: cloud.map(|(_, b)| b),
+            signal,
+        })
+    }
+
+    /// Senkou spans projected onto the current bar, once `displacement` bars
+    /// have passed since the first spans were computed
+    pub fn cloud(&self) -> Option<(f64, f64)> {
+        if self.leading_spans.len() == self.displacement + 1 {
+            self.leading_spans.front().copied()
+        } else {
+            None
+        }
+    }
+
+    /// Candles needed before the first reading
+    pub fn lookback(&self) -> usize {
+        self.tenkan_period
+            .max(self.kijun_period)
+            .max(self.senkou_b_period)
+    }
+
+    /// (highest high + lowest low) / 2 over the last `period` bars
+    fn midpoint(&self, period: usize) -> f64 {
+        let (highest, lowest) = self
+            .bars
+            .iter()
+            .rev()
+            .take(period)
+            .fold((f64::NEG_INFINITY, f64::INFINITY), |(hi, lo), &(h, l)| {
+                (hi.max(h), lo.min(l))
+            });
+        (highest + lowest) / 2.0
+    }
+}
+
+impl Default for Ichimoku {
+    fn default() -> Self {
+        Self::new(TENKAN_PERIOD, KIJUN_PERIOD, SENKOU_B_PERIOD, DISPLACEMENT)
+    }
+}
+
+/// Whether a difference changed sign since the previous bar
+fn crossed(prev: Option<f64>, current: f64) -> bool {
+    match prev {
+        Some(prev) => (prev <= 0.0 && current > 0.0) || (prev >= 0.0 && current < 0.0),
+        None => false,
+    }
+}
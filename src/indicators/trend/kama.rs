@@ -0,0 +1,139 @@
+//! Kaufman's Adaptive Moving Average (KAMA): a moving average that follows
+//! price quickly in clean trends and barely moves in choppy, sideways markets.
+
+use std::collections::VecDeque;
+
+pub const KAMA_DEFAULT_PERIOD: usize = 10;
+pub const KAMA_DEFAULT_FAST_PERIOD: usize = 2;
+pub const KAMA_DEFAULT_SLOW_PERIOD: usize = 30;
+/// Efficiency ratio above which the market counts as trending
+pub const KAMA_TRENDING_EFFICIENCY_RATIO: f64 = 0.6;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KamaSignal {
+    /// Efficiency ratio above [`KAMA_TRENDING_EFFICIENCY_RATIO`]
+    Trending,
+    /// Choppy price; KAMA is close to flat
+    Flat,
+    /// Price crossed above KAMA on this candle
+    BullishBreak,
+    /// Price crossed below KAMA on this candle
+    BearishBreak,
+}
+
+/// Stateful KAMA.
+///
+/// The efficiency ratio (ER) is the net price change over `period` candles
+/// divided by the sum of the individual candle-to-candle changes: 1 for a
+/// straight line, near 0 for noise. Each update moves KAMA toward price by
+/// `(ER × (fast_sc - slow_sc) + slow_sc)²`, where `fast_sc` and `slow_sc` are
+/// the EMA smoothing constants `2 / (n + 1)` of the fast and slow periods.
+/// KAMA is seeded with the simple average of the first `period` prices.
+#[derive(Debug, Clone)]
+pub struct KAMA {
+    period: usize,
+    fast_period: usize,
+    slow_period: usize,
+    fast_sc: f64,
+    slow_sc: f64,
+    /// The last `period + 1` prices, oldest first
+    prices: VecDeque<f64>,
+    value: Option<f64>,
+    efficiency_ratio: Option<f64>,
+}
+
+impl KAMA {
+    pub fn new(period: usize, fast_period: usize, slow_period: usize) -> Self {
+        let period = period.max(1);
+        Self {
+            period,
+            fast_period,
+            slow_period,
+            fast_sc: 2.0 / (fast_period as f64 + 1.0),
+            slow_sc: 2.0 / (slow_period as f64 + 1.0),
+            prices: VecDeque::with_capacity(period + 1),
+            value: None,
+            efficiency_ratio: None,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new(self.period, self.fast_period, self.slow_period);
+    }
+
+    /// Candles needed before the first reading
+    pub fn lookback(&self) -> usize {
+        self.period + 1
+    }
+
+    /// Feed the next price; returns `(kama, signal)` once `period + 1` prices
+    /// have been seen
+    pub fn update(&mut self, price: f64) -> Option<(f64, KamaSignal)> {
+        let previous_price = self.prices.back().copied();
+        self.prices.push_back(price);
+        if self.prices.len() > self.period + 1 {
+            self.prices.pop_front();
+        }
+        if self.prices.len() <= self.period {
+            return None;
+        }
+
+        let previous = self.value.unwrap_or_else(|| {
+            self.prices.iter().take(self.period).sum::<f64>() / self.period as f64
+        });
+
+        let change = (price - self.prices[0]).abs();
+        let volatility: f64 = self
+            .prices
+            .iter()
+            .zip(self.prices.iter().skip(1))
+            .map(|(a, b)| (b - a).abs())
+            .sum();
+        let efficiency_ratio = if volatility > 0.0 {
+            change / volatility
+        } else {
+            0.0
+        };
+        let smoothing = (efficiency_ratio * (self.fast_sc - self.slow_sc) + self.slow_sc).powi(2);
+        let kama = previous + smoothing * (price - previous);
+
+        // A break needs a previous KAMA for price to have been on the other side of
+        let signal = match (self.value, previous_price) {
+            (Some(prev_kama), Some(prev_price)) if prev_price <= prev_kama && price > kama => {
+                KamaSignal::BullishBreak
+            }
+            (Some(prev_kama), Some(prev_price)) if prev_price >= prev_kama && price < kama => {
+                KamaSignal::BearishBreak
+            }
+            _ if efficiency_ratio > KAMA_TRENDING_EFFICIENCY_RATIO => KamaSignal::Trending,
+            _ => KamaSignal::Flat,
+        };
+
+        self.value = Some(kama);
+        self.efficiency_ratio = Some(efficiency_ratio);
+        Some((kama, signal))
+    }
+
+    pub fn value(&self) -> Option<f64> {
+        self.value
+    }
+
+    /// Efficiency ratio (0.0-1.0) of the latest update
+    pub fn efficiency_ratio(&self) -> Option<f64> {
+        self.efficiency_ratio
+    }
+
+    pub fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Default for KAMA {
+    fn default() -> Self {
+        Self::new(
+            KAMA_DEFAULT_PERIOD,
+            KAMA_DEFAULT_FAST_PERIOD,
+            KAMA_DEFAULT_SLOW_PERIOD,
+        )
+    }
+}
@@ -1,7 +1,15 @@
-//! Trend indicators
+//! Trend indicators (EMA, KAMA, SuperTrend, Ichimoku, Parabolic SAR, ADX)
 
+pub mod adx;
 pub mod ema;
+pub mod ichimoku;
+pub mod kama;
+pub mod parabolic_sar;
 pub mod supertrend;
 
+pub use adx::*;
 pub use ema::*;
+pub use ichimoku::*;
+pub use kama::*;
+pub use parabolic_sar::*;
 pub use supertrend::*;
@@ -0,0 +1,177 @@
+//! Parabolic SAR (stop and reverse) for trailing stops and trend direction.
+
+pub const PARABOLIC_SAR_INITIAL_AF: f64 = 0.02;
+pub const PARABOLIC_SAR_AF_STEP: f64 = 0.02;
+pub const PARABOLIC_SAR_MAX_AF: f64 = 0.2;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ParabolicSarSignal {
+    /// Uptrend; price is above the SAR
+    Bullish,
+    /// Downtrend; price is below the SAR
+    Bearish,
+    /// Price crossed the SAR on this candle and the trend reversed
+    Flip,
+}
+
+/// Stateful Wilder Parabolic SAR.
+///
+/// Each candle the SAR moves toward the extreme point (EP, the highest high
+/// of an uptrend or lowest low of a downtrend) by the acceleration factor,
+/// and may not move into the previous two candles' range. The AF grows by
+/// `step` (up to `max`) every time a new EP is made. When price crosses the
+/// SAR the trend reverses: the SAR jumps to the old EP, the EP becomes the
+/// reversal candle's extreme and the AF restarts at `initial`. The reversal
+/// candle itself never increments the AF, even though its extreme is trivially
+/// a "new" EP for the new trend.
+#[derive(Debug, Clone)]
+pub struct ParabolicSar {
+    initial_af: f64,
+    step: f64,
+    max_af: f64,
+    uptrend: bool,
+    sar: Option<f64>,
+    ep: f64,
+    af: f64,
+    /// `(high, low)` of the last two candles, most recent first
+    prev: [Option<(f64, f64)>; 2],
+    prev_close: f64,
+}
+
+impl ParabolicSar {
+    pub fn new(initial_af: f64, step: f64, max_af: f64) -> Self {
+        Self {
+            initial_af,
+            step,
+            max_af: max_af.max(initial_af),
+            uptrend: true,
+            sar: None,
+            ep: 0.0,
+            af: initial_af,
+            prev: [None, None],
+            prev_close: 0.0,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new(self.initial_af, self.step, self.max_af);
+    }
+
+    /// Candles needed before the first reading
+    pub fn lookback(&self) -> usize {
+        2
+    }
+
+    /// Feed the next candle; returns `(sar, acceleration_factor, signal)`
+    /// from the second candle on.
+    ///
+    /// The second candle seeds the trend from the direction of its close and
+    /// places the SAR at the two candles' opposite extreme.
+    pub fn update(
+        &mut self,
+        high: f64,
+        low: f64,
+        close: f64,
+    ) -> Option<(f64, f64, ParabolicSarSignal)> {
+        let result = match (self.sar, self.prev[0]) {
+            (None, None) => None,
+            (None, Some((prev_high, prev_low))) => {
+                self.uptrend = close >= self.prev_close;
+                if self.uptrend {
+                    self.sar = Some(prev_low.min(low));
+                    self.ep = prev_high.max(high);
+                } else {
+                    self.sar = Some(prev_high.max(high));
+                    self.ep = prev_low.min(low);
+                }
+                self.af = self.initial_af;
+                Some(self.output(false))
+            }
+            (Some(sar), _) => Some(self.advance(sar, high, low)),
+        };
+
+        self.prev = [Some((high, low)), self.prev[0]];
+        self.prev_close = close;
+        result
+    }
+
+    fn advance(&mut self, sar: f64, high: f64, low: f64) -> (f64, f64, ParabolicSarSignal) {
+        let mut next = sar + self.af * (self.ep - sar);
+
+        // The SAR may not enter the previous two candles' range
+        for (prev_high, prev_low) in self.prev.iter().flatten() {
+            next = if self.uptrend {
+                next.min(*prev_low)
+            } else {
+                next.max(*prev_high)
+            };
+        }
+
+        let reversed = if self.uptrend {
+            low < next
+        } else {
+            high > next
+        };
+        if reversed {
+            // Stop and reverse: restart from the old EP, clear of this candle
+            next = if self.uptrend {
+                let prev_high = self.prev[0].map_or(high, |(h, _)| h);
+                self.ep.max(high).max(prev_high)
+            } else {
+                let prev_low = self.prev[0].map_or(low, |(_, l)| l);
+                self.ep.min(low).min(prev_low)
+            };
+            self.uptrend = !self.uptrend;
+            self.ep = if self.uptrend { high } else { low };
+            self.af = self.initial_af;
+        } else if self.uptrend && high > self.ep {
+            self.ep = high;
+            self.af = (self.af + self.step).min(self.max_af);
+        } else if !self.uptrend && low < self.ep {
+            self.ep = low;
+            self.af = (self.af + self.step).min(self.max_af);
+        }
+
+        self.sar = Some(next);
+        self.output(reversed)
+    }
+
+    fn output(&self, reversed: bool) -> (f64, f64, ParabolicSarSignal) {
+        let signal = if reversed {
+            ParabolicSarSignal::Flip
+        } else if self.uptrend {
+            ParabolicSarSignal::Bullish
+        } else {
+            ParabolicSarSignal::Bearish
+        };
+        (self.sar.unwrap_or_default(), self.af, signal)
+    }
+
+    pub fn value(&self) -> Option<f64> {
+        self.sar
+    }
+
+    pub fn acceleration_factor(&self) -> f64 {
+        self.af
+    }
+
+    /// Extreme point of the current trend
+    pub fn extreme_point(&self) -> Option<f64> {
+        self.sar.map(|_| self.ep)
+    }
+
+    /// Whether the current trend is up (`None` before the second candle)
+    pub fn is_uptrend(&self) -> Option<bool> {
+        self.sar.map(|_| self.uptrend)
+    }
+}
+
+impl Default for ParabolicSar {
+    fn default() -> Self {
+        Self::new(
+            PARABOLIC_SAR_INITIAL_AF,
+            PARABOLIC_SAR_AF_STEP,
+            PARABOLIC_SAR_MAX_AF,
+        )
+    }
+}
@@ -2,6 +2,11 @@
 
 use crate::indicators::volatility::atr::ATR;
 
+/// ATR period used when a strategy condition does not set `atr_period`
+pub const SUPERTREND_DEFAULT_ATR_PERIOD: usize = 10;
+/// Band multiplier used when a strategy condition does not set `multiplier`
+pub const SUPERTREND_DEFAULT_MULTIPLIER: f64 = 3.0;
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum SuperTrendSignal {
     Bullish,
@@ -17,6 +22,7 @@ pub struct SuperTrend {
     upper_band: Option<f64>,
     lower_band: Option<f64>,
     supertrend: Option<f64>,
+    prev_close: Option<f64>,
     prev_signal: Option<SuperTrendSignal>,
 }
 
@@ -28,10 +34,20 @@ impl SuperTrend {
             upper_band: None,
             lower_band: None,
             supertrend: None,
+            prev_close: None,
             prev_signal: None,
         }
     }
 
+    pub fn reset(&mut self) {
+        self.atr.reset();
+        self.upper_band = None;
+        self.lower_band = None;
+        self.supertrend = None;
+        self.prev_close = None;
+        self.prev_signal = None;
+    }
+
     pub fn update(&mut self, high: f64, low: f64, close: f64) -> SuperTrendSignal {
         let atr_value = self.atr.update(high, low, close);
         let hl_avg = (high + low) / 2.0;
@@ -41,15 +57,22 @@ impl SuperTrend {
         let prev_upper = self.upper_band;
         let prev_lower = self.lower_band;
         let prev_supertrend = self.supertrend;
+        let prev_close = self.prev_close.unwrap_or(close);
 
+        // Bands only ratchet toward price; they reset once the previous close
+        // has broken through them, so the current close can still cross
         let final_upper = match prev_upper {
-            Some(prev_upper) if basic_upper < prev_upper || close > prev_upper => basic_upper,
+            Some(prev_upper) if basic_upper < prev_upper || prev_close > prev_upper => {
+                basic_upper
+            }
             Some(prev_upper) => prev_upper,
             None => basic_upper,
         };
 
         let final_lower = match prev_lower {
-            Some(prev_lower) if basic_lower > prev_lower || close < prev_lower => basic_lower,
+            Some(prev_lower) if basic_lower > prev_lower || prev_close < prev_lower => {
+                basic_lower
+            }
             Some(prev_lower) => prev_lower,
             None => basic_lower,
         };
@@ -74,6 +97,7 @@ impl SuperTrend {
         self.upper_band = Some(final_upper);
         self.lower_band = Some(final_lower);
         self.supertrend = Some(supertrend);
+        self.prev_close = Some(close);
 
         let current_signal = if close > supertrend {
             SuperTrendSignal::Bullish
@@ -1,5 +1,10 @@
 use crate::indicators::error::IndicatorError;
 use crate::models::indicators::*;
+use crate::models::strategy::IndicatorType;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
 
 const RSI_MIN: f64 = 0.0;
 const RSI_MAX: f64 = 100.0;
@@ -10,6 +15,8 @@ const MIN_VOLUME: f64 = 0.0;
 const FUNDING_RATE_MIN: f64 = -1.0;
 const FUNDING_RATE_MAX: f64 = 1.0;
 const MACD_HISTOGRAM_TOLERANCE: f64 = 0.0001;
+const MAX_GAP_INTERVALS: i64 = 3;
+const VOLUME_SPIKE_MULTIPLIER: f64 = 100.0;
 
 pub fn validate_rsi(value: f64) -> Result<(), IndicatorError> {
     if !(RSI_MIN..=RSI_MAX).contains(&value) {
@@ -39,6 +46,102 @@ pub fn validate_period(period: u32) -> Result<(), IndicatorError> {
     Ok(())
 }
 
+/// Type and bounds a strategy condition parameter must satisfy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamKind {
+    /// Whole number of candles from `min` up to the [`validate_period`] maximum
+    Period { min: u32 },
+    /// RSI level strictly between 0 and 100
+    RsiLevel,
+    /// Finite number above zero
+    Positive,
+}
+
+/// Parameters each indicator accepts in a condition's `indicator_params`;
+/// indicators not listed take none
+pub fn indicator_param_schema(indicator: IndicatorType) -> &'static [(&'static str, ParamKind)] {
+    match indicator {
+        IndicatorType::RSI => &[
+            ("period", ParamKind::Period { min: MIN_PERIOD }),
+            ("overbought_threshold", ParamKind::RsiLevel),
+            ("oversold_threshold", ParamKind::RsiLevel),
+        ],
+        IndicatorType::EMA => &[
+            ("fast_period", ParamKind::Period { min: MIN_PERIOD }),
+            ("slow_period", ParamKind::Period { min: MIN_PERIOD }),
+            // 0 reports crosses on the candle they happen
+            ("confirmation_candles", ParamKind::Period { min: 0 }),
+        ],
+        IndicatorType::MACD => &[
+            ("fast_period", ParamKind::Period { min: MIN_PERIOD }),
+            ("slow_period", ParamKind::Period { min: MIN_PERIOD }),
+            ("signal_period", ParamKind::Period { min: MIN_PERIOD }),
+        ],
+        IndicatorType::SMA => &[("period", ParamKind::Period { min: MIN_PERIOD })],
+        IndicatorType::Bollinger => &[("squeeze_threshold_pct", ParamKind::Positive)],
+        // A 1-candle ATR is just the latest true range
+        IndicatorType::SuperTrend => &[
+            ("atr_period", ParamKind::Period { min: 2 }),
+            ("multiplier", ParamKind::Positive),
+        ],
+        _ => &[],
+    }
+}
+
+/// Check an `indicator_params` map against [`indicator_param_schema`]:
+/// unknown names, non-numeric values, and out-of-range values are rejected
+pub fn validate_indicator_params(
+    indicator: IndicatorType,
+    params: &HashMap<String, Value>,
+) -> Result<(), IndicatorError> {
+    let schema = indicator_param_schema(indicator);
+    for (name, value) in params {
+        let Some(&(_, kind)) = schema.iter().find(|(key, _)| key == name) else {
+            return Err(IndicatorError::ValidationError(format!(
+                "{:?} does not accept parameter '{}'",
+                indicator, name
+            )));
+        };
+        let Some(number) = value.as_f64() else {
+            return Err(IndicatorError::ValidationError(format!(
+                "{:?} parameter '{}' must be a number, got {}",
+                indicator, name, value
+            )));
+        };
+        match kind {
+            ParamKind::Period { min } => {
+                if number.fract() != 0.0 || !(min as f64..=MAX_PERIOD as f64).contains(&number) {
+                    return Err(IndicatorError::ValidationError(format!(
+                        "{:?} parameter '{}' must be an integer between {} and {}, got {}",
+                        indicator, name, min, MAX_PERIOD, number
+                    )));
+                }
+            }
+            ParamKind::RsiLevel => {
+                if !(number > RSI_MIN && number < RSI_MAX) {
+                    return Err(IndicatorError::OutOfRange {
+                        field: name.clone(),
+                        value: number,
+                        min: RSI_MIN,
+                        max: RSI_MAX,
+                    });
+                }
+            }
+            ParamKind::Positive => {
+                if !(number > 0.0 && number.is_finite()) {
+                    return Err(IndicatorError::OutOfRange {
+                        field: name.clone(),
+                        value: number,
+                        min: 0.0,
+                        max: f64::INFINITY,
+                    });
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
 pub fn validate_price(price: f64) -> Result<(), IndicatorError> {
     if price <= MIN_PRICE {
         return Err(IndicatorError::OutOfRange {
@@ -207,3 +310,234 @@ pub fn validate_indicator_set(set: &IndicatorSet) -> Result<(), IndicatorError>
 
     Ok(())
 }
+
+/// Soft data-quality issue in a candle series; evaluation can still proceed
+#[derive(Debug, Clone, PartialEq)]
+pub enum CandleValidationWarning {
+    /// Gap before `index` exceeds `MAX_GAP_INTERVALS` times the expected interval
+    LargeGap {
+        index: usize,
+        gap_secs: i64,
+        expected_secs: i64,
+    },
+    /// Volume at `index` exceeds `VOLUME_SPIKE_MULTIPLIER` times the median volume
+    VolumeSpike {
+        index: usize,
+        volume: f64,
+        median_volume: f64,
+    },
+    /// Candle at `index` traded no volume
+    ZeroVolume { index: usize },
+}
+
+/// Issue found by [`validate_candle_series`].
+///
+/// Impossible prices and duplicate or out-of-order timestamps are hard
+/// failures; soft issues are wrapped in `Warning` so callers can log them and
+/// carry on.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CandleValidationError {
+    CloseExceedsHigh {
+        index: usize,
+        close: f64,
+        high: f64,
+    },
+    LowExceedsClose {
+        index: usize,
+        low: f64,
+        close: f64,
+    },
+    /// An open, high, low or close that is zero, negative or not finite
+    NegativePrice {
+        index: usize,
+        price: f64,
+    },
+    DuplicateTimestamp {
+        index: usize,
+        timestamp: DateTime<Utc>,
+    },
+    OutOfOrder {
+        index: usize,
+        timestamp: DateTime<Utc>,
+        previous: DateTime<Utc>,
+    },
+    Warning(CandleValidationWarning),
+}
+
+impl CandleValidationError {
+    /// Whether this issue should stop the series from being evaluated
+    pub fn is_hard_failure(&self) -> bool {
+        !matches!(self, CandleValidationError::Warning(_))
+    }
+}
+
+impl fmt::Display for CandleValidationWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CandleValidationWarning::LargeGap {
+                index,
+                gap_secs,
+                expected_secs,
+            } => write!(
+                f,
+                "Gap of {}s before candle {} (expected interval {}s)",
+                gap_secs, index, expected_secs
+            ),
+            CandleValidationWarning::VolumeSpike {
+                index,
+                volume,
+                median_volume,
+            } => write!(
+                f,
+                "Volume spike at candle {}: {} (median {})",
+                index, volume, median_volume
+            ),
+            CandleValidationWarning::ZeroVolume { index } => {
+                write!(f, "Zero volume at candle {}", index)
+            }
+        }
+    }
+}
+
+impl fmt::Display for CandleValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CandleValidationError::CloseExceedsHigh { index, close, high } => write!(
+                f,
+                "Candle {} close {} is above its high {}",
+                index, close, high
+            ),
+            CandleValidationError::LowExceedsClose { index, low, close } => write!(
+                f,
+                "Candle {} low {} is above its close {}",
+                index, low, close
+            ),
+            CandleValidationError::NegativePrice { index, price } => {
+                write!(f, "Candle {} has invalid price {}", index, price)
+            }
+            CandleValidationError::DuplicateTimestamp { index, timestamp } => {
+                write!(f, "Duplicate timestamp {} at candle {}", timestamp, index)
+            }
+            CandleValidationError::OutOfOrder {
+                index,
+                timestamp,
+                previous,
+            } => write!(
+                f,
+                "Candle {} timestamp {} is before previous {}",
+                index, timestamp, previous
+            ),
+            CandleValidationError::Warning(warning) => write!(f, "{}", warning),
+        }
+    }
+}
+
+impl std::error::Error for CandleValidationError {}
+
+/// Check a candle series for impossible prices, ordering, gaps and volume
+/// anomalies.
+///
+/// The expected interval is the median delta between consecutive timestamps.
+/// Every issue found is returned; an empty vector means the series is clean.
+pub fn validate_candle_series(candles: &[Candle]) -> Vec<CandleValidationError> {
+    let mut issues = Vec::new();
+    for (index, candle) in candles.iter().enumerate() {
+        issues.extend(validate_candle_prices(index, candle));
+        if candle.volume == 0.0 {
+            issues.push(CandleValidationError::Warning(
+                CandleValidationWarning::ZeroVolume { index },
+            ));
+        }
+    }
+
+    if candles.len() < 2 {
+        return issues;
+    }
+
+    let deltas: Vec<i64> = candles
+        .windows(2)
+        .map(|pair| (pair[1].timestamp - pair[0].timestamp).num_seconds())
+        .collect();
+    let expected_secs = median_i64(deltas.iter().copied().filter(|delta| *delta > 0).collect());
+
+    for (i, delta) in deltas.iter().enumerate() {
+        let index = i + 1;
+        let candle = &candles[index];
+        if candle.timestamp == candles[i].timestamp {
+            issues.push(CandleValidationError::DuplicateTimestamp {
+                index,
+                timestamp: candle.timestamp,
+            });
+        } else if candle.timestamp < candles[i].timestamp {
+            issues.push(CandleValidationError::OutOfOrder {
+                index,
+                timestamp: candle.timestamp,
+                previous: candles[i].timestamp,
+            });
+        } else if let Some(expected_secs) = expected_secs {
+            if *delta > expected_secs * MAX_GAP_INTERVALS {
+                issues.push(CandleValidationError::Warning(
+                    CandleValidationWarning::LargeGap {
+                        index,
+                        gap_secs: *delta,
+                        expected_secs,
+                    },
+                ));
+            }
+        }
+    }
+
+    let mut volumes: Vec<f64> = candles
+        .iter()
+        .map(|candle| candle.volume)
+        .filter(|volume| volume.is_finite())
+        .collect();
+    volumes.sort_by(f64::total_cmp);
+    let median_volume = volumes.get(volumes.len() / 2).copied().unwrap_or(0.0);
+    if median_volume > 0.0 {
+        for (index, candle) in candles.iter().enumerate() {
+            if candle.volume > median_volume * VOLUME_SPIKE_MULTIPLIER {
+                issues.push(CandleValidationError::Warning(
+                    CandleValidationWarning::VolumeSpike {
+                        index,
+                        volume: candle.volume,
+                        median_volume,
+                    },
+                ));
+            }
+        }
+    }
+
+    issues
+}
+
+/// Price issues in a single candle; a bad price hides the range checks
+fn validate_candle_prices(index: usize, candle: &Candle) -> Option<CandleValidationError> {
+    let prices = [candle.open, candle.high, candle.low, candle.close];
+    if let Some(price) = prices.into_iter().find(|price| *price <= MIN_PRICE || !price.is_finite()) {
+        return Some(CandleValidationError::NegativePrice { index, price });
+    }
+    if candle.close > candle.high {
+        return Some(CandleValidationError::CloseExceedsHigh {
+            index,
+            close: candle.close,
+            high: candle.high,
+        });
+    }
+    if candle.low > candle.close {
+        return Some(CandleValidationError::LowExceedsClose {
+            index,
+            low: candle.low,
+            close: candle.close,
+        });
+    }
+    None
+}
+
+fn median_i64(mut values: Vec<i64>) -> Option<i64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_unstable();
+    Some(values[values.len() / 2])
+}
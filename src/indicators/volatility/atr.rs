@@ -1,6 +1,11 @@
 //! ATR (Average True Range) volatility regime detector.
 
+use crate::common::math;
+use crate::indicators::volatility::atr_pct::atr_pct;
 use crate::models::indicators::{AtrIndicator, Candle};
+use crate::models::signal::SignalDirection;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 #[derive(Debug, Clone)]
 pub struct ATR {
@@ -20,15 +25,15 @@ impl ATR {
         }
     }
 
+    pub fn reset(&mut self) {
+        self.true_ranges.clear();
+        self.current_atr = None;
+        self.prev_close = None;
+    }
+
     pub fn update(&mut self, high: f64, low: f64, close: f64) -> f64 {
-        let true_range = if let Some(prev_close) = self.prev_close {
-            let tr1 = high - low;
-            let tr2 = (high - prev_close).abs();
-            let tr3 = (low - prev_close).abs();
-            tr1.max(tr2).max(tr3)
-        } else {
-            high - low
-        };
+        // The first candle has no previous close, so its own close stands in
+        let true_range = math::true_range(high, low, self.prev_close.unwrap_or(close));
 
         self.true_ranges.push(true_range);
         if self.true_ranges.len() > self.period {
@@ -54,6 +59,28 @@ impl ATR {
         self.current_atr
     }
 
+    /// Most recently computed ATR, or 0.0 before the first update
+    pub fn current_atr(&self) -> f64 {
+        self.current_atr.unwrap_or(0.0)
+    }
+
+    /// ATR trailing stop: below price for longs, above price for shorts.
+    /// Neutral returns the price unchanged.
+    pub fn trailing_stop(&self, price: f64, direction: SignalDirection, multiplier: f64) -> f64 {
+        let offset = multiplier * self.current_atr();
+        match direction {
+            SignalDirection::Long => price - offset,
+            SignalDirection::Short => price + offset,
+            SignalDirection::Neutral => price,
+        }
+    }
+
+    /// Symmetric `(lower, upper)` bands `multiplier` ATRs around price
+    pub fn atr_bands(&self, price: f64, multiplier: f64) -> (f64, f64) {
+        let offset = multiplier * self.current_atr();
+        (price - offset, price + offset)
+    }
+
     pub fn get_volatility_regime(&self, atr: f64, lookback_avg: f64) -> VolatilityRegime {
         if lookback_avg <= f64::EPSILON {
             return VolatilityRegime::Normal;
@@ -72,7 +99,7 @@ impl ATR {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 pub enum VolatilityRegime {
     High,
     Elevated,
@@ -80,6 +107,22 @@ pub enum VolatilityRegime {
     Low,
 }
 
+impl VolatilityRegime {
+    /// Whether this regime is more volatile than `other`
+    pub fn exceeds(self, other: VolatilityRegime) -> bool {
+        self.level() > other.level()
+    }
+
+    fn level(self) -> u8 {
+        match self {
+            VolatilityRegime::Low => 0,
+            VolatilityRegime::Normal => 1,
+            VolatilityRegime::Elevated => 2,
+            VolatilityRegime::High => 3,
+        }
+    }
+}
+
 pub fn calculate_atr(candles: &[Candle], period: u32) -> Option<AtrIndicator> {
     if candles.len() < period as usize {
         return None;
@@ -93,6 +136,12 @@ pub fn calculate_atr(candles: &[Candle], period: u32) -> Option<AtrIndicator> {
     atr.current().map(|value| AtrIndicator { value, period })
 }
 
+/// ATR as a percentage of the last close, comparable across price scales
+pub fn calculate_atr_pct(candles: &[Candle], period: u32) -> Option<f64> {
+    let atr = calculate_atr(candles, period)?;
+    atr_pct(atr.value, candles.last()?.close)
+}
+
 pub fn calculate_atr_default(candles: &[Candle]) -> Option<AtrIndicator> {
     calculate_atr(candles, 14)
 }
@@ -0,0 +1,76 @@
+//! ATR% (ATR as a percentage of the close) for comparing volatility across symbols.
+
+use crate::indicators::volatility::atr::{VolatilityRegime, ATR};
+use std::collections::VecDeque;
+
+pub const ATR_PCT_DEFAULT_PERIOD: usize = 14;
+
+/// `atr / close × 100`, or `None` when the close is not positive
+pub fn atr_pct(atr: f64, close: f64) -> Option<f64> {
+    if close > f64::EPSILON && atr.is_finite() {
+        Some(atr / close * 100.0)
+    } else {
+        None
+    }
+}
+
+/// Streaming ATR%.
+///
+/// ATR is in price units, so the same relative move reads 1000× larger on a
+/// symbol priced 1000× higher. Dividing by the close removes the price scale.
+/// The volatility regime compares each reading against the mean of the
+/// previous `period` readings, like the regime of the plain ATR.
+#[derive(Debug, Clone)]
+pub struct AtrPct {
+    period: usize,
+    atr: ATR,
+    history: VecDeque<f64>,
+}
+
+impl AtrPct {
+    pub fn new(period: usize) -> Self {
+        let period = period.max(1);
+        Self {
+            period,
+            atr: ATR::new(period),
+            history: VecDeque::with_capacity(period),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.atr.reset();
+        self.history.clear();
+    }
+
+    /// Candles needed before the first reading
+    pub fn lookback(&self) -> usize {
+        1
+    }
+
+    /// Feed the next candle; returns `(atr_pct, regime)` unless the close is
+    /// not positive
+    pub fn update(&mut self, high: f64, low: f64, close: f64) -> Option<(f64, VolatilityRegime)> {
+        let value = atr_pct(self.atr.update(high, low, close), close)?;
+
+        // Compare against the mean of the previous readings, excluding this one
+        let lookback_avg = if self.history.is_empty() {
+            value
+        } else {
+            self.history.iter().sum::<f64>() / self.history.len() as f64
+        };
+        let regime = self.atr.get_volatility_regime(value, lookback_avg);
+
+        self.history.push_back(value);
+        if self.history.len() > self.period {
+            self.history.pop_front();
+        }
+
+        Some((value, regime))
+    }
+}
+
+impl Default for AtrPct {
+    fn default() -> Self {
+        Self::new(ATR_PCT_DEFAULT_PERIOD)
+    }
+}
@@ -2,6 +2,10 @@
 
 use crate::models::indicators::{BollingerBandsIndicator, Candle};
 
+/// Default bandwidth, in percent of the middle band, below which the bands
+/// are in a squeeze
+pub const DEFAULT_SQUEEZE_THRESHOLD_PCT: f64 = 2.0;
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum BollingerSignal {
     Squeeze,
@@ -18,6 +22,7 @@ pub struct BollingerBands {
     std_dev: f64,
     prices: Vec<f64>,
     prev_bandwidth: Option<f64>,
+    squeeze_threshold_pct: f64,
 }
 
 impl BollingerBands {
@@ -27,9 +32,22 @@ impl BollingerBands {
             std_dev,
             prices: Vec::new(),
             prev_bandwidth: None,
+            squeeze_threshold_pct: DEFAULT_SQUEEZE_THRESHOLD_PCT,
         }
     }
 
+    /// Signal `Squeeze` while the bandwidth is below `threshold_pct` percent
+    /// of the middle band
+    pub fn with_squeeze_threshold_pct(mut self, threshold_pct: f64) -> Self {
+        self.squeeze_threshold_pct = threshold_pct;
+        self
+    }
+
+    pub fn reset(&mut self) {
+        self.prices.clear();
+        self.prev_bandwidth = None;
+    }
+
     pub fn update(&mut self, close: f64) -> (f64, f64, f64, BollingerSignal) {
         self.prices.push(close);
         if self.prices.len() > self.period {
@@ -46,14 +64,10 @@ impl BollingerBands {
         let std = variance.sqrt();
         let upper = middle + (self.std_dev * std);
         let lower = middle - (self.std_dev * std);
-        let bandwidth = if middle.abs() > f64::EPSILON {
-            (upper - lower) / middle
-        } else {
-            0.0
-        };
+        let bandwidth = bandwidth_pct(upper, middle, lower).unwrap_or(0.0);
 
         let signal = if self.prices.len() == self.period {
-            if bandwidth < 0.05 {
+            if bandwidth < self.squeeze_threshold_pct {
                 BollingerSignal::Squeeze
             } else if close > upper {
                 BollingerSignal::UpperBreakout
@@ -79,6 +93,32 @@ impl BollingerBands {
     }
 }
 
+/// Where `price` sits within the bands: 0 at the lower band and 1 at the
+/// upper, clamped to [0, 1]. Zero-width bands give 0.5 at the band and 0 or
+/// 1 below or above it.
+pub fn percent_b(price: f64, upper: f64, lower: f64) -> f64 {
+    let width = upper - lower;
+    if width <= f64::EPSILON {
+        return if price > upper {
+            1.0
+        } else if price < lower {
+            0.0
+        } else {
+            0.5
+        };
+    }
+    ((price - lower) / width).clamp(0.0, 1.0)
+}
+
+/// Distance between the bands as a percentage of the middle band; `None`
+/// when the middle band is zero
+pub fn bandwidth_pct(upper: f64, middle: f64, lower: f64) -> Option<f64> {
+    if middle.abs() <= f64::EPSILON {
+        return None;
+    }
+    Some((upper - lower) / middle * 100.0)
+}
+
 pub fn calculate_bollinger_bands(
     candles: &[Candle],
     period: u32,
@@ -0,0 +1,79 @@
+//! Keltner Channel (EMA ± ATR bands) with Bollinger squeeze detection.
+
+use crate::indicators::trend::ema::EMA;
+use crate::indicators::volatility::atr::ATR;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KeltnerSignal {
+    /// Close above the upper band
+    Breakout,
+    /// Close below the lower band
+    Breakdown,
+    InsideChannel,
+    /// Bollinger Bands fit inside the Keltner bands (volatility compression)
+    BollingerSqueeze,
+}
+
+/// Stateful Keltner Channel: an EMA of closes with bands `multiplier` ATRs away.
+#[derive(Debug, Clone)]
+pub struct KeltnerChannel {
+    ema: EMA,
+    atr: ATR,
+    multiplier: f64,
+}
+
+impl KeltnerChannel {
+    pub fn new(ema_period: usize, atr_period: usize, multiplier: f64) -> Self {
+        Self {
+            ema: EMA::new(ema_period),
+            atr: ATR::new(atr_period),
+            multiplier,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.ema.reset();
+        self.atr.reset();
+    }
+
+    /// Candles needed before the first reading
+    pub fn lookback(&self) -> usize {
+        self.ema.lookback()
+    }
+
+    /// Feed the next candle; returns `(upper, middle, lower, signal)` once the
+    /// EMA is seeded.
+    ///
+    /// `bollinger` is the `(upper, lower)` Bollinger Bands for the same candle,
+    /// used to detect a squeeze; pass `None` to skip squeeze detection.
+    pub fn update(
+        &mut self,
+        high: f64,
+        low: f64,
+        close: f64,
+        bollinger: Option<(f64, f64)>,
+    ) -> Option<(f64, f64, f64, KeltnerSignal)> {
+        let atr = self.atr.update(high, low, close);
+        let middle = self.ema.update(close)?;
+        let offset = self.multiplier.abs() * atr;
+        let upper = middle + offset;
+        let lower = middle - offset;
+
+        let signal = match bollinger {
+            Some((bb_upper, bb_lower)) if bb_upper < upper && bb_lower > lower => {
+                KeltnerSignal::BollingerSqueeze
+            }
+            _ if close > upper => KeltnerSignal::Breakout,
+            _ if close < lower => KeltnerSignal::Breakdown,
+            _ => KeltnerSignal::InsideChannel,
+        };
+
+        Some((upper, middle, lower, signal))
+    }
+}
+
+impl Default for KeltnerChannel {
+    fn default() -> Self {
+        Self::new(20, 10, 2.0)
+    }
+}
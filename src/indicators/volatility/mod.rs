@@ -1,7 +1,11 @@
-//! Volatility indicators: Bollinger Bands, ATR
+//! Volatility indicators: Bollinger Bands, ATR, ATR%, Keltner Channel
 
 pub mod atr;
+pub mod atr_pct;
 pub mod bollinger;
+pub mod keltner;
 
 pub use atr::*;
+pub use atr_pct::*;
 pub use bollinger::*;
+pub use keltner::*;
@@ -1,7 +1,9 @@
-//! Volume-based indicators (OBV, Volume Profile)
+//! Volume-based indicators (OBV, Volume Profile, order flow)
 
 pub mod obv;
+pub mod order_flow;
 pub mod volume_profile;
 
 pub use obv::*;
+pub use order_flow::*;
 pub use volume_profile::*;
@@ -1,13 +1,70 @@
 //! On-Balance Volume indicator with divergence detection.
 
+/// Candles compared by [`detect_divergence`] in strategy evaluation
+pub const OBV_DIVERGENCE_LOOKBACK: usize = 20;
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum OBVSignal {
+    /// Price fell while OBV rose; from [`detect_divergence`] when a history
+    /// is available, otherwise from the last candle alone
     BullishDivergence,
+    /// Price rose while OBV fell
     BearishDivergence,
     Confirmation,
     Neutral,
 }
 
+/// Divergence between price swings and OBV swings
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DivergenceSignal {
+    /// Price made a lower low while OBV made a higher low
+    BullishDivergence,
+    /// Price made a higher high while OBV made a lower high
+    BearishDivergence,
+}
+
+impl From<DivergenceSignal> for OBVSignal {
+    fn from(signal: DivergenceSignal) -> Self {
+        match signal {
+            DivergenceSignal::BullishDivergence => OBVSignal::BullishDivergence,
+            DivergenceSignal::BearishDivergence => OBVSignal::BearishDivergence,
+        }
+    }
+}
+
+/// Compare the last `lookback` OBV and price readings for divergence.
+///
+/// The window is split into an earlier and a recent half, and the low (or
+/// high) of each half stands in for a swing. Both histories are aligned at
+/// their newest entry. Returns `None` when either history is shorter than
+/// `lookback`, `lookback` is below 4, or the swings agree.
+pub fn detect_divergence(
+    obv_history: &[f64],
+    price_history: &[f64],
+    lookback: usize,
+) -> Option<DivergenceSignal> {
+    if lookback < 4 || obv_history.len() < lookback || price_history.len() < lookback {
+        return None;
+    }
+    let obv = &obv_history[obv_history.len() - lookback..];
+    let price = &price_history[price_history.len() - lookback..];
+    let half = lookback / 2;
+
+    let min = |values: &[f64]| values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = |values: &[f64]| values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    let bullish =
+        min(&price[half..]) < min(&price[..half]) && min(&obv[half..]) > min(&obv[..half]);
+    let bearish =
+        max(&price[half..]) > max(&price[..half]) && max(&obv[half..]) < max(&obv[..half]);
+
+    match (bullish, bearish) {
+        (true, false) => Some(DivergenceSignal::BullishDivergence),
+        (false, true) => Some(DivergenceSignal::BearishDivergence),
+        _ => None,
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct OBV {
     current_obv: f64,
@@ -24,6 +81,12 @@ impl OBV {
         }
     }
 
+    pub fn reset(&mut self) {
+        self.current_obv = 0.0;
+        self.prev_close = None;
+        self.obv_ema = None;
+    }
+
     pub fn update(&mut self, close: f64, volume: f64) -> (f64, OBVSignal) {
         if let Some(prev_close) = self.prev_close {
             if close > prev_close {
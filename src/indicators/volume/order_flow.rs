@@ -0,0 +1,27 @@
+//! Order flow from the public trade feed.
+
+use crate::models::indicators::{Trade, TradeSide};
+
+/// Most recent trades the buy/sell ratio is computed over
+pub const BUY_SELL_RATIO_WINDOW: usize = 100;
+
+/// Buy volume divided by sell volume over the last [`BUY_SELL_RATIO_WINDOW`]
+/// trades, where each trade's side is its aggressor.
+///
+/// Above 1.0 buyers are lifting offers more than sellers hit bids. Returns
+/// `None` when the window holds no sell volume, since the ratio is then
+/// undefined.
+pub fn buy_sell_ratio<'a, I>(trades: I) -> Option<f64>
+where
+    I: IntoIterator<Item = &'a Trade>,
+    I::IntoIter: DoubleEndedIterator,
+{
+    let (mut buy_volume, mut sell_volume) = (0.0, 0.0);
+    for trade in trades.into_iter().rev().take(BUY_SELL_RATIO_WINDOW) {
+        match trade.side {
+            TradeSide::Buy => buy_volume += trade.size,
+            TradeSide::Sell => sell_volume += trade.size,
+        }
+    }
+    (sell_volume > 0.0).then(|| buy_volume / sell_volume)
+}
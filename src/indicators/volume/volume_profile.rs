@@ -2,6 +2,24 @@
 
 use std::collections::HashMap;
 
+/// Share of the total volume the value area covers
+pub const VALUE_AREA_VOLUME_PCT: f64 = 0.70;
+
+/// Largest share of the price one level may span
+pub const TICK_PRICE_FRACTION: f64 = 0.001;
+
+/// Level size for a symbol trading at `price`: [`TICK_PRICE_FRACTION`] of
+/// it, rounded down to a power of ten so levels fall on round prices.
+///
+/// A fixed tick would put every level of a cheap symbol in bucket 0. Prices
+/// that are not positive get a tick of 1.0.
+pub fn tick_size_for_price(price: f64) -> f64 {
+    if !(price.is_finite() && price > 0.0) {
+        return 1.0;
+    }
+    10f64.powi((price * TICK_PRICE_FRACTION).log10().floor() as i32)
+}
+
 #[derive(Debug, Clone)]
 pub struct VolumeNode {
     pub price_level: f64,
@@ -35,6 +53,11 @@ impl VolumeProfile {
         }
     }
 
+    pub fn reset(&mut self) {
+        self.price_levels.clear();
+        self.data_points.clear();
+    }
+
     pub fn update(&mut self, close: f64, volume: f64) {
         self.data_points.push((close, volume));
         if self.data_points.len() > self.lookback {
@@ -60,7 +83,13 @@ impl VolumeProfile {
                 volume: *vol,
             })
             .collect();
-        nodes.sort_by(|a, b| b.volume.partial_cmp(&a.volume).unwrap());
+        // Ties go to the lower price so the POC does not depend on map order
+        nodes.sort_by(|a, b| {
+            b.volume
+                .partial_cmp(&a.volume)
+                .unwrap()
+                .then(a.price_level.partial_cmp(&b.price_level).unwrap())
+        });
 
         let poc = nodes.first().map(|n| n.price_level).unwrap_or(0.0);
         let total_volume: f64 = nodes.iter().map(|n| n.volume).sum();
@@ -98,4 +127,53 @@ impl VolumeProfile {
 
         (nodes, poc, signal)
     }
+
+    /// `(low, high)` prices of the value area: the price levels around the
+    /// POC holding [`VALUE_AREA_VOLUME_PCT`] of the total volume.
+    ///
+    /// Starting at the POC, the area grows one level at a time towards the
+    /// neighbour with more volume, or both ways when they hold the same
+    /// volume, until it covers enough volume. `None` without any volume.
+    pub fn value_area(&self) -> Option<(f64, f64)> {
+        let total_volume: f64 = self.price_levels.values().sum();
+        if total_volume <= 0.0 {
+            return None;
+        }
+        let poc = self
+            .price_levels
+            .iter()
+            .max_by(|(a_bucket, a_volume), (b_bucket, b_volume)| {
+                a_volume
+                    .partial_cmp(b_volume)
+                    .unwrap()
+                    .then(b_bucket.cmp(a_bucket))
+            })
+            .map(|(bucket, _)| *bucket)?;
+        let min_bucket = *self.price_levels.keys().min()?;
+        let max_bucket = *self.price_levels.keys().max()?;
+        let volume_at = |bucket: i64| self.price_levels.get(&bucket).copied().unwrap_or(0.0);
+
+        let target = total_volume * VALUE_AREA_VOLUME_PCT;
+        let (mut low, mut high) = (poc, poc);
+        let mut covered = volume_at(poc);
+        while covered < target && (low > min_bucket || high < max_bucket) {
+            let above = (high < max_bucket).then(|| volume_at(high + 1));
+            let below = (low > min_bucket).then(|| volume_at(low - 1));
+            let (grow_up, grow_down) = match (above, below) {
+                (Some(above), Some(below)) => (above >= below, below >= above),
+                (Some(_), None) => (true, false),
+                _ => (false, true),
+            };
+            if grow_up {
+                high += 1;
+                covered += volume_at(high);
+            }
+            if grow_down {
+                low -= 1;
+                covered += volume_at(low);
+            }
+        }
+
+        Some((low as f64 * self.tick_size, high as f64 * self.tick_size))
+    }
 }
@@ -1,9 +1,19 @@
 //! Job context for dependency injection
 
+use crate::cache::redis::strategies_key;
+use crate::cache::RedisCache;
+use crate::core::runtime::RetryPolicyConfig;
 use crate::db::QuestDatabase;
 use crate::metrics::Metrics;
+use crate::models::dead_letter::DeadLetter;
+use crate::models::signal::SignalOutput;
+use crate::models::strategy::Strategy;
+use crate::output::{DiscordNotifier, NotificationConfig, TelegramNotifier, WebhookNotifier};
 use crate::services::market_data::MarketDataProvider;
+use crate::signals::risk::RollingSharpePenalty;
 use std::sync::Arc;
+use tokio::sync::{broadcast, RwLock};
+use tracing::{debug, warn};
 
 /// Context passed to job handlers via Apalis Data<T> pattern
 /// 
@@ -11,6 +21,15 @@ use std::sync::Arc;
 /// - Market data provider (reads from Redis/QuestDB cache)
 /// - Database (for storing signals)
 /// - Metrics (for tracking evaluation statistics)
+/// - Cache (for publishing the latest signal per symbol, and strategies to
+///   evaluate while QuestDB is unavailable)
+/// - Notification channels (Discord, Telegram) for high-confidence signals,
+///   and a signed webhook for every stored signal
+/// - An optional in-process broadcast of stored signals, for an API server
+///   running in the same process
+/// - The rolling Sharpe ratio of stored signals, shared across jobs to
+///   discount confidence after a run of poor signals
+/// - The retry policy of each job type
 /// 
 /// Note: WebSocket service is NOT included - jobs never create connections,
 /// they only read from stored data.
@@ -18,6 +37,16 @@ pub struct JobContext {
     pub data_provider: Arc<dyn MarketDataProvider + Send + Sync>,
     pub database: Option<Arc<QuestDatabase>>,
     pub metrics: Option<Arc<Metrics>>,
+    pub cache: Option<Arc<RedisCache>>,
+    /// Evaluation interval, used to derive cache TTLs for signals
+    pub eval_interval_seconds: u64,
+    pub notifications: NotificationConfig,
+    /// Receives every stored actionable signal; API servers in other
+    /// processes get them through Redis instead
+    pub signal_stream: Option<broadcast::Sender<SignalOutput>>,
+    pub sharpe_penalty: Arc<RwLock<RollingSharpePenalty>>,
+    /// Never retries unless set with [`JobContext::with_retry_policies`]
+    pub retry_policies: RetryPolicyConfig,
 }
 
 impl JobContext {
@@ -30,6 +59,163 @@ impl JobContext {
             data_provider,
             database,
             metrics,
+            cache: None,
+            eval_interval_seconds: 60,
+            notifications: NotificationConfig::default(),
+            signal_stream: None,
+            sharpe_penalty: Arc::new(RwLock::new(RollingSharpePenalty::default())),
+            retry_policies: RetryPolicyConfig::default(),
+        }
+    }
+
+    pub fn with_cache(mut self, cache: Arc<RedisCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    pub fn with_notifications(mut self, notifications: NotificationConfig) -> Self {
+        self.notifications = notifications;
+        self
+    }
+
+    pub fn with_discord(mut self, discord: Arc<DiscordNotifier>) -> Self {
+        self.notifications.discord = Some(discord);
+        self
+    }
+
+    pub fn with_telegram(mut self, telegram: Arc<TelegramNotifier>) -> Self {
+        self.notifications.telegram = Some(telegram);
+        self
+    }
+
+    pub fn with_webhook(mut self, webhook: Arc<WebhookNotifier>) -> Self {
+        self.notifications.webhook = Some(webhook);
+        self
+    }
+
+    /// Send every stored signal to `signal_stream`, e.g. an [`AppState`]'s
+    ///
+    /// [`AppState`]: crate::core::http::AppState
+    pub fn with_signal_stream(mut self, signal_stream: broadcast::Sender<SignalOutput>) -> Self {
+        self.signal_stream = Some(signal_stream);
+        self
+    }
+
+    /// Share a Sharpe ratio tracker, e.g. with another context in the same
+    /// process
+    pub fn with_sharpe_penalty(
+        mut self,
+        sharpe_penalty: Arc<RwLock<RollingSharpePenalty>>,
+    ) -> Self {
+        self.sharpe_penalty = sharpe_penalty;
+        self
+    }
+
+    pub fn with_retry_policies(mut self, retry_policies: RetryPolicyConfig) -> Self {
+        self.retry_policies = retry_policies;
+        self
+    }
+
+    pub fn with_eval_interval(mut self, eval_interval_seconds: u64) -> Self {
+        self.eval_interval_seconds = eval_interval_seconds;
+        self
+    }
+
+    /// Strategies for `symbol`, read from the Redis cache first.
+    ///
+    /// On a miss they are loaded from QuestDB and cached, so evaluation keeps
+    /// going from the cached copy while QuestDB is unavailable. Lookup
+    /// failures are logged and yield no strategies.
+    pub async fn get_strategies_cached(&self, symbol: &str) -> Vec<Strategy> {
+        if let Some(ref cache) = self.cache {
+            match cache
+                .get_json::<Vec<Strategy>>(&strategies_key(symbol))
+                .await
+            {
+                Ok(Some(strategies)) => {
+                    if let Some(ref metrics) = self.metrics {
+                        metrics.strategy_cache_hits_total.inc();
+                    }
+                    return strategies;
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    warn!(symbol = %symbol, error = %e, "Failed to read strategies for {} from cache", symbol);
+                }
+            }
+            if let Some(ref metrics) = self.metrics {
+                metrics.strategy_cache_misses_total.inc();
+            }
+        }
+
+        let Some(ref db) = self.database else {
+            debug!(symbol = %symbol, "No database or cached strategies available for {}", symbol);
+            return Vec::new();
+        };
+        match db.get_strategies(Some(symbol)).await {
+            Ok(strategies) => {
+                // An unavailable database also reads as empty, so only cache real results
+                if !strategies.is_empty() {
+                    self.cache_strategies(symbol, &strategies).await;
+                }
+                strategies
+            }
+            Err(e) => {
+                warn!(symbol = %symbol, error = %e, "Failed to load strategies for {}", symbol);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Reload the cached strategies for `symbols` from QuestDB.
+    ///
+    /// Called at startup and whenever QuestDB is healthy again, so edits made
+    /// through the API reach the cache and a symbol whose strategies were all
+    /// deleted is cached as empty.
+    pub async fn refresh_strategy_cache(
+        &self,
+        symbols: &[String],
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let (Some(db), Some(_)) = (&self.database, &self.cache) else {
+            return Ok(());
+        };
+        for symbol in symbols {
+            let strategies = db.get_strategies(Some(symbol)).await?;
+            self.cache_strategies(symbol, &strategies).await;
+        }
+        Ok(())
+    }
+
+    /// Store `strategies` under `strategies:{symbol}` without expiry
+    async fn cache_strategies(&self, symbol: &str, strategies: &[Strategy]) {
+        let Some(ref cache) = self.cache else {
+            return;
+        };
+        if let Err(e) = cache
+            .set_json(&strategies_key(symbol), &strategies, 0)
+            .await
+        {
+            warn!(symbol = %symbol, error = %e, "Failed to cache strategies for {}", symbol);
+        }
+    }
+
+    /// Persist a failed job to the dead-letter table.
+    ///
+    /// Does nothing without a database; failing to store the record is only
+    /// logged so it never masks the original error.
+    pub async fn record_failure(&self, dead_letter: DeadLetter) {
+        let Some(ref db) = self.database else {
+            return;
+        };
+        if let Err(e) = db.store_dead_letter(&dead_letter).await {
+            warn!(
+                job_type = %dead_letter.job_type,
+                symbol = %dead_letter.symbol,
+                error = %e,
+                "Failed to record dead letter for {} job on {}",
+                dead_letter.job_type,
+                dead_letter.symbol
+            );
         }
     }
 }
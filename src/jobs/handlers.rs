@@ -1,41 +1,123 @@
 //! Job handlers for signal evaluation workflow
 
+use crate::cache::redis::{latest_signal_key, signal_cluster_key, SIGNALS_CHANNEL};
+use crate::config::{get_strategy_timeout_seconds, get_worker_evaluation_parallelism};
+use crate::error::KryptexError;
+use crate::indicators::validation::validate_candle_series;
 use crate::jobs::context::JobContext;
-use crate::jobs::types::{EvaluateSignalJob, FetchCandlesJob, StoreSignalJob};
+use crate::jobs::types::{EvaluateSignalJob, FetchCandlesJob, JobType, StoreSignalJob};
+use crate::models::dead_letter::DeadLetter;
+use crate::positions::PositionTracker;
+use crate::signals::clustering::{
+    SignalCluster, DEFAULT_CLUSTER_SIGNAL_LIMIT, DEFAULT_CLUSTER_WINDOW_SECONDS,
+};
 use crate::signals::engine::MIN_CANDLES;
+use crate::signals::risk::RollingSharpePenalty;
+use crate::strategies::{LiveMarketData, StrategyEvaluator};
 use apalis::prelude::*;
+use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Instant;
-use tracing::{debug, error, info};
+use std::time::{Duration, Instant};
+use tracing::{debug, error, info, warn};
 
 /// Handler for fetching candles job
 /// 
 /// Reads candles from the data provider (which reads from Redis/QuestDB cache).
 /// If candles are available, enqueues EvaluateSignalJob.
+/// Failures are retried per the context's retry policy, then recorded in the
+/// dead-letter table (see [`record_failed_attempt`]).
 pub async fn handle_fetch_candles(
     job: FetchCandlesJob,
     ctx: Data<Arc<JobContext>>,
     eval_storage: Data<apalis_redis::RedisStorage<EvaluateSignalJob>>,
-) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    attempt: Attempt,
+) -> Result<(), KryptexError> {
+    let result = fetch_candles(&job, &ctx, &eval_storage).await;
+    if let Err(ref e) = result {
+        record_failed_attempt(
+            &ctx,
+            JobType::FetchCandles,
+            &attempt,
+            e,
+            DeadLetter::new(JobType::FetchCandles.as_str(), &job.symbol, e.to_string())
+                .with_retry_count(attempt.current())
+                .with_payload(&job),
+        )
+        .await;
+    }
+    result
+}
+
+/// How often a deferred fetch re-checks the high-priority queue
+const PRIORITY_QUEUE_POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// Longest a deferred fetch waits on the high-priority queue, so jobs stuck
+/// there cannot starve the default queue
+const PRIORITY_QUEUE_MAX_WAIT: Duration = Duration::from_secs(5);
+
+/// Handler for fetching candles from the default queue when a high-priority
+/// queue exists
+///
+/// Waits until `priority_storage` has no outstanding jobs (for at most
+/// `PRIORITY_QUEUE_MAX_WAIT`), then behaves like [`handle_fetch_candles`], so
+/// symbols with a confident previous signal are re-evaluated first.
+pub async fn handle_deferred_fetch_candles(
+    job: FetchCandlesJob,
+    ctx: Data<Arc<JobContext>>,
+    eval_storage: Data<apalis_redis::RedisStorage<EvaluateSignalJob>>,
+    priority_storage: Data<apalis_redis::RedisStorage<FetchCandlesJob>>,
+    attempt: Attempt,
+) -> Result<(), KryptexError> {
+    wait_for_priority_queue(&priority_storage, &job.symbol).await;
+    handle_fetch_candles(job, ctx, eval_storage, attempt).await
+}
+
+async fn wait_for_priority_queue(
+    priority_storage: &apalis_redis::RedisStorage<FetchCandlesJob>,
+    symbol: &str,
+) {
+    let mut storage = priority_storage.clone();
+    let started = Instant::now();
+    loop {
+        match storage.len().await {
+            Ok(0) => return,
+            Ok(pending) if started.elapsed() >= PRIORITY_QUEUE_MAX_WAIT => {
+                warn!(
+                    symbol = %symbol,
+                    pending,
+                    "FetchCandlesJob: {} high-priority jobs still queued, fetching {} anyway",
+                    pending,
+                    symbol
+                );
+                return;
+            }
+            Ok(_) => tokio::time::sleep(PRIORITY_QUEUE_POLL_INTERVAL).await,
+            Err(e) => {
+                warn!(symbol = %symbol, error = %e, "FetchCandlesJob: failed to read high-priority queue length");
+                return;
+            }
+        }
+    }
+}
+
+async fn fetch_candles(
+    job: &FetchCandlesJob,
+    ctx: &JobContext,
+    eval_storage: &apalis_redis::RedisStorage<EvaluateSignalJob>,
+) -> Result<(), KryptexError> {
     debug!(symbol = %job.symbol, "FetchCandlesJob: fetching candles for {}", job.symbol);
 
     let candles = ctx
         .data_provider
         .get_candles(&job.symbol, 250)
         .await
-        .map_err(|e| {
-            Box::new(std::io::Error::other(format!(
-                "Market data error: {}",
-                e
-            ))) as Box<dyn std::error::Error + Send + Sync>
-        })?;
+        .map_err(|e| KryptexError::MarketData(e.to_string()))?;
 
     if candles.is_empty() {
         debug!(symbol = %job.symbol, "FetchCandlesJob: no candles available yet for {}", job.symbol);
-        return Err(Box::new(std::io::Error::new(
-            std::io::ErrorKind::NotFound,
-            format!("No candles available for {}", job.symbol),
-        )) as Box<dyn std::error::Error + Send + Sync>);
+        return Err(KryptexError::NotFound(format!(
+            "No candles available for {}",
+            job.symbol
+        )));
     }
 
     debug!(
@@ -56,28 +138,60 @@ pub async fn handle_fetch_candles(
             MIN_CANDLES,
             job.symbol
         );
-        return Err(Box::new(std::io::Error::new(
-            std::io::ErrorKind::InvalidData,
-            format!(
-                "Not enough candles: {} < {}",
-                candles.len(),
-                MIN_CANDLES
-            ),
-        )) as Box<dyn std::error::Error + Send + Sync>);
+        return Err(KryptexError::InvalidInput(format!(
+            "Not enough candles: {} < {}",
+            candles.len(),
+            MIN_CANDLES
+        )));
     }
 
-    // Enqueue next job: EvaluateSignalJob
-    let next_job = EvaluateSignalJob {
-        symbol: job.symbol.clone(),
-        candles,
+    let mut hard_failures = Vec::new();
+    for issue in validate_candle_series(&candles) {
+        if issue.is_hard_failure() {
+            hard_failures.push(issue.to_string());
+        } else {
+            warn!(symbol = %job.symbol, "FetchCandlesJob: {}", issue);
+        }
+    }
+    if !hard_failures.is_empty() {
+        return Err(KryptexError::InvalidInput(format!(
+            "Invalid candle series for {}: {}",
+            job.symbol,
+            hard_failures.join("; ")
+        )));
+    }
+
+    // Other intervals are only used by multi-timeframe strategies, so they
+    // are skipped when none is enabled, and a failure here falls back to the
+    // primary series
+    let strategies = ctx.get_strategies_cached(&job.symbol).await;
+    let needs_intervals = strategies
+        .iter()
+        .any(|strategy| strategy.enabled && strategy.config.timeframe_weights.is_some());
+    let candles_by_interval = if !needs_intervals {
+        HashMap::new()
+    } else {
+        match ctx
+            .data_provider
+            .get_candles_by_interval(&job.symbol, 250)
+            .await
+        {
+            Ok(candles_by_interval) => candles_by_interval
+                .into_iter()
+                .filter(|(_, candles)| candles.len() >= MIN_CANDLES)
+                .collect(),
+            Err(e) => {
+                warn!(symbol = %job.symbol, error = %e, "FetchCandlesJob: failed to fetch per-interval candles for {}", job.symbol);
+                HashMap::new()
+            }
+        }
     };
-    let mut storage = (*eval_storage).clone();
-    storage.push(next_job).await.map_err(|e| {
-        Box::new(std::io::Error::other(format!(
-            "Failed to enqueue EvaluateSignalJob: {}",
-            e
-        ))) as Box<dyn std::error::Error + Send + Sync>
-    })?;
+
+    // Enqueue next job: EvaluateSignalJob
+    let next_job = EvaluateSignalJob::new(job.symbol.clone(), candles)
+        .with_candles_by_interval(candles_by_interval);
+    let mut storage = eval_storage.clone();
+    storage.push(next_job).await?;
 
     debug!(symbol = %job.symbol, "FetchCandlesJob: enqueued EvaluateSignalJob for {}", job.symbol);
     Ok(())
@@ -85,51 +199,170 @@ pub async fn handle_fetch_candles(
 
 /// Handler for evaluating signal job
 /// 
-/// Loads strategies for the symbol and evaluates each one.
+/// Loads strategies for the symbol (see [`JobContext::get_strategies_cached`])
+/// and evaluates each one.
 /// If signals are generated, enqueues StoreSignalJob for each.
+/// Failures are retried per the context's retry policy, then recorded in the
+/// dead-letter table (see [`record_failed_attempt`]).
 pub async fn handle_evaluate_signal(
     job: EvaluateSignalJob,
     ctx: Data<Arc<JobContext>>,
     store_storage: Data<apalis_redis::RedisStorage<StoreSignalJob>>,
-) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    attempt: Attempt,
+) -> Result<(), KryptexError> {
+    let result = evaluate_signal(&job, &ctx, &store_storage).await;
+    if let Err(ref e) = result {
+        record_failed_attempt(
+            &ctx,
+            JobType::EvaluateSignal,
+            &attempt,
+            e,
+            DeadLetter::new(JobType::EvaluateSignal.as_str(), &job.symbol, e.to_string())
+                .with_retry_count(attempt.current())
+                .with_payload(&job),
+        )
+        .await;
+    }
+    result
+}
+
+/// Count a failed attempt against the job type's retry policy.
+///
+/// The worker's retry layer waits out the backoff and retries the job once
+/// the error is returned; this only records the retry, or the dead letter
+/// when no retries are left. Errors that are not
+/// [transient](KryptexError::is_transient) are never retried.
+async fn record_failed_attempt(
+    ctx: &JobContext,
+    job_type: JobType,
+    attempt: &Attempt,
+    error: &KryptexError,
+    dead_letter: DeadLetter,
+) {
+    let policy = ctx.retry_policies.for_job(job_type);
+    if !error.is_transient() {
+        debug!(
+            job_type = job_type.as_str(),
+            symbol = %dead_letter.symbol,
+            "Not retrying {} job for {}, the error is permanent",
+            job_type.as_str(),
+            dead_letter.symbol
+        );
+        ctx.record_failure(dead_letter).await;
+    } else if policy.should_retry(attempt.current()) {
+        let retry_number = attempt.current();
+        debug!(
+            job_type = job_type.as_str(),
+            symbol = %dead_letter.symbol,
+            retry_number,
+            "Retrying {} job for {} ({} of {}) in {}s",
+            job_type.as_str(),
+            dead_letter.symbol,
+            retry_number,
+            policy.max_retries,
+            policy.backoff_seconds
+        );
+        if let Some(ref metrics) = ctx.metrics {
+            metrics
+                .job_retries_total
+                .with_label_values(&[job_type.as_str(), &retry_number.to_string()])
+                .inc();
+        }
+    } else {
+        if let Some(ref metrics) = ctx.metrics {
+            metrics
+                .job_max_retries_exceeded_total
+                .with_label_values(&[job_type.as_str()])
+                .inc();
+        }
+        ctx.record_failure(dead_letter).await;
+    }
+}
+
+async fn evaluate_signal(
+    job: &EvaluateSignalJob,
+    ctx: &JobContext,
+    store_storage: &apalis_redis::RedisStorage<StoreSignalJob>,
+) -> Result<(), KryptexError> {
+    let candles = job
+        .candles()
+        .map_err(|e| KryptexError::InvalidInput(format!("Failed to read job candles: {}", e)))?;
+    let candles_by_interval = job.candles_by_interval().map_err(|e| {
+        KryptexError::InvalidInput(format!("Failed to read job candles by interval: {}", e))
+    })?;
+
     debug!(
         symbol = %job.symbol,
-        candle_count = job.candles.len(),
+        candle_count = candles.len(),
         "EvaluateSignalJob: evaluating signals for {} with {} candles",
         job.symbol,
-        job.candles.len()
+        candles.len()
     );
 
-    // Load strategies for this symbol
-    let strategies = if let Some(ref db) = ctx.database {
-        db.get_strategies(Some(&job.symbol)).await.map_err(|e| {
-            Box::new(std::io::Error::other(format!(
-                "Failed to load strategies: {}",
-                e
-            ))) as Box<dyn std::error::Error + Send + Sync>
-        })?
-    } else {
-        debug!(
-            symbol = %job.symbol,
-            "EvaluateSignalJob: no database available, skipping strategy evaluation"
-        );
-        return Ok(());
-    };
+    // Load enabled strategies for this symbol, from the cache if QuestDB is unavailable
+    let mut strategies = ctx.get_strategies_cached(&job.symbol).await;
+    strategies.retain(|strategy| strategy.enabled);
 
     // If no strategies found, gracefully skip (no error)
     if strategies.is_empty() {
         debug!(
             symbol = %job.symbol,
-            "EvaluateSignalJob: no strategies found for {}, skipping evaluation",
+            "EvaluateSignalJob: no enabled strategies found for {}, skipping evaluation",
             job.symbol
         );
         return Ok(());
     }
 
-    // Evaluate each strategy
+    // Live readings for Spread and BuySellRatio conditions; strategies without them ignore these
+    let market_data = LiveMarketData {
+        top_of_book: ctx.data_provider.get_spread(&job.symbol).await,
+        buy_sell_ratio: ctx.data_provider.get_buy_sell_ratio(&job.symbol).await,
+    };
+
+    // Evaluate strategies concurrently, then handle their results in order
+    let candles = Arc::new(candles);
+    let candles_by_interval = Arc::new(candles_by_interval);
+    let timeout = Duration::from_secs(get_strategy_timeout_seconds());
+    let parallelism = get_worker_evaluation_parallelism(strategies.len());
+    let metrics = ctx.metrics.clone();
+    let evaluations =
+        StrategyEvaluator::evaluate_concurrent(&strategies, parallelism, timeout, move |strategy| {
+            if strategy.config.timeframe_weights.is_some() && !candles_by_interval.is_empty() {
+                StrategyEvaluator::evaluate_multi_timeframe_with_market_data(
+                    &strategy,
+                    &candles_by_interval,
+                    market_data,
+                    metrics.as_deref(),
+                )
+            } else {
+                StrategyEvaluator::evaluate_strategy_with_market_data(
+                    &strategy,
+                    &candles,
+                    market_data,
+                    metrics.as_deref(),
+                )
+            }
+        })
+        .await;
     let mut signals_generated = 0;
-    for strategy in &strategies {
-        if let Some(signal) = crate::signals::engine::SignalEngine::evaluate(&job.candles, strategy) {
+    for (strategy, evaluation) in strategies.iter().zip(evaluations) {
+        let Ok(evaluation) = evaluation else {
+            warn!(
+                symbol = %job.symbol,
+                strategy_id = strategy.id.unwrap_or(0),
+                strategy_name = %strategy.name,
+                timeout_seconds = timeout.as_secs(),
+                "EvaluateSignalJob: strategy '{}' timed out after {:?}, skipping",
+                strategy.name,
+                timeout
+            );
+            if let Some(ref metrics) = ctx.metrics {
+                metrics.strategy_evaluation_timeouts_total.inc();
+            }
+            continue;
+        };
+
+        if let Some(signal) = evaluation {
             let confidence_pct = (signal.confidence * 10000.0).round() / 100.0;
             info!(
                 symbol = %job.symbol,
@@ -150,13 +383,8 @@ pub async fn handle_evaluate_signal(
                 signal,
                 strategy_id: strategy.id.unwrap_or(0),
             };
-            let mut storage = (*store_storage).clone();
-            storage.push(next_job).await.map_err(|e| {
-                Box::new(std::io::Error::other(format!(
-                    "Failed to enqueue StoreSignalJob: {}",
-                    e
-                ))) as Box<dyn std::error::Error + Send + Sync>
-            })?;
+            let mut storage = store_storage.clone();
+            storage.push(next_job).await?;
 
             signals_generated += 1;
         } else {
@@ -186,48 +414,93 @@ pub async fn handle_evaluate_signal(
 
 /// Handler for storing signal job
 /// 
-/// Stores the signal in the database and updates metrics.
-/// This is the final step in the workflow.
+/// Stores the signal in the database and updates metrics, after scaling its
+/// confidence down while the rolling Sharpe ratio of recent signals is below
+/// target (see [`RollingSharpePenalty`]).
+/// This is the final step in the workflow. A failed database write fails the
+/// job before the signal is published, so it is retried per the context's
+/// retry policy and then recorded in the dead-letter table.
 pub async fn handle_store_signal(
-    job: StoreSignalJob,
+    mut job: StoreSignalJob,
     ctx: Data<Arc<JobContext>>,
-) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    attempt: Attempt,
+) -> Result<(), KryptexError> {
     let start = Instant::now();
     let symbol = &job.symbol;
 
+    // Skip signals that would stack onto a position already open in the same
+    // direction; if the lookup fails the signal goes through
+    let positions = ctx.cache.clone().map(PositionTracker::new);
+    if let Some(ref positions) = positions {
+        match positions.is_duplicate(&job.signal).await {
+            Ok(true) => {
+                info!(
+                    symbol = %symbol,
+                    direction = ?job.signal.direction,
+                    "StoreSignalJob: skipping {:?} signal for {}, a position in that direction is already open",
+                    job.signal.direction,
+                    symbol
+                );
+                if let Some(ref metrics) = ctx.metrics {
+                    metrics.signals_suppressed_duplicate_direction_total.inc();
+                }
+                return Ok(());
+            }
+            Ok(false) => {}
+            Err(e) => warn!(
+                symbol = %symbol,
+                error = %e,
+                "StoreSignalJob: failed to check open position for {}",
+                symbol
+            ),
+        }
+    }
+
     // Track active evaluation
     if let Some(ref metrics) = ctx.metrics {
         metrics.signal_evaluations_active.inc();
     }
 
+    // Discount confidence while recent signals have a poor Sharpe ratio; the
+    // signal itself is only recorded once stored, so a retry can't count twice
+    let sharpe = ctx.sharpe_penalty.read().await.current_sharpe();
+    let raw_confidence = job.signal.confidence;
+    job.signal.confidence = RollingSharpePenalty::penalize_confidence(raw_confidence, sharpe);
+    if job.signal.confidence < raw_confidence {
+        debug!(
+            symbol = %symbol,
+            sharpe,
+            "StoreSignalJob: rolling Sharpe {:.2} lowered confidence for {} from {:.4} to {:.4}",
+            sharpe,
+            symbol,
+            raw_confidence,
+            job.signal.confidence
+        );
+    }
+
     // Log at different levels based on signal strength
     let confidence_pct = (job.signal.confidence * 10000.0).round() / 100.0;
-    if job.signal.direction == crate::models::signal::SignalDirection::Neutral {
-        debug!(
+    if job.signal.is_actionable() {
+        info!(
             symbol = %symbol,
             direction = ?job.signal.direction,
             confidence = confidence_pct,
-            "StoreSignalJob: storing neutral signal for {} (confidence: {:.2}%)",
+            "StoreSignalJob: storing signal for {}: {:?} (confidence: {:.2}%)",
             symbol,
+            job.signal.direction,
             confidence_pct
         );
     } else {
-        info!(
+        debug!(
             symbol = %symbol,
             direction = ?job.signal.direction,
             confidence = confidence_pct,
-            "StoreSignalJob: storing signal for {}: {:?} (confidence: {:.2}%)",
+            "StoreSignalJob: storing non-actionable signal for {} (confidence: {:.2}%)",
             symbol,
-            job.signal.direction,
             confidence_pct
         );
     }
 
-    // Record successful evaluation
-    if let Some(ref metrics) = ctx.metrics {
-        metrics.signal_evaluations_total.inc();
-    }
-
     // Store signal in database if available
     if let Some(ref db) = ctx.database {
         if let Err(e) = db.store_signal(&job.signal, job.strategy_id).await {
@@ -239,18 +512,116 @@ pub async fn handle_store_signal(
                 symbol,
                 job.strategy_id
             );
-            // Still count as evaluation (storage failure is separate from evaluation success)
-        } else {
-            debug!(
+            record_failed_attempt(
+                &ctx,
+                JobType::StoreSignal,
+                &attempt,
+                &e,
+                DeadLetter::new(JobType::StoreSignal.as_str(), symbol, e.to_string())
+                    .with_strategy_id(job.strategy_id)
+                    .with_retry_count(attempt.current())
+                    .with_payload(&job),
+            )
+            .await;
+            if let Some(ref metrics) = ctx.metrics {
+                metrics.signal_evaluations_active.dec();
+            }
+            return Err(e);
+        }
+        debug!(
+            symbol = %symbol,
+            strategy_id = job.strategy_id,
+            "StoreSignalJob: stored signal in database for {} (strategy_id: {})",
+            symbol,
+            job.strategy_id
+        );
+    }
+    ctx.sharpe_penalty
+        .write()
+        .await
+        .record_signal(job.strategy_id, &job.signal);
+
+    // Record successful evaluation
+    if let Some(ref metrics) = ctx.metrics {
+        metrics.signal_evaluations_total.inc();
+        metrics.record_signal(symbol, job.signal.direction, job.signal.confidence);
+    }
+
+    // Publish as the latest signal for the symbol so readers can skip QuestDB
+    if let Some(ref cache) = ctx.cache {
+        let ttl_seconds = 2 * ctx.eval_interval_seconds;
+        if let Err(e) = cache
+            .set_json(&latest_signal_key(symbol), &job.signal, ttl_seconds)
+            .await
+        {
+            error!(
                 symbol = %symbol,
-                strategy_id = job.strategy_id,
-                "StoreSignalJob: stored signal in database for {} (strategy_id: {})",
-                symbol,
-                job.strategy_id
+                error = %e,
+                "StoreSignalJob: failed to cache latest signal for {}",
+                symbol
             );
         }
     }
 
+    // Only actionable signals are broadcast and open a position
+    if job.signal.is_actionable() {
+        // API servers relay published signals to `/api/signals/stream` clients
+        if let Some(ref cache) = ctx.cache {
+            if let Err(e) = cache.publish_json(SIGNALS_CHANNEL, &job.signal).await {
+                warn!(
+                    symbol = %symbol,
+                    error = %e,
+                    "StoreSignalJob: failed to publish signal for {}",
+                    symbol
+                );
+            }
+        }
+        if let Some(ref positions) = positions {
+            if let Err(e) = positions.record(&job.signal).await {
+                error!(
+                    symbol = %symbol,
+                    error = %e,
+                    "StoreSignalJob: failed to record open position for {}",
+                    symbol
+                );
+            }
+        }
+        if let Some(ref signal_stream) = ctx.signal_stream {
+            // Sending only fails when no client is listening
+            let _ = signal_stream.send(job.signal.clone());
+        }
+    }
+
+    // Post high-confidence signals to each channel, and every signal to the
+    // webhook; a failed post never fails the job
+    if let Some(ref discord) = ctx.notifications.discord {
+        log_notification(symbol, "Discord", discord.notify(&job.signal).await);
+    }
+    if let Some(ref telegram) = ctx.notifications.telegram {
+        log_notification(symbol, "Telegram", telegram.notify(&job.signal).await);
+    }
+    if let Some(ref webhook) = ctx.notifications.webhook {
+        // Delivery retries for several seconds, so it runs off the job
+        let webhook = webhook.clone();
+        let metrics = ctx.metrics.clone();
+        let signal = job.signal.clone();
+        tokio::spawn(async move {
+            let delivery_start = Instant::now();
+            let result = webhook.deliver(&signal).await;
+            if let Some(ref metrics) = metrics {
+                metrics
+                    .webhook_delivery_latency_seconds
+                    .observe(delivery_start.elapsed().as_secs_f64());
+                if result.is_ok() {
+                    metrics.webhook_deliveries_total.inc();
+                } else {
+                    metrics.webhook_failures_total.inc();
+                }
+            }
+            log_notification(&signal.symbol, "webhook", result.map(|()| true));
+        });
+    }
+
     // Record duration and decrement active
     if let Some(ref metrics) = ctx.metrics {
         let duration = start.elapsed();
@@ -263,3 +634,75 @@ pub async fn handle_store_signal(
     Ok(())
 }
 
+
+/// Log the outcome of posting a signal to a notification channel
+fn log_notification(
+    symbol: &str,
+    channel: &str,
+    result: Result<bool, Box<dyn std::error::Error + Send + Sync>>,
+) {
+    match result {
+        Ok(true) => debug!(
+            symbol = %symbol,
+            channel,
+            "StoreSignalJob: posted signal for {} to {}",
+            symbol,
+            channel
+        ),
+        Ok(false) => {}
+        Err(e) => warn!(
+            symbol = %symbol,
+            channel,
+            error = %e,
+            "StoreSignalJob: failed to post signal for {} to {}",
+            symbol,
+            channel
+        ),
+    }
+}
+
+/// How often [`handle_cluster_signals`] runs
+pub const CLUSTER_SIGNALS_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Periodic job clustering each symbol's most recent signals.
+///
+/// Reads the last signals for each symbol from QuestDB and caches their
+/// [`ClusterAnalysis`](crate::signals::clustering::ClusterAnalysis) in Redis
+/// until the next run. Does nothing without both a database and a cache;
+/// a symbol that fails is logged and skipped.
+pub async fn handle_cluster_signals(ctx: &JobContext, symbols: &[String]) {
+    let (Some(db), Some(cache)) = (&ctx.database, &ctx.cache) else {
+        return;
+    };
+    let ttl_seconds = 2 * CLUSTER_SIGNALS_INTERVAL.as_secs();
+    for symbol in symbols {
+        let signals = match db
+            .get_signals(Some(symbol), Some(DEFAULT_CLUSTER_SIGNAL_LIMIT))
+            .await
+        {
+            Ok(signals) => signals,
+            Err(e) => {
+                warn!(symbol = %symbol, error = %e, "ClusterSignals: failed to load signals for {}", symbol);
+                continue;
+            }
+        };
+        let analysis = SignalCluster::analyze(&signals, DEFAULT_CLUSTER_WINDOW_SECONDS);
+        debug!(
+            symbol = %symbol,
+            direction = ?analysis.cluster_direction,
+            agreement_ratio = analysis.agreement_ratio,
+            signal_count = analysis.signal_count,
+            "ClusterSignals: {} signals for {} agree {:.0}% on {:?}",
+            analysis.signal_count,
+            symbol,
+            analysis.agreement_ratio * 100.0,
+            analysis.cluster_direction
+        );
+        if let Err(e) = cache
+            .set_json(&signal_cluster_key(symbol), &analysis, ttl_seconds)
+            .await
+        {
+            warn!(symbol = %symbol, error = %e, "ClusterSignals: failed to cache cluster analysis for {}", symbol);
+        }
+    }
+}
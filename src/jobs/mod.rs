@@ -2,11 +2,13 @@
 
 pub mod context;
 pub mod handlers;
+pub mod replay;
 pub mod types;
 pub mod workflow;
 
 pub use context::JobContext;
-pub use types::{EvaluateSignalJob, FetchCandlesJob, StoreSignalJob};
+pub use replay::JobQueues;
+pub use types::{EvaluateSignalJob, FetchCandlesJob, JobPriority, StoreSignalJob};
 
 
 
@@ -0,0 +1,79 @@
+//! Replay of failed jobs recorded in the dead-letter table
+
+use crate::jobs::types::{EvaluateSignalJob, FetchCandlesJob, JobType, StoreSignalJob};
+use crate::models::dead_letter::DeadLetter;
+use apalis::prelude::*;
+use apalis_redis::RedisStorage;
+use serde::de::DeserializeOwned;
+
+/// Queues for every job in the signal workflow
+#[derive(Clone)]
+pub struct JobQueues {
+    pub fetch: RedisStorage<FetchCandlesJob>,
+    pub evaluate: RedisStorage<EvaluateSignalJob>,
+    pub store: RedisStorage<StoreSignalJob>,
+}
+
+/// Rebuild the failed job from a dead letter and push it back onto its queue.
+///
+/// Fetch jobs only need the symbol, so they can be replayed even when no
+/// payload was recorded. Evaluate and store jobs need the stored payload.
+/// Dead letters that cannot be turned back into a job fail with
+/// `ErrorKind::InvalidData`.
+pub async fn replay_dead_letter(
+    dead_letter: &DeadLetter,
+    queues: &JobQueues,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let job_type = JobType::parse(&dead_letter.job_type)
+        .ok_or_else(|| invalid_data(format!("Unknown job type '{}'", dead_letter.job_type)))?;
+
+    let pushed = match job_type {
+        JobType::FetchCandles => {
+            let job = match dead_letter.payload {
+                Some(_) => decode_payload(dead_letter)?,
+                None => FetchCandlesJob::new(dead_letter.symbol.clone()),
+            };
+            queues.fetch.clone().push(job).await.map(|_| ())
+        }
+        JobType::EvaluateSignal => {
+            let job: EvaluateSignalJob = decode_payload(dead_letter)?;
+            queues.evaluate.clone().push(job).await.map(|_| ())
+        }
+        JobType::StoreSignal => {
+            let job: StoreSignalJob = decode_payload(dead_letter)?;
+            queues.store.clone().push(job).await.map(|_| ())
+        }
+    };
+
+    pushed.map_err(|e| {
+        Box::new(std::io::Error::other(format!(
+            "Failed to enqueue {} job: {}",
+            job_type.as_str(),
+            e
+        ))) as Box<dyn std::error::Error + Send + Sync>
+    })
+}
+
+fn decode_payload<T: DeserializeOwned>(
+    dead_letter: &DeadLetter,
+) -> Result<T, Box<dyn std::error::Error + Send + Sync>> {
+    let payload = dead_letter.payload.as_deref().ok_or_else(|| {
+        invalid_data(format!(
+            "Dead letter for {} job has no stored payload",
+            dead_letter.job_type
+        ))
+    })?;
+    serde_json::from_str(payload).map_err(|e| {
+        invalid_data(format!(
+            "Failed to decode {} payload: {}",
+            dead_letter.job_type, e
+        ))
+    })
+}
+
+fn invalid_data(message: String) -> Box<dyn std::error::Error + Send + Sync> {
+    Box::new(std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        message,
+    ))
+}
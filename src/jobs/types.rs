@@ -1,20 +1,224 @@
 //! Job types for the signal evaluation workflow
 
+use crate::config;
 use crate::models::indicators::Candle;
 use crate::models::signal::SignalOutput;
-use serde::{Deserialize, Serialize};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
+
+/// The jobs in the signal workflow, as named in the dead-letter table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobType {
+    FetchCandles,
+    EvaluateSignal,
+    StoreSignal,
+}
+
+impl JobType {
+    /// Parse a job type as named in the dead-letter table
+    pub fn parse(job_type: &str) -> Option<Self> {
+        match job_type {
+            "fetch_candles" => Some(JobType::FetchCandles),
+            "evaluate_signal" => Some(JobType::EvaluateSignal),
+            "store_signal" => Some(JobType::StoreSignal),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobType::FetchCandles => "fetch_candles",
+            JobType::EvaluateSignal => "evaluate_signal",
+            JobType::StoreSignal => "store_signal",
+        }
+    }
+}
+
+/// Queue priority of a `FetchCandlesJob`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JobPriority {
+    /// Catch-up jobs for ticks missed while the scheduler was down
+    Low,
+    #[default]
+    Normal,
+    /// Symbols whose previous evaluation produced a confident Long or Short
+    High,
+}
+
+impl JobPriority {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobPriority::Low => "low",
+            JobPriority::Normal => "normal",
+            JobPriority::High => "high",
+        }
+    }
+
+    pub fn is_normal(&self) -> bool {
+        *self == JobPriority::Normal
+    }
+}
 
 /// Job to fetch candles for a symbol
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FetchCandlesJob {
     pub symbol: String,
+    /// Omitted from the payload when `Normal`, so older jobs still decode
+    #[serde(default, skip_serializing_if = "JobPriority::is_normal")]
+    pub priority: JobPriority,
+}
+
+impl FetchCandlesJob {
+    /// A `Normal` priority job for `symbol`
+    pub fn new(symbol: impl Into<String>) -> Self {
+        Self {
+            symbol: symbol.into(),
+            priority: JobPriority::Normal,
+        }
+    }
+
+    pub fn with_priority(mut self, priority: JobPriority) -> Self {
+        self.priority = priority;
+        self
+    }
 }
 
 /// Job to evaluate a signal from candles
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EvaluateSignalJob {
     pub symbol: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub candles: Vec<Candle>,
+    /// Compressed candle payload, used instead of `candles` when
+    /// `COMPRESS_JOB_PAYLOADS=true`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compressed_candles: Option<CompressedCandles>,
+    /// Candles for every tracked interval, for multi-timeframe strategies
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub candles_by_interval: HashMap<String, Vec<Candle>>,
+    /// Compressed form of `candles_by_interval`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub compressed_candles_by_interval: HashMap<String, CompressedCandles>,
+}
+
+impl EvaluateSignalJob {
+    /// Build a job, compressing the candles if `COMPRESS_JOB_PAYLOADS` is enabled
+    pub fn new(symbol: String, candles: Vec<Candle>) -> Self {
+        if config::get_compress_job_payloads() {
+            Self {
+                symbol,
+                candles: Vec::new(),
+                compressed_candles: Some(candles.into()),
+                candles_by_interval: HashMap::new(),
+                compressed_candles_by_interval: HashMap::new(),
+            }
+        } else {
+            Self {
+                symbol,
+                candles,
+                compressed_candles: None,
+                candles_by_interval: HashMap::new(),
+                compressed_candles_by_interval: HashMap::new(),
+            }
+        }
+    }
+
+    /// Attach candles for additional intervals, compressed like the main series
+    pub fn with_candles_by_interval(
+        mut self,
+        candles_by_interval: HashMap<String, Vec<Candle>>,
+    ) -> Self {
+        if config::get_compress_job_payloads() {
+            self.compressed_candles_by_interval = candles_by_interval
+                .into_iter()
+                .map(|(interval, candles)| (interval, candles.into()))
+                .collect();
+        } else {
+            self.candles_by_interval = candles_by_interval;
+        }
+        self
+    }
+
+    /// Candles carried by the job, decompressing them if needed
+    pub fn candles(&self) -> Result<Vec<Candle>, Box<dyn std::error::Error + Send + Sync>> {
+        match self.compressed_candles {
+            Some(ref compressed) => compressed.clone().try_into(),
+            None => Ok(self.candles.clone()),
+        }
+    }
+
+    /// Per-interval candles carried by the job, decompressing them if needed
+    pub fn candles_by_interval(
+        &self,
+    ) -> Result<HashMap<String, Vec<Candle>>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut candles_by_interval = self.candles_by_interval.clone();
+        for (interval, compressed) in &self.compressed_candles_by_interval {
+            candles_by_interval.insert(interval.clone(), compressed.clone().try_into()?);
+        }
+        Ok(candles_by_interval)
+    }
+}
+
+/// LZ4-compressed JSON encoding of a candle series.
+///
+/// Serialized as a base64 string so it stays compact inside JSON job payloads.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompressedCandles(pub Vec<u8>);
+
+impl CompressedCandles {
+    /// Size of the compressed payload in bytes
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<Vec<Candle>> for CompressedCandles {
+    fn from(candles: Vec<Candle>) -> Self {
+        // Serializing plain structs of numbers to JSON cannot fail
+        let json = serde_json::to_vec(&candles).expect("candles serialize to JSON");
+        Self(lz4_flex::compress_prepend_size(&json))
+    }
+}
+
+impl TryFrom<CompressedCandles> for Vec<Candle> {
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    fn try_from(compressed: CompressedCandles) -> Result<Self, Self::Error> {
+        let json = lz4_flex::decompress_size_prepended(&compressed.0).map_err(|e| {
+            Box::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Failed to decompress candles: {}", e),
+            )) as Box<dyn std::error::Error + Send + Sync>
+        })?;
+        serde_json::from_slice(&json).map_err(|e| {
+            Box::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Failed to deserialize candles: {}", e),
+            )) as Box<dyn std::error::Error + Send + Sync>
+        })
+    }
+}
+
+impl Serialize for CompressedCandles {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&BASE64.encode(&self.0))
+    }
+}
+
+impl<'de> Deserialize<'de> for CompressedCandles {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let encoded = String::deserialize(deserializer)?;
+        BASE64
+            .decode(encoded)
+            .map(Self)
+            .map_err(serde::de::Error::custom)
+    }
 }
 
 /// Job to store a signal in the database
@@ -24,7 +228,3 @@ pub struct StoreSignalJob {
     pub signal: SignalOutput,
     pub strategy_id: i64,
 }
-
-
-
-
@@ -1,15 +1,19 @@
+pub mod backtesting;
 pub mod cache;
 pub mod common;
 pub mod config;
 pub mod core;
 pub mod db;
 pub mod engine;
+pub mod error;
 pub mod evaluation;
 pub mod indicators;
 pub mod jobs;
 pub mod logging;
 pub mod metrics;
 pub mod models;
+pub mod output;
+pub mod positions;
 pub mod services;
 pub mod signals;
 pub mod strategies;
@@ -2,11 +2,35 @@
 //!
 //! Provides metrics for HTTP requests, signal evaluations, and system health.
 
+use crate::models::signal::SignalDirection;
+use crate::strategies::evaluator::IndicatorComputation;
 use prometheus::{
-    register_counter_with_registry, register_gauge_with_registry, register_histogram_with_registry,
-    Counter, Gauge, Histogram, Registry, TextEncoder,
+    register_counter_vec_with_registry, register_counter_with_registry,
+    register_gauge_vec_with_registry, register_gauge_with_registry,
+    register_histogram_with_registry, Counter, CounterVec, Gauge, GaugeVec, Histogram, Opts,
+    Registry, TextEncoder,
 };
-use std::sync::Arc;
+use std::sync::{Arc, LazyLock};
+
+/// Bucket upper bounds for `signal_confidence_histogram`
+pub const SIGNAL_CONFIDENCE_BUCKETS: [f64; 10] = [0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9, 1.0];
+/// Confidence at or above which a stored signal counts toward `signals_above_threshold_total`
+pub const SIGNAL_CONFIDENCE_THRESHOLD: f64 = 0.7;
+
+/// Number of times a math helper replaced a NaN/infinite result, labeled by function.
+///
+/// Global because the math helpers have no access to a `Metrics` instance; every
+/// `Metrics` registry exports it.
+pub static MATH_NAN_GUARD_TOTAL: LazyLock<CounterVec> = LazyLock::new(|| {
+    CounterVec::new(
+        Opts::new(
+            "math_nan_guard_total",
+            "Total number of times a math helper guarded against NaN or infinite values",
+        ),
+        &["function"],
+    )
+    .expect("valid math_nan_guard_total metric")
+});
 
 /// Metrics container for all application metrics
 #[derive(Clone)]
@@ -17,17 +41,66 @@ pub struct Metrics {
     pub http_requests_total: Counter,
     pub http_request_duration_seconds: Histogram,
     pub http_requests_in_flight: Gauge,
+    pub auth_failures_total: Counter,
+    pub api_rate_limit_hits_total: CounterVec,
 
     // Signal evaluation metrics
     pub signal_evaluations_total: Counter,
     pub signal_evaluation_duration_seconds: Histogram,
     pub signal_evaluations_active: Gauge,
     pub signal_evaluation_errors_total: Counter,
+    pub strategy_evaluation_timeouts_total: Counter,
+    pub indicator_computations_total: CounterVec,
+    pub indicator_computation_failures_total: CounterVec,
+    pub indicator_computations_none_ratio: Gauge,
+
+    // Stored signal metrics
+    pub signal_confidence_histogram: Histogram,
+    pub signals_by_direction_total: CounterVec,
+    pub signals_above_threshold_total: Counter,
+    pub signals_suppressed_duplicate_direction_total: Counter,
+
+    // Webhook metrics
+    pub webhook_deliveries_total: Counter,
+    pub webhook_failures_total: Counter,
+    pub webhook_delivery_latency_seconds: Histogram,
+
+    // Scheduler metrics
+    pub scheduler_missed_ticks_recovered_total: Counter,
+    pub jobs_deduplicated_total: Counter,
+    pub jobs_by_priority_total: CounterVec,
+    pub job_retries_total: CounterVec,
+    pub job_max_retries_exceeded_total: CounterVec,
+    pub strategies_enabled_total: Gauge,
+    pub strategies_disabled_total: Gauge,
 
     // System health metrics
     pub database_connected: Gauge,
     pub cache_connected: Gauge,
     pub websocket_connected: Gauge,
+
+    // Database pool metrics
+    pub database_pool_active_connections: Gauge,
+    pub database_pool_idle_connections: Gauge,
+
+    // WebSocket reconnection metrics
+    pub websocket_reconnect_attempts_total: Gauge,
+    pub websocket_last_reconnect_at_timestamp: Gauge,
+
+    // Cache metrics
+    pub cache_hits_total: Counter,
+    pub cache_misses_total: Counter,
+    pub strategy_cache_hits_total: Counter,
+    pub strategy_cache_misses_total: Counter,
+    pub strategy_refresh_errors_total: Counter,
+
+    // Market data provider metrics
+    pub provider_candle_buffer_size: GaugeVec,
+    pub rest_candle_fetches_total: Counter,
+    pub rest_candle_fetch_errors_total: Counter,
+    pub candles_synthetic_total: Counter,
+    pub candle_last_received_timestamp_seconds: GaugeVec,
+    pub candles_stale_total: Counter,
 }
 
 impl Metrics {
@@ -54,6 +127,19 @@ impl Metrics {
             &registry
         )?;
 
+        let auth_failures_total = register_counter_with_registry!(
+            "auth_failures_total",
+            "Total number of API requests rejected for a missing or invalid API key",
+            &registry
+        )?;
+
+        let api_rate_limit_hits_total = register_counter_vec_with_registry!(
+            "api_rate_limit_hits_total",
+            "Total number of API requests rejected by the per-client rate limit, labeled by path",
+            &["path"],
+            &registry
+        )?;
+
         // Signal evaluation metrics
         let signal_evaluations_total = register_counter_with_registry!(
             "signal_evaluations_total",
@@ -79,6 +165,124 @@ impl Metrics {
             &registry
         )?;
 
+        let strategy_evaluation_timeouts_total = register_counter_with_registry!(
+            "strategy_evaluation_timeouts_total",
+            "Total number of strategy evaluations abandoned after timing out",
+            &registry
+        )?;
+
+        let indicator_computations_total = register_counter_vec_with_registry!(
+            "indicator_computations_total",
+            "Total number of warm-up indicator computations during strategy evaluation, labeled by indicator",
+            &["indicator_name"],
+            &registry
+        )?;
+
+        let indicator_computation_failures_total = register_counter_vec_with_registry!(
+            "indicator_computation_failures_total",
+            "Total number of indicator computations with no reading despite enough candles, labeled by indicator",
+            &["indicator_name"],
+            &registry
+        )?;
+
+        let indicator_computations_none_ratio = register_gauge_with_registry!(
+            "indicator_computations_none_ratio",
+            "Share of warm-up indicators without a reading in the latest strategy evaluation",
+            &registry
+        )?;
+
+        // Stored signal metrics
+        let signal_confidence_histogram = register_histogram_with_registry!(
+            "signal_confidence_histogram",
+            "Confidence of stored signals",
+            SIGNAL_CONFIDENCE_BUCKETS.to_vec(),
+            &registry
+        )?;
+
+        let signals_by_direction_total = register_counter_vec_with_registry!(
+            "signals_by_direction_total",
+            "Total number of stored signals, labeled by direction and symbol",
+            &["direction", "symbol"],
+            &registry
+        )?;
+
+        let signals_above_threshold_total = register_counter_with_registry!(
+            "signals_above_threshold_total",
+            "Total number of stored signals with confidence of at least 0.7",
+            &registry
+        )?;
+
+        let signals_suppressed_duplicate_direction_total = register_counter_with_registry!(
+            "signals_suppressed_duplicate_direction_total",
+            "Total number of signals skipped because a position in the same direction was already open for the symbol",
+            &registry
+        )?;
+
+        // Webhook metrics
+        let webhook_deliveries_total = register_counter_with_registry!(
+            "webhook_deliveries_total",
+            "Total number of signals delivered to WEBHOOK_URL",
+            &registry
+        )?;
+
+        let webhook_failures_total = register_counter_with_registry!(
+            "webhook_failures_total",
+            "Total number of signals that could not be delivered to WEBHOOK_URL after all retries",
+            &registry
+        )?;
+
+        let webhook_delivery_latency_seconds = register_histogram_with_registry!(
+            "webhook_delivery_latency_seconds",
+            "Time to deliver a signal to WEBHOOK_URL in seconds, including retries",
+            &registry
+        )?;
+
+        // Scheduler metrics
+        let scheduler_missed_ticks_recovered_total = register_counter_with_registry!(
+            "scheduler_missed_ticks_recovered_total",
+            "Total number of scheduler ticks missed while the worker was down",
+            &registry
+        )?;
+
+        let jobs_deduplicated_total = register_counter_with_registry!(
+            "jobs_deduplicated_total",
+            "Total number of FetchCandlesJob pushes skipped because one was already queued for the interval",
+            &registry
+        )?;
+
+        let jobs_by_priority_total = register_counter_vec_with_registry!(
+            "jobs_by_priority_total",
+            "Total number of FetchCandlesJob enqueued by the scheduler, labeled by priority",
+            &["priority"],
+            &registry
+        )?;
+
+        let job_retries_total = register_counter_vec_with_registry!(
+            "job_retries_total",
+            "Total number of failed jobs scheduled for a retry, labeled by job type and retry attempt (1 for the first retry)",
+            &["job_type", "attempt_number"],
+            &registry
+        )?;
+
+        let job_max_retries_exceeded_total = register_counter_vec_with_registry!(
+            "job_max_retries_exceeded_total",
+            "Total number of jobs that failed with no retries left, labeled by job type",
+            &["job_type"],
+            &registry
+        )?;
+
+        let strategies_enabled_total = register_gauge_with_registry!(
+            "strategies_enabled_total",
+            "Number of enabled strategies, refreshed on each scheduler tick",
+            &registry
+        )?;
+
+        let strategies_disabled_total = register_gauge_with_registry!(
+            "strategies_disabled_total",
+            "Number of disabled strategies, refreshed on each scheduler tick",
+            &registry
+        )?;
+
         // System health metrics
         let database_connected = register_gauge_with_registry!(
             "database_connected",
@@ -98,21 +302,190 @@ impl Metrics {
             &registry
         )?;
 
+        // Database pool metrics
+        let database_pool_active_connections = register_gauge_with_registry!(
+            "database_pool_active_connections",
+            "Number of pooled QuestDB connections with a query in flight",
+            &registry
+        )?;
+
+        let database_pool_idle_connections = register_gauge_with_registry!(
+            "database_pool_idle_connections",
+            "Number of connected pooled QuestDB connections with no query in flight",
+            &registry
+        )?;
+
+        // WebSocket reconnection metrics
+        let websocket_reconnect_attempts_total = register_gauge_with_registry!(
+            "websocket_reconnect_attempts_total",
+            "Total number of WebSocket reconnection attempts",
+            &registry
+        )?;
+
+        let websocket_last_reconnect_at_timestamp = register_gauge_with_registry!(
+            "websocket_last_reconnect_at_timestamp",
+            "Unix timestamp of the last successful WebSocket reconnection",
+            &registry
+        )?;
+
+        // Cache metrics
+        let cache_hits_total = register_counter_with_registry!(
+            "cache_hits_total",
+            "Total number of cache lookups served from Redis",
+            &registry
+        )?;
+
+        let cache_misses_total = register_counter_with_registry!(
+            "cache_misses_total",
+            "Total number of cache lookups that fell back to the database",
+            &registry
+        )?;
+
+        let strategy_cache_hits_total = register_counter_with_registry!(
+            "strategy_cache_hits_total",
+            "Total number of worker strategy lookups served from Redis",
+            &registry
+        )?;
+
+        let strategy_cache_misses_total = register_counter_with_registry!(
+            "strategy_cache_misses_total",
+            "Total number of worker strategy lookups that fell back to QuestDB",
+            &registry
+        )?;
+
+        let strategy_refresh_errors_total = register_counter_with_registry!(
+            "strategy_refresh_errors_total",
+            "Total number of scheduler polls that failed to load strategies",
+            &registry
+        )?;
+
+        // Market data provider metrics
+        let provider_candle_buffer_size = register_gauge_vec_with_registry!(
+            "provider_candle_buffer_size",
+            "Number of candles held in memory, labeled by symbol and interval",
+            &["symbol_interval"],
+            &registry
+        )?;
+
+        let rest_candle_fetches_total = register_counter_with_registry!(
+            "rest_candle_fetches_total",
+            "Total number of candleSnapshot REST requests, including retries",
+            &registry
+        )?;
+
+        let rest_candle_fetch_errors_total = register_counter_with_registry!(
+            "rest_candle_fetch_errors_total",
+            "Total number of failed candleSnapshot REST requests",
+            &registry
+        )?;
+
+        let candles_synthetic_total = register_counter_with_registry!(
+            "candles_synthetic_total",
+            "Total number of candles interpolated over gaps in the in-memory candle buffer",
+            &registry
+        )?;
+
+        let candle_last_received_timestamp_seconds = register_gauge_vec_with_registry!(
+            "candle_last_received_timestamp_seconds",
+            "Unix time of the last live candle received, labeled by symbol and interval",
+            &["symbol", "interval"],
+            &registry
+        )?;
+
+        let candles_stale_total = register_counter_with_registry!(
+            "candles_stale_total",
+            "Total number of times a symbol's live candle feed was found stale and re-subscribed",
+            &registry
+        )?;
+
+        registry.register(Box::new(MATH_NAN_GUARD_TOTAL.clone()))?;
+
         Ok(Self {
             registry: Arc::new(registry),
             http_requests_total,
             http_request_duration_seconds,
             http_requests_in_flight,
+            auth_failures_total,
+            api_rate_limit_hits_total,
             signal_evaluations_total,
             signal_evaluation_duration_seconds,
             signal_evaluations_active,
             signal_evaluation_errors_total,
+            strategy_evaluation_timeouts_total,
+            indicator_computations_total,
+            indicator_computation_failures_total,
+            indicator_computations_none_ratio,
+            signal_confidence_histogram,
+            signals_by_direction_total,
+            signals_above_threshold_total,
+            signals_suppressed_duplicate_direction_total,
+            webhook_deliveries_total,
+            webhook_failures_total,
+            webhook_delivery_latency_seconds,
+            scheduler_missed_ticks_recovered_total,
+            jobs_deduplicated_total,
+            jobs_by_priority_total,
+            job_retries_total,
+            job_max_retries_exceeded_total,
+            strategies_enabled_total,
+            strategies_disabled_total,
             database_connected,
             cache_connected,
             websocket_connected,
+            database_pool_active_connections,
+            database_pool_idle_connections,
+            websocket_reconnect_attempts_total,
+            websocket_last_reconnect_at_timestamp,
+            cache_hits_total,
+            cache_misses_total,
+            strategy_cache_hits_total,
+            strategy_cache_misses_total,
+            strategy_refresh_errors_total,
+            provider_candle_buffer_size,
+            rest_candle_fetches_total,
+            rest_candle_fetch_errors_total,
+            candles_synthetic_total,
+            candle_last_received_timestamp_seconds,
+            candles_stale_total,
         })
     }
 
+    /// Record the direction and confidence of a stored signal
+    pub fn record_signal(&self, symbol: &str, direction: SignalDirection, confidence: f64) {
+        self.signal_confidence_histogram.observe(confidence);
+        self.signals_by_direction_total
+            .with_label_values(&[direction.as_str(), symbol])
+            .inc();
+        if confidence >= SIGNAL_CONFIDENCE_THRESHOLD {
+            self.signals_above_threshold_total.inc();
+        }
+    }
+
+    /// Count one evaluation's indicator computations and the failures among
+    /// them, and set `indicator_computations_none_ratio` to the share that
+    /// had no reading
+    pub fn record_indicator_computations(&self, computations: &[IndicatorComputation]) {
+        if computations.is_empty() {
+            return;
+        }
+        let mut missing = 0;
+        for computation in computations {
+            self.indicator_computations_total
+                .with_label_values(&[computation.indicator])
+                .inc();
+            if !computation.computed {
+                missing += 1;
+            }
+            if computation.is_failure() {
+                self.indicator_computation_failures_total
+                    .with_label_values(&[computation.indicator])
+                    .inc();
+            }
+        }
+        self.indicator_computations_none_ratio
+            .set(missing as f64 / computations.len() as f64);
+    }
+
     /// Export metrics in Prometheus text format
     pub fn export(&self) -> Result<String, prometheus::Error> {
         // Use the registry directly to gather metrics
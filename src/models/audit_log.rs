@@ -0,0 +1,48 @@
+//! Records of changes made to strategies through the API.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A strategy change as stored in the `audit_logs` table
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AuditLogEntry {
+    /// When the change was made
+    pub timestamp: DateTime<Utc>,
+    /// What was done: "create", "update", "delete", "enable" or "disable"
+    pub operation: String,
+    /// Strategy that was changed
+    pub strategy_id: i64,
+    /// Strategy name at the time of the change
+    pub strategy_name: String,
+    /// Name (or masked prefix) of the API key that made the change; `None`
+    /// when API key authentication is disabled
+    pub operator_key: Option<String>,
+    /// JSON body of the request that made the change, if it had one
+    pub request_json: Option<String>,
+}
+
+impl AuditLogEntry {
+    pub fn new(operation: &str, strategy_id: i64, strategy_name: &str) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            operation: operation.to_string(),
+            strategy_id,
+            strategy_name: strategy_name.to_string(),
+            operator_key: None,
+            request_json: None,
+        }
+    }
+
+    pub fn with_operator_key(mut self, operator_key: Option<String>) -> Self {
+        self.operator_key = operator_key;
+        self
+    }
+
+    /// Keep the request that made the change. A request that cannot be
+    /// serialized is recorded without a body.
+    pub fn with_request<T: Serialize>(mut self, request: &T) -> Self {
+        self.request_json = serde_json::to_string(request).ok();
+        self
+    }
+}
@@ -0,0 +1,78 @@
+//! Records of jobs that failed, kept for auditing and replay.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A failed job as stored in the `dead_letters` table
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DeadLetter {
+    /// Dead-letter ID (None until stored)
+    pub id: Option<i64>,
+    /// When the failure was recorded
+    pub timestamp: DateTime<Utc>,
+    /// Job that failed: "fetch_candles", "evaluate_signal" or "store_signal"
+    pub job_type: String,
+    /// Symbol the job was processing
+    pub symbol: String,
+    /// Strategy the job was processing, if any
+    pub strategy_id: Option<i64>,
+    /// Error returned by the job
+    pub error_message: String,
+    /// Attempts made before this failure (0 on the first attempt)
+    pub retry_count: i32,
+    /// JSON of the failed job, used to replay it. Not returned by the API
+    /// since evaluation jobs carry full candle series.
+    #[serde(default, skip_serializing)]
+    #[schema(ignore)]
+    pub payload: Option<String>,
+    /// When the job was last pushed back onto its queue
+    pub replayed_at: Option<DateTime<Utc>>,
+}
+
+impl DeadLetter {
+    pub fn new(job_type: &str, symbol: &str, error_message: impl Into<String>) -> Self {
+        Self {
+            id: None,
+            timestamp: Utc::now(),
+            job_type: job_type.to_string(),
+            symbol: symbol.to_string(),
+            strategy_id: None,
+            error_message: error_message.into(),
+            retry_count: 0,
+            payload: None,
+            replayed_at: None,
+        }
+    }
+
+    pub fn with_strategy_id(mut self, strategy_id: i64) -> Self {
+        self.strategy_id = Some(strategy_id);
+        self
+    }
+
+    pub fn with_retry_count(mut self, retry_count: usize) -> Self {
+        self.retry_count = i32::try_from(retry_count).unwrap_or(i32::MAX);
+        self
+    }
+
+    /// Keep the failed job so it can be replayed later. A job that cannot be
+    /// serialized is recorded without a payload.
+    pub fn with_payload<T: Serialize>(mut self, job: &T) -> Self {
+        self.payload = serde_json::to_string(job).ok();
+        self
+    }
+
+    pub fn is_replayed(&self) -> bool {
+        self.replayed_at.is_some()
+    }
+}
+
+/// Dead-letter counts for one job type and replay state
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DeadLetterStats {
+    /// Job that failed: "fetch_candles", "evaluate_signal" or "store_signal"
+    pub job_type: String,
+    /// Whether these failures have been replayed
+    pub is_replayed: bool,
+    pub count: i64,
+}
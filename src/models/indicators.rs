@@ -1,7 +1,7 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Candle {
     pub open: f64,
     pub high: f64,
@@ -13,6 +13,10 @@ pub struct Candle {
     pub open_interest: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub funding_rate: Option<f64>,
+    /// Interpolated over a feed gap rather than received from the exchange;
+    /// never stored in QuestDB
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_synthetic: bool,
 }
 
 impl Candle {
@@ -33,6 +37,7 @@ impl Candle {
             timestamp,
             open_interest: None,
             funding_rate: None,
+            is_synthetic: false,
         }
     }
 
@@ -47,6 +52,22 @@ impl Candle {
     }
 }
 
+/// Aggressor side of a trade: `Buy` when the buyer took liquidity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TradeSide {
+    Buy,
+    Sell,
+}
+
+/// A single fill from the exchange's public trade feed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Trade {
+    pub price: f64,
+    pub size: f64,
+    pub side: TradeSide,
+    pub timestamp: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MacdIndicator {
     pub macd: f64,
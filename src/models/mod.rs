@@ -1,14 +1,18 @@
 //! Shared data models spanning the engine layers.
 
+pub mod audit_log;
+pub mod dead_letter;
 pub mod indicators;
 pub mod signal;
 pub mod strategy;
 
+pub use audit_log::AuditLogEntry;
+pub use dead_letter::DeadLetter;
 pub use indicators::{
     EmaIndicator, IndicatorSet, MacdIndicator, RsiIndicator, SmaIndicator, VolumeIndicator,
 };
 pub use signal::{SignalDirection, SignalEvaluation, SignalOutput, SignalReason};
 pub use strategy::{
-    AggregationConfig, AggregationMethod, Condition, Comparison, IndicatorType, LogicalOperator,
-    Rule, RuleResult, RuleType, SignalThresholds, Strategy, StrategyConfig,
+    AggregationConfig, AggregationMethod, Condition, Comparison, EvaluationTrace, IndicatorType,
+    LogicalOperator, Rule, RuleResult, RuleType, SignalThresholds, Strategy, StrategyConfig,
 };
@@ -1,30 +1,62 @@
 use crate::models::indicators::IndicatorSet;
+use crate::models::strategy::DecayConfig;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
 pub enum SignalDirection {
     Long,
     Short,
     Neutral,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl SignalDirection {
+    /// Name stored in the `direction` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SignalDirection::Long => "Long",
+            SignalDirection::Short => "Short",
+            SignalDirection::Neutral => "Neutral",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct SignalReason {
     pub description: String,
     pub weight: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Filters for querying stored signals; `None` fields match everything
+#[derive(Debug, Clone, Default)]
+pub struct SignalFilter {
+    pub symbol: Option<String>,
+    pub direction: Option<SignalDirection>,
+    /// Inclusive lower bound on the signal timestamp
+    pub from: Option<DateTime<Utc>>,
+    /// Exclusive upper bound on the signal timestamp
+    pub to: Option<DateTime<Utc>>,
+    pub min_confidence: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct SignalOutput {
     pub direction: SignalDirection,
     pub confidence: f64,
     pub recommended_sl_pct: f64,
     pub recommended_tp_pct: f64,
+    /// Percentage of equity to commit, from the strategy's position size
+    /// model. `None` for neutral signals, and for Kelly sizing without both
+    /// a stop loss and a take profit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recommended_position_size_pct: Option<f64>,
     pub reasons: Vec<SignalReason>,
     pub symbol: String,
     pub price: f64,
     pub timestamp: DateTime<Utc>,
+    /// Confidence decay from the generating strategy, if it has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decay: Option<DecayConfig>,
 }
 
 impl SignalOutput {
@@ -42,12 +74,97 @@ impl SignalOutput {
             confidence,
             recommended_sl_pct,
             recommended_tp_pct,
+            recommended_position_size_pct: None,
             reasons,
             symbol,
             price,
             timestamp: Utc::now(),
+            decay: None,
         }
     }
+
+    pub fn with_decay(mut self, decay: Option<DecayConfig>) -> Self {
+        self.decay = decay;
+        self
+    }
+
+    /// Create a neutral signal with zero confidence, zero SL/TP and no reasons
+    pub fn new_neutral(symbol: String, price: f64) -> Self {
+        Self::new(SignalDirection::Neutral, 0.0, 0.0, 0.0, vec![], symbol, price)
+    }
+
+    /// Confidence decayed by the signal's age at `now`: `confidence * exp(-λt)`
+    /// with `λ = ln(2) / half_life_seconds`.
+    ///
+    /// Signals without a decay model (or with a zero half-life) keep their
+    /// confidence; signals timestamped after `now` are not decayed.
+    pub fn effective_confidence(&self, now: DateTime<Utc>) -> f64 {
+        let half_life = match self.decay {
+            Some(decay) if decay.half_life_seconds > 0 => decay.half_life_seconds as f64,
+            _ => return self.confidence,
+        };
+        let age_seconds = (now - self.timestamp).num_milliseconds().max(0) as f64 / 1000.0;
+        let lambda = std::f64::consts::LN_2 / half_life;
+        self.confidence * (-lambda * age_seconds).exp()
+    }
+
+    /// Whether the decayed confidence at `now` is below the decay model's
+    /// `min_display_threshold`. Signals without a decay model never expire.
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        match self.decay {
+            Some(decay) => self.effective_confidence(now) < decay.min_display_threshold,
+            None => false,
+        }
+    }
+
+    /// Whether the signal has a direction worth acting on
+    pub fn is_actionable(&self) -> bool {
+        !matches!(self.direction, SignalDirection::Neutral) && self.confidence > 0.0
+    }
+}
+
+/// Confidence quantiles over a group of signals
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ConfidencePercentiles {
+    pub p25: f64,
+    pub p50: f64,
+    pub p75: f64,
+    pub p90: f64,
+}
+
+/// Aggregate statistics for the signals of one symbol and strategy
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct SignalGroupStats {
+    pub symbol: String,
+    pub strategy_id: i64,
+    /// Signals in the requested range
+    pub count: i64,
+    /// Percentage of signals that were Long
+    pub long_pct: f64,
+    /// Percentage of signals that were Short
+    pub short_pct: f64,
+    /// Percentage of signals that were Neutral
+    pub neutral_pct: f64,
+    pub mean_confidence: f64,
+    /// Approximate confidence percentiles
+    pub confidence_percentiles: ConfidencePercentiles,
+    pub avg_sl_pct: f64,
+    pub avg_tp_pct: f64,
+    /// Signals in the last hour of the range
+    pub last_1h: i64,
+    /// Signals in the last 24 hours of the range
+    pub last_24h: i64,
+    /// Signals in the last 7 days of the range
+    pub last_7d: i64,
+}
+
+/// Signal statistics per symbol and strategy over `[from, to)`
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct SignalStats {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    /// One entry per symbol and strategy with signals in the range
+    pub groups: Vec<SignalGroupStats>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
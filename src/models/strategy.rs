@@ -1,5 +1,7 @@
 //! Strategy builder system data models
 
+use crate::indicators::volatility::atr::VolatilityRegime;
+use crate::signals::decision::StopLossTakeProfit;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -13,10 +15,59 @@ pub struct Strategy {
     pub name: String,
     pub symbol: String,
     pub config: StrategyConfig,
+    /// Disabled strategies are kept but not evaluated
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// File in `STRATEGY_DIR` the strategy is managed from; `None` for
+    /// strategies created through the API
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_file: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+fn default_enabled() -> bool {
+    true
+}
+
+impl Default for Strategy {
+    /// Starter strategy on BTC using [`StrategyConfig::default`]
+    fn default() -> Self {
+        let now = Utc::now();
+        Self {
+            id: None,
+            name: "Default RSI+MACD Long".to_string(),
+            symbol: "BTC".to_string(),
+            config: StrategyConfig::default(),
+            enabled: true,
+            source_file: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
+
+/// Aggregated signal statistics for a single strategy
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StrategyStats {
+    /// Strategy ID
+    pub strategy_id: i64,
+    /// Strategy name (None if the strategy has been deleted)
+    pub strategy_name: Option<String>,
+    /// Trading symbol (None if the strategy has been deleted)
+    pub symbol: Option<String>,
+    /// Total signals generated
+    pub total_signals: i64,
+    /// Number of long signals
+    pub long_count: i64,
+    /// Number of short signals
+    pub short_count: i64,
+    /// Number of neutral signals
+    pub neutral_count: i64,
+    /// Average signal confidence
+    pub avg_confidence: f64,
+}
+
 /// Main strategy configuration
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct StrategyConfig {
@@ -24,6 +75,226 @@ pub struct StrategyConfig {
     pub rules: Vec<Rule>,
     /// Aggregation configuration
     pub aggregation: AggregationConfig,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Optional manual category weights (e.g. {"momentum": 0.6, "trend": 0.4}).
+    /// Each rule's score is scaled by its category's weight over that
+    /// category's share of the conditions. When omitted, rules count equally.
+    pub category_weights: Option<HashMap<String, f64>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Optional per-timeframe score multipliers keyed by interval (e.g. {"15m": 1.0, "1h": 2.0}).
+    /// Used by multi-timeframe evaluation; unlisted intervals get a weight of 1.0.
+    pub timeframe_weights: Option<HashMap<String, f64>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Optional confidence decay for this strategy's signals.
+    /// When omitted, signals keep their confidence indefinitely.
+    pub decay_model: Option<DecayConfig>,
+    #[serde(default)]
+    /// How signals recommend a position size (default `FullKelly`)
+    pub position_size_model: PositionSizeModel,
+    #[serde(default = "default_max_kelly_fraction")]
+    /// Largest fraction of equity a Kelly model recommends (default 0.25)
+    pub max_kelly_fraction: f64,
+    #[serde(default)]
+    /// How signals place their stop loss and take profit (default ATR x1.2
+    /// and x2.0)
+    pub sl_tp_model: SlTpModel,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Optional UTC hour windows that scale the aggregated score before it is
+    /// compared with the thresholds. Hours outside every window are unchanged.
+    pub time_filter: Option<Vec<TimeWindow>>,
+    #[serde(default)]
+    /// Compute indicators on Heikin-Ashi candles instead of the raw ones
+    /// (default false). Stop loss and take profit still use the real price.
+    pub use_heikin_ashi: bool,
+}
+
+/// Score multiplier for a range of UTC hours
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TimeWindow {
+    /// First hour in the window (0-23)
+    pub start_utc_hour: u8,
+    /// Hour the window ends before (0-23); below `start_utc_hour` when the
+    /// window wraps past midnight
+    pub end_utc_hour: u8,
+    /// Applied to the score during the window: 1.0 leaves it unchanged and
+    /// 0.0 suppresses the strategy
+    pub weight_multiplier: f64,
+}
+
+impl TimeWindow {
+    /// Whether `hour` falls in `[start_utc_hour, end_utc_hour)`
+    pub fn contains(&self, hour: u32) -> bool {
+        let (start, end) = (u32::from(self.start_utc_hour), u32::from(self.end_utc_hour));
+        if start <= end {
+            (start..end).contains(&hour)
+        } else {
+            hour >= start || hour < end
+        }
+    }
+}
+
+/// Default effective confidence below which a decayed signal is expired
+pub const DEFAULT_MIN_DISPLAY_THRESHOLD: f64 = 0.1;
+
+/// Exponential confidence decay for stored signals
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct DecayConfig {
+    /// Seconds for a signal's confidence to halve
+    pub half_life_seconds: u64,
+    /// Effective confidence below which a signal is reported as expired (0.0-1.0)
+    #[serde(default = "default_min_display_threshold")]
+    pub min_display_threshold: f64,
+}
+
+fn default_min_display_threshold() -> f64 {
+    DEFAULT_MIN_DISPLAY_THRESHOLD
+}
+
+/// Default cap on the Kelly fraction of equity a signal recommends
+pub const DEFAULT_MAX_KELLY_FRACTION: f64 = 0.25;
+
+fn default_max_kelly_fraction() -> f64 {
+    DEFAULT_MAX_KELLY_FRACTION
+}
+
+/// Position sizing for a strategy's signals.
+///
+/// The Kelly variants scale the Kelly criterion
+/// `f* = (p × b - (1 - p)) / b`, with `p` the signal confidence and `b` the
+/// take profit to stop loss ratio. Serialized as `"HalfKelly"` or
+/// `{"Fixed": 0.02}`.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize, ToSchema)]
+pub enum PositionSizeModel {
+    #[default]
+    FullKelly,
+    HalfKelly,
+    QuarterKelly,
+    /// The same fraction of equity (0.0-1.0) for every signal
+    Fixed(f64),
+}
+
+impl PositionSizeModel {
+    /// Recommended fraction of equity (0.0-1.0) for a signal.
+    ///
+    /// Kelly fractions are clamped to `[0.0, max_kelly_fraction]`, so a
+    /// negative edge recommends no position. Kelly models return `None`
+    /// unless both `sl_pct` and `tp_pct` are positive, since the payoff
+    /// ratio is undefined without them.
+    pub fn fraction(
+        &self,
+        confidence: f64,
+        sl_pct: f64,
+        tp_pct: f64,
+        max_kelly_fraction: f64,
+    ) -> Option<f64> {
+        let multiplier = match *self {
+            PositionSizeModel::FullKelly => 1.0,
+            PositionSizeModel::HalfKelly => 0.5,
+            PositionSizeModel::QuarterKelly => 0.25,
+            PositionSizeModel::Fixed(fraction) => return Some(fraction),
+        };
+        if !(sl_pct > 0.0 && tp_pct > 0.0) {
+            return None;
+        }
+        let payoff = tp_pct / sl_pct;
+        let kelly = (confidence * payoff - (1.0 - confidence)) / payoff;
+        Some((kelly * multiplier).clamp(0.0, max_kelly_fraction.max(0.0)))
+    }
+}
+
+/// Stop loss and take profit placement for a strategy's signals.
+///
+/// Serialized as e.g. `{"FixedPct": {"sl_pct": 1.0, "tp_pct": 2.0}}`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub enum SlTpModel {
+    /// SL and TP at multiples of ATR(14) from the current price
+    AtrBased { sl_multiplier: f64, tp_multiplier: f64 },
+    /// The same SL and TP percentages for every signal
+    FixedPct { sl_pct: f64, tp_pct: f64 },
+    /// A fixed SL percentage with TP at `rr_ratio` times the SL
+    RiskReward { sl_pct: f64, rr_ratio: f64 },
+    /// TP at the middle Bollinger band. With `sl_at_band` the SL is at the
+    /// lower band for a Long and the upper band for a Short, otherwise at
+    /// the default 1.2 × ATR.
+    BollingerBased { sl_at_band: bool },
+}
+
+impl Default for SlTpModel {
+    fn default() -> Self {
+        SlTpModel::AtrBased {
+            sl_multiplier: StopLossTakeProfit::SL_ATR_MULTIPLIER,
+            tp_multiplier: StopLossTakeProfit::TP_ATR_MULTIPLIER,
+        }
+    }
+}
+
+impl StrategyConfig {
+    /// Score multiplier for `hour` (UTC): the product of every time filter
+    /// window containing it, or 1.0 when none does
+    pub fn time_multiplier(&self, hour: u32) -> f64 {
+        self.time_filter
+            .iter()
+            .flatten()
+            .filter(|window| window.contains(hour))
+            .map(|window| window.weight_multiplier)
+            .product()
+    }
+}
+
+impl Default for StrategyConfig {
+    /// Starter config: RSI oversold AND MACD bullish momentum, summed.
+    ///
+    /// Both passing scores +2 (long), both failing scores -2 (short).
+    fn default() -> Self {
+        let signal_state_rule = |id: &str, indicator: IndicatorType, state: &str| Rule {
+            id: id.to_string(),
+            rule_type: RuleType::Condition,
+            weight: Some(1.0),
+            operator: None,
+            condition: Some(Condition {
+                indicator,
+                indicator_params: HashMap::new(),
+                comparison: Comparison::SignalState,
+                threshold: None,
+                signal_state: Some(state.to_string()),
+            }),
+            children: None,
+        };
+
+        Self {
+            rules: vec![Rule {
+                id: "rsi_macd_long".to_string(),
+                rule_type: RuleType::Group,
+                weight: Some(1.0),
+                operator: Some(LogicalOperator::AND),
+                condition: None,
+                children: Some(vec![
+                    signal_state_rule("rsi_oversold", IndicatorType::RSI, "Oversold"),
+                    signal_state_rule(
+                        "macd_bullish_momentum",
+                        IndicatorType::MACD,
+                        "BullishMomentum",
+                    ),
+                ]),
+            }],
+            aggregation: AggregationConfig {
+                method: AggregationMethod::Sum,
+                thresholds: SignalThresholds {
+                    long_min: 2,
+                    short_max: -2,
+                },
+                filter: None,
+            },
+            category_weights: None,
+            timeframe_weights: None,
+            decay_model: None,
+            position_size_model: PositionSizeModel::default(),
+            max_kelly_fraction: DEFAULT_MAX_KELLY_FRACTION,
+            sl_tp_model: SlTpModel::default(),
+            time_filter: None,
+            use_heikin_ashi: false,
+        }
+    }
 }
 
 /// Individual condition or group
@@ -76,20 +347,71 @@ pub struct Condition {
     pub signal_state: Option<String>,
 }
 
+impl Condition {
+    /// Numeric indicator parameter, if present
+    pub fn param_f64(&self, name: &str) -> Option<f64> {
+        self.indicator_params.get(name).and_then(Value::as_f64)
+    }
+}
+
 /// Available indicator types
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "PascalCase")]
 pub enum IndicatorType {
     MACD,
     RSI,
+    StochRsi,
+    WilliamsR,
+    CCI,
+    MFI,
+    CMF,
     EMA,
+    /// Simple moving average over the `period` param (default 20)
+    SMA,
+    /// 20-period simple moving average
+    SMA20,
+    /// 50-period simple moving average
+    SMA50,
+    /// 200-period simple moving average
+    SMA200,
+    EmaRibbon,
+    /// Kaufman adaptive moving average
+    KAMA,
     SuperTrend,
+    Ichimoku,
+    ParabolicSar,
+    /// Position within the Fibonacci retracement of the latest swing
+    Fibonacci,
     Bollinger,
+    /// Price position within the Bollinger Bands, 0 at the lower band and 1
+    /// at the upper
+    BollingerPctB,
+    /// Bollinger Band width as a percentage of the middle band
+    BollingerBandwidth,
+    Keltner,
+    Donchian,
     ATR,
+    /// ATR as a percentage of the close, comparable across symbols
+    AtrPct,
     OBV,
     VolumeProfile,
+    /// Volume profile Point of Control price
+    VolumeProfilePOC,
+    /// High of the volume profile value area (70% of volume around the POC)
+    VolumeProfileVAH,
+    /// Low of the volume profile value area
+    VolumeProfileVAL,
+    VWAP,
     FundingRate,
     OpenInterest,
+    /// Top-of-book bid-ask spread in basis points of the mid price
+    Spread,
+    /// Buy volume over sell volume across the most recent trades
+    BuySellRatio,
+    /// Strongest candlestick pattern completed on the latest candle
+    CandlestickPattern,
+    /// Current UTC hour (0-23), for rules limited to a trading session
+    TimeOfDay,
 }
 
 /// Comparison operations
@@ -121,6 +443,22 @@ pub struct AggregationConfig {
     pub method: AggregationMethod,
     /// Signal thresholds
     pub thresholds: SignalThresholds,
+    /// Optional market regime gate; while it is not met the score is zero
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<AggregationFilter>,
+}
+
+/// Market condition a strategy requires before any rule can score.
+///
+/// Serialized externally tagged, e.g. `{"MinAdx": {"threshold": 25.0}}`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub enum AggregationFilter {
+    /// Only score while ADX is at least `threshold`, i.e. the market is
+    /// trending. Suppresses signals while ADX is unknown.
+    MinAdx { threshold: f64 },
+    /// Only score while the ATR volatility regime is no more volatile than
+    /// `regime`. Does not suppress while the regime is unknown.
+    MaxVolatilityRegime { regime: VolatilityRegime },
 }
 
 /// Aggregation methods
@@ -144,12 +482,20 @@ pub struct SignalThresholds {
 }
 
 /// Result of evaluating a rule
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct RuleResult {
     pub rule_id: String,
     pub passed: bool,
     pub score: i32,
     pub weight: f64,
+    /// Numeric indicator value the condition was compared against; `None` for
+    /// groups, signal-state checks on indicators without one, or missing data
+    #[serde(default)]
+    pub indicator_value: Option<f64>,
+    /// What this rule added to the aggregated score under the strategy's
+    /// aggregation method
+    #[serde(default)]
+    pub contribution: i32,
 }
 
 impl RuleResult {
@@ -159,7 +505,85 @@ impl RuleResult {
             passed,
             score,
             weight,
+            indicator_value: None,
+            contribution: score,
         }
     }
+
+    pub fn with_indicator_value(mut self, indicator_value: Option<f64>) -> Self {
+        self.indicator_value = indicator_value;
+        self
+    }
+}
+
+/// Indicator readings behind a single evaluation; `None` when an indicator
+/// had too few candles or no data (e.g. open interest on a spot series)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct IndicatorSnapshot {
+    /// Close of the last candle
+    pub current_price: f64,
+    pub rsi: Option<f64>,
+    pub stoch_rsi_k: Option<f64>,
+    pub stoch_rsi_d: Option<f64>,
+    pub williams_r: Option<f64>,
+    pub cci: Option<f64>,
+    pub mfi: Option<f64>,
+    pub cmf: Option<f64>,
+    pub macd: Option<f64>,
+    pub macd_signal: Option<f64>,
+    pub macd_histogram: Option<f64>,
+    pub ema_fast: Option<f64>,
+    pub ema_slow: Option<f64>,
+    pub sma_20: Option<f64>,
+    pub sma_50: Option<f64>,
+    pub sma_200: Option<f64>,
+    pub ema_ribbon_score: Option<f64>,
+    pub kama: Option<f64>,
+    pub supertrend: Option<f64>,
+    pub parabolic_sar: Option<f64>,
+    pub adx: Option<f64>,
+    pub bollinger_upper: Option<f64>,
+    pub bollinger_middle: Option<f64>,
+    pub bollinger_lower: Option<f64>,
+    /// Price position within the Bollinger Bands (0.0-1.0)
+    pub bb_percent_b: Option<f64>,
+    /// Bollinger Band width as a percentage of the middle band
+    pub bb_bandwidth: Option<f64>,
+    pub keltner_upper: Option<f64>,
+    pub keltner_middle: Option<f64>,
+    pub keltner_lower: Option<f64>,
+    pub donchian_upper: Option<f64>,
+    pub donchian_middle: Option<f64>,
+    pub donchian_lower: Option<f64>,
+    /// Nearest Fibonacci level at or below the price
+    pub fib_support: Option<f64>,
+    /// Nearest Fibonacci level above the price
+    pub fib_resistance: Option<f64>,
+    /// Retracement of the latest swing, 0.0 at its end and 1.0 at its start
+    pub fib_position_ratio: Option<f64>,
+    pub atr: Option<f64>,
+    /// ATR as a percentage of the close
+    pub atr_pct: Option<f64>,
+    pub vwap: Option<f64>,
+    pub vp_poc_price: Option<f64>,
+    pub vp_value_area_high: Option<f64>,
+    pub vp_value_area_low: Option<f64>,
+    pub open_interest: Option<f64>,
+    pub open_interest_ma: Option<f64>,
+    /// Open interest deviation from its moving average, in [-1, 1]
+    pub open_interest_score: Option<f64>,
+    pub funding_rate: Option<f64>,
+    pub bid_ask_spread_pct: Option<f64>,
+    pub buy_sell_ratio: Option<f64>,
+}
+
+/// Rule-by-rule breakdown of a strategy evaluation
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct EvaluationTrace {
+    /// Top-level rules in strategy order
+    pub rules: Vec<RuleResult>,
+    /// Aggregated score compared against `thresholds`
+    pub total_score: i32,
+    pub thresholds: SignalThresholds,
 }
 
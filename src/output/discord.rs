@@ -0,0 +1,162 @@
+//! Discord webhook notifications for high-confidence signals
+
+use crate::config::{get_discord_min_confidence, get_discord_webhook_url};
+use crate::models::signal::{SignalDirection, SignalOutput};
+use crate::output::http_client;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tracing::debug;
+
+/// Reasons listed in an embed, highest weight first
+pub const MAX_EMBED_REASONS: usize = 3;
+/// Embed sidebar color for Long signals (green)
+pub const LONG_COLOR: u32 = 0x2ECC71;
+/// Embed sidebar color for Short signals (red)
+pub const SHORT_COLOR: u32 = 0xE74C3C;
+
+/// Body of a Discord webhook execution
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookPayload {
+    pub embeds: Vec<Embed>,
+}
+
+/// Subset of Discord's embed object used for signals
+#[derive(Debug, Clone, Serialize)]
+pub struct Embed {
+    pub title: String,
+    pub description: String,
+    pub color: u32,
+    pub fields: Vec<EmbedField>,
+    pub timestamp: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EmbedField {
+    pub name: String,
+    pub value: String,
+    pub inline: bool,
+}
+
+impl EmbedField {
+    fn inline(name: &str, value: String) -> Self {
+        Self {
+            name: name.to_string(),
+            value,
+            inline: true,
+        }
+    }
+}
+
+/// Posts Long and Short signals at or above `min_confidence` to a Discord
+/// webhook as an embed.
+pub struct DiscordNotifier {
+    webhook_url: String,
+    min_confidence: f64,
+    client: reqwest::Client,
+}
+
+impl DiscordNotifier {
+    pub fn new(webhook_url: impl Into<String>, min_confidence: f64) -> Self {
+        Self {
+            webhook_url: webhook_url.into(),
+            min_confidence,
+            client: http_client(),
+        }
+    }
+
+    /// Read `DISCORD_WEBHOOK_URL` and `DISCORD_MIN_CONFIDENCE`; `None` when
+    /// no webhook is configured
+    pub fn from_env() -> Option<Self> {
+        get_discord_webhook_url().map(|url| Self::new(url, get_discord_min_confidence()))
+    }
+
+    pub fn with_client(mut self, client: reqwest::Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    pub fn min_confidence(&self) -> f64 {
+        self.min_confidence
+    }
+
+    /// Whether `signal` is directional and confident enough to post
+    pub fn should_notify(&self, signal: &SignalOutput) -> bool {
+        signal.direction != SignalDirection::Neutral && signal.confidence >= self.min_confidence
+    }
+
+    /// Webhook body announcing `signal`
+    pub fn payload(signal: &SignalOutput) -> WebhookPayload {
+        let color = match signal.direction {
+            SignalDirection::Long => LONG_COLOR,
+            SignalDirection::Short => SHORT_COLOR,
+            SignalDirection::Neutral => 0,
+        };
+
+        let mut reasons: Vec<_> = signal.reasons.iter().collect();
+        reasons.sort_by(|a, b| b.weight.total_cmp(&a.weight));
+        let reasons = reasons
+            .iter()
+            .take(MAX_EMBED_REASONS)
+            .map(|reason| format!("• {}", reason.description))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        WebhookPayload {
+            embeds: vec![Embed {
+                title: format!("{} {}", signal.symbol, signal.direction.as_str()),
+                description: if reasons.is_empty() {
+                    "No reasons recorded".to_string()
+                } else {
+                    reasons
+                },
+                color,
+                fields: vec![
+                    EmbedField::inline("Symbol", signal.symbol.clone()),
+                    EmbedField::inline("Direction", signal.direction.as_str().to_string()),
+                    EmbedField::inline("Confidence", format!("{:.1}%", signal.confidence * 100.0)),
+                    EmbedField::inline("Price", format!("{}", signal.price)),
+                    EmbedField::inline("Stop Loss", format!("{:.2}%", signal.recommended_sl_pct)),
+                    EmbedField::inline("Take Profit", format!("{:.2}%", signal.recommended_tp_pct)),
+                ],
+                timestamp: signal.timestamp,
+            }],
+        }
+    }
+
+    /// Post `signal` if it passes [`Self::should_notify`].
+    ///
+    /// Returns whether it was posted.
+    pub async fn notify(
+        &self,
+        signal: &SignalOutput,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        if !self.should_notify(signal) {
+            return Ok(false);
+        }
+
+        let response = self
+            .client
+            .post(&self.webhook_url)
+            .json(&Self::payload(signal))
+            .send()
+            .await
+            .map_err(|e| {
+                Box::new(std::io::Error::other(format!(
+                    "Discord webhook request failed: {}",
+                    e
+                ))) as Box<dyn std::error::Error + Send + Sync>
+            })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            debug!(status = %status, response = %text, "Discord webhook error response");
+            return Err(Box::new(std::io::Error::other(format!(
+                "Discord webhook error: {} - Response: {}",
+                status, text
+            ))));
+        }
+
+        Ok(true)
+    }
+}
@@ -0,0 +1,59 @@
+//! Fan-out of stored signals to external channels
+
+pub mod discord;
+pub mod telegram;
+pub mod webhook;
+
+pub use discord::DiscordNotifier;
+pub use telegram::TelegramNotifier;
+pub use webhook::WebhookNotifier;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Longest a single request to a notification channel may take, so a hung
+/// endpoint cannot hold up the store job
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// HTTP client for notification channels, with [`REQUEST_TIMEOUT`]
+pub(crate) fn http_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .expect("HTTP client with a timeout builds")
+}
+
+/// Notification channels that stored signals are sent to; each is enabled
+/// when configured
+#[derive(Clone, Default)]
+pub struct NotificationConfig {
+    pub discord: Option<Arc<DiscordNotifier>>,
+    pub telegram: Option<Arc<TelegramNotifier>>,
+    pub webhook: Option<Arc<WebhookNotifier>>,
+}
+
+impl NotificationConfig {
+    /// Enable every channel whose environment variables are set
+    pub fn from_env() -> Self {
+        Self {
+            discord: DiscordNotifier::from_env().map(Arc::new),
+            telegram: TelegramNotifier::from_env().map(Arc::new),
+            webhook: WebhookNotifier::from_env().map(Arc::new),
+        }
+    }
+
+    pub fn with_discord(mut self, discord: Arc<DiscordNotifier>) -> Self {
+        self.discord = Some(discord);
+        self
+    }
+
+    pub fn with_telegram(mut self, telegram: Arc<TelegramNotifier>) -> Self {
+        self.telegram = Some(telegram);
+        self
+    }
+
+    pub fn with_webhook(mut self, webhook: Arc<WebhookNotifier>) -> Self {
+        self.webhook = Some(webhook);
+        self
+    }
+}
@@ -0,0 +1,244 @@
+//! Telegram bot notifications for high-confidence signals
+
+use crate::config::{get_telegram_bot_token, get_telegram_chat_id, get_telegram_min_confidence};
+use crate::models::signal::{SignalDirection, SignalOutput};
+use crate::output::http_client;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+pub const TELEGRAM_API_BASE: &str = "https://api.telegram.org";
+/// Minimum time between two messages for the same symbol
+pub const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60);
+/// Reasons listed in a message, highest weight first
+pub const MAX_MESSAGE_REASONS: usize = 5;
+/// Blocks in the confidence bar
+pub const CONFIDENCE_BAR_WIDTH: usize = 10;
+
+/// Body of a Bot API `sendMessage` call
+#[derive(Debug, Clone, Serialize)]
+pub struct SendMessage {
+    pub chat_id: String,
+    pub text: String,
+    pub parse_mode: &'static str,
+}
+
+/// Allows one message per key per window.
+///
+/// A slot is claimed before sending so concurrent signals for the same
+/// symbol cannot both get through, and released again if the send fails.
+#[derive(Debug)]
+pub struct RateLimiter {
+    window: Duration,
+    last_sent: Mutex<HashMap<String, Instant>>,
+}
+
+impl RateLimiter {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            last_sent: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Claim the slot for `key` at `now`; `false` while the previous claim
+    /// is younger than the window
+    pub fn try_acquire(&self, key: &str, now: Instant) -> bool {
+        let mut last_sent = self.last_sent.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(&sent) = last_sent.get(key) {
+            if now.saturating_duration_since(sent) < self.window {
+                return false;
+            }
+        }
+        last_sent.insert(key.to_string(), now);
+        true
+    }
+
+    /// Give back the slot for `key` so the next message is not held back
+    pub fn release(&self, key: &str) {
+        self.last_sent
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(key);
+    }
+}
+
+/// Posts Long and Short signals at or above `min_confidence` to a Telegram
+/// chat, at most one per symbol per [`RATE_LIMIT_WINDOW`].
+pub struct TelegramNotifier {
+    bot_token: String,
+    chat_id: String,
+    min_confidence: f64,
+    api_base: String,
+    client: reqwest::Client,
+    rate_limiter: RateLimiter,
+}
+
+impl TelegramNotifier {
+    pub fn new(
+        bot_token: impl Into<String>,
+        chat_id: impl Into<String>,
+        min_confidence: f64,
+    ) -> Self {
+        Self {
+            bot_token: bot_token.into(),
+            chat_id: chat_id.into(),
+            min_confidence,
+            api_base: TELEGRAM_API_BASE.to_string(),
+            client: http_client(),
+            rate_limiter: RateLimiter::new(RATE_LIMIT_WINDOW),
+        }
+    }
+
+    /// Read `TELEGRAM_BOT_TOKEN`, `TELEGRAM_CHAT_ID` and
+    /// `TELEGRAM_MIN_CONFIDENCE`; `None` unless both the token and chat ID
+    /// are set
+    pub fn from_env() -> Option<Self> {
+        let bot_token = get_telegram_bot_token()?;
+        let chat_id = get_telegram_chat_id()?;
+        Some(Self::new(bot_token, chat_id, get_telegram_min_confidence()))
+    }
+
+    /// Send to a different Bot API server, e.g. a local one
+    pub fn with_api_base(mut self, api_base: impl Into<String>) -> Self {
+        self.api_base = api_base.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_client(mut self, client: reqwest::Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    pub fn min_confidence(&self) -> f64 {
+        self.min_confidence
+    }
+
+    /// Whether `signal` is directional and confident enough to post
+    pub fn should_notify(&self, signal: &SignalOutput) -> bool {
+        signal.direction != SignalDirection::Neutral && signal.confidence >= self.min_confidence
+    }
+
+    /// Markdown message announcing `signal`
+    pub fn message(signal: &SignalOutput) -> String {
+        let emoji = match signal.direction {
+            SignalDirection::Long => "📈",
+            SignalDirection::Short => "📉",
+            SignalDirection::Neutral => "➖",
+        };
+
+        let mut lines = vec![
+            format!(
+                "{} *{} {}*",
+                emoji,
+                escape_markdown(&signal.symbol),
+                signal.direction.as_str()
+            ),
+            format!(
+                "Confidence: {} {:.1}%",
+                confidence_bar(signal.confidence),
+                signal.confidence * 100.0
+            ),
+            format!("Price: {}", signal.price),
+            format!(
+                "SL: {:.2}% | TP: {:.2}%",
+                signal.recommended_sl_pct, signal.recommended_tp_pct
+            ),
+        ];
+
+        let mut reasons: Vec<_> = signal.reasons.iter().collect();
+        reasons.sort_by(|a, b| b.weight.total_cmp(&a.weight));
+        if !reasons.is_empty() {
+            lines.push(String::new());
+            lines.push("*Reasons*".to_string());
+            lines.extend(
+                reasons
+                    .iter()
+                    .take(MAX_MESSAGE_REASONS)
+                    .map(|reason| format!("• {}", escape_markdown(&reason.description))),
+            );
+        }
+
+        lines.join("\n")
+    }
+
+    /// Post `signal` if it passes [`Self::should_notify`] and no message for
+    /// its symbol was sent within [`RATE_LIMIT_WINDOW`].
+    ///
+    /// Returns whether it was posted.
+    pub async fn notify(
+        &self,
+        signal: &SignalOutput,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        if !self.should_notify(signal) {
+            return Ok(false);
+        }
+        if !self
+            .rate_limiter
+            .try_acquire(&signal.symbol, Instant::now())
+        {
+            debug!(symbol = %signal.symbol, "Telegram message rate limited");
+            return Ok(false);
+        }
+
+        let result = self.send(&Self::message(signal)).await;
+        if result.is_err() {
+            self.rate_limiter.release(&signal.symbol);
+        }
+        result.map(|()| true)
+    }
+
+    async fn send(&self, text: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let url = format!("{}/bot{}/sendMessage", self.api_base, self.bot_token);
+        let response = self
+            .client
+            .post(&url)
+            .json(&SendMessage {
+                chat_id: self.chat_id.clone(),
+                text: text.to_string(),
+                parse_mode: "Markdown",
+            })
+            .send()
+            .await
+            .map_err(|e| {
+                // The URL embeds the bot token, so keep it out of the error
+                Box::new(std::io::Error::other(format!(
+                    "Telegram request failed: {}",
+                    e.without_url()
+                ))) as Box<dyn std::error::Error + Send + Sync>
+            })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            debug!(status = %status, response = %text, "Telegram error response");
+            return Err(Box::new(std::io::Error::other(format!(
+                "Telegram API error: {} - Response: {}",
+                status, text
+            ))));
+        }
+
+        Ok(())
+    }
+}
+
+/// `confidence` (0.0-1.0) as [`CONFIDENCE_BAR_WIDTH`] blocks, filled
+/// proportionally
+pub fn confidence_bar(confidence: f64) -> String {
+    let filled = (confidence.clamp(0.0, 1.0) * CONFIDENCE_BAR_WIDTH as f64).round() as usize;
+    "█".repeat(filled) + &"░".repeat(CONFIDENCE_BAR_WIDTH - filled)
+}
+
+/// Escape the characters that legacy Telegram Markdown treats as markup
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '_' | '*' | '`' | '[') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
@@ -0,0 +1,135 @@
+//! Signed webhook delivery of stored signals
+
+use crate::config::{get_webhook_secret, get_webhook_url};
+use crate::models::signal::SignalOutput;
+use crate::output::http_client;
+use hmac::{Hmac, KeyInit, Mac};
+use sha2::Sha256;
+use std::time::Duration;
+use tracing::debug;
+
+/// Header carrying the payload's HMAC-SHA256 signature
+pub const SIGNATURE_HEADER: &str = "X-Kryptex-Signature";
+/// Retries after a delivery attempt fails with a 5xx or a request error
+pub const MAX_RETRIES: u32 = 3;
+/// Delay before the first retry, doubled for each retry after it
+pub const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// `sha256=` followed by the hex HMAC-SHA256 of `body` keyed with `secret`
+pub fn signature(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    let digest: String = mac
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    format!("sha256={}", digest)
+}
+
+/// POSTs every stored signal as JSON to a webhook, signed in
+/// [`SIGNATURE_HEADER`] when a secret is set.
+///
+/// 5xx responses and failed or timed-out requests (see
+/// [`REQUEST_TIMEOUT`](crate::output::REQUEST_TIMEOUT)) are retried up to
+/// [`MAX_RETRIES`] times,
+/// waiting [`INITIAL_BACKOFF`] and doubling it each time (1s, 2s, 4s). Other
+/// error responses are not retried, since the same payload would be rejected
+/// again.
+pub struct WebhookNotifier {
+    url: String,
+    secret: Option<String>,
+    initial_backoff: Duration,
+    client: reqwest::Client,
+}
+
+impl WebhookNotifier {
+    pub fn new(url: impl Into<String>, secret: Option<String>) -> Self {
+        Self {
+            url: url.into(),
+            secret,
+            initial_backoff: INITIAL_BACKOFF,
+            client: http_client(),
+        }
+    }
+
+    /// Read `WEBHOOK_URL` and `WEBHOOK_SECRET`; `None` when no URL is
+    /// configured
+    pub fn from_env() -> Option<Self> {
+        get_webhook_url().map(|url| Self::new(url, get_webhook_secret()))
+    }
+
+    pub fn with_client(mut self, client: reqwest::Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Wait `initial_backoff` before the first retry instead of
+    /// [`INITIAL_BACKOFF`]
+    pub fn with_initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Whether payloads carry a [`SIGNATURE_HEADER`]
+    pub fn is_signed(&self) -> bool {
+        self.secret.is_some()
+    }
+
+    /// POST `signal`, retrying 5xx responses and failed requests.
+    ///
+    /// Returns the last error once retries run out.
+    pub async fn deliver(
+        &self,
+        signal: &SignalOutput,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let body = serde_json::to_vec(signal)?;
+        let signature = self
+            .secret
+            .as_deref()
+            .map(|secret| signature(secret, &body));
+
+        let mut backoff = self.initial_backoff;
+        let mut retries = 0;
+        loop {
+            let mut request = self
+                .client
+                .post(&self.url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(body.clone());
+            if let Some(ref signature) = signature {
+                request = request.header(SIGNATURE_HEADER, signature);
+            }
+
+            let error = match request.send().await {
+                Ok(response) if response.status().is_success() => return Ok(()),
+                Ok(response) => {
+                    let status = response.status();
+                    let text = response.text().await.unwrap_or_default();
+                    debug!(status = %status, response = %text, "Webhook error response");
+                    let error = format!("Webhook error: {} - Response: {}", status, text);
+                    if !status.is_server_error() {
+                        return Err(Box::new(std::io::Error::other(error)));
+                    }
+                    error
+                }
+                Err(e) => format!("Webhook request failed: {}", e),
+            };
+
+            if retries == MAX_RETRIES {
+                return Err(Box::new(std::io::Error::other(error)));
+            }
+            retries += 1;
+            debug!(
+                retry = retries,
+                delay_ms = backoff.as_millis() as u64,
+                error = %error,
+                "Retrying webhook delivery"
+            );
+            tokio::time::sleep(backoff).await;
+            backoff *= 2;
+        }
+    }
+}
@@ -0,0 +1,78 @@
+//! Open positions implied by published signals, so a symbol is not sent a
+//! second signal in the direction it is already positioned.
+
+use crate::cache::RedisCache;
+use crate::error::KryptexError;
+use crate::models::signal::{SignalDirection, SignalOutput};
+use std::sync::Arc;
+
+const POSITION_KEY_PREFIX: &str = "positions";
+
+/// Redis key holding the signal that opened the position for `symbol`
+pub fn position_key(symbol: &str) -> String {
+    format!("{}:{}", POSITION_KEY_PREFIX, symbol)
+}
+
+/// The active signal for each symbol, stored in Redis so every worker and
+/// API server sees the same positions.
+///
+/// A position stays open until it is closed through the API; a signal in the
+/// other direction replaces it.
+#[derive(Clone)]
+pub struct PositionTracker {
+    cache: Arc<RedisCache>,
+}
+
+impl PositionTracker {
+    pub fn new(cache: Arc<RedisCache>) -> Self {
+        Self { cache }
+    }
+
+    /// The signal that opened the position for `symbol`, if one is open
+    pub async fn open_position(&self, symbol: &str) -> Result<Option<SignalOutput>, KryptexError> {
+        self.cache.get_json(&position_key(symbol)).await
+    }
+
+    /// Whether `signal` repeats the direction of the position already open
+    /// for its symbol. Neutral signals never do.
+    pub async fn is_duplicate(&self, signal: &SignalOutput) -> Result<bool, KryptexError> {
+        if signal.direction == SignalDirection::Neutral {
+            return Ok(false);
+        }
+        let open = self.open_position(&signal.symbol).await?;
+        Ok(open.is_some_and(|open| open.direction == signal.direction))
+    }
+
+    /// Record `signal` as the open position for its symbol. Neutral signals
+    /// leave the tracker unchanged.
+    pub async fn record(&self, signal: &SignalOutput) -> Result<(), KryptexError> {
+        if signal.direction == SignalDirection::Neutral {
+            return Ok(());
+        }
+        self.cache
+            .set_json(&position_key(&signal.symbol), signal, 0)
+            .await
+    }
+
+    /// Close the position for `symbol`, returning whether one was open
+    pub async fn close(&self, symbol: &str) -> Result<bool, KryptexError> {
+        self.cache.delete(&position_key(symbol)).await
+    }
+
+    /// Every open position, sorted by symbol
+    pub async fn list(&self) -> Result<Vec<SignalOutput>, KryptexError> {
+        let keys = self
+            .cache
+            .scan_keys(&format!("{}:*", POSITION_KEY_PREFIX))
+            .await?;
+        let mut positions = Vec::with_capacity(keys.len());
+        for key in keys {
+            // Closed between the scan and the read
+            if let Some(position) = self.cache.get_json::<SignalOutput>(&key).await? {
+                positions.push(position);
+            }
+        }
+        positions.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+        Ok(positions)
+    }
+}
@@ -1,17 +1,26 @@
 //! Hyperliquid WebSocket client
 
 use crate::config::get_hyperliquid_ws_url;
+use crate::metrics::Metrics;
+use crate::services::websocket::reconnect::{
+    ConnectionConfig, HeartbeatMonitor, ReconnectBackoff, MAX_MISSED_PONGS,
+};
 use async_trait::async_trait;
+use chrono::Utc;
 use futures_util::{SinkExt, StreamExt};
 use std::sync::Arc;
 use tokio::sync::{mpsc, RwLock};
-use tokio::time::{sleep, Duration};
+use tokio::task::JoinHandle;
+use tokio::time::{sleep, Duration, Instant, MissedTickBehavior};
 use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
 use tracing::{debug, error, info, warn};
 use url::Url;
 
 pub type WsStream = WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>;
 
+/// Default interval between `is_connected` checks while waiting for a connection
+pub const DEFAULT_CONNECTION_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
 #[derive(Debug, Clone)]
 pub enum ClientEvent {
     Message(String),
@@ -37,15 +46,87 @@ pub trait WebSocketClient: Send + Sync + 'static {
 
     async fn is_connected(&self) -> bool;
 
-    async fn wait_for_connection(&self, timeout: Duration) -> bool;
+    /// Poll `is_connected` every `poll_interval` until connected or `timeout` elapses
+    async fn wait_for_connection(&self, timeout: Duration, poll_interval: Duration) -> bool {
+        poll_until_connected(timeout, poll_interval, || self.is_connected()).await
+    }
+
+    /// Like `wait_for_connection` with the default poll interval, but returns a
+    /// `TimedOut` error instead of `false`
+    async fn wait_for_connection_or_err(
+        &self,
+        timeout: Duration,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if self
+            .wait_for_connection(timeout, DEFAULT_CONNECTION_POLL_INTERVAL)
+            .await
+        {
+            Ok(())
+        } else {
+            Err(Box::new(std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                format!("WebSocket connection not established within {:?}", timeout),
+            )))
+        }
+    }
+
+    /// When the most recent successful connection was established
+    async fn connection_established_at(&self) -> Option<Instant> {
+        None
+    }
+}
+
+async fn poll_until_connected<F, Fut>(
+    timeout: Duration,
+    poll_interval: Duration,
+    mut is_connected: F,
+) -> bool
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = bool>,
+{
+    let deadline = Instant::now() + timeout;
+    loop {
+        let now = Instant::now();
+        if now >= deadline {
+            return false;
+        }
+        if is_connected().await {
+            return true;
+        }
+        sleep(poll_interval.min(deadline - now)).await;
+    }
 }
 
 pub struct HyperliquidClient {
     url: String,
     sender: Arc<RwLock<Option<mpsc::UnboundedSender<Message>>>>,
     receiver: Arc<RwLock<Option<mpsc::UnboundedReceiver<ClientEvent>>>>,
-    reconnect_delay: Duration,
-    max_reconnect_delay: Duration,
+    config: ConnectionConfig,
+    metrics: Option<Arc<Metrics>>,
+    connected_at: Arc<RwLock<Option<Instant>>>,
+}
+
+/// Tasks serving one established connection
+struct Connection {
+    tasks: Vec<JoinHandle<()>>,
+    closed: mpsc::UnboundedReceiver<String>,
+}
+
+impl Connection {
+    /// Wait until any task reports the connection gone, then stop the rest.
+    /// Returns the reason the connection ended.
+    async fn closed(mut self) -> String {
+        let reason = self
+            .closed
+            .recv()
+            .await
+            .unwrap_or_else(|| "connection tasks ended".to_string());
+        for task in &self.tasks {
+            task.abort();
+        }
+        reason
+    }
 }
 
 impl HyperliquidClient {
@@ -58,57 +139,85 @@ impl HyperliquidClient {
             url,
             sender: Arc::new(RwLock::new(None)),
             receiver: Arc::new(RwLock::new(None)),
-            reconnect_delay: Duration::from_secs(1),
-            max_reconnect_delay: Duration::from_secs(60),
+            config: ConnectionConfig::from_env(),
+            metrics: None,
+            connected_at: Arc::new(RwLock::new(None)),
         }
     }
 
+    /// Override the heartbeat and backoff settings read from the environment
+    pub fn with_config(mut self, config: ConnectionConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Report connection status and reconnections to `metrics`
+    pub fn with_metrics(mut self, metrics: Arc<Metrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Connect and stay connected.
+    ///
+    /// Pings the server every `heartbeat_interval_secs` and drops the
+    /// connection after [`MAX_MISSED_PONGS`] pings in a row go unanswered.
+    /// Failed attempts and dropped connections are retried with
+    /// [`ReconnectBackoff`], which is reset by every successful handshake.
     pub async fn connect(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let mut current_delay = self.reconnect_delay;
+        let mut backoff = ReconnectBackoff::from_config(&self.config);
 
         let mut is_first_connection = true;
         loop {
             match self.try_connect().await {
-                Ok(()) => {
+                Ok(connection) => {
                     // Only print once per initial connection, not on every reconnect
                     if is_first_connection {
                         info!("Hyperliquid WebSocket connected");
                         is_first_connection = false;
                     } else {
-                        info!(delay = ?current_delay, "Hyperliquid WebSocket reconnected (delay was {:?})", current_delay);
-                    }
-                    current_delay = self.reconnect_delay;
-
-                    // Spawn ping task to keep connection alive
-                    let sender_clone = self.sender.clone();
-                    tokio::spawn(async move {
-                        let mut ping_interval = tokio::time::interval(Duration::from_secs(30));
-                        loop {
-                            ping_interval.tick().await;
-                            if let Some(sender) = sender_clone.read().await.as_ref() {
-                                if sender.send(Message::Ping(vec![])).is_err() {
-                                    break;
-                                }
-                            } else {
-                                break;
-                            }
+                        info!(
+                            attempts = backoff.attempts(),
+                            "Hyperliquid WebSocket reconnected after {} attempts",
+                            backoff.attempts()
+                        );
+                        if let Some(ref metrics) = self.metrics {
+                            metrics
+                                .websocket_last_reconnect_at_timestamp
+                                .set(Utc::now().timestamp() as f64);
                         }
-                    });
+                    }
+                    backoff.reset();
+                    if let Some(ref metrics) = self.metrics {
+                        metrics.websocket_connected.set(1.0);
+                    }
 
-                    // Wait for disconnection
-                    let _ = self.run_connection().await;
+                    let reason = connection.closed().await;
+                    *self.sender.write().await = None;
+                    if let Some(ref metrics) = self.metrics {
+                        metrics.websocket_connected.set(0.0);
+                    }
+                    warn!(reason = %reason, "Hyperliquid WebSocket connection lost: {}", reason);
                 }
                 Err(e) => {
-                    let error_msg = format!("{}", e);
-                    warn!(error = %e, delay = ?current_delay, "Failed to connect: {}. Retrying in {:?}...", error_msg, current_delay);
-                    sleep(current_delay).await;
-                    current_delay = std::cmp::min(current_delay * 2, self.max_reconnect_delay);
+                    warn!(error = %e, "Failed to connect: {}", e);
                 }
             }
+
+            let delay = backoff.next_delay();
+            if let Some(ref metrics) = self.metrics {
+                metrics.websocket_reconnect_attempts_total.inc();
+            }
+            info!(
+                attempt = backoff.attempts(),
+                delay = ?delay,
+                "Reconnecting Hyperliquid WebSocket in {:?}",
+                delay
+            );
+            sleep(delay).await;
         }
     }
 
-    async fn try_connect(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    async fn try_connect(&self) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
         let url = Url::parse(&self.url)?;
         let (ws_stream, _) = connect_async(url).await?;
 
@@ -116,6 +225,8 @@ impl HyperliquidClient {
 
         let (tx, mut rx) = mpsc::unbounded_channel::<Message>();
         let (event_tx, event_rx) = mpsc::unbounded_channel::<ClientEvent>();
+        let (closed_tx, closed_rx) = mpsc::unbounded_channel::<String>();
+        let heartbeat = Arc::new(HeartbeatMonitor::default());
 
         // Store sender and receiver
         {
@@ -127,16 +238,20 @@ impl HyperliquidClient {
             *receiver_guard = Some(event_rx);
         }
 
+        *self.connected_at.write().await = Some(Instant::now());
+
         // Send connection event
         let _ = event_tx.send(ClientEvent::Connected);
 
         // Spawn writer task
         let event_tx_writer = event_tx.clone();
-        tokio::spawn(async move {
+        let closed_tx_writer = closed_tx.clone();
+        let writer = tokio::spawn(async move {
             while let Some(msg) = rx.recv().await {
                 if let Err(e) = write.send(msg).await {
                     error!(error = %e, "Error sending message");
                     let _ = event_tx_writer.send(ClientEvent::Error(e.to_string()));
+                    let _ = closed_tx_writer.send(format!("write failed: {}", e));
                     break;
                 }
             }
@@ -144,8 +259,10 @@ impl HyperliquidClient {
 
         // Spawn reader task
         let event_tx_reader = event_tx.clone();
+        let closed_tx_reader = closed_tx.clone();
         let sender_for_pong = tx.clone();
-        tokio::spawn(async move {
+        let heartbeat_reader = heartbeat.clone();
+        let reader = tokio::spawn(async move {
             while let Some(msg) = read.next().await {
                 match msg {
                     Ok(Message::Text(text)) => {
@@ -154,14 +271,15 @@ impl HyperliquidClient {
                     Ok(Message::Close(frame)) => {
                         debug!(frame = ?frame, "WebSocket received Close frame");
                         let _ = event_tx_reader.send(ClientEvent::Disconnected);
-                        break;
+                        let _ = closed_tx_reader.send("server closed the connection".to_string());
+                        return;
                     }
                     Ok(Message::Ping(data)) => {
                         // Auto-respond to ping with pong
                         let _ = sender_for_pong.send(Message::Pong(data));
                     }
                     Ok(Message::Pong(_)) => {
-                        // Pong received, connection is alive
+                        heartbeat_reader.pong();
                     }
                     Ok(Message::Binary(data)) => {
                         debug!(
@@ -176,26 +294,44 @@ impl HyperliquidClient {
                     Err(e) => {
                         error!(error = %e, "WebSocket read error");
                         let _ = event_tx_reader.send(ClientEvent::Error(e.to_string()));
-                        break;
+                        let _ = closed_tx_reader.send(format!("read failed: {}", e));
+                        return;
                     }
                 }
             }
             debug!("WebSocket reader task ended");
+            let _ = event_tx_reader.send(ClientEvent::Disconnected);
+            let _ = closed_tx_reader.send("stream ended".to_string());
         });
 
-        Ok(())
-    }
-
-    async fn run_connection(&self) -> Result<(), Box<dyn std::error::Error>> {
-        // This method is called after connection is established
-        // The connection will be maintained by the spawned tasks
-        // Wait for disconnection event
-        while let Some(event) = self.receive().await {
-            if matches!(event, ClientEvent::Disconnected | ClientEvent::Error(_)) {
-                break;
+        // Spawn heartbeat task
+        let heartbeat_interval = self.config.heartbeat_interval();
+        let pinger = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(heartbeat_interval);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                if !heartbeat.ping() {
+                    warn!(
+                        missed = MAX_MISSED_PONGS,
+                        "WebSocket heartbeat: {} pings went unanswered, reconnecting",
+                        MAX_MISSED_PONGS
+                    );
+                    let _ = event_tx.send(ClientEvent::Disconnected);
+                    let _ = closed_tx.send("heartbeat timed out".to_string());
+                    return;
+                }
+                if tx.send(Message::Ping(vec![])).is_err() {
+                    let _ = closed_tx.send("writer stopped".to_string());
+                    return;
+                }
             }
-        }
-        Ok(())
+        });
+
+        Ok(Connection {
+            tasks: vec![writer, reader, pinger],
+            closed: closed_rx,
+        })
     }
 
     pub async fn send(
@@ -234,15 +370,13 @@ impl HyperliquidClient {
         self.sender.read().await.is_some()
     }
 
-    pub async fn wait_for_connection(&self, timeout: Duration) -> bool {
-        let start = std::time::Instant::now();
-        while start.elapsed() < timeout {
-            if self.is_connected().await {
-                return true;
-            }
-            sleep(Duration::from_millis(100)).await;
-        }
-        false
+    pub async fn wait_for_connection(&self, timeout: Duration, poll_interval: Duration) -> bool {
+        poll_until_connected(timeout, poll_interval, || self.is_connected()).await
+    }
+
+    /// When the most recent successful connection was established
+    pub async fn connection_established_at(&self) -> Option<Instant> {
+        *self.connected_at.read().await
     }
 }
 
@@ -277,14 +411,19 @@ impl WebSocketClient for HyperliquidClient {
         HyperliquidClient::is_connected(self).await
     }
 
-    async fn wait_for_connection(&self, timeout: Duration) -> bool {
-        HyperliquidClient::wait_for_connection(self, timeout).await
+    async fn wait_for_connection(&self, timeout: Duration, poll_interval: Duration) -> bool {
+        HyperliquidClient::wait_for_connection(self, timeout, poll_interval).await
+    }
+
+    async fn connection_established_at(&self) -> Option<Instant> {
+        HyperliquidClient::connection_established_at(self).await
     }
 }
 
 /// Simple in-memory WebSocket client mock for integration testing.
 pub struct MockWebSocketClient {
     connected: Arc<RwLock<bool>>,
+    connected_at: Arc<RwLock<Option<Instant>>>,
     sent_messages: Arc<RwLock<Vec<Message>>>,
     receiver: Arc<RwLock<Option<mpsc::UnboundedReceiver<ClientEvent>>>>,
     sender: mpsc::UnboundedSender<ClientEvent>,
@@ -295,6 +434,7 @@ impl MockWebSocketClient {
         let (tx, rx) = mpsc::unbounded_channel();
         Self {
             connected: Arc::new(RwLock::new(false)),
+            connected_at: Arc::new(RwLock::new(None)),
             sent_messages: Arc::new(RwLock::new(Vec::new())),
             receiver: Arc::new(RwLock::new(Some(rx))),
             sender: tx,
@@ -314,6 +454,9 @@ impl MockWebSocketClient {
     /// Manually set the connection state.
     pub async fn set_connected(&self, connected: bool) {
         *self.connected.write().await = connected;
+        if connected {
+            *self.connected_at.write().await = Some(Instant::now());
+        }
     }
 }
 
@@ -330,6 +473,7 @@ impl WebSocketClient for MockWebSocketClient {
             let mut guard = self.connected.write().await;
             *guard = true;
         }
+        *self.connected_at.write().await = Some(Instant::now());
         let _ = self.sender.send(ClientEvent::Connected);
         Ok(())
     }
@@ -354,14 +498,7 @@ impl WebSocketClient for MockWebSocketClient {
         *self.connected.read().await
     }
 
-    async fn wait_for_connection(&self, timeout: Duration) -> bool {
-        let start = std::time::Instant::now();
-        while start.elapsed() < timeout {
-            if self.is_connected().await {
-                return true;
-            }
-            sleep(Duration::from_millis(50)).await;
-        }
-        false
+    async fn connection_established_at(&self) -> Option<Instant> {
+        *self.connected_at.read().await
     }
 }
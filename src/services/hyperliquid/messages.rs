@@ -1,6 +1,9 @@
 //! Hyperliquid WebSocket message types
 
-use serde::{Deserialize, Serialize};
+use crate::models::indicators::{Trade, TradeSide};
+use chrono::DateTime;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "method")]
@@ -20,6 +23,18 @@ pub enum Subscription {
         coin: String,
         interval: String,
     },
+    // Before L2Book, which has the same shape; only matches `"type": "trades"`
+    Trades {
+        #[serde(rename = "type", deserialize_with = "trades_type")]
+        sub_type: String,
+        coin: String,
+    },
+    // Before AllMids, whose optional field would otherwise match any `{type, coin}`
+    L2Book {
+        #[serde(rename = "type")]
+        sub_type: String,
+        coin: String,
+    },
     AllMids {
         #[serde(rename = "type")]
         sub_type: String,
@@ -42,6 +57,20 @@ impl Subscription {
         }
     }
 
+    pub fn l2_book(coin: &str) -> Self {
+        Subscription::L2Book {
+            sub_type: "l2Book".to_string(),
+            coin: coin.to_string(),
+        }
+    }
+
+    pub fn trades(coin: &str) -> Self {
+        Subscription::Trades {
+            sub_type: "trades".to_string(),
+            coin: coin.to_string(),
+        }
+    }
+
     pub fn all_mids(dex: Option<String>) -> Self {
         Subscription::AllMids {
             sub_type: "allMids".to_string(),
@@ -50,6 +79,18 @@ impl Subscription {
     }
 }
 
+fn trades_type<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    let sub_type = String::deserialize(deserializer)?;
+    if sub_type == "trades" {
+        Ok(sub_type)
+    } else {
+        Err(serde::de::Error::custom(format!(
+            "expected trades subscription, got {}",
+            sub_type
+        )))
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct SubscriptionResponse {
     pub channel: String,
@@ -70,6 +111,8 @@ pub enum WebSocketMessage {
     SubscriptionResponse(SubscriptionResponse),
     CandleData(CandleData),
     AllMidsData(AllMidsData),
+    L2BookData(L2BookData),
+    TradesData(TradesData),
     Error(ErrorMessage),
 }
 
@@ -108,13 +151,110 @@ pub struct CandleUpdate {
 #[derive(Debug, Clone, Deserialize)]
 pub struct AllMidsData {
     pub channel: String,
-    pub data: Vec<MidPrice>,
+    pub data: AllMidsUpdate,
 }
 
+/// Mid prices keyed by coin, pushed roughly every 3 seconds
 #[derive(Debug, Clone, Deserialize)]
-pub struct MidPrice {
+pub struct AllMidsUpdate {
+    pub mids: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct L2BookData {
+    pub channel: String,
+    pub data: L2BookUpdate,
+}
+
+/// Order book snapshot: `levels` holds the bids, then the asks
+#[derive(Debug, Clone, Deserialize)]
+pub struct L2BookUpdate {
     pub coin: String,
+    pub time: u64,
+    pub levels: (Vec<L2Level>, Vec<L2Level>),
+}
+
+/// Price level: price, total size, and number of orders
+#[derive(Debug, Clone, Deserialize)]
+pub struct L2Level {
     pub px: String,
+    pub sz: String,
+    pub n: u64,
+}
+
+impl L2BookUpdate {
+    /// Highest bid price, skipping unparsable levels
+    pub fn best_bid(&self) -> Option<f64> {
+        self.levels
+            .0
+            .iter()
+            .filter_map(L2Level::price)
+            .reduce(f64::max)
+    }
+
+    /// Lowest ask price, skipping unparsable levels
+    pub fn best_ask(&self) -> Option<f64> {
+        self.levels
+            .1
+            .iter()
+            .filter_map(L2Level::price)
+            .reduce(f64::min)
+    }
+
+    /// `(best_bid, best_ask)` when both sides have a valid price
+    pub fn top_of_book(&self) -> Option<(f64, f64)> {
+        Some((self.best_bid()?, self.best_ask()?))
+    }
+}
+
+impl L2Level {
+    pub fn price(&self) -> Option<f64> {
+        self.px
+            .parse()
+            .ok()
+            .filter(|px: &f64| px.is_finite() && *px > 0.0)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TradesData {
+    pub channel: String,
+    pub data: Vec<WsTrade>,
+}
+
+/// Public fill; `side` is the aggressor, "B" for buy and "A" for sell
+#[derive(Debug, Clone, Deserialize)]
+pub struct WsTrade {
+    pub coin: String,
+    pub side: String,
+    pub px: String,
+    pub sz: String,
+    /// Milliseconds since the UNIX epoch
+    pub time: i64,
+    #[serde(default)]
+    pub tid: Option<u64>,
+}
+
+impl WsTrade {
+    /// Parsed trade, or `None` for an unknown side or invalid price, size or time
+    pub fn to_trade(&self) -> Option<Trade> {
+        let side = match self.side.as_str() {
+            "B" => TradeSide::Buy,
+            "A" => TradeSide::Sell,
+            _ => return None,
+        };
+        let price: f64 = self.px.parse().ok()?;
+        let size: f64 = self.sz.parse().ok()?;
+        if !(price.is_finite() && price > 0.0 && size.is_finite() && size >= 0.0) {
+            return None;
+        }
+        Some(Trade {
+            price,
+            size,
+            side,
+            timestamp: DateTime::from_timestamp_millis(self.time)?,
+        })
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
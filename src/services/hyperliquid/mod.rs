@@ -6,6 +6,8 @@ pub mod provider;
 pub mod rest;
 pub mod subscriptions;
 
-pub use client::{HyperliquidClient, MockWebSocketClient, WebSocketClient};
-pub use provider::HyperliquidMarketDataProvider;
+pub use client::{
+    HyperliquidClient, MockWebSocketClient, WebSocketClient, DEFAULT_CONNECTION_POLL_INTERVAL,
+};
+pub use provider::{EvictionPolicy, HyperliquidMarketDataProvider, ProviderConfig};
 pub use rest::HyperliquidRestClient;
@@ -1,44 +1,128 @@
 //! Hyperliquid market data provider implementation
 
+use crate::cache::redis::{latest_buy_sell_ratio_key, latest_spread_key};
 use crate::cache::RedisCache;
 use crate::config;
 use crate::db::QuestDatabase;
-use crate::models::indicators::Candle;
+use crate::indicators::structure::candle_aggregator::interval_minutes;
+use crate::indicators::structure::gap_fill::{
+    fill_candle_gaps_up_to, DEFAULT_MAX_GAP_FILL_CANDLES,
+};
+use crate::indicators::volume::order_flow::{buy_sell_ratio, BUY_SELL_RATIO_WINDOW};
+use crate::metrics::Metrics;
+use crate::models::indicators::{Candle, Trade};
 use crate::services::market_data::MarketDataProvider;
+use crate::services::websocket::freshness::{default_max_candle_age, DataFreshnessMonitor};
 use chrono::{DateTime, Duration as ChronoDuration, Utc};
 use serde_json;
 use std::collections::{HashMap, VecDeque};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
 use tokio::sync::RwLock;
 use tokio::time::{sleep, Duration};
 use tracing::{debug, error, warn};
 
 use super::client::{ClientEvent, HyperliquidClient, WebSocketClient};
-use super::messages::{CandleData, CandleUpdate, RequestMessage, Subscription, WebSocketMessage};
+use super::messages::{
+    CandleData, CandleUpdate, L2BookUpdate, RequestMessage, Subscription, WebSocketMessage, WsTrade,
+};
 use super::rest::HyperliquidRestClient;
 use super::subscriptions::{SubscriptionKey, SubscriptionManager};
 
+/// How long a cached top of book stays readable after the last `l2Book` update
+const SPREAD_CACHE_TTL_SECONDS: u64 = 60;
+/// How long a cached buy/sell ratio stays readable after the last `trades` update
+const BUY_SELL_RATIO_CACHE_TTL_SECONDS: u64 = 60;
+/// Trades kept in memory per coin, enough for the buy/sell ratio window
+const TRADES_PER_SYMBOL: usize = BUY_SELL_RATIO_WINDOW;
+/// `candleSnapshot` requests made before a backfill gives up
+const SNAPSHOT_FETCH_ATTEMPTS: u32 = 3;
+/// Delay before the second `candleSnapshot` attempt; doubles on each retry
+const SNAPSHOT_RETRY_INITIAL_DELAY: Duration = Duration::from_millis(250);
+/// Candles kept in memory per symbol-interval unless configured otherwise
+pub const DEFAULT_MAX_CANDLES_PER_SYMBOL: usize = 1000;
+
+/// How an in-memory candle buffer takes in a new candle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EvictionPolicy {
+    /// Append every candle, dropping the oldest once the buffer is full
+    DropOldest,
+    /// Replace a buffered candle with the same timestamp (an update to the
+    /// still-open candle), otherwise append and drop the oldest once full
+    #[default]
+    DropIfDuplicate,
+}
+
+impl EvictionPolicy {
+    /// Add `candle` to `buffer`, keeping at most `max_candles`
+    pub fn insert(self, buffer: &mut VecDeque<Candle>, candle: Candle, max_candles: usize) {
+        if self == EvictionPolicy::DropIfDuplicate {
+            buffer.retain(|c| c.timestamp != candle.timestamp);
+        }
+        buffer.push_back(candle);
+        while buffer.len() > max_candles {
+            buffer.pop_front();
+        }
+    }
+}
+
+/// In-memory buffering and subscription settings for the provider
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProviderConfig {
+    pub max_candles_per_symbol: usize,
+    pub eviction_policy: EvictionPolicy,
+    /// Intervals subscribed for each symbol; the first is the primary one
+    pub candle_intervals: Vec<String>,
+    /// Most candles interpolated into one gap when serving candles from the
+    /// in-memory buffer; longer outages are left unfilled
+    pub max_gap_fill_candles: usize,
+    /// Seconds without a live candle before a symbol's feed counts as
+    /// stale; twice the primary interval when unset
+    pub max_candle_age_secs: Option<u64>,
+}
+
+impl Default for ProviderConfig {
+    fn default() -> Self {
+        Self {
+            max_candles_per_symbol: DEFAULT_MAX_CANDLES_PER_SYMBOL,
+            eviction_policy: EvictionPolicy::default(),
+            candle_intervals: default_candle_intervals(),
+            max_gap_fill_candles: DEFAULT_MAX_GAP_FILL_CANDLES,
+            max_candle_age_secs: None,
+        }
+    }
+}
+
 pub struct HyperliquidMarketDataProvider {
     pub(crate) client: Arc<dyn WebSocketClient>,
     subscriptions: Arc<SubscriptionManager>,
     candles: Arc<RwLock<HashMap<String, VecDeque<Candle>>>>,
     latest_prices: Arc<RwLock<HashMap<String, f64>>>,
+    /// `(best_bid, best_ask)` per coin from the `l2Book` channel
+    latest_spreads: Arc<RwLock<HashMap<String, (f64, f64)>>>,
+    /// Most recent fills per coin from the `trades` channel, oldest first
+    latest_trades: Arc<RwLock<HashMap<String, VecDeque<Trade>>>>,
     candle_intervals: Vec<String>,
     pending_subscriptions: Arc<RwLock<Vec<(String, String)>>>, // (coin, interval)
     rest_client: Arc<HyperliquidRestClient>,
     database: Option<Arc<QuestDatabase>>,
     cache: Option<Arc<RedisCache>>,
     funding_cache: Arc<RwLock<HashMap<String, FundingCacheEntry>>>,
+    subscribe_all_mids: Arc<AtomicBool>,
+    config: Arc<std::sync::RwLock<ProviderConfig>>,
+    metrics: Arc<OnceLock<Arc<Metrics>>>,
+    freshness: DataFreshnessMonitor,
 }
 
 impl HyperliquidMarketDataProvider {
     pub fn new() -> Self {
-        Self::with_intervals(vec![
-            "1m".to_string(),
-            "5m".to_string(),
-            "15m".to_string(),
-            "1h".to_string(),
-        ])
+        Self::with_intervals(default_candle_intervals())
+    }
+
+    /// Provider with the default candle intervals and the given WebSocket client
+    pub fn with_websocket_client(websocket_client: Arc<dyn WebSocketClient>) -> Self {
+        let rest_client = Arc::new(HyperliquidRestClient::new());
+        Self::with_clients(websocket_client, rest_client, default_candle_intervals())
     }
 
     pub fn with_intervals(candle_intervals: Vec<String>) -> Self {
@@ -57,12 +141,21 @@ impl HyperliquidMarketDataProvider {
             subscriptions: Arc::new(SubscriptionManager::new()),
             candles: Arc::new(RwLock::new(HashMap::new())),
             latest_prices: Arc::new(RwLock::new(HashMap::new())),
+            latest_spreads: Arc::new(RwLock::new(HashMap::new())),
+            latest_trades: Arc::new(RwLock::new(HashMap::new())),
             candle_intervals: candle_intervals.clone(),
             pending_subscriptions: Arc::new(RwLock::new(Vec::new())),
             rest_client,
             database: None,
             cache: None,
             funding_cache: Arc::new(RwLock::new(HashMap::new())),
+            subscribe_all_mids: Arc::new(AtomicBool::new(true)),
+            config: Arc::new(std::sync::RwLock::new(ProviderConfig {
+                candle_intervals,
+                ..ProviderConfig::default()
+            })),
+            metrics: Arc::new(OnceLock::new()),
+            freshness: DataFreshnessMonitor::new(),
         };
 
         provider.spawn_background_tasks();
@@ -75,12 +168,18 @@ impl HyperliquidMarketDataProvider {
             subscriptions: self.subscriptions.clone(),
             candles: self.candles.clone(),
             latest_prices: self.latest_prices.clone(),
+            latest_spreads: self.latest_spreads.clone(),
+            latest_trades: self.latest_trades.clone(),
             pending_subscriptions: self.pending_subscriptions.clone(),
             candle_intervals: self.candle_intervals.clone(),
             database: self.database.clone(),
             cache: self.cache.clone(),
             rest_client: self.rest_client.clone(),
             funding_cache: self.funding_cache.clone(),
+            subscribe_all_mids: self.subscribe_all_mids.clone(),
+            config: self.config.clone(),
+            metrics: self.metrics.clone(),
+            freshness: self.freshness.clone(),
         }
     }
 
@@ -101,65 +200,6 @@ impl HyperliquidMarketDataProvider {
         coin: &str,
         interval: &str,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        // Fetch historical candles (even if database is unavailable, we can use Redis and in-memory)
-        let historical_count = config::get_historical_candle_count();
-        debug!(coin = %coin, interval = %interval, count = historical_count, "Fetching {} historical candles for {}/{}", historical_count, coin, interval);
-
-        match self
-            .rest_client
-            .fetch_historical_candles(coin, interval, historical_count)
-            .await
-        {
-            Ok(mut historical_candles) => {
-                debug!(coin = %coin, interval = %interval, count = historical_candles.len(), "Fetched {} historical candles for {}/{}", historical_candles.len(), coin, interval);
-
-                self.attach_funding_rates(coin, &mut historical_candles)
-                    .await;
-
-                // Store in QuestDB if available
-                if let Some(ref db) = self.database {
-                    if let Err(e) = db
-                        .store_candles_batch(coin, interval, &historical_candles)
-                        .await
-                    {
-                        warn!(coin = %coin, interval = %interval, error = %e, "Failed to store historical candles in QuestDB");
-                    } else {
-                        debug!(coin = %coin, interval = %interval, count = historical_candles.len(), "Stored {} historical candles in QuestDB", historical_candles.len());
-                    }
-                }
-
-                // Cache in Redis if available
-                if let Some(ref cache) = self.cache {
-                    if let Err(e) = cache
-                        .cache_candles(coin, interval, &historical_candles)
-                        .await
-                    {
-                        warn!(coin = %coin, interval = %interval, error = %e, "Failed to cache historical candles in Redis");
-                    } else {
-                        debug!(coin = %coin, interval = %interval, count = historical_candles.len(), "Cached {} historical candles in Redis", historical_candles.len());
-                    }
-                }
-
-                // Always update in-memory buffer
-                let symbol_key = format!("{}_{}", coin, interval);
-                let mut candles_map = self.candles.write().await;
-                let candles = candles_map
-                    .entry(symbol_key.clone())
-                    .or_insert_with(VecDeque::new);
-                for candle in historical_candles {
-                    candles.push_back(candle);
-                }
-                // Keep only last 1000 in memory
-                while candles.len() > 1000 {
-                    candles.pop_front();
-                }
-                debug!(symbol = %symbol_key, count = candles.len(), "Loaded {} historical candles into memory buffer", candles.len());
-            }
-            Err(e) => {
-                warn!(coin = %coin, interval = %interval, error = %e, "Failed to fetch historical candles for {}/{}", coin, interval);
-            }
-        }
-
         // Add to pending subscriptions
         {
             let mut pending = self.pending_subscriptions.write().await;
@@ -168,15 +208,136 @@ impl HyperliquidMarketDataProvider {
             }
         }
 
+        // The WebSocket only streams new candles; backfill history so the buffer isn't empty
+        self.backfill_candles(coin, interval).await;
+        self.freshness.watch(coin).await;
+
         // Try to subscribe if connected, otherwise it will be done on reconnect
         if self.client.is_connected().await {
-            self.subscribe_candle_internal(coin, interval).await
+            self.subscribe_live_channels(coin, interval).await
         } else {
             debug!(coin = %coin, interval = %interval, "Not connected yet, subscription queued for {}/{}", coin, interval);
             Ok(())
         }
     }
 
+    /// Subscribe to the candle, order book and trades channels for `coin`
+    async fn subscribe_live_channels(
+        &self,
+        coin: &str,
+        interval: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.subscribe_candle_internal(coin, interval).await?;
+        let task = self.clone_for_task();
+        task.subscribe_l2_book_internal(coin).await?;
+        task.subscribe_trades_internal(coin).await
+    }
+
+    /// Fetch recent candles over REST and store them in QuestDB, Redis and
+    /// the in-memory buffer; failures are logged, not returned
+    async fn backfill_candles(&self, coin: &str, interval: &str) {
+        let historical_count = config::get_historical_candle_count();
+        debug!(coin = %coin, interval = %interval, count = historical_count, "Fetching {} historical candles for {}/{}", historical_count, coin, interval);
+
+        let mut historical_candles = match self
+            .fetch_candle_snapshot(coin, interval, historical_count)
+            .await
+        {
+            Ok(candles) => candles,
+            Err(e) => {
+                warn!(coin = %coin, interval = %interval, error = %e, "Failed to fetch historical candles for {}/{}", coin, interval);
+                return;
+            }
+        };
+        debug!(coin = %coin, interval = %interval, count = historical_candles.len(), "Fetched {} historical candles for {}/{}", historical_candles.len(), coin, interval);
+
+        self.attach_funding_rates(coin, &mut historical_candles)
+            .await;
+
+        // Store in QuestDB if available
+        if let Some(ref db) = self.database {
+            if let Err(e) = db
+                .store_candles_batch(coin, interval, &historical_candles)
+                .await
+            {
+                warn!(coin = %coin, interval = %interval, error = %e, "Failed to store historical candles in QuestDB");
+            } else {
+                debug!(coin = %coin, interval = %interval, count = historical_candles.len(), "Stored {} historical candles in QuestDB", historical_candles.len());
+            }
+        }
+
+        // Cache in Redis if available
+        if let Some(ref cache) = self.cache {
+            if let Err(e) = cache
+                .store_candles(
+                    coin,
+                    interval,
+                    &historical_candles,
+                    config::get_candle_cache_ttl_seconds(),
+                )
+                .await
+            {
+                warn!(coin = %coin, interval = %interval, error = %e, "Failed to cache historical candles in Redis");
+            } else {
+                debug!(coin = %coin, interval = %interval, count = historical_candles.len(), "Cached {} historical candles in Redis", historical_candles.len());
+            }
+        }
+
+        // Always update in-memory buffer. Live candles that arrived while the
+        // snapshot was in flight go after the history with the same timestamp,
+        // so they win under `DropIfDuplicate`; synthetic ones give way to it.
+        let symbol_key = format!("{}_{}", coin, interval);
+        let (max_candles, eviction_policy) = buffer_settings(&self.config);
+        let mut candles_map = self.candles.write().await;
+        let candles = candles_map
+            .entry(symbol_key.clone())
+            .or_insert_with(VecDeque::new);
+        historical_candles.extend(candles.drain(..).filter(|c| !c.is_synthetic));
+        historical_candles.sort_by_key(|c| c.timestamp);
+        for candle in historical_candles {
+            eviction_policy.insert(candles, candle, max_candles);
+        }
+        record_buffer_size(&self.metrics, &symbol_key, candles.len());
+        debug!(symbol = %symbol_key, count = candles.len(), "Loaded {} historical candles into memory buffer", candles.len());
+    }
+
+    /// `candleSnapshot` request, retried up to [`SNAPSHOT_FETCH_ATTEMPTS`]
+    /// times with a doubling delay
+    async fn fetch_candle_snapshot(
+        &self,
+        coin: &str,
+        interval: &str,
+        count: usize,
+    ) -> Result<Vec<Candle>, Box<dyn std::error::Error + Send + Sync>> {
+        let metrics = self.metrics.get();
+        let mut delay = SNAPSHOT_RETRY_INITIAL_DELAY;
+        let mut attempt = 1;
+        loop {
+            if let Some(metrics) = metrics {
+                metrics.rest_candle_fetches_total.inc();
+            }
+            match self
+                .rest_client
+                .get_candle_snapshot(coin, interval, count)
+                .await
+            {
+                Ok(candles) => return Ok(candles),
+                Err(e) => {
+                    if let Some(metrics) = metrics {
+                        metrics.rest_candle_fetch_errors_total.inc();
+                    }
+                    if attempt >= SNAPSHOT_FETCH_ATTEMPTS {
+                        return Err(e);
+                    }
+                    debug!(coin = %coin, interval = %interval, attempt, error = %e, "Candle snapshot attempt {} failed, retrying in {:?}", attempt, delay);
+                }
+            }
+            sleep(delay).await;
+            delay *= 2;
+            attempt += 1;
+        }
+    }
+
     async fn subscribe_candle_internal(
         &self,
         coin: &str,
@@ -272,6 +433,70 @@ impl HyperliquidMarketDataProvider {
         self.client.clone()
     }
 
+    pub(crate) fn metrics(&self) -> Option<&Arc<Metrics>> {
+        self.metrics.get()
+    }
+
+    /// When each subscribed symbol last received a live candle
+    pub fn freshness(&self) -> &DataFreshnessMonitor {
+        &self.freshness
+    }
+
+    /// How long a symbol may go without a live candle before its feed
+    /// counts as stale
+    pub fn max_candle_age(&self) -> Duration {
+        let max_candle_age_secs = self
+            .config
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .max_candle_age_secs;
+        match max_candle_age_secs {
+            Some(secs) => Duration::from_secs(secs),
+            None => {
+                let minutes = interval_minutes(self.get_primary_interval()).unwrap_or(1);
+                default_max_candle_age(u64::from(minutes) * 60)
+            }
+        }
+    }
+
+    /// Unsubscribe from and re-subscribe to every candle channel of `coin`.
+    ///
+    /// Does nothing while disconnected, since reconnecting re-subscribes to
+    /// everything anyway.
+    pub async fn resubscribe(
+        &self,
+        coin: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if !self.client.is_connected().await {
+            return Ok(());
+        }
+        let intervals: Vec<String> = self
+            .pending_subscriptions
+            .read()
+            .await
+            .iter()
+            .filter(|(pending_coin, _)| pending_coin == coin)
+            .map(|(_, interval)| interval.clone())
+            .collect();
+        for interval in intervals {
+            let request = RequestMessage::Unsubscribe {
+                subscription: Subscription::candle(coin, &interval),
+            };
+            let json = serde_json::to_string(&request).map_err(|e| {
+                Box::new(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    e.to_string(),
+                )) as Box<dyn std::error::Error + Send + Sync>
+            })?;
+            self.client.send_text(json).await?;
+            self.subscriptions
+                .remove(&SubscriptionKey::candle(coin, &interval))
+                .await;
+            self.subscribe_candle_internal(coin, &interval).await?;
+        }
+        Ok(())
+    }
+
     pub fn with_database(mut self, database: Arc<QuestDatabase>) -> Self {
         self.database = Some(database);
         self
@@ -281,6 +506,55 @@ impl HyperliquidMarketDataProvider {
         self.cache = Some(cache);
         self
     }
+
+    /// Replace the buffering settings and candle intervals.
+    ///
+    /// Buffer limits apply to candles received from then on; a smaller
+    /// `max_candles_per_symbol` trims each buffer on its next update.
+    pub fn with_config(mut self, config: ProviderConfig) -> Self {
+        self.candle_intervals = config.candle_intervals.clone();
+        *self.config.write().unwrap_or_else(|e| e.into_inner()) = config;
+        self
+    }
+
+    /// Report in-memory buffer sizes to `metrics`
+    pub fn with_metrics(self, metrics: Arc<Metrics>) -> Self {
+        let _ = self.metrics.set(metrics);
+        self
+    }
+
+    /// Enable or disable the `allMids` subscription (enabled by default).
+    ///
+    /// When enabled, mid prices for all coins arrive every ~3 seconds instead
+    /// of only on candle updates.
+    pub fn with_all_mids(self, enabled: bool) -> Self {
+        self.subscribe_all_mids.store(enabled, Ordering::Relaxed);
+        self
+    }
+}
+
+fn default_candle_intervals() -> Vec<String> {
+    vec![
+        "1m".to_string(),
+        "5m".to_string(),
+        "15m".to_string(),
+        "1h".to_string(),
+    ]
+}
+
+/// `(max_candles_per_symbol, eviction_policy)` from the shared config
+fn buffer_settings(config: &std::sync::RwLock<ProviderConfig>) -> (usize, EvictionPolicy) {
+    let config = config.read().unwrap_or_else(|e| e.into_inner());
+    (config.max_candles_per_symbol, config.eviction_policy)
+}
+
+fn record_buffer_size(metrics: &OnceLock<Arc<Metrics>>, symbol_key: &str, size: usize) {
+    if let Some(metrics) = metrics.get() {
+        metrics
+            .provider_candle_buffer_size
+            .with_label_values(&[symbol_key])
+            .set(size as f64);
+    }
 }
 
 #[derive(Clone)]
@@ -290,6 +564,8 @@ struct TaskProvider {
     subscriptions: Arc<SubscriptionManager>,
     candles: Arc<RwLock<HashMap<String, VecDeque<Candle>>>>,
     latest_prices: Arc<RwLock<HashMap<String, f64>>>,
+    latest_spreads: Arc<RwLock<HashMap<String, (f64, f64)>>>,
+    latest_trades: Arc<RwLock<HashMap<String, VecDeque<Trade>>>>,
     pending_subscriptions: Arc<RwLock<Vec<(String, String)>>>,
     #[allow(dead_code)] // Used for resubscription
     candle_intervals: Vec<String>,
@@ -297,6 +573,10 @@ struct TaskProvider {
     cache: Option<Arc<RedisCache>>,
     rest_client: Arc<HyperliquidRestClient>,
     funding_cache: Arc<RwLock<HashMap<String, FundingCacheEntry>>>,
+    subscribe_all_mids: Arc<AtomicBool>,
+    config: Arc<std::sync::RwLock<ProviderConfig>>,
+    metrics: Arc<OnceLock<Arc<Metrics>>>,
+    freshness: DataFreshnessMonitor,
 }
 
 impl TaskProvider {
@@ -311,8 +591,15 @@ impl TaskProvider {
                     }
                     ClientEvent::Connected => {
                         debug!("WebSocket connected, resubscribing...");
+                        // A new connection starts without any server-side subscriptions
+                        self.subscriptions.clear().await;
                         // Wait a moment for connection to stabilize
                         sleep(Duration::from_millis(500)).await;
+                        if self.subscribe_all_mids.load(Ordering::Relaxed) {
+                            if let Err(e) = self.subscribe_all_mids_internal().await {
+                                debug!(error = %e, "Failed to subscribe to allMids");
+                            }
+                        }
                         // Resubscribe to all pending subscriptions
                         let pending = self.pending_subscriptions.read().await.clone();
                         debug!(
@@ -326,6 +613,12 @@ impl TaskProvider {
                             } else {
                                 debug!(coin = %coin, interval = %interval, "Resubscribed to {} {}", coin, interval);
                             }
+                            if let Err(e) = self.subscribe_l2_book_internal(&coin).await {
+                                debug!(coin = %coin, error = %e, "Failed to resubscribe to {} order book", coin);
+                            }
+                            if let Err(e) = self.subscribe_trades_internal(&coin).await {
+                                debug!(coin = %coin, error = %e, "Failed to resubscribe to {} trades", coin);
+                            }
                         }
                     }
                     ClientEvent::Disconnected => {
@@ -377,6 +670,89 @@ impl TaskProvider {
         Ok(())
     }
 
+    async fn subscribe_all_mids_internal(
+        &self,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let key = SubscriptionKey::all_mids();
+
+        if self.subscriptions.contains(&key).await {
+            return Ok(()); // Already subscribed
+        }
+
+        let request = RequestMessage::Subscribe {
+            subscription: Subscription::all_mids(None),
+        };
+
+        let json = serde_json::to_string(&request).map_err(|e| {
+            Box::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                e.to_string(),
+            )) as Box<dyn std::error::Error + Send + Sync>
+        })?;
+
+        debug!(subscription = %json, "TaskProvider sending subscription");
+
+        self.client.send_text(json).await.map_err(|e| {
+            Box::new(std::io::Error::other(format!(
+                "WebSocket send error: {}",
+                e
+            ))) as Box<dyn std::error::Error + Send + Sync>
+        })?;
+
+        self.subscriptions.add(key).await;
+        Ok(())
+    }
+
+    /// Subscribe to a coin's order book; repeated calls for a coin are no-ops
+    async fn subscribe_l2_book_internal(
+        &self,
+        coin: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.send_subscription(SubscriptionKey::l2_book(coin), Subscription::l2_book(coin))
+            .await
+    }
+
+    /// Subscribe to a coin's public fills; repeated calls for a coin are no-ops
+    async fn subscribe_trades_internal(
+        &self,
+        coin: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.send_subscription(SubscriptionKey::trades(coin), Subscription::trades(coin))
+            .await
+    }
+
+    /// Send `subscription` unless `key` is already subscribed
+    async fn send_subscription(
+        &self,
+        key: SubscriptionKey,
+        subscription: Subscription,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if self.subscriptions.contains(&key).await {
+            return Ok(()); // Already subscribed
+        }
+
+        let request = RequestMessage::Subscribe { subscription };
+
+        let json = serde_json::to_string(&request).map_err(|e| {
+            Box::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                e.to_string(),
+            )) as Box<dyn std::error::Error + Send + Sync>
+        })?;
+
+        debug!(subscription = %json, "TaskProvider sending subscription");
+
+        self.client.send_text(json).await.map_err(|e| {
+            Box::new(std::io::Error::other(format!(
+                "WebSocket send error: {}",
+                e
+            ))) as Box<dyn std::error::Error + Send + Sync>
+        })?;
+
+        self.subscriptions.add(key).await;
+        Ok(())
+    }
+
     async fn process_message(
         &self,
         text: &str,
@@ -452,19 +828,33 @@ impl TaskProvider {
             }
             WebSocketMessage::AllMidsData(mids_data) => {
                 debug!(
-                    count = mids_data.data.len(),
+                    count = mids_data.data.mids.len(),
                     "Received allMids data: {} prices",
-                    mids_data.data.len()
+                    mids_data.data.mids.len()
                 );
-                for mid in mids_data.data {
-                    let price: f64 = mid.px.parse().unwrap_or(0.0);
-                    let mut prices = self.latest_prices.write().await;
-                    prices.insert(mid.coin, price);
+                let mut prices = self.latest_prices.write().await;
+                for (coin, px) in mids_data.data.mids {
+                    match px.parse::<f64>() {
+                        Ok(price) => {
+                            prices.insert(coin, price);
+                        }
+                        Err(e) => {
+                            debug!(coin = %coin, px = %px, error = %e, "Skipping unparsable mid price");
+                        }
+                    }
                 }
             }
+            WebSocketMessage::L2BookData(book) => {
+                self.process_l2_book_update(book.data).await;
+            }
+            WebSocketMessage::TradesData(trades) => {
+                self.process_trades(trades.data).await;
+            }
             WebSocketMessage::SubscriptionResponse(resp) => {
                 let sub_info = match &resp.data.subscription {
                     Subscription::Candle { coin, interval, .. } => format!("{}/{}", coin, interval),
+                    Subscription::L2Book { coin, .. } => format!("l2Book/{}", coin),
+                    Subscription::Trades { coin, .. } => format!("trades/{}", coin),
                     Subscription::AllMids { .. } => "allMids".to_string(),
                     Subscription::Notification { user, .. } => format!("notification/{}", user),
                 };
@@ -482,6 +872,72 @@ impl TaskProvider {
         Ok(())
     }
 
+    /// Keep the top of book for `update.coin` in memory and in Redis
+    async fn process_l2_book_update(&self, update: L2BookUpdate) {
+        let Some(top_of_book) = update.top_of_book() else {
+            debug!(coin = %update.coin, "Skipping order book update without both sides");
+            return;
+        };
+
+        self.latest_spreads
+            .write()
+            .await
+            .insert(update.coin.clone(), top_of_book);
+
+        if let Some(ref cache) = self.cache {
+            if let Err(e) = cache
+                .set_json(
+                    &latest_spread_key(&update.coin),
+                    &top_of_book,
+                    SPREAD_CACHE_TTL_SECONDS,
+                )
+                .await
+            {
+                debug!(coin = %update.coin, error = %e, "Failed to cache spread for {}", update.coin);
+            }
+        }
+    }
+
+    /// Append fills to each coin's recent trades and publish the updated
+    /// buy/sell ratio to Redis
+    async fn process_trades(&self, trades: Vec<WsTrade>) {
+        let mut ratios = HashMap::new();
+        {
+            let mut latest_trades = self.latest_trades.write().await;
+            for ws_trade in trades {
+                let Some(trade) = ws_trade.to_trade() else {
+                    debug!(coin = %ws_trade.coin, side = %ws_trade.side, px = %ws_trade.px, sz = %ws_trade.sz, "Skipping unparsable trade");
+                    continue;
+                };
+                let recent = latest_trades.entry(ws_trade.coin.clone()).or_default();
+                recent.push_back(trade);
+                while recent.len() > TRADES_PER_SYMBOL {
+                    recent.pop_front();
+                }
+                ratios.insert(ws_trade.coin, buy_sell_ratio(recent.iter()));
+            }
+        }
+
+        let Some(ref cache) = self.cache else {
+            return;
+        };
+        for (coin, ratio) in ratios {
+            let Some(ratio) = ratio else {
+                continue;
+            };
+            if let Err(e) = cache
+                .set_json(
+                    &latest_buy_sell_ratio_key(&coin),
+                    &ratio,
+                    BUY_SELL_RATIO_CACHE_TTL_SECONDS,
+                )
+                .await
+            {
+                debug!(coin = %coin, error = %e, "Failed to cache buy/sell ratio for {}", coin);
+            }
+        }
+    }
+
     async fn process_candle_update(
         &self,
         update: CandleUpdate,
@@ -547,55 +1003,81 @@ impl TaskProvider {
             }
         }
 
-        // Update in-memory buffer
+        // Update in-memory buffer, interpolating candles missed during a brief outage
         let symbol_key = format!("{}_{}", coin, interval);
+        let (max_candles, eviction_policy) = buffer_settings(&self.config);
         let mut candles_map = self.candles.write().await;
         let candles = candles_map
             .entry(symbol_key.clone())
             .or_insert_with(VecDeque::new);
-
-        // Remove any existing candle with the same timestamp (update existing candle)
-        candles.retain(|c| c.timestamp != timestamp);
-        candles.push_back(candle.clone());
-
-        // Keep only last 1000 candles per symbol
-        while candles.len() > 1000 {
-            candles.pop_front();
+        let synthetic = self.gap_fill(candles.back(), &candle, interval);
+        if !synthetic.is_empty() {
+            debug!(symbol = %symbol_key, count = synthetic.len(), "Interpolated {} candles over a gap in {}", synthetic.len(), symbol_key);
+            if let Some(metrics) = self.metrics.get() {
+                metrics.candles_synthetic_total.inc_by(synthetic.len() as f64);
+            }
+        }
+        for synthetic_candle in synthetic {
+            eviction_policy.insert(candles, synthetic_candle, max_candles);
         }
+        eviction_policy.insert(candles, candle.clone(), max_candles);
+        record_buffer_size(&self.metrics, &symbol_key, candles.len());
 
         debug!(symbol = %symbol_key, count = candles.len(), "Stored candle for {}: total candles = {}", symbol_key, candles.len());
 
-        // Update Redis cache - get current cached candles, add new one, and update cache
+        // Write through to Redis; the sorted set replaces a candle with the same timestamp
         if let Some(ref cache) = self.cache {
-            if let Ok(Some(mut cached_candles)) = cache.get_cached_candles(coin, interval).await {
-                // Remove duplicate timestamp
-                cached_candles.retain(|c| c.timestamp != timestamp);
-                cached_candles.push(candle.clone());
-                // Keep only last 200 in cache
-                if cached_candles.len() > 200 {
-                    cached_candles.remove(0);
-                }
-                // Update cache
-                if let Err(e) = cache.cache_candles(coin, interval, &cached_candles).await {
-                    warn!(coin = %coin, interval = %interval, error = %e, "Failed to update Redis cache");
-                }
-            } else {
-                // Cache miss, just cache this single candle
-                if let Err(e) = cache
-                    .cache_candles(coin, interval, std::slice::from_ref(&candle))
-                    .await
-                {
-                    warn!(coin = %coin, interval = %interval, error = %e, "Failed to cache candle in Redis");
-                }
+            if let Err(e) = cache
+                .store_candles(
+                    coin,
+                    interval,
+                    std::slice::from_ref(&candle),
+                    config::get_candle_cache_ttl_seconds(),
+                )
+                .await
+            {
+                warn!(coin = %coin, interval = %interval, error = %e, "Failed to cache candle in Redis");
             }
         }
 
+        self.freshness.record_candle(coin).await;
+        if let Some(metrics) = self.metrics.get() {
+            metrics
+                .candle_last_received_timestamp_seconds
+                .with_label_values(&[coin.as_str(), interval.as_str()])
+                .set(Utc::now().timestamp() as f64);
+        }
+
         let mut prices = self.latest_prices.write().await;
         prices.insert(coin.clone(), close);
 
         Ok(())
     }
 
+    /// Synthetic candles for the intervals missing between the last buffered
+    /// candle and `candle`, up to the configured `max_gap_fill_candles`
+    fn gap_fill(&self, last: Option<&Candle>, candle: &Candle, interval: &str) -> Vec<Candle> {
+        let (Some(last), Some(minutes)) = (last, interval_minutes(interval)) else {
+            return Vec::new();
+        };
+        if last.timestamp >= candle.timestamp {
+            return Vec::new();
+        }
+        let max_gap_fill_candles = self
+            .config
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .max_gap_fill_candles;
+        fill_candle_gaps_up_to(
+            vec![last.clone(), candle.clone()],
+            u64::from(minutes) * 60,
+            max_gap_fill_candles,
+        )
+        .into_iter()
+        .filter(|c| c.is_synthetic)
+        .collect()
+    }
+
     async fn attach_live_funding_rate(&self, coin: &str, candle: &mut Candle) {
         if candle.funding_rate.is_some() {
             return;
@@ -651,19 +1133,128 @@ impl MarketDataProvider for HyperliquidMarketDataProvider {
         symbol: &str,
         limit: usize,
     ) -> Result<Vec<Candle>, Box<dyn std::error::Error + Send + Sync>> {
-        let interval = self.get_primary_interval();
+        self.get_interval_candles(symbol, self.get_primary_interval(), limit)
+            .await
+    }
+
+    async fn get_candles_by_interval(
+        &self,
+        symbol: &str,
+        limit: usize,
+    ) -> Result<HashMap<String, Vec<Candle>>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut candles_by_interval = HashMap::new();
+        for interval in &self.candle_intervals {
+            let candles = self.get_interval_candles(symbol, interval, limit).await?;
+            if !candles.is_empty() {
+                candles_by_interval.insert(interval.clone(), candles);
+            }
+        }
+        Ok(candles_by_interval)
+    }
+
+    async fn get_latest_price(
+        &self,
+        symbol: &str,
+    ) -> Result<f64, Box<dyn std::error::Error + Send + Sync>> {
+        // Kept fresh by allMids (~3s) when enabled, otherwise by candle closes
+        let prices = self.latest_prices.read().await;
+        if let Some(&price) = prices.get(symbol) {
+            Ok(price)
+        } else {
+            drop(prices); // Release lock before subscribing
+            // Subscribe to get price updates
+            if let Err(e) = self
+                .subscribe_candle(symbol, self.get_primary_interval())
+                .await
+            {
+                error!(symbol = %symbol, error = %e, "Failed to subscribe to {}", symbol);
+            }
+            // Wait a bit for price to arrive
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            let prices = self.latest_prices.read().await;
+            Ok(prices.get(symbol).copied().unwrap_or(0.0))
+        }
+    }
+
+    async fn subscribe(
+        &self,
+        symbol: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        // Subscribe to all intervals for this symbol
+        for interval in &self.candle_intervals {
+            if let Err(e) = self.subscribe_candle(symbol, interval).await {
+                error!(symbol = %symbol, interval = %interval, error = %e, "Failed to subscribe to {} {}", symbol, interval);
+                // Continue with other intervals even if one fails
+            }
+        }
+        Ok(())
+    }
+
+    async fn get_spread(&self, symbol: &str) -> Option<(f64, f64)> {
+        if let Some(&top_of_book) = self.latest_spreads.read().await.get(symbol) {
+            return Some(top_of_book);
+        }
+
+        // Jobs run in another process than the WebSocket service; read what it cached
+        let cache = self.cache.as_ref()?;
+        match cache.get_json(&latest_spread_key(symbol)).await {
+            Ok(top_of_book) => top_of_book,
+            Err(e) => {
+                warn!(symbol = %symbol, error = %e, "Failed to read spread from Redis");
+                None
+            }
+        }
+    }
+
+    async fn get_buy_sell_ratio(&self, symbol: &str) -> Option<f64> {
+        if let Some(trades) = self.latest_trades.read().await.get(symbol) {
+            return buy_sell_ratio(trades.iter());
+        }
+
+        // Jobs run in another process than the WebSocket service; read what it cached
+        let cache = self.cache.as_ref()?;
+        match cache.get_json(&latest_buy_sell_ratio_key(symbol)).await {
+            Ok(ratio) => ratio,
+            Err(e) => {
+                warn!(symbol = %symbol, error = %e, "Failed to read buy/sell ratio from Redis");
+                None
+            }
+        }
+    }
+}
+
+impl HyperliquidMarketDataProvider {
+    /// Most recent trades held in memory for `symbol`, oldest first
+    pub async fn recent_trades(&self, symbol: &str) -> Vec<Trade> {
+        self.latest_trades
+            .read()
+            .await
+            .get(symbol)
+            .map(|trades| trades.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Candles for one interval: Redis cache, then QuestDB, then the in-memory buffer
+    async fn get_interval_candles(
+        &self,
+        symbol: &str,
+        interval: &str,
+        limit: usize,
+    ) -> Result<Vec<Candle>, Box<dyn std::error::Error + Send + Sync>> {
         let symbol_key = format!("{}_{}", symbol, interval);
 
-        // Try Redis cache first
+        // Try Redis cache first; a partial window falls through so QuestDB can refill it
+        let mut partial_cache = Vec::new();
         if let Some(ref cache) = self.cache {
-            if let Ok(Some(mut cached_candles)) = cache.get_cached_candles(symbol, interval).await {
-                cached_candles.sort_by_key(|c| c.timestamp);
-                if cached_candles.len() > limit {
-                    let start = cached_candles.len() - limit;
-                    cached_candles = cached_candles[start..].to_vec();
+            match cache.get_candles(symbol, interval, limit).await {
+                Ok(cached_candles) if cached_candles.len() >= limit => {
+                    debug!(symbol = %symbol_key, count = cached_candles.len(), "get_candles for {}: found {} candles in Redis cache", symbol_key, cached_candles.len());
+                    return Ok(cached_candles);
+                }
+                Ok(cached_candles) => partial_cache = cached_candles,
+                Err(e) => {
+                    warn!(symbol = %symbol, interval = %interval, error = %e, "Failed to read candles from Redis");
                 }
-                debug!(symbol = %symbol_key, count = cached_candles.len(), "get_candles for {}: found {} candles in Redis cache", symbol_key, cached_candles.len());
-                return Ok(cached_candles);
             }
         }
 
@@ -676,7 +1267,14 @@ impl MarketDataProvider for HyperliquidMarketDataProvider {
 
                     // Update Redis cache with these candles
                     if let Some(ref cache) = self.cache {
-                        let _ = cache.cache_candles(symbol, interval, &db_candles).await;
+                        let _ = cache
+                            .store_candles(
+                                symbol,
+                                interval,
+                                &db_candles,
+                                config::get_candle_cache_ttl_seconds(),
+                            )
+                            .await;
                     }
 
                     return Ok(db_candles);
@@ -687,6 +1285,12 @@ impl MarketDataProvider for HyperliquidMarketDataProvider {
             }
         }
 
+        // Without QuestDB a partial cache window still beats nothing
+        if !partial_cache.is_empty() {
+            debug!(symbol = %symbol_key, count = partial_cache.len(), "get_candles for {}: using {} candles from Redis cache", symbol_key, partial_cache.len());
+            return Ok(partial_cache);
+        }
+
         // Fallback to in-memory buffer
         let candles_map = self.candles.read().await;
         if let Some(candles) = candles_map.get(&symbol_key) {
@@ -713,44 +1317,6 @@ impl MarketDataProvider for HyperliquidMarketDataProvider {
         }
     }
 
-    async fn get_latest_price(
-        &self,
-        symbol: &str,
-    ) -> Result<f64, Box<dyn std::error::Error + Send + Sync>> {
-        let prices = self.latest_prices.read().await;
-        if let Some(&price) = prices.get(symbol) {
-            Ok(price)
-        } else {
-            // Subscribe to get price updates
-            if let Err(e) = self
-                .subscribe_candle(symbol, self.get_primary_interval())
-                .await
-            {
-                error!(symbol = %symbol, error = %e, "Failed to subscribe to {}", symbol);
-            }
-            // Wait a bit for price to arrive
-            tokio::time::sleep(Duration::from_millis(500)).await;
-            let prices = self.latest_prices.read().await;
-            Ok(prices.get(symbol).copied().unwrap_or(0.0))
-        }
-    }
-
-    async fn subscribe(
-        &self,
-        symbol: &str,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        // Subscribe to all intervals for this symbol
-        for interval in &self.candle_intervals {
-            if let Err(e) = self.subscribe_candle(symbol, interval).await {
-                error!(symbol = %symbol, interval = %interval, error = %e, "Failed to subscribe to {} {}", symbol, interval);
-                // Continue with other intervals even if one fails
-            }
-        }
-        Ok(())
-    }
-}
-
-impl HyperliquidMarketDataProvider {
     /// Subscribe to a symbol for all configured intervals (public wrapper)
     pub async fn subscribe(
         &self,
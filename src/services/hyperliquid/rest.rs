@@ -71,7 +71,7 @@ impl HyperliquidRestClient {
         }
     }
 
-    /// Fetch historical candles for a symbol and interval
+    /// Fetch the most recent candles for a symbol and interval via `candleSnapshot`
     ///
     /// # Arguments
     /// * `coin` - The coin symbol (e.g., "BTC")
@@ -80,7 +80,7 @@ impl HyperliquidRestClient {
     ///
     /// # Returns
     /// Vector of Candle objects sorted by timestamp (oldest first)
-    pub async fn fetch_historical_candles(
+    pub async fn get_candle_snapshot(
         &self,
         coin: &str,
         interval: &str,
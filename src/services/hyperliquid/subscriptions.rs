@@ -19,6 +19,22 @@ impl SubscriptionKey {
         }
     }
 
+    pub fn l2_book(coin: &str) -> Self {
+        Self {
+            sub_type: "l2Book".to_string(),
+            coin: Some(coin.to_string()),
+            interval: None,
+        }
+    }
+
+    pub fn trades(coin: &str) -> Self {
+        Self {
+            sub_type: "trades".to_string(),
+            coin: Some(coin.to_string()),
+            interval: None,
+        }
+    }
+
     pub fn all_mids() -> Self {
         Self {
             sub_type: "allMids".to_string(),
@@ -54,6 +70,11 @@ impl SubscriptionManager {
         active.contains(key)
     }
 
+    pub async fn clear(&self) {
+        let mut active = self.active.write().await;
+        active.clear();
+    }
+
     pub async fn is_empty(&self) -> bool {
         let active = self.active.read().await;
         active.is_empty()
@@ -1,6 +1,7 @@
 //! Market data provider interface for future data source integration.
 
 use crate::models::indicators::Candle;
+use std::collections::HashMap;
 
 #[async_trait::async_trait]
 pub trait MarketDataProvider: Send + Sync {
@@ -11,6 +12,17 @@ pub trait MarketDataProvider: Send + Sync {
         limit: usize,
     ) -> Result<Vec<Candle>, Box<dyn std::error::Error + Send + Sync>>;
 
+    /// Get historical candles for every interval the provider tracks, keyed by
+    /// interval (e.g. "1m", "1h"). Providers with a single series return an
+    /// empty map.
+    async fn get_candles_by_interval(
+        &self,
+        _symbol: &str,
+        _limit: usize,
+    ) -> Result<HashMap<String, Vec<Candle>>, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(HashMap::new())
+    }
+
     /// Get the latest price for a symbol
     async fn get_latest_price(
         &self,
@@ -19,6 +31,17 @@ pub trait MarketDataProvider: Send + Sync {
 
     async fn subscribe(&self, symbol: &str)
         -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Latest `(best_bid, best_ask)` for a symbol, if the provider tracks the order book
+    async fn get_spread(&self, _symbol: &str) -> Option<(f64, f64)> {
+        None
+    }
+
+    /// Buy/sell volume ratio over a symbol's recent trades, if the provider
+    /// tracks the trade feed
+    async fn get_buy_sell_ratio(&self, _symbol: &str) -> Option<f64> {
+        None
+    }
 }
 
 pub struct PlaceholderMarketDataProvider;
@@ -47,3 +70,128 @@ impl MarketDataProvider for PlaceholderMarketDataProvider {
         Ok(())
     }
 }
+
+/// In-memory provider with pre-loaded candles and prices, for tests that
+/// don't exercise the Hyperliquid transport layer.
+#[derive(Debug, Clone, Default)]
+pub struct MockMarketDataProvider {
+    candles: HashMap<String, Vec<Candle>>,
+    interval_candles: HashMap<String, HashMap<String, Vec<Candle>>>,
+    prices: HashMap<String, f64>,
+    spreads: HashMap<String, (f64, f64)>,
+    buy_sell_ratios: HashMap<String, f64>,
+}
+
+impl MockMarketDataProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pre-load candles for a symbol (oldest first)
+    pub fn with_candles(mut self, symbol: &str, candles: Vec<Candle>) -> Self {
+        self.candles.insert(symbol.to_string(), candles);
+        self
+    }
+
+    /// Pre-load candles for one interval of a symbol (oldest first)
+    pub fn with_interval_candles(
+        mut self,
+        symbol: &str,
+        interval: &str,
+        candles: Vec<Candle>,
+    ) -> Self {
+        self.interval_candles
+            .entry(symbol.to_string())
+            .or_default()
+            .insert(interval.to_string(), candles);
+        self
+    }
+
+    /// Pre-load the latest price for a symbol
+    pub fn with_price(mut self, symbol: &str, price: f64) -> Self {
+        self.prices.insert(symbol.to_string(), price);
+        self
+    }
+
+    /// Pre-load the top of book `(best_bid, best_ask)` for a symbol
+    pub fn with_spread(mut self, symbol: &str, best_bid: f64, best_ask: f64) -> Self {
+        self.spreads
+            .insert(symbol.to_string(), (best_bid, best_ask));
+        self
+    }
+
+    /// Pre-load the buy/sell volume ratio for a symbol
+    pub fn with_buy_sell_ratio(mut self, symbol: &str, ratio: f64) -> Self {
+        self.buy_sell_ratios.insert(symbol.to_string(), ratio);
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl MarketDataProvider for MockMarketDataProvider {
+    async fn get_candles(
+        &self,
+        symbol: &str,
+        limit: usize,
+    ) -> Result<Vec<Candle>, Box<dyn std::error::Error + Send + Sync>> {
+        let candles = match self.candles.get(symbol) {
+            Some(candles) => candles,
+            None => return Ok(Vec::new()),
+        };
+        let start = candles.len().saturating_sub(limit);
+        Ok(candles[start..].to_vec())
+    }
+
+    async fn get_candles_by_interval(
+        &self,
+        symbol: &str,
+        limit: usize,
+    ) -> Result<HashMap<String, Vec<Candle>>, Box<dyn std::error::Error + Send + Sync>> {
+        let Some(intervals) = self.interval_candles.get(symbol) else {
+            return Ok(HashMap::new());
+        };
+        Ok(intervals
+            .iter()
+            .map(|(interval, candles)| {
+                let start = candles.len().saturating_sub(limit);
+                (interval.clone(), candles[start..].to_vec())
+            })
+            .collect())
+    }
+
+    async fn get_latest_price(
+        &self,
+        symbol: &str,
+    ) -> Result<f64, Box<dyn std::error::Error + Send + Sync>> {
+        self.prices
+            .get(symbol)
+            .copied()
+            .or_else(|| {
+                self.candles
+                    .get(symbol)
+                    .and_then(|candles| candles.last())
+                    .map(|candle| candle.close)
+            })
+            .ok_or_else(|| {
+                Box::new(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!("No price available for {}", symbol),
+                )) as Box<dyn std::error::Error + Send + Sync>
+            })
+    }
+
+    async fn subscribe(
+        &self,
+        _symbol: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        Ok(())
+    }
+
+    async fn get_spread(&self, symbol: &str) -> Option<(f64, f64)> {
+        self.spreads.get(symbol).copied()
+    }
+
+    async fn get_buy_sell_ratio(&self, symbol: &str) -> Option<f64> {
+        self.buy_sell_ratios.get(symbol).copied()
+    }
+}
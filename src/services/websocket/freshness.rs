@@ -0,0 +1,81 @@
+//! Data health for live candle feeds: detect symbols that stopped receiving
+//! candles while the connection itself looks healthy
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tokio::time::{Duration, Instant};
+
+/// How often the WebSocket service looks for stale symbols
+pub const DEFAULT_FRESHNESS_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Default staleness threshold for a feed of `interval_seconds` candles:
+/// two intervals without an update
+pub fn default_max_candle_age(interval_seconds: u64) -> Duration {
+    Duration::from_secs(interval_seconds.saturating_mul(2).max(1))
+}
+
+/// When each symbol last received a live candle.
+///
+/// Clones share the same timestamps, so the provider's message loop can
+/// record candles while the WebSocket service checks them.
+#[derive(Debug, Clone, Default)]
+pub struct DataFreshnessMonitor {
+    last_candle_received_at: Arc<RwLock<HashMap<String, Instant>>>,
+}
+
+impl DataFreshnessMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start tracking `symbol` from now, so a feed that never delivers a
+    /// candle is detected too. Symbols already tracked are left as they are.
+    pub async fn watch(&self, symbol: &str) {
+        self.last_candle_received_at
+            .write()
+            .await
+            .entry(symbol.to_string())
+            .or_insert_with(Instant::now);
+    }
+
+    /// Record a live candle for `symbol`
+    pub async fn record_candle(&self, symbol: &str) {
+        self.last_candle_received_at
+            .write()
+            .await
+            .insert(symbol.to_string(), Instant::now());
+    }
+
+    /// When `symbol` last received a candle, or started being tracked
+    pub async fn last_candle_received_at(&self, symbol: &str) -> Option<Instant> {
+        self.last_candle_received_at
+            .read()
+            .await
+            .get(symbol)
+            .copied()
+    }
+
+    /// Symbols without a candle for longer than `max_candle_age`, with how
+    /// long each has been silent, sorted by symbol.
+    ///
+    /// Reported symbols are reset to now, giving a re-subscription another
+    /// `max_candle_age` to recover before they are reported again.
+    pub async fn take_stale_symbols(&self, max_candle_age: Duration) -> Vec<(String, Duration)> {
+        let now = Instant::now();
+        let mut last_candle_received_at = self.last_candle_received_at.write().await;
+        let mut stale: Vec<(String, Duration)> = last_candle_received_at
+            .iter_mut()
+            .filter_map(|(symbol, received_at)| {
+                let age = now.duration_since(*received_at);
+                if age <= max_candle_age {
+                    return None;
+                }
+                *received_at = now;
+                Some((symbol.clone(), age))
+            })
+            .collect();
+        stale.sort_by(|a, b| a.0.cmp(&b.0));
+        stale
+    }
+}
@@ -1,11 +1,58 @@
 //! WebSocket service for maintaining long-lived connection to market data provider
 
-use crate::services::hyperliquid::HyperliquidMarketDataProvider;
+pub mod freshness;
+pub mod reconnect;
+
+use crate::cache::redis::WEBSOCKET_HEARTBEAT_KEY;
+use crate::cache::RedisCache;
+use crate::error::KryptexError;
+use crate::services::hyperliquid::{HyperliquidMarketDataProvider, DEFAULT_CONNECTION_POLL_INTERVAL};
+use chrono::{DateTime, Utc};
+use freshness::DEFAULT_FRESHNESS_CHECK_INTERVAL;
+use reconnect::ConnectionConfig;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tokio::time::Duration;
 use tracing::{info, warn};
 
+/// Heartbeats that can be missed before the last one expires
+const MISSED_HEARTBEATS: u64 = 3;
+
+/// Connection state the WebSocket service publishes to Redis, so API servers
+/// in other processes can report it on `/health`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebSocketHeartbeat {
+    pub connected: bool,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl WebSocketHeartbeat {
+    pub fn new(connected: bool) -> Self {
+        Self {
+            connected,
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Seconds a heartbeat sent every `interval` is kept, so it expires once
+    /// the service stops sending them
+    pub fn ttl_seconds(interval: Duration) -> u64 {
+        interval.as_secs().max(1) * MISSED_HEARTBEATS
+    }
+
+    /// Store this heartbeat under [`WEBSOCKET_HEARTBEAT_KEY`]
+    pub async fn publish(
+        &self,
+        cache: &RedisCache,
+        interval: Duration,
+    ) -> Result<(), KryptexError> {
+        cache
+            .set_json(WEBSOCKET_HEARTBEAT_KEY, self, Self::ttl_seconds(interval))
+            .await
+    }
+}
+
 /// WebSocket service that maintains a persistent connection to the market data provider
 /// 
 /// This service runs independently and maintains the WebSocket connection.
@@ -13,7 +60,10 @@ use tracing::{info, warn};
 /// Jobs read from the stored data and never create new connections.
 pub struct WebSocketService {
     provider: Arc<HyperliquidMarketDataProvider>,
+    cache: Option<Arc<RedisCache>>,
+    freshness_check_interval: Duration,
     handle: Arc<RwLock<Option<tokio::task::JoinHandle<()>>>>,
+    freshness_handle: Arc<RwLock<Option<tokio::task::JoinHandle<()>>>>,
 }
 
 impl WebSocketService {
@@ -26,22 +76,46 @@ impl WebSocketService {
         // so the connection is already being maintained
         Self {
             provider: Arc::new(provider),
+            cache: None,
+            freshness_check_interval: DEFAULT_FRESHNESS_CHECK_INTERVAL,
             handle: Arc::new(RwLock::new(None)),
+            freshness_handle: Arc::new(RwLock::new(None)),
         }
     }
 
+    /// Publish a [`WebSocketHeartbeat`] to `cache` every heartbeat interval
+    pub fn with_cache(mut self, cache: Arc<RedisCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Look for stale symbols every `interval` instead of
+    /// [`DEFAULT_FRESHNESS_CHECK_INTERVAL`]
+    pub fn with_freshness_check_interval(mut self, interval: Duration) -> Self {
+        self.freshness_check_interval = interval;
+        self
+    }
+
     /// Start the WebSocket service monitoring
     /// 
-    /// This monitors the connection health. The actual connection
-    /// is maintained by the provider's background tasks.
+    /// This monitors the connection health every heartbeat interval, and
+    /// data health every freshness check interval. The actual connection,
+    /// including heartbeats and reconnection backoff, is maintained by the
+    /// provider's background tasks. With a cache, each connection check is
+    /// published as a [`WebSocketHeartbeat`].
     pub async fn start(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let provider = self.provider.clone();
+        let cache = self.cache.clone();
         let handle_arc = self.handle.clone();
+        let heartbeat_interval = ConnectionConfig::from_env().heartbeat_interval();
 
         let handle = tokio::spawn(async move {
             // Wait for initial connection
             let client = provider.client();
-            if client.wait_for_connection(Duration::from_secs(10)).await {
+            let mut is_connected = client
+                .wait_for_connection(Duration::from_secs(10), DEFAULT_CONNECTION_POLL_INTERVAL)
+                .await;
+            if is_connected {
                 info!("WebSocket service: connection established");
             } else {
                 warn!("WebSocket service: connection timeout, background tasks will retry");
@@ -49,8 +123,16 @@ impl WebSocketService {
 
             // Monitor connection health periodically
             loop {
-                tokio::time::sleep(Duration::from_secs(60)).await;
-                let is_connected = client.is_connected().await;
+                if let Some(ref cache) = cache {
+                    if let Err(e) = WebSocketHeartbeat::new(is_connected)
+                        .publish(cache, heartbeat_interval)
+                        .await
+                    {
+                        warn!(error = %e, "WebSocket service: failed to publish heartbeat");
+                    }
+                }
+                tokio::time::sleep(heartbeat_interval).await;
+                is_connected = client.is_connected().await;
                 if !is_connected {
                     warn!("WebSocket service: connection lost, background tasks will reconnect");
                 }
@@ -62,11 +144,30 @@ impl WebSocketService {
             *h = Some(handle);
         }
 
+        let provider = self.provider.clone();
+        let freshness_check_interval = self.freshness_check_interval;
+        let freshness_handle = tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(freshness_check_interval).await;
+                check_data_freshness(&provider).await;
+            }
+        });
+        *self.freshness_handle.write().await = Some(freshness_handle);
+
         Ok(())
     }
 
+    /// Re-subscribe to every symbol that has gone longer than the provider's
+    /// `max_candle_age` without a live candle; returns those symbols
+    pub async fn check_data_freshness(&self) -> Vec<String> {
+        check_data_freshness(&self.provider).await
+    }
+
     /// Stop the WebSocket service
     pub async fn stop(&self) {
+        if let Some(h) = self.freshness_handle.write().await.take() {
+            h.abort();
+        }
         let mut handle = self.handle.write().await;
         if let Some(h) = handle.take() {
             h.abort();
@@ -85,6 +186,15 @@ impl WebSocketService {
             self.provider.subscribe(symbol).await
         }
 
+    /// Time since the provider's WebSocket connection was last established
+    pub async fn connection_age(&self) -> Option<Duration> {
+        self.provider
+            .client()
+            .connection_established_at()
+            .await
+            .map(|connected_at| connected_at.elapsed())
+    }
+
     /// Check if the service is running
     pub async fn is_running(&self) -> bool {
         let handle = self.handle.read().await;
@@ -92,3 +202,31 @@ impl WebSocketService {
     }
 }
 
+/// See [`WebSocketService::check_data_freshness`]
+async fn check_data_freshness(provider: &HyperliquidMarketDataProvider) -> Vec<String> {
+    let max_candle_age = provider.max_candle_age();
+    let stale = provider
+        .freshness()
+        .take_stale_symbols(max_candle_age)
+        .await;
+
+    let mut symbols = Vec::with_capacity(stale.len());
+    for (symbol, age) in stale {
+        warn!(
+            symbol = %symbol,
+            seconds_since_last_candle = age.as_secs(),
+            max_candle_age_secs = max_candle_age.as_secs(),
+            "WebSocket service: no candle for {} in {}s, re-subscribing",
+            symbol,
+            age.as_secs()
+        );
+        if let Some(metrics) = provider.metrics() {
+            metrics.candles_stale_total.inc();
+        }
+        if let Err(e) = provider.resubscribe(&symbol).await {
+            warn!(symbol = %symbol, error = %e, "WebSocket service: failed to re-subscribe to {}", symbol);
+        }
+        symbols.push(symbol);
+    }
+    symbols
+}
@@ -0,0 +1,162 @@
+//! Heartbeat and reconnection backoff for long-lived WebSocket connections
+
+use crate::config::{get_ws_heartbeat_interval_secs, get_ws_max_backoff_secs};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU32, Ordering};
+use tokio::time::Duration;
+
+pub const DEFAULT_HEARTBEAT_INTERVAL_SECS: u64 = 30;
+pub const DEFAULT_MAX_BACKOFF_SECS: u64 = 60;
+/// Consecutive unanswered pings after which the connection is dropped
+pub const MAX_MISSED_PONGS: u32 = 2;
+/// Delay before the first reconnection attempt
+pub const RECONNECT_INITIAL_DELAY: Duration = Duration::from_secs(1);
+/// Maximum fraction by which a reconnection delay is randomly shortened or stretched
+pub const RECONNECT_JITTER: f64 = 0.2;
+
+/// Heartbeat and reconnection settings for a WebSocket connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConnectionConfig {
+    /// Seconds between ping frames
+    pub heartbeat_interval_secs: u64,
+    /// Upper bound on the delay between reconnection attempts, in seconds
+    pub max_backoff_secs: u64,
+}
+
+impl ConnectionConfig {
+    /// Read `WS_HEARTBEAT_INTERVAL_SECS` and `WS_MAX_BACKOFF_SECS`
+    pub fn from_env() -> Self {
+        Self {
+            heartbeat_interval_secs: get_ws_heartbeat_interval_secs(),
+            max_backoff_secs: get_ws_max_backoff_secs(),
+        }
+    }
+
+    pub fn heartbeat_interval(&self) -> Duration {
+        Duration::from_secs(self.heartbeat_interval_secs.max(1))
+    }
+
+    pub fn max_backoff(&self) -> Duration {
+        Duration::from_secs(self.max_backoff_secs)
+    }
+}
+
+impl Default for ConnectionConfig {
+    fn default() -> Self {
+        Self {
+            heartbeat_interval_secs: DEFAULT_HEARTBEAT_INTERVAL_SECS,
+            max_backoff_secs: DEFAULT_MAX_BACKOFF_SECS,
+        }
+    }
+}
+
+/// Exponential backoff between reconnection attempts.
+///
+/// The delay starts at `initial` and doubles with every attempt up to `max`,
+/// then is randomly scaled by up to ±[`RECONNECT_JITTER`] so that many clients
+/// dropped at once do not reconnect in lockstep. Jitter never pushes a delay
+/// past `max`.
+#[derive(Debug, Clone)]
+pub struct ReconnectBackoff {
+    initial: Duration,
+    max: Duration,
+    attempts: u32,
+}
+
+impl ReconnectBackoff {
+    pub fn new(initial: Duration, max: Duration) -> Self {
+        Self {
+            initial,
+            max,
+            attempts: 0,
+        }
+    }
+
+    pub fn from_config(config: &ConnectionConfig) -> Self {
+        Self::new(RECONNECT_INITIAL_DELAY, config.max_backoff())
+    }
+
+    /// Delay before attempt `attempt` (counting from 0), without jitter
+    pub fn base_delay(&self, attempt: u32) -> Duration {
+        let factor = 2u32.checked_pow(attempt).unwrap_or(u32::MAX);
+        self.initial
+            .checked_mul(factor)
+            .unwrap_or(self.max)
+            .min(self.max)
+    }
+
+    /// Scale `delay` by `1 + RECONNECT_JITTER × sample`, where `sample` is in `[-1, 1]`
+    pub fn jittered(&self, delay: Duration, sample: f64) -> Duration {
+        let factor = 1.0 + RECONNECT_JITTER * sample.clamp(-1.0, 1.0);
+        delay.mul_f64(factor).min(self.max)
+    }
+
+    /// Delay before the next attempt; each call counts as one attempt
+    pub fn next_delay(&mut self) -> Duration {
+        let delay = self.jittered(self.base_delay(self.attempts), jitter_sample());
+        self.attempts = self.attempts.saturating_add(1);
+        delay
+    }
+
+    /// Attempts since the last reset
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// Start over from the initial delay, e.g. after a successful handshake
+    pub fn reset(&mut self) {
+        self.attempts = 0;
+    }
+}
+
+/// Counts pings that have not been answered by a pong.
+///
+/// Shared between the task sending pings and the task reading frames.
+#[derive(Debug)]
+pub struct HeartbeatMonitor {
+    unanswered: AtomicU32,
+    max_missed: u32,
+}
+
+impl HeartbeatMonitor {
+    pub fn new(max_missed: u32) -> Self {
+        Self {
+            unanswered: AtomicU32::new(0),
+            max_missed,
+        }
+    }
+
+    /// Record that a ping is about to be sent.
+    ///
+    /// Returns `false` when `max_missed` earlier pings in a row went
+    /// unanswered, meaning the connection should be dropped instead.
+    pub fn ping(&self) -> bool {
+        self.unanswered.fetch_add(1, Ordering::Relaxed) < self.max_missed
+    }
+
+    /// Record a pong; any pong answers every outstanding ping
+    pub fn pong(&self) {
+        self.unanswered.store(0, Ordering::Relaxed);
+    }
+
+    /// Pings sent since the last pong
+    pub fn unanswered(&self) -> u32 {
+        self.unanswered.load(Ordering::Relaxed)
+    }
+}
+
+impl Default for HeartbeatMonitor {
+    fn default() -> Self {
+        Self::new(MAX_MISSED_PONGS)
+    }
+}
+
+/// Uniform sample from `[-1, 1]`.
+///
+/// `RandomState` is randomly keyed per instance, which is enough to spread
+/// reconnects out without pulling in an RNG crate.
+fn jitter_sample() -> f64 {
+    let bits = RandomState::new().build_hasher().finish() >> 11;
+    bits as f64 / (1u64 << 53) as f64 * 2.0 - 1.0
+}
@@ -0,0 +1,241 @@
+//! Category weights derived from the indicators a strategy actually uses.
+
+use crate::config::CategoryWeights;
+use crate::indicators::error::IndicatorError;
+use crate::indicators::registry::IndicatorCategory;
+use crate::models::strategy::{IndicatorType, Rule, StrategyConfig};
+use std::collections::HashMap;
+
+const WEIGHT_SUM_TOLERANCE: f64 = 1e-6;
+
+impl IndicatorType {
+    /// Category this indicator contributes to
+    pub fn category(&self) -> IndicatorCategory {
+        match self {
+            IndicatorType::MACD
+            | IndicatorType::RSI
+            | IndicatorType::StochRsi
+            | IndicatorType::WilliamsR
+            | IndicatorType::CCI
+            | IndicatorType::MFI
+            | IndicatorType::CMF => IndicatorCategory::Momentum,
+            IndicatorType::EMA
+            | IndicatorType::SMA
+            | IndicatorType::SMA20
+            | IndicatorType::SMA50
+            | IndicatorType::SMA200
+            | IndicatorType::EmaRibbon
+            | IndicatorType::KAMA
+            | IndicatorType::SuperTrend
+            | IndicatorType::Ichimoku
+            | IndicatorType::ParabolicSar
+            | IndicatorType::Fibonacci
+            | IndicatorType::CandlestickPattern => IndicatorCategory::Trend,
+            IndicatorType::Bollinger
+            | IndicatorType::BollingerPctB
+            | IndicatorType::BollingerBandwidth
+            | IndicatorType::Keltner
+            | IndicatorType::Donchian
+            | IndicatorType::ATR
+            | IndicatorType::AtrPct => IndicatorCategory::Volatility,
+            IndicatorType::OBV
+            | IndicatorType::VolumeProfile
+            | IndicatorType::VolumeProfilePOC
+            | IndicatorType::VolumeProfileVAH
+            | IndicatorType::VolumeProfileVAL
+            | IndicatorType::VWAP
+            | IndicatorType::BuySellRatio
+            // Trading sessions matter for the liquidity they bring
+            | IndicatorType::TimeOfDay => IndicatorCategory::Volume,
+            IndicatorType::FundingRate | IndicatorType::OpenInterest | IndicatorType::Spread => {
+                IndicatorCategory::Perp
+            }
+        }
+    }
+}
+
+impl CategoryWeights {
+    /// Derive weights from a strategy's rules.
+    ///
+    /// If `config.category_weights` is set, those weights are used (keys are
+    /// category names, case-insensitive). Otherwise each category is weighted
+    /// by how many conditions reference it, so unused categories get zero.
+    /// The result is normalized to sum to 1.0; a strategy without conditions
+    /// falls back to the default weights.
+    pub fn from_strategy_config(config: &StrategyConfig) -> Self {
+        let mut weights = Self::zero();
+
+        if let Some(ref overrides) = config.category_weights {
+            for (name, weight) in overrides {
+                match name.to_lowercase().as_str() {
+                    "momentum" => weights.momentum = *weight,
+                    "trend" => weights.trend = *weight,
+                    "volatility" => weights.volatility = *weight,
+                    "volume" => weights.volume = *weight,
+                    "perp" => weights.perp = *weight,
+                    _ => {}
+                }
+            }
+        } else {
+            for rule in &config.rules {
+                count_categories(rule, &mut weights);
+            }
+        }
+
+        weights.scale()
+    }
+
+    /// Check that every weight is finite and non-negative and that they sum to 1.0.
+    ///
+    /// Weights built with a struct literal or deserialized from config skip
+    /// normalization, so callers that need valid weights should check here.
+    pub fn validate(&self) -> Result<(), IndicatorError> {
+        for (field, value) in self.fields() {
+            if !value.is_finite() || value < 0.0 {
+                return Err(IndicatorError::OutOfRange {
+                    field: field.to_string(),
+                    value,
+                    min: 0.0,
+                    max: 1.0,
+                });
+            }
+        }
+
+        let total: f64 = self.fields().iter().map(|(_, value)| value).sum();
+        if (total - 1.0).abs() > WEIGHT_SUM_TOLERANCE {
+            return Err(IndicatorError::ValidationError(format!(
+                "Category weights must sum to 1.0, got {}",
+                total
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Normalize the weights to sum to 1.0 without erroring.
+    ///
+    /// Negative weights are treated as zero; if nothing is left the default
+    /// weights are returned.
+    pub fn scale(&self) -> Self {
+        self.clone().normalized().unwrap_or_default()
+    }
+
+    fn fields(&self) -> [(&'static str, f64); 5] {
+        [
+            ("momentum", self.momentum),
+            ("trend", self.trend),
+            ("volatility", self.volatility),
+            ("volume", self.volume),
+            ("perp", self.perp),
+        ]
+    }
+
+    /// Score multiplier for each top-level rule, keyed by rule ID, under the
+    /// strategy's manual `category_weights`; `None` without them.
+    ///
+    /// A category's multiplier is its manual weight over its share of the
+    /// strategy's conditions, so weights matching the rules' own mix leave
+    /// the score unchanged. A rule with conditions in several categories
+    /// takes the mean of their multipliers.
+    pub fn rule_multipliers(config: &StrategyConfig) -> Option<HashMap<String, f64>> {
+        config.category_weights.as_ref()?;
+        let manual = Self::from_strategy_config(config);
+        let mut derived = Self::zero();
+        for rule in &config.rules {
+            count_categories(rule, &mut derived);
+        }
+        let derived = derived.normalized()?;
+
+        let multipliers = config
+            .rules
+            .iter()
+            .map(|rule| {
+                let mut categories = Vec::new();
+                collect_categories(rule, &mut categories);
+                let multiplier = if categories.is_empty() {
+                    1.0
+                } else {
+                    categories
+                        .iter()
+                        .map(|&category| manual.weight(category) / derived.weight(category))
+                        .sum::<f64>()
+                        / categories.len() as f64
+                };
+                (rule.id.clone(), multiplier)
+            })
+            .collect();
+        Some(multipliers)
+    }
+
+    fn weight(&self, category: IndicatorCategory) -> f64 {
+        match category {
+            IndicatorCategory::Momentum => self.momentum,
+            IndicatorCategory::Trend => self.trend,
+            IndicatorCategory::Volatility => self.volatility,
+            IndicatorCategory::Volume => self.volume,
+            IndicatorCategory::Perp => self.perp,
+        }
+    }
+
+    fn weight_mut(&mut self, category: IndicatorCategory) -> &mut f64 {
+        match category {
+            IndicatorCategory::Momentum => &mut self.momentum,
+            IndicatorCategory::Trend => &mut self.trend,
+            IndicatorCategory::Volatility => &mut self.volatility,
+            IndicatorCategory::Volume => &mut self.volume,
+            IndicatorCategory::Perp => &mut self.perp,
+        }
+    }
+
+    fn zero() -> Self {
+        Self {
+            momentum: 0.0,
+            trend: 0.0,
+            volatility: 0.0,
+            volume: 0.0,
+            perp: 0.0,
+        }
+    }
+
+    /// Scale weights to sum to 1.0 (negative weights are treated as zero).
+    /// Returns `None` when there is nothing to normalize.
+    fn normalized(mut self) -> Option<Self> {
+        self.momentum = self.momentum.max(0.0);
+        self.trend = self.trend.max(0.0);
+        self.volatility = self.volatility.max(0.0);
+        self.volume = self.volume.max(0.0);
+        self.perp = self.perp.max(0.0);
+
+        let total = self.momentum + self.trend + self.volatility + self.volume + self.perp;
+        if total <= f64::EPSILON {
+            return None;
+        }
+
+        self.momentum /= total;
+        self.trend /= total;
+        self.volatility /= total;
+        self.volume /= total;
+        self.perp /= total;
+        Some(self)
+    }
+}
+
+fn count_categories(rule: &Rule, weights: &mut CategoryWeights) {
+    let mut categories = Vec::new();
+    collect_categories(rule, &mut categories);
+    for category in categories {
+        *weights.weight_mut(category) += 1.0;
+    }
+}
+
+/// Category of every condition in `rule`, nested ones included
+fn collect_categories(rule: &Rule, categories: &mut Vec<IndicatorCategory>) {
+    if let Some(ref condition) = rule.condition {
+        categories.push(condition.indicator.category());
+    }
+    if let Some(ref children) = rule.children {
+        for child in children {
+            collect_categories(child, categories);
+        }
+    }
+}
@@ -0,0 +1,97 @@
+//! Clustering of recent signals for a symbol.
+//!
+//! Several signals firing for the same symbol within a short window, mostly in
+//! the same direction, point to the strategies agreeing on a regime change.
+//! The cluster is the signals within `window_seconds` of the newest one.
+
+use crate::models::signal::{SignalDirection, SignalOutput};
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+
+/// Window recent signals are clustered over
+pub const DEFAULT_CLUSTER_WINDOW_SECONDS: u64 = 900;
+/// Most recent signals per symbol read for clustering
+pub const DEFAULT_CLUSTER_SIGNAL_LIMIT: usize = 50;
+
+/// Consensus of the signals in a cluster
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ClusterAnalysis {
+    /// Most common direction in the cluster; `Neutral` when the most common
+    /// directions tie
+    pub cluster_direction: SignalDirection,
+    /// Fraction of the cluster's signals in `cluster_direction` (0.0-1.0)
+    pub agreement_ratio: f64,
+    /// Mean confidence of the signals in `cluster_direction`
+    pub avg_confidence: f64,
+    /// Signals in the cluster
+    pub signal_count: usize,
+    pub window_seconds: u64,
+}
+
+impl ClusterAnalysis {
+    fn empty(window_seconds: u64) -> Self {
+        Self {
+            cluster_direction: SignalDirection::Neutral,
+            agreement_ratio: 0.0,
+            avg_confidence: 0.0,
+            signal_count: 0,
+            window_seconds,
+        }
+    }
+}
+
+pub struct SignalCluster;
+
+impl SignalCluster {
+    /// Analyze the signals within `window_seconds` of the newest one.
+    ///
+    /// `signals` must be for a single symbol, in any order. A tie between the
+    /// most common directions yields `Neutral`.
+    pub fn analyze(signals: &[SignalOutput], window_seconds: u64) -> ClusterAnalysis {
+        let Some(newest) = signals.iter().map(|signal| signal.timestamp).max() else {
+            return ClusterAnalysis::empty(window_seconds);
+        };
+        let window_start = newest - Duration::seconds(window_seconds as i64);
+        let cluster: Vec<&SignalOutput> = signals
+            .iter()
+            .filter(|signal| signal.timestamp >= window_start)
+            .collect();
+
+        let count = |direction: SignalDirection| {
+            cluster
+                .iter()
+                .filter(|signal| signal.direction == direction)
+                .count()
+        };
+        let mut counts = [
+            (SignalDirection::Long, count(SignalDirection::Long)),
+            (SignalDirection::Short, count(SignalDirection::Short)),
+            (SignalDirection::Neutral, count(SignalDirection::Neutral)),
+        ];
+        counts.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+        let cluster_direction = if counts[0].1 == counts[1].1 {
+            SignalDirection::Neutral
+        } else {
+            counts[0].0
+        };
+
+        let agreeing: Vec<f64> = cluster
+            .iter()
+            .filter(|signal| signal.direction == cluster_direction)
+            .map(|signal| signal.confidence)
+            .collect();
+        let avg_confidence = if agreeing.is_empty() {
+            0.0
+        } else {
+            agreeing.iter().sum::<f64>() / agreeing.len() as f64
+        };
+
+        ClusterAnalysis {
+            cluster_direction,
+            agreement_ratio: agreeing.len() as f64 / cluster.len() as f64,
+            avg_confidence,
+            signal_count: cluster.len(),
+            window_seconds,
+        }
+    }
+}
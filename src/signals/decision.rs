@@ -1,6 +1,8 @@
 //! Signal direction decision logic
 
 use crate::models::signal::SignalDirection;
+use crate::models::strategy::SlTpModel;
+use crate::strategies::evaluator::IndicatorValues;
 
 pub struct DirectionThresholds;
 
@@ -28,13 +30,88 @@ impl DirectionThresholds {
 pub struct StopLossTakeProfit;
 
 impl StopLossTakeProfit {
+    /// ATR multiple the default stop loss is placed at
+    pub const SL_ATR_MULTIPLIER: f64 = 1.2;
+    /// ATR multiple the default take profit is placed at
+    pub const TP_ATR_MULTIPLIER: f64 = 2.0;
+
     /// Calculate SL and TP from ATR (SL = ATR * 1.2, TP = ATR * 2.0)
     pub fn calculate_from_atr(atr: f64, price: f64) -> (f64, f64) {
-        let sl_pct = (atr * 1.2 / price) * 100.0;
-        let tp_pct = (atr * 2.0 / price) * 100.0;
+        Self::calculate_from_atr_multipliers(
+            atr,
+            price,
+            Self::SL_ATR_MULTIPLIER,
+            Self::TP_ATR_MULTIPLIER,
+        )
+    }
+
+    /// Calculate SL and TP as percentages of `price` at the given ATR multiples
+    pub fn calculate_from_atr_multipliers(
+        atr: f64,
+        price: f64,
+        sl_multiplier: f64,
+        tp_multiplier: f64,
+    ) -> (f64, f64) {
+        let sl_pct = (atr * sl_multiplier / price) * 100.0;
+        let tp_pct = (atr * tp_multiplier / price) * 100.0;
         (sl_pct, tp_pct)
     }
 
+    /// `(sl_pct, tp_pct)` for a `direction` signal under `model`, as
+    /// percentages of `current_price`.
+    ///
+    /// A level that needs an indicator without a reading, or a non-positive
+    /// ATR, is 0.0, as is everything when `current_price` is not positive.
+    /// A Neutral signal has no side, so its Bollinger band SL is 0.0.
+    pub fn from_model(
+        model: &SlTpModel,
+        direction: SignalDirection,
+        indicator_values: &IndicatorValues,
+        current_price: f64,
+    ) -> (f64, f64) {
+        if current_price <= 0.0 {
+            return (0.0, 0.0);
+        }
+        let atr = indicator_values.atr_value.filter(|&atr| atr > 0.0);
+        let distance_pct = |level: f64| ((current_price - level).abs() / current_price) * 100.0;
+
+        match *model {
+            SlTpModel::AtrBased {
+                sl_multiplier,
+                tp_multiplier,
+            } => atr
+                .map(|atr| {
+                    Self::calculate_from_atr_multipliers(
+                        atr,
+                        current_price,
+                        sl_multiplier,
+                        tp_multiplier,
+                    )
+                })
+                .unwrap_or((0.0, 0.0)),
+            SlTpModel::FixedPct { sl_pct, tp_pct } => (sl_pct, tp_pct),
+            SlTpModel::RiskReward { sl_pct, rr_ratio } => (sl_pct, sl_pct * rr_ratio),
+            SlTpModel::BollingerBased { sl_at_band } => {
+                let tp_pct = indicator_values
+                    .bollinger_middle
+                    .map(distance_pct)
+                    .unwrap_or(0.0);
+                let sl_pct = if sl_at_band {
+                    let band = match direction {
+                        SignalDirection::Long => indicator_values.bollinger_lower,
+                        SignalDirection::Short => indicator_values.bollinger_upper,
+                        SignalDirection::Neutral => None,
+                    };
+                    band.map(distance_pct).unwrap_or(0.0)
+                } else {
+                    atr.map(|atr| Self::calculate_from_atr(atr, current_price).0)
+                        .unwrap_or(0.0)
+                };
+                (sl_pct, tp_pct)
+            }
+        }
+    }
+
     /// Calculate SL and TP for Long position
     pub fn calculate_long(atr: f64, price: f64) -> (f64, f64) {
         Self::calculate_from_atr(atr, price)
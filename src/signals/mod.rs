@@ -1,7 +1,11 @@
 //! Signal evaluation interfaces.
 
+pub mod categories;
+pub mod clustering;
 pub mod decision;
 pub mod engine;
+pub mod risk;
+pub mod scoring;
 
 pub use decision::*;
 pub use engine::*;
@@ -0,0 +1,234 @@
+//! Confidence penalty driven by the rolling Sharpe ratio of recent signals.
+//!
+//! Each stored long or short signal opens a paper trade at its price with its
+//! ATR-based stop loss and take profit. A later signal for the same symbol and
+//! strategy closes the trade once its price crosses either level, booking the
+//! stop loss as a loss of `recommended_sl_pct` and the take profit as a gain
+//! of `recommended_tp_pct`.
+
+use crate::models::signal::{SignalDirection, SignalOutput};
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+
+/// Closed trades kept for the rolling Sharpe ratio
+pub const DEFAULT_SHARPE_WINDOW: usize = 50;
+/// Closed trades needed before confidence is penalized
+pub const MIN_SHARPE_TRADES: usize = 10;
+/// Sharpe ratio at or above which confidence is left unchanged
+pub const SHARPE_TARGET: f64 = 1.0;
+
+/// Mean and variance of the last `capacity` values, updated in O(1) per value
+/// with Welford's online algorithm, run in reverse for evicted values
+#[derive(Debug, Clone)]
+pub struct RollingStats {
+    capacity: usize,
+    values: VecDeque<f64>,
+    mean: f64,
+    /// Sum of squared deviations from the mean
+    m2: f64,
+}
+
+impl RollingStats {
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            capacity,
+            values: VecDeque::with_capacity(capacity),
+            mean: 0.0,
+            m2: 0.0,
+        }
+    }
+
+    /// Add a value, evicting the oldest once the window is full
+    pub fn push(&mut self, value: f64) {
+        if self.values.len() == self.capacity {
+            if let Some(oldest) = self.values.pop_front() {
+                self.remove(oldest);
+            }
+        }
+        self.values.push_back(value);
+        let n = self.values.len() as f64;
+        let delta = value - self.mean;
+        self.mean += delta / n;
+        self.m2 += delta * (value - self.mean);
+    }
+
+    /// Undo the Welford update for `value`, already popped from `values`
+    fn remove(&mut self, value: f64) {
+        let n = self.values.len();
+        if n == 0 {
+            self.mean = 0.0;
+            self.m2 = 0.0;
+            return;
+        }
+        let delta = value - self.mean;
+        self.mean -= delta / n as f64;
+        // Rounding can leave a tiny negative remainder
+        self.m2 = (self.m2 - delta * (value - self.mean)).max(0.0);
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    /// Sample variance; 0.0 with fewer than two values
+    pub fn variance(&self) -> f64 {
+        match self.values.len() {
+            0 | 1 => 0.0,
+            n => self.m2 / (n - 1) as f64,
+        }
+    }
+
+    pub fn std_dev(&self) -> f64 {
+        self.variance().sqrt()
+    }
+}
+
+/// A paper trade opened on a stored signal
+#[derive(Debug, Clone, Copy)]
+struct PaperTrade {
+    direction: SignalDirection,
+    stop_loss_price: f64,
+    take_profit_price: f64,
+    sl_pct: f64,
+    tp_pct: f64,
+}
+
+impl PaperTrade {
+    /// Trade for an actionable signal, or `None` without a direction or SL/TP
+    fn open(signal: &SignalOutput) -> Option<Self> {
+        let (sl_pct, tp_pct) = (signal.recommended_sl_pct, signal.recommended_tp_pct);
+        if sl_pct <= 0.0 || tp_pct <= 0.0 || !signal.price.is_finite() {
+            return None;
+        }
+        let sl_offset = signal.price * sl_pct / 100.0;
+        let tp_offset = signal.price * tp_pct / 100.0;
+        let (stop_loss_price, take_profit_price) = match signal.direction {
+            SignalDirection::Long => (signal.price - sl_offset, signal.price + tp_offset),
+            SignalDirection::Short => (signal.price + sl_offset, signal.price - tp_offset),
+            SignalDirection::Neutral => return None,
+        };
+        Some(Self {
+            direction: signal.direction,
+            stop_loss_price,
+            take_profit_price,
+            sl_pct,
+            tp_pct,
+        })
+    }
+
+    /// P&L in percent if `price` has crossed the stop loss or take profit
+    fn close_at(&self, price: f64) -> Option<f64> {
+        let (stop_hit, target_hit) = match self.direction {
+            SignalDirection::Short => (
+                price >= self.stop_loss_price,
+                price <= self.take_profit_price,
+            ),
+            _ => (
+                price <= self.stop_loss_price,
+                price >= self.take_profit_price,
+            ),
+        };
+        if stop_hit {
+            Some(-self.sl_pct)
+        } else if target_hit {
+            Some(self.tp_pct)
+        } else {
+            None
+        }
+    }
+}
+
+/// Rolling Sharpe ratio of paper trades on stored signals, used to discount
+/// confidence while recent signals have performed poorly
+#[derive(Debug, Clone)]
+pub struct RollingSharpePenalty {
+    returns: RollingStats,
+    /// Open paper trade per (symbol, strategy_id)
+    open_trades: HashMap<(String, i64), PaperTrade>,
+}
+
+impl RollingSharpePenalty {
+    /// Track the last `window` closed trades
+    pub fn new(window: usize) -> Self {
+        Self {
+            returns: RollingStats::new(window),
+            open_trades: HashMap::new(),
+        }
+    }
+
+    /// Close the open trade for the signal's symbol and strategy if its price
+    /// crossed the stop loss or take profit, then open a new trade on the
+    /// signal when none is open
+    pub fn record_signal(&mut self, strategy_id: i64, signal: &SignalOutput) {
+        let key = (signal.symbol.clone(), strategy_id);
+        if let Some(pnl_pct) = self
+            .open_trades
+            .get(&key)
+            .and_then(|trade| trade.close_at(signal.price))
+        {
+            self.open_trades.remove(&key);
+            self.record_trade(pnl_pct);
+        }
+        if let Entry::Vacant(slot) = self.open_trades.entry(key) {
+            if let Some(trade) = PaperTrade::open(signal) {
+                slot.insert(trade);
+            }
+        }
+    }
+
+    /// Add a closed trade's P&L, in percent
+    pub fn record_trade(&mut self, pnl_pct: f64) {
+        if pnl_pct.is_finite() {
+            self.returns.push(pnl_pct);
+        }
+    }
+
+    /// Closed trades in the window
+    pub fn trade_count(&self) -> usize {
+        self.returns.len()
+    }
+
+    /// Mean trade P&L over its standard deviation, per trade (not annualized).
+    ///
+    /// `SHARPE_TARGET` until `MIN_SHARPE_TRADES` trades have closed, so new
+    /// deployments are not penalized. When every trade returned the same P&L,
+    /// `SHARPE_TARGET` if it was a gain and 0.0 otherwise.
+    pub fn current_sharpe(&self) -> f64 {
+        if self.returns.len() < MIN_SHARPE_TRADES {
+            return SHARPE_TARGET;
+        }
+        let std_dev = self.returns.std_dev();
+        if std_dev <= f64::EPSILON {
+            return if self.returns.mean() > 0.0 {
+                SHARPE_TARGET
+            } else {
+                0.0
+            };
+        }
+        self.returns.mean() / std_dev
+    }
+
+    /// Scale `confidence` by `sharpe` when it is below `SHARPE_TARGET`;
+    /// a Sharpe ratio at or below zero drops confidence to zero
+    pub fn penalize_confidence(confidence: f64, sharpe: f64) -> f64 {
+        if sharpe.is_nan() || sharpe >= SHARPE_TARGET {
+            return confidence;
+        }
+        confidence * (sharpe / SHARPE_TARGET).max(0.0)
+    }
+}
+
+impl Default for RollingSharpePenalty {
+    fn default() -> Self {
+        Self::new(DEFAULT_SHARPE_WINDOW)
+    }
+}
@@ -0,0 +1,23 @@
+//! Continuous scoring helpers that turn raw indicator readings into [-1, 1] scores.
+
+/// Relative deviation of open interest from its moving average, clamped to [-1, 1].
+///
+/// Positive when OI is above its average (new positions opening), negative when
+/// it is below. Returns 0.0 when the average is zero or not finite.
+pub fn normalize_open_interest(oi: f64, oi_ma: f64) -> f64 {
+    if !oi.is_finite() || !oi_ma.is_finite() || oi_ma.abs() < f64::EPSILON {
+        return 0.0;
+    }
+    ((oi - oi_ma) / oi_ma).clamp(-1.0, 1.0)
+}
+
+/// How strongly open interest confirms the direction of a price score.
+///
+/// Rising OI with price (either direction) confirms the move and returns a
+/// positive value; rising OI against the price direction returns a negative one.
+pub fn open_interest_confirmation(oi_score: f64, price_score: f64) -> f64 {
+    if price_score == 0.0 {
+        return 0.0;
+    }
+    oi_score * price_score.signum()
+}
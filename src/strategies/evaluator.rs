@@ -1,23 +1,141 @@
 //! Strategy evaluation engine that replaces hardcoded signal evaluation
 
-use crate::indicators::momentum::{macd, rsi};
-use crate::indicators::perp::{funding_rate, open_interest};
-use crate::indicators::trend::{ema, supertrend};
-use crate::indicators::volatility::{atr, bollinger};
+use crate::common::math;
+use crate::config::{
+    get_max_critical_candle_anomalies, CategoryWeights, get_strategy_timeout_seconds,
+    get_worker_evaluation_parallelism,
+};
+use crate::indicators::momentum::{cci, cmf, macd, mfi, rsi, stochastic_rsi, williams_r};
+use crate::indicators::perp::{funding_rate, open_interest, spread};
+use crate::indicators::structure::{candlestick, donchian, fibonacci, heikin_ashi, vwap};
+use crate::indicators::trend::{adx, ema, ichimoku, kama, parabolic_sar, supertrend};
+use crate::indicators::validation::validate_candle_series;
+use crate::indicators::volatility::{atr, atr_pct, bollinger, keltner};
 use crate::indicators::volume::{obv, volume_profile};
+use crate::metrics::Metrics;
 use crate::models::indicators::Candle;
 use crate::models::signal::{SignalDirection, SignalOutput, SignalReason};
 use crate::models::strategy::{
-    AggregationConfig, AggregationMethod, Comparison, Condition, IndicatorType, LogicalOperator,
-    Rule, RuleResult, RuleType, Strategy,
+    AggregationConfig, AggregationFilter, AggregationMethod, Comparison, Condition,
+    EvaluationTrace, IndicatorSnapshot, IndicatorType, LogicalOperator, Rule, RuleResult, RuleType,
+    Strategy, StrategyConfig,
 };
 use crate::signals::decision::StopLossTakeProfit;
-use chrono::Utc;
-use std::collections::VecDeque;
+use crate::signals::scoring::normalize_open_interest;
+use crate::strategies::multi_timeframe::MultiTimeframeEvaluator;
+use chrono::{DateTime, Timelike, Utc};
+use std::collections::{HashMap, VecDeque};
+use std::panic::resume_unwind;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinSet;
+use tokio::time::error::Elapsed;
+use tracing::warn;
 
-const MIN_CANDLES: usize = 50;
+pub(crate) const MIN_CANDLES: usize = 50;
 const VOLUME_PROFILE_LOOKBACK: usize = 240;
-const VOLUME_PROFILE_TICK: f64 = 10.0;
+const OI_MA_PERIOD: usize = 20;
+const DEFAULT_EMA_FAST_PERIOD: usize = 20;
+const DEFAULT_EMA_SLOW_PERIOD: usize = 50;
+
+/// SuperTrend settings as `(atr_period, multiplier)`, with the multiplier as
+/// its bit pattern so the pair can key a `HashMap`
+pub type SuperTrendKey = (usize, u64);
+
+const DEFAULT_SUPERTREND_KEY: SuperTrendKey = (
+    supertrend::SUPERTREND_DEFAULT_ATR_PERIOD,
+    supertrend::SUPERTREND_DEFAULT_MULTIPLIER.to_bits(),
+);
+
+/// SuperTrend settings for a condition, from its `atr_period` and
+/// `multiplier` params with defaults of 10 and 3.0
+pub fn supertrend_key(condition: &Condition) -> SuperTrendKey {
+    let atr_period = condition
+        .param_f64("atr_period")
+        .filter(|p| *p >= 1.0)
+        .map_or(supertrend::SUPERTREND_DEFAULT_ATR_PERIOD, |p| p as usize);
+    let multiplier = condition
+        .param_f64("multiplier")
+        .filter(|m| *m > 0.0 && m.is_finite())
+        .unwrap_or(supertrend::SUPERTREND_DEFAULT_MULTIPLIER);
+    (atr_period, multiplier.to_bits())
+}
+
+/// SMA periods read by `SMA20`, `SMA50` and `SMA200` conditions
+const STANDARD_SMA_PERIODS: [u32; 3] = [20, 50, 200];
+
+/// SMA period for a condition: fixed by `SMA20`, `SMA50` and `SMA200`, and
+/// read from the `period` param of an `SMA` condition with a default of 20
+pub fn sma_period(condition: &Condition) -> u32 {
+    match condition.indicator {
+        IndicatorType::SMA50 => 50,
+        IndicatorType::SMA200 => 200,
+        IndicatorType::SMA => condition
+            .param_f64("period")
+            .filter(|p| *p >= 1.0)
+            .map_or(STANDARD_SMA_PERIODS[0], |p| p as u32),
+        _ => STANDARD_SMA_PERIODS[0],
+    }
+}
+
+/// RSI period for a condition, from its `period` param with a default of 14
+pub fn rsi_period(condition: &Condition) -> usize {
+    condition
+        .param_f64("period")
+        .filter(|p| *p >= 1.0)
+        .map_or(rsi::RSI_DEFAULT_PERIOD, |p| p as usize)
+}
+
+/// MACD settings as `(fast_period, slow_period, signal_period)`
+pub type MacdKey = (u32, u32, u32);
+
+const DEFAULT_MACD_KEY: MacdKey = (
+    macd::MACD_DEFAULT_FAST_PERIOD,
+    macd::MACD_DEFAULT_SLOW_PERIOD,
+    macd::MACD_DEFAULT_SIGNAL_PERIOD,
+);
+
+/// MACD settings for a condition, from its `fast_period`, `slow_period` and
+/// `signal_period` params with defaults of 12, 26 and 9
+pub fn macd_key(condition: &Condition) -> MacdKey {
+    let period = |name: &str, default: u32| {
+        condition
+            .param_f64(name)
+            .filter(|p| *p >= 1.0)
+            .map_or(default, |p| p as u32)
+    };
+    (
+        period("fast_period", macd::MACD_DEFAULT_FAST_PERIOD),
+        period("slow_period", macd::MACD_DEFAULT_SLOW_PERIOD),
+        period("signal_period", macd::MACD_DEFAULT_SIGNAL_PERIOD),
+    )
+}
+
+/// RSI settings taken from a strategy's RSI condition
+#[derive(Debug, Clone, Copy)]
+struct RsiParams {
+    period: usize,
+    overbought: f64,
+    oversold: f64,
+}
+
+/// Indicator settings taken from a strategy's conditions
+#[derive(Debug, Clone)]
+struct IndicatorParams {
+    ema_fast_period: usize,
+    ema_slow_period: usize,
+    ema_confirmation_candles: u32,
+    rsi: RsiParams,
+    /// Distinct RSI periods other than `rsi.period`
+    rsi_periods: Vec<usize>,
+    /// Distinct non-default SuperTrend settings
+    supertrend_keys: Vec<SuperTrendKey>,
+    /// Distinct non-default MACD settings
+    macd_keys: Vec<MacdKey>,
+    /// Distinct SMA periods other than 20, 50 and 200
+    sma_periods: Vec<u32>,
+    bollinger_squeeze_threshold_pct: f64,
+}
 
 /// Container for all computed indicator values
 #[derive(Debug, Clone)]
@@ -25,47 +143,183 @@ pub struct IndicatorValues {
     // RSI
     pub rsi_value: Option<f64>,
     pub rsi_signal: Option<rsi::RSISignal>,
+    /// Swing divergence between recent closes and RSI readings
+    pub rsi_divergence: Option<rsi::DivergenceType>,
+    /// RSI reading and divergence for each other period set by the
+    /// strategy's RSI conditions
+    pub rsi_by_period: HashMap<usize, (Option<f64>, Option<rsi::DivergenceType>)>,
+
+    // Stochastic RSI
+    pub stoch_rsi_k: Option<f64>,
+    pub stoch_rsi_d: Option<f64>,
+    pub stoch_rsi_signal: Option<stochastic_rsi::StochRsiSignal>,
+
+    // Williams %R
+    pub williams_r_value: Option<f64>,
+    pub williams_r_signal: Option<williams_r::WilliamsRSignal>,
+
+    // CCI
+    pub cci_value: Option<f64>,
+    pub cci_signal: Option<cci::CCISignal>,
+
+    // MFI
+    pub mfi_value: Option<f64>,
+    pub mfi_signal: Option<mfi::MFISignal>,
+
+    // CMF
+    pub cmf_value: Option<f64>,
+    pub cmf_signal: Option<cmf::CMFSignal>,
     
     // MACD
     pub macd_value: Option<f64>,
     pub macd_signal_value: Option<f64>,
     pub macd_histogram: Option<f64>,
     pub macd_signal: Option<macd::MACDSignal>,
+    /// MACD line, signal line, histogram and signal for each non-default
+    /// `(fast_period, slow_period, signal_period)` set by the strategy's MACD
+    /// conditions
+    pub macd_by_params: HashMap<MacdKey, (f64, f64, f64, macd::MACDSignal)>,
     
     // EMA
     pub ema_fast: Option<f64>,
     pub ema_slow: Option<f64>,
     pub ema_signal: Option<ema::EMATrendSignal>,
+
+    // SMA (simple moving averages of the close)
+    pub sma_20: Option<f64>,
+    pub sma_50: Option<f64>,
+    pub sma_200: Option<f64>,
+    /// SMA for each other period set by the strategy's SMA conditions
+    pub sma_by_period: HashMap<u32, f64>,
+
+    // EMA ribbon
+    pub ema_ribbon_score: Option<f64>,
+    pub ema_ribbon_signal: Option<ema::RibbonSignal>,
+
+    // KAMA (Kaufman adaptive moving average)
+    pub kama_value: Option<f64>,
+    pub kama_efficiency_ratio: Option<f64>,
+    pub kama_signal: Option<kama::KamaSignal>,
     
     // SuperTrend
     pub supertrend_value: Option<f64>,
     pub supertrend_signal: Option<supertrend::SuperTrendSignal>,
+    /// Value and signal for each non-default `(atr_period, multiplier)` pair
+    /// set by the strategy's SuperTrend conditions
+    pub supertrend_by_params: HashMap<SuperTrendKey, (Option<f64>, supertrend::SuperTrendSignal)>,
+
+    // Ichimoku (needs 52 candles before the first reading)
+    pub ichimoku: Option<ichimoku::IchimokuIndicator>,
+
+    // Parabolic SAR
+    pub parabolic_sar_value: Option<f64>,
+    pub parabolic_sar_af: Option<f64>,
+    pub parabolic_sar_signal: Option<parabolic_sar::ParabolicSarSignal>,
+
+    // ADX (trend strength, needs 28 candles before the first reading)
+    pub adx_value: Option<f64>,
     
     // Bollinger Bands
     pub bollinger_upper: Option<f64>,
     pub bollinger_middle: Option<f64>,
     pub bollinger_lower: Option<f64>,
     pub bollinger_signal: Option<bollinger::BollingerSignal>,
+    /// Price position within the bands, clamped to [0, 1]
+    pub bb_percent_b: Option<f64>,
+    /// Band width as a percentage of the middle band
+    pub bb_bandwidth: Option<f64>,
+
+    // Keltner Channel
+    pub keltner_upper: Option<f64>,
+    pub keltner_middle: Option<f64>,
+    pub keltner_lower: Option<f64>,
+    pub keltner_signal: Option<keltner::KeltnerSignal>,
+
+    // Donchian Channel
+    pub donchian_upper: Option<f64>,
+    pub donchian_middle: Option<f64>,
+    pub donchian_lower: Option<f64>,
+    pub donchian_signal: Option<donchian::DonchianSignal>,
+
+    // Fibonacci retracement of the latest swing (pivots need 5 candles either side)
+    pub fib_support: Option<f64>,
+    pub fib_resistance: Option<f64>,
+    pub fib_position_ratio: Option<f64>,
+
+    // Strongest candlestick pattern completed on the latest candle
+    pub candlestick_pattern: Option<candlestick::CandlestickPattern>,
     
     // ATR
     pub atr_value: Option<f64>,
     pub volatility_regime: Option<atr::VolatilityRegime>,
+
+    // ATR% (ATR over close, in percent)
+    pub atr_pct_value: Option<f64>,
+    pub volatility_regime_pct: Option<atr::VolatilityRegime>,
     
     // OBV
     pub obv_signal: Option<obv::OBVSignal>,
     
     // Volume Profile
     pub volume_profile_signal: Option<volume_profile::VolumeProfileSignal>,
+    /// Point of Control: the price level with the most volume
+    pub vp_poc_price: Option<f64>,
+    /// Bounds of the value area holding 70% of the volume around the POC
+    pub vp_value_area_high: Option<f64>,
+    pub vp_value_area_low: Option<f64>,
+
+    // VWAP (resets at UTC midnight)
+    pub vwap_value: Option<f64>,
+    pub vwap_signal: Option<vwap::VwapSignal>,
     
     // Open Interest
     pub oi_signal: Option<open_interest::OpenInterestSignal>,
+    pub oi_trend: Option<open_interest::OpenInterestTrend>,
+    pub oi_value: Option<f64>,
+    pub oi_ma: Option<f64>,
+    pub oi_ma_period: usize,
+    /// OI deviation from its moving average, normalized to [-1, 1]
+    pub oi_score: Option<f64>,
     
     // Funding Rate
     pub funding_signal: Option<funding_rate::FundingSignal>,
     pub funding_rate_value: Option<f64>,
+
+    // Bid-ask spread, from the live order book rather than candles
+    pub bid_ask_spread_pct: Option<f64>,
+
+    // Buy/sell volume ratio, from the live trade feed
+    pub buy_sell_ratio: Option<f64>,
     
     // Current price
     pub current_price: f64,
+
+    /// When the values were computed, or the candle a historical replay is
+    /// evaluated at; `TimeOfDay` conditions and the strategy's time filter
+    /// use its UTC hour
+    pub evaluated_at: DateTime<Utc>,
+
+    /// Whether each warm-up indicator produced a reading
+    pub computations: Vec<IndicatorComputation>,
+}
+
+/// Whether an indicator that needs warm-up candles produced a reading
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndicatorComputation {
+    pub indicator: &'static str,
+    /// Candles the indicator needs before it always has a reading
+    pub min_candles: usize,
+    /// Candles the indicator was computed over
+    pub candles: usize,
+    pub computed: bool,
+}
+
+impl IndicatorComputation {
+    /// No reading despite enough candles, so a bug or bad data rather than
+    /// a short series
+    pub fn is_failure(&self) -> bool {
+        !self.computed && self.candles >= self.min_candles
+    }
 }
 
 impl IndicatorValues {
@@ -73,60 +327,517 @@ impl IndicatorValues {
         Self {
             rsi_value: None,
             rsi_signal: None,
+            rsi_divergence: None,
+            rsi_by_period: HashMap::new(),
+            stoch_rsi_k: None,
+            stoch_rsi_d: None,
+            stoch_rsi_signal: None,
+            williams_r_value: None,
+            williams_r_signal: None,
+            cci_value: None,
+            cci_signal: None,
+            mfi_value: None,
+            mfi_signal: None,
+            cmf_value: None,
+            cmf_signal: None,
             macd_value: None,
             macd_signal_value: None,
             macd_histogram: None,
             macd_signal: None,
+            macd_by_params: HashMap::new(),
             ema_fast: None,
             ema_slow: None,
             ema_signal: None,
+            sma_20: None,
+            sma_50: None,
+            sma_200: None,
+            sma_by_period: HashMap::new(),
+            ema_ribbon_score: None,
+            ema_ribbon_signal: None,
+            kama_value: None,
+            kama_efficiency_ratio: None,
+            kama_signal: None,
             supertrend_value: None,
             supertrend_signal: None,
+            supertrend_by_params: HashMap::new(),
+            ichimoku: None,
+            parabolic_sar_value: None,
+            parabolic_sar_af: None,
+            parabolic_sar_signal: None,
+            adx_value: None,
             bollinger_upper: None,
             bollinger_middle: None,
             bollinger_lower: None,
             bollinger_signal: None,
+            bb_percent_b: None,
+            bb_bandwidth: None,
+            keltner_upper: None,
+            keltner_middle: None,
+            keltner_lower: None,
+            keltner_signal: None,
+            donchian_upper: None,
+            donchian_middle: None,
+            donchian_lower: None,
+            donchian_signal: None,
+            fib_support: None,
+            fib_resistance: None,
+            fib_position_ratio: None,
+            candlestick_pattern: None,
             atr_value: None,
             volatility_regime: None,
+            atr_pct_value: None,
+            volatility_regime_pct: None,
             obv_signal: None,
             volume_profile_signal: None,
+            vp_poc_price: None,
+            vp_value_area_high: None,
+            vp_value_area_low: None,
+            vwap_value: None,
+            vwap_signal: None,
             oi_signal: None,
+            oi_trend: None,
+            oi_value: None,
+            oi_ma: None,
+            oi_ma_period: OI_MA_PERIOD,
+            oi_score: None,
             funding_signal: None,
             funding_rate_value: None,
+            bid_ask_spread_pct: None,
+            buy_sell_ratio: None,
             current_price,
+            evaluated_at: Utc::now(),
+            computations: Vec::new(),
         }
     }
+
+    /// RSI reading and divergence for a condition's period (see
+    /// [`rsi_period`]), from the main calculator unless it uses another period
+    pub fn rsi_for(&self, condition: &Condition) -> (Option<f64>, Option<rsi::DivergenceType>) {
+        self.rsi_by_period
+            .get(&rsi_period(condition))
+            .copied()
+            .unwrap_or((self.rsi_value, self.rsi_divergence))
+    }
+
+    /// SuperTrend value and signal for a condition's `atr_period` and
+    /// `multiplier`, from the default calculator when neither is set
+    pub fn supertrend_for(
+        &self,
+        condition: &Condition,
+    ) -> (Option<f64>, Option<&supertrend::SuperTrendSignal>) {
+        let key = supertrend_key(condition);
+        if key == DEFAULT_SUPERTREND_KEY {
+            return (self.supertrend_value, self.supertrend_signal.as_ref());
+        }
+        self.supertrend_by_params
+            .get(&key)
+            .map_or((None, None), |(value, signal)| (*value, Some(signal)))
+    }
+
+    /// MACD line and signal for a condition's `fast_period`, `slow_period`
+    /// and `signal_period`, from the default calculator when none is set
+    pub fn macd_for(&self, condition: &Condition) -> (Option<f64>, Option<macd::MACDSignal>) {
+        let key = macd_key(condition);
+        if key == DEFAULT_MACD_KEY {
+            return (self.macd_value, self.macd_signal);
+        }
+        self.macd_by_params
+            .get(&key)
+            .map_or((None, None), |&(value, _, _, signal)| {
+                (Some(value), Some(signal))
+            })
+    }
+
+    /// SMA for a condition's period (see [`sma_period`])
+    pub fn sma_for(&self, condition: &Condition) -> Option<f64> {
+        match sma_period(condition) {
+            20 => self.sma_20,
+            50 => self.sma_50,
+            200 => self.sma_200,
+            period => self.sma_by_period.get(&period).copied(),
+        }
+    }
+
+    /// Numeric readings, for returning over the API
+    pub fn snapshot(&self) -> IndicatorSnapshot {
+        IndicatorSnapshot {
+            current_price: self.current_price,
+            rsi: self.rsi_value,
+            stoch_rsi_k: self.stoch_rsi_k,
+            stoch_rsi_d: self.stoch_rsi_d,
+            williams_r: self.williams_r_value,
+            cci: self.cci_value,
+            mfi: self.mfi_value,
+            cmf: self.cmf_value,
+            macd: self.macd_value,
+            macd_signal: self.macd_signal_value,
+            macd_histogram: self.macd_histogram,
+            ema_fast: self.ema_fast,
+            ema_slow: self.ema_slow,
+            sma_20: self.sma_20,
+            sma_50: self.sma_50,
+            sma_200: self.sma_200,
+            ema_ribbon_score: self.ema_ribbon_score,
+            kama: self.kama_value,
+            supertrend: self.supertrend_value,
+            parabolic_sar: self.parabolic_sar_value,
+            adx: self.adx_value,
+            bollinger_upper: self.bollinger_upper,
+            bollinger_middle: self.bollinger_middle,
+            bollinger_lower: self.bollinger_lower,
+            bb_percent_b: self.bb_percent_b,
+            bb_bandwidth: self.bb_bandwidth,
+            keltner_upper: self.keltner_upper,
+            keltner_middle: self.keltner_middle,
+            keltner_lower: self.keltner_lower,
+            donchian_upper: self.donchian_upper,
+            donchian_middle: self.donchian_middle,
+            donchian_lower: self.donchian_lower,
+            fib_support: self.fib_support,
+            fib_resistance: self.fib_resistance,
+            fib_position_ratio: self.fib_position_ratio,
+            atr: self.atr_value,
+            atr_pct: self.atr_pct_value,
+            vwap: self.vwap_value,
+            vp_poc_price: self.vp_poc_price,
+            vp_value_area_high: self.vp_value_area_high,
+            vp_value_area_low: self.vp_value_area_low,
+            open_interest: self.oi_value,
+            open_interest_ma: self.oi_ma,
+            open_interest_score: self.oi_score,
+            funding_rate: self.funding_rate_value,
+            bid_ask_spread_pct: self.bid_ask_spread_pct,
+            buy_sell_ratio: self.buy_sell_ratio,
+        }
+    }
+
+    /// Set `bid_ask_spread_pct` from the top of the book, if there is a valid one
+    pub fn set_spread(&mut self, top_of_book: Option<(f64, f64)>) {
+        self.bid_ask_spread_pct =
+            top_of_book.and_then(|(best_bid, best_ask)| spread::bid_ask_spread_pct(best_bid, best_ask));
+    }
+
+    /// Set the readings that come from live market data rather than candles
+    pub fn set_market_data(&mut self, market_data: LiveMarketData) {
+        self.set_spread(market_data.top_of_book);
+        self.buy_sell_ratio = market_data.buy_sell_ratio;
+    }
+}
+
+/// Live readings for conditions that candles can't answer. Conditions on a
+/// missing reading fail.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LiveMarketData {
+    /// `(best_bid, best_ask)`, for `Spread` conditions
+    pub top_of_book: Option<(f64, f64)>,
+    /// Buy/sell volume ratio over recent trades, for `BuySellRatio` conditions
+    pub buy_sell_ratio: Option<f64>,
 }
 
 pub struct StrategyEvaluator;
 
 impl StrategyEvaluator {
     /// Evaluate a strategy against candles
+    ///
+    /// Returns `None` when the series has fewer than `MIN_CANDLES` candles or
+    /// more critical anomalies than `MAX_CRITICAL_CANDLE_ANOMALIES` allows.
     pub fn evaluate_strategy(
         strategy: &Strategy,
         candles: &[Candle],
     ) -> Option<SignalOutput> {
+        Self::evaluate_strategy_with_spread(strategy, candles, None)
+    }
+
+    /// Like [`Self::evaluate_strategy`], evaluated as of `evaluated_at`
+    /// rather than now, for replaying historical candles. Time filters and
+    /// `TimeOfDay` conditions use its UTC hour.
+    pub fn evaluate_strategy_at(
+        strategy: &Strategy,
+        candles: &[Candle],
+        evaluated_at: DateTime<Utc>,
+    ) -> Option<SignalOutput> {
+        let (rule_results, indicator_values, current_price) = Self::evaluate_candles(
+            strategy,
+            candles,
+            LiveMarketData::default(),
+            evaluated_at,
+        )?;
+        Self::build_signal(strategy, &rule_results, &indicator_values, current_price)
+    }
+
+    /// Like [`Self::evaluate_strategy`], with the current `(best_bid, best_ask)`
+    /// for `Spread` conditions. Without it those conditions fail.
+    pub fn evaluate_strategy_with_spread(
+        strategy: &Strategy,
+        candles: &[Candle],
+        top_of_book: Option<(f64, f64)>,
+    ) -> Option<SignalOutput> {
+        let market_data = LiveMarketData {
+            top_of_book,
+            ..LiveMarketData::default()
+        };
+        Self::evaluate_strategy_with_market_data(strategy, candles, market_data, None)
+    }
+
+    /// Like [`Self::evaluate_strategy`], with live readings for `Spread` and
+    /// `BuySellRatio` conditions. Indicator computations are recorded in
+    /// `metrics` when given.
+    pub fn evaluate_strategy_with_market_data(
+        strategy: &Strategy,
+        candles: &[Candle],
+        market_data: LiveMarketData,
+        metrics: Option<&Metrics>,
+    ) -> Option<SignalOutput> {
+        let (rule_results, indicator_values, current_price) =
+            Self::evaluate_candles(strategy, candles, market_data, Utc::now())?;
+        if let Some(metrics) = metrics {
+            metrics.record_indicator_computations(&indicator_values.computations);
+        }
+        Self::build_signal(strategy, &rule_results, &indicator_values, current_price)
+    }
+
+    /// Dry run of [`Self::evaluate_strategy_with_market_data`] as of
+    /// `evaluated_at` that also reports how each top-level rule was decided,
+    /// for debugging strategies that never fire
+    pub fn evaluate_with_trace(
+        strategy: &Strategy,
+        candles: &[Candle],
+        market_data: &LiveMarketData,
+        evaluated_at: DateTime<Utc>,
+    ) -> Option<(SignalOutput, EvaluationTrace)> {
+        let (mut rule_results, indicator_values, current_price) =
+            Self::evaluate_candles(strategy, candles, *market_data, evaluated_at)?;
+        let signal = Self::build_signal(strategy, &rule_results, &indicator_values, current_price)?;
+
+        let aggregation = &strategy.config.aggregation;
+        Self::assign_contributions(&mut rule_results, &strategy.config, &indicator_values);
+        let trace = EvaluationTrace {
+            total_score: Self::strategy_score(&rule_results, &strategy.config, &indicator_values),
+            thresholds: aggregation.thresholds.clone(),
+            rules: rule_results,
+        };
+
+        Some((signal, trace))
+    }
+
+    /// Like [`Self::evaluate_strategy_with_market_data`] as of `evaluated_at`,
+    /// also returning the indicator values the signal was built from
+    pub fn evaluate_with_indicators(
+        strategy: &Strategy,
+        candles: &[Candle],
+        market_data: &LiveMarketData,
+        evaluated_at: DateTime<Utc>,
+    ) -> Option<(SignalOutput, IndicatorValues)> {
+        let (rule_results, indicator_values, current_price) =
+            Self::evaluate_candles(strategy, candles, *market_data, evaluated_at)?;
+        let signal = Self::build_signal(strategy, &rule_results, &indicator_values, current_price)?;
+        Some((signal, indicator_values))
+    }
+
+    /// Rule results, indicator values and the current price, or `None` when
+    /// the candles are too few or too broken to evaluate
+    fn evaluate_candles(
+        strategy: &Strategy,
+        candles: &[Candle],
+        market_data: LiveMarketData,
+        evaluated_at: DateTime<Utc>,
+    ) -> Option<(Vec<RuleResult>, IndicatorValues, f64)> {
         if candles.len() < MIN_CANDLES {
             return None;
         }
 
+        let critical = validate_candle_series(candles)
+            .iter()
+            .filter(|issue| issue.is_hard_failure())
+            .count();
+        let max_critical = get_max_critical_candle_anomalies();
+        if critical > max_critical {
+            warn!(
+                strategy = %strategy.name,
+                symbol = %strategy.symbol,
+                "Skipping evaluation: {} critical candle anomalies (max {})",
+                critical,
+                max_critical
+            );
+            return None;
+        }
+
         let current_price = candles.last()?.close;
-        let indicator_values = Self::compute_indicators(candles, current_price);
+        let mut indicator_values =
+            Self::compute_strategy_indicators(strategy, candles, current_price);
+        indicator_values.set_market_data(market_data);
+        indicator_values.evaluated_at = evaluated_at;
+        let rule_results = Self::evaluate_rules(strategy, &indicator_values);
+
+        Some((rule_results, indicator_values, current_price))
+    }
+
+    /// Evaluate a strategy against candles from several intervals
+    ///
+    /// See [`MultiTimeframeEvaluator`] for how timeframes are combined.
+    pub fn evaluate_multi_timeframe(
+        strategy: &Strategy,
+        candles_by_interval: &HashMap<String, Vec<Candle>>,
+    ) -> Option<SignalOutput> {
+        MultiTimeframeEvaluator::evaluate(strategy, candles_by_interval)
+    }
+
+    /// Like [`Self::evaluate_multi_timeframe`], with the current
+    /// `(best_bid, best_ask)` for `Spread` conditions
+    pub fn evaluate_multi_timeframe_with_spread(
+        strategy: &Strategy,
+        candles_by_interval: &HashMap<String, Vec<Candle>>,
+        top_of_book: Option<(f64, f64)>,
+    ) -> Option<SignalOutput> {
+        MultiTimeframeEvaluator::evaluate_with_spread(strategy, candles_by_interval, top_of_book)
+    }
+
+    /// Like [`Self::evaluate_multi_timeframe`], with live readings for
+    /// `Spread` and `BuySellRatio` conditions
+    pub fn evaluate_multi_timeframe_with_market_data(
+        strategy: &Strategy,
+        candles_by_interval: &HashMap<String, Vec<Candle>>,
+        market_data: LiveMarketData,
+        metrics: Option<&Metrics>,
+    ) -> Option<SignalOutput> {
+        MultiTimeframeEvaluator::evaluate_with_market_data(
+            strategy,
+            candles_by_interval,
+            market_data,
+            metrics,
+        )
+    }
+
+    /// Evaluate every strategy against the same candles on the blocking pool,
+    /// up to `WORKER_EVALUATION_PARALLELISM` at a time
+    ///
+    /// Signals come back in strategy order. Strategies that produce no signal
+    /// or run past `STRATEGY_TIMEOUT_SECONDS` are left out.
+    pub async fn evaluate_all_concurrent(
+        strategies: &[Strategy],
+        candles: &[Candle],
+    ) -> Vec<SignalOutput> {
+        let candles: Arc<[Candle]> = Arc::from(candles);
+        let parallelism = get_worker_evaluation_parallelism(strategies.len());
+        let timeout = Duration::from_secs(get_strategy_timeout_seconds());
+        Self::evaluate_concurrent(strategies, parallelism, timeout, move |strategy| {
+            Self::evaluate_strategy(&strategy, &candles)
+        })
+        .await
+        .into_iter()
+        .filter_map(|evaluation| evaluation.ok().flatten())
+        .collect()
+    }
 
-        // Evaluate all rules
-        let mut rule_results = Vec::new();
-        for rule in &strategy.config.rules {
-            if let Some(result) = Self::evaluate_rule(rule, &indicator_values) {
-                rule_results.push(result);
+    /// Run `evaluate` for each strategy on the blocking pool, at most
+    /// `parallelism` at a time, returning one result per strategy in order
+    ///
+    /// A result is `Err` if its evaluation does not finish within `timeout`.
+    /// The blocking thread cannot be cancelled, so it keeps running in the
+    /// background, but its slot goes to the next strategy. Panics in
+    /// `evaluate` are propagated.
+    pub async fn evaluate_concurrent<T, F>(
+        strategies: &[Strategy],
+        parallelism: usize,
+        timeout: Duration,
+        evaluate: F,
+    ) -> Vec<Result<T, Elapsed>>
+    where
+        F: Fn(Strategy) -> T + Send + Sync + 'static,
+        T: Send + 'static,
+    {
+        let evaluate = Arc::new(evaluate);
+        let mut pending = strategies.iter().cloned().enumerate();
+        let mut results: Vec<Option<Result<T, Elapsed>>> =
+            strategies.iter().map(|_| None).collect();
+        let mut tasks = JoinSet::new();
+
+        loop {
+            while tasks.len() < parallelism.max(1) {
+                let Some((index, strategy)) = pending.next() else {
+                    break;
+                };
+                let evaluate = Arc::clone(&evaluate);
+                let handle = tokio::task::spawn_blocking(move || evaluate(strategy));
+                tasks.spawn(async move { (index, tokio::time::timeout(timeout, handle).await) });
             }
+            let Some(joined) = tasks.join_next().await else {
+                break;
+            };
+            let (index, result) = joined.unwrap_or_else(|e| resume_unwind(e.into_panic()));
+            results[index] =
+                Some(result.map(|value| value.unwrap_or_else(|e| resume_unwind(e.into_panic()))));
+        }
+
+        results
+            .into_iter()
+            .map(|result| result.expect("every strategy is evaluated"))
+            .collect()
+    }
+
+    /// Compute indicators using the strategy's indicator parameters
+    pub(crate) fn compute_strategy_indicators(
+        strategy: &Strategy,
+        candles: &[Candle],
+        current_price: f64,
+    ) -> IndicatorValues {
+        let rules = &strategy.config.rules;
+        let (ema_fast_period, ema_slow_period) = Self::ema_periods(rules);
+        let mut supertrend_keys = Vec::new();
+        Self::supertrend_keys(rules, &mut supertrend_keys);
+        let mut macd_keys = Vec::new();
+        Self::macd_keys(rules, &mut macd_keys);
+        let mut sma_periods = Vec::new();
+        Self::sma_periods(rules, &mut sma_periods);
+        let rsi = Self::rsi_params(rules);
+        let mut rsi_periods = Vec::new();
+        Self::rsi_periods(rules, &mut rsi_periods);
+        rsi_periods.retain(|&period| period != rsi.period);
+        let params = IndicatorParams {
+            ema_fast_period,
+            ema_slow_period,
+            ema_confirmation_candles: Self::ema_confirmation_candles(rules),
+            rsi,
+            rsi_periods,
+            supertrend_keys,
+            macd_keys,
+            sma_periods,
+            bollinger_squeeze_threshold_pct: Self::bollinger_squeeze_threshold_pct(rules),
+        };
+        if strategy.config.use_heikin_ashi {
+            let candles = heikin_ashi::transform_to_heikin_ashi(candles);
+            Self::compute_indicators(&candles, current_price, &params)
+        } else {
+            Self::compute_indicators(candles, current_price, &params)
         }
+    }
+
+    /// Evaluate every top-level rule, skipping rules without enough data
+    pub(crate) fn evaluate_rules(
+        strategy: &Strategy,
+        indicator_values: &IndicatorValues,
+    ) -> Vec<RuleResult> {
+        strategy
+            .config
+            .rules
+            .iter()
+            .filter_map(|rule| Self::evaluate_rule(rule, indicator_values))
+            .collect()
+    }
 
+    /// Turn rule results into a signal; `indicator_values` feed the SL/TP model
+    pub(crate) fn build_signal(
+        strategy: &Strategy,
+        rule_results: &[RuleResult],
+        indicator_values: &IndicatorValues,
+        current_price: f64,
+    ) -> Option<SignalOutput> {
         if rule_results.is_empty() {
             return None;
         }
 
         // Aggregate results
-        let total_score = Self::aggregate_results(&rule_results, &strategy.config.aggregation);
+        let total_score = Self::strategy_score(rule_results, &strategy.config, indicator_values);
         
         // Determine signal direction from score
         let direction = if total_score >= strategy.config.aggregation.thresholds.long_min {
@@ -141,15 +852,20 @@ impl StrategyEvaluator {
         let max_possible_score = rule_results.iter().map(|r| r.weight.abs() as i32).sum::<i32>().max(1);
         let confidence = (total_score.abs() as f64 / max_possible_score as f64).min(1.0);
 
-        // Calculate SL/TP from ATR if available
-        let (sl_pct, tp_pct) = if let Some(atr) = indicator_values.atr_value {
-            if atr > 0.0 {
-                StopLossTakeProfit::calculate_from_atr(atr, current_price)
-            } else {
-                (0.0, 0.0)
-            }
-        } else {
-            (0.0, 0.0)
+        let (sl_pct, tp_pct) = StopLossTakeProfit::from_model(
+            &strategy.config.sl_tp_model,
+            direction,
+            indicator_values,
+            current_price,
+        );
+
+        let position_size_pct = match direction {
+            SignalDirection::Neutral => None,
+            _ => strategy
+                .config
+                .position_size_model
+                .fraction(confidence, sl_pct, tp_pct, strategy.config.max_kelly_fraction)
+                .map(|fraction| fraction * 100.0),
         };
 
         // Build reasons
@@ -167,43 +883,353 @@ impl StrategyEvaluator {
             confidence,
             recommended_sl_pct: sl_pct,
             recommended_tp_pct: tp_pct,
+            recommended_position_size_pct: position_size_pct,
             reasons,
             symbol: strategy.symbol.clone(),
             price: current_price,
             timestamp: Utc::now(),
+            decay: strategy.config.decay_model,
+        })
+    }
+
+    /// First condition on `indicator`, searching nested rules depth-first,
+    /// that sets any of `params`
+    fn find_condition<'a>(
+        rules: &'a [Rule],
+        indicator: IndicatorType,
+        params: &[&str],
+    ) -> Option<&'a Condition> {
+        rules.iter().find_map(|rule| {
+            rule.condition
+                .as_ref()
+                .filter(|c| {
+                    c.indicator == indicator
+                        && params.iter().any(|p| c.indicator_params.contains_key(*p))
+                })
+                .or_else(|| {
+                    rule.children
+                        .as_deref()
+                        .and_then(|children| Self::find_condition(children, indicator, params))
+                })
         })
     }
 
+    /// Integer period parameter `name`, or `default` when unset or below 1
+    fn period_param(condition: Option<&Condition>, name: &str, default: usize) -> usize {
+        condition
+            .and_then(|c| c.param_f64(name))
+            .filter(|p| *p >= 1.0)
+            .map_or(default, |p| p as usize)
+    }
+
+    /// EMA crossover periods from the first EMA condition that sets
+    /// `fast_period`/`slow_period`, falling back to 20/50
+    fn ema_periods(rules: &[Rule]) -> (usize, usize) {
+        let condition =
+            Self::find_condition(rules, IndicatorType::EMA, &["fast_period", "slow_period"]);
+        (
+            Self::period_param(condition, "fast_period", DEFAULT_EMA_FAST_PERIOD),
+            Self::period_param(condition, "slow_period", DEFAULT_EMA_SLOW_PERIOD),
+        )
+    }
+
+    /// Candles an EMA cross must hold from the first EMA condition that sets
+    /// `confirmation_candles`, falling back to 0 (no confirmation)
+    fn ema_confirmation_candles(rules: &[Rule]) -> u32 {
+        Self::find_condition(rules, IndicatorType::EMA, &["confirmation_candles"])
+            .and_then(|c| c.param_f64("confirmation_candles"))
+            .filter(|n| *n >= 0.0)
+            .map_or(0, |n| n as u32)
+    }
+
+    /// RSI period and overbought/oversold thresholds of the main calculator,
+    /// from the first RSI condition that sets any of them, falling back to 14
+    /// and 70/30
+    fn rsi_params(rules: &[Rule]) -> RsiParams {
+        let condition = Self::find_condition(
+            rules,
+            IndicatorType::RSI,
+            &["period", "overbought_threshold", "oversold_threshold"],
+        );
+        let threshold =
+            |name: &str, default: f64| condition.and_then(|c| c.param_f64(name)).unwrap_or(default);
+        RsiParams {
+            period: condition.map_or(rsi::RSI_DEFAULT_PERIOD, rsi_period),
+            overbought: threshold("overbought_threshold", rsi::DEFAULT_OVERBOUGHT),
+            oversold: threshold("oversold_threshold", rsi::DEFAULT_OVERSOLD),
+        }
+    }
+
+    /// Bollinger squeeze threshold from the first Bollinger condition that
+    /// sets `squeeze_threshold_pct`, falling back to 2%
+    fn bollinger_squeeze_threshold_pct(rules: &[Rule]) -> f64 {
+        Self::find_condition(rules, IndicatorType::Bollinger, &["squeeze_threshold_pct"])
+            .and_then(|c| c.param_f64("squeeze_threshold_pct"))
+            .filter(|t| *t > 0.0)
+            .unwrap_or(bollinger::DEFAULT_SQUEEZE_THRESHOLD_PCT)
+    }
+
+    /// Add the distinct non-default SuperTrend settings of every SuperTrend
+    /// condition in `rules`, including nested ones, to `keys`
+    fn supertrend_keys(rules: &[Rule], keys: &mut Vec<SuperTrendKey>) {
+        for rule in rules {
+            if let Some(condition) = rule
+                .condition
+                .as_ref()
+                .filter(|c| c.indicator == IndicatorType::SuperTrend)
+            {
+                let key = supertrend_key(condition);
+                if key != DEFAULT_SUPERTREND_KEY && !keys.contains(&key) {
+                    keys.push(key);
+                }
+            }
+            if let Some(children) = rule.children.as_deref() {
+                Self::supertrend_keys(children, keys);
+            }
+        }
+    }
+
+    /// Add the distinct non-default MACD settings of every MACD condition in
+    /// `rules`, including nested ones, to `keys`
+    fn macd_keys(rules: &[Rule], keys: &mut Vec<MacdKey>) {
+        for rule in rules {
+            if let Some(condition) = rule
+                .condition
+                .as_ref()
+                .filter(|c| c.indicator == IndicatorType::MACD)
+            {
+                let key = macd_key(condition);
+                if key != DEFAULT_MACD_KEY && !keys.contains(&key) {
+                    keys.push(key);
+                }
+            }
+            if let Some(children) = rule.children.as_deref() {
+                Self::macd_keys(children, keys);
+            }
+        }
+    }
+
+    /// Add the distinct periods of every RSI condition in `rules`, including
+    /// nested ones, to `periods`
+    fn rsi_periods(rules: &[Rule], periods: &mut Vec<usize>) {
+        for rule in rules {
+            if let Some(condition) = rule
+                .condition
+                .as_ref()
+                .filter(|c| c.indicator == IndicatorType::RSI)
+            {
+                let period = rsi_period(condition);
+                if !periods.contains(&period) {
+                    periods.push(period);
+                }
+            }
+            if let Some(children) = rule.children.as_deref() {
+                Self::rsi_periods(children, periods);
+            }
+        }
+    }
+
+    /// Add the distinct periods other than 20, 50 and 200 of every `SMA`
+    /// condition in `rules`, including nested ones, to `periods`
+    fn sma_periods(rules: &[Rule], periods: &mut Vec<u32>) {
+        for rule in rules {
+            if let Some(condition) = rule
+                .condition
+                .as_ref()
+                .filter(|c| c.indicator == IndicatorType::SMA)
+            {
+                let period = sma_period(condition);
+                if !STANDARD_SMA_PERIODS.contains(&period) && !periods.contains(&period) {
+                    periods.push(period);
+                }
+            }
+            if let Some(children) = rule.children.as_deref() {
+                Self::sma_periods(children, periods);
+            }
+        }
+    }
+
+    /// Latest `period` RSI over `closes` and its divergence from them, like
+    /// the main RSI of [`Self::compute_indicators`]
+    fn rsi_reading(closes: &[f64], period: usize) -> (Option<f64>, Option<rsi::DivergenceType>) {
+        let mut calculator = rsi::RSI::new(period);
+        let (mut readings, mut reading_closes) = (Vec::new(), Vec::new());
+        for &close in closes {
+            if let Some(reading) = calculator.update(close) {
+                readings.push(reading);
+                reading_closes.push(close);
+            }
+        }
+        let Some(&latest) = readings.last() else {
+            return (None, None);
+        };
+        let recent = readings.len().saturating_sub(rsi::RSI_DIVERGENCE_LOOKBACK);
+        let divergence = rsi::detect_rsi_divergence(
+            &reading_closes[recent..],
+            &readings[recent..],
+            rsi::RSI_DIVERGENCE_SWING_BARS,
+        );
+        (Some(latest), divergence)
+    }
+
     /// Compute all indicator values from candles
-    fn compute_indicators(candles: &[Candle], current_price: f64) -> IndicatorValues {
+    fn compute_indicators(
+        candles: &[Candle],
+        current_price: f64,
+        params: &IndicatorParams,
+    ) -> IndicatorValues {
         let mut values = IndicatorValues::new(current_price);
 
         // Initialize indicators with default parameters
-        let mut ema_cross = ema::EMACrossover::new(20, 50);
-        let mut supertrend = supertrend::SuperTrend::new(10, 3.0);
-        let mut rsi = rsi::RSI::new(14);
-        let mut macd = macd::MACD::new(12, 26, 9);
+        let mut ema_cross = ema::EMACrossover::new(params.ema_fast_period, params.ema_slow_period)
+            .with_confirmation_candles(params.ema_confirmation_candles);
+        let mut ema_ribbon = ema::EmaRibbon::default();
+        let mut kama = kama::KAMA::default();
+        let mut supertrend = supertrend::SuperTrend::new(
+            supertrend::SUPERTREND_DEFAULT_ATR_PERIOD,
+            supertrend::SUPERTREND_DEFAULT_MULTIPLIER,
+        );
+        // One calculator per distinct setting used by the strategy's conditions
+        let mut custom_supertrends: HashMap<SuperTrendKey, supertrend::SuperTrend> =
+            params
+                .supertrend_keys
+                .iter()
+                .map(|&(atr_period, multiplier)| {
+                    let calculator =
+                        supertrend::SuperTrend::new(atr_period, f64::from_bits(multiplier));
+                    ((atr_period, multiplier), calculator)
+                })
+                .collect();
+        let mut ichimoku = ichimoku::Ichimoku::default();
+        let mut parabolic_sar = parabolic_sar::ParabolicSar::default();
+        let mut adx = adx::ADX::default();
+        let mut rsi = rsi::RSI::new(params.rsi.period);
+        // Stochastic RSI keeps the standard 14-period RSI as its input
+        let mut stoch_rsi_source = rsi::RSI::new(rsi::RSI_DEFAULT_PERIOD);
+        let mut stoch_rsi = stochastic_rsi::StochasticRsi::default();
+        let mut williams_r = williams_r::WilliamsR::default();
+        let mut cci = cci::CCI::default();
+        let mut mfi = mfi::MFI::default();
+        let mut cmf = cmf::CMF::default();
+        let mut macd = macd::MACD::new(
+            macd::MACD_DEFAULT_FAST_PERIOD as usize,
+            macd::MACD_DEFAULT_SLOW_PERIOD as usize,
+            macd::MACD_DEFAULT_SIGNAL_PERIOD as usize,
+        );
+        let mut custom_macds: HashMap<MacdKey, macd::MACD> = params
+            .macd_keys
+            .iter()
+            .map(|&(fast, slow, signal)| {
+                let calculator = macd::MACD::new(fast as usize, slow as usize, signal as usize);
+                ((fast, slow, signal), calculator)
+            })
+            .collect();
         let mut atr = atr::ATR::new(14);
-        let mut bollinger = bollinger::BollingerBands::new(20, 2.0);
+        let mut atr_pct = atr_pct::AtrPct::default();
+        let mut bollinger = bollinger::BollingerBands::new(20, 2.0)
+            .with_squeeze_threshold_pct(params.bollinger_squeeze_threshold_pct);
+        let mut keltner = keltner::KeltnerChannel::default();
+        let mut donchian = donchian::DonchianChannel::default();
         let mut obv = obv::OBV::new();
+        let volume_profile_tick = volume_profile::tick_size_for_price(
+            candles.last().map(|candle| candle.close).unwrap_or(0.0),
+        );
         let mut volume_profile =
-            volume_profile::VolumeProfile::new(VOLUME_PROFILE_TICK, VOLUME_PROFILE_LOOKBACK);
+            volume_profile::VolumeProfile::new(volume_profile_tick, VOLUME_PROFILE_LOOKBACK);
+        let mut vwap = vwap::VWAP::new();
         let mut open_interest = open_interest::OpenInterest::new();
         let mut funding_rate = funding_rate::FundingRate::new(24);
         let mut atr_history: VecDeque<f64> = VecDeque::new();
-        let mut prev_close: Option<f64> = None;
+        let mut obv_history: VecDeque<f64> = VecDeque::new();
+        let mut close_history: VecDeque<f64> = VecDeque::new();
+        let mut oi_history: VecDeque<f64> = VecDeque::new();
+        let mut rsi_history: VecDeque<f64> = VecDeque::new();
+        let mut rsi_close_history: VecDeque<f64> = VecDeque::new();
 
         for candle in candles {
             // Update indicators
             values.ema_signal = Some(ema_cross.update(candle.close));
+            if let Some((score, ribbon_sig)) = ema_ribbon.update(candle.close) {
+                values.ema_ribbon_score = Some(score);
+                values.ema_ribbon_signal = Some(ribbon_sig);
+            }
+            if let Some((kama_value, kama_sig)) = kama.update(candle.close) {
+                values.kama_value = Some(kama_value);
+                values.kama_efficiency_ratio = kama.efficiency_ratio();
+                values.kama_signal = Some(kama_sig);
+            }
             values.supertrend_signal = Some(supertrend.update(candle.high, candle.low, candle.close));
+            for (key, calculator) in custom_supertrends.iter_mut() {
+                let signal = calculator.update(candle.high, candle.low, candle.close);
+                values
+                    .supertrend_by_params
+                    .insert(*key, (calculator.value(), signal));
+            }
+            // Stays None when there is less history than the Senkou Span B period
+            if let Some(reading) = ichimoku.update(candle.high, candle.low, candle.close) {
+                values.ichimoku = Some(reading);
+            }
+            if let Some((sar, af, sar_sig)) =
+                parabolic_sar.update(candle.high, candle.low, candle.close)
+            {
+                values.parabolic_sar_value = Some(sar);
+                values.parabolic_sar_af = Some(af);
+                values.parabolic_sar_signal = Some(sar_sig);
+            }
+            if let Some((adx_value, _, _)) = adx.update(candle.high, candle.low, candle.close) {
+                values.adx_value = Some(adx_value);
+            }
             
+            if let Some(source_rsi) = stoch_rsi_source.update(candle.close) {
+                if let Some((k, d, signal)) = stoch_rsi.update(source_rsi) {
+                    values.stoch_rsi_k = Some(k);
+                    values.stoch_rsi_d = Some(d);
+                    values.stoch_rsi_signal = Some(signal);
+                }
+            }
             if let Some(rsi_value) = rsi.update(candle.close) {
                 values.rsi_value = Some(rsi_value);
-                if let Some(prev) = prev_close {
-                    let price_change = candle.close - prev;
-                    values.rsi_signal = Some(rsi.get_signal(rsi_value, price_change));
+                rsi_history.push_back(rsi_value);
+                rsi_close_history.push_back(candle.close);
+                if rsi_history.len() > rsi::RSI_DIVERGENCE_LOOKBACK {
+                    rsi_history.pop_front();
+                    rsi_close_history.pop_front();
                 }
+                values.rsi_divergence = rsi::detect_rsi_divergence(
+                    rsi_close_history.make_contiguous(),
+                    rsi_history.make_contiguous(),
+                    rsi::RSI_DIVERGENCE_SWING_BARS,
+                );
+                values.rsi_signal = Some(rsi::RSI::classify(
+                    rsi_value,
+                    values.rsi_divergence,
+                    params.rsi.overbought,
+                    params.rsi.oversold,
+                ));
+            }
+
+            if let Some((wr_value, wr_sig)) = williams_r.update(candle.high, candle.low, candle.close) {
+                values.williams_r_value = Some(wr_value);
+                values.williams_r_signal = Some(wr_sig);
+            }
+
+            if let Some((cci_value, cci_sig)) = cci.update(candle.high, candle.low, candle.close) {
+                values.cci_value = Some(cci_value);
+                values.cci_signal = Some(cci_sig);
+            }
+
+            if let Some((mfi_value, mfi_sig)) =
+                mfi.update(candle.high, candle.low, candle.close, candle.volume)
+            {
+                values.mfi_value = Some(mfi_value);
+                values.mfi_signal = Some(mfi_sig);
+            }
+
+            if let Some((cmf_value, cmf_sig)) =
+                cmf.update(candle.high, candle.low, candle.close, candle.volume)
+            {
+                values.cmf_value = Some(cmf_value);
+                values.cmf_signal = Some(cmf_sig);
             }
 
             let (macd_val, macd_sig_val, macd_hist, macd_sig) = macd.update(candle.close);
@@ -211,35 +1237,102 @@ impl StrategyEvaluator {
             values.macd_signal_value = Some(macd_sig_val);
             values.macd_histogram = Some(macd_hist);
             values.macd_signal = Some(macd_sig);
+            for (key, calculator) in custom_macds.iter_mut() {
+                values
+                    .macd_by_params
+                    .insert(*key, calculator.update(candle.close));
+            }
 
             let (bb_upper, bb_middle, bb_lower, bb_sig) = bollinger.update(candle.close);
             values.bollinger_upper = Some(bb_upper);
             values.bollinger_middle = Some(bb_middle);
             values.bollinger_lower = Some(bb_lower);
             values.bollinger_signal = Some(bb_sig);
+            values.bb_percent_b = Some(bollinger::percent_b(candle.close, bb_upper, bb_lower));
+            values.bb_bandwidth = bollinger::bandwidth_pct(bb_upper, bb_middle, bb_lower);
 
-            let atr_value = atr.update(candle.high, candle.low, candle.close);
-            atr_history.push_back(atr_value);
-            if atr_history.len() > 14 {
-                atr_history.pop_front();
+            if let Some((kc_upper, kc_middle, kc_lower, kc_sig)) =
+                keltner.update(candle.high, candle.low, candle.close, Some((bb_upper, bb_lower)))
+            {
+                values.keltner_upper = Some(kc_upper);
+                values.keltner_middle = Some(kc_middle);
+                values.keltner_lower = Some(kc_lower);
+                values.keltner_signal = Some(kc_sig);
+            }
+
+            if let Some((dc_upper, dc_middle, dc_lower, dc_sig)) =
+                donchian.update(candle.high, candle.low, candle.close)
+            {
+                values.donchian_upper = Some(dc_upper);
+                values.donchian_middle = Some(dc_middle);
+                values.donchian_lower = Some(dc_lower);
+                values.donchian_signal = Some(dc_sig);
             }
+
+            let atr_value = atr.update(candle.high, candle.low, candle.close);
             values.atr_value = Some(atr_value);
+            // Compare against the mean of the previous ATRs, excluding the current one
             let lookback_avg = if atr_history.is_empty() {
                 atr_value
             } else {
                 atr_history.iter().sum::<f64>() / atr_history.len() as f64
             };
             values.volatility_regime = Some(atr.get_volatility_regime(atr_value, lookback_avg));
+            atr_history.push_back(atr_value);
+            if atr_history.len() > 14 {
+                atr_history.pop_front();
+            }
+
+            if let Some((atr_pct_value, regime)) =
+                atr_pct.update(candle.high, candle.low, candle.close)
+            {
+                values.atr_pct_value = Some(atr_pct_value);
+                values.volatility_regime_pct = Some(regime);
+            }
 
-            let (_, obv_sig) = obv.update(candle.close, candle.volume);
-            values.obv_signal = Some(obv_sig);
+            let (obv_value, obv_sig) = obv.update(candle.close, candle.volume);
+            obv_history.push_back(obv_value);
+            close_history.push_back(candle.close);
+            if obv_history.len() > obv::OBV_DIVERGENCE_LOOKBACK {
+                obv_history.pop_front();
+                close_history.pop_front();
+            }
+            // A divergence across the lookback outweighs the single-candle signal
+            let divergence = obv::detect_divergence(
+                obv_history.make_contiguous(),
+                close_history.make_contiguous(),
+                obv::OBV_DIVERGENCE_LOOKBACK,
+            );
+            values.obv_signal = Some(divergence.map_or(obv_sig, Into::into));
 
             volume_profile.update(candle.close, candle.volume);
-            let (_, _, vp_sig) = volume_profile.get_profile();
+            let (nodes, poc, vp_sig) = volume_profile.get_profile();
             values.volume_profile_signal = Some(vp_sig);
+            values.vp_poc_price = nodes.iter().any(|node| node.volume > 0.0).then_some(poc);
+
+            // A new session has no VWAP until it sees volume
+            match vwap.update(candle.timestamp, candle.close, candle.volume) {
+                Some((vwap_value, vwap_sig)) => {
+                    values.vwap_value = Some(vwap_value);
+                    values.vwap_signal = Some(vwap_sig);
+                }
+                None => {
+                    values.vwap_value = None;
+                    values.vwap_signal = None;
+                }
+            }
 
             if let Some(oi) = candle.open_interest {
                 values.oi_signal = Some(open_interest.update(oi, candle.close));
+                values.oi_trend = open_interest.trend();
+                oi_history.push_back(oi);
+                if oi_history.len() > values.oi_ma_period {
+                    oi_history.pop_front();
+                }
+                let oi_ma = oi_history.iter().sum::<f64>() / oi_history.len() as f64;
+                values.oi_value = Some(oi);
+                values.oi_ma = Some(oi_ma);
+                values.oi_score = Some(normalize_open_interest(oi, oi_ma));
             }
 
             if let Some(funding) = candle.funding_rate {
@@ -256,9 +1349,89 @@ impl StrategyEvaluator {
 
             // Store SuperTrend value
             values.supertrend_value = supertrend.value();
+        }
+
+        if let Some((low, high)) = volume_profile.value_area() {
+            values.vp_value_area_low = Some(low);
+            values.vp_value_area_high = Some(high);
+        }
+
+        // SMAs only need the latest window, so are computed once over the closes
+        let closes: Vec<f64> = candles.iter().map(|candle| candle.close).collect();
+        values.sma_20 = math::sma(&closes, 20);
+        values.sma_50 = math::sma(&closes, 50);
+        values.sma_200 = math::sma(&closes, 200);
+        values.sma_by_period = params
+            .sma_periods
+            .iter()
+            .filter_map(|&period| Some((period, math::sma(&closes, period as usize)?)))
+            .collect();
+        values.rsi_by_period = params
+            .rsi_periods
+            .iter()
+            .map(|&period| (period, Self::rsi_reading(&closes, period)))
+            .collect();
 
-            prev_close = Some(candle.close);
+        // Swings are found over the whole series rather than candle by candle
+        if let Some(levels) = fibonacci::FibonacciLevels::default().detect(candles) {
+            values.fib_support =
+                fibonacci::FibonacciLevels::nearest_support(current_price, &levels)
+                    .map(|(level, _)| level);
+            values.fib_resistance =
+                fibonacci::FibonacciLevels::nearest_resistance(current_price, &levels)
+                    .map(|(level, _)| level);
+            values.fib_position_ratio = levels.position_ratio(current_price);
         }
+        values.candlestick_pattern = candlestick::latest_pattern(candles);
+
+        let computation = |indicator, min_candles, computed| IndicatorComputation {
+            indicator,
+            min_candles,
+            candles: candles.len(),
+            computed,
+        };
+        values.computations = vec![
+            computation(
+                "ema",
+                params.ema_slow_period.max(params.ema_fast_period),
+                values.ema_slow.is_some(),
+            ),
+            computation(
+                "ema_ribbon",
+                ema_ribbon.lookback(),
+                values.ema_ribbon_score.is_some(),
+            ),
+            computation("kama", kama.lookback(), values.kama_value.is_some()),
+            computation("ichimoku", ichimoku.lookback(), values.ichimoku.is_some()),
+            computation(
+                "parabolic_sar",
+                parabolic_sar.lookback(),
+                values.parabolic_sar_value.is_some(),
+            ),
+            computation("adx", adx.lookback(), values.adx_value.is_some()),
+            computation("rsi", rsi.lookback(), values.rsi_value.is_some()),
+            // The stochastic's first RSI input is the source RSI's first reading
+            computation(
+                "stoch_rsi",
+                stoch_rsi_source.lookback() + stoch_rsi.lookback() - 1,
+                values.stoch_rsi_k.is_some(),
+            ),
+            computation(
+                "williams_r",
+                williams_r.lookback(),
+                values.williams_r_value.is_some(),
+            ),
+            computation("cci", cci.lookback(), values.cci_value.is_some()),
+            computation("mfi", mfi.lookback(), values.mfi_value.is_some()),
+            computation("cmf", cmf.lookback(), values.cmf_value.is_some()),
+            computation("keltner", keltner.lookback(), values.keltner_middle.is_some()),
+            computation(
+                "donchian",
+                donchian.lookback(),
+                values.donchian_middle.is_some(),
+            ),
+            computation("atr_pct", atr_pct.lookback(), values.atr_pct_value.is_some()),
+        ];
 
         values
     }
@@ -269,17 +1442,16 @@ impl StrategyEvaluator {
             RuleType::Condition => {
                 if let Some(ref condition) = rule.condition {
                     let passed = Self::evaluate_condition(condition, indicator_values);
+                    let indicator_value = Self::get_indicator_value(condition, indicator_values);
                     let score = if passed {
                         rule.weight.unwrap_or(1.0) as i32
                     } else {
                         -(rule.weight.unwrap_or(1.0) as i32)
                     };
-                    Some(RuleResult::new(
-                        rule.id.clone(),
-                        passed,
-                        score,
-                        rule.weight.unwrap_or(1.0),
-                    ))
+                    Some(
+                        RuleResult::new(rule.id.clone(), passed, score, rule.weight.unwrap_or(1.0))
+                            .with_indicator_value(indicator_value),
+                    )
                 } else {
                     None
                 }
@@ -319,18 +1491,18 @@ impl StrategyEvaluator {
     }
 
     /// Evaluate a condition against indicator values
-    fn evaluate_condition(condition: &Condition, indicator_values: &IndicatorValues) -> bool {
+    pub fn evaluate_condition(condition: &Condition, indicator_values: &IndicatorValues) -> bool {
         match condition.comparison {
             Comparison::SignalState => {
                 if let Some(ref signal_state) = condition.signal_state {
-                    Self::check_signal_state(condition.indicator, signal_state, indicator_values)
+                    Self::check_signal_state(condition, signal_state, indicator_values)
                 } else {
                     false
                 }
             }
             _ => {
                 // For numeric comparisons, get the indicator value
-                let value = Self::get_indicator_value(condition.indicator, indicator_values);
+                let value = Self::get_indicator_value(condition, indicator_values);
                 if let Some(val) = value {
                     Self::compare_value(val, condition.comparison, condition.threshold)
                 } else {
@@ -341,28 +1513,68 @@ impl StrategyEvaluator {
     }
 
     /// Get numeric value for an indicator
-    fn get_indicator_value(indicator: IndicatorType, values: &IndicatorValues) -> Option<f64> {
-        match indicator {
-            IndicatorType::RSI => values.rsi_value,
-            IndicatorType::MACD => values.macd_value,
+    fn get_indicator_value(condition: &Condition, values: &IndicatorValues) -> Option<f64> {
+        match condition.indicator {
+            IndicatorType::RSI => values.rsi_for(condition).0,
+            IndicatorType::StochRsi => values.stoch_rsi_k,
+            IndicatorType::WilliamsR => values.williams_r_value,
+            IndicatorType::CCI => values.cci_value,
+            IndicatorType::MFI => values.mfi_value,
+            IndicatorType::CMF => values.cmf_value,
+            IndicatorType::MACD => values.macd_for(condition).0,
             IndicatorType::EMA => values.ema_fast,
+            IndicatorType::SMA
+            | IndicatorType::SMA20
+            | IndicatorType::SMA50
+            | IndicatorType::SMA200 => values.sma_for(condition),
+            IndicatorType::EmaRibbon => values.ema_ribbon_score,
+            IndicatorType::KAMA => values.kama_value,
             IndicatorType::ATR => values.atr_value,
+            IndicatorType::AtrPct => values.atr_pct_value,
             IndicatorType::Bollinger => values.bollinger_middle,
-            IndicatorType::SuperTrend => values.supertrend_value,
+            IndicatorType::BollingerPctB => values.bb_percent_b,
+            IndicatorType::BollingerBandwidth => values.bb_bandwidth,
+            IndicatorType::Keltner => values.keltner_middle,
+            IndicatorType::Donchian => values.donchian_middle,
+            IndicatorType::SuperTrend => values.supertrend_for(condition).0,
+            IndicatorType::Ichimoku => values.ichimoku.map(|i| i.kijun_sen),
+            IndicatorType::ParabolicSar => values.parabolic_sar_value,
+            IndicatorType::Fibonacci => values.fib_position_ratio,
+            IndicatorType::CandlestickPattern => values.candlestick_pattern.map(|p| p.score()),
             IndicatorType::FundingRate => values.funding_rate_value,
-            _ => None, // OBV, VolumeProfile, OpenInterest don't have simple numeric values
+            IndicatorType::OpenInterest => values.oi_score,
+            IndicatorType::VWAP => values.vwap_value,
+            IndicatorType::VolumeProfilePOC => values.vp_poc_price,
+            IndicatorType::VolumeProfileVAH => values.vp_value_area_high,
+            IndicatorType::VolumeProfileVAL => values.vp_value_area_low,
+            IndicatorType::Spread => values
+                .bid_ask_spread_pct
+                .map(|pct| pct * spread::BPS_PER_PCT),
+            IndicatorType::BuySellRatio => values.buy_sell_ratio,
+            IndicatorType::TimeOfDay => Some(f64::from(values.evaluated_at.hour())),
+            _ => None, // OBV and VolumeProfile don't have simple numeric values
         }
     }
 
     /// Check signal state for an indicator
     fn check_signal_state(
-        indicator: IndicatorType,
+        condition: &Condition,
         signal_state: &str,
         values: &IndicatorValues,
     ) -> bool {
-        match indicator {
+        match condition.indicator {
             IndicatorType::RSI => {
-                if let Some(signal) = values.rsi_signal {
+                if let (Some(rsi_value), divergence) = values.rsi_for(condition) {
+                    let signal = rsi::RSI::classify(
+                        rsi_value,
+                        divergence,
+                        condition
+                            .param_f64("overbought_threshold")
+                            .unwrap_or(rsi::DEFAULT_OVERBOUGHT),
+                        condition
+                            .param_f64("oversold_threshold")
+                            .unwrap_or(rsi::DEFAULT_OVERSOLD),
+                    );
                     match signal_state {
                         "Oversold" => matches!(signal, rsi::RSISignal::Oversold),
                         "Overbought" => matches!(signal, rsi::RSISignal::Overbought),
@@ -374,11 +1586,85 @@ impl StrategyEvaluator {
                     false
                 }
             }
+            IndicatorType::StochRsi => {
+                if let Some(signal) = values.stoch_rsi_signal {
+                    use stochastic_rsi::StochRsiSignal;
+                    match signal_state {
+                        "Oversold" => matches!(signal, StochRsiSignal::Oversold),
+                        "Overbought" => matches!(signal, StochRsiSignal::Overbought),
+                        "BullishCross" => matches!(signal, StochRsiSignal::BullishCross),
+                        "BearishCross" => matches!(signal, StochRsiSignal::BearishCross),
+                        _ => false,
+                    }
+                } else {
+                    false
+                }
+            }
+            IndicatorType::CCI => {
+                if let Some(signal) = values.cci_signal {
+                    use cci::CCISignal;
+                    match signal_state {
+                        "Overbought" => matches!(signal, CCISignal::Overbought),
+                        "Oversold" => matches!(signal, CCISignal::Oversold),
+                        "BullishZeroCross" => matches!(signal, CCISignal::BullishZeroCross),
+                        "BearishZeroCross" => matches!(signal, CCISignal::BearishZeroCross),
+                        "Neutral" => matches!(signal, CCISignal::Neutral),
+                        _ => false,
+                    }
+                } else {
+                    false
+                }
+            }
+            IndicatorType::MFI => {
+                if let Some(signal) = values.mfi_signal {
+                    use mfi::MFISignal;
+                    match signal_state {
+                        "Overbought" => matches!(signal, MFISignal::Overbought),
+                        "Oversold" => matches!(signal, MFISignal::Oversold),
+                        "Neutral" => matches!(signal, MFISignal::Neutral),
+                        _ => false,
+                    }
+                } else {
+                    false
+                }
+            }
+            IndicatorType::CMF => {
+                if let Some(signal) = values.cmf_signal {
+                    use cmf::CMFSignal;
+                    match signal_state {
+                        "Bullish" => matches!(signal, CMFSignal::Bullish),
+                        "Bearish" => matches!(signal, CMFSignal::Bearish),
+                        "Neutral" => matches!(signal, CMFSignal::Neutral),
+                        _ => false,
+                    }
+                } else {
+                    false
+                }
+            }
+            IndicatorType::WilliamsR => {
+                if let Some(signal) = values.williams_r_signal {
+                    use williams_r::WilliamsRSignal;
+                    match signal_state {
+                        "Overbought" => matches!(signal, WilliamsRSignal::Overbought),
+                        "Oversold" => matches!(signal, WilliamsRSignal::Oversold),
+                        "Neutral" => matches!(signal, WilliamsRSignal::Neutral),
+                        _ => false,
+                    }
+                } else {
+                    false
+                }
+            }
             IndicatorType::EMA => {
                 if let Some(signal) = values.ema_signal {
                     match signal_state {
                         "BullishCross" => matches!(signal, ema::EMATrendSignal::BullishCross),
                         "BearishCross" => matches!(signal, ema::EMATrendSignal::BearishCross),
+                        "PendingBullishCross" => {
+                            matches!(signal, ema::EMATrendSignal::PendingBullishCross)
+                        }
+                        "PendingBearishCross" => {
+                            matches!(signal, ema::EMATrendSignal::PendingBearishCross)
+                        }
                         "StrongUptrend" => matches!(signal, ema::EMATrendSignal::StrongUptrend),
                         "StrongDowntrend" => matches!(signal, ema::EMATrendSignal::StrongDowntrend),
                         _ => false,
@@ -387,8 +1673,37 @@ impl StrategyEvaluator {
                     false
                 }
             }
+            IndicatorType::EmaRibbon => {
+                if let Some(signal) = values.ema_ribbon_signal {
+                    use ema::RibbonSignal;
+                    match signal_state {
+                        "StrongBull" => matches!(signal, RibbonSignal::StrongBull),
+                        "StrongBear" => matches!(signal, RibbonSignal::StrongBear),
+                        "Expanding" => matches!(signal, RibbonSignal::Expanding),
+                        "Contracting" => matches!(signal, RibbonSignal::Contracting),
+                        "Flat" => matches!(signal, RibbonSignal::Flat),
+                        _ => false,
+                    }
+                } else {
+                    false
+                }
+            }
+            IndicatorType::KAMA => {
+                if let Some(signal) = values.kama_signal {
+                    use kama::KamaSignal;
+                    match signal_state {
+                        "Trending" => matches!(signal, KamaSignal::Trending),
+                        "Flat" => matches!(signal, KamaSignal::Flat),
+                        "BullishBreak" => matches!(signal, KamaSignal::BullishBreak),
+                        "BearishBreak" => matches!(signal, KamaSignal::BearishBreak),
+                        _ => false,
+                    }
+                } else {
+                    false
+                }
+            }
             IndicatorType::MACD => {
-                if let Some(signal) = values.macd_signal {
+                if let Some(signal) = values.macd_for(condition).1 {
                     match signal_state {
                         "BullishCross" => matches!(signal, macd::MACDSignal::BullishCross),
                         "BearishCross" => matches!(signal, macd::MACDSignal::BearishCross),
@@ -400,6 +1715,157 @@ impl StrategyEvaluator {
                     false
                 }
             }
+            IndicatorType::OpenInterest => {
+                if let Some(trend) = values.oi_trend {
+                    use open_interest::OITrendSignal;
+                    match signal_state {
+                        "StrongIncrease" => matches!(trend.signal, OITrendSignal::StrongIncrease),
+                        "StrongDecrease" => matches!(trend.signal, OITrendSignal::StrongDecrease),
+                        "WeakIncrease" => matches!(trend.signal, OITrendSignal::WeakIncrease),
+                        "WeakDecrease" => matches!(trend.signal, OITrendSignal::WeakDecrease),
+                        "Consolidating" => matches!(trend.signal, OITrendSignal::Consolidating),
+                        _ => false,
+                    }
+                } else {
+                    false
+                }
+            }
+            IndicatorType::OBV => {
+                if let Some(signal) = values.obv_signal {
+                    use obv::OBVSignal;
+                    match signal_state {
+                        "BullishDivergence" => matches!(signal, OBVSignal::BullishDivergence),
+                        "BearishDivergence" => matches!(signal, OBVSignal::BearishDivergence),
+                        "Confirmation" => matches!(signal, OBVSignal::Confirmation),
+                        "Neutral" => matches!(signal, OBVSignal::Neutral),
+                        _ => false,
+                    }
+                } else {
+                    false
+                }
+            }
+            IndicatorType::Keltner => {
+                if let Some(signal) = values.keltner_signal {
+                    use keltner::KeltnerSignal;
+                    match signal_state {
+                        "Breakout" => matches!(signal, KeltnerSignal::Breakout),
+                        "Breakdown" => matches!(signal, KeltnerSignal::Breakdown),
+                        "InsideChannel" => matches!(signal, KeltnerSignal::InsideChannel),
+                        "BollingerSqueeze" => matches!(signal, KeltnerSignal::BollingerSqueeze),
+                        _ => false,
+                    }
+                } else {
+                    false
+                }
+            }
+            IndicatorType::Donchian => {
+                if let Some(signal) = values.donchian_signal {
+                    use donchian::DonchianSignal;
+                    match signal_state {
+                        "UpperBreakout" => matches!(signal, DonchianSignal::UpperBreakout),
+                        "LowerBreakdown" => matches!(signal, DonchianSignal::LowerBreakdown),
+                        "Midline" => matches!(signal, DonchianSignal::Midline),
+                        _ => false,
+                    }
+                } else {
+                    false
+                }
+            }
+            IndicatorType::Ichimoku => {
+                if let Some(reading) = values.ichimoku {
+                    use ichimoku::IchimokuSignal;
+                    match signal_state {
+                        "PriceAboveCloud" => matches!(reading.signal, IchimokuSignal::PriceAboveCloud),
+                        "PriceBelowCloud" => matches!(reading.signal, IchimokuSignal::PriceBelowCloud),
+                        "TKCross" => matches!(reading.signal, IchimokuSignal::TKCross),
+                        "CloudTwist" => matches!(reading.signal, IchimokuSignal::CloudTwist),
+                        _ => false,
+                    }
+                } else {
+                    false
+                }
+            }
+            IndicatorType::SuperTrend => {
+                if let (_, Some(signal)) = values.supertrend_for(condition) {
+                    use supertrend::SuperTrendSignal;
+                    match signal_state {
+                        "Bullish" => matches!(signal, SuperTrendSignal::Bullish),
+                        "Bearish" => matches!(signal, SuperTrendSignal::Bearish),
+                        "BullishFlip" => matches!(signal, SuperTrendSignal::BullishFlip),
+                        "BearishFlip" => matches!(signal, SuperTrendSignal::BearishFlip),
+                        _ => false,
+                    }
+                } else {
+                    false
+                }
+            }
+            IndicatorType::Bollinger => {
+                if let Some(signal) = values.bollinger_signal {
+                    use bollinger::BollingerSignal;
+                    match signal_state {
+                        "Squeeze" => matches!(signal, BollingerSignal::Squeeze),
+                        "UpperBreakout" => matches!(signal, BollingerSignal::UpperBreakout),
+                        "LowerBreakout" => matches!(signal, BollingerSignal::LowerBreakout),
+                        "WalkingBands" => matches!(signal, BollingerSignal::WalkingBands),
+                        "MeanReversion" => matches!(signal, BollingerSignal::MeanReversion),
+                        "Neutral" => matches!(signal, BollingerSignal::Neutral),
+                        _ => false,
+                    }
+                } else {
+                    false
+                }
+            }
+            IndicatorType::ParabolicSar => {
+                if let Some(signal) = values.parabolic_sar_signal {
+                    use parabolic_sar::ParabolicSarSignal;
+                    match signal_state {
+                        "Bullish" => matches!(signal, ParabolicSarSignal::Bullish),
+                        "Bearish" => matches!(signal, ParabolicSarSignal::Bearish),
+                        "Flip" => matches!(signal, ParabolicSarSignal::Flip),
+                        _ => false,
+                    }
+                } else {
+                    false
+                }
+            }
+            IndicatorType::VWAP => {
+                if let Some(signal) = values.vwap_signal {
+                    match signal_state {
+                        "PriceAbove" => matches!(signal, vwap::VwapSignal::PriceAbove),
+                        "PriceBelow" => matches!(signal, vwap::VwapSignal::PriceBelow),
+                        "Crossing" => matches!(signal, vwap::VwapSignal::Crossing),
+                        _ => false,
+                    }
+                } else {
+                    false
+                }
+            }
+            IndicatorType::CandlestickPattern => {
+                if let Some(pattern) = values.candlestick_pattern {
+                    use candlestick::PatternSignal;
+                    match signal_state {
+                        "Bullish" => pattern.signal == PatternSignal::Bullish,
+                        "Bearish" => pattern.signal == PatternSignal::Bearish,
+                        name => pattern.kind.as_str() == name,
+                    }
+                } else {
+                    false
+                }
+            }
+            IndicatorType::AtrPct => {
+                if let Some(regime) = values.volatility_regime_pct {
+                    use atr::VolatilityRegime;
+                    match signal_state {
+                        "High" => matches!(regime, VolatilityRegime::High),
+                        "Elevated" => matches!(regime, VolatilityRegime::Elevated),
+                        "Normal" => matches!(regime, VolatilityRegime::Normal),
+                        "Low" => matches!(regime, VolatilityRegime::Low),
+                        _ => false,
+                    }
+                } else {
+                    false
+                }
+            }
             _ => false, // Other indicators not yet implemented
         }
     }
@@ -421,34 +1887,134 @@ impl StrategyEvaluator {
         }
     }
 
-    /// Aggregate rule results according to aggregation config
-    fn aggregate_results(results: &[RuleResult], config: &AggregationConfig) -> i32 {
-        match config.method {
-            AggregationMethod::Sum => results.iter().map(|r| r.score).sum(),
-            AggregationMethod::WeightedSum => {
-                results.iter().map(|r| (r.score as f64 * r.weight) as i32).sum()
+    /// Set each result's share of [`Self::aggregate_results`]. Under
+    /// `Majority` a rule contributes its vote (±1), and under `All`/`Any` every
+    /// rule contributes nothing when the gate is not met. Nothing contributes
+    /// while the aggregation filter is not met. Manual category weights scale
+    /// each contribution.
+    fn assign_contributions(
+        results: &mut [RuleResult],
+        config: &StrategyConfig,
+        values: &IndicatorValues,
+    ) {
+        let category_multipliers = CategoryWeights::rule_multipliers(config);
+        let config = &config.aggregation;
+        let filter_met = Self::filter_met(config.filter, values);
+        let gate_met = match config.method {
+            AggregationMethod::All => results.iter().all(|r| r.passed),
+            AggregationMethod::Any => results.iter().any(|r| r.passed),
+            _ => true,
+        };
+        for result in results.iter_mut() {
+            let contribution = match config.method {
+                _ if !filter_met => 0,
+                AggregationMethod::WeightedSum => (result.score as f64 * result.weight) as i32,
+                AggregationMethod::Majority => result.score.signum(),
+                _ if gate_met => result.score,
+                _ => 0,
+            };
+            let multiplier = category_multipliers
+                .as_ref()
+                .and_then(|multipliers| multipliers.get(&result.rule_id))
+                .copied()
+                .unwrap_or(1.0);
+            result.contribution = (f64::from(contribution) * multiplier).round() as i32;
+        }
+    }
+
+    /// Whether the market passes the aggregation `filter` (always, without one)
+    fn filter_met(filter: Option<AggregationFilter>, values: &IndicatorValues) -> bool {
+        match filter {
+            None => true,
+            Some(AggregationFilter::MinAdx { threshold }) => {
+                values.adx_value.is_some_and(|adx| adx >= threshold)
             }
+            Some(AggregationFilter::MaxVolatilityRegime { regime }) => !values
+                .volatility_regime
+                .is_some_and(|current| current.exceeds(regime)),
+        }
+    }
+
+    /// Aggregated score scaled by the time filter multiplier for the hour
+    /// the values were computed, as compared with the thresholds
+    pub fn strategy_score(
+        results: &[RuleResult],
+        config: &StrategyConfig,
+        values: &IndicatorValues,
+    ) -> i32 {
+        let category_multipliers = CategoryWeights::rule_multipliers(config);
+        let score = Self::aggregate_results(
+            results,
+            &config.aggregation,
+            values,
+            category_multipliers.as_ref(),
+        );
+        let multiplier = config.time_multiplier(values.evaluated_at.hour());
+        (score as f64 * multiplier) as i32
+    }
+
+    /// Aggregate rule results under `config`'s method; zero while the
+    /// aggregation filter is not met.
+    ///
+    /// `category_multipliers` (see [`CategoryWeights::rule_multipliers`])
+    /// scale each rule's score, or its vote under `Majority`, before the
+    /// total is rounded.
+    fn aggregate_results(
+        results: &[RuleResult],
+        config: &AggregationConfig,
+        values: &IndicatorValues,
+        category_multipliers: Option<&HashMap<String, f64>>,
+    ) -> i32 {
+        if !Self::filter_met(config.filter, values) {
+            return 0;
+        }
+        let multiplier = |result: &RuleResult| {
+            category_multipliers
+                .and_then(|multipliers| multipliers.get(&result.rule_id))
+                .copied()
+                .unwrap_or(1.0)
+        };
+        let weighted_sum = |results: &[RuleResult]| {
+            results
+                .iter()
+                .map(|r| f64::from(r.score) * multiplier(r))
+                .sum::<f64>()
+                .round() as i32
+        };
+        match config.method {
+            AggregationMethod::Sum => weighted_sum(results),
+            AggregationMethod::WeightedSum => results
+                .iter()
+                .map(|r| f64::from((r.score as f64 * r.weight) as i32) * multiplier(r))
+                .sum::<f64>()
+                .round() as i32,
             AggregationMethod::Majority => {
-                let positive = results.iter().filter(|r| r.score > 0).count();
-                let negative = results.iter().filter(|r| r.score < 0).count();
+                let votes = |positive: bool| {
+                    results
+                        .iter()
+                        .filter(|r| if positive { r.score > 0 } else { r.score < 0 })
+                        .map(multiplier)
+                        .sum::<f64>()
+                };
+                let (positive, negative) = (votes(true), votes(false));
                 if positive > negative {
-                    positive as i32
+                    positive.round() as i32
                 } else if negative > positive {
-                    -(negative as i32)
+                    -(negative.round() as i32)
                 } else {
                     0
                 }
             }
             AggregationMethod::All => {
                 if results.iter().all(|r| r.passed) {
-                    results.iter().map(|r| r.score).sum()
+                    weighted_sum(results)
                 } else {
                     0
                 }
             }
             AggregationMethod::Any => {
                 if results.iter().any(|r| r.passed) {
-                    results.iter().map(|r| r.score).sum()
+                    weighted_sum(results)
                 } else {
                     0
                 }
@@ -1,5 +1,11 @@
 //! Strategy definitions that consume indicators and emit intents.
 
 pub mod evaluator;
+pub mod multi_timeframe;
+pub mod templates;
+pub mod validation;
 
-pub use evaluator::{IndicatorValues, StrategyEvaluator};
+pub use evaluator::{IndicatorValues, LiveMarketData, StrategyEvaluator};
+pub use multi_timeframe::MultiTimeframeEvaluator;
+pub use templates::{StrategyTemplate, Templates};
+pub use validation::{validate_strategy_config, ValidationError};
@@ -0,0 +1,208 @@
+//! Strategy evaluation across several candle intervals.
+
+use crate::indicators::structure::candle_aggregator::{aggregate_candles, interval_minutes};
+use crate::metrics::Metrics;
+use crate::models::indicators::Candle;
+use crate::models::signal::SignalOutput;
+use crate::models::strategy::{RuleResult, Strategy};
+use crate::strategies::evaluator::{
+    IndicatorValues, LiveMarketData, StrategyEvaluator, MIN_CANDLES,
+};
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+/// Weight for intervals missing from `StrategyConfig::timeframe_weights`
+const DEFAULT_TIMEFRAME_WEIGHT: f64 = 1.0;
+
+/// Evaluates a strategy on every interval and merges the per-rule results.
+///
+/// Each rule's score is the weighted average of its score on each timeframe,
+/// so a rule that fires long on one interval and short on another is pulled
+/// toward whichever interval carries more weight. A rule passes when the
+/// timeframes it passed on hold at least half of the weight. SL/TP and the
+/// reported price come from the heaviest timeframe.
+///
+/// Intervals listed in `timeframe_weights` that were not fetched, or have too
+/// few candles, are synthesized by aggregating the finest interval that
+/// divides them evenly, so a single 1m feed can drive 15m and 1h rules.
+pub struct MultiTimeframeEvaluator;
+
+impl MultiTimeframeEvaluator {
+    pub fn evaluate(
+        strategy: &Strategy,
+        candles_by_interval: &HashMap<String, Vec<Candle>>,
+    ) -> Option<SignalOutput> {
+        Self::evaluate_with_spread(strategy, candles_by_interval, None)
+    }
+
+    /// Like [`Self::evaluate`], applying the current `(best_bid, best_ask)` to
+    /// every timeframe
+    pub fn evaluate_with_spread(
+        strategy: &Strategy,
+        candles_by_interval: &HashMap<String, Vec<Candle>>,
+        top_of_book: Option<(f64, f64)>,
+    ) -> Option<SignalOutput> {
+        let market_data = LiveMarketData {
+            top_of_book,
+            ..LiveMarketData::default()
+        };
+        Self::evaluate_with_market_data(strategy, candles_by_interval, market_data, None)
+    }
+
+    /// Like [`Self::evaluate`], applying the same live readings to every
+    /// timeframe. Each timeframe's indicator computations are recorded in
+    /// `metrics` when given.
+    pub fn evaluate_with_market_data(
+        strategy: &Strategy,
+        candles_by_interval: &HashMap<String, Vec<Candle>>,
+        market_data: LiveMarketData,
+        metrics: Option<&Metrics>,
+    ) -> Option<SignalOutput> {
+        let candles_by_interval = Self::with_aggregated_timeframes(strategy, candles_by_interval);
+        let mut timeframes: Vec<TimeframeResult> = candles_by_interval
+            .iter()
+            .filter(|(_, candles)| candles.len() >= MIN_CANDLES)
+            .filter_map(|(interval, candles)| {
+                let weight = Self::timeframe_weight(strategy, interval);
+                if weight <= 0.0 {
+                    return None;
+                }
+                let current_price = candles.last()?.close;
+                let mut values = StrategyEvaluator::compute_strategy_indicators(
+                    strategy,
+                    candles,
+                    current_price,
+                );
+                values.set_market_data(market_data);
+                if let Some(metrics) = metrics {
+                    metrics.record_indicator_computations(&values.computations);
+                }
+                let rule_results = StrategyEvaluator::evaluate_rules(strategy, &values);
+                Some(TimeframeResult {
+                    interval: interval.clone(),
+                    weight,
+                    values,
+                    rule_results,
+                    current_price,
+                })
+            })
+            .collect();
+
+        // Heaviest timeframe first; ties broken by interval for a stable choice
+        timeframes.sort_by(|a, b| {
+            b.weight
+                .total_cmp(&a.weight)
+                .then_with(|| a.interval.cmp(&b.interval))
+        });
+        let primary = timeframes.first()?;
+
+        let rule_results = Self::merge_rule_results(strategy, &timeframes);
+        StrategyEvaluator::build_signal(
+            strategy,
+            &rule_results,
+            &primary.values,
+            primary.current_price,
+        )
+    }
+
+    /// `candles_by_interval` plus any weighted interval that can only be built
+    /// by aggregation; borrowed unchanged when nothing needs synthesizing
+    pub fn with_aggregated_timeframes<'a>(
+        strategy: &Strategy,
+        candles_by_interval: &'a HashMap<String, Vec<Candle>>,
+    ) -> Cow<'a, HashMap<String, Vec<Candle>>> {
+        let Some(ref weights) = strategy.config.timeframe_weights else {
+            return Cow::Borrowed(candles_by_interval);
+        };
+
+        // Finest usable series first
+        let mut sources: Vec<(u32, &Vec<Candle>)> = candles_by_interval
+            .iter()
+            .filter(|(_, candles)| candles.len() >= MIN_CANDLES)
+            .filter_map(|(interval, candles)| Some((interval_minutes(interval)?, candles)))
+            .collect();
+        sources.sort_by_key(|(minutes, _)| *minutes);
+
+        let mut candles_by_interval = Cow::Borrowed(candles_by_interval);
+        for interval in weights.keys() {
+            let have = candles_by_interval.get(interval).map_or(0, Vec::len);
+            if have >= MIN_CANDLES || Self::timeframe_weight(strategy, interval) <= 0.0 {
+                continue;
+            }
+            let Some(target) = interval_minutes(interval) else {
+                continue;
+            };
+            let Some((_, source)) = sources
+                .iter()
+                .find(|(minutes, _)| *minutes < target && target % *minutes == 0)
+            else {
+                continue;
+            };
+
+            let aggregated = aggregate_candles(source, target);
+            if aggregated.len() > have {
+                candles_by_interval
+                    .to_mut()
+                    .insert(interval.clone(), aggregated);
+            }
+        }
+        candles_by_interval
+    }
+
+    fn timeframe_weight(strategy: &Strategy, interval: &str) -> f64 {
+        strategy
+            .config
+            .timeframe_weights
+            .as_ref()
+            .and_then(|weights| weights.get(interval))
+            .copied()
+            .filter(|weight| weight.is_finite())
+            .unwrap_or(DEFAULT_TIMEFRAME_WEIGHT)
+    }
+
+    fn merge_rule_results(strategy: &Strategy, timeframes: &[TimeframeResult]) -> Vec<RuleResult> {
+        strategy
+            .config
+            .rules
+            .iter()
+            .filter_map(|rule| {
+                let mut total_weight = 0.0;
+                let mut passed_weight = 0.0;
+                let mut weighted_score = 0.0;
+                let mut rule_weight = None;
+
+                for timeframe in timeframes {
+                    let Some(result) = timeframe
+                        .rule_results
+                        .iter()
+                        .find(|result| result.rule_id == rule.id)
+                    else {
+                        continue;
+                    };
+                    total_weight += timeframe.weight;
+                    weighted_score += result.score as f64 * timeframe.weight;
+                    if result.passed {
+                        passed_weight += timeframe.weight;
+                    }
+                    rule_weight.get_or_insert(result.weight);
+                }
+
+                let rule_weight = rule_weight?;
+                Some(RuleResult::new(
+                    rule.id.clone(),
+                    passed_weight * 2.0 >= total_weight,
+                    (weighted_score / total_weight).round() as i32,
+                    rule_weight,
+                ))
+            })
+            .collect()
+    }
+}
+
+struct TimeframeResult {
+    interval: String,
+    weight: f64,
+    values: IndicatorValues,
+    rule_results: Vec<RuleResult>,
+    current_price: f64,
+}
@@ -0,0 +1,268 @@
+//! Pre-built strategy configs that new strategies can start from.
+
+use crate::indicators::trend::adx::ADX_TRENDING_THRESHOLD;
+use crate::indicators::volatility::atr::VolatilityRegime;
+use crate::models::strategy::{
+    AggregationConfig, AggregationFilter, AggregationMethod, Comparison, Condition, IndicatorType,
+    PositionSizeModel, Rule, RuleType, SignalThresholds, SlTpModel, StrategyConfig,
+    DEFAULT_MAX_KELLY_FRACTION,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use utoipa::ToSchema;
+
+/// Name and summary of a template, as listed by the API
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct StrategyTemplate {
+    /// Template name, passed as `from_template` when creating a strategy
+    pub name: String,
+    /// What the template trades on
+    pub description: String,
+}
+
+/// Named strategy templates.
+///
+/// Each rule is a condition that passes when the market favors a long, so a
+/// failing rule votes short with the same weight. The thresholds require every
+/// rule to agree before a direction is emitted.
+pub struct Templates;
+
+type TemplateEntry = (&'static str, &'static str, fn() -> StrategyConfig);
+
+const TEMPLATES: [TemplateEntry; 6] = [
+    (
+        "macd_rsi_basic",
+        "MACD bullish momentum confirmed by RSI above 50",
+        Templates::macd_rsi_basic,
+    ),
+    (
+        "trend_following_ema_supertrend",
+        "EMA 20/50 strong trend confirmed by SuperTrend direction",
+        Templates::trend_following_ema_supertrend,
+    ),
+    (
+        "mean_reversion_bollinger_rsi",
+        "Buys stretched dips: RSI below 40 with a close under the lower Bollinger Band",
+        Templates::mean_reversion_bollinger_rsi,
+    ),
+    (
+        "funding_rate_contrarian",
+        "Trades against crowded positioning: long on negative funding, short on positive",
+        Templates::funding_rate_contrarian,
+    ),
+    (
+        "trend_following_adx_filtered",
+        "EMA 20/50 and SuperTrend trend following, silent while ADX is below 25",
+        Templates::trend_following_adx_filtered,
+    ),
+    (
+        "mean_reversion_calm_markets",
+        "Bollinger/RSI dip buying that sits out High volatility regimes",
+        Templates::mean_reversion_calm_markets,
+    ),
+];
+
+impl Templates {
+    /// All templates, in a stable order
+    pub fn list() -> Vec<StrategyTemplate> {
+        TEMPLATES
+            .iter()
+            .map(|(name, description, _)| StrategyTemplate {
+                name: name.to_string(),
+                description: description.to_string(),
+            })
+            .collect()
+    }
+
+    /// Config for the named template, or `None` if there is no such template
+    pub fn get(name: &str) -> Option<StrategyConfig> {
+        TEMPLATES
+            .iter()
+            .find(|(template, _, _)| *template == name)
+            .map(|(_, _, config)| config())
+    }
+
+    /// MACD bullish momentum (+1) and RSI above 50 (+1)
+    pub fn macd_rsi_basic() -> StrategyConfig {
+        config(
+            vec![
+                signal_state_rule(
+                    "macd_bullish_momentum",
+                    IndicatorType::MACD,
+                    "BullishMomentum",
+                    1.0,
+                ),
+                threshold_rule(
+                    "rsi_above_50",
+                    IndicatorType::RSI,
+                    Comparison::GreaterThan,
+                    50.0,
+                    1.0,
+                ),
+            ],
+            2,
+        )
+    }
+
+    /// EMA 20/50 strong uptrend (+2) and a bullish SuperTrend (+2)
+    pub fn trend_following_ema_supertrend() -> StrategyConfig {
+        config(
+            vec![
+                signal_state_rule(
+                    "ema_strong_uptrend",
+                    IndicatorType::EMA,
+                    "StrongUptrend",
+                    2.0,
+                ),
+                signal_state_rule(
+                    "supertrend_bullish",
+                    IndicatorType::SuperTrend,
+                    "Bullish",
+                    2.0,
+                ),
+            ],
+            4,
+        )
+    }
+
+    /// RSI below 40 (+2) and a close below the lower Bollinger Band (+1)
+    pub fn mean_reversion_bollinger_rsi() -> StrategyConfig {
+        config(
+            vec![
+                threshold_rule(
+                    "rsi_below_40",
+                    IndicatorType::RSI,
+                    Comparison::LessThan,
+                    40.0,
+                    2.0,
+                ),
+                signal_state_rule(
+                    "bollinger_lower_breakout",
+                    IndicatorType::Bollinger,
+                    "LowerBreakout",
+                    1.0,
+                ),
+            ],
+            3,
+        )
+    }
+
+    /// Negative funding (+2) and RSI below 50 (+1)
+    pub fn funding_rate_contrarian() -> StrategyConfig {
+        config(
+            vec![
+                threshold_rule(
+                    "funding_negative",
+                    IndicatorType::FundingRate,
+                    Comparison::LessThan,
+                    0.0,
+                    2.0,
+                ),
+                threshold_rule(
+                    "rsi_below_50",
+                    IndicatorType::RSI,
+                    Comparison::LessThan,
+                    50.0,
+                    1.0,
+                ),
+            ],
+            3,
+        )
+    }
+
+    /// [`Self::trend_following_ema_supertrend`], scoring only while ADX is
+    /// at least 25; ranging markets whipsaw trend followers
+    pub fn trend_following_adx_filtered() -> StrategyConfig {
+        with_filter(
+            Self::trend_following_ema_supertrend(),
+            AggregationFilter::MinAdx {
+                threshold: ADX_TRENDING_THRESHOLD,
+            },
+        )
+    }
+
+    /// [`Self::mean_reversion_bollinger_rsi`], scoring only while volatility
+    /// is at most Elevated; dips in a High regime tend to keep falling
+    pub fn mean_reversion_calm_markets() -> StrategyConfig {
+        with_filter(
+            Self::mean_reversion_bollinger_rsi(),
+            AggregationFilter::MaxVolatilityRegime {
+                regime: VolatilityRegime::Elevated,
+            },
+        )
+    }
+}
+
+/// Summed config that goes long at `+threshold` and short at `-threshold`
+fn config(rules: Vec<Rule>, threshold: i32) -> StrategyConfig {
+    StrategyConfig {
+        rules,
+        aggregation: AggregationConfig {
+            method: AggregationMethod::Sum,
+            thresholds: SignalThresholds {
+                long_min: threshold,
+                short_max: -threshold,
+            },
+            filter: None,
+        },
+        category_weights: None,
+        timeframe_weights: None,
+        decay_model: None,
+        position_size_model: PositionSizeModel::default(),
+        max_kelly_fraction: DEFAULT_MAX_KELLY_FRACTION,
+        sl_tp_model: SlTpModel::default(),
+        time_filter: None,
+        use_heikin_ashi: false,
+    }
+}
+
+/// `config` with its score gated by `filter`
+fn with_filter(mut config: StrategyConfig, filter: AggregationFilter) -> StrategyConfig {
+    config.aggregation.filter = Some(filter);
+    config
+}
+
+fn signal_state_rule(id: &str, indicator: IndicatorType, state: &str, weight: f64) -> Rule {
+    condition_rule(
+        id,
+        weight,
+        Condition {
+            indicator,
+            indicator_params: HashMap::new(),
+            comparison: Comparison::SignalState,
+            threshold: None,
+            signal_state: Some(state.to_string()),
+        },
+    )
+}
+
+fn threshold_rule(
+    id: &str,
+    indicator: IndicatorType,
+    comparison: Comparison,
+    threshold: f64,
+    weight: f64,
+) -> Rule {
+    condition_rule(
+        id,
+        weight,
+        Condition {
+            indicator,
+            indicator_params: HashMap::new(),
+            comparison,
+            threshold: Some(threshold),
+            signal_state: None,
+        },
+    )
+}
+
+fn condition_rule(id: &str, weight: f64, condition: Condition) -> Rule {
+    Rule {
+        id: id.to_string(),
+        rule_type: RuleType::Condition,
+        weight: Some(weight),
+        operator: None,
+        condition: Some(condition),
+        children: None,
+    }
+}
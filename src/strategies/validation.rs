@@ -0,0 +1,279 @@
+//! Structural validation for user-submitted strategy configs.
+
+use crate::indicators::error::IndicatorError;
+use crate::indicators::momentum::rsi;
+use crate::indicators::validation::validate_indicator_params;
+use crate::models::strategy::{
+    Condition, IndicatorType, PositionSizeModel, Rule, RuleType, SlTpModel, StrategyConfig,
+};
+use crate::strategies::evaluator::macd_key;
+use std::collections::HashSet;
+use std::fmt;
+
+/// Maximum number of top-level rules a strategy may define
+pub const MAX_STRATEGY_RULES: usize = 100;
+
+/// A reason a strategy config is rejected
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationError {
+    /// More than [`MAX_STRATEGY_RULES`] top-level rules
+    TooManyRules(usize),
+    /// Two rules share an ID
+    DuplicateRuleId(String),
+    /// A group contains a rule with the group's own ID
+    RuleCycle(String),
+    /// `long_min` is not above `short_max`, so one score could be both
+    ThresholdsOverlap { long_min: i32, short_max: i32 },
+    /// A condition's indicator params are missing, unknown or out of range
+    InvalidParams { rule_id: String, reason: String },
+    /// Every rule scores zero, so the strategy can only ever be neutral
+    NoDirectionalRule,
+    /// The decay model's half-life or display threshold is invalid
+    InvalidDecay(String),
+    /// The Kelly cap or fixed position size is not a fraction of equity
+    InvalidPositionSize(String),
+    /// A time filter window has an hour past 23, no hours, or a negative
+    /// multiplier
+    InvalidTimeFilter(String),
+    /// A stop loss / take profit percentage, ratio or multiplier is negative
+    /// or not finite
+    InvalidSlTp(String),
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::TooManyRules(count) => write!(
+                f,
+                "Strategy has {} rules, maximum is {}",
+                count, MAX_STRATEGY_RULES
+            ),
+            ValidationError::DuplicateRuleId(id) => {
+                write!(f, "Rule ID '{}' is used more than once", id)
+            }
+            ValidationError::RuleCycle(id) => {
+                write!(f, "Group '{}' contains a rule with its own ID", id)
+            }
+            ValidationError::ThresholdsOverlap {
+                long_min,
+                short_max,
+            } => write!(
+                f,
+                "long_min ({}) must be greater than short_max ({})",
+                long_min, short_max
+            ),
+            ValidationError::InvalidParams { rule_id, reason } => {
+                write!(f, "Rule '{}': {}", rule_id, reason)
+            }
+            ValidationError::NoDirectionalRule => write!(
+                f,
+                "Strategy needs at least one condition rule with a weight of 1 or more"
+            ),
+            ValidationError::InvalidDecay(reason) => write!(f, "Decay model: {}", reason),
+            ValidationError::InvalidPositionSize(reason) => {
+                write!(f, "Position sizing: {}", reason)
+            }
+            ValidationError::InvalidTimeFilter(reason) => write!(f, "Time filter: {}", reason),
+            ValidationError::InvalidSlTp(reason) => write!(f, "SL/TP model: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Check a strategy config, returning every problem found; empty when valid
+pub fn validate_strategy_config(config: &StrategyConfig) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+    if config.rules.len() > MAX_STRATEGY_RULES {
+        errors.push(ValidationError::TooManyRules(config.rules.len()));
+    }
+
+    let mut seen_ids = HashSet::new();
+    let mut ancestors = Vec::new();
+    for rule in &config.rules {
+        validate_rule(rule, &mut ancestors, &mut seen_ids, &mut errors);
+    }
+
+    let thresholds = &config.aggregation.thresholds;
+    if thresholds.long_min <= thresholds.short_max {
+        errors.push(ValidationError::ThresholdsOverlap {
+            long_min: thresholds.long_min,
+            short_max: thresholds.short_max,
+        });
+    }
+
+    if !config.rules.iter().any(is_directional) {
+        errors.push(ValidationError::NoDirectionalRule);
+    }
+
+    if let Some(decay) = config.decay_model {
+        if decay.half_life_seconds == 0 {
+            errors.push(ValidationError::InvalidDecay(
+                "half_life_seconds must be positive".to_string(),
+            ));
+        }
+        if !(0.0..=1.0).contains(&decay.min_display_threshold) {
+            errors.push(ValidationError::InvalidDecay(
+                IndicatorError::OutOfRange {
+                    field: "min_display_threshold".to_string(),
+                    value: decay.min_display_threshold,
+                    min: 0.0,
+                    max: 1.0,
+                }
+                .to_string(),
+            ));
+        }
+    }
+
+    if !(config.max_kelly_fraction > 0.0 && config.max_kelly_fraction <= 1.0) {
+        errors.push(ValidationError::InvalidPositionSize(format!(
+            "max_kelly_fraction must be in (0, 1], got {}",
+            config.max_kelly_fraction
+        )));
+    }
+    if let PositionSizeModel::Fixed(fraction) = config.position_size_model {
+        if !(0.0..=1.0).contains(&fraction) {
+            errors.push(ValidationError::InvalidPositionSize(format!(
+                "Fixed fraction must be in [0, 1], got {}",
+                fraction
+            )));
+        }
+    }
+
+    let sl_tp_fields = match config.sl_tp_model {
+        SlTpModel::AtrBased {
+            sl_multiplier,
+            tp_multiplier,
+        } => vec![
+            ("sl_multiplier", sl_multiplier),
+            ("tp_multiplier", tp_multiplier),
+        ],
+        SlTpModel::FixedPct { sl_pct, tp_pct } => vec![("sl_pct", sl_pct), ("tp_pct", tp_pct)],
+        SlTpModel::RiskReward { sl_pct, rr_ratio } => {
+            vec![("sl_pct", sl_pct), ("rr_ratio", rr_ratio)]
+        }
+        SlTpModel::BollingerBased { .. } => Vec::new(),
+    };
+    for (field, value) in sl_tp_fields {
+        if !(value.is_finite() && value >= 0.0) {
+            errors.push(ValidationError::InvalidSlTp(format!(
+                "{} must be finite and non-negative, got {}",
+                field, value
+            )));
+        }
+    }
+
+    for window in config.time_filter.iter().flatten() {
+        let (start, end) = (window.start_utc_hour, window.end_utc_hour);
+        if start > 23 || end > 23 {
+            errors.push(ValidationError::InvalidTimeFilter(format!(
+                "hours must be 0-23, got {}-{}",
+                start, end
+            )));
+        } else if start == end {
+            errors.push(ValidationError::InvalidTimeFilter(format!(
+                "window {}-{} covers no hours",
+                start, end
+            )));
+        }
+        if !(window.weight_multiplier.is_finite() && window.weight_multiplier >= 0.0) {
+            errors.push(ValidationError::InvalidTimeFilter(format!(
+                "weight_multiplier must be finite and non-negative, got {}",
+                window.weight_multiplier
+            )));
+        }
+    }
+
+    errors
+}
+
+/// Check `rule` and its children, given the IDs of the groups containing it
+/// and every ID seen so far
+fn validate_rule<'a>(
+    rule: &'a Rule,
+    ancestors: &mut Vec<&'a str>,
+    seen_ids: &mut HashSet<&'a str>,
+    errors: &mut Vec<ValidationError>,
+) {
+    let id = rule.id.as_str();
+    if ancestors.contains(&id) {
+        errors.push(ValidationError::RuleCycle(id.to_string()));
+    } else if !seen_ids.insert(id)
+        && !errors.contains(&ValidationError::DuplicateRuleId(id.to_string()))
+    {
+        errors.push(ValidationError::DuplicateRuleId(id.to_string()));
+    }
+
+    if let Some(ref condition) = rule.condition {
+        if let Err(e) = validate_condition_params(condition) {
+            errors.push(ValidationError::InvalidParams {
+                rule_id: id.to_string(),
+                reason: e.to_string(),
+            });
+        }
+    }
+    if let Some(ref children) = rule.children {
+        ancestors.push(id);
+        for child in children {
+            validate_rule(child, ancestors, seen_ids, errors);
+        }
+        ancestors.pop();
+    }
+}
+
+/// Whether `rule` can move the score: a condition whose weight truncates to
+/// a nonzero score, or a group containing one
+fn is_directional(rule: &Rule) -> bool {
+    match rule.rule_type {
+        RuleType::Condition => rule.condition.is_some() && rule.weight.unwrap_or(1.0) as i32 != 0,
+        RuleType::Group | RuleType::WeightedGroup => rule
+            .children
+            .as_ref()
+            .is_some_and(|children| children.iter().any(is_directional)),
+    }
+}
+
+/// Check `indicator_params` against the indicator's parameter schema, then
+/// that RSI thresholds and EMA and MACD periods are ordered
+fn validate_condition_params(condition: &Condition) -> Result<(), IndicatorError> {
+    validate_indicator_params(condition.indicator, &condition.indicator_params)?;
+    match condition.indicator {
+        IndicatorType::RSI => {
+            let overbought = condition
+                .param_f64("overbought_threshold")
+                .unwrap_or(rsi::DEFAULT_OVERBOUGHT);
+            let oversold = condition
+                .param_f64("oversold_threshold")
+                .unwrap_or(rsi::DEFAULT_OVERSOLD);
+            if overbought <= oversold {
+                return Err(IndicatorError::ValidationError(format!(
+                    "RSI overbought threshold ({}) must be greater than oversold threshold ({})",
+                    overbought, oversold
+                )));
+            }
+        }
+        IndicatorType::EMA => {
+            let fast = condition.param_f64("fast_period");
+            let slow = condition.param_f64("slow_period");
+            if let (Some(fast), Some(slow)) = (fast, slow) {
+                if fast >= slow {
+                    return Err(IndicatorError::ValidationError(format!(
+                        "EMA fast period ({}) must be less than slow period ({})",
+                        fast, slow
+                    )));
+                }
+            }
+        }
+        IndicatorType::MACD => {
+            let (fast, slow, _) = macd_key(condition);
+            if fast >= slow {
+                return Err(IndicatorError::ValidationError(format!(
+                    "MACD fast period ({}) must be less than slow period ({})",
+                    fast, slow
+                )));
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
@@ -4,6 +4,7 @@
 //! - api_server: HTTP API endpoints and business logic
 //! - websocket_service: WebSocket connection and data ingestion
 //! - worker: Job processing and workflow execution
+//! - database: QuestDB schema migrations
 
 #[path = "integration/api_server.rs"]
 mod api_server;
@@ -14,6 +15,9 @@ mod websocket_service;
 #[path = "integration/worker.rs"]
 mod worker;
 
+#[path = "integration/database.rs"]
+mod database;
+
 // Legacy integration tests (can be migrated to api_server)
 #[path = "integration/hyperliquid.rs"]
 mod hyperliquid;
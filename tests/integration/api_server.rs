@@ -5,9 +5,25 @@
 #[path = "api_server/test_utils.rs"]
 mod test_utils;
 
+use chrono::{Duration, Utc};
+use perptrix::cache::RedisCache;
+use perptrix::config::watcher::{ReloadStatus, StrategyWatcher};
+use perptrix::db::QuestDatabase;
+use perptrix::models::dead_letter::DeadLetter;
+use perptrix::models::indicators::Candle;
+use perptrix::models::signal::{SignalDirection, SignalFilter, SignalOutput};
+use perptrix::models::strategy::{
+    Comparison, Condition, EvaluationTrace, IndicatorType, Rule, RuleType, Strategy, StrategyConfig,
+};
+use perptrix::positions::PositionTracker;
+use perptrix::services::market_data::MockMarketDataProvider;
 use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
 
-use test_utils::TestApiServer;
+use perptrix::core::http::AppState;
+use test_utils::{serve, TestApiServer};
 
 #[tokio::test]
 async fn health_endpoint_reports_healthy_status() {
@@ -55,6 +71,14 @@ async fn metrics_endpoint_exposes_prometheus_metrics() {
         body.contains("http_requests_in_flight"),
         "Expected http_requests_in_flight metric"
     );
+    assert!(
+        body.contains("signal_confidence_histogram"),
+        "Expected signal_confidence_histogram metric"
+    );
+    assert!(
+        body.contains("signals_above_threshold_total"),
+        "Expected signals_above_threshold_total metric"
+    );
 }
 
 #[tokio::test]
@@ -122,6 +146,1742 @@ async fn api_server_handles_concurrent_requests() {
     }
 }
 
+fn assert_database_unavailable(response: axum_test::TestResponse) {
+    assert_eq!(response.status_code(), 503);
+    let body: Value = response.json();
+    assert_eq!(body["error"], "database_unavailable");
+    assert_eq!(body["message"], "QuestDB is not connected");
+}
+
+#[tokio::test]
+async fn strategy_endpoints_report_missing_database() {
+    let app = TestApiServer::new().await;
+
+    assert_database_unavailable(app.server.get("/api/strategies").await);
+    assert_database_unavailable(app.server.get("/api/strategies/1").await);
+    assert_database_unavailable(app.server.delete("/api/strategies/1").await);
+    assert_database_unavailable(app.server.post("/api/strategies/1/clone").await);
+    assert_database_unavailable(app.server.put("/api/strategies/1/enable").await);
+    assert_database_unavailable(app.server.put("/api/strategies/1/disable").await);
+    assert_database_unavailable(app.server.post("/api/strategies/1/live-evaluate").await);
+    assert_database_unavailable(
+        app.server
+            .post("/api/strategies/default")
+            .json(&serde_json::json!({ "symbol": "ETH" }))
+            .await,
+    );
+    assert_database_unavailable(
+        app.server
+            .post("/api/strategies")
+            .json(&serde_json::json!({
+                "name": "test",
+                "symbol": "BTC",
+                "config": strategy_body("test", 1)["config"]
+            }))
+            .await,
+    );
+}
+
+#[tokio::test]
+async fn strategy_templates_are_listed_and_usable_on_create() {
+    let app = TestApiServer::new().await;
+
+    let templates: Value = app.server.get("/api/strategy-templates").await.json();
+    let names: Vec<&str> = templates
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            "macd_rsi_basic",
+            "trend_following_ema_supertrend",
+            "mean_reversion_bollinger_rsi",
+            "funding_rate_contrarian",
+            "trend_following_adx_filtered",
+            "mean_reversion_calm_markets"
+        ]
+    );
+    assert!(templates[0]["description"].as_str().is_some());
+
+    let create = |body: Value| app.server.post("/api/strategies").json(&body);
+    let response = create(serde_json::json!({
+        "name": "templated",
+        "symbol": "BTC",
+        "from_template": "no_such_template"
+    }))
+    .await;
+    assert_eq!(response.status_code(), 400);
+    assert_eq!(response.json::<Value>()["error"], "invalid_request");
+
+    let response = create(serde_json::json!({ "name": "empty", "symbol": "BTC" })).await;
+    assert_eq!(response.status_code(), 400);
+    assert_eq!(response.json::<Value>()["error"], "invalid_request");
+
+    // A known template resolves and validates, then needs the database
+    assert_database_unavailable(
+        create(serde_json::json!({
+            "name": "templated",
+            "symbol": "BTC",
+            "from_template": "trend_following_ema_supertrend"
+        }))
+        .await,
+    );
+}
+
+#[tokio::test]
+async fn backtest_rejects_empty_range_before_database_lookup() {
+    let app = TestApiServer::new().await;
+    let request = |from: &str, to: &str| {
+        serde_json::json!({ "symbol": "BTC", "interval": "1m", "from": from, "to": to })
+    };
+
+    let response = app
+        .server
+        .post("/api/strategies/1/backtest")
+        .json(&request("2024-01-02T00:00:00Z", "2024-01-01T00:00:00Z"))
+        .await;
+    assert_eq!(response.status_code(), 400);
+    let body: Value = response.json();
+    assert_eq!(body["error"], "invalid_request");
+
+    assert_database_unavailable(
+        app.server
+            .post("/api/strategies/1/backtest")
+            .json(&request("2024-01-01T00:00:00Z", "2024-01-02T00:00:00Z"))
+            .await,
+    );
+}
+
+#[tokio::test]
+async fn strategy_trace_requires_database() {
+    let app = TestApiServer::new().await;
+    assert_database_unavailable(
+        app.server
+            .post("/api/strategies/1/trace")
+            .json(&serde_json::json!({ "symbol": "BTC" }))
+            .await,
+    );
+}
+
+#[tokio::test]
+async fn strategy_trace_reports_rule_outcomes() {
+    let db = match QuestDatabase::connect(std::time::Duration::ZERO).await {
+        Ok(db) => Arc::new(db),
+        Err(e) => {
+            eprintln!("Skipping strategy trace test, QuestDB unavailable: {}", e);
+            return;
+        }
+    };
+
+    let symbol = format!("TRACE{}", Utc::now().timestamp_millis());
+    let start = Utc::now() - Duration::minutes(300);
+    let candles: Vec<Candle> = (0..300)
+        .map(|i| {
+            let price = 100.0 + (i as f64 * 0.3).sin();
+            Candle::new(
+                price,
+                price + 0.5,
+                price - 0.5,
+                price,
+                1_000.0,
+                start + Duration::minutes(i),
+            )
+        })
+        .collect();
+    db.store_candles_batch(&symbol, "1m", &candles).await.unwrap();
+
+    // RSI always lies in [0, 100], so one rule must pass and the other fail
+    let rsi_rule = |id: &str, comparison: &str| {
+        serde_json::json!({
+            "id": id,
+            "type": "Condition",
+            "weight": 1.0,
+            "condition": {
+                "indicator": "RSI",
+                "comparison": comparison,
+                "threshold": 0.0
+            }
+        })
+    };
+    let config: StrategyConfig = serde_json::from_value(serde_json::json!({
+        "rules": [rsi_rule("known_pass", "GreaterThan"), rsi_rule("known_fail", "LessThan")],
+        "aggregation": {
+            "method": "Sum",
+            "thresholds": { "long_min": 1, "short_max": -1 }
+        }
+    }))
+    .unwrap();
+    let strategy = Strategy {
+        name: format!("trace_{}", symbol),
+        symbol: symbol.clone(),
+        config,
+        ..Strategy::default()
+    };
+    let id = db.create_strategy(&strategy).await.unwrap();
+
+    let app = TestApiServer::with_database(db.clone()).await;
+    let response = app
+        .server
+        .post(&format!("/api/strategies/{}/trace", id))
+        .json(&serde_json::json!({ "symbol": symbol }))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let trace: EvaluationTrace = response.json();
+    let passed: Vec<(&str, bool)> = trace
+        .rules
+        .iter()
+        .map(|rule| (rule.rule_id.as_str(), rule.passed))
+        .collect();
+    assert_eq!(passed, [("known_pass", true), ("known_fail", false)]);
+    assert!(trace.rules.iter().all(|rule| rule.indicator_value.is_some()));
+    assert_eq!(trace.total_score, 0);
+
+    // No candles stored for this symbol
+    let response = app
+        .server
+        .post(&format!("/api/strategies/{}/trace", id))
+        .json(&serde_json::json!({ "symbol": format!("{}X", symbol) }))
+        .await;
+    assert_eq!(response.status_code(), 400);
+
+    db.delete_strategy(id).await.unwrap();
+}
+
+#[tokio::test]
+async fn live_evaluation_returns_signal_without_storing_it() {
+    let db = match QuestDatabase::connect(std::time::Duration::ZERO).await {
+        Ok(db) => Arc::new(db),
+        Err(e) => {
+            eprintln!("Skipping live evaluation test, QuestDB unavailable: {}", e);
+            return;
+        }
+    };
+
+    let symbol = format!("LIVE{}", Utc::now().timestamp_millis());
+    let start = Utc::now() - Duration::minutes(300);
+    let candles: Vec<Candle> = (0..300)
+        .map(|i| {
+            let price = 100.0 + (i as f64 * 0.3).sin();
+            Candle::new(
+                price,
+                price + 0.5,
+                price - 0.5,
+                price,
+                1_000.0,
+                start + Duration::minutes(i),
+            )
+        })
+        .collect();
+    db.store_candles_batch(&symbol, "1m", &candles)
+        .await
+        .unwrap();
+    // Too few 5m candles to evaluate
+    let five_minute: Vec<Candle> = candles.iter().step_by(5).take(30).cloned().collect();
+    db.store_candles_batch(&symbol, "5m", &five_minute)
+        .await
+        .unwrap();
+
+    let strategy = Strategy {
+        name: format!("live_{}", symbol),
+        symbol: symbol.clone(),
+        ..Strategy::default()
+    };
+    let id = db.create_strategy(&strategy).await.unwrap();
+
+    let app = TestApiServer::with_database(db.clone()).await;
+    let response = app
+        .server
+        .post(&format!("/api/strategies/{}/live-evaluate", id))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let body: Value = response.json();
+    assert_eq!(body["interval"], "1m");
+    assert_eq!(body["candles"], 250);
+    assert_eq!(body["signal"]["symbol"], symbol.as_str());
+    assert!(body["indicators"]["rsi"].is_number());
+    assert_eq!(body["indicators"]["current_price"], body["signal"]["price"]);
+    let stored = db.get_signals(Some(&symbol), None).await.unwrap();
+    assert!(stored.is_empty());
+
+    let response = app
+        .server
+        .post(&format!("/api/strategies/{}/live-evaluate?interval=5m", id))
+        .await;
+    assert_eq!(response.status_code(), 422);
+    let body: Value = response.json();
+    assert_eq!(body["error"], "insufficient_candles");
+    assert_eq!(body["required"], 50);
+    assert_eq!(body["available"], 30);
+    assert_eq!(body["deficit"], 20);
+
+    let response = app
+        .server
+        .post("/api/strategies/999999999/live-evaluate")
+        .await;
+    assert_eq!(response.status_code(), 404);
+
+    db.delete_strategy(id).await.unwrap();
+}
+
+#[tokio::test]
+async fn live_evaluation_reads_the_current_spread() {
+    let db = match QuestDatabase::connect(std::time::Duration::ZERO).await {
+        Ok(db) => Arc::new(db),
+        Err(e) => {
+            eprintln!("Skipping live spread test, QuestDB unavailable: {}", e);
+            return;
+        }
+    };
+
+    let symbol = format!("SPREAD{}", Utc::now().timestamp_millis());
+    let start = Utc::now() - Duration::minutes(100);
+    let candles: Vec<Candle> = (0..100)
+        .map(|i| {
+            Candle::new(
+                100.0,
+                100.5,
+                99.5,
+                100.0,
+                1_000.0,
+                start + Duration::minutes(i),
+            )
+        })
+        .collect();
+    db.store_candles_batch(&symbol, "1m", &candles)
+        .await
+        .unwrap();
+
+    let mut strategy = Strategy {
+        name: format!("live_spread_{}", symbol),
+        symbol: symbol.clone(),
+        ..Strategy::default()
+    };
+    strategy.config.rules = vec![Rule {
+        id: "tight_spread".to_string(),
+        rule_type: RuleType::Condition,
+        weight: Some(1.0),
+        operator: None,
+        condition: Some(Condition {
+            indicator: IndicatorType::Spread,
+            indicator_params: HashMap::new(),
+            comparison: Comparison::LessThan,
+            threshold: Some(5.0),
+            signal_state: None,
+        }),
+        children: None,
+    }];
+    strategy.config.aggregation.thresholds.long_min = 1;
+    strategy.config.aggregation.thresholds.short_max = -1;
+    let id = db.create_strategy(&strategy).await.unwrap();
+
+    let market_data = MockMarketDataProvider::new().with_spread(&symbol, 100.0, 100.01);
+    let app = TestApiServer::with_database_and_market_data(db.clone(), Arc::new(market_data)).await;
+    let response = app
+        .server
+        .post(&format!("/api/strategies/{}/live-evaluate", id))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let body: Value = response.json();
+    assert_eq!(body["signal"]["direction"], "Long");
+
+    // Without live data the spread condition fails
+    let app = TestApiServer::with_database(db.clone()).await;
+    let response = app
+        .server
+        .post(&format!("/api/strategies/{}/live-evaluate", id))
+        .await;
+    let body: Value = response.json();
+    assert_eq!(body["signal"]["direction"], "Short");
+
+    db.delete_strategy(id).await.unwrap();
+}
+
+#[tokio::test]
+async fn strategies_can_be_disabled_and_re_enabled() {
+    let db = match QuestDatabase::connect(std::time::Duration::ZERO).await {
+        Ok(db) => Arc::new(db),
+        Err(e) => {
+            eprintln!("Skipping strategy enable test, QuestDB unavailable: {}", e);
+            return;
+        }
+    };
+
+    let symbol = format!("TOGGLE{}", Utc::now().timestamp_millis());
+    let id = db
+        .create_strategy(&Strategy {
+            symbol: symbol.clone(),
+            ..Strategy::default()
+        })
+        .await
+        .unwrap();
+    let app = TestApiServer::with_database(db.clone()).await;
+
+    let response = app.server.get(&format!("/api/strategies/{}", id)).await;
+    assert_eq!(response.json::<Value>()["enabled"], true);
+
+    for (action, enabled) in [("disable", false), ("disable", false), ("enable", true)] {
+        let response = app
+            .server
+            .put(&format!("/api/strategies/{}/{}", id, action))
+            .await;
+        assert_eq!(response.status_code(), 200);
+        assert_eq!(response.json::<Value>()["enabled"], enabled);
+        assert_eq!(db.get_strategy(id).await.unwrap().enabled, enabled);
+    }
+
+    let response = app.server.put("/api/strategies/1/disable").await;
+    assert_eq!(response.status_code(), 404);
+
+    db.delete_strategy(id).await.unwrap();
+}
+
+#[tokio::test]
+async fn cloned_strategy_is_independent_of_original() {
+    let db = match QuestDatabase::connect(std::time::Duration::ZERO).await {
+        Ok(db) => Arc::new(db),
+        Err(e) => {
+            eprintln!("Skipping strategy clone test, QuestDB unavailable: {}", e);
+            return;
+        }
+    };
+
+    let symbol = format!("CLONE{}", Utc::now().timestamp_millis());
+    let original = Strategy {
+        name: format!("clone_{}", symbol),
+        symbol: symbol.clone(),
+        ..Strategy::default()
+    };
+    let id = db.create_strategy(&original).await.unwrap();
+    let app = TestApiServer::with_database(db.clone()).await;
+
+    let response = app
+        .server
+        .post(&format!("/api/strategies/{}/clone", id))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let copy: Value = response.json();
+    let copy_id = copy["id"].as_i64().unwrap();
+    assert_ne!(copy_id, id);
+    assert_eq!(copy["name"], format!("clone_{} (copy)", symbol));
+    assert_eq!(copy["symbol"], symbol);
+    assert_eq!(copy["created_at"], copy["updated_at"]);
+
+    let response = app
+        .server
+        .post(&format!("/api/strategies/{}/clone", id))
+        .json(&serde_json::json!({ "name": "renamed", "symbol": format!("{}X", symbol) }))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let renamed: Value = response.json();
+    let renamed_id = renamed["id"].as_i64().unwrap();
+    assert_eq!(renamed["name"], "renamed");
+    assert_eq!(renamed["symbol"], format!("{}X", symbol));
+
+    // Editing the clone leaves the original untouched
+    let mut config = original.config.clone();
+    config.aggregation.thresholds.long_min += 5;
+    let response = app
+        .server
+        .put(&format!("/api/strategies/{}", copy_id))
+        .json(&serde_json::json!({ "config": config }))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let long_min = |strategy: Strategy| strategy.config.aggregation.thresholds.long_min;
+    assert_eq!(
+        long_min(db.get_strategy(id).await.unwrap()),
+        original.config.aggregation.thresholds.long_min
+    );
+    assert_eq!(
+        long_min(db.get_strategy(copy_id).await.unwrap()),
+        original.config.aggregation.thresholds.long_min + 5
+    );
+
+    let response = app.server.post("/api/strategies/1/clone").await;
+    assert_eq!(response.status_code(), 404);
+
+    for strategy_id in [id, copy_id, renamed_id] {
+        db.delete_strategy(strategy_id).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn strategy_stats_requires_database() {
+    let app = TestApiServer::new().await;
+    assert_database_unavailable(app.server.get("/api/strategies/stats").await);
+}
+
+#[tokio::test]
+async fn signal_stats_require_database_and_a_valid_range() {
+    let app = TestApiServer::new().await;
+    assert_database_unavailable(app.server.get("/api/signals/stats").await);
+
+    let response = app
+        .server
+        .get("/api/signals/stats?from=2024-01-02T00:00:00Z&to=2024-01-01T00:00:00Z")
+        .await;
+    assert_eq!(response.status_code(), 400);
+    let body: Value = response.json();
+    assert_eq!(body["error"], "invalid_request");
+}
+
+#[tokio::test]
+async fn signal_list_requires_database() {
+    let app = TestApiServer::new().await;
+    assert_database_unavailable(app.server.get("/api/signals?symbol=BTC").await);
+}
+
+#[tokio::test]
+async fn signal_list_rejects_invalid_pages_and_ranges() {
+    let app = TestApiServer::new().await;
+
+    for query in [
+        "/api/signals?page=0",
+        "/api/signals?page_size=0",
+        "/api/signals?from=2024-01-02T00:00:00Z&to=2024-01-01T00:00:00Z",
+    ] {
+        let response = app.server.get(query).await;
+        assert_eq!(response.status_code(), 400, "{}", query);
+        let body: Value = response.json();
+        assert_eq!(body["error"], "invalid_request");
+    }
+}
+
+#[tokio::test]
+async fn signals_are_filtered_and_paginated() {
+    let db = match QuestDatabase::connect(std::time::Duration::ZERO).await {
+        Ok(db) => Arc::new(db),
+        Err(e) => {
+            eprintln!("Skipping signal pagination test, QuestDB unavailable: {}", e);
+            return;
+        }
+    };
+
+    let symbol = format!("PAGE{}", Utc::now().timestamp_millis());
+    let start = Utc::now();
+    // Five signals one second apart: Long, Short, Long, Short, Long
+    for i in 0..5 {
+        let direction = if i % 2 == 0 {
+            SignalDirection::Long
+        } else {
+            SignalDirection::Short
+        };
+        let confidence = 0.2 * (i + 1) as f64;
+        let mut signal =
+            SignalOutput::new(direction, confidence, 1.0, 2.0, Vec::new(), symbol.clone(), 100.0);
+        signal.timestamp = start + Duration::seconds(i);
+        db.store_signal(&signal, 1).await.unwrap();
+    }
+
+    let filter = SignalFilter {
+        symbol: Some(symbol.clone()),
+        ..SignalFilter::default()
+    };
+    let (first, total) = db.query_signals_paginated(&filter, 1, 2).await.unwrap();
+    assert_eq!(total, 5);
+    assert_eq!(first.len(), 2);
+    assert!(first[0].timestamp > first[1].timestamp);
+    let (last, _) = db.query_signals_paginated(&filter, 3, 2).await.unwrap();
+    assert_eq!(last.len(), 1);
+    assert_eq!(last[0].timestamp, start);
+    let (past_end, total) = db.query_signals_paginated(&filter, 4, 2).await.unwrap();
+    assert!(past_end.is_empty());
+    assert_eq!(total, 5);
+
+    let longs = SignalFilter {
+        direction: Some(SignalDirection::Long),
+        min_confidence: Some(0.5),
+        ..filter.clone()
+    };
+    let (signals, total) = db.query_signals_paginated(&longs, 1, 50).await.unwrap();
+    assert_eq!(total, 2);
+    assert!(signals
+        .iter()
+        .all(|s| s.direction == SignalDirection::Long && s.confidence >= 0.5));
+
+    let window = SignalFilter {
+        from: Some(start + Duration::seconds(1)),
+        to: Some(start + Duration::seconds(3)),
+        ..filter.clone()
+    };
+    let (signals, total) = db.query_signals_paginated(&window, 1, 50).await.unwrap();
+    assert_eq!((signals.len(), total), (2, 2));
+
+    let app = TestApiServer::with_database(db).await;
+    let body: Value = app
+        .server
+        .get(&format!("/api/signals?symbol={}&direction=Short&page_size=1", symbol))
+        .await
+        .json();
+    assert_eq!(body["total"], 2);
+    assert_eq!(body["page"], 1);
+    assert_eq!(body["page_size"], 1);
+    assert_eq!(body["data"].as_array().unwrap().len(), 1);
+    assert_eq!(body["data"][0]["direction"], "Short");
+    assert!(body["data"][0]["effective_confidence"].is_number());
+
+    let body: Value = app
+        .server
+        .get("/api/signals?symbol=NO_SUCH_SYMBOL")
+        .await
+        .json();
+    assert_eq!(body["total"], 0);
+    assert!(body["data"].as_array().unwrap().is_empty());
+}
+
+/// Read `count` events from a Server-Sent Events response as `(id, data)` pairs
+async fn read_sse_events(response: &mut reqwest::Response, count: usize) -> Vec<(i64, Value)> {
+    let mut buffer = String::new();
+    let mut events = Vec::new();
+    while events.len() < count {
+        let chunk = tokio::time::timeout(std::time::Duration::from_secs(5), response.chunk())
+            .await
+            .expect("event within 5 seconds")
+            .expect("read stream")
+            .expect("stream still open");
+        buffer.push_str(std::str::from_utf8(&chunk).unwrap());
+        while let Some(end) = buffer.find("\n\n") {
+            let event: String = buffer.drain(..end + 2).collect();
+            let field = |name: &str| {
+                event
+                    .lines()
+                    .find_map(|line| line.strip_prefix(name)?.strip_prefix(": "))
+                    .map(str::to_string)
+            };
+            // Keep-alive comments have neither field
+            if let (Some(id), Some(data)) = (field("id"), field("data")) {
+                events.push((id.parse().unwrap(), serde_json::from_str(&data).unwrap()));
+            }
+        }
+    }
+    events
+}
+
+fn stream_signal(symbol: &str, timestamp: chrono::DateTime<Utc>) -> SignalOutput {
+    let mut signal = SignalOutput::new(
+        SignalDirection::Long,
+        0.8,
+        1.0,
+        2.0,
+        Vec::new(),
+        symbol.to_string(),
+        100.0,
+    );
+    signal.timestamp = timestamp;
+    signal
+}
+
+#[tokio::test]
+async fn signal_stream_delivers_new_signals_filtered_by_symbol() {
+    let state = AppState::builder().build();
+    let signal_stream = state.signal_stream.clone();
+    let base_url = serve(state).await;
+
+    let mut response = reqwest::Client::new()
+        .get(format!("{}/api/signals/stream?symbol=BTC", base_url))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["content-type"], "text/event-stream");
+
+    let now = Utc::now();
+    signal_stream.send(stream_signal("ETH", now)).unwrap();
+    signal_stream
+        .send(stream_signal("BTC", now + Duration::seconds(1)))
+        .unwrap();
+    signal_stream
+        .send(stream_signal("BTC", now + Duration::seconds(2)))
+        .unwrap();
+
+    let events = read_sse_events(&mut response, 2).await;
+    assert!(events.iter().all(|(_, signal)| signal["symbol"] == "BTC"));
+    assert_eq!(events[0].0, (now + Duration::seconds(1)).timestamp_millis());
+    assert_eq!(events[1].0, (now + Duration::seconds(2)).timestamp_millis());
+    assert_eq!(events[0].1["direction"], "Long");
+    assert_eq!(events[0].1["confidence"], 0.8);
+}
+
+#[tokio::test]
+async fn signal_stream_without_symbol_delivers_every_signal() {
+    let state = AppState::builder().build();
+    let signal_stream = state.signal_stream.clone();
+    let base_url = serve(state).await;
+
+    let mut response = reqwest::Client::new()
+        .get(format!("{}/api/signals/stream", base_url))
+        .send()
+        .await
+        .unwrap();
+
+    let now = Utc::now();
+    signal_stream.send(stream_signal("ETH", now)).unwrap();
+    signal_stream.send(stream_signal("BTC", now)).unwrap();
+
+    let symbols: Vec<_> = read_sse_events(&mut response, 2)
+        .await
+        .into_iter()
+        .map(|(_, signal)| signal["symbol"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(symbols, ["ETH", "BTC"]);
+}
+
+#[tokio::test]
+async fn signal_stream_replays_stored_signals_after_last_event_id() {
+    let db = match QuestDatabase::connect(std::time::Duration::ZERO).await {
+        Ok(db) => Arc::new(db),
+        Err(e) => {
+            eprintln!("Skipping signal stream replay test, QuestDB unavailable: {}", e);
+            return;
+        }
+    };
+
+    let symbol = format!("SSE{}", Utc::now().timestamp_millis());
+    let start = Utc::now() - Duration::minutes(1);
+    let stored: Vec<_> = (0..3)
+        .map(|i| stream_signal(&symbol, start + Duration::seconds(i)))
+        .collect();
+    for signal in &stored {
+        db.store_signal(signal, 1).await.unwrap();
+    }
+
+    let state = AppState::builder().with_database(db).build();
+    let signal_stream = state.signal_stream.clone();
+    let base_url = serve(state).await;
+
+    // Reconnect after having seen the first signal
+    let mut response = reqwest::Client::new()
+        .get(format!("{}/api/signals/stream?symbol={}", base_url, symbol))
+        .header("Last-Event-ID", stored[0].timestamp.timestamp_millis().to_string())
+        .send()
+        .await
+        .unwrap();
+
+    // Already replayed, so not sent twice
+    signal_stream.send(stored[2].clone()).unwrap();
+    let live = stream_signal(&symbol, start + Duration::seconds(10));
+    signal_stream.send(live.clone()).unwrap();
+
+    let ids: Vec<_> = read_sse_events(&mut response, 3)
+        .await
+        .into_iter()
+        .map(|(id, _)| id)
+        .collect();
+    assert_eq!(
+        ids,
+        [&stored[1], &stored[2], &live].map(|signal| signal.timestamp.timestamp_millis())
+    );
+}
+
+#[tokio::test]
+async fn dead_letters_require_database() {
+    let app = TestApiServer::new().await;
+    assert_database_unavailable(app.server.get("/api/jobs/dead-letters").await);
+    assert_database_unavailable(
+        app.server
+            .get("/api/jobs/dead-letters?symbol=BTC&job_type=fetch_candles")
+            .await,
+    );
+    assert_database_unavailable(app.server.get("/api/jobs/dead-letters/stats").await);
+    assert_database_unavailable(app.server.post("/api/jobs/dead-letters/1/replay").await);
+}
+
+#[tokio::test]
+async fn audit_logs_require_database_and_a_valid_range() {
+    let app = TestApiServer::new().await;
+    assert_database_unavailable(app.server.get("/api/audit-logs").await);
+    assert_database_unavailable(app.server.get("/api/audit-logs?strategy_id=1").await);
+
+    let response = app
+        .server
+        .get("/api/audit-logs?from=2024-01-02T00:00:00Z&to=2024-01-01T00:00:00Z")
+        .await;
+    assert_eq!(response.status_code(), 400);
+}
+
+fn assert_cache_unavailable(response: axum_test::TestResponse) {
+    assert_eq!(response.status_code(), 503);
+    let body: Value = response.json();
+    assert_eq!(body["error"], "cache_unavailable");
+}
+
+#[tokio::test]
+async fn positions_require_redis() {
+    let app = TestApiServer::new().await;
+    assert_cache_unavailable(app.server.get("/api/positions").await);
+    assert_cache_unavailable(app.server.post("/api/positions/BTC/close").await);
+}
+
+#[tokio::test]
+async fn open_positions_are_listed_until_closed() {
+    // Connection attempts are retried, so bound how long a missing Redis takes
+    let connect = tokio::time::timeout(std::time::Duration::from_secs(5), RedisCache::new());
+    let cache = match connect.await {
+        Ok(Ok(cache)) => Arc::new(cache),
+        _ => {
+            eprintln!("Skipping position test, Redis unavailable");
+            return;
+        }
+    };
+    let app = TestApiServer::with_cache(cache.clone()).await;
+    let positions = PositionTracker::new(cache);
+
+    let symbol = format!("POS{}", Utc::now().timestamp_millis());
+    let signal = SignalOutput::new(
+        SignalDirection::Long,
+        0.8,
+        1.0,
+        2.0,
+        Vec::new(),
+        symbol.clone(),
+        100.0,
+    );
+    positions.record(&signal).await.expect("record position");
+
+    let listed: Vec<SignalOutput> = app.server.get("/api/positions").await.json();
+    let open = listed
+        .iter()
+        .find(|p| p.symbol == symbol)
+        .expect("open position listed");
+    assert_eq!(open.direction, SignalDirection::Long);
+
+    let close_path = format!("/api/positions/{}/close", symbol);
+    assert_eq!(app.server.post(&close_path).await.status_code(), 204);
+    let listed: Vec<SignalOutput> = app.server.get("/api/positions").await.json();
+    assert!(listed.iter().all(|p| p.symbol != symbol));
+    assert_eq!(app.server.post(&close_path).await.status_code(), 404);
+}
+
+#[tokio::test]
+async fn strategy_lifecycle_is_audit_logged() {
+    let db = match QuestDatabase::connect(std::time::Duration::ZERO).await {
+        Ok(db) => Arc::new(db),
+        Err(e) => {
+            eprintln!("Skipping audit log test, QuestDB unavailable: {}", e);
+            return;
+        }
+    };
+    let app =
+        TestApiServer::with_database_and_api_keys(db, vec!["ops:secret-key".to_string()]).await;
+
+    let symbol = format!("AUDIT{}", Utc::now().timestamp_millis());
+    let response = app
+        .server
+        .post("/api/strategies")
+        .add_header("X-API-Key", "secret-key")
+        .json(&serde_json::json!({
+            "name": "audited",
+            "symbol": symbol,
+            "config": strategy_body("audited", 1)["config"]
+        }))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let id = response.json::<Value>()["id"].as_i64().unwrap();
+
+    let response = app
+        .server
+        .put(&format!("/api/strategies/{}", id))
+        .add_header("X-API-Key", "secret-key")
+        .json(&serde_json::json!({ "name": "audited v2" }))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    for action in ["disable", "enable"] {
+        let response = app
+            .server
+            .put(&format!("/api/strategies/{}/{}", id, action))
+            .add_header("X-API-Key", "secret-key")
+            .await;
+        assert_eq!(response.status_code(), 200);
+    }
+    let response = app
+        .server
+        .delete(&format!("/api/strategies/{}", id))
+        .add_header("X-API-Key", "secret-key")
+        .await;
+    assert_eq!(response.status_code(), 204);
+
+    let response = app
+        .server
+        .get(&format!("/api/audit-logs?strategy_id={}", id))
+        .add_header("X-API-Key", "secret-key")
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let entries: Vec<Value> = response.json();
+    let operations: Vec<&str> = entries
+        .iter()
+        .rev()
+        .map(|entry| entry["operation"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        operations,
+        ["create", "update", "disable", "enable", "delete"]
+    );
+    assert!(entries.iter().all(|entry| entry["operator_key"] == "ops"));
+    assert!(entries.iter().all(|entry| entry["strategy_id"] == id));
+
+    // Oldest last: the create records the full request, the update only the
+    // fields that changed, and the delete the name it had
+    let request = |entry: &Value| -> Value {
+        serde_json::from_str(entry["request_json"].as_str().unwrap()).unwrap()
+    };
+    assert_eq!(request(&entries[4])["symbol"], symbol);
+    assert_eq!(
+        request(&entries[3]),
+        serde_json::json!({ "name": "audited v2", "symbol": null, "config": null })
+    );
+    assert!(entries[0]["request_json"].is_null());
+    assert_eq!(entries[0]["strategy_name"], "audited v2");
+
+    // `from` is inclusive and `to` exclusive
+    let from = entries[2]["timestamp"].as_str().unwrap();
+    let to = entries[0]["timestamp"].as_str().unwrap();
+    let response = app
+        .server
+        .get("/api/audit-logs")
+        .add_query_param("strategy_id", id)
+        .add_query_param("from", from)
+        .add_query_param("to", to)
+        .add_header("X-API-Key", "secret-key")
+        .await;
+    let operations: Vec<String> = response
+        .json::<Vec<Value>>()
+        .iter()
+        .map(|entry| entry["operation"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(operations, ["enable", "disable"]);
+}
+
+#[tokio::test]
+async fn health_reports_database_status() {
+    let app = TestApiServer::new().await;
+    let body: Value = app.server.get("/health").await.json();
+    assert_eq!(body["database_status"], "unavailable");
+}
+
+#[tokio::test]
+async fn health_is_degraded_while_the_websocket_is_disconnected() {
+    use perptrix::services::hyperliquid::client::MockWebSocketClient;
+
+    let client = Arc::new(MockWebSocketClient::new());
+    let app = TestApiServer::with_websocket_client(client.clone(), None).await;
+
+    let response = app.server.get("/health").await;
+    assert_eq!(response.status_code(), 207);
+    let body: Value = response.json();
+    assert_eq!(body["status"], "degraded");
+    assert_eq!(body["services"]["websocket"]["status"], "Down");
+    assert!(body["services"]["websocket"]["last_error"].is_string());
+    assert_eq!(body["services"]["database"]["status"], "Disabled");
+    assert_eq!(body["services"]["cache"]["status"], "Disabled");
+
+    client.set_connected(true).await;
+    let response = app.server.get("/health").await;
+    assert_eq!(response.status_code(), 200);
+    let body: Value = response.json();
+    assert_eq!(body["status"], "healthy");
+    assert_eq!(body["services"]["websocket"]["status"], "Up");
+    assert!(body["services"]["websocket"]["last_error"].is_null());
+}
+
+#[tokio::test]
+async fn health_checks_redis_alongside_a_disconnected_websocket() {
+    use perptrix::services::hyperliquid::client::MockWebSocketClient;
+
+    let cache = match RedisCache::new().await {
+        Ok(cache) => Arc::new(cache),
+        Err(e) => {
+            eprintln!("Skipping Redis health test, Redis unavailable: {}", e);
+            return;
+        }
+    };
+    let client = Arc::new(MockWebSocketClient::new());
+    let app = TestApiServer::with_websocket_client(client, Some(cache)).await;
+
+    let response = app.server.get("/health").await;
+    assert_eq!(response.status_code(), 207);
+    let body: Value = response.json();
+    assert_eq!(body["services"]["cache"]["status"], "Up");
+    assert!(body["services"]["cache"]["latency_ms"].is_u64());
+    assert_eq!(body["services"]["websocket"]["status"], "Down");
+}
+
+#[tokio::test]
+async fn health_reports_the_websocket_service_heartbeat() {
+    use perptrix::cache::redis::WEBSOCKET_HEARTBEAT_KEY;
+    use perptrix::services::websocket::WebSocketHeartbeat;
+
+    let cache = match RedisCache::new().await {
+        Ok(cache) => Arc::new(cache),
+        Err(e) => {
+            eprintln!("Skipping WebSocket heartbeat test, Redis unavailable: {}", e);
+            return;
+        }
+    };
+    cache.delete(WEBSOCKET_HEARTBEAT_KEY).await.unwrap();
+    let app = TestApiServer::with_websocket_heartbeat(cache.clone()).await;
+    let websocket_status = || async {
+        let body: Value = app.server.get("/health").await.json();
+        body["services"]["websocket"].clone()
+    };
+
+    // No WebSocket service running
+    let websocket = websocket_status().await;
+    assert_eq!(websocket["status"], "Down");
+    assert_eq!(websocket["last_error"], "No heartbeat from the WebSocket service");
+
+    let interval = std::time::Duration::from_secs(30);
+    WebSocketHeartbeat::new(false)
+        .publish(&cache, interval)
+        .await
+        .unwrap();
+    let websocket = websocket_status().await;
+    assert_eq!(websocket["status"], "Down");
+    assert_eq!(websocket["last_error"], "WebSocket is not connected");
+
+    WebSocketHeartbeat::new(true)
+        .publish(&cache, interval)
+        .await
+        .unwrap();
+    assert_eq!(websocket_status().await["status"], "Up");
+
+    cache.delete(WEBSOCKET_HEARTBEAT_KEY).await.unwrap();
+}
+
+#[tokio::test]
+async fn database_pool_shares_connections_beyond_its_size() {
+    let db = match QuestDatabase::connect(std::time::Duration::ZERO).await {
+        Ok(db) => db,
+        Err(e) => {
+            eprintln!("Skipping database pool test, QuestDB unavailable: {}", e);
+            return;
+        }
+    };
+    let pool = db.pool();
+    let size = pool.size();
+    assert!(pool.is_healthy().await);
+    assert_eq!(pool.stats().idle, size);
+
+    // Holding more checkouts than connections never blocks
+    let mut held = Vec::new();
+    for _ in 0..size * 2 {
+        held.push(pool.get().await);
+    }
+    assert!(held.iter().all(|client| client.is_some()));
+    assert_eq!(pool.stats().active, size);
+    drop(held);
+    assert_eq!(pool.stats().idle, size);
+
+    let queries = (0..size * 3).map(|_| db.get_strategies(None));
+    for result in futures_util::future::join_all(queries).await {
+        result.expect("concurrent query succeeds");
+    }
+    assert_eq!(pool.stats().idle, size);
+}
+
+#[tokio::test]
+async fn strategy_stats_split_signal_counts_by_strategy() {
+    let db = match QuestDatabase::connect(std::time::Duration::ZERO).await {
+        Ok(db) => db,
+        Err(e) => {
+            eprintln!("Skipping strategy stats test, QuestDB unavailable: {}", e);
+            return;
+        }
+    };
+
+    let since = Utc::now() - Duration::seconds(1);
+    let config: StrategyConfig = serde_json::from_value(serde_json::json!({
+        "rules": [],
+        "aggregation": {
+            "method": "Sum",
+            "thresholds": { "long_min": 1, "short_max": -1 }
+        }
+    }))
+    .unwrap();
+
+    let mut strategy_ids = Vec::new();
+    for (offset, name) in ["stats-a", "stats-b"].iter().enumerate() {
+        let created_at = Utc::now() + Duration::milliseconds(offset as i64);
+        let strategy = Strategy {
+            id: None,
+            name: name.to_string(),
+            symbol: "BTC".to_string(),
+            config: config.clone(),
+            enabled: true,
+            source_file: None,
+            created_at,
+            updated_at: created_at,
+        };
+        strategy_ids.push(db.create_strategy(&strategy).await.unwrap());
+    }
+
+    // 6 signals for the first strategy, 4 for the second
+    let directions = [
+        (0, SignalDirection::Long),
+        (0, SignalDirection::Long),
+        (0, SignalDirection::Long),
+        (0, SignalDirection::Long),
+        (0, SignalDirection::Short),
+        (0, SignalDirection::Short),
+        (1, SignalDirection::Long),
+        (1, SignalDirection::Short),
+        (1, SignalDirection::Neutral),
+        (1, SignalDirection::Neutral),
+    ];
+    for (i, (strategy, direction)) in directions.iter().enumerate() {
+        let mut signal =
+            SignalOutput::new(*direction, 0.5, 1.0, 2.0, Vec::new(), "BTC".to_string(), 42_000.0);
+        signal.timestamp = Utc::now() + Duration::milliseconds(i as i64);
+        db.store_signal(&signal, strategy_ids[*strategy]).await.unwrap();
+    }
+
+    let stats = db.get_signal_stats_by_strategy(since).await.unwrap();
+    let first = stats
+        .iter()
+        .find(|s| s.strategy_id == strategy_ids[0])
+        .expect("stats for first strategy");
+    let second = stats
+        .iter()
+        .find(|s| s.strategy_id == strategy_ids[1])
+        .expect("stats for second strategy");
+
+    assert_eq!(first.total_signals, 6);
+    assert_eq!((first.long_count, first.short_count, first.neutral_count), (4, 2, 0));
+    assert_eq!(first.strategy_name.as_deref(), Some("stats-a"));
+    assert_eq!(second.total_signals, 4);
+    assert_eq!((second.long_count, second.short_count, second.neutral_count), (1, 1, 2));
+    assert_eq!(second.symbol.as_deref(), Some("BTC"));
+    assert!((second.avg_confidence - 0.5).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn signal_stats_aggregate_by_symbol_and_strategy() {
+    let db = match QuestDatabase::connect(std::time::Duration::ZERO).await {
+        Ok(db) => Arc::new(db),
+        Err(e) => {
+            eprintln!("Skipping signal stats test, QuestDB unavailable: {}", e);
+            return;
+        }
+    };
+
+    let symbol = format!("STATS{}", Utc::now().timestamp_millis());
+    let now = Utc::now();
+    // Strategy 1: four signals spread over the week
+    // (age, direction, confidence, sl_pct, tp_pct)
+    let seeded = [
+        (Duration::minutes(30), SignalDirection::Long, 0.2, 1.0, 2.0),
+        (Duration::hours(2), SignalDirection::Long, 0.4, 2.0, 4.0),
+        (Duration::days(2), SignalDirection::Short, 0.6, 3.0, 6.0),
+        (Duration::days(3), SignalDirection::Neutral, 0.8, 4.0, 8.0),
+    ];
+    for (age, direction, confidence, sl_pct, tp_pct) in seeded {
+        let mut signal =
+            SignalOutput::new(direction, confidence, sl_pct, tp_pct, Vec::new(), symbol.clone(), 100.0);
+        signal.timestamp = now - age;
+        db.store_signal(&signal, 1).await.unwrap();
+    }
+    // Strategy 2: one recent signal, and one older than the range
+    for age in [Duration::minutes(10), Duration::days(10)] {
+        let mut signal =
+            SignalOutput::new(SignalDirection::Short, 0.9, 1.5, 3.0, Vec::new(), symbol.clone(), 100.0);
+        signal.timestamp = now - age;
+        db.store_signal(&signal, 2).await.unwrap();
+    }
+
+    let to = now + Duration::seconds(1);
+    let stats = db
+        .get_signal_stats(Some(&symbol), to - Duration::days(7), to)
+        .await
+        .unwrap();
+    assert_eq!(stats.groups.len(), 2);
+    assert!(stats.groups.iter().all(|group| group.symbol == symbol));
+
+    let first = &stats.groups[0];
+    assert_eq!(first.strategy_id, 1);
+    assert_eq!(first.count, 4);
+    assert_eq!(
+        (first.long_pct, first.short_pct, first.neutral_pct),
+        (50.0, 25.0, 25.0)
+    );
+    assert!((first.mean_confidence - 0.5).abs() < 1e-9);
+    assert!((first.avg_sl_pct - 2.5).abs() < 1e-9);
+    assert!((first.avg_tp_pct - 5.0).abs() < 1e-9);
+    assert_eq!((first.last_1h, first.last_24h, first.last_7d), (1, 2, 4));
+    // Nearest-rank percentiles of 0.2, 0.4, 0.6, 0.8, to 3 significant digits
+    let percentiles = first.confidence_percentiles;
+    for (actual, expected) in [
+        (percentiles.p25, 0.2),
+        (percentiles.p50, 0.4),
+        (percentiles.p75, 0.6),
+        (percentiles.p90, 0.8),
+    ] {
+        assert!((actual - expected).abs() < 0.01, "{:?}", percentiles);
+    }
+
+    let second = &stats.groups[1];
+    assert_eq!(second.strategy_id, 2);
+    assert_eq!((second.count, second.short_pct), (1, 100.0));
+    assert!((second.confidence_percentiles.p50 - 0.9).abs() < 0.01);
+
+    let app = TestApiServer::with_database(db).await;
+    let body: Value = app
+        .server
+        .get(&format!("/api/signals/stats?symbol={}", symbol))
+        .await
+        .json();
+    let groups = body["groups"].as_array().unwrap();
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0]["count"], 4);
+    assert_eq!(groups[0]["last_1h"], 1);
+    assert_eq!(groups[1]["strategy_id"], 2);
+    assert!(body["from"].is_string() && body["to"].is_string());
+}
+
+#[tokio::test]
+async fn dead_letters_are_filtered_and_newest_first() {
+    let db = match QuestDatabase::connect(std::time::Duration::ZERO).await {
+        Ok(db) => db,
+        Err(e) => {
+            eprintln!("Skipping dead letter test, QuestDB unavailable: {}", e);
+            return;
+        }
+    };
+
+    let symbol = format!("DLQ{}", Utc::now().timestamp_millis());
+    let failures = [
+        ("fetch_candles", "No candles available"),
+        ("evaluate_signal", "Failed to load strategies"),
+        ("fetch_candles", "Not enough candles: 10 < 50"),
+    ];
+    for (i, (job_type, error)) in failures.iter().enumerate() {
+        let mut dead_letter = DeadLetter::new(job_type, &symbol, *error).with_retry_count(i);
+        dead_letter.timestamp = Utc::now() + Duration::milliseconds(i as i64);
+        db.store_dead_letter(&dead_letter).await.unwrap();
+    }
+
+    let all = db.get_dead_letters(Some(&symbol), None, 200).await.unwrap();
+    assert_eq!(all.len(), 3);
+    assert_eq!(all[0].error_message, "Not enough candles: 10 < 50");
+    assert_eq!(all[0].retry_count, 2);
+    assert_eq!(all[0].strategy_id, None);
+
+    let fetches = db
+        .get_dead_letters(Some(&symbol), Some("fetch_candles"), 200)
+        .await
+        .unwrap();
+    assert_eq!(fetches.len(), 2);
+    assert!(fetches.iter().all(|d| d.job_type == "fetch_candles"));
+}
+
+#[tokio::test]
+async fn dead_letter_replay_state_is_counted_in_stats() {
+    let db = match QuestDatabase::connect(std::time::Duration::ZERO).await {
+        Ok(db) => Arc::new(db),
+        Err(e) => {
+            eprintln!("Skipping dead letter stats test, QuestDB unavailable: {}", e);
+            return;
+        }
+    };
+
+    let symbol = format!("DLQSTATS{}", Utc::now().timestamp_millis());
+    let id = db
+        .store_dead_letter(&DeadLetter::new("store_signal", &symbol, "connection reset"))
+        .await
+        .unwrap();
+    let stored = db.get_dead_letter(id).await.unwrap();
+    assert_eq!(stored.id, Some(id));
+    assert_eq!(stored.replayed_at, None);
+
+    let app = TestApiServer::with_database(db.clone()).await;
+    let count = |stats: &Value, replayed: bool| {
+        stats
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|s| s["job_type"] == "store_signal" && s["is_replayed"] == replayed)
+            .map_or(0, |s| s["count"].as_i64().unwrap())
+    };
+    let before: Value = app.server.get("/api/jobs/dead-letters/stats").await.json();
+
+    // Replaying needs the Redis job queues, which this server doesn't have
+    let response = app
+        .server
+        .post(&format!("/api/jobs/dead-letters/{}/replay", id))
+        .await;
+    assert_eq!(response.status_code(), 503);
+    assert_eq!(response.json::<Value>()["error"], "job_queue_unavailable");
+
+    db.mark_dead_letter_replayed(id, Utc::now()).await.unwrap();
+    assert!(db.get_dead_letter(id).await.unwrap().is_replayed());
+
+    let after: Value = app.server.get("/api/jobs/dead-letters/stats").await.json();
+    assert_eq!(count(&after, true), count(&before, true) + 1);
+    assert_eq!(count(&after, false), count(&before, false) - 1);
+}
+
+fn strategy_body(name: &str, rule_count: usize) -> Value {
+    let rules: Vec<Value> = (0..rule_count)
+        .map(|i| {
+            serde_json::json!({
+                "id": format!("rule_{}", i),
+                "type": "Condition",
+                "condition": {
+                    "indicator": "RSI",
+                    "comparison": "LessThan",
+                    "threshold": 30.0
+                }
+            })
+        })
+        .collect();
+    serde_json::json!({
+        "name": name,
+        "symbol": "BTC",
+        "config": {
+            "rules": rules,
+            "aggregation": {
+                "method": "Sum",
+                "thresholds": { "long_min": 1, "short_max": -1 }
+            }
+        }
+    })
+}
+
+/// A create-strategy body padded to exactly `size` bytes
+fn strategy_body_of_size(size: usize) -> String {
+    let base = strategy_body("", 1).to_string().len();
+    strategy_body(&"a".repeat(size - base), 1).to_string()
+}
+
+#[tokio::test]
+async fn request_body_at_limit_is_accepted() {
+    let app = TestApiServer::new().await;
+    let body = strategy_body_of_size(1_048_576);
+    assert_eq!(body.len(), 1_048_576);
+
+    let response = app
+        .server
+        .post("/api/strategies")
+        .bytes(body.into())
+        .content_type("application/json")
+        .await;
+    // Passes the size limit and reaches the handler, which has no database
+    assert_database_unavailable(response);
+}
+
+#[tokio::test]
+async fn request_body_over_limit_is_rejected() {
+    let app = TestApiServer::new().await;
+    let response = app
+        .server
+        .post("/api/strategies")
+        .bytes(strategy_body_of_size(1_048_577).into())
+        .content_type("application/json")
+        .await;
+
+    assert_eq!(response.status_code(), 413);
+    let body: Value = response.json();
+    assert_eq!(body["error"], "request_too_large");
+    assert_eq!(body["max_bytes"], 1_048_576);
+}
+
+#[tokio::test]
+async fn strategy_with_too_many_rules_is_rejected() {
+    let app = TestApiServer::new().await;
+    let response = app
+        .server
+        .post("/api/strategies")
+        .json(&strategy_body("too many", 101))
+        .await;
+
+    assert_eq!(response.status_code(), 422);
+    let body: Value = response.json();
+    assert_eq!(body["error"], "invalid_strategy");
+}
+
+#[tokio::test]
+async fn strategy_validation_reports_errors_without_a_database() {
+    let app = TestApiServer::new().await;
+    let validate = |body: Value| app.server.post("/api/strategies/validate").json(&body);
+
+    let response = validate(strategy_body("valid", 1)).await;
+    assert_eq!(response.status_code(), 200);
+    let body: Value = response.json();
+    assert_eq!(body["valid"], true);
+    assert_eq!(body["errors"], serde_json::json!([]));
+
+    let response = validate(serde_json::json!({
+        "name": "templated",
+        "symbol": "BTC",
+        "from_template": "macd_rsi_basic"
+    }))
+    .await;
+    assert_eq!(response.json::<Value>()["valid"], true);
+
+    let mut invalid = strategy_body("invalid", 2);
+    invalid["config"]["rules"][1]["id"] = "rule_0".into();
+    invalid["config"]["aggregation"]["thresholds"]["long_min"] = (-1).into();
+    let response = validate(invalid.clone()).await;
+    assert_eq!(response.status_code(), 200);
+    let body: Value = response.json();
+    assert_eq!(body["valid"], false);
+    assert_eq!(
+        body["errors"],
+        serde_json::json!([
+            "Rule ID 'rule_0' is used more than once",
+            "long_min (-1) must be greater than short_max (-1)"
+        ])
+    );
+
+    // Creating the same config is rejected before the database is needed
+    let response = app.server.post("/api/strategies").json(&invalid).await;
+    assert_eq!(response.status_code(), 422);
+    let body: Value = response.json();
+    assert_eq!(body["error"], "invalid_strategy");
+    assert_eq!(
+        body["message"],
+        "Rule ID 'rule_0' is used more than once; long_min (-1) must be greater than short_max (-1)"
+    );
+}
+
+#[tokio::test]
+async fn reload_status_reports_disabled_hot_reload() {
+    let app = TestApiServer::new().await;
+    let response = app.server.get("/api/config/reload-status").await;
+    assert_eq!(response.status_code(), 200);
+
+    let body: Value = response.json();
+    assert!(body["strategy_dir"].is_null());
+    assert!(body["last_reload_at"].is_null());
+    assert_eq!(body["errors"], serde_json::json!([]));
+}
+
+/// Poll until `check` passes, for changes applied by the file watcher
+async fn eventually<F, Fut>(what: &str, mut check: F)
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = bool>,
+{
+    for _ in 0..100 {
+        if check().await {
+            return;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    panic!("Timed out waiting for {}", what);
+}
+
+#[tokio::test]
+async fn strategy_files_are_hot_reloaded() {
+    let db = match QuestDatabase::connect(std::time::Duration::ZERO).await {
+        Ok(db) => Arc::new(db),
+        Err(e) => {
+            eprintln!("Skipping strategy hot reload test, QuestDB unavailable: {}", e);
+            return;
+        }
+    };
+
+    let dir = tempfile::tempdir().unwrap();
+    let name = format!("hot_reload_{}", Utc::now().timestamp_millis());
+    let path = dir.path().join(format!("{}.json", name));
+    let strategy_file = |long_min: i32| {
+        serde_json::json!({
+            "symbol": "HOTRELOAD",
+            "rules": StrategyConfig::default().rules,
+            "aggregation": {
+                "method": "Sum",
+                "thresholds": { "long_min": long_min, "short_max": -long_min }
+            }
+        })
+        .to_string()
+    };
+    // Present before the watcher starts, so picked up by the initial scan
+    std::fs::write(&path, strategy_file(2)).unwrap();
+
+    let status = Arc::new(RwLock::new(ReloadStatus::default()));
+    let handle = StrategyWatcher::new(dir.path(), db.clone(), status.clone())
+        .watch()
+        .await
+        .unwrap();
+    let stored = |db: Arc<QuestDatabase>, name: String| async move {
+        db.get_strategies(Some("HOTRELOAD"))
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|strategy| strategy.name == name)
+    };
+
+    let created = stored(db.clone(), name.clone()).await.expect("created on start");
+    assert_eq!(created.config.aggregation.thresholds.long_min, 2);
+
+    std::fs::write(&path, strategy_file(5)).unwrap();
+    eventually("the strategy update", || {
+        let (db, name) = (db.clone(), name.clone());
+        async move {
+            stored(db, name)
+                .await
+                .is_some_and(|strategy| strategy.config.aggregation.thresholds.long_min == 5)
+        }
+    })
+    .await;
+    let updated = stored(db.clone(), name.clone()).await.unwrap();
+    assert_eq!(updated.id, created.id);
+
+    let broken = dir.path().join("broken.json");
+    std::fs::write(&broken, "{ \"symbol\": ").unwrap();
+    eventually("the broken file error", || {
+        let status = status.clone();
+        async move { !status.read().await.errors.is_empty() }
+    })
+    .await;
+
+    let app = TestApiServer::with_reload_status(status.clone()).await;
+    let body: Value = app.server.get("/api/config/reload-status").await.json();
+    assert_eq!(body["strategy_dir"], dir.path().display().to_string());
+    assert!(body["last_reload_at"].is_string());
+    assert_eq!(body["errors"].as_array().unwrap().len(), 1);
+    assert_eq!(body["errors"][0]["path"], broken.display().to_string());
+    assert!(body["errors"][0]["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("Invalid strategy file"));
+
+    std::fs::remove_file(&path).unwrap();
+    eventually("the strategy delete", || {
+        let (db, name) = (db.clone(), name.clone());
+        async move { stored(db, name).await.is_none() }
+    })
+    .await;
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn strategy_files_leave_api_strategies_alone() {
+    let db = match QuestDatabase::connect(std::time::Duration::ZERO).await {
+        Ok(db) => Arc::new(db),
+        Err(e) => {
+            eprintln!("Skipping strategy hot reload test, QuestDB unavailable: {}", e);
+            return;
+        }
+    };
+
+    let name = format!("api_owned_{}", Utc::now().timestamp_millis());
+    let api_strategy = Strategy {
+        name: name.clone(),
+        symbol: "APIOWNED".to_string(),
+        ..Strategy::default()
+    };
+    let api_id = db.create_strategy(&api_strategy).await.unwrap();
+
+    // A file whose default name collides with the API strategy
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(format!("{}.json", name));
+    let file = serde_json::json!({
+        "symbol": "APIOWNED",
+        "rules": StrategyConfig::default().rules,
+        "aggregation": {
+            "method": "Sum",
+            "thresholds": { "long_min": 7, "short_max": -7 }
+        }
+    });
+    std::fs::write(&path, file.to_string()).unwrap();
+
+    let status = Arc::new(RwLock::new(ReloadStatus::default()));
+    let mut watcher = StrategyWatcher::new(dir.path(), db.clone(), status.clone());
+    watcher.reload_all().await;
+    assert!(status.read().await.errors.is_empty());
+
+    let named: Vec<Strategy> = db
+        .get_strategies(Some("APIOWNED"))
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|strategy| strategy.name == name)
+        .collect();
+    assert_eq!(named.len(), 2);
+    let api_stored = db.get_strategy(api_id).await.unwrap();
+    assert_eq!(api_stored.source_file, None);
+    assert_eq!(
+        api_stored.config.aggregation.thresholds.long_min,
+        api_strategy.config.aggregation.thresholds.long_min
+    );
+
+    // Deleting the file removes only the file's strategy
+    std::fs::remove_file(&path).unwrap();
+    watcher.handle_path(&path).await;
+    let remaining: Vec<Option<i64>> = db
+        .get_strategies(Some("APIOWNED"))
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|strategy| strategy.name == name)
+        .map(|strategy| strategy.id)
+        .collect();
+    assert_eq!(remaining, vec![Some(api_id)]);
+
+    // A fresh watcher that never loaded the file must not fall back to the name
+    let mut fresh = StrategyWatcher::new(dir.path(), db.clone(), status);
+    fresh.handle_path(&path).await;
+    assert!(db.get_strategy(api_id).await.is_ok());
+
+    db.delete_strategy(api_id).await.unwrap();
+}
+
+async fn authenticated_server() -> TestApiServer {
+    TestApiServer::with_api_keys(vec!["ops:secret-key".to_string(), "plain-key".to_string()]).await
+}
+
+#[tokio::test]
+async fn api_routes_require_api_key() {
+    let app = authenticated_server().await;
+
+    let response = app.server.get("/api/strategies").await;
+    assert_eq!(response.status_code(), 401);
+    let body: Value = response.json();
+    assert_eq!(body["error"], "unauthorized");
+
+    let response = app
+        .server
+        .get("/api/strategies")
+        .add_header("X-API-Key", "wrong-key")
+        .await;
+    assert_eq!(response.status_code(), 401);
+
+    assert_eq!(app.metrics.auth_failures_total.get(), 2.0);
+}
+
+#[tokio::test]
+async fn valid_api_keys_are_accepted() {
+    let app = authenticated_server().await;
+
+    for key in ["secret-key", "plain-key"] {
+        // Past auth, the request reaches the handler and fails on the missing database
+        assert_database_unavailable(
+            app.server
+                .get("/api/strategies")
+                .add_header("X-API-Key", key)
+                .await,
+        );
+    }
+    assert_eq!(app.metrics.auth_failures_total.get(), 0.0);
+}
+
+#[tokio::test]
+async fn health_and_metrics_are_public() {
+    let app = authenticated_server().await;
+
+    assert_eq!(app.server.get("/health").await.status_code(), 200);
+    let response = app.server.get("/metrics").await;
+    assert_eq!(response.status_code(), 200);
+    assert!(response.text().contains("auth_failures_total"));
+}
+
+/// Default `API_RATE_LIMIT_BURST`
+const RATE_LIMIT_BURST: usize = 20;
+
+/// Request `path` until it is rate limited; returns how many requests got through
+async fn exhaust_rate_limit(app: &TestApiServer, path: &str) -> (usize, axum_test::TestResponse) {
+    let mut allowed = 0;
+    loop {
+        let response = app.server.get(path).await;
+        if response.status_code() == 429 {
+            return (allowed, response);
+        }
+        assert_eq!(response.status_code(), 200);
+        allowed += 1;
+        assert!(
+            allowed <= 2 * RATE_LIMIT_BURST,
+            "{} was never rate limited",
+            path
+        );
+    }
+}
+
+#[tokio::test]
+async fn requests_over_the_rate_limit_are_rejected() {
+    let app = TestApiServer::with_client_addresses().await;
+
+    let (allowed, response) = exhaust_rate_limit(&app, "/api/strategy-templates").await;
+    // The bucket refills at 2 requests per second while the burst is spent
+    assert!(
+        (RATE_LIMIT_BURST..=RATE_LIMIT_BURST + 1).contains(&allowed),
+        "{} requests allowed",
+        allowed
+    );
+    let body: Value = response.json();
+    assert_eq!(body["error"], "rate_limited");
+    assert_eq!(response.header("retry-after"), "1");
+
+    assert_eq!(
+        app.metrics
+            .api_rate_limit_hits_total
+            .with_label_values(&["/api/strategy-templates"])
+            .get(),
+        1.0
+    );
+}
+
+#[tokio::test]
+async fn retry_after_is_long_enough_to_succeed() {
+    let app = TestApiServer::with_client_addresses().await;
+
+    let (_, response) = exhaust_rate_limit(&app, "/api/strategy-templates").await;
+    let retry_after: u64 = response
+        .header("retry-after")
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(retry_after >= 1);
+
+    tokio::time::sleep(std::time::Duration::from_secs(retry_after)).await;
+    assert_eq!(
+        app.server
+            .get("/api/strategy-templates")
+            .await
+            .status_code(),
+        200
+    );
+}
+
+#[tokio::test]
+async fn health_and_metrics_bypass_the_rate_limit() {
+    let app = TestApiServer::with_client_addresses().await;
+    exhaust_rate_limit(&app, "/api/strategy-templates").await;
+
+    for _ in 0..2 * RATE_LIMIT_BURST {
+        assert_eq!(app.server.get("/health").await.status_code(), 200);
+    }
+    let response = app.server.get("/metrics").await;
+    assert_eq!(response.status_code(), 200);
+    assert!(response.text().contains("api_rate_limit_hits_total"));
+}
+
+#[tokio::test]
+async fn unmatched_paths_are_limited_under_one_label() {
+    let app = TestApiServer::with_client_addresses().await;
+
+    let mut limited = None;
+    for i in 0..2 * RATE_LIMIT_BURST {
+        let response = app.server.get(&format!("/health/{}", i)).await;
+        if response.status_code() == 429 {
+            limited = Some(i);
+            break;
+        }
+        assert_eq!(response.status_code(), 404);
+    }
+    assert!(limited.is_some(), "Unmatched paths were never rate limited");
+
+    let hits = &app.metrics.api_rate_limit_hits_total;
+    assert_eq!(hits.with_label_values(&["unmatched"]).get(), 1.0);
+    assert_eq!(
+        hits.with_label_values(&[&format!("/health/{}", limited.unwrap())])
+            .get(),
+        0.0
+    );
+}
+
 // Future tests for business logic endpoints will go here:
 // - GET /signals - List signals
 // - GET /signals/{symbol} - Get signals for a symbol
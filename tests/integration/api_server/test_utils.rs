@@ -1,10 +1,15 @@
 //! Test utilities for API server integration tests
 
 use axum_test::TestServer;
-use perptrix::core::http::{create_router, AppState, HealthStatus};
+use perptrix::cache::RedisCache;
+use perptrix::config::watcher::ReloadStatus;
+use perptrix::core::http::{create_router, AppState, AppStateBuilder};
+use perptrix::db::QuestDatabase;
 use perptrix::metrics::Metrics;
+use perptrix::services::hyperliquid::client::WebSocketClient;
+use perptrix::services::market_data::MarketDataProvider;
+use std::net::SocketAddr;
 use std::sync::Arc;
-use std::time::Instant;
 use tokio::sync::RwLock;
 
 /// Test helper for API server integration tests
@@ -16,13 +21,89 @@ pub struct TestApiServer {
 
 impl TestApiServer {
     pub async fn new() -> Self {
+        Self::with_api_keys(Vec::new()).await
+    }
+
+    /// Server that requires one of `api_keys` on `/api/*` routes
+    pub async fn with_api_keys(api_keys: Vec<String>) -> Self {
+        Self::build(AppState::builder().with_api_keys(api_keys))
+    }
+
+    /// Server backed by a live QuestDB connection
+    pub async fn with_database(database: Arc<QuestDatabase>) -> Self {
+        Self::build(AppState::builder().with_database(database))
+    }
+
+    /// Server backed by a live QuestDB connection that requires one of
+    /// `api_keys` on `/api/*` routes
+    pub async fn with_database_and_api_keys(
+        database: Arc<QuestDatabase>,
+        api_keys: Vec<String>,
+    ) -> Self {
+        Self::build(
+            AppState::builder()
+                .with_database(database)
+                .with_api_keys(api_keys),
+        )
+    }
+
+    /// Server backed by a live Redis connection
+    pub async fn with_cache(cache: Arc<RedisCache>) -> Self {
+        Self::build(AppState::builder().with_cache(cache))
+    }
+
+    /// Server whose `/health` reports on `websocket_client`, optionally
+    /// alongside a live Redis connection
+    pub async fn with_websocket_client(
+        websocket_client: Arc<dyn WebSocketClient>,
+        cache: Option<Arc<RedisCache>>,
+    ) -> Self {
+        let mut builder = AppState::builder().with_websocket_client(websocket_client);
+        if let Some(cache) = cache {
+            builder = builder.with_cache(cache);
+        }
+        Self::build(builder)
+    }
+
+    /// Server whose `/health` reports the WebSocket service's heartbeat in
+    /// `cache`, like the api-server binary
+    pub async fn with_websocket_heartbeat(cache: Arc<RedisCache>) -> Self {
+        Self::build(AppState::builder().with_cache(cache).with_websocket_heartbeat())
+    }
+
+    /// Server backed by a live QuestDB connection that reads live spreads and
+    /// trades from `market_data`
+    pub async fn with_database_and_market_data(
+        database: Arc<QuestDatabase>,
+        market_data: Arc<dyn MarketDataProvider + Send + Sync>,
+    ) -> Self {
+        Self::build(
+            AppState::builder()
+                .with_database(database)
+                .with_market_data(market_data),
+        )
+    }
+
+    /// Server that reports `reload_status` from `/api/config/reload-status`
+    pub async fn with_reload_status(reload_status: Arc<RwLock<ReloadStatus>>) -> Self {
+        Self::build(AppState::builder().with_reload_status(reload_status))
+    }
+
+    /// Server listening on a real port, so requests carry a peer address and
+    /// are rate limited per client
+    pub async fn with_client_addresses() -> Self {
         let metrics = Arc::new(Metrics::new().expect("metrics initialization"));
-        let state = AppState {
-            health: Arc::new(RwLock::new(HealthStatus::default())),
-            metrics: metrics.clone(),
-            start_time: Arc::new(Instant::now()),
-            database: None,
-        };
+        let state = AppState::builder().with_metrics(metrics.clone()).build();
+
+        let app = create_router(state).into_make_service_with_connect_info::<SocketAddr>();
+        let server = TestServer::new(app).expect("start test server");
+
+        Self { server, metrics }
+    }
+
+    fn build(builder: AppStateBuilder) -> Self {
+        let metrics = Arc::new(Metrics::new().expect("metrics initialization"));
+        let state = builder.with_metrics(metrics.clone()).build();
 
         let app = create_router(state);
         let server = TestServer::new(app).expect("start test server");
@@ -31,3 +112,17 @@ impl TestApiServer {
     }
 }
 
+/// Serve `state` on a free local port, returning its base URL, for clients
+/// that need a real connection (e.g. to read a streamed response)
+#[allow(dead_code)]
+pub async fn serve(state: AppState) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind test listener");
+    let addr = listener.local_addr().expect("listener address");
+    let app = create_router(state).into_make_service_with_connect_info::<SocketAddr>();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.expect("serve test app");
+    });
+    format!("http://{}", addr)
+}
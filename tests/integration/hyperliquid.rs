@@ -1,6 +1,8 @@
 //! Integration tests for the Hyperliquid-powered HTTP stack.
 mod test_utils;
 
+use perptrix::services::hyperliquid::client::ClientEvent;
+use perptrix::services::market_data::MarketDataProvider;
 use serde_json::Value;
 use tokio_tungstenite::tungstenite::Message;
 
@@ -60,3 +62,52 @@ async fn subscriptions_use_mocked_dependencies() {
     );
 }
 
+#[tokio::test]
+async fn all_mids_updates_latest_price() {
+    let app = TestApp::new().await;
+
+    let message = serde_json::json!({
+        "channel": "allMids",
+        "data": { "mids": { "BTC": "43210.5", "ETH": "2250.25" } }
+    });
+    app.websocket
+        .push_event(ClientEvent::Message(message.to_string()))
+        .await;
+
+    let mut price = 0.0;
+    for _ in 0..20 {
+        price = app.provider.get_latest_price("BTC").await.expect("price");
+        if price == 43210.5 {
+            break;
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+    }
+    assert_eq!(price, 43210.5);
+    assert_eq!(
+        app.provider.get_latest_price("ETH").await.expect("price"),
+        2250.25
+    );
+}
+
+#[tokio::test]
+async fn all_mids_subscription_sent_on_connect() {
+    let app = TestApp::new().await;
+
+    let mut subscribed = false;
+    for _ in 0..40 {
+        subscribed = app
+            .websocket
+            .sent_messages()
+            .await
+            .iter()
+            .any(|message| match message {
+                Message::Text(payload) => payload.contains("allMids"),
+                _ => false,
+            });
+        if subscribed {
+            break;
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+    }
+    assert!(subscribed, "Expected allMids subscription after connecting");
+}
@@ -1,13 +1,11 @@
 use std::sync::Arc;
-use std::time::Instant;
 
 use axum_test::TestServer;
-use perptrix::core::http::{create_router, AppState, HealthStatus};
+use perptrix::core::http::{create_router, AppState};
 use perptrix::metrics::Metrics;
 use perptrix::services::hyperliquid::{
     HyperliquidMarketDataProvider, HyperliquidRestClient, MockWebSocketClient,
 };
-use tokio::sync::RwLock;
 use wiremock::matchers::{body_string_contains, method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
@@ -40,12 +38,7 @@ impl TestApp {
         );
 
         let metrics = Arc::new(Metrics::new().expect("metrics initialization"));
-        let state = AppState {
-            health: Arc::new(RwLock::new(HealthStatus::default())),
-            metrics: metrics.clone(),
-            start_time: Arc::new(Instant::now()),
-            database: None,
-        };
+        let state = AppState::builder().with_metrics(metrics.clone()).build();
 
         let router = create_router(state);
         let server = TestServer::new(router).expect("start test server");
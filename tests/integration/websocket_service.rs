@@ -5,9 +5,25 @@
 #[path = "websocket_service/test_utils.rs"]
 mod test_utils;
 
-use tokio::time::{sleep, Duration};
+use futures_util::StreamExt;
+use perptrix::metrics::Metrics;
+use perptrix::services::hyperliquid::client::ClientEvent;
+use perptrix::services::hyperliquid::{
+    HyperliquidClient, HyperliquidMarketDataProvider, HyperliquidRestClient, MockWebSocketClient,
+    WebSocketClient,
+};
+use perptrix::services::market_data::MarketDataProvider;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tokio::time::{sleep, Duration, Instant};
+use tokio_tungstenite::accept_async;
+use wiremock::matchers::{body_string_contains, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
 
-use test_utils::TestWebSocketService;
+use test_utils::{
+    mock_hyperliquid_candles, mock_hyperliquid_funding_history, TestWebSocketService,
+};
 
 #[tokio::test]
 async fn websocket_service_initializes() {
@@ -152,3 +168,318 @@ async fn websocket_service_stores_data_to_cache() {
     assert!(true, "Service should store data when storage is configured");
 }
 
+
+/// Local WebSocket server that closes the first `drops` connections right
+/// after the handshake and keeps later ones open, answering pings.
+/// Returns its URL and the number of handshakes it has accepted.
+async fn flaky_websocket_server(drops: usize) -> (String, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}", listener.local_addr().unwrap());
+    let accepted = Arc::new(AtomicUsize::new(0));
+
+    let counter = accepted.clone();
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let Ok(mut ws) = accept_async(stream).await else {
+                continue;
+            };
+            if counter.fetch_add(1, Ordering::SeqCst) < drops {
+                let _ = ws.close(None).await;
+            } else {
+                tokio::spawn(async move { while let Some(Ok(_)) = ws.next().await {} });
+            }
+        }
+    });
+
+    (url, accepted)
+}
+
+#[tokio::test]
+async fn websocket_client_reconnects_after_consecutive_disconnects() {
+    let (url, accepted) = flaky_websocket_server(5).await;
+    let metrics = Arc::new(Metrics::new().expect("Should create metrics"));
+    let client = Arc::new(HyperliquidClient::with_url(url).with_metrics(metrics.clone()));
+
+    let connection = client.clone();
+    let handle = tokio::spawn(async move {
+        let _ = WebSocketClient::connect(connection.as_ref()).await;
+    });
+
+    // Every handshake resets the backoff, so each reconnect waits about 1s
+    let deadline = Instant::now() + Duration::from_secs(15);
+    while !(accepted.load(Ordering::SeqCst) == 6 && metrics.websocket_connected.get() == 1.0) {
+        assert!(
+            Instant::now() < deadline,
+            "client did not reconnect: {} handshakes",
+            accepted.load(Ordering::SeqCst)
+        );
+        sleep(Duration::from_millis(50)).await;
+    }
+
+    assert!(client.is_connected().await);
+    assert_eq!(metrics.websocket_reconnect_attempts_total.get(), 5.0);
+    assert!(metrics.websocket_last_reconnect_at_timestamp.get() > 0.0);
+    handle.abort();
+}
+
+#[tokio::test]
+async fn websocket_service_tracks_order_book_spread() {
+    let mut service = TestWebSocketService::new().await;
+    service.start().await.expect("Service should start");
+    service.websocket.set_connected(true).await;
+    service.service.subscribe("BTC").await.expect("Should subscribe to BTC");
+
+    let sent_messages = service.websocket.sent_messages().await;
+    assert!(
+        sent_messages.iter().any(|msg| matches!(
+            msg,
+            tokio_tungstenite::tungstenite::Message::Text(text)
+                if text.contains("l2Book") && text.contains("BTC")
+        )),
+        "Should subscribe to the BTC order book"
+    );
+
+    let book = serde_json::json!({
+        "channel": "l2Book",
+        "data": {
+            "coin": "BTC",
+            "time": 0,
+            "levels": [
+                [{"px": "100.0", "sz": "1", "n": 1}],
+                [{"px": "100.5", "sz": "1", "n": 1}]
+            ]
+        }
+    });
+    service
+        .websocket
+        .push_event(ClientEvent::Message(book.to_string()))
+        .await;
+
+    let provider = service.get_provider();
+    let deadline = Instant::now() + Duration::from_secs(5);
+    let mut spread = None;
+    while spread.is_none() && Instant::now() < deadline {
+        spread = provider.get_spread("BTC").await;
+        sleep(Duration::from_millis(20)).await;
+    }
+    assert_eq!(spread, Some((100.0, 100.5)));
+}
+
+#[tokio::test]
+async fn websocket_service_computes_buy_sell_ratio_from_trades() {
+    let mut service = TestWebSocketService::new().await;
+    service.start().await.expect("Service should start");
+    service.websocket.set_connected(true).await;
+    service.service.subscribe("BTC").await.expect("Should subscribe to BTC");
+
+    let sent_messages = service.websocket.sent_messages().await;
+    assert!(
+        sent_messages.iter().any(|msg| matches!(
+            msg,
+            tokio_tungstenite::tungstenite::Message::Text(text)
+                if text.contains("\"trades\"") && text.contains("BTC")
+        )),
+        "Should subscribe to BTC trades"
+    );
+
+    // 30 buys of 2.0 and 20 sells of 1.0: a ratio of 60 / 20
+    for i in 0..50u64 {
+        let (side, size) = if i % 5 < 3 {
+            ("B", "2.0")
+        } else {
+            ("A", "1.0")
+        };
+        let trade = serde_json::json!({
+            "channel": "trades",
+            "data": [{
+                "coin": "BTC",
+                "side": side,
+                "px": "65000.0",
+                "sz": size,
+                "hash": "0x0",
+                "time": 1_700_000_000_000u64 + i,
+                "tid": i,
+                "users": ["0xbuyer", "0xseller"]
+            }]
+        });
+        service
+            .websocket
+            .push_event(ClientEvent::Message(trade.to_string()))
+            .await;
+    }
+
+    let provider = service.get_provider();
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while provider.recent_trades("BTC").await.len() < 50 && Instant::now() < deadline {
+        sleep(Duration::from_millis(20)).await;
+    }
+    assert_eq!(provider.recent_trades("BTC").await.len(), 50);
+    assert_eq!(provider.get_buy_sell_ratio("BTC").await, Some(3.0));
+    assert_eq!(provider.get_buy_sell_ratio("ETH").await, None);
+}
+
+/// Provider on a disconnected mock WebSocket whose REST calls go to `server`
+fn provider_with_rest(server: &MockServer) -> (HyperliquidMarketDataProvider, Arc<Metrics>) {
+    let metrics = Arc::new(Metrics::new().expect("metrics initialization"));
+    let rest_client = Arc::new(HyperliquidRestClient::with_client(
+        server.uri(),
+        reqwest::Client::new(),
+    ));
+    let provider = HyperliquidMarketDataProvider::with_clients(
+        Arc::new(MockWebSocketClient::new()),
+        rest_client,
+        vec!["1m".to_string()],
+    )
+    .with_metrics(metrics.clone());
+    (provider, metrics)
+}
+
+/// Fail the first `times` candleSnapshot requests with a 500
+async fn mock_failing_candle_snapshots(server: &MockServer, times: u64) {
+    Mock::given(method("POST"))
+        .and(path("/info"))
+        .and(body_string_contains("candleSnapshot"))
+        .respond_with(ResponseTemplate::new(500))
+        .up_to_n_times(times)
+        .with_priority(1)
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn subscribe_backfills_candle_snapshot_into_buffer() {
+    let server = MockServer::start().await;
+    mock_hyperliquid_candles(&server).await;
+    mock_hyperliquid_funding_history(&server).await;
+    let (provider, metrics) = provider_with_rest(&server);
+
+    provider.subscribe("BTC").await.expect("Should subscribe");
+
+    let candles = provider.get_candles("BTC", 500).await.unwrap();
+    assert_eq!(candles.len(), 1);
+    assert_eq!(candles[0].close, 105.0);
+    assert_eq!(metrics.rest_candle_fetches_total.get(), 1.0);
+    assert_eq!(metrics.rest_candle_fetch_errors_total.get(), 0.0);
+}
+
+#[tokio::test]
+async fn subscribe_retries_failed_candle_snapshots() {
+    let server = MockServer::start().await;
+    mock_failing_candle_snapshots(&server, 2).await;
+    mock_hyperliquid_candles(&server).await;
+    mock_hyperliquid_funding_history(&server).await;
+    let (provider, metrics) = provider_with_rest(&server);
+
+    provider.subscribe("BTC").await.expect("Should subscribe");
+
+    assert_eq!(provider.get_candles("BTC", 500).await.unwrap().len(), 1);
+    assert_eq!(metrics.rest_candle_fetches_total.get(), 3.0);
+    assert_eq!(metrics.rest_candle_fetch_errors_total.get(), 2.0);
+}
+
+#[tokio::test]
+async fn subscribe_gives_up_after_three_snapshot_attempts() {
+    let server = MockServer::start().await;
+    mock_failing_candle_snapshots(&server, 10).await;
+    let (provider, metrics) = provider_with_rest(&server);
+
+    // A failed backfill doesn't fail the subscription
+    provider.subscribe("BTC").await.expect("Should subscribe");
+
+    assert_eq!(metrics.rest_candle_fetches_total.get(), 3.0);
+    assert_eq!(metrics.rest_candle_fetch_errors_total.get(), 3.0);
+}
+
+#[tokio::test]
+async fn websocket_service_resubscribes_when_candle_feed_goes_silent() {
+    use perptrix::services::hyperliquid::ProviderConfig;
+    use perptrix::services::websocket::WebSocketService;
+
+    let server = MockServer::start().await;
+    mock_hyperliquid_candles(&server).await;
+    mock_hyperliquid_funding_history(&server).await;
+    let websocket = Arc::new(MockWebSocketClient::new());
+    let metrics = Arc::new(Metrics::new().expect("metrics initialization"));
+    let provider = HyperliquidMarketDataProvider::with_clients(
+        websocket.clone(),
+        Arc::new(HyperliquidRestClient::with_client(
+            server.uri(),
+            reqwest::Client::new(),
+        )),
+        vec!["1m".to_string()],
+    )
+    .with_config(ProviderConfig {
+        candle_intervals: vec!["1m".to_string()],
+        max_candle_age_secs: Some(1),
+        ..ProviderConfig::default()
+    })
+    .with_metrics(metrics.clone());
+    let service =
+        WebSocketService::new(provider).with_freshness_check_interval(Duration::from_millis(200));
+    service.start().await.expect("Service should start");
+    websocket.set_connected(true).await;
+    service
+        .subscribe("BTC")
+        .await
+        .expect("Should subscribe to BTC");
+
+    // A live candle keeps the feed fresh and records when it arrived
+    let candle = serde_json::json!({
+        "channel": "candle",
+        "data": {
+            "t": 1_700_000_000_000u64,
+            "T": 1_700_000_059_999u64,
+            "s": "BTC",
+            "i": "1m",
+            "o": "100",
+            "h": "101",
+            "l": "99",
+            "c": "100.5",
+            "v": "10",
+            "n": 5
+        }
+    });
+    websocket
+        .push_event(ClientEvent::Message(candle.to_string()))
+        .await;
+    let gauge = metrics
+        .candle_last_received_timestamp_seconds
+        .with_label_values(&["BTC", "1m"]);
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while gauge.get() == 0.0 && Instant::now() < deadline {
+        sleep(Duration::from_millis(20)).await;
+    }
+    assert!(gauge.get() > 0.0, "Candle should be recorded");
+    assert!(service.check_data_freshness().await.is_empty());
+    assert_eq!(metrics.candles_stale_total.get(), 0.0);
+
+    // Then the feed goes silent while the connection stays up
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while metrics.candles_stale_total.get() == 0.0 && Instant::now() < deadline {
+        sleep(Duration::from_millis(50)).await;
+    }
+    service.stop().await;
+    assert_eq!(metrics.candles_stale_total.get(), 1.0);
+
+    let sent: Vec<String> = websocket
+        .sent_messages()
+        .await
+        .into_iter()
+        .filter_map(|msg| match msg {
+            tokio_tungstenite::tungstenite::Message::Text(text) => Some(text),
+            _ => None,
+        })
+        .filter(|text| text.contains("\"candle\""))
+        .collect();
+    let unsubscribe = sent
+        .iter()
+        .position(|text| text.contains("unsubscribe"))
+        .expect("Stale candle feed should be unsubscribed");
+    assert!(
+        sent[unsubscribe + 1..]
+            .iter()
+            .any(|text| text.contains("\"subscribe\"")),
+        "Stale candle feed should be subscribed again, sent {:?}",
+        sent
+    );
+}
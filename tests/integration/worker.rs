@@ -16,9 +16,7 @@ async fn worker_processes_fetch_candles_job() {
     let worker = TestWorker::new().await;
     
     // Enqueue a FetchCandlesJob
-    let job = FetchCandlesJob {
-        symbol: "BTC".to_string(),
-    };
+    let job = FetchCandlesJob::new("BTC");
     
     let mut storage = (*worker.fetch_storage).clone();
     storage.push(job)
@@ -40,10 +38,7 @@ async fn worker_processes_evaluate_signal_job() {
     let candles = test_utils::create_test_candles(250);
     
     // Enqueue an EvaluateSignalJob
-    let job = EvaluateSignalJob {
-        symbol: "BTC".to_string(),
-        candles,
-    };
+    let job = EvaluateSignalJob::new("BTC".to_string(), candles);
     
     let mut storage = (*worker.eval_storage).clone();
     storage.push(job)
@@ -100,9 +95,7 @@ async fn worker_workflow_chains_jobs() {
     let worker = TestWorker::new().await;
     
     // Start with FetchCandlesJob
-    let fetch_job = FetchCandlesJob {
-        symbol: "BTC".to_string(),
-    };
+    let fetch_job = FetchCandlesJob::new("BTC");
     
     let mut storage = (*worker.fetch_storage).clone();
     storage.push(fetch_job)
@@ -122,9 +115,7 @@ async fn worker_handles_missing_candles_gracefully() {
     let worker = TestWorker::new().await;
     
     // Enqueue a job for a symbol with no candles
-    let job = FetchCandlesJob {
-        symbol: "NONEXISTENT".to_string(),
-    };
+    let job = FetchCandlesJob::new("NONEXISTENT");
     
     let mut storage = (*worker.fetch_storage).clone();
     storage.push(job)
@@ -145,9 +136,7 @@ async fn worker_retries_failed_jobs() {
     let worker = TestWorker::new().await;
     
     // Enqueue a job that will fail
-    let job = FetchCandlesJob {
-        symbol: "INVALID".to_string(),
-    };
+    let job = FetchCandlesJob::new("INVALID");
     
     let mut storage = (*worker.fetch_storage).clone();
     storage.push(job)
@@ -168,9 +157,7 @@ async fn multiple_workers_process_jobs_in_parallel() {
     
     // Enqueue multiple jobs
     for i in 0..5 {
-        let job = FetchCandlesJob {
-            symbol: format!("SYMBOL{}", i),
-        };
+        let job = FetchCandlesJob::new(format!("SYMBOL{}", i));
         let mut storage = (*worker1.fetch_storage).clone();
         storage.push(job)
             .await
@@ -209,3 +196,1490 @@ async fn worker_reads_from_cache_not_websocket() {
     );
 }
 
+
+#[tokio::test]
+async fn cache_round_trips_latest_signal_as_json() {
+    use perptrix::cache::redis::latest_signal_key;
+    use perptrix::cache::RedisCache;
+    use perptrix::models::signal::{SignalDirection, SignalOutput, SignalReason};
+
+    let cache = RedisCache::new().await.expect("Should connect to Redis");
+
+    let signal = SignalOutput::new(
+        SignalDirection::Short,
+        0.62,
+        1.5,
+        3.0,
+        vec![SignalReason {
+            description: "Bearish MACD cross".to_string(),
+            weight: 0.8,
+        }],
+        "ETH".to_string(),
+        2500.0,
+    );
+
+    let key = latest_signal_key("ETH");
+    cache
+        .set_json(&key, &signal, 120)
+        .await
+        .expect("Should cache signal");
+
+    let cached: SignalOutput = cache
+        .get_json(&key)
+        .await
+        .expect("Should read cached signal")
+        .expect("Signal should be present");
+
+    assert_eq!(cached.direction, signal.direction);
+    assert_eq!(cached.confidence, signal.confidence);
+    assert_eq!(cached.recommended_sl_pct, signal.recommended_sl_pct);
+    assert_eq!(cached.recommended_tp_pct, signal.recommended_tp_pct);
+    assert_eq!(cached.symbol, signal.symbol);
+    assert_eq!(cached.price, signal.price);
+    assert_eq!(cached.timestamp, signal.timestamp);
+    assert_eq!(cached.reasons.len(), 1);
+    assert_eq!(cached.reasons[0].description, signal.reasons[0].description);
+    assert_eq!(cached.reasons[0].weight, signal.reasons[0].weight);
+}
+
+fn cache_test_candle(minute: i64, close: f64) -> perptrix::models::indicators::Candle {
+    let timestamp = chrono::DateTime::from_timestamp(1_700_000_000 + minute * 60, 0).unwrap();
+    perptrix::models::indicators::Candle::new(close - 1.0, close + 2.0, close - 2.0, close, 10.0, timestamp)
+        .with_open_interest(5_000.0)
+        .with_funding_rate(0.0001)
+}
+
+#[tokio::test]
+async fn candle_cache_round_trips_sorted_by_timestamp() {
+    use perptrix::cache::RedisCache;
+
+    let cache = RedisCache::new()
+        .await
+        .expect("Should connect to Redis")
+        .with_max_candles_per_symbol(4);
+    let symbol = format!("CACHE{}", chrono::Utc::now().timestamp_millis());
+    cache.invalidate_candles(&symbol, "1m").await.unwrap();
+
+    // Stored out of order; minute 2 is rewritten with a new close
+    let candles: Vec<_> = [3, 1, 2, 0, 4]
+        .iter()
+        .map(|&minute| cache_test_candle(minute, 100.0 + minute as f64))
+        .collect();
+    cache.store_candles(&symbol, "1m", &candles, 60).await.unwrap();
+    cache
+        .store_candles(&symbol, "1m", &[cache_test_candle(2, 250.0)], 60)
+        .await
+        .unwrap();
+
+    // Trimmed to the newest 4, returned oldest first
+    let cached = cache.get_candles(&symbol, "1m", 10).await.unwrap();
+    let closes: Vec<f64> = cached.iter().map(|c| c.close).collect();
+    assert_eq!(closes, vec![101.0, 250.0, 103.0, 104.0]);
+    assert_eq!(cached[0].timestamp, candles[1].timestamp);
+    assert_eq!(cached[0].open_interest, Some(5_000.0));
+    assert_eq!(cached[0].funding_rate, Some(0.0001));
+
+    let latest = cache.get_candles(&symbol, "1m", 2).await.unwrap();
+    assert_eq!(latest.iter().map(|c| c.close).collect::<Vec<_>>(), vec![103.0, 104.0]);
+
+    cache.invalidate_candles(&symbol, "1m").await.unwrap();
+    assert!(cache.get_candles(&symbol, "1m", 10).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn candle_cache_expires_after_ttl() {
+    use perptrix::cache::RedisCache;
+
+    let cache = RedisCache::new().await.expect("Should connect to Redis");
+    let symbol = format!("TTL{}", chrono::Utc::now().timestamp_millis());
+
+    cache
+        .store_candles(&symbol, "1m", &[cache_test_candle(0, 100.0)], 1)
+        .await
+        .unwrap();
+    assert_eq!(cache.get_candles(&symbol, "1m", 10).await.unwrap().len(), 1);
+
+    sleep(Duration::from_millis(2100)).await;
+    assert!(cache.get_candles(&symbol, "1m", 10).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn scheduler_picks_up_added_symbols_on_next_tick() {
+    use perptrix::core::scheduler::JobScheduler;
+
+    let worker = TestWorker::new().await;
+    let scheduler = JobScheduler::new(worker.fetch_storage.clone(), Vec::new(), 1)
+        .expect("Should create scheduler");
+
+    scheduler.add_symbol("DYN-PERP".to_string(), 1).await;
+    assert_eq!(scheduler.list_symbols().await, vec!["DYN-PERP".to_string()]);
+
+    let mut storage = (*worker.fetch_storage).clone();
+    let queued_before = storage.len().await.expect("Should read queue length");
+
+    scheduler.start().await.expect("Should start scheduler");
+    sleep(Duration::from_millis(2500)).await;
+    scheduler.stop().await;
+
+    let queued_after = storage.len().await.expect("Should read queue length");
+    assert!(
+        queued_after > queued_before,
+        "Added symbol should be enqueued on the next tick"
+    );
+
+    scheduler.remove_symbol("DYN-PERP").await;
+    assert!(scheduler.list_symbols().await.is_empty());
+}
+
+#[tokio::test]
+async fn scheduler_enqueues_catch_up_job_for_missed_ticks() {
+    use perptrix::cache::redis::scheduler_last_tick_key;
+    use perptrix::cache::RedisCache;
+    use perptrix::core::scheduler::JobScheduler;
+    use perptrix::metrics::Metrics;
+    use std::sync::Arc;
+
+    let worker = TestWorker::new().await;
+    let cache = Arc::new(RedisCache::new().await.expect("Should connect to Redis"));
+    let metrics = Arc::new(Metrics::new().expect("Should create metrics"));
+
+    // Unique per run so a fetch lock left by an earlier run cannot skip the job
+    let symbol = &format!("RECOVER-PERP-{}", chrono::Utc::now().timestamp_millis());
+    let last_tick = chrono::Utc::now() - chrono::Duration::minutes(5);
+    cache
+        .set_json(&scheduler_last_tick_key(symbol), &last_tick, 0)
+        .await
+        .expect("Should store last tick");
+
+    let scheduler = JobScheduler::new(worker.fetch_storage.clone(), vec![symbol.to_string()], 60)
+        .expect("Should create scheduler")
+        .with_cache(cache.clone())
+        .with_metrics(metrics.clone());
+
+    let mut storage = (*worker.fetch_storage).clone();
+    let queued_before = storage.len().await.expect("Should read queue length");
+
+    scheduler.start().await.expect("Should start scheduler");
+    scheduler.stop().await;
+
+    let queued_after = storage.len().await.expect("Should read queue length");
+    assert!(queued_after > queued_before, "Catch-up job should be enqueued");
+    assert!(metrics.scheduler_missed_ticks_recovered_total.get() >= 4.0);
+
+    let recorded: chrono::DateTime<chrono::Utc> = cache
+        .get_json(&scheduler_last_tick_key(symbol))
+        .await
+        .expect("Should read last tick")
+        .expect("Last tick should be recorded");
+    assert!(recorded > last_tick);
+
+    scheduler.remove_symbol(symbol).await;
+}
+
+#[tokio::test]
+async fn scheduler_deduplicates_fetch_jobs_within_an_interval() {
+    use perptrix::cache::RedisCache;
+    use perptrix::core::scheduler::{EnqueueOutcome, JobScheduler};
+    use perptrix::metrics::Metrics;
+    use std::sync::Arc;
+
+    let worker = TestWorker::new().await;
+    let cache = Arc::new(RedisCache::new().await.expect("Should connect to Redis"));
+    let metrics = Arc::new(Metrics::new().expect("Should create metrics"));
+
+    let symbol = format!("DEDUP-PERP-{}", chrono::Utc::now().timestamp_millis());
+    let other = format!("{}-2", symbol);
+    // A 2s interval holds each symbol's fetch lock for 1s
+    let scheduler = JobScheduler::new(worker.fetch_storage.clone(), vec![symbol.clone(), other.clone()], 2)
+        .expect("Should create scheduler")
+        .with_cache(cache.clone())
+        .with_metrics(metrics.clone());
+
+    // Rapid ticks, as when queued ticks fire back to back after a restart
+    let mut outcomes = Vec::new();
+    for _ in 0..5 {
+        outcomes.push(scheduler.enqueue_deduplicated(&symbol).await);
+    }
+    assert_eq!(outcomes[0], EnqueueOutcome::Enqueued);
+    assert!(outcomes[1..].iter().all(|outcome| *outcome == EnqueueOutcome::Deduplicated));
+    assert_eq!(metrics.jobs_deduplicated_total.get(), 4.0);
+
+    // Locks are per symbol
+    assert_eq!(scheduler.enqueue_deduplicated(&other).await, EnqueueOutcome::Enqueued);
+
+    // Once the lock expires the next interval's job goes through
+    sleep(Duration::from_millis(1200)).await;
+    assert_eq!(scheduler.enqueue_deduplicated(&symbol).await, EnqueueOutcome::Enqueued);
+}
+
+#[tokio::test]
+async fn scheduler_releases_the_fetch_lock_when_the_push_fails() {
+    use apalis_redis::{Config, RedisStorage};
+    use perptrix::cache::RedisCache;
+    use perptrix::core::scheduler::{EnqueueOutcome, JobScheduler};
+    use std::sync::Arc;
+
+    let cache = Arc::new(RedisCache::new().await.expect("Should connect to Redis"));
+    let conn = apalis_redis::connect(test_utils::redis_url())
+        .await
+        .expect("Should connect to Redis");
+    let namespace = format!("lock-release-test-{}", chrono::Utc::now().timestamp_millis());
+    let config = Config::default().set_namespace(&namespace);
+    // A string where the queue expects its job hash makes every push fail
+    let job_data_hash = config.job_data_hash();
+    cache
+        .set_json(&job_data_hash, &"not a hash", 0)
+        .await
+        .expect("Should write to Redis");
+    let storage = Arc::new(RedisStorage::new_with_config(conn, config));
+
+    let symbol = format!("LOCK-PERP-{}", chrono::Utc::now().timestamp_millis());
+    let scheduler = JobScheduler::new(storage, vec![symbol.clone()], 60)
+        .expect("Should create scheduler")
+        .with_cache(cache.clone());
+
+    assert_eq!(scheduler.enqueue_deduplicated(&symbol).await, EnqueueOutcome::Failed);
+
+    // Without the released lock this would be deduplicated for the next 59s
+    cache.delete(&job_data_hash).await.expect("Should delete from Redis");
+    assert_eq!(scheduler.enqueue_deduplicated(&symbol).await, EnqueueOutcome::Enqueued);
+}
+
+/// Serves the same candles for every symbol, recording the order of fetches
+struct RecordingProvider {
+    candles: Vec<perptrix::models::indicators::Candle>,
+    fetched: std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+}
+
+#[async_trait::async_trait]
+impl perptrix::services::market_data::MarketDataProvider for RecordingProvider {
+    async fn get_candles(
+        &self,
+        symbol: &str,
+        _limit: usize,
+    ) -> Result<Vec<perptrix::models::indicators::Candle>, Box<dyn std::error::Error + Send + Sync>> {
+        self.fetched.lock().unwrap().push(symbol.to_string());
+        Ok(self.candles.clone())
+    }
+
+    async fn get_latest_price(
+        &self,
+        _symbol: &str,
+    ) -> Result<f64, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(self.candles.last().map(|candle| candle.close).unwrap_or_default())
+    }
+
+    async fn subscribe(&self, _symbol: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn high_priority_fetch_jobs_are_processed_before_low_priority_ones() {
+    use apalis_redis::{Config, RedisStorage};
+    use perptrix::core::runtime::{RuntimeConfig, SignalRuntime};
+    use perptrix::jobs::context::JobContext;
+    use perptrix::jobs::types::JobPriority;
+    use std::sync::{Arc, Mutex};
+
+    let worker = TestWorker::new().await;
+    let conn = apalis_redis::connect(test_utils::redis_url())
+        .await
+        .expect("Should connect to Redis");
+    // Fresh queues so jobs left by other tests cannot interleave
+    let namespace = format!("priority-test-{}", chrono::Utc::now().timestamp_millis());
+    let fetch_storage = Arc::new(RedisStorage::new_with_config(
+        conn.clone(),
+        Config::default().set_namespace(&namespace),
+    ));
+    let priority_storage = Arc::new(RedisStorage::new_with_config(
+        conn,
+        Config::default().set_namespace(&format!("{}:high", namespace)),
+    ));
+
+    // Both queues are full before any worker starts, low-priority jobs first
+    let mut storage = (*fetch_storage).clone();
+    for _ in 0..3 {
+        storage
+            .push(FetchCandlesJob::new("ETH").with_priority(JobPriority::Low))
+            .await
+            .expect("Should enqueue low-priority job");
+    }
+    let mut storage = (*priority_storage).clone();
+    for _ in 0..3 {
+        storage
+            .push(FetchCandlesJob::new("BTC").with_priority(JobPriority::High))
+            .await
+            .expect("Should enqueue high-priority job");
+    }
+
+    let fetched = Arc::new(Mutex::new(Vec::new()));
+    let provider = RecordingProvider {
+        candles: test_utils::create_test_candles(250),
+        fetched: fetched.clone(),
+    };
+    let job_context = Arc::new(JobContext::new(Arc::new(provider), None, None));
+    let runtime = SignalRuntime::new(
+        RuntimeConfig::default(),
+        job_context,
+        fetch_storage,
+        worker.eval_storage.clone(),
+        worker.store_storage.clone(),
+    )
+    .with_priority_storage(priority_storage);
+    let handles = runtime.start_workers().await.expect("Should start workers");
+
+    sleep(Duration::from_secs(3)).await;
+    for handle in handles {
+        handle.abort();
+    }
+
+    let fetched = fetched.lock().unwrap().clone();
+    assert_eq!(fetched.len(), 6, "All jobs should be processed: {:?}", fetched);
+    assert_eq!(fetched[..3], ["BTC", "BTC", "BTC"], "{:?}", fetched);
+    assert_eq!(fetched[3..], ["ETH", "ETH", "ETH"], "{:?}", fetched);
+}
+
+fn replay_queues(worker: &TestWorker) -> perptrix::jobs::JobQueues {
+    perptrix::jobs::JobQueues {
+        fetch: (*worker.fetch_storage).clone(),
+        evaluate: (*worker.eval_storage).clone(),
+        store: (*worker.store_storage).clone(),
+    }
+}
+
+/// Store the dead letter, replay it and mark it replayed, as the API does
+async fn replay_stored_dead_letter(
+    db: &perptrix::db::QuestDatabase,
+    worker: &TestWorker,
+    dead_letter: &perptrix::models::dead_letter::DeadLetter,
+) -> perptrix::models::dead_letter::DeadLetter {
+    let id = db.store_dead_letter(dead_letter).await.expect("Should store dead letter");
+    let stored = db.get_dead_letter(id).await.expect("Should load dead letter");
+    assert!(!stored.is_replayed());
+    assert_eq!(stored.payload, dead_letter.payload);
+
+    perptrix::jobs::replay::replay_dead_letter(&stored, &replay_queues(worker))
+        .await
+        .expect("Should replay dead letter");
+    db.mark_dead_letter_replayed(id, chrono::Utc::now())
+        .await
+        .expect("Should mark dead letter replayed");
+
+    let replayed = db.get_dead_letter(id).await.expect("Should reload dead letter");
+    assert!(replayed.is_replayed());
+    replayed
+}
+
+#[tokio::test]
+async fn dead_letter_replay_requeues_fetch_candles_job() {
+    use perptrix::jobs::handlers::handle_fetch_candles;
+
+    let worker = TestWorker::new().await;
+    let Some(db) = worker.job_context.database.clone() else {
+        eprintln!("Skipping replay test, QuestDB unavailable");
+        return;
+    };
+
+    // The mock provider has no candles for this symbol, so the job fails
+    let symbol = format!("REPLAY{}", chrono::Utc::now().timestamp_millis());
+    let result = handle_fetch_candles(
+        FetchCandlesJob::new(symbol.clone()),
+        Data::new(worker.job_context.clone()),
+        Data::new((*worker.eval_storage).clone()),
+        Attempt::new_with_value(0),
+    )
+    .await;
+    assert!(result.is_err());
+
+    let recorded = db
+        .get_dead_letters(Some(&symbol), Some("fetch_candles"), 10)
+        .await
+        .expect("Should load dead letters");
+    assert_eq!(recorded.len(), 1);
+    let payload: FetchCandlesJob =
+        serde_json::from_str(recorded[0].payload.as_deref().expect("payload recorded")).unwrap();
+    assert_eq!(payload.symbol, symbol);
+
+    let mut storage = (*worker.fetch_storage).clone();
+    let queued_before = storage.len().await.expect("Should read queue length");
+    perptrix::jobs::replay::replay_dead_letter(&recorded[0], &replay_queues(&worker))
+        .await
+        .expect("Should replay dead letter");
+    let queued_after = storage.len().await.expect("Should read queue length");
+    assert_eq!(queued_after, queued_before + 1);
+}
+
+#[tokio::test]
+async fn dead_letter_replay_requeues_evaluate_signal_job() {
+    use perptrix::models::dead_letter::DeadLetter;
+
+    let worker = TestWorker::new().await;
+    let Some(db) = worker.job_context.database.clone() else {
+        eprintln!("Skipping replay test, QuestDB unavailable");
+        return;
+    };
+
+    let symbol = format!("REPLAY{}", chrono::Utc::now().timestamp_millis());
+    let job = EvaluateSignalJob::new(symbol.clone(), test_utils::create_test_candles(60));
+    let dead_letter = DeadLetter::new("evaluate_signal", &symbol, "Failed to load strategies")
+        .with_payload(&job);
+
+    let mut storage = (*worker.eval_storage).clone();
+    let queued_before = storage.len().await.expect("Should read queue length");
+    replay_stored_dead_letter(&db, &worker, &dead_letter).await;
+    let queued_after = storage.len().await.expect("Should read queue length");
+    assert_eq!(queued_after, queued_before + 1);
+
+    let stats = db.get_dead_letter_stats().await.expect("Should load stats");
+    assert!(stats
+        .iter()
+        .any(|s| s.job_type == "evaluate_signal" && s.is_replayed && s.count >= 1));
+}
+
+#[tokio::test]
+async fn dead_letter_replay_requeues_store_signal_job() {
+    use perptrix::models::dead_letter::DeadLetter;
+    use perptrix::models::signal::{SignalDirection, SignalOutput};
+
+    let worker = TestWorker::new().await;
+    let Some(db) = worker.job_context.database.clone() else {
+        eprintln!("Skipping replay test, QuestDB unavailable");
+        return;
+    };
+
+    let symbol = format!("REPLAY{}", chrono::Utc::now().timestamp_millis());
+    let job = StoreSignalJob {
+        symbol: symbol.clone(),
+        signal: SignalOutput::new(SignalDirection::Short, 0.8, 2.0, 4.0, Vec::new(), symbol.clone(), 100.0),
+        strategy_id: 3,
+    };
+    let dead_letter = DeadLetter::new("store_signal", &symbol, "connection reset")
+        .with_strategy_id(3)
+        .with_payload(&job);
+
+    let mut storage = (*worker.store_storage).clone();
+    let queued_before = storage.len().await.expect("Should read queue length");
+    let replayed = replay_stored_dead_letter(&db, &worker, &dead_letter).await;
+    let queued_after = storage.len().await.expect("Should read queue length");
+    assert_eq!(queued_after, queued_before + 1);
+    assert_eq!(replayed.strategy_id, Some(3));
+
+    // Store jobs can't be rebuilt without their signal
+    let mut missing_payload = replayed.clone();
+    missing_payload.payload = None;
+    let err = perptrix::jobs::replay::replay_dead_letter(&missing_payload, &replay_queues(&worker))
+        .await
+        .expect_err("Replay without payload should fail");
+    assert!(err.to_string().contains("no stored payload"));
+}
+
+#[tokio::test]
+async fn store_signal_job_broadcasts_to_signal_stream() {
+    use perptrix::jobs::context::JobContext;
+    use perptrix::jobs::handlers::handle_store_signal;
+    use perptrix::models::signal::{SignalDirection, SignalOutput};
+    use perptrix::services::market_data::MockMarketDataProvider;
+    use std::sync::Arc;
+    use tokio::sync::broadcast;
+
+    let (signal_stream, mut receiver) = broadcast::channel(8);
+    let ctx = Arc::new(
+        JobContext::new(Arc::new(MockMarketDataProvider::new()), None, None)
+            .with_signal_stream(signal_stream),
+    );
+    let signal = SignalOutput::new(SignalDirection::Short, 0.6, 1.0, 2.0, Vec::new(), "ETH".to_string(), 3_000.0);
+
+    handle_store_signal(
+        StoreSignalJob {
+            symbol: signal.symbol.clone(),
+            signal: signal.clone(),
+            strategy_id: 1,
+        },
+        Data::new(ctx),
+        Attempt::new_with_value(0),
+    )
+    .await
+    .expect("Should store signal");
+
+    let streamed = receiver.try_recv().expect("Signal should be broadcast");
+    assert_eq!(streamed.symbol, "ETH");
+    assert_eq!(streamed.timestamp, signal.timestamp);
+    assert!(receiver.try_recv().is_err());
+}
+
+#[tokio::test]
+async fn store_signal_job_does_not_broadcast_non_actionable_signals() {
+    use perptrix::jobs::context::JobContext;
+    use perptrix::jobs::handlers::handle_store_signal;
+    use perptrix::models::signal::{SignalDirection, SignalOutput};
+    use perptrix::services::market_data::MockMarketDataProvider;
+    use std::sync::Arc;
+    use tokio::sync::broadcast;
+
+    let (signal_stream, mut receiver) = broadcast::channel(8);
+    let ctx = Arc::new(
+        JobContext::new(Arc::new(MockMarketDataProvider::new()), None, None)
+            .with_signal_stream(signal_stream),
+    );
+    let neutral = SignalOutput::new_neutral("ETH".to_string(), 3_000.0);
+    let no_confidence = SignalOutput::new(
+        SignalDirection::Long,
+        0.0,
+        1.0,
+        2.0,
+        Vec::new(),
+        "ETH".to_string(),
+        3_000.0,
+    );
+
+    for signal in [neutral, no_confidence] {
+        handle_store_signal(
+            StoreSignalJob {
+                symbol: signal.symbol.clone(),
+                signal,
+                strategy_id: 1,
+            },
+            Data::new(ctx.clone()),
+            Attempt::new_with_value(0),
+        )
+        .await
+        .expect("Should store signal");
+    }
+
+    assert!(
+        receiver.try_recv().is_err(),
+        "Non-actionable signals should not be broadcast"
+    );
+}
+
+#[tokio::test]
+async fn store_signal_job_skips_signals_stacking_on_an_open_position() {
+    use perptrix::cache::RedisCache;
+    use perptrix::jobs::context::JobContext;
+    use perptrix::jobs::handlers::handle_store_signal;
+    use perptrix::metrics::Metrics;
+    use perptrix::models::signal::{SignalDirection, SignalOutput};
+    use perptrix::positions::PositionTracker;
+    use perptrix::services::market_data::MockMarketDataProvider;
+    use std::sync::Arc;
+    use tokio::sync::broadcast;
+
+    let cache = Arc::new(RedisCache::new().await.expect("Should connect to Redis"));
+    let metrics = Arc::new(Metrics::new().expect("Should create metrics"));
+    let (signal_stream, mut receiver) = broadcast::channel(8);
+    let ctx = Arc::new(
+        JobContext::new(Arc::new(MockMarketDataProvider::new()), None, Some(metrics.clone()))
+            .with_cache(cache.clone())
+            .with_signal_stream(signal_stream),
+    );
+    let symbol = format!("STACK{}", chrono::Utc::now().timestamp_millis());
+    let store = |direction| {
+        let signal = SignalOutput::new(direction, 0.7, 1.0, 2.0, Vec::new(), symbol.clone(), 100.0);
+        handle_store_signal(
+            StoreSignalJob {
+                symbol: symbol.clone(),
+                signal,
+                strategy_id: 1,
+            },
+            Data::new(ctx.clone()),
+            Attempt::new_with_value(0),
+        )
+    };
+
+    store(SignalDirection::Long).await.expect("Should store signal");
+    assert!(receiver.try_recv().is_ok(), "First long should be broadcast");
+
+    // Same direction while the long is open
+    store(SignalDirection::Long).await.expect("Should skip signal");
+    assert!(receiver.try_recv().is_err(), "Second long should be skipped");
+    assert_eq!(metrics.signals_suppressed_duplicate_direction_total.get(), 1.0);
+
+    // A reversal goes through and replaces the open position
+    store(SignalDirection::Short).await.expect("Should store signal");
+    assert!(receiver.try_recv().is_ok(), "Short should be broadcast");
+    let positions = PositionTracker::new(cache);
+    let open = positions.open_position(&symbol).await.expect("Should read position");
+    assert_eq!(open.map(|p| p.direction), Some(SignalDirection::Short));
+
+    // Closing the position lets the same direction through again
+    assert!(positions.close(&symbol).await.expect("Should close position"));
+    store(SignalDirection::Short).await.expect("Should store signal");
+    assert!(receiver.try_recv().is_ok(), "Short after close should be broadcast");
+    assert_eq!(metrics.signals_suppressed_duplicate_direction_total.get(), 1.0);
+
+    positions.close(&symbol).await.expect("Should close position");
+}
+
+#[tokio::test]
+async fn store_signal_job_discounts_confidence_by_rolling_sharpe() {
+    use perptrix::jobs::context::JobContext;
+    use perptrix::jobs::handlers::handle_store_signal;
+    use perptrix::models::signal::{SignalDirection, SignalOutput};
+    use perptrix::services::market_data::MockMarketDataProvider;
+    use perptrix::signals::risk::RollingSharpePenalty;
+    use std::sync::Arc;
+    use tokio::sync::{broadcast, RwLock};
+
+    // Mean 0.5 over a sample standard deviation of 1.0: Sharpe 0.5
+    let mut penalty = RollingSharpePenalty::default();
+    for i in 0..20 {
+        penalty.record_trade(if i % 2 == 0 { 1.5 } else { -0.5 });
+    }
+    let sharpe = penalty.current_sharpe();
+    assert!(sharpe < 1.0, "{}", sharpe);
+
+    let (signal_stream, mut receiver) = broadcast::channel(8);
+    let ctx = Arc::new(
+        JobContext::new(Arc::new(MockMarketDataProvider::new()), None, None)
+            .with_signal_stream(signal_stream)
+            .with_sharpe_penalty(Arc::new(RwLock::new(penalty))),
+    );
+    let signal = SignalOutput::new(SignalDirection::Long, 0.8, 1.0, 2.0, Vec::new(), "ETH".to_string(), 3_000.0);
+
+    handle_store_signal(
+        StoreSignalJob {
+            symbol: signal.symbol.clone(),
+            signal,
+            strategy_id: 1,
+        },
+        Data::new(ctx),
+        Attempt::new_with_value(0),
+    )
+    .await
+    .expect("Should store signal");
+
+    let streamed = receiver.try_recv().expect("Signal should be broadcast");
+    assert!((streamed.confidence - 0.8 * sharpe).abs() < 1e-9, "{}", streamed.confidence);
+}
+
+#[tokio::test]
+async fn store_signal_job_records_the_sharpe_trade_only_once_stored() {
+    use perptrix::db::QuestDatabase;
+    use perptrix::jobs::context::JobContext;
+    use perptrix::jobs::handlers::handle_store_signal;
+    use perptrix::models::signal::{SignalDirection, SignalOutput};
+    use perptrix::services::market_data::MockMarketDataProvider;
+    use perptrix::signals::risk::RollingSharpePenalty;
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+
+    let db = match QuestDatabase::connect(std::time::Duration::ZERO).await {
+        Ok(db) => Arc::new(db),
+        Err(_) => {
+            eprintln!("Skipping Sharpe store failure test, QuestDB unavailable");
+            return;
+        }
+    };
+
+    // An open long with its stop loss at 99
+    let mut penalty = RollingSharpePenalty::default();
+    let open = SignalOutput::new(SignalDirection::Long, 0.8, 1.0, 2.0, Vec::new(), "ETH".to_string(), 100.0);
+    penalty.record_signal(1, &open);
+    let penalty = Arc::new(RwLock::new(penalty));
+    let ctx = Arc::new(
+        JobContext::new(Arc::new(MockMarketDataProvider::new()), Some(db), None)
+            .with_sharpe_penalty(penalty.clone()),
+    );
+
+    // Crosses the stop loss, but QuestDB rejects designated timestamps before 1970
+    let mut signal = SignalOutput::new(SignalDirection::Short, 0.8, 1.0, 2.0, Vec::new(), "ETH".to_string(), 90.0);
+    signal.timestamp = chrono::DateTime::from_timestamp(-60, 0).unwrap();
+    for attempt in 0..2 {
+        let result = handle_store_signal(
+            StoreSignalJob {
+                symbol: signal.symbol.clone(),
+                signal: signal.clone(),
+                strategy_id: 1,
+            },
+            Data::new(ctx.clone()),
+            Attempt::new_with_value(attempt),
+        )
+        .await;
+        assert!(result.is_err(), "Store should fail");
+    }
+    assert_eq!(penalty.read().await.trade_count(), 0);
+
+    // Once a store succeeds the open trade closes, once
+    signal.timestamp = chrono::Utc::now();
+    handle_store_signal(
+        StoreSignalJob {
+            symbol: signal.symbol.clone(),
+            signal,
+            strategy_id: 1,
+        },
+        Data::new(ctx),
+        Attempt::new_with_value(0),
+    )
+    .await
+    .expect("Should store signal");
+    assert_eq!(penalty.read().await.trade_count(), 1);
+}
+
+#[tokio::test]
+async fn store_signal_job_posts_discord_embed() {
+    use perptrix::jobs::context::JobContext;
+    use perptrix::jobs::handlers::handle_store_signal;
+    use perptrix::models::signal::{SignalDirection, SignalOutput, SignalReason};
+    use perptrix::output::DiscordNotifier;
+    use perptrix::services::market_data::MockMarketDataProvider;
+    use serde_json::Value;
+    use std::sync::Arc;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let webhook = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/webhook"))
+        .respond_with(ResponseTemplate::new(204))
+        .mount(&webhook)
+        .await;
+
+    let notifier = DiscordNotifier::new(format!("{}/webhook", webhook.uri()), 0.7);
+    let ctx = Arc::new(
+        JobContext::new(Arc::new(MockMarketDataProvider::new()), None, None)
+            .with_discord(Arc::new(notifier)),
+    );
+    let reasons = (1..=4)
+        .map(|weight| SignalReason {
+            description: format!("Rule {} passed", weight),
+            weight: weight as f64,
+        })
+        .collect();
+    let store = |signal: SignalOutput| {
+        handle_store_signal(
+            StoreSignalJob {
+                symbol: signal.symbol.clone(),
+                signal,
+                strategy_id: 1,
+            },
+            Data::new(ctx.clone()),
+            Attempt::new_with_value(0),
+        )
+    };
+
+    store(SignalOutput::new(SignalDirection::Long, 0.85, 1.5, 2.5, reasons, "BTC".to_string(), 50_000.0))
+        .await
+        .expect("Should store signal");
+    // Below the threshold, and neutral, signals are not posted
+    store(SignalOutput::new(SignalDirection::Short, 0.5, 1.5, 2.5, Vec::new(), "BTC".to_string(), 50_000.0))
+        .await
+        .expect("Should store signal");
+    store(SignalOutput::new(SignalDirection::Neutral, 0.9, 0.0, 0.0, Vec::new(), "BTC".to_string(), 50_000.0))
+        .await
+        .expect("Should store signal");
+
+    let requests = webhook.received_requests().await.expect("wiremock requests");
+    assert_eq!(requests.len(), 1);
+    let body: Value = serde_json::from_slice(&requests[0].body).expect("JSON payload");
+    let embeds = body["embeds"].as_array().expect("embeds array");
+    assert_eq!(embeds.len(), 1);
+
+    let embed = &embeds[0];
+    assert_eq!(embed["title"], "BTC Long");
+    assert!(embed["color"].is_u64());
+    assert!(embed["timestamp"]
+        .as_str()
+        .is_some_and(|timestamp| chrono::DateTime::parse_from_rfc3339(timestamp).is_ok()));
+    // Top three reasons by weight
+    assert_eq!(
+        embed["description"],
+        "• Rule 4 passed\n• Rule 3 passed\n• Rule 2 passed"
+    );
+
+    let fields: Vec<(&str, &str)> = embed["fields"]
+        .as_array()
+        .expect("fields array")
+        .iter()
+        .map(|field| {
+            assert_eq!(field["inline"], true);
+            (field["name"].as_str().unwrap(), field["value"].as_str().unwrap())
+        })
+        .collect();
+    assert_eq!(
+        fields,
+        [
+            ("Symbol", "BTC"),
+            ("Direction", "Long"),
+            ("Confidence", "85.0%"),
+            ("Price", "50000"),
+            ("Stop Loss", "1.50%"),
+            ("Take Profit", "2.50%"),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn store_signal_job_sends_rate_limited_telegram_message() {
+    use perptrix::jobs::context::JobContext;
+    use perptrix::jobs::handlers::handle_store_signal;
+    use perptrix::models::signal::{SignalDirection, SignalOutput, SignalReason};
+    use perptrix::output::{NotificationConfig, TelegramNotifier};
+    use perptrix::services::market_data::MockMarketDataProvider;
+    use serde_json::Value;
+    use std::sync::Arc;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let api = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/bot123:token/sendMessage"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "ok": true })))
+        .mount(&api)
+        .await;
+
+    let notifier = TelegramNotifier::new("123:token", "-100200", 0.6).with_api_base(api.uri());
+    let ctx = Arc::new(
+        JobContext::new(Arc::new(MockMarketDataProvider::new()), None, None)
+            .with_notifications(NotificationConfig::default().with_telegram(Arc::new(notifier))),
+    );
+    let store = |symbol: &str, direction: SignalDirection, confidence: f64| {
+        let reasons = vec![SignalReason {
+            description: "rsi_oversold passed".to_string(),
+            weight: 1.0,
+        }];
+        let signal = SignalOutput::new(direction, confidence, 1.5, 2.5, reasons, symbol.to_string(), 50_000.0);
+        handle_store_signal(
+            StoreSignalJob {
+                symbol: symbol.to_string(),
+                signal,
+                strategy_id: 1,
+            },
+            Data::new(ctx.clone()),
+            Attempt::new_with_value(0),
+        )
+    };
+
+    store("BTC", SignalDirection::Long, 0.8).await.expect("Should store signal");
+    // Same symbol within a minute is held back, other symbols are not
+    store("BTC", SignalDirection::Short, 0.9).await.expect("Should store signal");
+    store("ETH", SignalDirection::Short, 0.7).await.expect("Should store signal");
+    // Below the threshold
+    store("SOL", SignalDirection::Long, 0.5).await.expect("Should store signal");
+
+    let requests = api.received_requests().await.expect("wiremock requests");
+    let bodies: Vec<Value> = requests
+        .iter()
+        .map(|request| serde_json::from_slice(&request.body).expect("JSON payload"))
+        .collect();
+    assert_eq!(bodies.len(), 2);
+    assert!(bodies.iter().all(|body| body["chat_id"] == "-100200" && body["parse_mode"] == "Markdown"));
+
+    let text = bodies[0]["text"].as_str().unwrap();
+    assert!(text.starts_with("📈 *BTC Long*"), "{}", text);
+    assert!(text.contains("Confidence: ████████░░ 80.0%"), "{}", text);
+    assert!(text.contains("Price: 50000"), "{}", text);
+    assert!(text.contains("SL: 1.50% | TP: 2.50%"), "{}", text);
+    // Markdown characters in reasons are escaped
+    assert!(text.contains("• rsi\\_oversold passed"), "{}", text);
+    assert!(bodies[1]["text"].as_str().unwrap().starts_with("📉 *ETH Short*"));
+}
+
+#[tokio::test]
+async fn store_signal_job_delivers_signed_webhook_and_counts_deliveries() {
+    use perptrix::jobs::context::JobContext;
+    use perptrix::jobs::handlers::handle_store_signal;
+    use perptrix::metrics::Metrics;
+    use perptrix::models::signal::{SignalDirection, SignalOutput};
+    use perptrix::output::webhook::{signature, SIGNATURE_HEADER};
+    use perptrix::output::WebhookNotifier;
+    use perptrix::services::market_data::MockMarketDataProvider;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/ok"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/down"))
+        .respond_with(ResponseTemplate::new(502))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/hung"))
+        .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(60)))
+        .mount(&server)
+        .await;
+
+    let metrics = Arc::new(Metrics::new().expect("Should create metrics"));
+    let store = |endpoint: &str, direction: SignalDirection| {
+        let webhook = WebhookNotifier::new(format!("{}/{}", server.uri(), endpoint), Some("s3cret".to_string()))
+            .with_initial_backoff(Duration::from_millis(1));
+        let ctx = Arc::new(
+            JobContext::new(Arc::new(MockMarketDataProvider::new()), None, Some(metrics.clone()))
+                .with_webhook(Arc::new(webhook)),
+        );
+        let signal = SignalOutput::new(direction, 0.3, 1.5, 2.5, Vec::new(), "BTC".to_string(), 50_000.0);
+        async move {
+            handle_store_signal(
+                StoreSignalJob {
+                    symbol: signal.symbol.clone(),
+                    signal,
+                    strategy_id: 1,
+                },
+                Data::new(ctx),
+                Attempt::new_with_value(0),
+            )
+            .await
+        }
+    };
+
+    // Every signal is delivered, whatever its direction or confidence
+    store("ok", SignalDirection::Neutral).await.expect("Should store signal");
+    // A failed delivery does not fail the job
+    store("down", SignalDirection::Long).await.expect("Should store signal");
+    // Delivery runs in the background, so a hung endpoint cannot stall the job
+    tokio::time::timeout(Duration::from_secs(30), store("hung", SignalDirection::Short))
+        .await
+        .expect("Store job should not wait on webhook delivery")
+        .expect("Should store signal");
+
+    for _ in 0..100 {
+        if metrics.webhook_deliveries_total.get() + metrics.webhook_failures_total.get() >= 2.0 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    let requests = server.received_requests().await.expect("wiremock requests");
+    let delivered = &requests[0];
+    assert_eq!(delivered.url.path(), "/ok");
+    assert_eq!(
+        delivered.headers.get(SIGNATURE_HEADER).unwrap().to_str().unwrap(),
+        signature("s3cret", &delivered.body)
+    );
+    assert_eq!(requests.iter().filter(|r| r.url.path() == "/down").count(), 4);
+
+    assert_eq!(metrics.webhook_deliveries_total.get(), 1.0);
+    assert_eq!(metrics.webhook_failures_total.get(), 1.0);
+    assert_eq!(metrics.webhook_delivery_latency_seconds.get_sample_count(), 2);
+}
+
+#[tokio::test]
+async fn evaluate_signal_job_uses_cached_strategies_without_database() {
+    use perptrix::cache::redis::strategies_key;
+    use perptrix::cache::RedisCache;
+    use perptrix::jobs::context::JobContext;
+    use perptrix::jobs::handlers::handle_evaluate_signal;
+    use perptrix::metrics::Metrics;
+    use perptrix::models::strategy::Strategy;
+    use perptrix::services::market_data::MockMarketDataProvider;
+    use std::sync::Arc;
+
+    let worker = TestWorker::new().await;
+    let cache = Arc::new(RedisCache::new().await.expect("Should connect to Redis"));
+    let metrics = Arc::new(Metrics::new().expect("Should create metrics"));
+    let symbol = format!("CACHED{}", chrono::Utc::now().timestamp_millis());
+    let strategy = Strategy {
+        id: Some(7),
+        symbol: symbol.clone(),
+        ..Strategy::default()
+    };
+    cache
+        .set_json(&strategies_key(&symbol), &vec![strategy], 60)
+        .await
+        .expect("Should cache strategies");
+
+    let ctx = Arc::new(
+        JobContext::new(Arc::new(MockMarketDataProvider::new()), None, Some(metrics.clone()))
+            .with_cache(cache),
+    );
+    let strategies = ctx.get_strategies_cached(&symbol).await;
+    assert_eq!(strategies.len(), 1);
+    assert_eq!(strategies[0].id, Some(7));
+
+    handle_evaluate_signal(
+        EvaluateSignalJob::new(symbol.clone(), test_utils::create_test_candles(250)),
+        Data::new(ctx.clone()),
+        Data::new((*worker.store_storage).clone()),
+        Attempt::new_with_value(0),
+    )
+    .await
+    .expect("Should evaluate cached strategies");
+    assert_eq!(metrics.strategy_cache_hits_total.get(), 2.0);
+    assert_eq!(metrics.strategy_cache_misses_total.get(), 0.0);
+
+    // Nothing cached and no database to fall back to
+    assert!(ctx.get_strategies_cached(&format!("{}X", symbol)).await.is_empty());
+    assert_eq!(metrics.strategy_cache_misses_total.get(), 1.0);
+}
+
+#[tokio::test]
+async fn strategy_cache_miss_loads_from_database_and_caches() {
+    use perptrix::cache::redis::strategies_key;
+    use perptrix::models::strategy::Strategy;
+
+    let worker = TestWorker::new().await;
+    let (Some(db), Some(cache)) = (
+        worker.job_context.database.clone(),
+        worker.job_context.cache.clone(),
+    ) else {
+        eprintln!("Skipping strategy cache test, QuestDB or Redis unavailable");
+        return;
+    };
+
+    let symbol = format!("CACHEMISS{}", chrono::Utc::now().timestamp_millis());
+    let id = db
+        .create_strategy(&Strategy {
+            symbol: symbol.clone(),
+            ..Strategy::default()
+        })
+        .await
+        .expect("Should create strategy");
+
+    let strategies = worker.job_context.get_strategies_cached(&symbol).await;
+    assert_eq!(strategies.iter().map(|s| s.id).collect::<Vec<_>>(), vec![Some(id)]);
+    let cached: Vec<Strategy> = cache
+        .get_json(&strategies_key(&symbol))
+        .await
+        .expect("Should read cache")
+        .expect("Strategies should be cached");
+    assert_eq!(cached.len(), 1);
+
+    // A refresh after the strategy is deleted caches the empty set
+    db.delete_strategy(id).await.expect("Should delete strategy");
+    worker
+        .job_context
+        .refresh_strategy_cache(std::slice::from_ref(&symbol))
+        .await
+        .expect("Should refresh strategy cache");
+    assert!(worker.job_context.get_strategies_cached(&symbol).await.is_empty());
+}
+
+/// Strategy whose single rule always passes, so every evaluation signals Long
+fn always_long_strategy(symbol: &str, enabled: bool) -> perptrix::models::strategy::Strategy {
+    use perptrix::models::strategy::{
+        AggregationConfig, AggregationMethod, Comparison, Condition, IndicatorType, Rule,
+        RuleType, SignalThresholds, Strategy, StrategyConfig,
+    };
+
+    Strategy {
+        id: Some(11),
+        symbol: symbol.to_string(),
+        enabled,
+        config: StrategyConfig {
+            rules: vec![Rule {
+                id: "atr_positive".to_string(),
+                rule_type: RuleType::Condition,
+                weight: Some(1.0),
+                operator: None,
+                condition: Some(Condition {
+                    indicator: IndicatorType::ATR,
+                    indicator_params: std::collections::HashMap::new(),
+                    comparison: Comparison::GreaterThan,
+                    threshold: Some(0.0),
+                    signal_state: None,
+                }),
+                children: None,
+            }],
+            aggregation: AggregationConfig {
+                method: AggregationMethod::Sum,
+                thresholds: SignalThresholds {
+                    long_min: 1,
+                    short_max: -2,
+                },
+                filter: None,
+            },
+            ..StrategyConfig::default()
+        },
+        ..Strategy::default()
+    }
+}
+
+/// Serves the same candles for the primary series and for one extra
+/// interval, counting the per-interval fetches
+struct IntervalCountingProvider {
+    candles: Vec<perptrix::models::indicators::Candle>,
+    interval_fetches: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+}
+
+#[async_trait::async_trait]
+impl perptrix::services::market_data::MarketDataProvider for IntervalCountingProvider {
+    async fn get_candles(
+        &self,
+        _symbol: &str,
+        _limit: usize,
+    ) -> Result<Vec<perptrix::models::indicators::Candle>, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(self.candles.clone())
+    }
+
+    async fn get_candles_by_interval(
+        &self,
+        _symbol: &str,
+        _limit: usize,
+    ) -> Result<
+        std::collections::HashMap<String, Vec<perptrix::models::indicators::Candle>>,
+        Box<dyn std::error::Error + Send + Sync>,
+    > {
+        self.interval_fetches
+            .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        Ok([("1h".to_string(), self.candles.clone())].into())
+    }
+
+    async fn get_latest_price(
+        &self,
+        _symbol: &str,
+    ) -> Result<f64, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(self.candles.last().map(|candle| candle.close).unwrap_or_default())
+    }
+
+    async fn subscribe(&self, _symbol: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn fetch_candles_job_only_fetches_intervals_for_multi_timeframe_strategies() {
+    use perptrix::cache::redis::strategies_key;
+    use perptrix::cache::RedisCache;
+    use perptrix::jobs::context::JobContext;
+    use perptrix::jobs::handlers::handle_fetch_candles;
+    use perptrix::models::indicators::Candle;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let worker = TestWorker::new().await;
+    let cache = Arc::new(RedisCache::new().await.expect("Should connect to Redis"));
+    let symbol = format!("INTERVALS{}", chrono::Utc::now().timestamp_millis());
+    let start = chrono::Utc::now() - chrono::Duration::minutes(250);
+    let candles: Vec<Candle> = (0..250)
+        .map(|i| {
+            let close = 100.0 + (i % 7) as f64;
+            let timestamp = start + chrono::Duration::minutes(i);
+            Candle::new(close, close + 1.0, close - 1.0, close, 1_000.0, timestamp)
+        })
+        .collect();
+    let interval_fetches = Arc::new(AtomicUsize::new(0));
+    let provider = IntervalCountingProvider {
+        candles,
+        interval_fetches: interval_fetches.clone(),
+    };
+    let ctx = Arc::new(JobContext::new(Arc::new(provider), None, None).with_cache(cache.clone()));
+
+    let mut weighted = always_long_strategy(&symbol, false);
+    weighted.config.timeframe_weights = Some([("1h".to_string(), 1.0)].into());
+    let fetch = || {
+        handle_fetch_candles(
+            FetchCandlesJob::new(symbol.clone()),
+            Data::new(ctx.clone()),
+            Data::new((*worker.eval_storage).clone()),
+            Attempt::new_with_value(0),
+        )
+    };
+
+    // Neither enabled strategy weights other intervals
+    cache
+        .set_json(
+            &strategies_key(&symbol),
+            &vec![always_long_strategy(&symbol, true), weighted.clone()],
+            60,
+        )
+        .await
+        .expect("Should cache strategies");
+    fetch().await.expect("Should fetch candles");
+    assert_eq!(interval_fetches.load(Ordering::SeqCst), 0);
+
+    weighted.enabled = true;
+    cache
+        .set_json(&strategies_key(&symbol), &vec![weighted], 60)
+        .await
+        .expect("Should cache strategies");
+    fetch().await.expect("Should fetch candles");
+    assert_eq!(interval_fetches.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn disabled_strategies_produce_no_signals_until_re_enabled() {
+    use apalis_redis::{Config, RedisStorage};
+    use perptrix::cache::redis::strategies_key;
+    use perptrix::cache::RedisCache;
+    use perptrix::jobs::context::JobContext;
+    use perptrix::jobs::handlers::handle_evaluate_signal;
+    use perptrix::services::market_data::MockMarketDataProvider;
+    use std::sync::Arc;
+
+    let cache = Arc::new(RedisCache::new().await.expect("Should connect to Redis"));
+    let symbol = format!("DISABLED{}", chrono::Utc::now().timestamp_millis());
+    // A queue of its own, so jobs pushed by other tests are not counted
+    let conn = apalis_redis::connect(test_utils::redis_url())
+        .await
+        .expect("Should connect to Redis");
+    let mut store_storage: RedisStorage<StoreSignalJob> = RedisStorage::new_with_config(
+        conn,
+        Config::default().set_namespace(&format!("store:{}", symbol)),
+    );
+    let ctx = Arc::new(
+        JobContext::new(Arc::new(MockMarketDataProvider::new()), None, None)
+            .with_cache(cache.clone()),
+    );
+
+    for (enabled, expected_signals) in [(false, 0), (true, 1)] {
+        cache
+            .set_json(&strategies_key(&symbol), &vec![always_long_strategy(&symbol, enabled)], 60)
+            .await
+            .expect("Should cache strategies");
+        handle_evaluate_signal(
+            EvaluateSignalJob::new(symbol.clone(), test_utils::create_test_candles(250)),
+            Data::new(ctx.clone()),
+            Data::new(store_storage.clone()),
+            Attempt::new_with_value(0),
+        )
+        .await
+        .expect("Should evaluate strategies");
+        assert_eq!(
+            store_storage.len().await.expect("Should read queue length"),
+            expected_signals,
+            "enabled = {}",
+            enabled
+        );
+    }
+}
+
+#[tokio::test]
+async fn scheduler_skips_symbols_whose_strategies_are_all_disabled() {
+    use apalis_redis::{Config, RedisStorage};
+    use perptrix::core::scheduler::JobScheduler;
+    use perptrix::metrics::Metrics;
+    use std::sync::Arc;
+
+    let worker = TestWorker::new().await;
+    let Some(db) = worker.job_context.database.clone() else {
+        eprintln!("Skipping disabled strategy scheduling test, QuestDB unavailable");
+        return;
+    };
+
+    let symbol = format!("SKIPPED{}", chrono::Utc::now().timestamp_millis());
+    let mut strategy = always_long_strategy(&symbol, false);
+    let id = db.create_strategy(&strategy).await.expect("Should create strategy");
+
+    let conn = apalis_redis::connect(test_utils::redis_url())
+        .await
+        .expect("Should connect to Redis");
+    let mut fetch_storage: RedisStorage<FetchCandlesJob> = RedisStorage::new_with_config(
+        conn,
+        Config::default().set_namespace(&format!("fetch:{}", symbol)),
+    );
+    let metrics = Arc::new(Metrics::new().expect("Should create metrics"));
+    let scheduler = JobScheduler::new(Arc::new(fetch_storage.clone()), vec![symbol.clone()], 1)
+        .expect("Should create scheduler")
+        .with_database(db.clone())
+        .with_metrics(metrics.clone());
+
+    scheduler.start().await.expect("Should start scheduler");
+    sleep(Duration::from_millis(2500)).await;
+    assert_eq!(fetch_storage.len().await.expect("Should read queue length"), 0);
+    assert!(metrics.strategies_disabled_total.get() >= 1.0);
+
+    // Re-enabled strategies are scheduled again from the next tick
+    strategy.enabled = true;
+    db.update_strategy(id, &strategy).await.expect("Should enable strategy");
+    sleep(Duration::from_millis(2500)).await;
+    scheduler.stop().await;
+    assert!(fetch_storage.len().await.expect("Should read queue length") > 0);
+    assert!(metrics.strategies_enabled_total.get() >= 1.0);
+
+    db.delete_strategy(id).await.expect("Should delete strategy");
+}
+
+#[tokio::test]
+async fn scheduler_picks_up_new_strategies_within_the_refresh_interval() {
+    use perptrix::cache::redis::ACTIVE_STRATEGIES_KEY;
+    use perptrix::core::scheduler::JobScheduler;
+    use perptrix::models::strategy::Strategy;
+
+    let worker = TestWorker::new().await;
+    let Some(db) = worker.job_context.database.clone() else {
+        eprintln!("Skipping strategy refresh test, QuestDB unavailable");
+        return;
+    };
+    let Some(cache) = worker.job_context.cache.clone() else {
+        eprintln!("Skipping strategy refresh test, Redis unavailable");
+        return;
+    };
+
+    let scheduler = JobScheduler::new(worker.fetch_storage.clone(), Vec::new(), 60)
+        .expect("Should create scheduler")
+        .with_database(db.clone())
+        .with_cache(cache.clone())
+        .with_strategy_refresh_interval(1);
+    scheduler.start().await.expect("Should start scheduler");
+
+    let symbol = format!("REFRESH{}", chrono::Utc::now().timestamp_millis());
+    let id = db
+        .create_strategy(&always_long_strategy(&symbol, true))
+        .await
+        .expect("Should create strategy");
+    sleep(Duration::from_millis(2500)).await;
+
+    assert!(
+        scheduler.list_symbols().await.contains(&symbol),
+        "New strategy's symbol should be scheduled without a restart"
+    );
+    let cached: Vec<Strategy> = cache
+        .get_json(ACTIVE_STRATEGIES_KEY)
+        .await
+        .expect("Should read active strategies")
+        .expect("Active strategies should be cached");
+    assert!(cached.iter().any(|strategy| strategy.symbol == symbol));
+
+    // Deleting the symbol's last strategy unschedules it again
+    db.delete_strategy(id).await.expect("Should delete strategy");
+    sleep(Duration::from_millis(2500)).await;
+    scheduler.stop().await;
+    assert!(!scheduler.list_symbols().await.contains(&symbol));
+}
+
+/// Fails the first `failures` candle fetches, then serves candles
+struct FlakyProvider {
+    candles: Vec<perptrix::models::indicators::Candle>,
+    failures: usize,
+    calls: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+}
+
+#[async_trait::async_trait]
+impl perptrix::services::market_data::MarketDataProvider for FlakyProvider {
+    async fn get_candles(
+        &self,
+        _symbol: &str,
+        _limit: usize,
+    ) -> Result<Vec<perptrix::models::indicators::Candle>, Box<dyn std::error::Error + Send + Sync>> {
+        let call = self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        if call < self.failures {
+            return Err(format!("forced failure {}", call + 1).into());
+        }
+        Ok(self.candles.clone())
+    }
+
+    async fn get_latest_price(
+        &self,
+        _symbol: &str,
+    ) -> Result<f64, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(self.candles.last().map(|candle| candle.close).unwrap_or_default())
+    }
+
+    async fn subscribe(&self, _symbol: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        Ok(())
+    }
+}
+
+/// Run one `FetchCandlesJob` whose first `failures` fetches fail, and whose
+/// later fetches return `candle_count` candles, through a fetch worker
+/// allowed `max_retries` retries, returning the metrics and the number of
+/// fetches made
+async fn run_flaky_fetch_job(
+    failures: usize,
+    candle_count: usize,
+    max_retries: u32,
+) -> (std::sync::Arc<perptrix::metrics::Metrics>, usize) {
+    use apalis_redis::{Config, RedisStorage};
+    use perptrix::core::runtime::{JobRetryPolicy, RetryPolicyConfig, RuntimeConfig, SignalRuntime};
+    use perptrix::jobs::context::JobContext;
+    use perptrix::metrics::Metrics;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let worker = TestWorker::new().await;
+    let conn = apalis_redis::connect(test_utils::redis_url())
+        .await
+        .expect("Should connect to Redis");
+    // Fresh queue so jobs left by other tests cannot interleave
+    let namespace = format!("retry-test-{}", chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default());
+    let fetch_storage = Arc::new(RedisStorage::new_with_config(
+        conn,
+        Config::default().set_namespace(&namespace),
+    ));
+    let mut storage = (*fetch_storage).clone();
+    storage
+        .push(FetchCandlesJob::new("BTC"))
+        .await
+        .expect("Should enqueue job");
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let provider = FlakyProvider {
+        candles: test_utils::create_test_candles(candle_count),
+        failures,
+        calls: calls.clone(),
+    };
+    let metrics = Arc::new(Metrics::new().expect("Should create metrics"));
+    let job_context = JobContext::new(Arc::new(provider), None, Some(metrics.clone()))
+        .with_retry_policies(RetryPolicyConfig {
+            fetch_candles: JobRetryPolicy::new(max_retries, 0),
+            ..RetryPolicyConfig::default()
+        });
+    let runtime = SignalRuntime::new(
+        RuntimeConfig::default(),
+        Arc::new(job_context),
+        fetch_storage,
+        worker.eval_storage.clone(),
+        worker.store_storage.clone(),
+    );
+    let handles = runtime.start_workers().await.expect("Should start workers");
+
+    sleep(Duration::from_secs(3)).await;
+    for handle in handles {
+        handle.abort();
+    }
+
+    (metrics, calls.load(Ordering::SeqCst))
+}
+
+fn fetch_retries(metrics: &perptrix::metrics::Metrics, attempt_number: usize) -> f64 {
+    metrics
+        .job_retries_total
+        .with_label_values(&["fetch_candles", &attempt_number.to_string()])
+        .get()
+}
+
+#[tokio::test]
+async fn failed_job_is_retried_until_it_succeeds() {
+    let (metrics, calls) = run_flaky_fetch_job(2, 250, 3).await;
+
+    assert_eq!(calls, 3, "Two failures and one success");
+    assert_eq!(fetch_retries(&metrics, 1), 1.0);
+    assert_eq!(fetch_retries(&metrics, 2), 1.0);
+    assert_eq!(fetch_retries(&metrics, 3), 0.0);
+    assert_eq!(
+        metrics
+            .job_max_retries_exceeded_total
+            .with_label_values(&["fetch_candles"])
+            .get(),
+        0.0
+    );
+}
+
+#[tokio::test]
+async fn failed_job_exceeding_max_retries_is_dead_lettered() {
+    let (metrics, calls) = run_flaky_fetch_job(usize::MAX, 250, 2).await;
+
+    assert_eq!(calls, 3, "The first attempt and two retries");
+    assert_eq!(fetch_retries(&metrics, 1), 1.0);
+    assert_eq!(fetch_retries(&metrics, 2), 1.0);
+    assert_eq!(fetch_retries(&metrics, 3), 0.0);
+    assert_eq!(
+        metrics
+            .job_max_retries_exceeded_total
+            .with_label_values(&["fetch_candles"])
+            .get(),
+        1.0
+    );
+}
+
+#[tokio::test]
+async fn failed_job_with_a_permanent_error_is_not_retried() {
+    // Too few candles is not something a retry can fix
+    let (metrics, calls) = run_flaky_fetch_job(0, 10, 3).await;
+
+    assert_eq!(calls, 1, "Only the first attempt");
+    assert_eq!(fetch_retries(&metrics, 1), 0.0);
+    assert_eq!(
+        metrics
+            .job_max_retries_exceeded_total
+            .with_label_values(&["fetch_candles"])
+            .get(),
+        0.0
+    );
+}
@@ -1,38 +1,92 @@
 //! Unit tests - organized by module structure
 
+#[path = "unit/backtesting/backtester.rs"]
+mod backtesting_backtester;
+
 #[path = "unit/common/math.rs"]
 mod common_math;
 
+#[path = "unit/config/watcher.rs"]
+mod config_watcher;
+
+#[path = "unit/db/migrations.rs"]
+mod db_migrations;
+
+#[path = "unit/db/pool.rs"]
+mod db_pool;
+
+#[path = "unit/error.rs"]
+mod error;
+
 #[path = "unit/indicators/parser.rs"]
 mod indicators_parser;
 
 #[path = "unit/indicators/registry.rs"]
 mod indicators_registry;
 
+#[path = "unit/indicators/test_utils.rs"]
+mod indicators_test_utils;
+
 #[path = "unit/indicators/validation.rs"]
 mod indicators_validation;
 
+#[path = "unit/indicators/momentum/cci.rs"]
+mod indicators_momentum_cci;
+
+#[path = "unit/indicators/momentum/cmf.rs"]
+mod indicators_momentum_cmf;
+
 #[path = "unit/indicators/momentum/macd.rs"]
 mod indicators_momentum_macd;
 
+#[path = "unit/indicators/momentum/mfi.rs"]
+mod indicators_momentum_mfi;
+
 #[path = "unit/indicators/momentum/rsi.rs"]
 mod indicators_momentum_rsi;
 
+#[path = "unit/indicators/momentum/stochastic_rsi.rs"]
+mod indicators_momentum_stochastic_rsi;
+
+#[path = "unit/indicators/momentum/williams_r.rs"]
+mod indicators_momentum_williams_r;
+
 #[path = "unit/indicators/trend/ema.rs"]
 mod indicators_trend_ema;
 
+#[path = "unit/indicators/trend/parabolic_sar.rs"]
+mod indicators_trend_parabolic_sar;
+
 #[path = "unit/indicators/trend/supertrend.rs"]
 mod indicators_trend_supertrend;
 
+#[path = "unit/indicators/trend/ichimoku.rs"]
+mod indicators_trend_ichimoku;
+
+#[path = "unit/indicators/trend/adx.rs"]
+mod indicators_trend_adx;
+
+#[path = "unit/indicators/trend/kama.rs"]
+mod indicators_trend_kama;
+
 #[path = "unit/indicators/volatility/bollinger.rs"]
 mod indicators_volatility_bollinger;
 
 #[path = "unit/indicators/volatility/atr.rs"]
 mod indicators_volatility_atr;
 
+#[path = "unit/indicators/volatility/atr_pct.rs"]
+mod indicators_volatility_atr_pct;
+
+#[path = "unit/indicators/volatility/keltner.rs"]
+mod indicators_volatility_keltner;
+
 #[path = "unit/indicators/volume/obv.rs"]
 mod indicators_volume_obv;
 
+#[path = "unit/indicators/volume/order_flow.rs"]
+mod indicators_volume_order_flow;
+
 #[path = "unit/indicators/volume/volume_profile.rs"]
 mod indicators_volume_volume_profile;
 
@@ -42,23 +96,122 @@ mod indicators_perp_open_interest;
 #[path = "unit/indicators/perp/funding_rate.rs"]
 mod indicators_perp_funding_rate;
 
+#[path = "unit/indicators/structure/candle_aggregator.rs"]
+mod indicators_structure_candle_aggregator;
+
+#[path = "unit/indicators/structure/candlestick.rs"]
+mod indicators_structure_candlestick;
+
+#[path = "unit/indicators/structure/donchian.rs"]
+mod indicators_structure_donchian;
+
+#[path = "unit/indicators/structure/fibonacci.rs"]
+mod indicators_structure_fibonacci;
+
+#[path = "unit/indicators/structure/gap_fill.rs"]
+mod indicators_structure_gap_fill;
+
+#[path = "unit/indicators/structure/heikin_ashi.rs"]
+mod indicators_structure_heikin_ashi;
+
+#[path = "unit/indicators/structure/vwap.rs"]
+mod indicators_structure_vwap;
+
+#[path = "unit/models/audit_log.rs"]
+mod models_audit_log;
+
+#[path = "unit/models/dead_letter.rs"]
+mod models_dead_letter;
+
+#[path = "unit/models/signal.rs"]
+mod models_signal;
+
+#[path = "unit/models/strategy.rs"]
+mod models_strategy;
+
+#[path = "unit/output/telegram.rs"]
+mod output_telegram;
+
+#[path = "unit/output/webhook.rs"]
+mod output_webhook;
+
+#[path = "unit/signals/categories.rs"]
+mod signals_categories;
+
+#[path = "unit/signals/clustering.rs"]
+mod signals_clustering;
+
 #[path = "unit/signals/decision.rs"]
 mod signals_decision;
 
 #[path = "unit/signals/engine.rs"]
 mod signals_engine;
 
+#[path = "unit/signals/risk.rs"]
+mod signals_risk;
+
 #[path = "unit/signals/scenarios.rs"]
 mod signals_scenarios;
 
+#[path = "unit/signals/scoring.rs"]
+mod signals_scoring;
+
 #[path = "unit/engine/aggregator.rs"]
 mod engine_aggregator;
 
+#[path = "unit/jobs/types.rs"]
+mod jobs_types;
+
+#[path = "unit/strategies/evaluator.rs"]
+mod strategies_evaluator;
+
+#[path = "unit/strategies/multi_timeframe.rs"]
+mod strategies_multi_timeframe;
+
+#[path = "unit/strategies/templates.rs"]
+mod strategies_templates;
+
+#[path = "unit/strategies/validation.rs"]
+mod strategies_validation;
+
+#[path = "unit/services/hyperliquid/client.rs"]
+mod services_hyperliquid_client;
+
+#[path = "unit/services/hyperliquid/messages.rs"]
+mod services_hyperliquid_messages;
+
+#[path = "unit/services/hyperliquid/provider.rs"]
+mod services_hyperliquid_provider;
+
 #[path = "unit/services/market_data.rs"]
 mod services_market_data;
 
+#[path = "unit/services/websocket/freshness.rs"]
+mod services_websocket_freshness;
+
+#[path = "unit/services/websocket/heartbeat.rs"]
+mod services_websocket_heartbeat;
+
+#[path = "unit/services/websocket/reconnect.rs"]
+mod services_websocket_reconnect;
+
+#[path = "unit/metrics.rs"]
+mod metrics;
+
+#[path = "unit/core/auth.rs"]
+mod core_auth;
+
 #[path = "unit/core/http.rs"]
 mod core_http;
 
+#[path = "unit/core/rate_limit.rs"]
+mod core_rate_limit;
+
 #[path = "unit/core/runtime.rs"]
 mod core_runtime;
+
+#[path = "unit/core/scheduler.rs"]
+mod core_scheduler;
+
+#[path = "unit/test_utils.rs"]
+mod test_utils;
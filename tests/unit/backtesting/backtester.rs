@@ -0,0 +1,149 @@
+//! Unit tests for strategy backtesting.
+
+use crate::test_utils::trending_candles;
+use perptrix::backtesting::{Backtester, ExitReason};
+use perptrix::models::signal::SignalDirection;
+use perptrix::models::strategy::{
+    AggregationConfig, AggregationMethod, Comparison, Condition, IndicatorType, PositionSizeModel,
+    Rule, RuleType, SignalThresholds, SlTpModel, Strategy, StrategyConfig, TimeWindow,
+    DEFAULT_MAX_KELLY_FRACTION,
+};
+use std::collections::HashMap;
+
+/// Strategy whose single rule always passes, so every window signals `direction`
+fn always_signal(direction: SignalDirection) -> Strategy {
+    let (long_min, short_max) = match direction {
+        SignalDirection::Long => (1, -2),
+        _ => (2, 1),
+    };
+    Strategy {
+        config: StrategyConfig {
+            rules: vec![Rule {
+                id: "atr_positive".to_string(),
+                rule_type: RuleType::Condition,
+                weight: Some(1.0),
+                operator: None,
+                condition: Some(Condition {
+                    indicator: IndicatorType::ATR,
+                    indicator_params: HashMap::new(),
+                    comparison: Comparison::GreaterThan,
+                    threshold: Some(0.0),
+                    signal_state: None,
+                }),
+                children: None,
+            }],
+            aggregation: AggregationConfig {
+                method: AggregationMethod::Sum,
+                thresholds: SignalThresholds {
+                    long_min,
+                    short_max,
+                },
+                filter: None,
+            },
+            category_weights: None,
+            timeframe_weights: None,
+            decay_model: None,
+            position_size_model: PositionSizeModel::default(),
+            max_kelly_fraction: DEFAULT_MAX_KELLY_FRACTION,
+            sl_tp_model: SlTpModel::default(),
+            time_filter: None,
+            use_heikin_ashi: false,
+        },
+        ..Strategy::default()
+    }
+}
+
+#[test]
+fn backtest_needs_a_full_window() {
+    let report = Backtester::new(always_signal(SignalDirection::Long))
+        .run(&trending_candles(49, 1_000.0, 1.0));
+    assert_eq!(report.total_signals, 0);
+    assert_eq!(report.total_trades, 0);
+    assert!(report.trades.is_empty());
+}
+
+#[test]
+fn long_trades_in_an_uptrend_take_profit() {
+    let candles = trending_candles(120, 1_000.0, 1.0);
+    let report = Backtester::new(always_signal(SignalDirection::Long)).run(&candles);
+
+    // One signal per window, but only one position is held at a time
+    assert_eq!(report.total_signals, candles.len() - 49);
+    assert!(report.total_trades > 1);
+    assert!(report.total_trades < report.total_signals);
+    assert_eq!(report.total_trades, report.trades.len());
+
+    let (last, closed) = report.trades.split_last().unwrap();
+    assert!(closed
+        .iter()
+        .all(|t| t.exit_reason == ExitReason::TakeProfit));
+    assert!(closed.iter().all(|t| t.pnl_pct > 0.0));
+    assert_eq!(last.exit_time, candles.last().unwrap().timestamp);
+    assert!(report.max_drawdown_pct.abs() < 1e-9);
+    assert!(report.average_pnl_pct > 0.0);
+
+    // Trades are chronological and never overlap
+    for pair in report.trades.windows(2) {
+        assert!(pair[0].exit_time <= pair[1].entry_time);
+    }
+}
+
+#[test]
+fn long_trades_in_a_downtrend_stop_out_and_draw_down() {
+    let report = Backtester::new(always_signal(SignalDirection::Long))
+        .run(&trending_candles(120, 1_000.0, -1.0));
+
+    assert!(report.total_trades > 1);
+    let stopped: Vec<_> = report
+        .trades
+        .iter()
+        .filter(|t| t.exit_reason == ExitReason::StopLoss)
+        .collect();
+    assert_eq!(stopped.len(), report.total_trades - 1);
+    assert!(stopped
+        .iter()
+        .all(|t| (t.exit_price - t.stop_loss_price).abs() < 1e-9 && t.pnl_pct < 0.0));
+    assert_eq!(report.win_rate, 0.0);
+
+    // Every trade loses, so the drawdown is the total loss
+    let total_loss: f64 = report.trades.iter().map(|t| t.pnl_pct).sum();
+    assert!((report.max_drawdown_pct + total_loss).abs() < 1e-9);
+}
+
+#[test]
+fn short_trades_in_a_downtrend_win() {
+    let report = Backtester::new(always_signal(SignalDirection::Short))
+        .run(&trending_candles(120, 1_000.0, -1.0));
+
+    assert!(report.total_trades > 1);
+    assert!(report
+        .trades
+        .iter()
+        .all(|t| t.signal.direction == SignalDirection::Short));
+    let (_, closed) = report.trades.split_last().unwrap();
+    assert!(closed
+        .iter()
+        .all(|t| t.exit_reason == ExitReason::TakeProfit && t.take_profit_price < t.entry_price));
+    assert!(report.win_rate > 0.5);
+}
+
+#[test]
+fn time_filters_follow_the_replayed_candles() {
+    // Candles run from 00:00 to 01:59 UTC; suppress everything before 01:00
+    let mut strategy = always_signal(SignalDirection::Long);
+    strategy.config.time_filter = Some(vec![TimeWindow {
+        start_utc_hour: 0,
+        end_utc_hour: 1,
+        weight_multiplier: 0.0,
+    }]);
+    let candles = trending_candles(120, 1_000.0, 1.0);
+
+    let report = Backtester::new(strategy).run(&candles);
+
+    // Only windows ending at 01:00 or later signal, whenever the test runs
+    assert_eq!(report.total_signals, 60);
+    assert!(report
+        .trades
+        .iter()
+        .all(|t| t.entry_time >= candles[60].timestamp));
+}
@@ -1,6 +1,8 @@
 //! Unit tests for common math utilities
 
 use perptrix::common::math::*;
+use perptrix::metrics::MATH_NAN_GUARD_TOTAL;
+use proptest::prelude::*;
 
 #[test]
 fn test_sma() {
@@ -35,5 +37,59 @@ fn test_true_range() {
     assert_eq!(tr2, 3.0);
 }
 
+#[test]
+fn true_range_guards_invalid_inputs() {
+    let before = MATH_NAN_GUARD_TOTAL
+        .with_label_values(&["true_range"])
+        .get();
+
+    assert_eq!(true_range(f64::NAN, 8.0, 9.0), 0.0);
+    assert_eq!(true_range(10.0, 8.0, f64::INFINITY), 0.0);
+    assert_eq!(true_range(8.0, 10.0, 9.0), 0.0);
+    assert_eq!(true_range(f64::MAX, -f64::MAX, 0.0), 0.0);
+
+    let after = MATH_NAN_GUARD_TOTAL
+        .with_label_values(&["true_range"])
+        .get();
+    assert!(after - before >= 4.0);
+}
 
+fn any_f64() -> impl Strategy<Value = f64> {
+    prop_oneof![
+        any::<f64>(),
+        Just(f64::NAN),
+        Just(f64::INFINITY),
+        Just(f64::NEG_INFINITY),
+        Just(f64::MIN_POSITIVE / 2.0),
+        Just(f64::MAX),
+        Just(0.0),
+    ]
+}
 
+proptest! {
+    #[test]
+    fn true_range_is_always_finite_and_non_negative(
+        high in any_f64(),
+        low in any_f64(),
+        prev_close in any_f64(),
+    ) {
+        let tr = true_range(high, low, prev_close);
+        prop_assert!(tr.is_finite());
+        prop_assert!(tr >= 0.0);
+    }
+}
+
+#[test]
+fn test_linear_regression() {
+    let fit = linear_regression(&[1.0, 3.0, 5.0, 7.0]).unwrap();
+    assert!((fit.slope - 2.0).abs() < 1e-12);
+    assert!((fit.intercept - 1.0).abs() < 1e-12);
+    assert!((fit.r_squared - 1.0).abs() < 1e-12);
+
+    let flat = linear_regression(&[4.0, 4.0, 4.0]).unwrap();
+    assert_eq!(flat.slope, 0.0);
+    assert_eq!(flat.r_squared, 0.0);
+
+    assert!(linear_regression(&[1.0]).is_none());
+    assert!(linear_regression(&[1.0, f64::NAN]).is_none());
+}
@@ -0,0 +1,82 @@
+//! Unit tests for strategy file parsing used by hot reload.
+
+use perptrix::config::watcher::{parse_strategy_file, ReloadStatus};
+use perptrix::models::strategy::AggregationMethod;
+use serde_json::json;
+use std::path::Path;
+
+fn strategy_json(extra: serde_json::Value) -> String {
+    let mut file = json!({
+        "symbol": "ETH",
+        "rules": [{
+            "id": "rsi_oversold",
+            "type": "Condition",
+            "weight": 1.0,
+            "condition": {
+                "indicator": "RSI",
+                "comparison": "SignalState",
+                "signal_state": "Oversold"
+            }
+        }],
+        "aggregation": {
+            "method": "Sum",
+            "thresholds": { "long_min": 1, "short_max": -1 }
+        }
+    });
+    for (key, value) in extra.as_object().unwrap() {
+        file[key] = value.clone();
+    }
+    file.to_string()
+}
+
+#[test]
+fn strategy_file_is_a_config_with_a_symbol() {
+    let path = Path::new("/strategies/eth_rsi.json");
+    let strategy = parse_strategy_file(path, &strategy_json(json!({}))).unwrap();
+    assert_eq!(strategy.name, "eth_rsi");
+    assert_eq!(strategy.symbol, "ETH");
+    assert_eq!(strategy.source_file.as_deref(), Some("eth_rsi.json"));
+    assert_eq!(strategy.config.rules.len(), 1);
+    assert!(matches!(
+        strategy.config.aggregation.method,
+        AggregationMethod::Sum
+    ));
+
+    let named =
+        parse_strategy_file(path, &strategy_json(json!({ "name": "ETH Dip Buyer" }))).unwrap();
+    assert_eq!(named.name, "ETH Dip Buyer");
+}
+
+#[test]
+fn invalid_strategy_files_are_rejected() {
+    let path = Path::new("broken.json");
+    let error = parse_strategy_file(path, "{ \"symbol\": ").unwrap_err();
+    assert!(error.to_string().starts_with("Invalid strategy file"));
+
+    let error = parse_strategy_file(path, &json!({ "symbol": "BTC" }).to_string()).unwrap_err();
+    assert!(error.to_string().contains("rules"));
+
+    let overbought = strategy_json(json!({
+        "rules": [{
+            "id": "rsi",
+            "type": "Condition",
+            "condition": {
+                "indicator": "RSI",
+                "indicator_params": { "overbought_threshold": 150.0 },
+                "comparison": "SignalState",
+                "signal_state": "Overbought"
+            }
+        }]
+    }));
+    let error = parse_strategy_file(path, &overbought).unwrap_err();
+    assert!(error.to_string().starts_with("Invalid strategy config"));
+}
+
+#[test]
+fn reload_status_defaults_to_disabled() {
+    let status = serde_json::to_value(ReloadStatus::default()).unwrap();
+    assert_eq!(
+        status,
+        json!({ "strategy_dir": null, "last_reload_at": null, "errors": [] })
+    );
+}
@@ -0,0 +1,69 @@
+//! Unit tests for API key authentication
+
+use axum::body::Body;
+use axum::extract::Request;
+use axum::response::{IntoResponse, Response};
+use perptrix::core::auth::{ApiKeyAuthLayer, ApiKeyIdentity};
+use perptrix::metrics::Metrics;
+use std::convert::Infallible;
+use tower::{service_fn, Layer, ServiceExt};
+
+/// Echo the identity the layer stored in the request extensions
+async fn echo_identity(request: Request) -> Result<Response, Infallible> {
+    let key_id = request
+        .extensions()
+        .get::<ApiKeyIdentity>()
+        .map(|identity| identity.key_id.clone())
+        .unwrap_or_default();
+    Ok(key_id.into_response())
+}
+
+async fn call(layer: &ApiKeyAuthLayer, path: &str, key: Option<&str>) -> (u16, String) {
+    let mut request = Request::builder().uri(path);
+    if let Some(key) = key {
+        request = request.header("X-API-Key", key);
+    }
+    let response = layer
+        .layer(service_fn(echo_identity))
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status().as_u16();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn authenticated_requests_carry_key_identity() {
+    let metrics = Metrics::default();
+    let layer = ApiKeyAuthLayer::new(
+        ["ops:secret-key", "abcdef123"],
+        metrics.auth_failures_total.clone(),
+    );
+
+    assert_eq!(
+        call(&layer, "/api/signals", Some("secret-key")).await,
+        (200, "ops".to_string())
+    );
+    assert_eq!(
+        call(&layer, "/api/signals", Some("abcdef123")).await,
+        (200, "abcd****".to_string())
+    );
+    assert_eq!(call(&layer, "/api/signals", Some("ops")).await.0, 401);
+    assert_eq!(metrics.auth_failures_total.get(), 1.0);
+}
+
+#[tokio::test]
+async fn empty_key_list_disables_auth() {
+    let metrics = Metrics::default();
+    let layer = ApiKeyAuthLayer::new(["", " "], metrics.auth_failures_total.clone());
+    assert!(!layer.is_enabled());
+
+    assert_eq!(
+        call(&layer, "/api/signals", None).await,
+        (200, String::new())
+    );
+    assert_eq!(metrics.auth_failures_total.get(), 0.0);
+}
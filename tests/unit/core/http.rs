@@ -1,23 +1,111 @@
 //! Unit tests for HTTP server
 
 use axum::extract::State;
-use perptrix::core::http::{health_check, AppState, HealthStatus};
+use axum::http::StatusCode;
+use perptrix::core::http::{
+    health_check, AppState, DetailedHealth, HealthStatus, ServiceHealth, ServiceStatus,
+};
 use perptrix::metrics::Metrics;
+use perptrix::services::hyperliquid::client::MockWebSocketClient;
 use std::sync::Arc;
-use std::time::Instant;
-use tokio::sync::RwLock;
+use std::time::Duration;
 
 #[tokio::test]
 async fn test_health_check() {
-    let state = AppState {
-        health: Arc::new(RwLock::new(HealthStatus::default())),
-        metrics: Arc::new(Metrics::default()),
-        start_time: Arc::new(Instant::now()),
-        database: None,
-    };
-    let result = health_check(State(state)).await;
-    assert!(result.is_ok());
+    let state = AppState::builder().build();
+    let (status, _) = health_check(State(state)).await;
+    assert_eq!(status, StatusCode::OK);
 }
 
+#[tokio::test]
+async fn health_reports_unavailable_database() {
+    let state = AppState::builder().build();
+    let (_, response) = health_check(State(state)).await;
+    assert_eq!(response.database_status, "unavailable");
+}
+
+#[tokio::test]
+async fn services_that_are_not_configured_are_disabled() {
+    let health = HealthStatus::check_all(None, None, None, None).await;
+    for service in [&health.database, &health.cache, &health.websocket] {
+        assert_eq!(*service, ServiceHealth::disabled());
+    }
+    assert_eq!(health.status_code(), StatusCode::OK);
+    assert_eq!(health.status(), "healthy");
+}
+
+#[tokio::test]
+async fn websocket_health_follows_the_connection() {
+    let client = MockWebSocketClient::new();
 
+    let health = HealthStatus::check_all(None, None, Some(&client), None).await;
+    assert_eq!(health.websocket.status, ServiceStatus::Down);
+    assert!(health.websocket.latency_ms.is_some());
+    assert!(health.websocket.last_error.is_some());
 
+    client.set_connected(true).await;
+    let health = HealthStatus::check_all(None, None, Some(&client), None).await;
+    assert_eq!(health.websocket.status, ServiceStatus::Up);
+    assert_eq!(health.websocket.last_error, None);
+}
+
+fn detailed_health(
+    database: ServiceHealth,
+    cache: ServiceHealth,
+    websocket: ServiceHealth,
+) -> DetailedHealth {
+    DetailedHealth {
+        database,
+        cache,
+        websocket,
+    }
+}
+
+#[test]
+fn health_status_code_reflects_the_worst_service() {
+    let up = || ServiceHealth::up(Duration::from_millis(3));
+    let down = || ServiceHealth::down(Duration::from_millis(3), "unreachable");
+    let degraded = || ServiceHealth::degraded(Duration::from_millis(3), "slow");
+
+    let cases = [
+        (detailed_health(up(), up(), up()), StatusCode::OK, "healthy"),
+        (
+            detailed_health(up(), up(), ServiceHealth::disabled()),
+            StatusCode::OK,
+            "healthy",
+        ),
+        (
+            detailed_health(degraded(), up(), up()),
+            StatusCode::MULTI_STATUS,
+            "degraded",
+        ),
+        (
+            detailed_health(up(), down(), up()),
+            StatusCode::MULTI_STATUS,
+            "degraded",
+        ),
+        (
+            detailed_health(up(), up(), down()),
+            StatusCode::MULTI_STATUS,
+            "degraded",
+        ),
+        (
+            detailed_health(down(), up(), up()),
+            StatusCode::SERVICE_UNAVAILABLE,
+            "unhealthy",
+        ),
+    ];
+    for (health, code, status) in cases {
+        assert_eq!(health.status_code(), code, "{:?}", health);
+        assert_eq!(health.status(), status, "{:?}", health);
+    }
+}
+
+#[test]
+fn builder_keeps_provided_metrics() {
+    let metrics = Arc::new(Metrics::default());
+    let state = AppState::builder().with_metrics(metrics.clone()).build();
+    assert!(Arc::ptr_eq(&state.metrics, &metrics));
+    assert!(state.database.is_none());
+    assert!(state.cache.is_none());
+}
@@ -0,0 +1,47 @@
+//! Unit tests for the API rate limiter's token bucket
+
+use perptrix::core::rate_limit::{retry_after_secs, TokenBucket};
+use std::time::{Duration, Instant};
+
+#[test]
+fn bucket_allows_a_burst_then_refills_at_the_rate() {
+    let start = Instant::now();
+    let mut bucket = TokenBucket::new(3.0, start);
+
+    for _ in 0..3 {
+        assert!(bucket.try_acquire(3.0, 2.0, start).is_ok());
+    }
+    let wait = bucket.try_acquire(3.0, 2.0, start).unwrap_err();
+    assert_eq!(wait, Duration::from_millis(500));
+
+    // Half a token is not enough
+    let quarter = start + Duration::from_millis(250);
+    let wait = bucket.try_acquire(3.0, 2.0, quarter).unwrap_err();
+    assert_eq!(wait, Duration::from_millis(250));
+
+    let later = start + Duration::from_millis(500);
+    assert!(bucket.try_acquire(3.0, 2.0, later).is_ok());
+    assert!(bucket.try_acquire(3.0, 2.0, later).is_err());
+}
+
+#[test]
+fn bucket_never_holds_more_than_its_capacity() {
+    let start = Instant::now();
+    let mut bucket = TokenBucket::new(2.0, start);
+    assert!(bucket.try_acquire(2.0, 1.0, start).is_ok());
+
+    let idle = start + Duration::from_secs(3600);
+    assert!(bucket.is_full(2.0, 1.0, idle));
+    assert!(bucket.try_acquire(2.0, 1.0, idle).is_ok());
+    assert!(bucket.try_acquire(2.0, 1.0, idle).is_ok());
+    assert!(bucket.try_acquire(2.0, 1.0, idle).is_err());
+    assert!(!bucket.is_full(2.0, 1.0, idle));
+}
+
+#[test]
+fn retry_after_rounds_up_to_whole_seconds() {
+    assert_eq!(retry_after_secs(Duration::from_millis(1)), 1);
+    assert_eq!(retry_after_secs(Duration::from_millis(500)), 1);
+    assert_eq!(retry_after_secs(Duration::from_millis(1_001)), 2);
+    assert_eq!(retry_after_secs(Duration::ZERO), 1);
+}
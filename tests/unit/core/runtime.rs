@@ -1,6 +1,8 @@
 //! Unit tests for signal runtime
 
-use perptrix::core::runtime::RuntimeConfig;
+use perptrix::core::runtime::{JobRetryPolicy, RetryPolicyConfig, RuntimeConfig};
+use perptrix::jobs::types::JobType;
+use std::time::Duration;
 
 #[test]
 fn test_runtime_config_default() {
@@ -15,3 +17,33 @@ fn test_runtime_config_default() {
 
 
 
+
+#[test]
+fn test_retry_policy_retries_until_max_retries() {
+    let policy = JobRetryPolicy::new(2, 5);
+    // Attempts are counted from 1, like Apalis does
+    assert!(policy.should_retry(1));
+    assert!(policy.should_retry(2));
+    assert!(!policy.should_retry(3));
+    assert_eq!(policy.backoff(), Duration::from_secs(5));
+}
+
+#[test]
+fn test_retry_policy_config_default_never_retries() {
+    let config = RetryPolicyConfig::default();
+    for job_type in [JobType::FetchCandles, JobType::EvaluateSignal, JobType::StoreSignal] {
+        assert!(!config.for_job(job_type).should_retry(1));
+    }
+}
+
+#[test]
+fn test_retry_policy_config_selects_policy_by_job_type() {
+    let config = RetryPolicyConfig {
+        fetch_candles: JobRetryPolicy::new(1, 0),
+        evaluate_signal: JobRetryPolicy::new(2, 0),
+        store_signal: JobRetryPolicy::new(3, 0),
+    };
+    assert_eq!(config.for_job(JobType::FetchCandles).max_retries, 1);
+    assert_eq!(config.for_job(JobType::EvaluateSignal).max_retries, 2);
+    assert_eq!(config.for_job(JobType::StoreSignal).max_retries, 3);
+}
@@ -0,0 +1,110 @@
+//! Unit tests for scheduler job deduplication, priorities, disabled
+//! strategies and strategy change detection.
+
+use perptrix::cache::redis::fetch_lock_key;
+use perptrix::core::scheduler::{
+    fetch_lock_ttl, job_priority, strategies_hash, StrategyAvailability,
+};
+use perptrix::jobs::types::JobPriority;
+use perptrix::models::signal::{SignalDirection, SignalOutput};
+use perptrix::models::strategy::Strategy;
+use std::collections::HashSet;
+
+fn signal(direction: SignalDirection, confidence: f64) -> SignalOutput {
+    SignalOutput::new(
+        direction,
+        confidence,
+        1.0,
+        2.0,
+        vec![],
+        "BTC".to_string(),
+        42_000.0,
+    )
+}
+
+#[test]
+fn fetch_lock_expires_just_before_the_next_tick() {
+    assert_eq!(fetch_lock_ttl(60), 59);
+    assert_eq!(fetch_lock_ttl(300), 299);
+    // Never zero, which Redis rejects
+    assert_eq!(fetch_lock_ttl(1), 1);
+    assert_eq!(fetch_lock_ttl(0), 1);
+}
+
+#[test]
+fn fetch_lock_key_is_per_symbol() {
+    assert_eq!(fetch_lock_key("BTC"), "lock:fetch:BTC");
+}
+
+#[test]
+fn confident_directional_signals_are_high_priority() {
+    for direction in [SignalDirection::Long, SignalDirection::Short] {
+        assert_eq!(
+            job_priority(Some(&signal(direction, 0.7)), JobPriority::Normal),
+            JobPriority::High
+        );
+        // Overrides the Low priority of catch-up jobs too
+        assert_eq!(
+            job_priority(Some(&signal(direction, 0.95)), JobPriority::Low),
+            JobPriority::High
+        );
+    }
+}
+
+#[test]
+fn other_signals_keep_the_base_priority() {
+    let weak = signal(SignalDirection::Long, 0.69);
+    let neutral = signal(SignalDirection::Neutral, 0.9);
+    for base in [JobPriority::Low, JobPriority::Normal] {
+        assert_eq!(job_priority(Some(&weak), base), base);
+        assert_eq!(job_priority(Some(&neutral), base), base);
+        assert_eq!(job_priority(None, base), base);
+    }
+}
+
+fn strategy(symbol: &str, enabled: bool) -> Strategy {
+    Strategy {
+        symbol: symbol.to_string(),
+        enabled,
+        ..Strategy::default()
+    }
+}
+
+#[test]
+fn symbols_are_disabled_only_when_every_strategy_is() {
+    let availability = StrategyAvailability::from_strategies(&[
+        strategy("BTC", true),
+        strategy("BTC", false),
+        strategy("ETH", false),
+        strategy("ETH", false),
+        strategy("SOL", true),
+    ]);
+
+    assert_eq!(availability.enabled, 2);
+    assert_eq!(availability.disabled, 3);
+    assert_eq!(
+        availability.disabled_symbols,
+        HashSet::from(["ETH".to_string()])
+    );
+
+    // Symbols without strategies are not disabled
+    assert_eq!(
+        StrategyAvailability::from_strategies(&[]),
+        StrategyAvailability::default()
+    );
+}
+
+#[test]
+fn strategies_hash_changes_only_with_strategy_content() {
+    let strategies = vec![strategy("BTC", true), strategy("ETH", true)];
+    assert_eq!(strategies_hash(&strategies), strategies_hash(&strategies.clone()));
+
+    let mut disabled = strategies.clone();
+    disabled[1].enabled = false;
+    assert_ne!(strategies_hash(&strategies), strategies_hash(&disabled));
+
+    let mut added = strategies.clone();
+    added.push(strategy("SOL", true));
+    assert_ne!(strategies_hash(&strategies), strategies_hash(&added));
+    assert_ne!(strategies_hash(&strategies), strategies_hash(&[]));
+}
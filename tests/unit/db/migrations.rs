@@ -0,0 +1,51 @@
+//! Unit tests for the QuestDB migration list and pending-migration selection.
+
+use perptrix::db::migrations::{migrations, Migration, MigrationRunner};
+use std::collections::HashSet;
+
+#[test]
+fn versions_are_consecutive_from_one() {
+    let versions: Vec<i32> = migrations().iter().map(|m| m.version).collect();
+    let expected: Vec<i32> = (1..=versions.len() as i32).collect();
+    assert_eq!(versions, expected);
+}
+
+#[test]
+fn every_migration_can_run_twice() {
+    for migration in migrations() {
+        assert!(
+            migration.sql.contains("IF NOT EXISTS"),
+            "migration {} is not idempotent",
+            migration.version
+        );
+        assert!(!migration.description.is_empty());
+    }
+}
+
+#[test]
+fn pending_skips_applied_versions_in_order() {
+    let all = [
+        Migration {
+            version: 3,
+            description: "third",
+            sql: "",
+        },
+        Migration {
+            version: 1,
+            description: "first",
+            sql: "",
+        },
+        Migration {
+            version: 2,
+            description: "second",
+            sql: "",
+        },
+    ];
+
+    let pending = MigrationRunner::pending(&all, &HashSet::from([2]));
+    let versions: Vec<i32> = pending.iter().map(|m| m.version).collect();
+    assert_eq!(versions, vec![1, 3]);
+
+    // Once everything is recorded, running again applies nothing
+    assert!(MigrationRunner::pending(&all, &HashSet::from([1, 2, 3])).is_empty());
+}
@@ -0,0 +1,38 @@
+//! Unit tests for QuestDB connection pool retries.
+
+use perptrix::db::pool::QuestDbPool;
+use std::time::Instant;
+use tokio::time::Duration;
+
+#[tokio::test]
+async fn connect_retries_until_timeout() {
+    // Nothing listens on port 1, so every attempt is refused
+    let start = Instant::now();
+    let result = QuestDbPool::connect(
+        "host=127.0.0.1 port=1 user=admin connect_timeout=1",
+        2,
+        Duration::from_millis(600),
+    )
+    .await;
+    let elapsed = start.elapsed();
+
+    let error = result.err().expect("refused connections should fail");
+    assert!(error.to_string().contains("attempts"), "{}", error);
+    assert!(error.is_transient());
+    // Backed off at least once (250ms, then 350ms up to the deadline)
+    assert!(elapsed >= Duration::from_millis(600), "{:?}", elapsed);
+    assert!(elapsed < Duration::from_secs(5), "{:?}", elapsed);
+}
+
+#[tokio::test]
+async fn connect_with_zero_timeout_tries_once() {
+    let result = QuestDbPool::connect(
+        "host=127.0.0.1 port=1 user=admin connect_timeout=1",
+        2,
+        Duration::ZERO,
+    )
+    .await;
+
+    let error = result.err().expect("refused connections should fail");
+    assert!(error.to_string().contains("after 1 attempts"), "{}", error);
+}
@@ -19,6 +19,7 @@ fn bullish_signals() -> IndicatorSignals {
         obv_signal: obv::OBVSignal::Confirmation,
         volume_profile_signal: volume_profile::VolumeProfileSignal::POCSupport,
         oi_signal: open_interest::OpenInterestSignal::BullishExpansion,
+        oi_score: None,
         funding_signal: funding_rate::FundingSignal::NeutralNegative,
     }
 }
@@ -69,3 +70,23 @@ fn funding_extremes_support_contrarian_longs() {
     assert!(result.score_breakdown.perp_score >= 2);
     assert!(result.confidence > 0.5);
 }
+
+#[test]
+fn rising_open_interest_with_trend_boosts_confidence() {
+    let aggregator = SignalAggregator::new();
+    let baseline = aggregator.aggregate(bullish_signals());
+
+    let mut signals = bullish_signals();
+    signals.oi_score = Some(0.8);
+    let confirmed = aggregator.aggregate(signals);
+    assert!(confirmed.confidence >= baseline.confidence);
+    assert!(confirmed
+        .reasons
+        .iter()
+        .any(|r| r.contains("confirms trend")));
+
+    let mut signals = bullish_signals();
+    signals.oi_score = Some(-0.8);
+    let weakened = aggregator.aggregate(signals);
+    assert!(weakened.confidence < baseline.confidence);
+}
@@ -0,0 +1,114 @@
+//! Unit tests for KryptexError conversions and transience classification.
+
+use perptrix::error::KryptexError;
+use std::error::Error;
+use std::io;
+
+#[tokio::test]
+async fn database_errors_convert_and_classify_by_cause() {
+    // Nothing listens on port 1, so the connection is refused
+    let refused = tokio_postgres::connect(
+        "host=127.0.0.1 port=1 user=admin connect_timeout=1",
+        tokio_postgres::NoTls,
+    )
+    .await
+    .err()
+    .expect("refused connection should fail");
+    let error = KryptexError::from(refused);
+    assert!(matches!(error, KryptexError::Database(_)));
+    assert!(error.source().is_some());
+    assert!(error.is_transient(), "{}", error);
+
+    let invalid = "port=not-a-number"
+        .parse::<tokio_postgres::Config>()
+        .expect_err("invalid port should not parse");
+    let error = KryptexError::from(invalid);
+    assert!(matches!(error, KryptexError::Database(_)));
+    assert!(!error.is_transient(), "{}", error);
+}
+
+#[test]
+fn cache_errors_convert_and_classify_by_kind() {
+    let refused = redis::RedisError::from(io::Error::from(io::ErrorKind::ConnectionRefused));
+    let error = KryptexError::from(refused);
+    assert!(matches!(error, KryptexError::Cache(_)));
+    assert!(error.to_string().starts_with("Cache error"));
+    assert!(error.is_transient());
+
+    let loading = KryptexError::from(redis::RedisError::from((
+        redis::ErrorKind::BusyLoadingError,
+        "loading dataset",
+    )));
+    assert!(loading.is_transient());
+
+    let wrong_type = KryptexError::from(redis::RedisError::from((
+        redis::ErrorKind::TypeError,
+        "not a string",
+    )));
+    assert!(!wrong_type.is_transient());
+}
+
+#[test]
+fn serialization_errors_convert_and_are_permanent() {
+    let invalid = serde_json::from_str::<f64>("not json").unwrap_err();
+    let error: KryptexError = invalid.into();
+    assert!(matches!(error, KryptexError::Serialization(_)));
+    assert!(error.source().is_some());
+    assert!(!error.is_transient());
+}
+
+#[test]
+fn io_errors_convert_and_classify_by_kind() {
+    for kind in [
+        io::ErrorKind::ConnectionRefused,
+        io::ErrorKind::ConnectionReset,
+        io::ErrorKind::NotConnected,
+        io::ErrorKind::TimedOut,
+        io::ErrorKind::BrokenPipe,
+    ] {
+        let error = KryptexError::from(io::Error::from(kind));
+        assert!(matches!(error, KryptexError::Io(_)));
+        assert!(error.is_transient(), "{:?}", kind);
+    }
+
+    for kind in [
+        io::ErrorKind::PermissionDenied,
+        io::ErrorKind::InvalidData,
+        io::ErrorKind::Other,
+    ] {
+        assert!(
+            !KryptexError::from(io::Error::from(kind)).is_transient(),
+            "{:?}",
+            kind
+        );
+    }
+}
+
+#[test]
+fn websocket_errors_convert_and_are_transient() {
+    let closed = tokio_tungstenite::tungstenite::Error::ConnectionClosed;
+    let error = KryptexError::from(closed);
+    assert!(matches!(error, KryptexError::WebSocket(_)));
+    assert!(error.to_string().starts_with("WebSocket error"));
+    assert!(error.is_transient());
+}
+
+#[test]
+fn market_data_errors_are_transient() {
+    let error = KryptexError::MarketData("no candles cached for BTC".to_string());
+    assert_eq!(error.to_string(), "Market data error: no candles cached for BTC");
+    assert!(error.source().is_none());
+    assert!(error.is_transient());
+}
+
+#[test]
+fn invalid_input_and_missing_records_are_permanent() {
+    let invalid = KryptexError::InvalidInput("Not enough candles: 10 < 50".to_string());
+    assert_eq!(invalid.to_string(), "Not enough candles: 10 < 50");
+    assert!(invalid.source().is_none());
+    assert!(!invalid.is_transient());
+
+    let missing = KryptexError::NotFound("Strategy with id 7 not found".to_string());
+    assert_eq!(missing.to_string(), "Strategy with id 7 not found");
+    assert!(!missing.is_transient());
+}
@@ -0,0 +1,77 @@
+//! Unit tests for the Commodity Channel Index indicator.
+
+use crate::indicators_test_utils::candle;
+use perptrix::indicators::momentum::cci::{CCISignal, CCI, CCI_OVERBOUGHT, CCI_OVERSOLD};
+use proptest::prelude::*;
+
+#[test]
+fn cci_matches_reference_calculation() {
+    let mut cci = CCI::new(3);
+    assert!(cci.update(11.0, 9.0, 10.0).is_none());
+    assert!(cci.update(12.0, 10.0, 11.0).is_none());
+
+    // Typical prices 10, 11, 12: mean 11, mean deviation 2/3
+    let (value, signal) = cci.update(13.0, 11.0, 12.0).unwrap();
+    let expected = (12.0 - 11.0) / (0.015 * (2.0 / 3.0));
+    assert!((value - expected).abs() < 1e-9);
+    assert_eq!(signal, CCISignal::Overbought);
+}
+
+#[test]
+fn cci_flat_window_reads_zero() {
+    let mut cci = CCI::new(5);
+    let mut last = None;
+    for _ in 0..5 {
+        last = cci.update(101.0, 99.0, 100.0);
+    }
+    let (value, signal) = last.unwrap();
+    assert_eq!(value, 0.0);
+    assert!(value.is_finite());
+    assert_eq!(signal, CCISignal::Neutral);
+}
+
+#[test]
+fn cci_flags_zero_crosses() {
+    assert_eq!(
+        CCI::get_signal(20.0, Some(-5.0)),
+        CCISignal::BullishZeroCross
+    );
+    assert_eq!(
+        CCI::get_signal(-20.0, Some(5.0)),
+        CCISignal::BearishZeroCross
+    );
+    assert_eq!(CCI::get_signal(20.0, Some(5.0)), CCISignal::Neutral);
+    assert_eq!(CCI::get_signal(20.0, None), CCISignal::Neutral);
+    // Extremes win over a cross
+    assert_eq!(CCI::get_signal(150.0, Some(-5.0)), CCISignal::Overbought);
+    assert_eq!(CCI::get_signal(-150.0, Some(5.0)), CCISignal::Oversold);
+    // Thresholds are exclusive
+    assert_eq!(CCI::get_signal(100.0, Some(100.0)), CCISignal::Neutral);
+    assert_eq!(CCI::get_signal(-100.0, Some(-100.0)), CCISignal::Neutral);
+}
+
+#[test]
+fn cci_reset_clears_history() {
+    let mut cci = CCI::new(2);
+    cci.update(11.0, 9.0, 10.0);
+    cci.update(13.0, 11.0, 12.0);
+    cci.reset();
+    assert!(cci.update(11.0, 9.0, 10.0).is_none());
+}
+
+proptest! {
+    #[test]
+    fn cci_signal_matches_thresholds(
+        candles in prop::collection::vec(candle(), 1..80),
+        period in 1usize..30,
+    ) {
+        let mut cci = CCI::new(period);
+        for (high, low, close) in candles {
+            if let Some((value, signal)) = cci.update(high, low, close) {
+                prop_assert!(value.is_finite());
+                prop_assert_eq!(signal == CCISignal::Overbought, value > CCI_OVERBOUGHT);
+                prop_assert_eq!(signal == CCISignal::Oversold, value < CCI_OVERSOLD);
+            }
+        }
+    }
+}
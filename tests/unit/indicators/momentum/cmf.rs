@@ -0,0 +1,91 @@
+//! Unit tests for the Chaikin Money Flow indicator.
+
+use perptrix::indicators::momentum::cmf::{
+    CMFSignal, CMF, CMF_BEARISH_THRESHOLD, CMF_BULLISH_THRESHOLD,
+};
+
+/// Monotone series where each candle closes in the upper (or lower) part of
+/// its range, as a steady trend would
+fn trending(count: usize, step: f64) -> Vec<(f64, f64, f64, f64)> {
+    (0..count)
+        .map(|i| {
+            let mid = 100.0 + i as f64 * step;
+            (
+                mid + 1.0,
+                mid - 1.0,
+                mid + 0.6 * step.signum(),
+                1_000.0 + i as f64,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn cmf_matches_reference_calculation() {
+    let mut cmf = CMF::new(3);
+    // MFV: +100 (close on high), -200 (close on low), 0 (mid-range)
+    assert!(cmf.update(12.0, 10.0, 12.0, 100.0).is_none());
+    assert!(cmf.update(12.0, 10.0, 10.0, 200.0).is_none());
+    let (value, signal) = cmf.update(12.0, 10.0, 11.0, 300.0).unwrap();
+    assert!((value - (100.0 - 200.0) / 600.0).abs() < 1e-12);
+    assert_eq!(signal, CMFSignal::Bearish);
+
+    // The first candle drops out; 3/4 of the way up the range is MFV +50
+    let (value, _) = cmf.update(14.0, 10.0, 13.0, 100.0).unwrap();
+    assert!((value - (-200.0 + 50.0) / 600.0).abs() < 1e-12);
+}
+
+#[test]
+fn cmf_sign_follows_monotone_trends() {
+    for (step, expected) in [(0.5, CMFSignal::Bullish), (-0.5, CMFSignal::Bearish)] {
+        let mut cmf = CMF::default();
+        let readings: Vec<_> = trending(60, step)
+            .into_iter()
+            .filter_map(|(high, low, close, volume)| cmf.update(high, low, close, volume))
+            .collect();
+
+        assert_eq!(readings.len(), 41);
+        for (value, signal) in readings {
+            assert!((-1.0..=1.0).contains(&value));
+            assert_eq!(value.signum(), step.signum());
+            assert!((value.abs() - 0.6).abs() < 1e-9);
+            assert_eq!(signal, expected);
+        }
+    }
+}
+
+#[test]
+fn cmf_handles_flat_candles() {
+    let mut cmf = CMF::new(2);
+    assert!(cmf.update(100.0, 100.0, 100.0, 500.0).is_none());
+    let (value, signal) = cmf.update(100.0, 100.0, 100.0, 500.0).unwrap();
+    assert_eq!(value, 0.0);
+    assert_eq!(signal, CMFSignal::Neutral);
+
+    // A flat candle adds volume without flow, diluting the reading
+    let (value, _) = cmf.update(101.0, 99.0, 101.0, 500.0).unwrap();
+    assert!((value - 0.5).abs() < 1e-12);
+
+    // No volume at all reads neutral instead of NaN
+    let mut cmf = CMF::new(2);
+    cmf.update(101.0, 99.0, 101.0, 0.0);
+    let (value, _) = cmf.update(101.0, 99.0, 99.0, 0.0).unwrap();
+    assert_eq!(value, 0.0);
+
+    cmf.reset();
+    assert!(cmf.update(101.0, 99.0, 100.0, 10.0).is_none());
+}
+
+#[test]
+fn cmf_signal_thresholds_are_exclusive() {
+    assert_eq!(CMF::get_signal(CMF_BULLISH_THRESHOLD), CMFSignal::Neutral);
+    assert_eq!(
+        CMF::get_signal(CMF_BULLISH_THRESHOLD + 1e-6),
+        CMFSignal::Bullish
+    );
+    assert_eq!(CMF::get_signal(CMF_BEARISH_THRESHOLD), CMFSignal::Neutral);
+    assert_eq!(
+        CMF::get_signal(CMF_BEARISH_THRESHOLD - 1e-6),
+        CMFSignal::Bearish
+    );
+}
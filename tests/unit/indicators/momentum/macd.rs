@@ -1,7 +1,8 @@
 //! Unit tests for the MACD momentum indicator.
 
 use chrono::Utc;
-use perptrix::indicators::momentum::calculate_macd_default;
+use perptrix::common::math::ema_from_previous;
+use perptrix::indicators::momentum::{calculate_macd, calculate_macd_default};
 use perptrix::indicators::momentum::macd::{MACDSignal, MACD};
 use perptrix::models::indicators::Candle;
 
@@ -39,3 +40,68 @@ fn legacy_macd_calculation_available() {
     let indicator = calculate_macd_default(&candles).expect("MACD result");
     assert!(indicator.histogram.is_finite());
 }
+
+#[test]
+fn streaming_macd_matches_batch_calculation() {
+    let closes: Vec<f64> = (0..80)
+        .map(|i| 100.0 + (i as f64 * 0.3).sin() * 5.0 + i as f64 * 0.1)
+        .collect();
+    let candles: Vec<_> = closes.iter().map(|&c| candle(c)).collect();
+
+    let mut streaming = MACD::new(12, 26, 9);
+    let mut last = None;
+    for &close in &closes {
+        last = Some(streaming.update(close));
+    }
+    let (macd_val, signal, histogram, _) = last.expect("streaming output");
+
+    let batch = calculate_macd(&candles, 12, 26, 9).expect("batch output");
+    assert!((batch.macd - macd_val).abs() < 1e-9);
+    assert!((batch.signal - signal).abs() < 1e-9);
+    assert!((batch.histogram - histogram).abs() < 1e-9);
+
+    // Reference: EMAs seeded with the first close, signal seeded with SMA of the first 9 MACD values
+    let mut fast = closes[0];
+    let mut slow = closes[0];
+    let mut macd_series = Vec::new();
+    for &close in &closes {
+        fast = ema_from_previous(close, fast, 12);
+        slow = ema_from_previous(close, slow, 26);
+        macd_series.push(fast - slow);
+    }
+    let mut expected_signal = macd_series[..9].iter().sum::<f64>() / 9.0;
+    for &value in &macd_series[9..] {
+        expected_signal = ema_from_previous(value, expected_signal, 9);
+    }
+    assert!((expected_signal - signal).abs() < 1e-9);
+}
+
+#[test]
+fn macd_signal_line_seeds_after_signal_period() {
+    let mut macd = MACD::new(3, 6, 4);
+    for (i, price) in [100.0, 101.0, 103.0, 102.0].into_iter().enumerate() {
+        assert!(!macd.is_ready(), "not ready before update {}", i + 1);
+        macd.update(price);
+    }
+    assert!(macd.is_ready());
+    assert_eq!(macd.macd_history().len(), 4);
+
+    macd.update(104.0);
+    assert_eq!(macd.macd_history().len(), 4);
+}
+
+#[test]
+fn macd_reset_matches_fresh_instance() {
+    let mut reused = MACD::new(3, 6, 3);
+    for price in [100.0, 98.0, 97.0, 99.0, 101.0] {
+        reused.update(price);
+    }
+    reused.reset();
+    assert!(!reused.is_ready());
+    assert!(reused.macd_history().is_empty());
+
+    let mut fresh = MACD::new(3, 6, 3);
+    for price in [50.0, 51.0, 53.0, 52.0, 54.0, 55.0] {
+        assert_eq!(reused.update(price), fresh.update(price));
+    }
+}
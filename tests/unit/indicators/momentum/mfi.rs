@@ -0,0 +1,112 @@
+//! Unit tests for the Money Flow Index indicator.
+
+use perptrix::indicators::momentum::mfi::{MFISignal, MFI, MFI_OVERBOUGHT, MFI_OVERSOLD};
+use perptrix::indicators::momentum::rsi::RSI;
+
+/// Candle whose typical price equals its close
+fn candle(close: f64, volume: f64) -> (f64, f64, f64, f64) {
+    (close + 1.0, close - 1.0, close, volume)
+}
+
+#[test]
+fn mfi_matches_reference_calculation() {
+    let mut mfi = MFI::new(3);
+    assert!(mfi.update(11.0, 9.0, 10.0, 100.0).is_none());
+    assert!(mfi.update(12.0, 10.0, 11.0, 200.0).is_none());
+    assert!(mfi.update(11.0, 9.0, 10.0, 300.0).is_none());
+
+    // Flows: +11 * 200, -10 * 300, +12 * 100
+    let (value, signal) = mfi.update(13.0, 11.0, 12.0, 100.0).unwrap();
+    let positive = 11.0 * 200.0 + 12.0 * 100.0;
+    let negative = 10.0 * 300.0;
+    let expected = 100.0 - 100.0 / (1.0 + positive / negative);
+    assert!((value - expected).abs() < 1e-9);
+    assert_eq!(signal, MFISignal::Neutral);
+
+    // The oldest flow (+11 * 200) drops out of the buffer
+    let (value, _) = mfi.update(12.0, 10.0, 11.0, 50.0).unwrap();
+    let positive = 12.0 * 100.0;
+    let negative = 10.0 * 300.0 + 11.0 * 50.0;
+    let expected = 100.0 - 100.0 / (1.0 + positive / negative);
+    assert!((value - expected).abs() < 1e-9);
+}
+
+#[test]
+fn mfi_weights_moves_by_volume_unlike_rsi() {
+    // Equal-sized up and down moves, but selling happens on triple the volume
+    let mut mfi = MFI::default();
+    let mut rsi = RSI::new(14);
+    let mut readings = None;
+    for i in 0..15 {
+        let (close, volume) = if i % 2 == 0 {
+            (100.0, 300.0)
+        } else {
+            (101.0, 100.0)
+        };
+        let (high, low, close, volume) = candle(close, volume);
+        let rsi_value = rsi.update(close);
+        if let Some((mfi_value, signal)) = mfi.update(high, low, close, volume) {
+            readings = Some((mfi_value, signal, rsi_value.unwrap()));
+        }
+    }
+
+    let (mfi_value, signal, rsi_value) = readings.unwrap();
+    // Price alone is balanced...
+    assert!((rsi_value - 50.0).abs() < 1e-9);
+    // ...but three times the money left on the way down
+    assert!(
+        mfi_value < 30.0,
+        "MFI {} should reflect heavy selling",
+        mfi_value
+    );
+    assert!((mfi_value - rsi_value).abs() > 20.0);
+    assert_eq!(signal, MFISignal::Neutral);
+
+    // Flipping the volumes flips the reading around 50
+    let mut mirrored = MFI::default();
+    let mut mirrored_value = None;
+    for i in 0..15 {
+        let (close, volume) = if i % 2 == 0 {
+            (100.0, 100.0)
+        } else {
+            (101.0, 300.0)
+        };
+        let (high, low, close, volume) = candle(close, volume);
+        mirrored_value = mirrored.update(high, low, close, volume).map(|(v, _)| v);
+    }
+    assert!(mirrored_value.unwrap() > 70.0);
+}
+
+#[test]
+fn mfi_one_sided_flow_hits_extremes() {
+    let mut rising = MFI::new(5);
+    let mut falling = MFI::new(5);
+    let mut last = (None, None);
+    for i in 0..6 {
+        let (high, low, close, volume) = candle(100.0 + i as f64, 1_000.0);
+        last.0 = rising.update(high, low, close, volume);
+        let (high, low, close, volume) = candle(100.0 - i as f64, 1_000.0);
+        last.1 = falling.update(high, low, close, volume);
+    }
+    assert_eq!(last.0, Some((100.0, MFISignal::Overbought)));
+    assert_eq!(last.1, Some((0.0, MFISignal::Oversold)));
+
+    let mut flat = MFI::new(5);
+    let mut reading = None;
+    for _ in 0..6 {
+        let (high, low, close, volume) = candle(100.0, 1_000.0);
+        reading = flat.update(high, low, close, volume);
+    }
+    assert_eq!(reading, Some((50.0, MFISignal::Neutral)));
+
+    flat.reset();
+    assert!(flat.update(101.0, 99.0, 100.0, 10.0).is_none());
+}
+
+#[test]
+fn mfi_signal_thresholds_are_exclusive() {
+    assert_eq!(MFI::get_signal(MFI_OVERSOLD), MFISignal::Neutral);
+    assert_eq!(MFI::get_signal(MFI_OVERSOLD - 0.1), MFISignal::Oversold);
+    assert_eq!(MFI::get_signal(MFI_OVERBOUGHT), MFISignal::Neutral);
+    assert_eq!(MFI::get_signal(MFI_OVERBOUGHT + 0.1), MFISignal::Overbought);
+}
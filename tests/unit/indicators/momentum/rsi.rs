@@ -2,7 +2,9 @@
 
 use chrono::Utc;
 use perptrix::indicators::momentum::calculate_rsi_default;
-use perptrix::indicators::momentum::rsi::{RSISignal, RSI};
+use perptrix::indicators::momentum::rsi::{
+    detect_rsi_divergence, DivergenceType, RSISignal, DEFAULT_OVERBOUGHT, DEFAULT_OVERSOLD, RSI,
+};
 use perptrix::models::indicators::Candle;
 
 fn candle(price: f64) -> Candle {
@@ -12,32 +14,25 @@ fn candle(price: f64) -> Candle {
 #[test]
 fn rsi_detects_extremes() {
     let mut rsi = RSI::new(5);
-    let mut prev_close = None;
     let mut saw_overbought = false;
     let mut saw_oversold = false;
 
     for price in [50.0, 51.0, 52.0, 53.0, 54.0, 55.0, 56.0] {
         if let Some(value) = rsi.update(price) {
-            if let Some(prev) = prev_close {
-                let signal = rsi.get_signal(value, price - prev);
-                if signal == RSISignal::Overbought {
-                    saw_overbought = true;
-                }
+            let signal = rsi.get_signal(value, &[], &[], DEFAULT_OVERBOUGHT, DEFAULT_OVERSOLD);
+            if signal == RSISignal::Overbought {
+                saw_overbought = true;
             }
         }
-        prev_close = Some(price);
     }
 
     for price in [56.0, 55.0, 54.0, 53.0, 52.0, 51.0, 50.0, 49.5] {
         if let Some(value) = rsi.update(price) {
-            if let Some(prev) = prev_close {
-                let signal = rsi.get_signal(value, price - prev);
-                if signal == RSISignal::Oversold {
-                    saw_oversold = true;
-                }
+            let signal = rsi.get_signal(value, &[], &[], DEFAULT_OVERBOUGHT, DEFAULT_OVERSOLD);
+            if signal == RSISignal::Oversold {
+                saw_oversold = true;
             }
         }
-        prev_close = Some(price);
     }
 
     assert!(saw_overbought, "RSI should flag overbought on a long rally");
@@ -50,3 +45,141 @@ fn legacy_rsi_wrapper_still_available() {
     let indicator = calculate_rsi_default(&candles).expect("RSI result");
     assert!(indicator.value.is_finite());
 }
+
+#[test]
+fn rsi_reset_clears_state() {
+    let mut rsi = RSI::new(3);
+    for price in [10.0, 11.0, 12.0, 13.0, 12.5] {
+        rsi.update(price);
+    }
+    assert!(rsi.last().is_some());
+
+    rsi.reset();
+    assert_eq!(rsi.last(), None);
+    assert_eq!(
+        rsi.update(20.0),
+        None,
+        "first update after reset only seeds prev_close"
+    );
+
+    let mut fresh = RSI::new(3);
+    fresh.update(20.0);
+    for price in [21.0, 19.5, 22.0, 23.0] {
+        assert_eq!(rsi.update(price), fresh.update(price));
+    }
+}
+
+#[test]
+fn rsi_signal_respects_custom_thresholds() {
+    assert_eq!(RSI::classify(28.0, None, 70.0, 30.0), RSISignal::Oversold);
+    assert_eq!(RSI::classify(28.0, None, 80.0, 20.0), RSISignal::Neutral);
+    assert_eq!(RSI::classify(18.0, None, 80.0, 20.0), RSISignal::Oversold);
+    assert_eq!(RSI::classify(75.0, None, 80.0, 20.0), RSISignal::Neutral);
+}
+
+#[test]
+fn higher_price_high_with_lower_rsi_high_is_bearish() {
+    // Price swing highs 3 -> 4 at indices 1 and 3; RSI highs 70 -> 65
+    let prices = [1.0, 3.0, 2.0, 4.0, 3.0];
+    let rsi = [50.0, 70.0, 60.0, 65.0, 55.0];
+    assert_eq!(
+        detect_rsi_divergence(&prices, &rsi, 1),
+        Some(DivergenceType::Bearish)
+    );
+
+    // RSI confirming the higher high is no divergence
+    let rsi = [50.0, 60.0, 55.0, 70.0, 60.0];
+    assert_eq!(detect_rsi_divergence(&prices, &rsi, 1), None);
+}
+
+#[test]
+fn lower_price_low_with_higher_rsi_low_is_bullish() {
+    // Price swing lows 3 -> 2 at indices 1 and 3; RSI lows 30 -> 35
+    let prices = [5.0, 3.0, 4.0, 2.0, 3.0];
+    let rsi = [50.0, 30.0, 40.0, 35.0, 45.0];
+    assert_eq!(
+        detect_rsi_divergence(&prices, &rsi, 1),
+        Some(DivergenceType::Bullish)
+    );
+
+    // Histories are aligned at their newest entry
+    let longer_prices = [9.0, 8.0, 5.0, 3.0, 4.0, 2.0, 3.0];
+    assert_eq!(
+        detect_rsi_divergence(&longer_prices, &rsi, 1),
+        Some(DivergenceType::Bullish)
+    );
+}
+
+#[test]
+fn rsi_swings_pair_with_nearby_price_swings() {
+    // Price highs at indices 1 (3) and 4 (4). The RSI peaks a bar late at
+    // index 2 (70), then at index 4 (62): a lower high, although RSI at the
+    // price swing bars (55 -> 62) is higher.
+    let prices = [1.0, 3.0, 2.0, 1.0, 4.0, 3.0];
+    let rsi = [50.0, 55.0, 70.0, 60.0, 62.0, 58.0];
+    assert_eq!(
+        detect_rsi_divergence(&prices, &rsi, 1),
+        Some(DivergenceType::Bearish)
+    );
+
+    // No RSI swing near the price swings
+    let rising = [10.0, 20.0, 30.0, 40.0, 50.0, 60.0];
+    assert_eq!(detect_rsi_divergence(&prices, &rising, 1), None);
+}
+
+#[test]
+fn unconfirmed_swings_are_ignored() {
+    // The second price high is the newest reading, so it is not yet a swing
+    let prices = [1.0, 3.0, 2.0, 4.0];
+    let rsi = [50.0, 70.0, 60.0, 65.0];
+    assert_eq!(detect_rsi_divergence(&prices, &rsi, 1), None);
+    assert_eq!(detect_rsi_divergence(&[], &[], 1), None);
+}
+
+#[test]
+fn latest_swing_decides_between_divergences() {
+    // Expanding range: highs 4, 5, 6 at 1, 3, 5 and lows 2, 1, 0 at 2, 4, 6.
+    // RSI highs fall 70 -> 65 (bearish at 5); RSI lows rise 30 -> 35
+    // (bullish at 6).
+    let prices = [3.0, 4.0, 2.0, 5.0, 1.0, 6.0, 0.0, 3.0];
+    let rsi = [50.0, 60.0, 40.0, 70.0, 30.0, 65.0, 35.0, 50.0];
+    assert_eq!(
+        detect_rsi_divergence(&prices, &rsi, 1),
+        Some(DivergenceType::Bullish)
+    );
+
+    // Before the low at 6 is confirmed only the bearish swing counts
+    assert_eq!(
+        detect_rsi_divergence(&prices[..7], &rsi[..7], 1),
+        Some(DivergenceType::Bearish)
+    );
+}
+
+#[test]
+fn rsi_signal_reports_swing_divergence() {
+    // Price highs 10 -> 12 at indices 3 and 9, three bars clear on each side;
+    // RSI highs 80 -> 70
+    let prices = [
+        1.0, 2.0, 3.0, 10.0, 3.0, 2.0, 1.0, 2.0, 3.0, 12.0, 3.0, 2.0, 1.0,
+    ];
+    let rsi_values = [
+        40.0, 45.0, 50.0, 80.0, 50.0, 45.0, 40.0, 45.0, 50.0, 70.0, 50.0, 45.0, 40.0,
+    ];
+    let rsi = RSI::new(14);
+    assert_eq!(
+        rsi.get_signal(
+            40.0,
+            &prices,
+            &rsi_values,
+            DEFAULT_OVERBOUGHT,
+            DEFAULT_OVERSOLD
+        ),
+        RSISignal::BearishDivergence
+    );
+    // The divergence outranks the zone the latest reading is in
+    assert_eq!(
+        RSI::classify(20.0, Some(DivergenceType::Bearish), 70.0, 30.0),
+        RSISignal::BearishDivergence
+    );
+    assert_eq!(RSI::classify(20.0, None, 70.0, 30.0), RSISignal::Oversold);
+}
@@ -0,0 +1,56 @@
+//! Unit tests for the Stochastic RSI momentum indicator.
+
+use perptrix::indicators::momentum::stochastic_rsi::{
+    calculate_stoch_rsi, StochRsiSignal, StochasticRsi,
+};
+
+#[test]
+fn stoch_rsi_needs_full_windows() {
+    // 14 RSI values fill the stochastic window, then K and D need 2 more each
+    let series: Vec<f64> = (0..17).map(|i| 40.0 + i as f64).collect();
+    assert_eq!(calculate_stoch_rsi(&series, 14, 3, 3), None);
+    assert_eq!(calculate_stoch_rsi(&[], 14, 3, 3), None);
+
+    let series: Vec<f64> = (0..18).map(|i| 40.0 + i as f64).collect();
+    let (k, d) = calculate_stoch_rsi(&series, 14, 3, 3).expect("warmed up");
+    assert!((k - 100.0).abs() < 1e-9);
+    assert!((d - 100.0).abs() < 1e-9);
+}
+
+#[test]
+fn stoch_rsi_flat_series_is_neutral_without_cross() {
+    let mut stoch_rsi = StochasticRsi::new(5, 3, 3);
+    let mut last = None;
+    for _ in 0..20 {
+        last = stoch_rsi.update(55.0);
+    }
+    let (k, d, signal) = last.expect("warmed up");
+    assert_eq!(k, d);
+    assert_eq!(k, 50.0);
+    assert_eq!(signal, StochRsiSignal::Neutral);
+}
+
+#[test]
+fn stoch_rsi_flags_extremes_and_crosses() {
+    let mut stoch_rsi = StochasticRsi::new(5, 1, 3);
+    let mut signals = Vec::new();
+
+    // Falling RSI pins %K at 0, then a bounce lifts %K over %D while %D is still low
+    for rsi in [60.0, 55.0, 50.0, 45.0, 40.0, 35.0, 30.0, 25.0, 45.0] {
+        if let Some((_, _, signal)) = stoch_rsi.update(rsi) {
+            signals.push(signal);
+        }
+    }
+    assert!(signals.contains(&StochRsiSignal::Oversold));
+    assert_eq!(signals.last(), Some(&StochRsiSignal::BullishCross));
+
+    stoch_rsi.reset();
+    signals.clear();
+    for rsi in [40.0, 45.0, 50.0, 55.0, 60.0, 65.0, 70.0, 75.0, 55.0] {
+        if let Some((_, _, signal)) = stoch_rsi.update(rsi) {
+            signals.push(signal);
+        }
+    }
+    assert!(signals.contains(&StochRsiSignal::Overbought));
+    assert_eq!(signals.last(), Some(&StochRsiSignal::BearishCross));
+}
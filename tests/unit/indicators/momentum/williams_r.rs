@@ -0,0 +1,61 @@
+//! Unit tests for the Williams %R momentum indicator.
+
+use crate::indicators_test_utils::candle;
+use perptrix::indicators::momentum::williams_r::{WilliamsR, WilliamsRSignal};
+use proptest::prelude::*;
+
+#[test]
+fn williams_r_waits_for_full_window() {
+    let mut williams_r = WilliamsR::new(3);
+    assert!(williams_r.update(110.0, 100.0, 105.0).is_none());
+    assert!(williams_r.update(112.0, 101.0, 104.0).is_none());
+
+    // Range 100..112, close 106 sits exactly halfway
+    let (value, signal) = williams_r.update(108.0, 102.0, 106.0).unwrap();
+    assert!((value + 50.0).abs() < 1e-9);
+    assert_eq!(signal, WilliamsRSignal::Neutral);
+}
+
+#[test]
+fn williams_r_signal_boundaries() {
+    assert_eq!(WilliamsR::get_signal(0.0), WilliamsRSignal::Overbought);
+    assert_eq!(WilliamsR::get_signal(-20.0), WilliamsRSignal::Overbought);
+    assert_eq!(WilliamsR::get_signal(-20.1), WilliamsRSignal::Neutral);
+    assert_eq!(WilliamsR::get_signal(-79.9), WilliamsRSignal::Neutral);
+    assert_eq!(WilliamsR::get_signal(-80.0), WilliamsRSignal::Oversold);
+    assert_eq!(WilliamsR::get_signal(-100.0), WilliamsRSignal::Oversold);
+}
+
+#[test]
+fn williams_r_extremes_and_flat_range() {
+    let mut williams_r = WilliamsR::new(2);
+    williams_r.update(110.0, 100.0, 105.0);
+    let (value, signal) = williams_r.update(120.0, 105.0, 120.0).unwrap();
+    assert_eq!(value, 0.0);
+    assert_eq!(signal, WilliamsRSignal::Overbought);
+
+    let (value, signal) = williams_r.update(119.0, 90.0, 90.0).unwrap();
+    assert_eq!(value, -100.0);
+    assert_eq!(signal, WilliamsRSignal::Oversold);
+
+    williams_r.reset();
+    williams_r.update(100.0, 100.0, 100.0);
+    let (value, signal) = williams_r.update(100.0, 100.0, 100.0).unwrap();
+    assert_eq!(value, -50.0);
+    assert_eq!(signal, WilliamsRSignal::Neutral);
+}
+
+proptest! {
+    #[test]
+    fn williams_r_stays_in_range(
+        candles in prop::collection::vec(candle(), 1..80),
+        period in 1usize..30,
+    ) {
+        let mut williams_r = WilliamsR::new(period);
+        for (high, low, close) in candles {
+            if let Some((value, _)) = williams_r.update(high, low, close) {
+                prop_assert!((-100.0..=0.0).contains(&value), "%R {} out of range", value);
+            }
+        }
+    }
+}
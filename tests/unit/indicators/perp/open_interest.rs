@@ -1,6 +1,6 @@
 //! Unit tests for the open interest indicator.
 
-use perptrix::indicators::perp::open_interest::{OpenInterest, OpenInterestSignal};
+use perptrix::indicators::perp::open_interest::{OITrendSignal, OpenInterest, OpenInterestSignal};
 
 #[test]
 fn open_interest_detects_expansions_and_squeezes() {
@@ -17,3 +17,45 @@ fn open_interest_detects_expansions_and_squeezes() {
     assert_eq!(oi.update(1050.0, 98.0), OpenInterestSignal::LongSqueeze);
     assert_eq!(oi.update(1000.0, 100.0), OpenInterestSignal::ShortSqueeze);
 }
+
+#[test]
+fn open_interest_trend_requires_full_window() {
+    let mut oi = OpenInterest::with_trend_period(5);
+    for i in 0..4 {
+        oi.update(1000.0 + i as f64, 100.0);
+    }
+    assert!(oi.trend().is_none());
+    oi.update(1004.0, 100.0);
+    assert!(oi.trend().is_some());
+}
+
+#[test]
+fn monotonically_increasing_open_interest_is_strong_increase() {
+    let mut oi = OpenInterest::new();
+    for i in 0..30 {
+        oi.update(1000.0 + i as f64 * 15.0, 100.0);
+    }
+    let trend = oi.trend().expect("window is full");
+    assert_eq!(trend.signal, OITrendSignal::StrongIncrease);
+    assert!(trend.slope > 0.0);
+    assert!(trend.r_squared > 0.99);
+}
+
+#[test]
+fn falling_and_choppy_open_interest_trends() {
+    let mut falling = OpenInterest::with_trend_period(10);
+    for i in 0..10 {
+        falling.update(2000.0 - i as f64 * 20.0, 100.0);
+    }
+    assert_eq!(
+        falling.trend().unwrap().signal,
+        OITrendSignal::StrongDecrease
+    );
+
+    let mut choppy = OpenInterest::with_trend_period(10);
+    for i in 0..10 {
+        let oi = if i % 2 == 0 { 1000.0 } else { 1100.0 };
+        choppy.update(oi, 100.0);
+    }
+    assert_eq!(choppy.trend().unwrap().signal, OITrendSignal::Consolidating);
+}
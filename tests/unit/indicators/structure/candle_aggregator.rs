@@ -0,0 +1,132 @@
+//! Unit tests for synthesizing higher-timeframe candles.
+
+use crate::test_utils::minute;
+use perptrix::indicators::structure::candle_aggregator::{aggregate_candles, interval_minutes};
+use perptrix::models::indicators::Candle;
+use proptest::prelude::*;
+
+fn one_minute_candles(count: usize) -> Vec<Candle> {
+    (0..count as i64)
+        .map(|i| {
+            let price = 100.0 + i as f64;
+            Candle {
+                open_interest: Some(1_000.0 + i as f64),
+                funding_rate: Some(0.0001 * (i % 3) as f64),
+                ..Candle::new(
+                    price,
+                    price + 2.0,
+                    price - 2.0,
+                    price + 0.5,
+                    10.0,
+                    minute(i),
+                )
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn aggregate_combines_ohlcv_per_bucket() {
+    let candles = one_minute_candles(30);
+    let aggregated = aggregate_candles(&candles, 15);
+
+    assert_eq!(aggregated.len(), 2);
+    let first = &aggregated[0];
+    assert_eq!(first.open, candles[0].open);
+    assert_eq!(first.high, candles[14].high);
+    assert_eq!(first.low, candles[0].low);
+    assert_eq!(first.close, candles[14].close);
+    assert_eq!(first.volume, 150.0);
+    assert_eq!(first.timestamp, candles[14].timestamp);
+    assert_eq!(first.open_interest, candles[14].open_interest);
+    let mean_funding = (5.0 * 0.0 + 5.0 * 0.0001 + 5.0 * 0.0002) / 15.0;
+    assert!((first.funding_rate.unwrap() - mean_funding).abs() < 1e-12);
+    assert_eq!(aggregated[1].open, candles[15].open);
+}
+
+#[test]
+fn buckets_are_aligned_to_utc() {
+    // Starting 5 minutes into a bucket leaves a 10-candle first bucket
+    let candles = one_minute_candles(25);
+    let aggregated = aggregate_candles(&candles[5..], 15);
+
+    assert_eq!(aggregated.len(), 2);
+    assert_eq!(aggregated[0].volume, 100.0);
+    assert_eq!(aggregated[1].volume, 100.0);
+}
+
+#[test]
+fn missing_perp_data_stays_missing() {
+    let candles: Vec<Candle> = (0..4)
+        .map(|i| Candle::new(1.0, 2.0, 0.5, 1.5, 1.0, minute(i)))
+        .collect();
+    let aggregated = aggregate_candles(&candles, 60);
+
+    assert_eq!(aggregated.len(), 1);
+    assert_eq!(aggregated[0].open_interest, None);
+    assert_eq!(aggregated[0].funding_rate, None);
+    assert!(aggregate_candles(&[], 60).is_empty());
+}
+
+#[test]
+fn interval_minutes_parses_common_intervals() {
+    assert_eq!(interval_minutes("1m"), Some(1));
+    assert_eq!(interval_minutes("15m"), Some(15));
+    assert_eq!(interval_minutes("4h"), Some(240));
+    assert_eq!(interval_minutes("1d"), Some(1440));
+    assert_eq!(interval_minutes("0m"), None);
+    assert_eq!(interval_minutes("m"), None);
+    assert_eq!(interval_minutes("1w"), None);
+}
+
+fn candle_series() -> impl Strategy<Value = Vec<Candle>> {
+    prop::collection::vec(
+        (
+            1.0f64..1_000.0,
+            0.0f64..10.0,
+            0.0f64..10.0,
+            0.0f64..1_000.0,
+            1i64..5,
+            prop::option::of(0.0f64..1e6),
+            prop::option::of(-0.01f64..0.01),
+        ),
+        1..200,
+    )
+    .prop_map(|rows| {
+        let mut offset = 0;
+        rows.into_iter()
+            .map(
+                |(open, up, down, volume, gap, open_interest, funding_rate)| {
+                    offset += gap;
+                    Candle {
+                        open_interest,
+                        funding_rate,
+                        ..Candle::new(open, open + up, open - down, open, volume, minute(offset))
+                    }
+                },
+            )
+            .collect()
+    })
+}
+
+proptest! {
+    #[test]
+    fn reaggregating_is_idempotent(
+        candles in candle_series(),
+        target in prop::sample::select(vec![5u32, 15, 60, 240]),
+    ) {
+        let once = aggregate_candles(&candles, target);
+        prop_assert_eq!(aggregate_candles(&once, target), once);
+    }
+
+    #[test]
+    fn aggregation_preserves_totals(candles in candle_series()) {
+        let aggregated = aggregate_candles(&candles, 15);
+        let volume: f64 = candles.iter().map(|c| c.volume).sum();
+        let aggregated_volume: f64 = aggregated.iter().map(|c| c.volume).sum();
+
+        prop_assert!((volume - aggregated_volume).abs() < 1e-6);
+        prop_assert_eq!(aggregated.first().unwrap().open, candles[0].open);
+        prop_assert_eq!(aggregated.last().unwrap().close, candles.last().unwrap().close);
+    }
+}
@@ -0,0 +1,165 @@
+//! Unit tests for candlestick pattern detection.
+
+use chrono::{Duration, TimeZone, Utc};
+use perptrix::indicators::structure::candlestick::{
+    detect_patterns, latest_pattern, CandlestickPattern, CandlestickPatternKind, PatternSignal,
+};
+use perptrix::models::indicators::Candle;
+
+/// Candles from `(open, high, low, close)`, one minute apart
+fn candles(ohlc: &[(f64, f64, f64, f64)]) -> Vec<Candle> {
+    let start = Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap();
+    ohlc.iter()
+        .enumerate()
+        .map(|(i, &(open, high, low, close))| {
+            Candle::new(
+                open,
+                high,
+                low,
+                close,
+                10.0,
+                start + Duration::minutes(i as i64),
+            )
+        })
+        .collect()
+}
+
+fn kinds(patterns: &[CandlestickPattern]) -> Vec<(CandlestickPatternKind, usize)> {
+    patterns.iter().map(|p| (p.kind, p.index)).collect()
+}
+
+#[test]
+fn hammer_has_a_long_lower_shadow_and_no_upper_shadow() {
+    let pattern = latest_pattern(&candles(&[(10.0, 10.5, 8.0, 10.5)])).unwrap();
+    assert_eq!(pattern.kind, CandlestickPatternKind::Hammer);
+    assert_eq!(pattern.signal, PatternSignal::Bullish);
+    assert_eq!(pattern.strength, 1);
+    assert_eq!(pattern.index, 0);
+
+    // An upper shadow of a fifth of the range is too long
+    assert_eq!(latest_pattern(&candles(&[(10.0, 11.0, 8.0, 10.5)])), None);
+    // A lower shadow of exactly twice the body is too short
+    assert_eq!(latest_pattern(&candles(&[(10.0, 10.5, 9.0, 10.5)])), None);
+}
+
+#[test]
+fn shooting_star_has_a_long_upper_shadow_and_no_lower_shadow() {
+    let pattern = latest_pattern(&candles(&[(10.5, 12.5, 10.0, 10.0)])).unwrap();
+    assert_eq!(pattern.kind, CandlestickPatternKind::ShootingStar);
+    assert_eq!(pattern.signal, PatternSignal::Bearish);
+    assert_eq!(pattern.score(), -1.0);
+}
+
+#[test]
+fn doji_has_a_body_under_five_percent_of_its_range() {
+    let pattern = latest_pattern(&candles(&[(10.0, 11.0, 9.0, 10.02)])).unwrap();
+    assert_eq!(pattern.kind, CandlestickPatternKind::Doji);
+    assert_eq!(pattern.signal, PatternSignal::Neutral);
+    assert_eq!(pattern.score(), 0.0);
+
+    // A long-legged doji is not also a hammer
+    let patterns = detect_patterns(&candles(&[(10.0, 10.0, 8.0, 10.0)]));
+    assert_eq!(kinds(&patterns), [(CandlestickPatternKind::Doji, 0)]);
+
+    assert_eq!(latest_pattern(&candles(&[(10.0, 11.0, 9.0, 10.2)])), None);
+    // A flat candle has no range to compare against
+    assert_eq!(latest_pattern(&candles(&[(10.0, 10.0, 10.0, 10.0)])), None);
+}
+
+#[test]
+fn engulfing_body_covers_the_opposite_body_before_it() {
+    let bullish = candles(&[(11.0, 11.2, 9.8, 10.0), (9.9, 11.6, 9.8, 11.5)]);
+    let pattern = latest_pattern(&bullish).unwrap();
+    assert_eq!(pattern.kind, CandlestickPatternKind::BullishEngulfing);
+    assert_eq!(pattern.signal, PatternSignal::Bullish);
+    assert_eq!(pattern.strength, 2);
+    assert_eq!(pattern.index, 1);
+
+    let bearish = candles(&[(10.0, 11.2, 9.8, 11.0), (11.1, 11.2, 9.4, 9.5)]);
+    let pattern = latest_pattern(&bearish).unwrap();
+    assert_eq!(pattern.kind, CandlestickPatternKind::BearishEngulfing);
+    assert_eq!(pattern.score(), -2.0);
+
+    // Closing inside the previous body, or in the same direction, is not engulfing
+    let inside = candles(&[(11.0, 11.2, 9.8, 10.0), (9.9, 11.6, 9.8, 10.9)]);
+    assert_eq!(latest_pattern(&inside), None);
+    let same_direction = candles(&[(10.0, 11.2, 9.8, 11.0), (9.9, 11.6, 9.8, 11.5)]);
+    assert_eq!(latest_pattern(&same_direction), None);
+}
+
+#[test]
+fn morning_star_reverses_a_long_bearish_candle() {
+    let series = candles(&[
+        (12.0, 12.1, 9.9, 10.0),
+        (9.8, 10.0, 9.5, 9.7),
+        (9.9, 11.6, 9.8, 11.5),
+    ]);
+    let pattern = latest_pattern(&series).unwrap();
+    assert_eq!(pattern.kind, CandlestickPatternKind::MorningStar);
+    assert_eq!(pattern.signal, PatternSignal::Bullish);
+    assert_eq!(pattern.strength, 3);
+    assert_eq!(pattern.index, 2);
+    assert_eq!(
+        kinds(&detect_patterns(&series)),
+        [(CandlestickPatternKind::MorningStar, 2)]
+    );
+
+    // Closing below the middle of the first body is not a reversal
+    let weak = candles(&[
+        (12.0, 12.1, 9.9, 10.0),
+        (9.8, 10.0, 9.5, 9.7),
+        (9.9, 11.0, 9.8, 10.9),
+    ]);
+    assert_eq!(latest_pattern(&weak), None);
+}
+
+#[test]
+fn evening_star_reverses_a_long_bullish_candle() {
+    let series = candles(&[
+        (10.0, 12.1, 9.9, 12.0),
+        (12.2, 12.5, 12.0, 12.3),
+        (12.1, 12.2, 10.4, 10.5),
+    ]);
+    let pattern = latest_pattern(&series).unwrap();
+    assert_eq!(pattern.kind, CandlestickPatternKind::EveningStar);
+    assert_eq!(pattern.signal, PatternSignal::Bearish);
+    assert_eq!(pattern.score(), -3.0);
+
+    // A large middle body is not a star
+    let large_middle = candles(&[
+        (10.0, 12.1, 9.9, 12.0),
+        (12.2, 13.0, 12.0, 12.9),
+        (12.1, 12.2, 10.4, 10.5),
+    ]);
+    assert_eq!(latest_pattern(&large_middle), None);
+}
+
+#[test]
+fn patterns_ending_on_the_same_candle_are_strongest_first() {
+    // The third candle completes a morning star and engulfs the second
+    let series = candles(&[
+        (12.0, 12.1, 9.9, 10.0),
+        (9.8, 10.0, 9.5, 9.7),
+        (9.6, 11.6, 9.5, 11.5),
+    ]);
+    assert_eq!(
+        kinds(&detect_patterns(&series)),
+        [
+            (CandlestickPatternKind::MorningStar, 2),
+            (CandlestickPatternKind::BullishEngulfing, 2),
+        ]
+    );
+    assert_eq!(
+        latest_pattern(&series).unwrap().kind,
+        CandlestickPatternKind::MorningStar
+    );
+
+    // Earlier patterns are listed but only the last candle's count as latest
+    let series = candles(&[(10.0, 10.5, 8.0, 10.5), (10.0, 11.0, 9.0, 10.5)]);
+    assert_eq!(
+        kinds(&detect_patterns(&series)),
+        [(CandlestickPatternKind::Hammer, 0)]
+    );
+    assert_eq!(latest_pattern(&series), None);
+    assert_eq!(latest_pattern(&[]), None);
+}
@@ -0,0 +1,98 @@
+//! Unit tests for the Donchian Channel breakout indicator.
+
+use crate::indicators_test_utils::candle;
+use perptrix::indicators::structure::donchian::{DonchianChannel, DonchianSignal};
+use proptest::prelude::*;
+
+/// Deterministic zig-zag series so both implementations see the same input
+fn series(len: usize) -> Vec<(f64, f64, f64)> {
+    (0..len)
+        .map(|i| {
+            let base = 100.0 + (i as f64 * 0.37).sin() * 20.0 + (i % 17) as f64;
+            (base + 1.5, base - 1.5, base)
+        })
+        .collect()
+}
+
+/// Reference O(period) rescan of the window for every candle
+fn naive_channel(candles: &[(f64, f64, f64)], period: usize) -> Vec<(f64, f64)> {
+    (period..=candles.len())
+        .map(|end| {
+            let window = &candles[end - period..end];
+            let upper = window.iter().map(|c| c.0).fold(f64::MIN, f64::max);
+            let lower = window.iter().map(|c| c.1).fold(f64::MAX, f64::min);
+            (upper, lower)
+        })
+        .collect()
+}
+
+fn deque_channel(candles: &[(f64, f64, f64)], period: usize) -> Vec<(f64, f64)> {
+    let mut donchian = DonchianChannel::new(period);
+    candles
+        .iter()
+        .filter_map(|&(high, low, close)| donchian.update(high, low, close))
+        .map(|(upper, _, lower, _)| (upper, lower))
+        .collect()
+}
+
+#[test]
+fn donchian_waits_for_full_window() {
+    let mut donchian = DonchianChannel::new(3);
+    assert!(donchian.update(105.0, 95.0, 100.0).is_none());
+    assert!(donchian.update(110.0, 98.0, 108.0).is_none());
+
+    let (upper, middle, lower, signal) = donchian.update(104.0, 90.0, 97.0).unwrap();
+    assert_eq!(upper, 110.0);
+    assert_eq!(lower, 90.0);
+    assert_eq!(middle, 100.0);
+    assert_eq!(signal, DonchianSignal::Midline);
+    assert_eq!(donchian.midline(), Some(100.0));
+
+    // 110 drops out of the window once two newer candles arrive
+    donchian.update(103.0, 96.0, 100.0);
+    let (upper, _, _, _) = donchian.update(102.0, 97.0, 100.0).unwrap();
+    assert_eq!(upper, 104.0);
+}
+
+#[test]
+fn donchian_flags_breakouts_against_prior_channel() {
+    let mut donchian = DonchianChannel::new(5);
+    for _ in 0..5 {
+        donchian.update(101.0, 99.0, 100.0);
+    }
+
+    let (upper, _, _, signal) = donchian.update(106.0, 100.0, 105.0).unwrap();
+    assert_eq!(signal, DonchianSignal::UpperBreakout);
+    assert_eq!(upper, 106.0);
+
+    let (_, _, lower, signal) = donchian.update(101.0, 90.0, 92.0).unwrap();
+    assert_eq!(signal, DonchianSignal::LowerBreakdown);
+    assert_eq!(lower, 90.0);
+
+    donchian.reset();
+    assert_eq!(donchian.upper(), None);
+    assert!(donchian.update(101.0, 99.0, 100.0).is_none());
+}
+
+#[test]
+fn donchian_matches_naive_scan() {
+    let candles = series(1000);
+    for period in [1, 2, 20, 55, 250] {
+        assert_eq!(
+            deque_channel(&candles, period),
+            naive_channel(&candles, period),
+            "period {}",
+            period
+        );
+    }
+}
+
+proptest! {
+    #[test]
+    fn donchian_agrees_with_naive_scan(
+        candles in prop::collection::vec(candle(), 1..120),
+        period in 1usize..40,
+    ) {
+        prop_assert_eq!(deque_channel(&candles, period), naive_channel(&candles, period));
+    }
+}
@@ -0,0 +1,156 @@
+//! Unit tests for Fibonacci retracement levels and swing detection.
+
+use chrono::{Duration, TimeZone, Utc};
+use perptrix::indicators::structure::fibonacci::{FibonacciLevels, SwingDirection, FIB_RATIOS};
+use perptrix::models::indicators::Candle;
+
+fn assert_levels(actual: &[f64], expected: &[f64]) {
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(expected) {
+        assert!((a - e).abs() < 1e-9, "{:?} != {:?}", actual, expected);
+    }
+}
+
+/// Candles whose midpoints follow `path`, one unit either side
+fn candles(path: &[f64]) -> Vec<Candle> {
+    let start = Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap();
+    path.iter()
+        .enumerate()
+        .map(|(i, &mid)| {
+            Candle::new(
+                mid,
+                mid + 1.0,
+                mid - 1.0,
+                mid,
+                10.0,
+                start + Duration::minutes(i as i64),
+            )
+        })
+        .collect()
+}
+
+/// Straight line from `from` to `to` over `steps` candles, excluding `from`
+fn leg(from: f64, to: f64, steps: usize) -> Vec<f64> {
+    (1..=steps)
+        .map(|i| from + (to - from) * i as f64 / steps as f64)
+        .collect()
+}
+
+#[test]
+fn upswing_levels_retrace_down_from_the_high() {
+    let levels = FibonacciLevels::calculate(200.0, 100.0);
+
+    assert_eq!(levels.direction, SwingDirection::Up);
+    assert_levels(
+        &levels.levels,
+        &[200.0, 176.4, 161.8, 150.0, 138.2, 121.4, 100.0],
+    );
+    assert_eq!(
+        levels
+            .position_ratio(161.8)
+            .map(|r| (r * 1e6).round() / 1e6),
+        Some(0.382)
+    );
+
+    assert_eq!(
+        FibonacciLevels::nearest_support(155.0, &levels),
+        Some((150.0, 0.5))
+    );
+    let (resistance, ratio) = FibonacciLevels::nearest_resistance(155.0, &levels).unwrap();
+    assert!((resistance - 161.8).abs() < 1e-9);
+    assert_eq!(ratio, 0.382);
+}
+
+#[test]
+fn downswing_levels_retrace_up_from_the_low() {
+    let levels = FibonacciLevels::calculate_downswing(200.0, 100.0);
+
+    assert_eq!(levels.direction, SwingDirection::Down);
+    assert_levels(
+        &levels.levels,
+        &[100.0, 123.6, 138.2, 150.0, 161.8, 178.6, 200.0],
+    );
+    assert_eq!(levels.position_ratio(150.0), Some(0.5));
+
+    let (support, ratio) = FibonacciLevels::nearest_support(130.0, &levels).unwrap();
+    assert!((support - 123.6).abs() < 1e-9);
+    assert_eq!(ratio, 0.236);
+    let (resistance, ratio) = FibonacciLevels::nearest_resistance(130.0, &levels).unwrap();
+    assert!((resistance - 138.2).abs() < 1e-9);
+    assert_eq!(ratio, 0.382);
+}
+
+#[test]
+fn levels_outside_the_swing_have_one_side_only() {
+    let levels = FibonacciLevels::calculate(200.0, 100.0);
+
+    assert_eq!(FibonacciLevels::nearest_support(90.0, &levels), None);
+    assert_eq!(
+        FibonacciLevels::nearest_resistance(90.0, &levels),
+        Some((100.0, 1.0))
+    );
+    assert_eq!(
+        FibonacciLevels::nearest_support(210.0, &levels),
+        Some((200.0, 0.0))
+    );
+    assert_eq!(FibonacciLevels::nearest_resistance(210.0, &levels), None);
+    // A price on a level is supported by it
+    assert_eq!(
+        FibonacciLevels::nearest_support(150.0, &levels),
+        Some((150.0, 0.5))
+    );
+    assert_eq!(
+        levels.iter().map(|(_, ratio)| ratio).collect::<Vec<_>>(),
+        FIB_RATIOS
+    );
+}
+
+#[test]
+fn flat_swing_has_no_position() {
+    let levels = FibonacciLevels::calculate(100.0, 100.0);
+    assert_eq!(levels.position_ratio(100.0), None);
+}
+
+#[test]
+fn detect_finds_an_upswing_from_pivots() {
+    // Down to a low, up to a high, then a partial pullback
+    let mut path = vec![150.0];
+    path.extend(leg(150.0, 100.0, 10));
+    path.extend(leg(100.0, 200.0, 20));
+    path.extend(leg(200.0, 170.0, 8));
+    let fib = FibonacciLevels::new(5);
+
+    let levels = fib.detect(&candles(&path)).expect("both pivots confirmed");
+    assert_eq!(levels.direction, SwingDirection::Up);
+    assert_eq!(levels.swing_high, 201.0);
+    assert_eq!(levels.swing_low, 99.0);
+}
+
+#[test]
+fn detect_finds_a_downswing_from_pivots() {
+    // Up to a high, down to a low, then a partial bounce
+    let mut path = vec![150.0];
+    path.extend(leg(150.0, 200.0, 10));
+    path.extend(leg(200.0, 100.0, 20));
+    path.extend(leg(100.0, 130.0, 8));
+    let fib = FibonacciLevels::new(5);
+
+    let levels = fib.detect(&candles(&path)).expect("both pivots confirmed");
+    assert_eq!(levels.direction, SwingDirection::Down);
+    assert_eq!(levels.swing_high, 201.0);
+    assert_eq!(levels.swing_low, 99.0);
+    assert_levels(&[levels.levels[0], levels.levels[6]], &[99.0, 201.0]);
+}
+
+#[test]
+fn detect_waits_for_pivot_confirmation() {
+    // The low is only 3 candles old, so no pivot low is confirmed yet
+    let mut path = vec![150.0];
+    path.extend(leg(150.0, 200.0, 10));
+    path.extend(leg(200.0, 100.0, 20));
+    path.extend(leg(100.0, 110.0, 3));
+    let fib = FibonacciLevels::new(5);
+
+    assert_eq!(fib.detect(&candles(&path)), None);
+    assert_eq!(fib.detect(&candles(&path[..5])), None);
+}
@@ -0,0 +1,133 @@
+//! Unit tests for interpolating candles over feed gaps.
+
+use crate::test_utils::minute;
+use chrono::Duration;
+use perptrix::indicators::structure::gap_fill::{
+    fill_candle_gaps, fill_candle_gaps_up_to, DEFAULT_MAX_GAP_FILL_CANDLES,
+};
+use perptrix::models::indicators::Candle;
+use proptest::prelude::*;
+
+fn candle(price: f64, volume: f64, at: i64) -> Candle {
+    Candle::new(
+        price,
+        price + 2.0,
+        price - 2.0,
+        price + 1.0,
+        volume,
+        minute(at),
+    )
+}
+
+#[test]
+fn interpolates_each_field_across_a_gap() {
+    let before = candle(100.0, 10.0, 0).with_open_interest(1_000.0);
+    let after = candle(140.0, 50.0, 4).with_open_interest(2_000.0);
+
+    let filled = fill_candle_gaps(vec![before.clone(), after.clone()], 60);
+
+    assert_eq!(filled.len(), 5);
+    assert_eq!(filled[0], before);
+    assert_eq!(filled[4], after);
+    for (i, synthetic) in filled[1..4].iter().enumerate() {
+        let step = i as f64 + 1.0;
+        assert!(synthetic.is_synthetic);
+        assert_eq!(synthetic.timestamp, minute(i as i64 + 1));
+        assert_eq!(synthetic.open, 100.0 + 10.0 * step);
+        assert_eq!(synthetic.high, 102.0 + 10.0 * step);
+        assert_eq!(synthetic.low, 98.0 + 10.0 * step);
+        assert_eq!(synthetic.close, 101.0 + 10.0 * step);
+        assert_eq!(synthetic.volume, 10.0 + 10.0 * step);
+        assert_eq!(synthetic.open_interest, Some(1_000.0 + 250.0 * step));
+        // Only one side has a funding rate
+        assert_eq!(synthetic.funding_rate, None);
+    }
+    assert!(!filled[0].is_synthetic && !filled[4].is_synthetic);
+}
+
+#[test]
+fn small_jitter_is_not_a_gap() {
+    let candles = vec![
+        candle(100.0, 1.0, 0),
+        Candle {
+            timestamp: minute(1) + Duration::seconds(29),
+            ..candle(101.0, 1.0, 1)
+        },
+    ];
+    assert_eq!(fill_candle_gaps(candles.clone(), 60), candles);
+
+    // Just past 1.5 intervals one candle is missing
+    let candles = vec![
+        candle(100.0, 1.0, 0),
+        Candle {
+            timestamp: minute(1) + Duration::seconds(31),
+            ..candle(101.0, 1.0, 1)
+        },
+    ];
+    let filled = fill_candle_gaps(candles, 60);
+    assert_eq!(filled.len(), 3);
+    assert_eq!(filled[1].timestamp, minute(1));
+}
+
+#[test]
+fn long_outages_are_left_unfilled() {
+    let max = DEFAULT_MAX_GAP_FILL_CANDLES as i64;
+    let just_fits = vec![candle(100.0, 1.0, 0), candle(100.0, 1.0, max + 1)];
+    assert_eq!(fill_candle_gaps(just_fits, 60).len(), 2 + max as usize);
+
+    let too_long = vec![candle(100.0, 1.0, 0), candle(100.0, 1.0, max + 2)];
+    assert_eq!(fill_candle_gaps(too_long.clone(), 60), too_long);
+
+    let hour_long = vec![candle(100.0, 1.0, 0), candle(100.0, 1.0, 60)];
+    assert_eq!(fill_candle_gaps_up_to(hour_long.clone(), 60, 59).len(), 61);
+    assert_eq!(fill_candle_gaps_up_to(hour_long.clone(), 60, 58), hour_long);
+    assert_eq!(fill_candle_gaps_up_to(hour_long.clone(), 60, 0), hour_long);
+}
+
+#[test]
+fn synthetic_candles_are_not_serialized_as_real_ones() {
+    let real = serde_json::to_value(candle(100.0, 1.0, 0)).unwrap();
+    assert!(real.get("is_synthetic").is_none());
+
+    let filled = fill_candle_gaps(vec![candle(100.0, 1.0, 0), candle(100.0, 1.0, 2)], 60);
+    let json = serde_json::to_string(&filled[1]).unwrap();
+    let round_trip: Candle = serde_json::from_str(&json).unwrap();
+    assert!(round_trip.is_synthetic);
+}
+
+/// Chronological candles with 1 to 20 minute steps
+fn gappy_series() -> impl Strategy<Value = Vec<Candle>> {
+    prop::collection::vec((1.0f64..1_000.0, 0.0f64..1_000.0, 1i64..20), 0..100).prop_map(|rows| {
+        let mut offset = 0;
+        rows.into_iter()
+            .map(|(price, volume, gap)| {
+                offset += gap;
+                candle(price, volume, offset)
+            })
+            .collect()
+    })
+}
+
+proptest! {
+    #[test]
+    fn filled_timestamps_strictly_increase(
+        candles in gappy_series(),
+        max_fill in 0usize..25,
+    ) {
+        let filled = fill_candle_gaps_up_to(candles.clone(), 60, max_fill);
+
+        prop_assert!(filled.windows(2).all(|pair| pair[0].timestamp < pair[1].timestamp));
+        let real: Vec<Candle> = filled.iter().filter(|c| !c.is_synthetic).cloned().collect();
+        prop_assert_eq!(real, candles);
+    }
+
+    #[test]
+    fn filled_gaps_leave_one_interval_steps(candles in gappy_series()) {
+        let filled = fill_candle_gaps_up_to(candles, 60, 20);
+
+        for pair in filled.windows(2) {
+            prop_assert_eq!(pair[1].timestamp - pair[0].timestamp, Duration::minutes(1));
+            prop_assert!(pair[1].low <= pair[1].open && pair[1].open <= pair[1].high);
+        }
+    }
+}
@@ -0,0 +1,114 @@
+//! Unit tests for the Heikin-Ashi candle transformation.
+
+use crate::test_utils::minute;
+use perptrix::indicators::momentum::rsi::RSI;
+use perptrix::indicators::structure::heikin_ashi::{
+    transform_to_heikin_ashi, transform_to_heikin_ashi_with,
+};
+use perptrix::models::indicators::Candle;
+use proptest::prelude::*;
+
+fn candle(open: f64, high: f64, low: f64, close: f64, at: i64) -> Candle {
+    Candle::new(open, high, low, close, 10.0, minute(at))
+}
+
+#[test]
+fn averages_each_candle_with_the_previous_heikin_ashi_candle() {
+    let candles = vec![
+        candle(100.0, 110.0, 90.0, 104.0, 0),
+        candle(104.0, 112.0, 100.0, 108.0, 1),
+    ];
+
+    let ha = transform_to_heikin_ashi(&candles);
+
+    assert_eq!(ha.len(), 2);
+    // The first candle seeds its open from its own open and close
+    assert_eq!(ha[0].open, 102.0);
+    assert_eq!(ha[0].close, 101.0);
+    assert_eq!(ha[0].high, 110.0);
+    assert_eq!(ha[0].low, 90.0);
+    assert_eq!(ha[1].open, 101.5);
+    assert_eq!(ha[1].close, 106.0);
+    assert_eq!(ha[1].high, 112.0);
+    assert_eq!(ha[1].low, 100.0);
+    // Volume and timestamps carry over
+    assert_eq!(ha[1].volume, 10.0);
+    assert_eq!(ha[1].timestamp, minute(1));
+}
+
+#[test]
+fn open_interest_and_funding_rate_are_kept_unless_disabled() {
+    let candles = vec![candle(100.0, 101.0, 99.0, 100.5, 0)
+        .with_open_interest(1_000.0)
+        .with_funding_rate(0.0001)];
+
+    let kept = transform_to_heikin_ashi(&candles);
+    assert_eq!(kept[0].open_interest, Some(1_000.0));
+    assert_eq!(kept[0].funding_rate, Some(0.0001));
+
+    let cleared = transform_to_heikin_ashi_with(&candles, false);
+    assert_eq!(cleared[0].open_interest, None);
+    assert_eq!(cleared[0].funding_rate, None);
+
+    assert!(transform_to_heikin_ashi(&[]).is_empty());
+}
+
+/// Total bar-to-bar movement of an RSI over `candles`
+fn rsi_path_length(candles: &[Candle]) -> f64 {
+    let mut rsi = RSI::new(14);
+    let readings: Vec<f64> = candles.iter().filter_map(|c| rsi.update(c.close)).collect();
+    readings.windows(2).map(|w| (w[1] - w[0]).abs()).sum()
+}
+
+#[test]
+fn heikin_ashi_smooths_rsi_on_a_choppy_trend() {
+    // Uptrend that gains 3 then gives back 2 every other bar
+    let mut price = 100.0;
+    let candles: Vec<Candle> = (0..120)
+        .map(|i| {
+            let open = price;
+            price += if i % 2 == 0 { 3.0 } else { -2.0 };
+            candle(open, open.max(price) + 0.5, open.min(price) - 0.5, price, i)
+        })
+        .collect();
+
+    let raw = rsi_path_length(&candles);
+    let smoothed = rsi_path_length(&transform_to_heikin_ashi(&candles));
+    assert!(
+        smoothed < raw / 2.0,
+        "Heikin-Ashi RSI moved {:.1} vs {:.1} on raw candles",
+        smoothed,
+        raw
+    );
+}
+
+/// Chronological candles with arbitrary, internally consistent OHLC
+fn random_series() -> impl Strategy<Value = Vec<Candle>> {
+    prop::collection::vec(
+        (1.0f64..1_000.0, 1.0f64..1_000.0, 0.0f64..50.0, 0.0f64..50.0),
+        0..100,
+    )
+    .prop_map(|rows| {
+        rows.into_iter()
+            .enumerate()
+            .map(|(i, (open, close, wick_up, wick_down))| {
+                let high = open.max(close) + wick_up;
+                let low = open.min(close) - wick_down;
+                candle(open, high, low, close, i as i64)
+            })
+            .collect()
+    })
+}
+
+proptest! {
+    #[test]
+    fn heikin_ashi_wicks_contain_the_body(candles in random_series()) {
+        let ha = transform_to_heikin_ashi(&candles);
+        prop_assert_eq!(ha.len(), candles.len());
+        for (ha, raw) in ha.iter().zip(&candles) {
+            prop_assert!(ha.low <= ha.open.min(ha.close));
+            prop_assert!(ha.high >= ha.open.max(ha.close));
+            prop_assert!(ha.low <= raw.low && ha.high >= raw.high);
+        }
+    }
+}
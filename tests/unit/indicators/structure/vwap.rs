@@ -0,0 +1,82 @@
+//! Unit tests for the intraday VWAP indicator.
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use perptrix::indicators::structure::vwap::{VwapSignal, VWAP};
+
+fn at(hour: u32, minute: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 3, 1, hour, minute, 0).unwrap()
+}
+
+#[test]
+fn vwap_weights_closes_by_volume() {
+    let mut vwap = VWAP::new();
+    assert_eq!(vwap.value(), None);
+
+    vwap.update(at(9, 0), 100.0, 1.0);
+    let (value, signal) = vwap.update(at(9, 1), 110.0, 3.0).expect("vwap");
+    assert!((value - 107.5).abs() < 1e-9);
+    assert_eq!(signal, VwapSignal::PriceAbove);
+
+    let (_, signal) = vwap.update(at(9, 2), 104.0, 0.5).expect("vwap");
+    assert_eq!(signal, VwapSignal::Crossing);
+    let (_, signal) = vwap.update(at(9, 3), 103.0, 0.5).expect("vwap");
+    assert_eq!(signal, VwapSignal::PriceBelow);
+}
+
+#[test]
+fn vwap_resets_at_utc_midnight() {
+    let mut vwap = VWAP::new();
+    vwap.update(at(23, 58), 100.0, 10.0);
+    let (before, _) = vwap.update(at(23, 59), 200.0, 10.0).expect("vwap");
+    assert!((before - 150.0).abs() < 1e-9);
+
+    let midnight = at(0, 0) + Duration::days(1);
+    let (after, signal) = vwap.update(midnight, 300.0, 1.0).expect("vwap");
+    assert_eq!(after, 300.0);
+    assert_eq!(signal, VwapSignal::PriceAbove);
+    assert_eq!(vwap.session(), Some(midnight.date_naive()));
+
+    // A zero-volume first candle leaves the new session without a VWAP
+    let next_day = midnight + Duration::days(1);
+    assert_eq!(vwap.update(next_day, 300.0, 0.0), None);
+    assert_eq!(vwap.value(), None);
+}
+
+#[test]
+fn vwap_state_is_consistent_across_feeds() {
+    let candles: Vec<(DateTime<Utc>, f64, f64)> = (0..120)
+        .map(|i| {
+            let timestamp = at(22, 0) + Duration::minutes(i * 3);
+            let close = 100.0 + (i as f64 * 0.7).sin() * 5.0;
+            (timestamp, close, 1.0 + (i % 7) as f64)
+        })
+        .collect();
+
+    let mut single = VWAP::new();
+    let single_results: Vec<_> = candles
+        .iter()
+        .map(|&(ts, close, volume)| single.update(ts, close, volume))
+        .collect();
+
+    // Feed the same candles in chunks, carrying the state over between them
+    let mut chunked = VWAP::new();
+    let mut chunked_results = Vec::new();
+    for chunk in candles.chunks(17) {
+        let mut carried = chunked.clone();
+        for &(ts, close, volume) in chunk {
+            chunked_results.push(carried.update(ts, close, volume));
+        }
+        chunked = carried;
+    }
+    assert_eq!(single_results, chunked_results);
+
+    // The final value only reflects candles from the last session
+    let last_day = candles.last().unwrap().0.date_naive();
+    let (pv, v) = candles
+        .iter()
+        .filter(|(ts, _, _)| ts.date_naive() == last_day)
+        .fold((0.0, 0.0), |(pv, v), &(_, close, volume)| {
+            (pv + close * volume, v + volume)
+        });
+    assert!((single.value().unwrap() - pv / v).abs() < 1e-9);
+}
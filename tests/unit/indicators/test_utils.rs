@@ -0,0 +1,11 @@
+//! Shared helpers for the indicator unit tests.
+
+use proptest::prelude::*;
+
+/// A random `(high, low, close)` candle with the close inside its range
+pub fn candle() -> impl Strategy<Value = (f64, f64, f64)> {
+    (1.0..100_000.0f64, 0.0..1.0f64, 0.0..1.0f64).prop_map(|(low, spread, position)| {
+        let high = low * (1.0 + spread);
+        (high, low, low + (high - low) * position)
+    })
+}
@@ -0,0 +1,65 @@
+//! Unit tests for the ADX trend strength indicator.
+
+use perptrix::indicators::trend::adx::{ADX, ADX_DEFAULT_PERIOD};
+
+fn feed(
+    adx: &mut ADX,
+    candles: impl IntoIterator<Item = (f64, f64, f64)>,
+) -> Option<(f64, f64, f64)> {
+    candles
+        .into_iter()
+        .map(|(high, low, close)| adx.update(high, low, close))
+        .last()
+        .flatten()
+}
+
+#[test]
+fn first_reading_after_two_periods() {
+    let mut adx = ADX::default();
+    for i in 0..2 * ADX_DEFAULT_PERIOD - 1 {
+        let price = 100.0 + i as f64;
+        assert!(adx.update(price + 0.5, price - 0.5, price).is_none());
+    }
+    assert!(adx.update(200.0, 199.0, 199.5).is_some());
+}
+
+#[test]
+fn steady_trend_reads_strong_in_its_direction() {
+    let mut adx = ADX::default();
+    let uptrend = (0..60).map(|i| {
+        let price = 100.0 + i as f64;
+        (price + 0.5, price - 0.5, price)
+    });
+    let (value, plus_di, minus_di) = feed(&mut adx, uptrend).unwrap();
+    assert!(value > 90.0, "ADX {}", value);
+    assert!(plus_di > minus_di);
+
+    adx.reset();
+    assert!(adx.value().is_none());
+    let downtrend = (0..60).map(|i| {
+        let price = 200.0 - i as f64;
+        (price + 0.5, price - 0.5, price)
+    });
+    let (value, plus_di, minus_di) = feed(&mut adx, downtrend).unwrap();
+    assert!(value > 90.0, "ADX {}", value);
+    assert!(minus_di > plus_di);
+}
+
+#[test]
+fn choppy_market_reads_weak() {
+    let mut adx = ADX::default();
+    let choppy = (0..100).map(|i| {
+        let price = 100.0 + if i % 2 == 0 { 1.0 } else { -1.0 };
+        (price + 0.5, price - 0.5, price)
+    });
+    let (value, _, _) = feed(&mut adx, choppy).unwrap();
+    assert!(value < 20.0, "ADX {}", value);
+}
+
+#[test]
+fn flat_market_reads_zero() {
+    let mut adx = ADX::new(5);
+    let (value, plus_di, minus_di) =
+        feed(&mut adx, (0..20).map(|_| (100.0, 100.0, 100.0))).unwrap();
+    assert_eq!((value, plus_di, minus_di), (0.0, 0.0, 0.0));
+}
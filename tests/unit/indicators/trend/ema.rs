@@ -1,9 +1,13 @@
 //! Unit tests for EMA trackers and crossover signals.
 
 use chrono::Utc;
+use perptrix::common::math;
 use perptrix::indicators::trend::calculate_ema;
-use perptrix::indicators::trend::ema::{EMACrossover, EMATrendSignal, EMA};
+use perptrix::indicators::trend::ema::{
+    EMACrossover, EMATrendSignal, EmaRibbon, RibbonSignal, EMA, EMA_RIBBON_PERIODS,
+};
 use perptrix::models::indicators::Candle;
+use proptest::prelude::*;
 
 fn build_candle(price: f64) -> Candle {
     Candle::new(price, price + 0.5, price - 0.5, price, 1000.0, Utc::now())
@@ -12,28 +16,83 @@ fn build_candle(price: f64) -> Candle {
 #[test]
 fn ema_updates_smoothly() {
     let mut ema = EMA::new(5);
-    let prices = vec![100.0, 101.0, 102.0, 103.0, 104.0];
-    let mut last = 0.0;
+    let prices = vec![100.0, 101.0, 102.0, 103.0, 104.0, 105.0];
+    let mut last = None;
     for price in prices {
         last = ema.update(price);
     }
-    assert!(last > 100.0);
+    assert!(last.unwrap() > 100.0);
     assert!(ema.get().is_some());
 }
 
+#[test]
+fn ema_seeds_with_sma_of_first_period() {
+    let mut ema = EMA::new(3);
+    assert_eq!(ema.update(1.0), None);
+    assert_eq!(ema.update(2.0), None);
+    assert!(!ema.is_ready());
+    assert_eq!(ema.update(3.0), Some(2.0));
+    assert_eq!(ema.count(), 3);
+
+    // multiplier = 2 / (3 + 1) = 0.5
+    assert_eq!(ema.update(6.0), Some(4.0));
+}
+
+#[test]
+fn streaming_ema_matches_math_ema() {
+    let prices: Vec<f64> = (0..40)
+        .map(|i| 100.0 + (i as f64 * 0.7).sin() * 5.0)
+        .collect();
+    let mut ema = EMA::new(10);
+    for &price in &prices {
+        ema.update(price);
+    }
+    let expected = math::ema(&prices, 10).unwrap();
+    assert!((ema.get().unwrap() - expected).abs() < 1e-9);
+}
+
+#[test]
+fn ema_reset_clears_seed() {
+    let mut ema = EMA::new(2);
+    ema.update(10.0);
+    ema.update(20.0);
+    ema.reset();
+    assert_eq!(ema.get(), None);
+    assert_eq!(ema.count(), 0);
+    assert_eq!(ema.update(5.0), None);
+    assert_eq!(ema.update(7.0), Some(6.0));
+}
+
+#[test]
+fn crossover_exposes_component_emas() {
+    let mut crossover = EMACrossover::new(2, 4);
+    for price in [10.0, 11.0, 12.0, 13.0] {
+        crossover.update(price);
+    }
+    assert_eq!(crossover.fast_ema().period(), 2);
+    assert_eq!(crossover.slow_ema().period(), 4);
+    assert_eq!(crossover.fast_ema().get(), crossover.fast());
+    assert_eq!(crossover.slow_ema().get(), Some(11.5));
+}
+
 #[test]
 fn ema_crossover_detects_signals() {
     let mut crossover = EMACrossover::new(3, 6);
     let mut bullish_seen = false;
     let mut bearish_seen = false;
 
-    for price in [100.0, 101.0, 102.0, 103.0, 104.0, 105.0] {
+    // Seed both EMAs on a decline so the fast EMA starts below the slow one
+    for price in [110.0, 109.0, 108.0, 107.0, 106.0, 105.0, 104.0] {
+        crossover.update(price);
+    }
+
+    for price in [105.0, 107.0, 109.0, 111.0, 113.0, 115.0] {
         if crossover.update(price) == EMATrendSignal::BullishCross {
             bullish_seen = true;
         }
     }
 
-    for price in [105.0, 104.0, 103.0, 102.0, 101.0, 100.0] {
+    for price in [113.0, 111.0, 109.0, 107.0, 105.0, 103.0] {
         if crossover.update(price) == EMATrendSignal::BearishCross {
             bearish_seen = true;
         }
@@ -49,6 +108,116 @@ fn ema_crossover_detects_signals() {
     );
 }
 
+#[test]
+fn confirmed_cross_is_reported_after_holding() {
+    let prices = [
+        110.0, 109.0, 108.0, 107.0, 106.0, 105.0, 104.0, 105.0, 107.0, 109.0, 111.0, 113.0, 115.0,
+    ];
+    let mut plain = EMACrossover::new(3, 6);
+    let mut confirmed = EMACrossover::new(3, 6).with_confirmation_candles(2);
+    let signals: Vec<_> = prices
+        .iter()
+        .map(|&price| (plain.update(price), confirmed.update(price)))
+        .collect();
+
+    let cross = signals
+        .iter()
+        .position(|(plain, _)| *plain == EMATrendSignal::BullishCross)
+        .expect("a bullish cross in the uptrend");
+    assert_eq!(signals[cross].1, EMATrendSignal::PendingBullishCross);
+    assert_eq!(signals[cross + 1].1, EMATrendSignal::PendingBullishCross);
+    assert_eq!(signals[cross + 2].1, EMATrendSignal::BullishCross);
+    assert!(signals[cross + 3..]
+        .iter()
+        .all(|(_, signal)| *signal != EMATrendSignal::BullishCross));
+}
+
+#[test]
+fn crossing_back_cancels_a_pending_cross() {
+    // The fast EMA is the price itself; the slow EMA seeds at 10
+    let mut crossover = EMACrossover::new(1, 2).with_confirmation_candles(2);
+    assert_eq!(crossover.update(10.0), EMATrendSignal::Neutral);
+    assert_eq!(crossover.update(10.0), EMATrendSignal::Neutral);
+    assert_eq!(crossover.update(12.0), EMATrendSignal::PendingBullishCross);
+    assert_eq!(crossover.update(9.0), EMATrendSignal::PendingBearishCross);
+    assert_eq!(crossover.update(9.0), EMATrendSignal::PendingBearishCross);
+    assert_eq!(crossover.update(9.0), EMATrendSignal::BearishCross);
+    assert_ne!(crossover.update(9.0), EMATrendSignal::BearishCross);
+}
+
+/// Signals from an unconfirmed and a confirmed crossover over the same
+/// prices, with whether the fast EMA was above the slow one
+fn crossover_runs(
+    prices: &[f64],
+    confirmation_candles: u32,
+) -> Vec<(EMATrendSignal, EMATrendSignal, Option<bool>)> {
+    let mut plain = EMACrossover::new(2, 5);
+    let mut confirmed = EMACrossover::new(2, 5).with_confirmation_candles(confirmation_candles);
+    prices
+        .iter()
+        .map(|&price| {
+            let signals = (plain.update(price), confirmed.update(price));
+            let above = plain
+                .fast()
+                .zip(plain.slow())
+                .map(|(fast, slow)| fast > slow);
+            (signals.0, signals.1, above)
+        })
+        .collect()
+}
+
+fn is_cross(signal: EMATrendSignal) -> bool {
+    matches!(
+        signal,
+        EMATrendSignal::BullishCross | EMATrendSignal::BearishCross
+    )
+}
+
+proptest! {
+    #[test]
+    fn crossovers_are_pending_on_the_cross_candle(
+        prices in prop::collection::vec(50.0f64..150.0, 0..200),
+        confirmation_candles in 1u32..5,
+    ) {
+        for (plain, confirmed, _) in crossover_runs(&prices, confirmation_candles) {
+            match plain {
+                EMATrendSignal::BullishCross => {
+                    prop_assert_eq!(confirmed, EMATrendSignal::PendingBullishCross)
+                }
+                EMATrendSignal::BearishCross => {
+                    prop_assert_eq!(confirmed, EMATrendSignal::PendingBearishCross)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    #[test]
+    fn confirmed_crosses_held_without_reversal(
+        prices in prop::collection::vec(50.0f64..150.0, 0..200),
+        confirmation_candles in 1u32..5,
+    ) {
+        let runs = crossover_runs(&prices, confirmation_candles);
+        let n = confirmation_candles as usize;
+        for (i, &(plain, confirmed, _)) in runs.iter().enumerate() {
+            // Each cross is confirmed exactly when it has held for n more candles
+            let held_since = i.checked_sub(n).filter(|&start| {
+                is_cross(runs[start].0)
+                    && runs[start + 1..=i].iter().all(|r| !is_cross(r.0))
+                    && runs[start..=i].iter().all(|r| r.2 == runs[start].2)
+            });
+            match held_since {
+                Some(start) => prop_assert_eq!(confirmed, runs[start].0),
+                None => prop_assert!(!is_cross(confirmed)),
+            }
+            // Away from crosses both trackers agree
+            if !is_cross(plain) && runs[i.saturating_sub(n)..i].iter().all(|r| !is_cross(r.0)) {
+                prop_assert_eq!(confirmed, plain);
+            }
+        }
+    }
+}
+
 #[test]
 fn legacy_calculate_ema_still_works() {
     let candles: Vec<_> = (0..30).map(|i| build_candle(100.0 + i as f64)).collect();
@@ -56,3 +225,83 @@ fn legacy_calculate_ema_still_works() {
     assert_eq!(ema_indicator.period, 12);
     assert!(ema_indicator.value.is_finite());
 }
+
+#[test]
+fn crossover_reset_matches_fresh_instance() {
+    let mut reused = EMACrossover::new(3, 6);
+    for price in [120.0, 118.0, 115.0, 111.0, 108.0] {
+        reused.update(price);
+    }
+    reused.reset();
+    assert_eq!(reused.fast(), None);
+    assert_eq!(reused.slow(), None);
+
+    let mut fresh = EMACrossover::new(3, 6);
+    for price in [100.0, 101.0, 102.5, 104.0, 103.0] {
+        assert_eq!(reused.update(price), fresh.update(price));
+    }
+    assert_eq!(reused.fast(), fresh.fast());
+    assert_eq!(reused.slow(), fresh.slow());
+}
+
+#[test]
+fn ribbon_scores_partial_alignment() {
+    let mut ribbon = EmaRibbon::new(&[3, 1, 2]);
+    assert!(ribbon.update(10.0).is_none());
+    assert!(ribbon.update(20.0).is_none());
+
+    // EMAs 30 > 25 > 20: fully stacked
+    let (score, signal) = ribbon.update(30.0).unwrap();
+    assert_eq!(score, 1.0);
+    assert_eq!(signal, RibbonSignal::StrongBull);
+    assert!((ribbon.width().unwrap() - 10.0 / 30.0).abs() < 1e-12);
+
+    // EMAs 22 < 23 > 21: one pair each way, and the spread narrowed
+    let (score, signal) = ribbon.update(22.0).unwrap();
+    assert_eq!(score, 0.0);
+    assert_eq!(signal, RibbonSignal::Contracting);
+    let values: Vec<f64> = ribbon.values().into_iter().map(Option::unwrap).collect();
+    for (value, expected) in values.iter().zip([22.0, 23.0, 21.0]) {
+        assert!((value - expected).abs() < 1e-9);
+    }
+
+    ribbon.reset();
+    assert_eq!(ribbon.score(), None);
+    assert!(ribbon.update(10.0).is_none());
+}
+
+#[test]
+fn ribbon_tracks_a_trend_reversal() {
+    let prices: Vec<f64> = (0..300)
+        .map(|i| {
+            if i < 150 {
+                100.0 + i as f64 * 0.5
+            } else {
+                175.0 - (i - 150) as f64 * 0.5
+            }
+        })
+        .collect();
+
+    let mut ribbon = EmaRibbon::default();
+    let readings: Vec<_> = prices.iter().map(|&price| ribbon.update(price)).collect();
+    let first = readings.iter().position(Option::is_some).unwrap();
+    assert_eq!(first, EMA_RIBBON_PERIODS[5] - 1);
+
+    // Stacked up, unwinds as the fast EMAs roll over, fans out the other way
+    let mut phases: Vec<RibbonSignal> = Vec::new();
+    for (score, signal) in readings.into_iter().flatten() {
+        assert!((-1.0..=1.0).contains(&score));
+        if phases.last() != Some(&signal) {
+            phases.push(signal);
+        }
+    }
+    assert_eq!(
+        phases,
+        vec![
+            RibbonSignal::StrongBull,
+            RibbonSignal::Contracting,
+            RibbonSignal::Expanding,
+            RibbonSignal::StrongBear,
+        ]
+    );
+}
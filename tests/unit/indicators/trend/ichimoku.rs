@@ -0,0 +1,104 @@
+//! Unit tests for the Ichimoku Cloud indicator.
+
+use perptrix::indicators::trend::ichimoku::{
+    Ichimoku, IchimokuIndicator, IchimokuSignal, DISPLACEMENT, SENKOU_B_PERIOD,
+};
+
+/// Closes falling for `down` bars, then rising for `up` bars
+fn v_shaped_closes(down: usize, up: usize) -> Vec<f64> {
+    let bottom = 200.0 - down as f64;
+    (0..down)
+        .map(|i| 200.0 - i as f64)
+        .chain((0..up).map(|i| bottom + i as f64 * 2.0))
+        .collect()
+}
+
+fn feed(ichimoku: &mut Ichimoku, closes: &[f64]) -> Vec<Option<IchimokuIndicator>> {
+    closes
+        .iter()
+        .map(|&close| ichimoku.update(close + 1.0, close - 1.0, close))
+        .collect()
+}
+
+#[test]
+fn ichimoku_needs_senkou_b_lookback() {
+    let mut ichimoku = Ichimoku::default();
+    let readings = feed(&mut ichimoku, &v_shaped_closes(SENKOU_B_PERIOD, 0));
+
+    assert!(readings[..SENKOU_B_PERIOD - 1].iter().all(Option::is_none));
+    let first = readings[SENKOU_B_PERIOD - 1].expect("first reading");
+    assert_eq!(first.cloud_span_a, None);
+    assert_eq!(first.signal, IchimokuSignal::Neutral);
+
+    // Tenkan is the 9-bar midpoint, Chikou the current close
+    let close = 200.0 - (SENKOU_B_PERIOD - 1) as f64;
+    assert!((first.tenkan_sen - (close + 4.0)).abs() < 1e-9);
+    assert_eq!(first.chikou_span, close);
+}
+
+#[test]
+fn senkou_spans_are_projected_26_bars_forward() {
+    let mut ichimoku = Ichimoku::default();
+    let readings: Vec<IchimokuIndicator> = feed(&mut ichimoku, &v_shaped_closes(60, 60))
+        .into_iter()
+        .flatten()
+        .collect();
+
+    for (i, reading) in readings.iter().enumerate() {
+        if i < DISPLACEMENT {
+            assert_eq!(reading.cloud_span_a, None);
+            continue;
+        }
+        let projected = readings[i - DISPLACEMENT];
+        assert_eq!(reading.cloud_span_a, Some(projected.senkou_span_a));
+        assert_eq!(reading.cloud_span_b, Some(projected.senkou_span_b));
+    }
+    let projected = readings[readings.len() - 1 - DISPLACEMENT];
+    assert_eq!(
+        ichimoku.cloud(),
+        Some((projected.senkou_span_a, projected.senkou_span_b))
+    );
+}
+
+#[test]
+fn cloud_twist_detected_when_trend_reverses() {
+    let mut ichimoku = Ichimoku::default();
+    let readings: Vec<IchimokuIndicator> = feed(&mut ichimoku, &v_shaped_closes(80, 80))
+        .into_iter()
+        .flatten()
+        .collect();
+
+    // The downtrend leaves Span A below Span B and price below the cloud
+    assert!(readings[..20]
+        .iter()
+        .all(|r| r.senkou_span_a < r.senkou_span_b));
+    assert!(readings
+        .iter()
+        .any(|r| r.signal == IchimokuSignal::PriceBelowCloud));
+
+    let twists: Vec<usize> = readings
+        .iter()
+        .enumerate()
+        .filter(|(_, r)| r.signal == IchimokuSignal::CloudTwist)
+        .map(|(i, _)| i)
+        .collect();
+    assert_eq!(twists.len(), 1);
+    let twist = twists[0];
+    assert!(readings[twist - 1].senkou_span_a <= readings[twist - 1].senkou_span_b);
+    assert!(readings[twist].senkou_span_a > readings[twist].senkou_span_b);
+
+    // Tenkan turns above Kijun before the cloud flips, and price ends above the cloud
+    let tk_cross = readings
+        .iter()
+        .position(|r| r.signal == IchimokuSignal::TKCross)
+        .expect("tk cross");
+    assert!(tk_cross < twist);
+    assert_eq!(
+        readings.last().unwrap().signal,
+        IchimokuSignal::PriceAboveCloud
+    );
+
+    ichimoku.reset();
+    assert_eq!(ichimoku.cloud(), None);
+    assert_eq!(ichimoku.update(1.0, 0.0, 0.5), None);
+}
@@ -0,0 +1,115 @@
+//! Unit tests for Kaufman's adaptive moving average.
+
+use perptrix::indicators::trend::ema::EMA;
+use perptrix::indicators::trend::kama::{KamaSignal, KAMA, KAMA_DEFAULT_PERIOD};
+
+const EPS: f64 = 1e-9;
+
+/// Prices wandering within ±2 of 100 with no trend, from a fixed-seed LCG
+fn sideways_prices(len: usize) -> Vec<f64> {
+    let mut seed: u64 = 12345;
+    (0..len)
+        .map(|_| {
+            seed = (seed * 1_103_515_245 + 12_345) % (1 << 31);
+            100.0 + (seed as f64 / (1u64 << 31) as f64 - 0.5) * 4.0
+        })
+        .collect()
+}
+
+/// Sum of the absolute changes between consecutive readings
+fn total_movement(readings: &[f64]) -> f64 {
+    readings.windows(2).map(|w| (w[1] - w[0]).abs()).sum()
+}
+
+#[test]
+fn kama_starts_after_period_plus_one_prices() {
+    let mut kama = KAMA::default();
+    for price in 1..=KAMA_DEFAULT_PERIOD {
+        assert_eq!(kama.update(price as f64), None);
+    }
+
+    // Seeded at the average of 1..=10 (5.5); a straight line has an
+    // efficiency ratio of 1, so the fast constant (2/3)² applies
+    let (value, signal) = kama.update(11.0).expect("first reading");
+    let expected = 5.5 + (2.0_f64 / 3.0).powi(2) * (11.0 - 5.5);
+    assert!((value - expected).abs() < EPS, "{}", value);
+    assert_eq!(signal, KamaSignal::Trending);
+    assert_eq!(kama.efficiency_ratio(), Some(1.0));
+}
+
+#[test]
+fn kama_is_smoother_than_ema_in_sideways_markets() {
+    let prices = sideways_prices(200);
+    let mut kama = KAMA::default();
+    let mut ema = EMA::new(KAMA_DEFAULT_PERIOD);
+    let (mut kama_readings, mut ema_readings) = (Vec::new(), Vec::new());
+    for &price in &prices {
+        let kama_value = kama.update(price);
+        let ema_value = ema.update(price);
+        // Compare only once both have a reading
+        if let (Some((kama_value, _)), Some(ema_value)) = (kama_value, ema_value) {
+            kama_readings.push(kama_value);
+            ema_readings.push(ema_value);
+        }
+    }
+    assert_eq!(kama_readings.len(), prices.len() - KAMA_DEFAULT_PERIOD);
+
+    let (kama_movement, ema_movement) = (
+        total_movement(&kama_readings),
+        total_movement(&ema_readings),
+    );
+    assert!(
+        kama_movement < ema_movement,
+        "KAMA moved {} vs EMA {}",
+        kama_movement,
+        ema_movement
+    );
+
+    let deviation = |readings: &[f64]| readings.iter().map(|r| (r - 100.0).abs()).sum::<f64>();
+    assert!(deviation(&kama_readings) < deviation(&ema_readings));
+}
+
+#[test]
+fn kama_is_flat_without_a_trend() {
+    let mut kama = KAMA::default();
+    let mut last = None;
+    for price in sideways_prices(100) {
+        last = kama.update(price).or(last);
+    }
+    assert!(kama.efficiency_ratio().unwrap() <= 0.6);
+    assert_ne!(last.map(|(_, signal)| signal), Some(KamaSignal::Trending));
+}
+
+#[test]
+fn price_crossing_kama_is_a_break() {
+    let mut kama = KAMA::default();
+    for _ in 0..20 {
+        let reading = kama.update(100.0);
+        // No movement at all: an efficiency ratio of 0
+        if let Some((value, signal)) = reading {
+            assert!((value - 100.0).abs() < EPS);
+            assert_eq!(signal, KamaSignal::Flat);
+        }
+    }
+
+    let (value, signal) = kama.update(105.0).unwrap();
+    assert!(value > 100.0 && value < 105.0);
+    assert_eq!(signal, KamaSignal::BullishBreak);
+
+    let (_, signal) = kama.update(90.0).unwrap();
+    assert_eq!(signal, KamaSignal::BearishBreak);
+}
+
+#[test]
+fn reset_clears_history() {
+    let mut kama = KAMA::default();
+    for price in sideways_prices(30) {
+        kama.update(price);
+    }
+    assert!(kama.value().is_some());
+
+    kama.reset();
+    assert_eq!(kama.value(), None);
+    assert_eq!(kama.efficiency_ratio(), None);
+    assert_eq!(kama.update(100.0), None);
+}
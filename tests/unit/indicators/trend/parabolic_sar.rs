@@ -0,0 +1,221 @@
+//! Unit tests for the Parabolic SAR indicator.
+
+use perptrix::indicators::trend::parabolic_sar::{ParabolicSar, ParabolicSarSignal};
+
+const EPS: f64 = 1e-9;
+
+fn assert_close(actual: f64, expected: f64, context: &str) {
+    assert!(
+        (actual - expected).abs() < EPS,
+        "{}: expected {}, got {}",
+        context,
+        expected,
+        actual
+    );
+}
+
+/// Textbook Wilder SAR over a whole series, written independently of the
+/// streaming implementation. Returns `(sar, af, flipped)` from the second candle.
+fn reference_sar(candles: &[(f64, f64, f64)]) -> Vec<(f64, f64, bool)> {
+    let (initial, step, max) = (0.02, 0.02, 0.2);
+    let mut out = Vec::new();
+    if candles.len() < 2 {
+        return out;
+    }
+
+    let (h0, l0, c0) = candles[0];
+    let (h1, l1, c1) = candles[1];
+    let mut long = c1 >= c0;
+    let mut sar = if long { l0.min(l1) } else { h0.max(h1) };
+    let mut ep = if long { h0.max(h1) } else { l0.min(l1) };
+    let mut af = initial;
+    out.push((sar, af, false));
+
+    for t in 2..candles.len() {
+        let (high, low, _) = candles[t];
+        let (h1, l1, _) = candles[t - 1];
+        let (h2, l2, _) = candles[t - 2];
+
+        sar += af * (ep - sar);
+        sar = if long {
+            sar.min(l1).min(l2)
+        } else {
+            sar.max(h1).max(h2)
+        };
+
+        let flipped = if long { low < sar } else { high > sar };
+        if flipped {
+            sar = if long {
+                ep.max(high).max(h1)
+            } else {
+                ep.min(low).min(l1)
+            };
+            long = !long;
+            ep = if long { high } else { low };
+            af = initial;
+        } else if long && high > ep {
+            ep = high;
+            af = f64::min(af + step, max);
+        } else if !long && low < ep {
+            ep = low;
+            af = f64::min(af + step, max);
+        }
+        out.push((sar, af, flipped));
+    }
+    out
+}
+
+/// 30 candles up then 30 down, one point per candle, two points wide
+fn rise_then_fall() -> Vec<(f64, f64, f64)> {
+    (0..60)
+        .map(|i| {
+            let base = if i < 30 {
+                100.0 + i as f64
+            } else {
+                159.0 - i as f64
+            };
+            (base + 1.0, base - 1.0, base)
+        })
+        .collect()
+}
+
+/// Choppy series that reverses several times
+fn zigzag(len: usize) -> Vec<(f64, f64, f64)> {
+    (0..len)
+        .map(|i| {
+            let base = 100.0 + (i as f64 * 0.45).sin() * 8.0 + (i as f64 * 0.13).cos() * 3.0;
+            (base + 0.8, base - 0.8, base + 0.2)
+        })
+        .collect()
+}
+
+fn run(candles: &[(f64, f64, f64)]) -> Vec<(f64, f64, ParabolicSarSignal)> {
+    let mut sar = ParabolicSar::default();
+    candles
+        .iter()
+        .filter_map(|&(high, low, close)| sar.update(high, low, close))
+        .collect()
+}
+
+#[test]
+fn parabolic_sar_matches_hand_computed_values() {
+    let candles = [
+        (10.0, 9.0, 9.5),
+        (11.0, 10.0, 10.5),
+        (12.0, 11.0, 11.5),
+        (13.0, 12.0, 12.5),
+        (14.0, 13.0, 13.5),
+        (13.5, 12.0, 12.5),
+        (10.0, 8.0, 8.5),
+        (9.0, 7.0, 7.5),
+        (8.0, 6.5, 7.0),
+    ];
+    // Worked by hand:
+    //  2: uptrend seeded, SAR = min(9, 10), EP = 11
+    //  3: 9 + 0.02 * (11 - 9) = 9.04, capped at candle 1's low of 9; EP 12, AF 0.04
+    //  4: 9 + 0.04 * (12 - 9) = 9.12; EP 13, AF 0.06
+    //  5: 9.12 + 0.06 * (13 - 9.12) = 9.3528; EP 14, AF 0.08
+    //  6: 9.3528 + 0.08 * (14 - 9.3528) = 9.724576; no new high
+    //  7: 10.06660992, low 8 pierces it: SAR jumps to EP 14, EP 8, AF 0.02
+    //  8: 14 + 0.02 * (8 - 14) = 13.88; EP 7, AF 0.04
+    //  9: 13.88 + 0.04 * (7 - 13.88) = 13.6048; EP 6.5, AF 0.06
+    let expected = [
+        (9.0, 0.02, ParabolicSarSignal::Bullish),
+        (9.0, 0.04, ParabolicSarSignal::Bullish),
+        (9.12, 0.06, ParabolicSarSignal::Bullish),
+        (9.3528, 0.08, ParabolicSarSignal::Bullish),
+        (9.724576, 0.08, ParabolicSarSignal::Bullish),
+        (14.0, 0.02, ParabolicSarSignal::Flip),
+        (13.88, 0.04, ParabolicSarSignal::Bearish),
+        (13.6048, 0.06, ParabolicSarSignal::Bearish),
+    ];
+
+    let mut sar = ParabolicSar::default();
+    assert!(sar.update(10.0, 9.0, 9.5).is_none());
+    for (i, (&(high, low, close), &(exp_sar, exp_af, exp_signal))) in
+        candles[1..].iter().zip(expected.iter()).enumerate()
+    {
+        let (value, af, signal) = sar.update(high, low, close).unwrap();
+        let context = format!("candle {}", i + 2);
+        assert_close(value, exp_sar, &context);
+        assert_close(af, exp_af, &context);
+        assert_eq!(signal, exp_signal, "{}", context);
+    }
+    assert_eq!(sar.is_uptrend(), Some(false));
+    assert_eq!(sar.extreme_point(), Some(6.5));
+}
+
+#[test]
+fn parabolic_sar_flip_resets_af_without_double_increment() {
+    let output = run(&rise_then_fall());
+    assert_eq!(output.len(), 59);
+
+    // AF steps by 0.02 per new high and caps at 0.2
+    assert_close(output[0].1, 0.02, "seed");
+    assert_close(output[9].1, 0.2, "cap reached");
+    assert_close(output[27].1, 0.2, "cap held");
+    assert!(output[..29]
+        .iter()
+        .all(|&(_, _, signal)| signal == ParabolicSarSignal::Bullish));
+
+    // The top is candle 29 (high 130); the first lower low that crosses the
+    // trailing SAR flips the trend and puts the SAR at that high
+    let flips: Vec<usize> = output
+        .iter()
+        .enumerate()
+        .filter(|(_, &(_, _, signal))| signal == ParabolicSarSignal::Flip)
+        .map(|(i, _)| i)
+        .collect();
+    assert_eq!(flips.len(), 1);
+    let flip = flips[0];
+    assert_close(output[flip].0, 130.0, "SAR after flip");
+    assert_close(output[flip].1, 0.02, "AF on flip candle");
+    assert_close(output[flip + 1].1, 0.04, "AF after first new low");
+    assert!(output[flip + 1..]
+        .iter()
+        .all(|&(_, _, signal)| signal == ParabolicSarSignal::Bearish));
+}
+
+#[test]
+fn parabolic_sar_matches_reference_across_flips() {
+    let candles = zigzag(200);
+    let output = run(&candles);
+    let reference = reference_sar(&candles);
+    assert_eq!(output.len(), reference.len());
+
+    let mut flips = 0;
+    for (i, (&(sar, af, signal), &(ref_sar, ref_af, ref_flip))) in
+        output.iter().zip(reference.iter()).enumerate()
+    {
+        let context = format!("candle {}", i + 1);
+        assert_close(sar, ref_sar, &context);
+        assert_close(af, ref_af, &context);
+        assert_eq!(signal == ParabolicSarSignal::Flip, ref_flip, "{}", context);
+        if ref_flip {
+            flips += 1;
+            assert_close(af, 0.02, &context);
+        }
+    }
+    assert!(flips >= 4, "expected several reversals, got {}", flips);
+}
+
+#[test]
+fn parabolic_sar_stays_on_the_far_side_of_price() {
+    let candles = zigzag(120);
+    let mut sar = ParabolicSar::default();
+    for &(high, low, close) in &candles {
+        let Some((value, _, signal)) = sar.update(high, low, close) else {
+            continue;
+        };
+        match (signal, sar.is_uptrend()) {
+            (ParabolicSarSignal::Bullish, _) | (ParabolicSarSignal::Flip, Some(true)) => {
+                assert!(value <= low + EPS, "SAR {} above low {}", value, low)
+            }
+            _ => assert!(value >= high - EPS, "SAR {} below high {}", value, high),
+        }
+    }
+
+    sar.reset();
+    assert_eq!(sar.value(), None);
+    assert!(sar.update(101.0, 99.0, 100.0).is_none());
+}
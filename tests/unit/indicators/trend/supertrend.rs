@@ -43,3 +43,26 @@ fn supertrend_flips_with_price_direction() {
     }
     assert!(bearish_seen, "Expected bearish regime during down-move");
 }
+
+#[test]
+fn supertrend_turns_bearish_after_a_bullish_start() {
+    // Seeds bullish on the first candle, then falls steadily; the lower band
+    // must not keep resetting under price
+    let mut supertrend = SuperTrend::new(10, 3.0);
+    let mut signals = Vec::new();
+    for i in 0..60 {
+        let close = 200.0 - i as f64;
+        signals.push(supertrend.update(close + 1.0, close - 1.0, close));
+    }
+
+    assert_eq!(signals[0], SuperTrendSignal::Bullish);
+    assert_eq!(
+        signals
+            .iter()
+            .filter(|signal| **signal == SuperTrendSignal::BearishFlip)
+            .count(),
+        1
+    );
+    assert_eq!(signals.last(), Some(&SuperTrendSignal::Bearish));
+    assert!(supertrend.value().unwrap() > 141.0);
+}
@@ -1,5 +1,8 @@
 use perptrix::indicators::validation::*;
 use perptrix::models::indicators::*;
+use perptrix::models::strategy::IndicatorType;
+use serde_json::{json, Value};
+use std::collections::HashMap;
 
 #[test]
 fn test_validate_rsi_valid() {
@@ -278,3 +281,178 @@ fn test_validate_indicator_set_invalid_funding_rate() {
     set = set.with_funding_rate(2.0);
     assert!(validate_indicator_set(&set).is_err());
 }
+
+fn candle_series(count: usize) -> Vec<Candle> {
+    let start = chrono::Utc::now();
+    (0..count)
+        .map(|i| {
+            Candle::new(
+                100.0,
+                101.0,
+                99.0,
+                100.5,
+                1000.0,
+                start + chrono::Duration::minutes(i as i64),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_validate_candle_series_clean() {
+    assert!(validate_candle_series(&candle_series(20)).is_empty());
+    assert!(validate_candle_series(&[]).is_empty());
+}
+
+#[test]
+fn test_validate_candle_series_duplicate_and_volume_spike() {
+    let mut candles = candle_series(20);
+    candles[5].timestamp = candles[4].timestamp;
+    candles[12].volume = 200_000.0;
+
+    let issues = validate_candle_series(&candles);
+    assert_eq!(issues.len(), 2);
+    assert!(matches!(
+        issues[0],
+        CandleValidationError::DuplicateTimestamp { index: 5, .. }
+    ));
+    assert!(issues[0].is_hard_failure());
+    assert!(matches!(
+        issues[1],
+        CandleValidationError::Warning(CandleValidationWarning::VolumeSpike { index: 12, .. })
+    ));
+    assert!(!issues[1].is_hard_failure());
+}
+
+#[test]
+fn test_validate_candle_series_gap_and_out_of_order() {
+    let mut candles = candle_series(20);
+    for candle in candles.iter_mut().skip(10) {
+        candle.timestamp += chrono::Duration::minutes(10);
+    }
+    candles.swap(15, 16);
+
+    let issues = validate_candle_series(&candles);
+    assert!(issues.iter().any(|issue| matches!(
+        issue,
+        CandleValidationError::Warning(CandleValidationWarning::LargeGap { index: 10, .. })
+    )));
+    assert!(issues
+        .iter()
+        .any(|issue| matches!(issue, CandleValidationError::OutOfOrder { index: 16, .. })));
+}
+
+#[test]
+fn test_validate_candle_series_impossible_prices() {
+    let mut candles = candle_series(20);
+    candles[3].close = 102.0;
+    candles[7].low = 100.8;
+    candles[11].open = -1.0;
+    candles[14].high = f64::NAN;
+
+    let issues = validate_candle_series(&candles);
+    assert_eq!(
+        issues[..3],
+        [
+            CandleValidationError::CloseExceedsHigh {
+                index: 3,
+                close: 102.0,
+                high: 101.0,
+            },
+            CandleValidationError::LowExceedsClose {
+                index: 7,
+                low: 100.8,
+                close: 100.5,
+            },
+            CandleValidationError::NegativePrice {
+                index: 11,
+                price: -1.0,
+            },
+        ]
+    );
+    assert!(matches!(
+        issues[3],
+        CandleValidationError::NegativePrice { index: 14, price } if price.is_nan()
+    ));
+    assert_eq!(issues.len(), 4);
+    assert!(issues.iter().all(CandleValidationError::is_hard_failure));
+}
+
+#[test]
+fn test_validate_candle_series_zero_volume_is_a_warning() {
+    let mut candles = candle_series(20);
+    candles[9].volume = 0.0;
+
+    let issues = validate_candle_series(&candles);
+    assert_eq!(
+        issues,
+        vec![CandleValidationError::Warning(
+            CandleValidationWarning::ZeroVolume { index: 9 }
+        )]
+    );
+    assert!(!issues[0].is_hard_failure());
+}
+
+fn params(entries: &[(&str, Value)]) -> HashMap<String, Value> {
+    entries
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.clone()))
+        .collect()
+}
+
+#[test]
+fn test_validate_indicator_params_valid() {
+    let rsi = params(&[
+        ("period", json!(21)),
+        ("overbought_threshold", json!(75.5)),
+        ("oversold_threshold", json!(25)),
+    ]);
+    assert!(validate_indicator_params(IndicatorType::RSI, &rsi).is_ok());
+
+    let ema = params(&[("fast_period", json!(9)), ("slow_period", json!(21.0))]);
+    assert!(validate_indicator_params(IndicatorType::EMA, &ema).is_ok());
+    let ema = params(&[("confirmation_candles", json!(0))]);
+    assert!(validate_indicator_params(IndicatorType::EMA, &ema).is_ok());
+
+    let supertrend = params(&[("atr_period", json!(2)), ("multiplier", json!(0.5))]);
+    assert!(validate_indicator_params(IndicatorType::SuperTrend, &supertrend).is_ok());
+
+    let bollinger = params(&[("squeeze_threshold_pct", json!(3.5))]);
+    assert!(validate_indicator_params(IndicatorType::Bollinger, &bollinger).is_ok());
+    assert!(validate_indicator_params(IndicatorType::MACD, &HashMap::new()).is_ok());
+    let macd = params(&[
+        ("fast_period", json!(8)),
+        ("slow_period", json!(21)),
+        ("signal_period", json!(5)),
+    ]);
+    assert!(validate_indicator_params(IndicatorType::MACD, &macd).is_ok());
+}
+
+#[test]
+fn test_validate_indicator_params_invalid() {
+    let invalid = [
+        (IndicatorType::RSI, params(&[("period", json!(0))])),
+        (IndicatorType::RSI, params(&[("period", json!(9.5))])),
+        (IndicatorType::RSI, params(&[("period", json!(1001))])),
+        (IndicatorType::RSI, params(&[("period", json!("14"))])),
+        (IndicatorType::RSI, params(&[("oversold_threshold", json!(0.0))])),
+        (IndicatorType::RSI, params(&[("overbought_threshold", json!(100))])),
+        (IndicatorType::RSI, params(&[("fast_period", json!(9))])),
+        (IndicatorType::EMA, params(&[("fast_period", json!(null))])),
+        (IndicatorType::EMA, params(&[("confirmation_candles", json!(-1))])),
+        (IndicatorType::Bollinger, params(&[("squeeze_threshold_pct", json!(0))])),
+        (IndicatorType::SuperTrend, params(&[("atr_period", json!(1))])),
+        (IndicatorType::SuperTrend, params(&[("multiplier", json!(0.0))])),
+        (IndicatorType::SuperTrend, params(&[("multiplier", json!(-3.0))])),
+        (IndicatorType::MACD, params(&[("period", json!(12))])),
+        (IndicatorType::MACD, params(&[("signal_period", json!(0))])),
+    ];
+    for (indicator, params) in invalid {
+        assert!(
+            validate_indicator_params(indicator, &params).is_err(),
+            "{:?} {:?}",
+            indicator,
+            params
+        );
+    }
+}
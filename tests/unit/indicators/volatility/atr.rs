@@ -4,6 +4,7 @@ use chrono::Utc;
 use perptrix::indicators::volatility::atr::{VolatilityRegime, ATR};
 use perptrix::indicators::volatility::calculate_atr_default;
 use perptrix::models::indicators::Candle;
+use perptrix::models::signal::SignalDirection;
 
 fn candle(high: f64, low: f64, close: f64) -> Candle {
     Candle::new(close, high, low, close, 1000.0, Utc::now())
@@ -31,3 +32,37 @@ fn legacy_wrapper_remains() {
     let indicator = calculate_atr_default(&candles).expect("ATR result");
     assert!(indicator.value > 0.0);
 }
+
+#[test]
+fn trailing_stop_offsets_by_direction() {
+    let mut atr = ATR::new(3);
+    for _ in 0..3 {
+        atr.update(102.0, 100.0, 101.0);
+    }
+    let current = atr.current_atr();
+    assert!((current - 2.0).abs() < 1e-9);
+
+    assert!((atr.trailing_stop(100.0, SignalDirection::Long, 1.5) - 97.0).abs() < 1e-9);
+    assert!((atr.trailing_stop(100.0, SignalDirection::Short, 1.5) - 103.0).abs() < 1e-9);
+    assert_eq!(atr.trailing_stop(100.0, SignalDirection::Neutral, 1.5), 100.0);
+
+    let (lower, upper) = atr.atr_bands(100.0, 2.0);
+    assert!((lower - 96.0).abs() < 1e-9);
+    assert!((upper - 104.0).abs() < 1e-9);
+}
+
+#[test]
+fn trailing_stop_before_update_returns_price() {
+    let atr = ATR::new(14);
+    assert_eq!(atr.current_atr(), 0.0);
+    assert_eq!(atr.trailing_stop(50.0, SignalDirection::Long, 2.0), 50.0);
+}
+
+#[test]
+fn regime_compares_current_atr_against_rolling_mean() {
+    let atr = ATR::new(14);
+    assert_eq!(atr.get_volatility_regime(0.5, 1.0), VolatilityRegime::Low);
+    assert_eq!(atr.get_volatility_regime(0.9, 1.0), VolatilityRegime::Normal);
+    assert_eq!(atr.get_volatility_regime(1.2, 1.0), VolatilityRegime::Elevated);
+    assert_eq!(atr.get_volatility_regime(2.0, 1.0), VolatilityRegime::High);
+}
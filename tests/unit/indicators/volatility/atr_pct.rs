@@ -0,0 +1,111 @@
+//! Unit tests for ATR% volatility normalization.
+
+use chrono::{Duration, Utc};
+use perptrix::indicators::volatility::atr::calculate_atr_pct;
+use perptrix::indicators::volatility::atr_pct::{atr_pct, AtrPct};
+use perptrix::indicators::volatility::{calculate_atr, VolatilityRegime};
+use perptrix::models::indicators::Candle;
+use perptrix::models::strategy::{Comparison, Condition, IndicatorType};
+use perptrix::strategies::evaluator::{IndicatorValues, StrategyEvaluator};
+use std::collections::HashMap;
+
+/// Choppy series around 100 whose ranges widen over time, scaled by `scale`
+fn candles(count: usize, scale: f64) -> Vec<Candle> {
+    let start = Utc::now() - Duration::minutes(count as i64);
+    (0..count)
+        .map(|i| {
+            let close = (100.0 + (i as f64 * 0.9).sin() * 2.0) * scale;
+            let range = (1.0 + i as f64 * 0.05) * scale;
+            Candle::new(
+                close,
+                close + range,
+                close - range,
+                close,
+                1_000.0,
+                start + Duration::minutes(i as i64),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn atr_pct_is_atr_over_close() {
+    let series = candles(30, 1.0);
+    let atr = calculate_atr(&series, 14).unwrap().value;
+    let close = series.last().unwrap().close;
+    let pct = calculate_atr_pct(&series, 14).unwrap();
+    assert!((pct - atr / close * 100.0).abs() < 1e-12);
+
+    assert_eq!(atr_pct(2.0, 200.0), Some(1.0));
+    assert_eq!(atr_pct(2.0, 0.0), None);
+    assert_eq!(atr_pct(2.0, -5.0), None);
+    assert!(calculate_atr_pct(&series[..5], 14).is_none());
+}
+
+#[test]
+fn atr_pct_is_scale_invariant() {
+    let base = candles(60, 1.0);
+    let base_pct = calculate_atr_pct(&base, 14).unwrap();
+    for scale in [0.001, 30.0, 50_000.0] {
+        let scaled = candles(60, scale);
+        // Plain ATR scales with price...
+        let ratio =
+            calculate_atr(&scaled, 14).unwrap().value / calculate_atr(&base, 14).unwrap().value;
+        assert!((ratio - scale).abs() / scale < 1e-9);
+        // ...ATR% does not
+        assert!((calculate_atr_pct(&scaled, 14).unwrap() - base_pct).abs() < 1e-9);
+
+        let mut base_stream = AtrPct::default();
+        let mut scaled_stream = AtrPct::default();
+        for (a, b) in base.iter().zip(&scaled) {
+            let (base_value, base_regime) = base_stream.update(a.high, a.low, a.close).unwrap();
+            let (value, regime) = scaled_stream.update(b.high, b.low, b.close).unwrap();
+            assert!((value - base_value).abs() < 1e-9);
+            assert_eq!(regime, base_regime);
+        }
+    }
+}
+
+#[test]
+fn atr_pct_regime_tracks_widening_ranges() {
+    let mut indicator = AtrPct::new(5);
+    let mut regime = None;
+    for _ in 0..10 {
+        regime = indicator
+            .update(101.0, 99.0, 100.0)
+            .map(|(_, regime)| regime);
+    }
+    assert_eq!(regime, Some(VolatilityRegime::Normal));
+
+    // A burst of wide candles lifts ATR% well above its recent mean
+    for _ in 0..3 {
+        regime = indicator
+            .update(110.0, 90.0, 100.0)
+            .map(|(_, regime)| regime);
+    }
+    assert_eq!(regime, Some(VolatilityRegime::High));
+
+    assert!(indicator.update(1.0, 0.0, 0.0).is_none());
+    indicator.reset();
+    let (value, regime) = indicator.update(101.0, 99.0, 100.0).unwrap();
+    assert!((value - 2.0).abs() < 1e-12);
+    assert_eq!(regime, VolatilityRegime::Normal);
+}
+
+#[test]
+fn atr_pct_condition_requires_minimum_volatility() {
+    let condition = Condition {
+        indicator: IndicatorType::AtrPct,
+        indicator_params: HashMap::new(),
+        comparison: Comparison::GreaterThan,
+        threshold: Some(0.5),
+        signal_state: None,
+    };
+    let mut values = IndicatorValues::new(100.0);
+    assert!(!StrategyEvaluator::evaluate_condition(&condition, &values));
+
+    values.atr_pct_value = Some(0.8);
+    assert!(StrategyEvaluator::evaluate_condition(&condition, &values));
+    values.atr_pct_value = Some(0.3);
+    assert!(!StrategyEvaluator::evaluate_condition(&condition, &values));
+}
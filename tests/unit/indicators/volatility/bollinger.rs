@@ -1,7 +1,9 @@
 //! Unit tests for Bollinger Bands signals.
 
 use chrono::Utc;
-use perptrix::indicators::volatility::bollinger::{BollingerBands, BollingerSignal};
+use perptrix::indicators::volatility::bollinger::{
+    bandwidth_pct, percent_b, BollingerBands, BollingerSignal,
+};
 use perptrix::indicators::volatility::calculate_bollinger_bands_default;
 use perptrix::models::indicators::Candle;
 
@@ -40,3 +42,46 @@ fn legacy_wrapper_available() {
     let indicator = calculate_bollinger_bands_default(&candles).expect("Bollinger result");
     assert!(indicator.upper > indicator.lower);
 }
+
+#[test]
+fn percent_b_is_position_between_the_bands_clamped() {
+    assert_eq!(percent_b(95.0, 110.0, 90.0), 0.25);
+    assert_eq!(percent_b(110.0, 110.0, 90.0), 1.0);
+    assert_eq!(percent_b(130.0, 110.0, 90.0), 1.0);
+    assert_eq!(percent_b(70.0, 110.0, 90.0), 0.0);
+}
+
+#[test]
+fn zero_width_bands_have_zero_bandwidth_and_midpoint_percent_b() {
+    let mut bb = BollingerBands::new(5, 2.0);
+    let mut latest = None;
+    for _ in 0..5 {
+        latest = Some(bb.update(100.0));
+    }
+    let (upper, middle, lower, signal) = latest.unwrap();
+
+    assert_eq!(upper, lower);
+    assert_eq!(bandwidth_pct(upper, middle, lower), Some(0.0));
+    assert_eq!(percent_b(100.0, upper, lower), 0.5);
+    assert_eq!(percent_b(100.5, upper, lower), 1.0);
+    assert_eq!(percent_b(99.5, upper, lower), 0.0);
+    assert_eq!(signal, BollingerSignal::Squeeze);
+
+    assert_eq!(bandwidth_pct(1.0, 0.0, -1.0), None);
+}
+
+#[test]
+fn squeeze_threshold_is_configurable() {
+    // Alternating 99/101 gives bands at 98/102: a 4% bandwidth
+    let closes = [99.0, 101.0, 99.0, 101.0];
+    let last_reading =
+        |mut bb: BollingerBands| closes.iter().map(|&close| bb.update(close)).last().unwrap();
+
+    let (upper, middle, lower, signal) = last_reading(BollingerBands::new(4, 2.0));
+    assert!((bandwidth_pct(upper, middle, lower).unwrap() - 4.0).abs() < 1e-9);
+    assert_ne!(signal, BollingerSignal::Squeeze);
+
+    let (_, _, _, signal) =
+        last_reading(BollingerBands::new(4, 2.0).with_squeeze_threshold_pct(5.0));
+    assert_eq!(signal, BollingerSignal::Squeeze);
+}
@@ -0,0 +1,67 @@
+//! Unit tests for the Keltner Channel volatility indicator.
+
+use crate::indicators_test_utils::candle;
+use perptrix::indicators::volatility::keltner::{KeltnerChannel, KeltnerSignal};
+use proptest::prelude::*;
+
+#[test]
+fn keltner_waits_for_ema_seed() {
+    let mut keltner = KeltnerChannel::default();
+    for i in 0..19 {
+        let close = 100.0 + i as f64;
+        assert!(keltner
+            .update(close + 1.0, close - 1.0, close, None)
+            .is_none());
+    }
+    let (upper, middle, lower, signal) = keltner.update(120.0, 118.0, 119.0, None).unwrap();
+    assert!((middle - 109.5).abs() < 1e-9);
+    assert!((upper - middle - (middle - lower)).abs() < 1e-9);
+    // A steady climb leaves the close above EMA + 2 ATR
+    assert_eq!(signal, KeltnerSignal::Breakout);
+}
+
+#[test]
+fn keltner_flags_breakouts_and_squeeze() {
+    let mut keltner = KeltnerChannel::default();
+    let mut last = None;
+    for _ in 0..20 {
+        last = keltner.update(101.0, 99.0, 100.0, None);
+    }
+    let (upper, _, lower, _) = last.unwrap();
+
+    let (_, _, _, signal) = keltner.update(130.0, 120.0, 125.0, None).unwrap();
+    assert_eq!(signal, KeltnerSignal::Breakout);
+
+    keltner.reset();
+    for _ in 0..20 {
+        keltner.update(101.0, 99.0, 100.0, None);
+    }
+    let (_, _, _, signal) = keltner.update(80.0, 70.0, 75.0, None).unwrap();
+    assert_eq!(signal, KeltnerSignal::Breakdown);
+
+    // Bollinger Bands inside the channel take precedence over price location
+    keltner.reset();
+    for _ in 0..20 {
+        keltner.update(101.0, 99.0, 100.0, None);
+    }
+    let squeeze = Some((upper - 0.1, lower + 0.1));
+    let (_, _, _, signal) = keltner.update(101.0, 99.0, 100.0, squeeze).unwrap();
+    assert_eq!(signal, KeltnerSignal::BollingerSqueeze);
+}
+
+proptest! {
+    #[test]
+    fn keltner_bands_are_ordered(
+        candles in prop::collection::vec(candle(), 1..80),
+        multiplier in -5.0..5.0f64,
+    ) {
+        let mut keltner = KeltnerChannel::new(20, 10, multiplier);
+        for (high, low, close) in candles {
+            if let Some((upper, middle, lower, _)) = keltner.update(high, low, close, None) {
+                prop_assert!(upper.is_finite() && lower.is_finite());
+                prop_assert!(upper >= middle);
+                prop_assert!(middle >= lower);
+            }
+        }
+    }
+}
@@ -1,6 +1,6 @@
 //! Unit tests for the OBV indicator.
 
-use perptrix::indicators::volume::obv::{OBVSignal, OBV};
+use perptrix::indicators::volume::obv::{detect_divergence, DivergenceSignal, OBVSignal, OBV};
 
 #[test]
 fn obv_detects_confirmation_and_divergence() {
@@ -19,3 +19,52 @@ fn obv_detects_confirmation_and_divergence() {
     assert!(confirmation_seen);
     assert_eq!(signal, OBVSignal::BullishDivergence);
 }
+
+/// Price falls to a lower low while OBV rises to a higher low
+fn bullish_histories() -> (Vec<f64>, Vec<f64>) {
+    let price = vec![100.0, 98.0, 99.0, 97.0, 98.0, 96.0, 97.0, 95.0];
+    let obv = vec![
+        1000.0, 900.0, 1100.0, 1000.0, 1200.0, 1100.0, 1300.0, 1200.0,
+    ];
+    (obv, price)
+}
+
+#[test]
+fn divergence_detects_lower_price_low_with_higher_obv_low() {
+    let (obv, price) = bullish_histories();
+    assert_eq!(
+        detect_divergence(&obv, &price, 8),
+        Some(DivergenceSignal::BullishDivergence)
+    );
+    assert_eq!(
+        OBVSignal::from(DivergenceSignal::BullishDivergence),
+        OBVSignal::BullishDivergence
+    );
+}
+
+#[test]
+fn divergence_detects_higher_price_high_with_lower_obv_high() {
+    let (obv, price) = bullish_histories();
+    let price: Vec<f64> = price.iter().map(|p| 200.0 - p).collect();
+    let obv: Vec<f64> = obv.iter().map(|v| -v).collect();
+    assert_eq!(
+        detect_divergence(&obv, &price, 8),
+        Some(DivergenceSignal::BearishDivergence)
+    );
+}
+
+#[test]
+fn divergence_requires_disagreement_and_enough_history() {
+    let (obv, price) = bullish_histories();
+    // OBV falling with price confirms the move
+    let confirming: Vec<f64> = obv.iter().map(|v| -v).collect();
+    assert_eq!(detect_divergence(&confirming, &price, 8), None);
+
+    assert_eq!(detect_divergence(&obv, &price, 9), None);
+    assert_eq!(detect_divergence(&obv, &price, 2), None);
+    // Only the newest `lookback` readings are compared
+    assert_eq!(
+        detect_divergence(&obv, &price, 4),
+        Some(DivergenceSignal::BullishDivergence)
+    );
+}
@@ -0,0 +1,43 @@
+//! Unit tests for the buy/sell volume ratio.
+
+use chrono::{DateTime, Utc};
+use perptrix::indicators::volume::order_flow::{buy_sell_ratio, BUY_SELL_RATIO_WINDOW};
+use perptrix::models::indicators::{Trade, TradeSide};
+
+fn trade(side: TradeSide, size: f64) -> Trade {
+    Trade {
+        price: 100.0,
+        size,
+        side,
+        timestamp: DateTime::<Utc>::UNIX_EPOCH,
+    }
+}
+
+#[test]
+fn ratio_divides_buy_volume_by_sell_volume() {
+    let trades = vec![
+        trade(TradeSide::Buy, 3.0),
+        trade(TradeSide::Sell, 1.0),
+        trade(TradeSide::Buy, 1.0),
+        trade(TradeSide::Sell, 1.0),
+    ];
+    assert_eq!(buy_sell_ratio(&trades), Some(2.0));
+}
+
+#[test]
+fn ratio_only_counts_the_most_recent_window() {
+    // Old sells fall out of the window, leaving one sell among the latest trades
+    let mut trades = vec![trade(TradeSide::Sell, 100.0); 10];
+    trades.extend(vec![trade(TradeSide::Buy, 1.0); BUY_SELL_RATIO_WINDOW - 1]);
+    trades.push(trade(TradeSide::Sell, 1.0));
+
+    let expected = (BUY_SELL_RATIO_WINDOW - 1) as f64;
+    assert_eq!(buy_sell_ratio(&trades), Some(expected));
+}
+
+#[test]
+fn ratio_is_undefined_without_sell_volume() {
+    assert_eq!(buy_sell_ratio(&[]), None);
+    assert_eq!(buy_sell_ratio(&[trade(TradeSide::Buy, 1.0)]), None);
+    assert_eq!(buy_sell_ratio(&[trade(TradeSide::Sell, 1.0)]), Some(0.0));
+}
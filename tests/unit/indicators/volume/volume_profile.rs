@@ -1,6 +1,8 @@
 //! Unit tests for the volume profile helper.
 
-use perptrix::indicators::volume::volume_profile::{VolumeProfile, VolumeProfileSignal};
+use perptrix::indicators::volume::volume_profile::{
+    tick_size_for_price, VolumeProfile, VolumeProfileSignal, VALUE_AREA_VOLUME_PCT,
+};
 
 #[test]
 fn volume_profile_identifies_poc_and_lvn() {
@@ -20,3 +22,96 @@ fn volume_profile_identifies_poc_and_lvn() {
     let (_, _, signal) = vp.get_profile();
     assert_eq!(signal, VolumeProfileSignal::NearLVN);
 }
+
+/// Volume traded at prices within `[low, high]`
+fn volume_between(trades: &[(f64, f64)], low: f64, high: f64) -> f64 {
+    trades
+        .iter()
+        .filter(|(price, _)| *price >= low - 1e-9 && *price <= high + 1e-9)
+        .map(|(_, volume)| volume)
+        .sum()
+}
+
+#[test]
+fn value_area_covers_seventy_percent_of_volume_around_the_poc() {
+    // Uneven volume over 40 one-tick price levels
+    let trades: Vec<(f64, f64)> = (0..200)
+        .map(|i| {
+            let price = 100.0 + ((i * 7) % 40) as f64;
+            let volume = 10.0 + ((i * 13) % 17) as f64 * 25.0;
+            (price, volume)
+        })
+        .collect();
+    let mut vp = VolumeProfile::new(1.0, trades.len());
+    for &(price, volume) in &trades {
+        vp.update(price, volume);
+    }
+
+    let (_, poc, _) = vp.get_profile();
+    let (low, high) = vp.value_area().unwrap();
+    let total: f64 = trades.iter().map(|(_, volume)| volume).sum();
+
+    assert!(low <= poc && poc <= high, "POC {} outside {}..{}", poc, low, high);
+    assert!(volume_between(&trades, low, high) >= total * VALUE_AREA_VOLUME_PCT);
+    // Dropping either end level leaves less than the target
+    let short_of_target = |low: f64, high: f64| {
+        volume_between(&trades, low, high) < total * VALUE_AREA_VOLUME_PCT
+    };
+    assert!(high - low < 39.0);
+    assert!(short_of_target(low + 1.0, high) || short_of_target(low, high - 1.0));
+}
+
+#[test]
+fn value_area_is_symmetric_for_a_symmetric_profile() {
+    let mut vp = VolumeProfile::new(1.0, 100);
+    for (offset, volume) in [(0.0, 500.0), (1.0, 200.0), (2.0, 100.0), (3.0, 50.0)] {
+        vp.update(100.0 + offset, volume);
+        if offset > 0.0 {
+            vp.update(100.0 - offset, volume);
+        }
+    }
+
+    // 70% of 1200 needs the POC and both neighbours (900)
+    assert_eq!(vp.value_area(), Some((99.0, 101.0)));
+}
+
+#[test]
+fn value_area_grows_towards_the_heavier_side() {
+    let mut vp = VolumeProfile::new(1.0, 100);
+    for (price, volume) in [(98.0, 50.0), (99.0, 100.0), (100.0, 400.0), (101.0, 300.0)] {
+        vp.update(price, volume);
+    }
+
+    // 400 at the POC plus 300 above reaches 70% of 850
+    assert_eq!(vp.value_area(), Some((100.0, 101.0)));
+}
+
+#[test]
+fn value_area_needs_volume() {
+    let mut vp = VolumeProfile::new(1.0, 10);
+    assert_eq!(vp.value_area(), None);
+
+    vp.update(100.0, 0.0);
+    assert_eq!(vp.value_area(), None);
+}
+
+#[test]
+fn tick_size_scales_with_the_price() {
+    assert_eq!(tick_size_for_price(50_000.0), 10.0);
+    assert_eq!(tick_size_for_price(2_500.0), 1.0);
+    assert!((tick_size_for_price(1.5) - 0.001).abs() < 1e-12);
+    assert_eq!(tick_size_for_price(0.0), 1.0);
+    assert_eq!(tick_size_for_price(f64::NAN), 1.0);
+
+    // A sub-dollar symbol keeps its levels apart instead of rounding to 0
+    let tick = tick_size_for_price(0.5);
+    let mut vp = VolumeProfile::new(tick, 20);
+    for _ in 0..5 {
+        vp.update(0.52, 1_000.0);
+    }
+    vp.update(0.48, 100.0);
+    let (_, poc, _) = vp.get_profile();
+    assert!((poc - 0.52).abs() < 1e-9, "POC {}", poc);
+    let (low, high) = vp.value_area().unwrap();
+    assert!(low > 0.0 && high >= poc);
+}
@@ -0,0 +1,172 @@
+//! Unit tests for job payload serialization.
+
+use chrono::{Duration, TimeZone, Utc};
+use perptrix::jobs::types::{
+    CompressedCandles, EvaluateSignalJob, FetchCandlesJob, JobPriority, StoreSignalJob,
+};
+use perptrix::models::indicators::Candle;
+use perptrix::models::signal::{SignalDirection, SignalOutput};
+use std::collections::HashMap;
+
+fn candles(count: usize) -> Vec<Candle> {
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    (0..count)
+        .map(|i| {
+            let price = 42_000.0 + (i as f64 * 0.1).sin() * 250.0;
+            Candle::new(
+                price,
+                price + 12.5,
+                price - 12.5,
+                price + 3.0,
+                1_500.0 + i as f64,
+                start + Duration::minutes(5 * i as i64),
+            )
+        })
+        .collect()
+}
+
+/// JSON float parsing is not guaranteed to be bit-exact, so prices and
+/// volumes are compared within a relative tolerance.
+fn assert_candles_close(actual: &[Candle], expected: &[Candle]) {
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(expected) {
+        assert_eq!(a.timestamp, e.timestamp);
+        for (got, want) in [
+            (a.open, e.open),
+            (a.high, e.high),
+            (a.low, e.low),
+            (a.close, e.close),
+            (a.volume, e.volume),
+        ] {
+            assert!((got - want).abs() <= want.abs() * 1e-12, "{got} != {want}");
+        }
+    }
+}
+
+#[test]
+fn compressed_candles_round_trip() {
+    let original = candles(250);
+    let compressed = CompressedCandles::from(original.clone());
+    let restored: Vec<Candle> = compressed.try_into().expect("decompress");
+    assert_candles_close(&restored, &original);
+}
+
+#[test]
+fn compressed_payload_is_smaller_than_raw_json() {
+    let original = candles(250);
+    let raw = serde_json::to_vec(&EvaluateSignalJob {
+        symbol: "BTC".to_string(),
+        candles: original.clone(),
+        compressed_candles: None,
+        candles_by_interval: HashMap::new(),
+        compressed_candles_by_interval: HashMap::new(),
+    })
+    .unwrap();
+    let compressed = serde_json::to_vec(&EvaluateSignalJob {
+        symbol: "BTC".to_string(),
+        candles: Vec::new(),
+        compressed_candles: Some(original.into()),
+        candles_by_interval: HashMap::new(),
+        compressed_candles_by_interval: HashMap::new(),
+    })
+    .unwrap();
+
+    assert!(
+        compressed.len() < raw.len(),
+        "compressed {} bytes, raw {} bytes",
+        compressed.len(),
+        raw.len()
+    );
+}
+
+#[test]
+fn evaluate_job_round_trips_through_json() {
+    let original = candles(50);
+    let job = EvaluateSignalJob {
+        symbol: "ETH".to_string(),
+        candles: Vec::new(),
+        compressed_candles: Some(original.clone().into()),
+        candles_by_interval: HashMap::new(),
+        compressed_candles_by_interval: HashMap::new(),
+    };
+
+    let json = serde_json::to_string(&job).unwrap();
+    assert!(!json.contains("\"candles\""));
+    let decoded: EvaluateSignalJob = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded.symbol, "ETH");
+    assert_candles_close(&decoded.candles().unwrap(), &original);
+}
+
+#[test]
+fn uncompressed_evaluate_job_reads_plain_candles() {
+    let original = candles(10);
+    let json = serde_json::json!({ "symbol": "SOL", "candles": original }).to_string();
+    let decoded: EvaluateSignalJob = serde_json::from_str(&json).unwrap();
+    assert!(decoded.compressed_candles.is_none());
+    assert_candles_close(&decoded.candles().unwrap(), &original);
+}
+
+#[test]
+fn corrupt_compressed_payload_is_an_error() {
+    let job = EvaluateSignalJob {
+        symbol: "BTC".to_string(),
+        candles: Vec::new(),
+        compressed_candles: Some(CompressedCandles(vec![0xff, 0x00, 0x01])),
+        candles_by_interval: HashMap::new(),
+        compressed_candles_by_interval: HashMap::new(),
+    };
+    assert!(job.candles().is_err());
+}
+
+#[test]
+fn compressed_interval_candles_round_trip() {
+    let hourly = candles(60);
+    let job = EvaluateSignalJob {
+        symbol: "BTC".to_string(),
+        candles: Vec::new(),
+        compressed_candles: None,
+        candles_by_interval: HashMap::from([("1m".to_string(), candles(5))]),
+        compressed_candles_by_interval: HashMap::from([("1h".to_string(), hourly.clone().into())]),
+    };
+
+    let json = serde_json::to_string(&job).unwrap();
+    let decoded: EvaluateSignalJob = serde_json::from_str(&json).unwrap();
+    let by_interval = decoded.candles_by_interval().unwrap();
+    assert_eq!(by_interval.len(), 2);
+    assert_candles_close(&by_interval["1h"], &hourly);
+    assert_eq!(by_interval["1m"].len(), 5);
+}
+
+#[test]
+fn fetch_and_store_jobs_round_trip() {
+    let fetch = FetchCandlesJob::new("BTC");
+    let decoded: FetchCandlesJob =
+        serde_json::from_str(&serde_json::to_string(&fetch).unwrap()).unwrap();
+    assert_eq!(decoded.symbol, "BTC");
+
+    let store = StoreSignalJob {
+        symbol: "BTC".to_string(),
+        signal: SignalOutput::new_neutral("BTC".to_string(), 42_000.0),
+        strategy_id: 7,
+    };
+    let decoded: StoreSignalJob =
+        serde_json::from_str(&serde_json::to_string(&store).unwrap()).unwrap();
+    assert_eq!(decoded.strategy_id, 7);
+    assert_eq!(decoded.signal.direction, SignalDirection::Neutral);
+}
+
+#[test]
+fn fetch_job_priority_is_omitted_when_normal() {
+    let normal = serde_json::to_value(FetchCandlesJob::new("BTC")).unwrap();
+    assert_eq!(normal, serde_json::json!({ "symbol": "BTC" }));
+
+    // Jobs queued before priorities existed decode as Normal
+    let legacy: FetchCandlesJob = serde_json::from_str(r#"{"symbol":"ETH"}"#).unwrap();
+    assert_eq!(legacy.priority, JobPriority::Normal);
+
+    let high = FetchCandlesJob::new("BTC").with_priority(JobPriority::High);
+    let decoded: FetchCandlesJob =
+        serde_json::from_str(&serde_json::to_string(&high).unwrap()).unwrap();
+    assert_eq!(decoded.priority, JobPriority::High);
+    assert_eq!(decoded.priority.as_str(), "high");
+}
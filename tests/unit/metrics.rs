@@ -0,0 +1,91 @@
+//! Unit tests for the Prometheus metrics registry.
+
+use perptrix::metrics::Metrics;
+use perptrix::models::signal::SignalDirection;
+use perptrix::strategies::evaluator::IndicatorComputation;
+
+#[test]
+fn export_includes_signal_metrics() {
+    let metrics = Metrics::default();
+    let body = metrics.export().unwrap();
+    assert!(body.contains("signal_confidence_histogram_bucket{le=\"0.1\"} 0"));
+    assert!(body.contains("signal_confidence_histogram_bucket{le=\"1\"} 0"));
+    assert!(body.contains("signals_above_threshold_total 0"));
+}
+
+#[test]
+fn record_signal_updates_distribution_and_counters() {
+    let metrics = Metrics::default();
+    metrics.record_signal("BTC", SignalDirection::Long, 0.85);
+    metrics.record_signal("BTC", SignalDirection::Short, 0.7);
+    metrics.record_signal("ETH", SignalDirection::Neutral, 0.35);
+
+    assert_eq!(metrics.signal_confidence_histogram.get_sample_count(), 3);
+    // The threshold is inclusive
+    assert_eq!(metrics.signals_above_threshold_total.get(), 2.0);
+    assert_eq!(
+        metrics
+            .signals_by_direction_total
+            .with_label_values(&["Long", "BTC"])
+            .get(),
+        1.0
+    );
+
+    let body = metrics.export().unwrap();
+    assert!(body.contains("signal_confidence_histogram_bucket{le=\"0.3\"} 0"));
+    assert!(body.contains("signal_confidence_histogram_bucket{le=\"0.4\"} 1"));
+    assert!(body.contains("signal_confidence_histogram_bucket{le=\"0.7\"} 2"));
+    assert!(body.contains("signal_confidence_histogram_bucket{le=\"0.9\"} 3"));
+    assert!(body.contains("signals_by_direction_total{direction=\"Long\",symbol=\"BTC\"} 1"));
+    assert!(body.contains("signals_by_direction_total{direction=\"Short\",symbol=\"BTC\"} 1"));
+    assert!(body.contains("signals_by_direction_total{direction=\"Neutral\",symbol=\"ETH\"} 1"));
+    assert!(body.contains("signals_above_threshold_total 2"));
+}
+
+fn computation(indicator: &'static str, candles: usize, computed: bool) -> IndicatorComputation {
+    IndicatorComputation {
+        indicator,
+        min_candles: 20,
+        candles,
+        computed,
+    }
+}
+
+#[test]
+fn missing_readings_count_as_failures_only_with_enough_candles() {
+    let metrics = Metrics::default();
+    metrics.record_indicator_computations(&[
+        computation("cci", 50, true),
+        // Warming up
+        computation("cmf", 19, false),
+        // Enough candles but no reading
+        computation("donchian", 20, false),
+        computation("keltner", 50, false),
+    ]);
+
+    let failures = |name| {
+        metrics
+            .indicator_computation_failures_total
+            .with_label_values(&[name])
+            .get()
+    };
+    assert_eq!(failures("cci"), 0.0);
+    assert_eq!(failures("cmf"), 0.0);
+    assert_eq!(failures("donchian"), 1.0);
+    assert_eq!(failures("keltner"), 1.0);
+    assert_eq!(
+        metrics
+            .indicator_computations_total
+            .with_label_values(&["cmf"])
+            .get(),
+        1.0
+    );
+    assert_eq!(metrics.indicator_computations_none_ratio.get(), 0.75);
+
+    // The ratio reflects the latest evaluation only
+    metrics.record_indicator_computations(&[computation("cci", 50, true)]);
+    assert_eq!(metrics.indicator_computations_none_ratio.get(), 0.0);
+    let body = metrics.export().unwrap();
+    assert!(body.contains("indicator_computations_total{indicator_name=\"cci\"} 2"));
+    assert!(body.contains("indicator_computation_failures_total{indicator_name=\"donchian\"} 1"));
+}
@@ -0,0 +1,35 @@
+//! Unit tests for strategy audit log entries.
+
+use perptrix::models::audit_log::AuditLogEntry;
+use serde_json::json;
+
+#[test]
+fn audit_log_entry_records_operator_and_request() {
+    let entry = AuditLogEntry::new("update", 7, "RSI swing")
+        .with_operator_key(Some("ops".to_string()))
+        .with_request(&json!({ "name": "RSI swing" }));
+
+    assert_eq!(entry.operation, "update");
+    assert_eq!(entry.strategy_id, 7);
+    assert_eq!(entry.strategy_name, "RSI swing");
+    assert_eq!(entry.operator_key.as_deref(), Some("ops"));
+    assert_eq!(
+        entry.request_json.as_deref(),
+        Some(r#"{"name":"RSI swing"}"#)
+    );
+
+    let json = serde_json::to_value(&entry).unwrap();
+    assert_eq!(json["operation"], "update");
+    assert_eq!(json["operator_key"], "ops");
+}
+
+#[test]
+fn audit_log_entry_defaults_to_no_operator_or_request() {
+    let entry = AuditLogEntry::new("delete", 3, "Old strategy");
+    assert_eq!(entry.operator_key, None);
+    assert_eq!(entry.request_json, None);
+
+    let json = serde_json::to_value(&entry).unwrap();
+    assert!(json["operator_key"].is_null());
+    assert!(json["request_json"].is_null());
+}
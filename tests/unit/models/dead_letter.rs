@@ -0,0 +1,63 @@
+//! Unit tests for dead-letter records.
+
+use perptrix::jobs::types::{FetchCandlesJob, JobType};
+use perptrix::models::dead_letter::DeadLetter;
+
+#[test]
+fn dead_letter_records_job_context() {
+    let dead_letter = DeadLetter::new(JobType::StoreSignal.as_str(), "BTC", "connection reset")
+        .with_strategy_id(7)
+        .with_retry_count(2);
+
+    assert_eq!(dead_letter.job_type, "store_signal");
+    assert_eq!(dead_letter.symbol, "BTC");
+    assert_eq!(dead_letter.strategy_id, Some(7));
+    assert_eq!(dead_letter.error_message, "connection reset");
+    assert_eq!(dead_letter.retry_count, 2);
+
+    let json = serde_json::to_value(&dead_letter).unwrap();
+    assert_eq!(json["job_type"], "store_signal");
+    assert_eq!(json["strategy_id"], 7);
+}
+
+#[test]
+fn dead_letter_defaults_to_first_attempt_without_strategy() {
+    let dead_letter = DeadLetter::new(JobType::FetchCandles.as_str(), "ETH", "timeout");
+    assert_eq!(dead_letter.job_type, "fetch_candles");
+    assert_eq!(dead_letter.strategy_id, None);
+    assert_eq!(dead_letter.retry_count, 0);
+    assert_eq!(
+        DeadLetter::new("fetch_candles", "ETH", "timeout")
+            .with_retry_count(usize::MAX)
+            .retry_count,
+        i32::MAX
+    );
+    assert_eq!(JobType::EvaluateSignal.as_str(), "evaluate_signal");
+}
+
+#[test]
+fn dead_letter_payload_is_kept_out_of_api_responses() {
+    let job = FetchCandlesJob::new("SOL");
+    let dead_letter = DeadLetter::new(JobType::FetchCandles.as_str(), "SOL", "timeout")
+        .with_payload(&job);
+
+    assert_eq!(dead_letter.id, None);
+    assert!(!dead_letter.is_replayed());
+    assert_eq!(dead_letter.payload.as_deref(), Some(r#"{"symbol":"SOL"}"#));
+
+    let json = serde_json::to_value(&dead_letter).unwrap();
+    assert!(json.get("payload").is_none());
+    assert!(json["replayed_at"].is_null());
+}
+
+#[test]
+fn job_type_parses_dead_letter_names() {
+    for job_type in [
+        JobType::FetchCandles,
+        JobType::EvaluateSignal,
+        JobType::StoreSignal,
+    ] {
+        assert_eq!(JobType::parse(job_type.as_str()), Some(job_type));
+    }
+    assert_eq!(JobType::parse("backfill"), None);
+}
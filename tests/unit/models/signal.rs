@@ -0,0 +1,104 @@
+//! Unit tests for signal output helpers.
+
+use chrono::Duration;
+use perptrix::models::signal::{SignalDirection, SignalOutput, SignalReason};
+use perptrix::models::strategy::{DecayConfig, DEFAULT_MIN_DISPLAY_THRESHOLD};
+
+#[test]
+fn new_neutral_zeroes_scores() {
+    let signal = SignalOutput::new_neutral("BTC".to_string(), 42_000.0);
+    assert_eq!(signal.direction, SignalDirection::Neutral);
+    assert_eq!(signal.confidence, 0.0);
+    assert_eq!(signal.recommended_sl_pct, 0.0);
+    assert_eq!(signal.recommended_tp_pct, 0.0);
+    assert!(signal.reasons.is_empty());
+    assert_eq!(signal.symbol, "BTC");
+    assert_eq!(signal.price, 42_000.0);
+    assert!(!signal.is_actionable());
+}
+
+#[test]
+fn directional_signal_with_confidence_is_actionable() {
+    let reasons = vec![SignalReason {
+        description: "EMA bullish cross".to_string(),
+        weight: 1.0,
+    }];
+    let long = SignalOutput::new(
+        SignalDirection::Long,
+        0.7,
+        1.0,
+        2.0,
+        reasons,
+        "ETH".to_string(),
+        2500.0,
+    );
+    assert!(long.is_actionable());
+
+    let zero_confidence = SignalOutput::new(
+        SignalDirection::Short,
+        0.0,
+        1.0,
+        2.0,
+        vec![],
+        "ETH".to_string(),
+        2500.0,
+    );
+    assert!(!zero_confidence.is_actionable());
+}
+
+fn decaying_signal(confidence: f64, half_life_seconds: u64) -> SignalOutput {
+    SignalOutput::new(
+        SignalDirection::Long,
+        confidence,
+        1.0,
+        2.0,
+        vec![],
+        "BTC".to_string(),
+        42_000.0,
+    )
+    .with_decay(Some(DecayConfig {
+        half_life_seconds,
+        min_display_threshold: 0.2,
+    }))
+}
+
+#[test]
+fn effective_confidence_halves_every_half_life() {
+    let signal = decaying_signal(0.8, 600);
+    let at = |seconds| signal.timestamp + Duration::seconds(seconds);
+
+    assert!((signal.effective_confidence(at(0)) - 0.8).abs() < 1e-12);
+    assert!((signal.effective_confidence(at(600)) - 0.4).abs() < 1e-12);
+    assert!((signal.effective_confidence(at(1200)) - 0.2).abs() < 1e-12);
+    // A signal from the future is not boosted
+    assert!((signal.effective_confidence(at(-600)) - 0.8).abs() < 1e-12);
+
+    assert!(!signal.is_expired(at(1199)));
+    assert!(signal.is_expired(at(1201)));
+}
+
+#[test]
+fn signals_without_decay_keep_confidence() {
+    let mut signal = decaying_signal(0.8, 600).with_decay(None);
+    let much_later = signal.timestamp + Duration::days(30);
+    assert_eq!(signal.effective_confidence(much_later), 0.8);
+    assert!(!signal.is_expired(much_later));
+
+    signal = signal.with_decay(Some(DecayConfig {
+        half_life_seconds: 0,
+        min_display_threshold: DEFAULT_MIN_DISPLAY_THRESHOLD,
+    }));
+    assert_eq!(signal.effective_confidence(much_later), 0.8);
+}
+
+#[test]
+fn decay_threshold_defaults_when_omitted() {
+    let decay: DecayConfig = serde_json::from_str(r#"{"half_life_seconds": 60}"#).unwrap();
+    assert_eq!(decay.min_display_threshold, DEFAULT_MIN_DISPLAY_THRESHOLD);
+
+    // Signals serialized before decay existed still deserialize
+    let json = serde_json::to_value(SignalOutput::new_neutral("BTC".to_string(), 1.0)).unwrap();
+    assert!(json.get("decay").is_none());
+    let signal: SignalOutput = serde_json::from_value(json).unwrap();
+    assert!(signal.decay.is_none());
+}
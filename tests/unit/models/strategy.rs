@@ -0,0 +1,164 @@
+//! Unit tests for strategy position sizing, SL/TP models and time filters.
+
+use perptrix::models::strategy::{
+    PositionSizeModel, SlTpModel, StrategyConfig, TimeWindow, DEFAULT_MAX_KELLY_FRACTION,
+};
+
+/// Kelly fraction at 60% confidence with TP twice the SL: (0.6 × 2 - 0.4) / 2
+const KELLY_60_2R: f64 = 0.4;
+
+fn assert_close(actual: Option<f64>, expected: f64) {
+    let actual = actual.expect("a position size");
+    assert!(
+        (actual - expected).abs() < 1e-12,
+        "expected {}, got {}",
+        expected,
+        actual
+    );
+}
+
+#[test]
+fn full_kelly_is_capped_at_max_fraction() {
+    let model = PositionSizeModel::FullKelly;
+    assert_close(model.fraction(0.6, 1.0, 2.0, 1.0), KELLY_60_2R);
+    assert_close(
+        model.fraction(0.6, 1.0, 2.0, DEFAULT_MAX_KELLY_FRACTION),
+        0.25,
+    );
+}
+
+#[test]
+fn half_kelly_halves_the_kelly_fraction() {
+    let model = PositionSizeModel::HalfKelly;
+    assert_close(model.fraction(0.6, 1.0, 2.0, 1.0), KELLY_60_2R / 2.0);
+    assert_close(model.fraction(0.6, 1.0, 2.0, 0.1), 0.1);
+}
+
+#[test]
+fn quarter_kelly_quarters_the_kelly_fraction() {
+    let model = PositionSizeModel::QuarterKelly;
+    assert_close(model.fraction(0.6, 1.0, 2.0, 1.0), KELLY_60_2R / 4.0);
+    // Only the SL/TP ratio matters, not the units
+    assert_close(model.fraction(0.6, 1.5, 3.0, 1.0), KELLY_60_2R / 4.0);
+}
+
+#[test]
+fn fixed_ignores_confidence_and_sl_tp() {
+    let model = PositionSizeModel::Fixed(0.02);
+    assert_close(model.fraction(0.9, 1.0, 2.0, 0.25), 0.02);
+    assert_close(model.fraction(0.1, 0.0, 0.0, 0.25), 0.02);
+}
+
+#[test]
+fn negative_edge_recommends_no_position() {
+    // 30% confidence at 1:1 loses on average
+    for model in [
+        PositionSizeModel::FullKelly,
+        PositionSizeModel::HalfKelly,
+        PositionSizeModel::QuarterKelly,
+    ] {
+        assert_close(model.fraction(0.3, 1.0, 1.0, 0.25), 0.0);
+    }
+}
+
+#[test]
+fn kelly_needs_both_stop_loss_and_take_profit() {
+    for model in [
+        PositionSizeModel::FullKelly,
+        PositionSizeModel::HalfKelly,
+        PositionSizeModel::QuarterKelly,
+    ] {
+        assert_eq!(model.fraction(0.8, 0.0, 2.0, 0.25), None);
+        assert_eq!(model.fraction(0.8, 1.0, 0.0, 0.25), None);
+        assert_eq!(model.fraction(0.8, 0.0, 0.0, 0.25), None);
+    }
+}
+
+#[test]
+fn sizing_defaults_when_omitted_from_config() {
+    let config: StrategyConfig = serde_json::from_value(serde_json::json!({
+        "rules": [],
+        "aggregation": {
+            "method": "Sum",
+            "thresholds": { "long_min": 1, "short_max": -1 }
+        }
+    }))
+    .unwrap();
+    assert_eq!(config.position_size_model, PositionSizeModel::FullKelly);
+    assert_eq!(config.max_kelly_fraction, DEFAULT_MAX_KELLY_FRACTION);
+
+    let fixed: PositionSizeModel = serde_json::from_str(r#"{"Fixed": 0.05}"#).unwrap();
+    assert_eq!(fixed, PositionSizeModel::Fixed(0.05));
+    let half: PositionSizeModel = serde_json::from_str(r#""HalfKelly""#).unwrap();
+    assert_eq!(half, PositionSizeModel::HalfKelly);
+}
+
+#[test]
+fn sl_tp_model_defaults_when_omitted_from_config() {
+    let config: StrategyConfig = serde_json::from_value(serde_json::json!({
+        "rules": [],
+        "aggregation": {
+            "method": "Sum",
+            "thresholds": { "long_min": 1, "short_max": -1 }
+        }
+    }))
+    .unwrap();
+    assert_eq!(config.sl_tp_model, SlTpModel::default());
+
+    let model: SlTpModel =
+        serde_json::from_str(r#"{"RiskReward": {"sl_pct": 1.0, "rr_ratio": 2.0}}"#).unwrap();
+    assert_eq!(
+        model,
+        SlTpModel::RiskReward {
+            sl_pct: 1.0,
+            rr_ratio: 2.0
+        }
+    );
+}
+
+#[test]
+fn time_windows_wrap_past_midnight() {
+    let overnight = TimeWindow {
+        start_utc_hour: 22,
+        end_utc_hour: 2,
+        weight_multiplier: 0.5,
+    };
+    assert!(overnight.contains(23));
+    assert!(overnight.contains(0));
+    assert!(overnight.contains(1));
+    assert!(!overnight.contains(2));
+    assert!(!overnight.contains(21));
+
+    let asia = TimeWindow {
+        start_utc_hour: 0,
+        end_utc_hour: 8,
+        weight_multiplier: 0.0,
+    };
+    assert!(asia.contains(0));
+    assert!(!asia.contains(23));
+    assert!(!asia.contains(8));
+}
+
+#[test]
+fn overlapping_time_windows_multiply() {
+    let config = StrategyConfig {
+        time_filter: Some(vec![
+            TimeWindow {
+                start_utc_hour: 22,
+                end_utc_hour: 2,
+                weight_multiplier: 0.5,
+            },
+            TimeWindow {
+                start_utc_hour: 0,
+                end_utc_hour: 8,
+                weight_multiplier: 0.0,
+            },
+        ]),
+        ..Default::default()
+    };
+    assert_eq!(config.time_multiplier(21), 1.0);
+    assert_eq!(config.time_multiplier(23), 0.5);
+    assert_eq!(config.time_multiplier(0), 0.0);
+    assert_eq!(config.time_multiplier(8), 1.0);
+    assert_eq!(StrategyConfig::default().time_multiplier(0), 1.0);
+}
@@ -0,0 +1,32 @@
+//! Unit tests for Telegram message formatting and rate limiting.
+
+use perptrix::output::telegram::{confidence_bar, RateLimiter, RATE_LIMIT_WINDOW};
+use std::time::{Duration, Instant};
+
+#[test]
+fn confidence_bar_fills_proportionally() {
+    assert_eq!(confidence_bar(0.0), "░░░░░░░░░░");
+    assert_eq!(confidence_bar(0.64), "██████░░░░");
+    assert_eq!(confidence_bar(0.66), "███████░░░");
+    assert_eq!(confidence_bar(1.0), "██████████");
+    // Out-of-range confidences are clamped
+    assert_eq!(confidence_bar(1.5), "██████████");
+    assert_eq!(confidence_bar(-0.2), "░░░░░░░░░░");
+}
+
+#[test]
+fn rate_limiter_allows_one_message_per_symbol_per_window() {
+    let limiter = RateLimiter::new(RATE_LIMIT_WINDOW);
+    let start = Instant::now();
+
+    assert!(limiter.try_acquire("BTC", start));
+    assert!(!limiter.try_acquire("BTC", start + Duration::from_secs(59)));
+    assert!(limiter.try_acquire("ETH", start + Duration::from_secs(1)));
+    assert!(limiter.try_acquire("BTC", start + RATE_LIMIT_WINDOW));
+
+    // A released slot can be claimed again straight away
+    let later = start + Duration::from_secs(120);
+    assert!(limiter.try_acquire("SOL", later));
+    limiter.release("SOL");
+    assert!(limiter.try_acquire("SOL", later));
+}
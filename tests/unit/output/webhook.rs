@@ -0,0 +1,129 @@
+//! Unit tests for signed webhook delivery.
+
+use perptrix::models::signal::{SignalDirection, SignalOutput};
+use perptrix::output::webhook::{signature, WebhookNotifier, MAX_RETRIES, SIGNATURE_HEADER};
+use std::time::Duration;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn signal() -> SignalOutput {
+    SignalOutput::new(
+        SignalDirection::Long,
+        0.8,
+        1.5,
+        2.5,
+        Vec::new(),
+        "BTC".to_string(),
+        50_000.0,
+    )
+}
+
+async fn server_responding(status: u16) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/hook"))
+        .respond_with(ResponseTemplate::new(status))
+        .mount(&server)
+        .await;
+    server
+}
+
+fn notifier(server: &MockServer, secret: Option<&str>) -> WebhookNotifier {
+    WebhookNotifier::new(format!("{}/hook", server.uri()), secret.map(str::to_string))
+        .with_initial_backoff(Duration::from_millis(10))
+}
+
+#[test]
+fn signature_is_hex_hmac_sha256() {
+    // RFC 4231 test case 2
+    assert_eq!(
+        signature("Jefe", b"what do ya want for nothing?"),
+        "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+    );
+}
+
+#[tokio::test]
+async fn delivers_signal_json_with_valid_signature() {
+    let server = server_responding(200).await;
+    let webhook = notifier(&server, Some("s3cret"));
+    assert!(webhook.is_signed());
+
+    webhook.deliver(&signal()).await.expect("delivered");
+
+    let requests = server.received_requests().await.expect("wiremock requests");
+    assert_eq!(requests.len(), 1);
+    let request = &requests[0];
+    let header = request
+        .headers
+        .get(SIGNATURE_HEADER)
+        .expect("signature header")
+        .to_str()
+        .unwrap();
+    assert_eq!(header, signature("s3cret", &request.body));
+    assert_ne!(header, signature("other", &request.body));
+
+    let body: SignalOutput = serde_json::from_slice(&request.body).expect("signal JSON");
+    assert_eq!(body.symbol, "BTC");
+    assert_eq!(body.direction, SignalDirection::Long);
+    assert_eq!(body.price, 50_000.0);
+}
+
+#[tokio::test]
+async fn unsigned_without_secret() {
+    let server = server_responding(204).await;
+    notifier(&server, None)
+        .deliver(&signal())
+        .await
+        .expect("delivered");
+
+    let requests = server.received_requests().await.expect("wiremock requests");
+    assert!(requests[0].headers.get(SIGNATURE_HEADER).is_none());
+}
+
+#[tokio::test]
+async fn server_errors_are_retried_with_backoff() {
+    let server = server_responding(503).await;
+    let start = std::time::Instant::now();
+
+    let result = notifier(&server, Some("s3cret")).deliver(&signal()).await;
+
+    assert!(result.unwrap_err().to_string().contains("503"));
+    let requests = server.received_requests().await.expect("wiremock requests");
+    assert_eq!(requests.len(), 1 + MAX_RETRIES as usize);
+    // 10ms + 20ms + 40ms
+    assert!(start.elapsed() >= Duration::from_millis(70));
+}
+
+#[tokio::test]
+async fn recovers_when_a_retry_succeeds() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/hook"))
+        .respond_with(ResponseTemplate::new(500))
+        .up_to_n_times(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/hook"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    notifier(&server, None)
+        .deliver(&signal())
+        .await
+        .expect("delivered on the third attempt");
+    let requests = server.received_requests().await.expect("wiremock requests");
+    assert_eq!(requests.len(), 3);
+}
+
+#[tokio::test]
+async fn client_errors_are_not_retried() {
+    let server = server_responding(400).await;
+
+    let result = notifier(&server, Some("s3cret")).deliver(&signal()).await;
+
+    assert!(result.unwrap_err().to_string().contains("400"));
+    let requests = server.received_requests().await.expect("wiremock requests");
+    assert_eq!(requests.len(), 1);
+}
@@ -0,0 +1,44 @@
+//! Unit tests for WebSocket connection waiting.
+
+use perptrix::services::hyperliquid::{
+    HyperliquidClient, MockWebSocketClient, WebSocketClient, DEFAULT_CONNECTION_POLL_INTERVAL,
+};
+use std::time::{Duration, Instant};
+
+#[tokio::test]
+async fn wait_for_connection_returns_false_immediately_with_zero_timeout() {
+    let client = HyperliquidClient::with_url("ws://127.0.0.1:9".to_string());
+    let start = Instant::now();
+
+    assert!(
+        !client
+            .wait_for_connection(Duration::ZERO, DEFAULT_CONNECTION_POLL_INTERVAL)
+            .await
+    );
+    assert!(start.elapsed() < DEFAULT_CONNECTION_POLL_INTERVAL);
+    assert!(client.connection_established_at().await.is_none());
+}
+
+#[tokio::test]
+async fn wait_for_connection_or_err_times_out() {
+    let client = MockWebSocketClient::new();
+    let err = client
+        .wait_for_connection_or_err(Duration::from_millis(20))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("not established"));
+}
+
+#[tokio::test]
+async fn connect_records_connection_time() {
+    let client = MockWebSocketClient::new();
+    assert!(client.connection_established_at().await.is_none());
+
+    client.connect().await.expect("mock connect succeeds");
+    assert!(
+        client
+            .wait_for_connection(Duration::from_secs(1), Duration::from_millis(10))
+            .await
+    );
+    assert!(client.connection_established_at().await.is_some());
+}
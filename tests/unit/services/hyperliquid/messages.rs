@@ -0,0 +1,115 @@
+//! Unit tests for Hyperliquid WebSocket message parsing.
+
+use perptrix::models::indicators::TradeSide;
+use perptrix::services::hyperliquid::messages::{
+    RequestMessage, Subscription, SubscriptionResponse, WebSocketMessage,
+};
+use serde_json::json;
+
+#[test]
+fn l2_book_message_yields_top_of_book() {
+    let message = json!({
+        "channel": "l2Book",
+        "data": {
+            "coin": "BTC",
+            "time": 1_700_000_000_000u64,
+            "levels": [
+                [
+                    {"px": "64999.5", "sz": "1.2", "n": 3},
+                    {"px": "65000.0", "sz": "0.4", "n": 1},
+                    {"px": "bad", "sz": "9.9", "n": 1}
+                ],
+                [
+                    {"px": "65001.0", "sz": "0.8", "n": 2},
+                    {"px": "65002.5", "sz": "2.0", "n": 4}
+                ]
+            ]
+        }
+    });
+
+    let WebSocketMessage::L2BookData(book) = serde_json::from_value(message).unwrap() else {
+        panic!("expected an l2Book message");
+    };
+    assert_eq!(book.data.coin, "BTC");
+    assert_eq!(book.data.levels.0.len(), 3);
+    assert_eq!(book.data.top_of_book(), Some((65000.0, 65001.0)));
+}
+
+#[test]
+fn l2_book_without_asks_has_no_top_of_book() {
+    let message = json!({
+        "channel": "l2Book",
+        "data": {"coin": "ETH", "time": 0, "levels": [[{"px": "3000", "sz": "1", "n": 1}], []]}
+    });
+    let WebSocketMessage::L2BookData(book) = serde_json::from_value(message).unwrap() else {
+        panic!("expected an l2Book message");
+    };
+    assert_eq!(book.data.best_bid(), Some(3000.0));
+    assert_eq!(book.data.top_of_book(), None);
+}
+
+#[test]
+fn l2_book_subscription_round_trips() {
+    let request = RequestMessage::Subscribe {
+        subscription: Subscription::l2_book("SOL"),
+    };
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        json!({"method": "subscribe", "subscription": {"type": "l2Book", "coin": "SOL"}})
+    );
+
+    let response: SubscriptionResponse = serde_json::from_value(json!({
+        "channel": "subscriptionResponse",
+        "data": {"method": "subscribe", "subscription": {"type": "l2Book", "coin": "SOL"}}
+    }))
+    .unwrap();
+    assert!(
+        matches!(response.data.subscription, Subscription::L2Book { ref coin, .. } if coin == "SOL")
+    );
+}
+
+#[test]
+fn trades_message_yields_parsed_trades() {
+    let message = json!({
+        "channel": "trades",
+        "data": [
+            {"coin": "BTC", "side": "B", "px": "65000.5", "sz": "0.25", "hash": "0x0", "time": 1_700_000_000_000u64, "tid": 1, "users": ["0xa", "0xb"]},
+            {"coin": "BTC", "side": "A", "px": "64999.0", "sz": "1.5", "hash": "0x0", "time": 1_700_000_000_500u64, "tid": 2, "users": ["0xc", "0xd"]},
+            {"coin": "BTC", "side": "X", "px": "64999.0", "sz": "1", "hash": "0x0", "time": 0, "tid": 3, "users": []}
+        ]
+    });
+
+    let WebSocketMessage::TradesData(trades) = serde_json::from_value(message).unwrap() else {
+        panic!("expected a trades message");
+    };
+    assert_eq!(trades.data.len(), 3);
+
+    let buy = trades.data[0].to_trade().unwrap();
+    assert_eq!(buy.side, TradeSide::Buy);
+    assert_eq!(buy.price, 65000.5);
+    assert_eq!(buy.size, 0.25);
+    assert_eq!(buy.timestamp.timestamp_millis(), 1_700_000_000_000);
+    assert_eq!(trades.data[1].to_trade().unwrap().side, TradeSide::Sell);
+    // Unknown aggressor side
+    assert_eq!(trades.data[2].to_trade(), None);
+}
+
+#[test]
+fn trades_subscription_is_not_mistaken_for_l2_book() {
+    let request = RequestMessage::Subscribe {
+        subscription: Subscription::trades("ETH"),
+    };
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        json!({"method": "subscribe", "subscription": {"type": "trades", "coin": "ETH"}})
+    );
+
+    let response: SubscriptionResponse = serde_json::from_value(json!({
+        "channel": "subscriptionResponse",
+        "data": {"method": "subscribe", "subscription": {"type": "trades", "coin": "ETH"}}
+    }))
+    .unwrap();
+    assert!(
+        matches!(response.data.subscription, Subscription::Trades { ref coin, .. } if coin == "ETH")
+    );
+}
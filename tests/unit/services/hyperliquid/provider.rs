@@ -0,0 +1,63 @@
+//! Unit tests for the provider's in-memory candle buffer.
+
+use crate::test_utils::minute;
+use chrono::{DateTime, Utc};
+use perptrix::models::indicators::Candle;
+use perptrix::indicators::structure::gap_fill::DEFAULT_MAX_GAP_FILL_CANDLES;
+use perptrix::services::hyperliquid::{EvictionPolicy, ProviderConfig};
+use std::collections::VecDeque;
+
+fn candle(close: f64, timestamp: DateTime<Utc>) -> Candle {
+    Candle::new(close, close + 1.0, close - 1.0, close, 10.0, timestamp)
+}
+
+#[test]
+fn drop_oldest_never_exceeds_the_cap() {
+    let mut buffer = VecDeque::new();
+    for i in 0..50 {
+        EvictionPolicy::DropOldest.insert(&mut buffer, candle(i as f64, minute(i)), 10);
+        assert!(buffer.len() <= 10);
+    }
+
+    assert_eq!(buffer.len(), 10);
+    assert_eq!(buffer.front().unwrap().timestamp, minute(40));
+    assert_eq!(buffer.back().unwrap().timestamp, minute(49));
+
+    // Repeated timestamps are appended, still within the cap
+    for close in 0..20 {
+        EvictionPolicy::DropOldest.insert(&mut buffer, candle(close as f64, minute(49)), 10);
+    }
+    assert_eq!(buffer.len(), 10);
+    assert!(buffer.iter().all(|c| c.timestamp == minute(49)));
+}
+
+#[test]
+fn drop_if_duplicate_replaces_matching_timestamps() {
+    let mut buffer = VecDeque::new();
+    for i in 0..5 {
+        EvictionPolicy::DropIfDuplicate.insert(&mut buffer, candle(i as f64, minute(i)), 10);
+    }
+
+    EvictionPolicy::DropIfDuplicate.insert(&mut buffer, candle(99.0, minute(4)), 10);
+    assert_eq!(buffer.len(), 5);
+    assert_eq!(buffer.back().unwrap().close, 99.0);
+    assert_eq!(
+        buffer.iter().filter(|c| c.timestamp == minute(4)).count(),
+        1
+    );
+
+    for i in 5..20 {
+        EvictionPolicy::DropIfDuplicate.insert(&mut buffer, candle(i as f64, minute(i)), 10);
+    }
+    assert_eq!(buffer.len(), 10);
+    assert_eq!(buffer.front().unwrap().timestamp, minute(10));
+}
+
+#[test]
+fn default_config_keeps_previous_behavior() {
+    let config = ProviderConfig::default();
+    assert_eq!(config.max_candles_per_symbol, 1000);
+    assert_eq!(config.eviction_policy, EvictionPolicy::DropIfDuplicate);
+    assert_eq!(config.candle_intervals, vec!["1m", "5m", "15m", "1h"]);
+    assert_eq!(config.max_gap_fill_candles, DEFAULT_MAX_GAP_FILL_CANDLES);
+}
@@ -1,6 +1,10 @@
 //! Unit tests for market data provider
 
-use perptrix::services::market_data::{MarketDataProvider, PlaceholderMarketDataProvider};
+use chrono::Utc;
+use perptrix::models::indicators::Candle;
+use perptrix::services::market_data::{
+    MarketDataProvider, MockMarketDataProvider, PlaceholderMarketDataProvider,
+};
 
 #[tokio::test]
 async fn test_placeholder_provider() {
@@ -12,3 +16,34 @@ async fn test_placeholder_provider() {
 
 
 
+
+fn candle(close: f64) -> Candle {
+    Candle::new(close, close + 1.0, close - 1.0, close, 100.0, Utc::now())
+}
+
+#[tokio::test]
+async fn mock_provider_returns_preloaded_candles() {
+    let candles: Vec<Candle> = (0..10).map(|i| candle(100.0 + i as f64)).collect();
+    let provider = MockMarketDataProvider::new().with_candles("BTC", candles);
+
+    let latest = provider.get_candles("BTC", 3).await.unwrap();
+    assert_eq!(latest.len(), 3);
+    assert_eq!(latest[0].close, 107.0);
+    assert_eq!(latest[2].close, 109.0);
+
+    let all = provider.get_candles("BTC", 500).await.unwrap();
+    assert_eq!(all.len(), 10);
+
+    assert!(provider.get_candles("ETH", 10).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn mock_provider_prefers_explicit_price() {
+    let provider = MockMarketDataProvider::new()
+        .with_candles("BTC", vec![candle(100.0), candle(101.0)])
+        .with_price("ETH", 2500.0);
+
+    assert_eq!(provider.get_latest_price("ETH").await.unwrap(), 2500.0);
+    assert_eq!(provider.get_latest_price("BTC").await.unwrap(), 101.0);
+    assert!(provider.get_latest_price("SOL").await.is_err());
+}
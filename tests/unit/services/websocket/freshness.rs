@@ -0,0 +1,57 @@
+//! Unit tests for tracking candle feed freshness.
+
+use perptrix::services::websocket::freshness::{default_max_candle_age, DataFreshnessMonitor};
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[test]
+fn default_max_age_is_two_intervals() {
+    assert_eq!(default_max_candle_age(60), Duration::from_secs(120));
+    assert_eq!(default_max_candle_age(3600), Duration::from_secs(7200));
+    assert_eq!(default_max_candle_age(0), Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn silent_symbols_are_reported_once_per_max_age() {
+    let monitor = DataFreshnessMonitor::new();
+    let max_age = Duration::from_millis(100);
+    monitor.watch("BTC").await;
+    monitor.watch("ETH").await;
+    assert!(monitor.take_stale_symbols(max_age).await.is_empty());
+
+    sleep(Duration::from_millis(60)).await;
+    monitor.record_candle("ETH").await;
+    sleep(Duration::from_millis(60)).await;
+
+    let stale = monitor.take_stale_symbols(max_age).await;
+    assert_eq!(stale.len(), 1);
+    assert_eq!(stale[0].0, "BTC");
+    assert!(stale[0].1 > max_age);
+    // BTC gets another max age before it is reported again
+    assert!(monitor.take_stale_symbols(max_age).await.is_empty());
+
+    sleep(Duration::from_millis(120)).await;
+    let stale: Vec<String> = monitor
+        .take_stale_symbols(max_age)
+        .await
+        .into_iter()
+        .map(|(symbol, _)| symbol)
+        .collect();
+    assert_eq!(stale, vec!["BTC".to_string(), "ETH".to_string()]);
+}
+
+#[tokio::test]
+async fn watching_does_not_reset_a_tracked_symbol() {
+    let monitor = DataFreshnessMonitor::new();
+    assert_eq!(monitor.last_candle_received_at("BTC").await, None);
+
+    monitor.record_candle("BTC").await;
+    let received_at = monitor.last_candle_received_at("BTC").await;
+    sleep(Duration::from_millis(5)).await;
+    monitor.watch("BTC").await;
+    assert_eq!(monitor.last_candle_received_at("BTC").await, received_at);
+
+    // Clones share timestamps
+    monitor.clone().record_candle("BTC").await;
+    assert!(monitor.last_candle_received_at("BTC").await > received_at);
+}
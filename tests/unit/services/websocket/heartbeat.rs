@@ -0,0 +1,20 @@
+//! Unit tests for the WebSocket service heartbeat read by `/health`.
+
+use perptrix::services::websocket::WebSocketHeartbeat;
+use std::time::Duration;
+
+#[test]
+fn heartbeat_outlives_a_few_missed_intervals() {
+    assert_eq!(WebSocketHeartbeat::ttl_seconds(Duration::from_secs(30)), 90);
+    // Sub-second intervals still keep the heartbeat for a few seconds
+    assert_eq!(WebSocketHeartbeat::ttl_seconds(Duration::from_millis(200)), 3);
+}
+
+#[test]
+fn heartbeat_round_trips_through_json() {
+    let heartbeat = WebSocketHeartbeat::new(true);
+    let json = serde_json::to_string(&heartbeat).unwrap();
+    let parsed: WebSocketHeartbeat = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, heartbeat);
+    assert!(json.contains("\"connected\":true"));
+}
@@ -0,0 +1,93 @@
+//! Unit tests for WebSocket heartbeat tracking and reconnection backoff.
+
+use perptrix::services::websocket::reconnect::{
+    ConnectionConfig, HeartbeatMonitor, ReconnectBackoff, DEFAULT_HEARTBEAT_INTERVAL_SECS,
+    DEFAULT_MAX_BACKOFF_SECS, MAX_MISSED_PONGS, RECONNECT_INITIAL_DELAY,
+};
+use std::time::Duration;
+
+#[test]
+fn base_delay_doubles_up_to_the_cap() {
+    let backoff = ReconnectBackoff::from_config(&ConnectionConfig::default());
+    let delays: Vec<u64> = (0..9)
+        .map(|attempt| backoff.base_delay(attempt).as_secs())
+        .collect();
+    assert_eq!(delays, vec![1, 2, 4, 8, 16, 32, 60, 60, 60]);
+
+    // Far past the cap the multiplication must not overflow
+    assert_eq!(backoff.base_delay(u32::MAX), Duration::from_secs(60));
+}
+
+#[test]
+fn jitter_stays_within_twenty_percent_and_under_the_cap() {
+    let backoff = ReconnectBackoff::new(Duration::from_secs(1), Duration::from_secs(60));
+    let base = Duration::from_secs(10);
+    assert_eq!(backoff.jittered(base, -1.0), Duration::from_secs(8));
+    assert_eq!(backoff.jittered(base, 0.0), base);
+    assert_eq!(backoff.jittered(base, 1.0), Duration::from_secs(12));
+    // Out-of-range samples are clamped
+    assert_eq!(backoff.jittered(base, 5.0), Duration::from_secs(12));
+
+    let capped = Duration::from_secs(60);
+    assert_eq!(backoff.jittered(capped, 1.0), capped);
+    assert_eq!(backoff.jittered(capped, -1.0), Duration::from_secs(48));
+}
+
+#[test]
+fn next_delay_counts_attempts_and_resets() {
+    let mut backoff = ReconnectBackoff::new(RECONNECT_INITIAL_DELAY, Duration::from_secs(60));
+    for attempt in 0..10 {
+        let base = backoff.base_delay(attempt);
+        let delay = backoff.next_delay();
+        assert!(
+            delay >= base.mul_f64(0.8) && delay <= base.mul_f64(1.2).min(Duration::from_secs(60))
+        );
+        assert_eq!(backoff.attempts(), attempt + 1);
+    }
+
+    backoff.reset();
+    assert_eq!(backoff.attempts(), 0);
+    let delay = backoff.next_delay();
+    assert!(delay >= Duration::from_millis(800) && delay <= Duration::from_millis(1200));
+}
+
+#[test]
+fn next_delay_is_jittered() {
+    let delays: Vec<Duration> = (0..20)
+        .map(|_| {
+            ReconnectBackoff::new(Duration::from_secs(10), Duration::from_secs(60)).next_delay()
+        })
+        .collect();
+    assert!(delays.iter().any(|delay| *delay != delays[0]));
+}
+
+#[test]
+fn heartbeat_allows_two_missed_pongs() {
+    let heartbeat = HeartbeatMonitor::default();
+    assert_eq!(MAX_MISSED_PONGS, 2);
+
+    assert!(heartbeat.ping());
+    assert!(heartbeat.ping());
+    assert_eq!(heartbeat.unanswered(), 2);
+    assert!(!heartbeat.ping());
+
+    let heartbeat = HeartbeatMonitor::default();
+    assert!(heartbeat.ping());
+    assert!(heartbeat.ping());
+    heartbeat.pong();
+    assert_eq!(heartbeat.unanswered(), 0);
+    assert!(heartbeat.ping());
+    assert!(heartbeat.ping());
+}
+
+#[test]
+fn connection_config_defaults() {
+    let config = ConnectionConfig::default();
+    assert_eq!(
+        config.heartbeat_interval_secs,
+        DEFAULT_HEARTBEAT_INTERVAL_SECS
+    );
+    assert_eq!(config.heartbeat_interval(), Duration::from_secs(30));
+    assert_eq!(config.max_backoff_secs, DEFAULT_MAX_BACKOFF_SECS);
+    assert_eq!(config.max_backoff(), Duration::from_secs(60));
+}
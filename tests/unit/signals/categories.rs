@@ -0,0 +1,218 @@
+//! Unit tests for strategy-derived category weights.
+
+use chrono::{Duration, Utc};
+use perptrix::config::CategoryWeights;
+use perptrix::indicators::registry::{IndicatorCategory, IndicatorRegistry};
+use perptrix::models::indicators::Candle;
+use perptrix::models::signal::SignalDirection;
+use perptrix::models::strategy::{
+    AggregationConfig, AggregationMethod, Comparison, Condition, IndicatorType, LogicalOperator,
+    PositionSizeModel, Rule, RuleType, SignalThresholds, SlTpModel, Strategy, StrategyConfig,
+    DEFAULT_MAX_KELLY_FRACTION,
+};
+use perptrix::strategies::StrategyEvaluator;
+use std::collections::HashMap;
+
+fn condition_rule(id: &str, indicator: IndicatorType) -> Rule {
+    Rule {
+        id: id.to_string(),
+        rule_type: RuleType::Condition,
+        weight: Some(1.0),
+        operator: None,
+        condition: Some(Condition {
+            indicator,
+            indicator_params: HashMap::new(),
+            comparison: Comparison::GreaterThan,
+            threshold: Some(0.0),
+            signal_state: None,
+        }),
+        children: None,
+    }
+}
+
+fn config(rules: Vec<Rule>, category_weights: Option<HashMap<String, f64>>) -> StrategyConfig {
+    StrategyConfig {
+        rules,
+        aggregation: AggregationConfig {
+            method: AggregationMethod::Sum,
+            thresholds: SignalThresholds {
+                long_min: 1,
+                short_max: -1,
+            },
+            filter: None,
+        },
+        category_weights,
+        timeframe_weights: None,
+        decay_model: None,
+        position_size_model: PositionSizeModel::default(),
+        max_kelly_fraction: DEFAULT_MAX_KELLY_FRACTION,
+        sl_tp_model: SlTpModel::default(),
+        time_filter: None,
+        use_heikin_ashi: false,
+    }
+}
+
+#[test]
+fn momentum_only_strategy_puts_all_weight_on_momentum() {
+    let config = config(
+        vec![
+            condition_rule("rsi", IndicatorType::RSI),
+            condition_rule("macd", IndicatorType::MACD),
+        ],
+        None,
+    );
+    let registry = IndicatorRegistry::with_weights(CategoryWeights::from_strategy_config(&config));
+
+    assert_eq!(registry.category_weight(IndicatorCategory::Momentum), 1.0);
+    assert_eq!(registry.category_weight(IndicatorCategory::Trend), 0.0);
+    assert_eq!(registry.category_weight(IndicatorCategory::Volatility), 0.0);
+    assert_eq!(registry.category_weight(IndicatorCategory::Volume), 0.0);
+    assert_eq!(registry.category_weight(IndicatorCategory::Perp), 0.0);
+}
+
+#[test]
+fn weights_are_proportional_to_nested_rule_counts() {
+    let group = Rule {
+        id: "group".to_string(),
+        rule_type: RuleType::Group,
+        weight: None,
+        operator: Some(LogicalOperator::AND),
+        condition: None,
+        children: Some(vec![
+            condition_rule("ema", IndicatorType::EMA),
+            condition_rule("supertrend", IndicatorType::SuperTrend),
+            condition_rule("funding", IndicatorType::FundingRate),
+        ]),
+    };
+    let config = config(vec![condition_rule("rsi", IndicatorType::RSI), group], None);
+    let weights = CategoryWeights::from_strategy_config(&config);
+
+    assert!((weights.momentum - 0.25).abs() < 1e-9);
+    assert!((weights.trend - 0.5).abs() < 1e-9);
+    assert!((weights.perp - 0.25).abs() < 1e-9);
+    assert_eq!(weights.volatility, 0.0);
+    assert_eq!(weights.volume, 0.0);
+}
+
+#[test]
+fn manual_override_is_normalized() {
+    let overrides = HashMap::from([("Momentum".to_string(), 3.0), ("trend".to_string(), 1.0)]);
+    let config = config(vec![condition_rule("obv", IndicatorType::OBV)], Some(overrides));
+    let weights = CategoryWeights::from_strategy_config(&config);
+
+    assert!((weights.momentum - 0.75).abs() < 1e-9);
+    assert!((weights.trend - 0.25).abs() < 1e-9);
+    assert_eq!(weights.volume, 0.0);
+}
+
+#[test]
+fn strategy_without_conditions_uses_defaults() {
+    let weights = CategoryWeights::from_strategy_config(&config(vec![], None));
+    let defaults = CategoryWeights::default();
+    assert_eq!(weights.momentum, defaults.momentum);
+    assert_eq!(weights.trend, defaults.trend);
+}
+
+fn weights_summing_to_point_nine() -> CategoryWeights {
+    CategoryWeights {
+        momentum: 0.3,
+        trend: 0.3,
+        volatility: 0.1,
+        volume: 0.1,
+        perp: 0.1,
+    }
+}
+
+#[test]
+fn validate_rejects_weights_not_summing_to_one() {
+    let weights = weights_summing_to_point_nine();
+    assert!(weights.validate().is_err());
+    assert!(IndicatorRegistry::try_with_weights(weights).is_err());
+    assert!(CategoryWeights::default().validate().is_ok());
+}
+
+#[test]
+fn validate_rejects_negative_weights() {
+    let weights = CategoryWeights {
+        momentum: 1.2,
+        trend: -0.2,
+        volatility: 0.0,
+        volume: 0.0,
+        perp: 0.0,
+    };
+    assert!(weights.validate().is_err());
+}
+
+#[test]
+fn scale_corrects_weights_not_summing_to_one() {
+    let scaled = weights_summing_to_point_nine().scale();
+    assert!(scaled.validate().is_ok());
+    assert!((scaled.momentum - 0.3 / 0.9).abs() < 1e-9);
+    assert!((scaled.perp - 0.1 / 0.9).abs() < 1e-9);
+}
+
+#[test]
+fn rule_multipliers_compare_manual_weights_with_the_rule_mix() {
+    let rules = vec![
+        condition_rule("rsi", IndicatorType::RSI),
+        condition_rule("macd", IndicatorType::MACD),
+        condition_rule("ema", IndicatorType::EMA),
+    ];
+    assert!(CategoryWeights::rule_multipliers(&config(rules.clone(), None)).is_none());
+
+    // Momentum is 2/3 of the conditions and trend 1/3
+    let overrides = HashMap::from([("momentum".to_string(), 1.0), ("trend".to_string(), 1.0)]);
+    let multipliers = CategoryWeights::rule_multipliers(&config(rules, Some(overrides))).unwrap();
+    assert!((multipliers["rsi"] - 0.75).abs() < 1e-9);
+    assert!((multipliers["macd"] - 0.75).abs() < 1e-9);
+    assert!((multipliers["ema"] - 1.5).abs() < 1e-9);
+}
+
+#[test]
+fn manual_category_weights_scale_the_aggregated_score() {
+    let start = Utc::now() - Duration::minutes(100);
+    let candles: Vec<Candle> = (0..100)
+        .map(|i| {
+            let price = 100.0 + (i % 5) as f64;
+            Candle::new(
+                price,
+                price + 1.0,
+                price - 1.0,
+                price,
+                1_000.0,
+                start + Duration::minutes(i),
+            )
+        })
+        .collect();
+    // A passing momentum rule (+1) and a failing trend rule (-1) cancel out
+    let mut trend_rule = condition_rule("ema_high", IndicatorType::EMA);
+    trend_rule.condition.as_mut().unwrap().threshold = Some(1e9);
+    let rules = vec![
+        condition_rule("rsi_above_zero", IndicatorType::RSI),
+        trend_rule,
+    ];
+    let direction = |category_weights: Option<HashMap<String, f64>>| {
+        let strategy = Strategy {
+            id: None,
+            name: "weighted".to_string(),
+            symbol: "BTC".to_string(),
+            config: config(rules.clone(), category_weights),
+            enabled: true,
+            source_file: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        StrategyEvaluator::evaluate_strategy(&strategy, &candles).map(|signal| signal.direction)
+    };
+    assert_eq!(direction(None), Some(SignalDirection::Neutral));
+
+    // Momentum at 0.75 against its 0.5 share of the conditions scales it by
+    // 1.5, and trend at 0.25 by 0.5: 1.5 - 0.5 reaches the long threshold
+    let momentum_heavy =
+        HashMap::from([("momentum".to_string(), 0.75), ("trend".to_string(), 0.25)]);
+    assert_eq!(direction(Some(momentum_heavy)), Some(SignalDirection::Long));
+
+    // Weights matching the rules' own mix change nothing
+    let matching = HashMap::from([("momentum".to_string(), 1.0), ("trend".to_string(), 1.0)]);
+    assert_eq!(direction(Some(matching)), Some(SignalDirection::Neutral));
+}
@@ -0,0 +1,93 @@
+//! Unit tests for signal clustering.
+
+use chrono::{Duration, Utc};
+use perptrix::models::signal::{SignalDirection, SignalOutput};
+use perptrix::signals::clustering::SignalCluster;
+
+/// A BTC signal fired `seconds_ago` seconds before now
+fn signal(direction: SignalDirection, confidence: f64, seconds_ago: i64) -> SignalOutput {
+    let mut signal = SignalOutput::new(
+        direction,
+        confidence,
+        0.02,
+        0.04,
+        vec![],
+        "BTC".to_string(),
+        100.0,
+    );
+    signal.timestamp = Utc::now() - Duration::seconds(seconds_ago);
+    signal
+}
+
+#[test]
+fn no_signals_have_no_consensus() {
+    let analysis = SignalCluster::analyze(&[], 900);
+    assert_eq!(analysis.cluster_direction, SignalDirection::Neutral);
+    assert_eq!(analysis.agreement_ratio, 0.0);
+    assert_eq!(analysis.avg_confidence, 0.0);
+    assert_eq!(analysis.signal_count, 0);
+}
+
+#[test]
+fn mixed_batch_clusters_on_the_most_common_direction() {
+    let signals = vec![
+        signal(SignalDirection::Long, 0.8, 0),
+        signal(SignalDirection::Short, 0.9, 60),
+        signal(SignalDirection::Long, 0.6, 120),
+        signal(SignalDirection::Neutral, 0.3, 180),
+        signal(SignalDirection::Long, 0.7, 240),
+    ];
+
+    let analysis = SignalCluster::analyze(&signals, 900);
+    assert_eq!(analysis.cluster_direction, SignalDirection::Long);
+    assert_eq!(analysis.signal_count, 5);
+    assert!((analysis.agreement_ratio - 0.6).abs() < 1e-9);
+    // Only the long signals count towards the confidence
+    assert!((analysis.avg_confidence - 0.7).abs() < 1e-9);
+}
+
+#[test]
+fn signals_outside_the_window_are_ignored() {
+    let signals = vec![
+        signal(SignalDirection::Short, 0.5, 3600),
+        signal(SignalDirection::Long, 0.8, 0),
+        signal(SignalDirection::Short, 0.6, 3700),
+        signal(SignalDirection::Long, 0.6, 300),
+        signal(SignalDirection::Short, 0.7, 3800),
+    ];
+
+    let analysis = SignalCluster::analyze(&signals, 900);
+    assert_eq!(analysis.cluster_direction, SignalDirection::Long);
+    assert_eq!(analysis.signal_count, 2);
+    assert_eq!(analysis.agreement_ratio, 1.0);
+    assert!((analysis.avg_confidence - 0.7).abs() < 1e-9);
+}
+
+#[test]
+fn window_is_measured_back_from_the_newest_signal() {
+    // Both signals are hours old but within a minute of each other
+    let signals = vec![
+        signal(SignalDirection::Short, 0.8, 7200),
+        signal(SignalDirection::Short, 0.6, 7230),
+    ];
+
+    let analysis = SignalCluster::analyze(&signals, 60);
+    assert_eq!(analysis.cluster_direction, SignalDirection::Short);
+    assert_eq!(analysis.signal_count, 2);
+}
+
+#[test]
+fn tied_directions_are_neutral() {
+    let signals = vec![
+        signal(SignalDirection::Long, 0.9, 0),
+        signal(SignalDirection::Short, 0.9, 30),
+        signal(SignalDirection::Long, 0.7, 60),
+        signal(SignalDirection::Short, 0.7, 90),
+    ];
+
+    let analysis = SignalCluster::analyze(&signals, 900);
+    assert_eq!(analysis.cluster_direction, SignalDirection::Neutral);
+    assert_eq!(analysis.agreement_ratio, 0.0);
+    assert_eq!(analysis.avg_confidence, 0.0);
+    assert_eq!(analysis.signal_count, 4);
+}
@@ -1,7 +1,10 @@
 //! Unit tests for signal decision logic
 
 use perptrix::models::signal::SignalDirection;
+use perptrix::models::signal::SignalDirection::{Long, Neutral, Short};
+use perptrix::models::strategy::SlTpModel;
 use perptrix::signals::decision::{DirectionThresholds, StopLossTakeProfit};
+use perptrix::strategies::evaluator::IndicatorValues;
 
 #[test]
 fn test_determine_direction_long() {
@@ -46,5 +49,119 @@ fn test_calculate_sl_tp() {
     assert_eq!(tp, 20.0);
 }
 
+fn assert_sl_tp(actual: (f64, f64), expected: (f64, f64)) {
+    assert!(
+        (actual.0 - expected.0).abs() < 1e-9 && (actual.1 - expected.1).abs() < 1e-9,
+        "expected {:?}, got {:?}",
+        expected,
+        actual
+    );
+}
+
+fn sl_tp(
+    model: &SlTpModel,
+    direction: SignalDirection,
+    values: &IndicatorValues,
+    price: f64,
+) -> (f64, f64) {
+    StopLossTakeProfit::from_model(model, direction, values, price)
+}
+
+fn values_with_atr(atr: Option<f64>) -> IndicatorValues {
+    let mut values = IndicatorValues::new(200.0);
+    values.atr_value = atr;
+    values
+}
+
+#[test]
+fn atr_sl_tp_scales_atr_by_the_multipliers() {
+    let model = SlTpModel::AtrBased {
+        sl_multiplier: 1.5,
+        tp_multiplier: 3.0,
+    };
+    // ATR of 4 at a price of 200 is 2%
+    assert_sl_tp(
+        sl_tp(&model, Long, &values_with_atr(Some(4.0)), 200.0),
+        (3.0, 6.0),
+    );
+    assert_eq!(
+        sl_tp(&model, Long, &values_with_atr(None), 200.0),
+        (0.0, 0.0)
+    );
+    assert_eq!(
+        sl_tp(&model, Long, &values_with_atr(Some(0.0)), 200.0),
+        (0.0, 0.0)
+    );
 
+    // The default keeps the original 1.2 / 2.0 multiples
+    assert_sl_tp(
+        sl_tp(
+            &SlTpModel::default(),
+            Long,
+            &values_with_atr(Some(4.0)),
+            200.0,
+        ),
+        (2.4, 4.0),
+    );
+}
 
+#[test]
+fn fixed_sl_tp_ignores_indicators() {
+    let model = SlTpModel::FixedPct {
+        sl_pct: 1.0,
+        tp_pct: 2.5,
+    };
+    assert_eq!(
+        sl_tp(&model, Long, &values_with_atr(None), 200.0),
+        (1.0, 2.5)
+    );
+    assert_eq!(
+        sl_tp(&model, Long, &values_with_atr(Some(4.0)), 200.0),
+        (1.0, 2.5)
+    );
+}
+
+#[test]
+fn risk_reward_tp_is_the_sl_times_the_ratio() {
+    let model = SlTpModel::RiskReward {
+        sl_pct: 1.5,
+        rr_ratio: 3.0,
+    };
+    assert_sl_tp(
+        sl_tp(&model, Long, &values_with_atr(None), 200.0),
+        (1.5, 4.5),
+    );
+}
+
+#[test]
+fn bollinger_sl_tp_targets_the_middle_band() {
+    let mut values = values_with_atr(Some(4.0));
+    values.bollinger_upper = Some(220.0);
+    values.bollinger_middle = Some(210.0);
+    values.bollinger_lower = Some(190.0);
+    let at_band = SlTpModel::BollingerBased { sl_at_band: true };
+
+    // TP at the middle (210), a Long's SL at the lower band (190)
+    assert_sl_tp(sl_tp(&at_band, Long, &values, 200.0), (5.0, 5.0));
+    // Above the middle a Long still stops out at the lower band
+    assert_sl_tp(
+        sl_tp(&at_band, Long, &values, 215.0),
+        (25.0 / 2.15, 5.0 / 2.15),
+    );
+    // A Short stops out at the upper band (220)
+    assert_sl_tp(
+        sl_tp(&at_band, Short, &values, 215.0),
+        (5.0 / 2.15, 5.0 / 2.15),
+    );
+    // A Neutral signal has no side to stop out on
+    assert_sl_tp(sl_tp(&at_band, Neutral, &values, 200.0), (0.0, 5.0));
+
+    // Otherwise the SL falls back to 1.2 × ATR
+    let atr_stop = SlTpModel::BollingerBased { sl_at_band: false };
+    assert_sl_tp(sl_tp(&atr_stop, Short, &values, 200.0), (2.4, 5.0));
+
+    assert_eq!(
+        sl_tp(&at_band, Long, &values_with_atr(Some(4.0)), 200.0),
+        (0.0, 0.0)
+    );
+}
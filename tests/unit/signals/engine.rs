@@ -1,10 +1,11 @@
 //! Unit tests for signal engine
 
-use chrono::Utc;
+use chrono::{Duration, Utc};
 use perptrix::models::indicators::Candle;
 use perptrix::models::strategy::{
-    AggregationConfig, AggregationMethod, Condition, Comparison, IndicatorType, Rule, RuleType,
-    SignalThresholds, Strategy, StrategyConfig,
+    AggregationConfig, AggregationMethod, Comparison, Condition, IndicatorType, PositionSizeModel,
+    Rule, RuleType, SignalThresholds, SlTpModel, Strategy, StrategyConfig,
+    DEFAULT_MAX_KELLY_FRACTION,
 };
 use perptrix::signals::engine::SignalEngine;
 
@@ -36,8 +37,19 @@ fn create_test_strategy(symbol: &str) -> Strategy {
                     long_min: 1, // Lower threshold so tests can pass
                     short_max: -1,
                 },
+                filter: None,
             },
+            category_weights: None,
+            timeframe_weights: None,
+            decay_model: None,
+            position_size_model: PositionSizeModel::default(),
+            max_kelly_fraction: DEFAULT_MAX_KELLY_FRACTION,
+            sl_tp_model: SlTpModel::default(),
+            time_filter: None,
+            use_heikin_ashi: false,
         },
+        enabled: true,
+        source_file: None,
         created_at: Utc::now(),
         updated_at: Utc::now(),
     }
@@ -53,7 +65,7 @@ fn create_uptrend_candles(count: usize) -> Vec<Candle> {
             price - 0.2,
             price + 0.1,
             1000.0,
-            Utc::now(),
+            Utc::now() + Duration::minutes(i as i64),
         )
         .with_open_interest(10_000.0 + (i as f64 * 20.0))
         .with_funding_rate(0.0001);
@@ -0,0 +1,125 @@
+//! Unit tests for the rolling Sharpe ratio confidence penalty.
+
+use perptrix::models::signal::{SignalDirection, SignalOutput};
+use perptrix::signals::risk::{
+    RollingSharpePenalty, RollingStats, MIN_SHARPE_TRADES, SHARPE_TARGET,
+};
+
+/// Sample mean and variance computed in one batch
+fn naive_stats(values: &[f64]) -> (f64, f64) {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    (mean, variance)
+}
+
+#[test]
+fn rolling_stats_match_a_batch_computation_over_the_window() {
+    let values: Vec<f64> = (0..200)
+        .map(|i| (i as f64 * 0.37).sin() * 3.0 + (i % 7) as f64 - 2.5)
+        .collect();
+    let mut stats = RollingStats::new(25);
+
+    for (i, &value) in values.iter().enumerate() {
+        stats.push(value);
+        let window = &values[(i + 1).saturating_sub(25)..=i];
+        assert_eq!(stats.len(), window.len());
+        if window.len() < 2 {
+            continue;
+        }
+        let (mean, variance) = naive_stats(window);
+        assert!((stats.mean() - mean).abs() < 1e-9, "mean at {}", i);
+        assert!(
+            (stats.variance() - variance).abs() < 1e-9,
+            "variance at {}",
+            i
+        );
+    }
+}
+
+#[test]
+fn rolling_stats_of_fewer_than_two_values_have_no_variance() {
+    let mut stats = RollingStats::new(1);
+    assert!(stats.is_empty());
+    assert_eq!(stats.variance(), 0.0);
+
+    stats.push(4.0);
+    stats.push(-2.0);
+    assert_eq!(stats.len(), 1);
+    assert_eq!(stats.mean(), -2.0);
+    assert_eq!(stats.variance(), 0.0);
+}
+
+#[test]
+fn confidence_is_scaled_by_sharpe_below_target() {
+    assert_eq!(
+        RollingSharpePenalty::penalize_confidence(0.8, SHARPE_TARGET),
+        0.8
+    );
+    assert_eq!(RollingSharpePenalty::penalize_confidence(0.8, 2.5), 0.8);
+    assert!((RollingSharpePenalty::penalize_confidence(0.8, 0.5) - 0.4).abs() < 1e-12);
+    assert!((RollingSharpePenalty::penalize_confidence(0.8, 0.25) - 0.2).abs() < 1e-12);
+    assert_eq!(RollingSharpePenalty::penalize_confidence(0.8, 0.0), 0.0);
+    assert_eq!(RollingSharpePenalty::penalize_confidence(0.8, -1.2), 0.0);
+    assert_eq!(
+        RollingSharpePenalty::penalize_confidence(0.8, f64::NAN),
+        0.8
+    );
+}
+
+#[test]
+fn sharpe_is_neutral_until_enough_trades_close() {
+    let mut penalty = RollingSharpePenalty::default();
+    for _ in 0..MIN_SHARPE_TRADES - 1 {
+        penalty.record_trade(-1.0);
+    }
+    assert_eq!(penalty.current_sharpe(), SHARPE_TARGET);
+
+    penalty.record_trade(-1.0);
+    assert_eq!(penalty.current_sharpe(), 0.0);
+}
+
+#[test]
+fn sharpe_is_mean_over_sample_std_dev() {
+    let returns: Vec<f64> = (0..20)
+        .map(|i| if i % 4 == 0 { -1.0 } else { 2.0 })
+        .collect();
+    let mut penalty = RollingSharpePenalty::new(returns.len());
+    for &pnl in &returns {
+        penalty.record_trade(pnl);
+    }
+
+    let (mean, variance) = naive_stats(&returns);
+    assert_eq!(penalty.trade_count(), returns.len());
+    assert!((penalty.current_sharpe() - mean / variance.sqrt()).abs() < 1e-9);
+}
+
+fn signal(direction: SignalDirection, price: f64) -> SignalOutput {
+    SignalOutput::new(direction, 0.7, 2.0, 4.0, vec![], "BTC".to_string(), price)
+}
+
+#[test]
+fn signals_close_paper_trades_at_stop_loss_or_take_profit() {
+    let mut penalty = RollingSharpePenalty::default();
+
+    // Long from 100: SL at 98, TP at 104
+    penalty.record_signal(1, &signal(SignalDirection::Long, 100.0));
+    penalty.record_signal(1, &signal(SignalDirection::Neutral, 101.0));
+    // Another strategy's signal does not touch strategy 1's trade
+    penalty.record_signal(2, &signal(SignalDirection::Neutral, 97.0));
+    assert_eq!(penalty.trade_count(), 0);
+
+    penalty.record_signal(1, &signal(SignalDirection::Short, 104.5));
+    assert_eq!(penalty.trade_count(), 1);
+
+    // The short opened at 104.5 stops out above 106.59
+    penalty.record_signal(1, &signal(SignalDirection::Neutral, 107.0));
+    assert_eq!(penalty.trade_count(), 2);
+
+    for _ in 0..MIN_SHARPE_TRADES {
+        penalty.record_signal(1, &signal(SignalDirection::Long, 100.0));
+        penalty.record_signal(1, &signal(SignalDirection::Neutral, 97.0));
+    }
+    // Mostly losses: 4% once, -2% for every other trade
+    assert!(penalty.current_sharpe() < 0.0);
+}
@@ -1,10 +1,11 @@
 //! Integration tests for market scenarios
 
-use chrono::Utc;
+use chrono::{Duration, Utc};
 use perptrix::models::indicators::Candle;
 use perptrix::models::strategy::{
-    AggregationConfig, AggregationMethod, Condition, Comparison, IndicatorType, Rule, RuleType,
-    SignalThresholds, Strategy, StrategyConfig,
+    AggregationConfig, AggregationMethod, Comparison, Condition, IndicatorType, PositionSizeModel,
+    Rule, RuleType, SignalThresholds, SlTpModel, Strategy, StrategyConfig,
+    DEFAULT_MAX_KELLY_FRACTION,
 };
 use perptrix::signals::engine::SignalEngine;
 
@@ -36,8 +37,19 @@ fn create_test_strategy(symbol: &str) -> Strategy {
                     long_min: 1, // Lower threshold so tests can pass
                     short_max: -1,
                 },
+                filter: None,
             },
+            category_weights: None,
+            timeframe_weights: None,
+            decay_model: None,
+            position_size_model: PositionSizeModel::default(),
+            max_kelly_fraction: DEFAULT_MAX_KELLY_FRACTION,
+            sl_tp_model: SlTpModel::default(),
+            time_filter: None,
+            use_heikin_ashi: false,
         },
+        enabled: true,
+        source_file: None,
         created_at: Utc::now(),
         updated_at: Utc::now(),
     }
@@ -53,7 +65,7 @@ fn create_uptrend_candles(count: usize) -> Vec<Candle> {
             base - 0.2,
             base + 0.1,
             1000.0 + (i as f64 * 10.0),
-            Utc::now(),
+            Utc::now() + Duration::minutes(i as i64),
         )
         .with_open_interest(10_000.0 + (i as f64 * 50.0))
         .with_funding_rate(0.0002);
@@ -65,14 +77,14 @@ fn create_uptrend_candles(count: usize) -> Vec<Candle> {
 fn create_downtrend_candles(count: usize) -> Vec<Candle> {
     let mut candles = Vec::new();
     for i in 0..count {
-        let base = 100.0 - (i as f64 * 0.5);
+        let base = 200.0 - (i as f64 * 0.5);
         let candle = Candle::new(
             base,
             base + 0.2,
             base - 0.3,
             base - 0.1,
             1000.0 + (i as f64 * 10.0),
-            Utc::now(),
+            Utc::now() + Duration::minutes(i as i64),
         )
         .with_open_interest(10_000.0 + (i as f64 * 80.0))
         .with_funding_rate(-0.0006);
@@ -86,7 +98,8 @@ fn create_ranging_candles(count: usize, min: f64, max: f64) -> Vec<Candle> {
     for i in 0..count {
         let cycle = (i as f64 % 20.0) / 20.0;
         let price = min + (max - min) * cycle;
-        let candle = Candle::new(price, price + 0.1, price - 0.1, price, 1000.0, Utc::now())
+        let time = Utc::now() + Duration::minutes(i as i64);
+        let candle = Candle::new(price, price + 0.1, price - 0.1, price, 1000.0, time)
             .with_open_interest(9_500.0 + (i as f64 % 10.0) * 20.0)
             .with_funding_rate(0.0);
         candles.push(candle);
@@ -105,7 +118,7 @@ fn create_volatile_candles(count: usize) -> Vec<Candle> {
             base - volatility.abs() - 0.5,
             base + volatility,
             1000.0 + (i as f64 * 50.0),
-            Utc::now(),
+            Utc::now() + Duration::minutes(i as i64),
         )
         .with_open_interest(10_000.0 + ((i as f64 % 7.0) - 3.0) * 120.0)
         .with_funding_rate(if i % 2 == 0 { 0.0004 } else { -0.0004 });
@@ -129,7 +142,7 @@ fn create_reversal_candles(count: usize) -> Vec<Candle> {
             base - 0.2,
             base + if i < midpoint { 0.1 } else { -0.1 },
             1000.0 + (i as f64 * 10.0),
-            Utc::now(),
+            Utc::now() + Duration::minutes(i as i64),
         )
         .with_open_interest(if i < midpoint {
             10_000.0 + (i as f64 * 60.0)
@@ -262,3 +275,29 @@ fn extreme_negative_funding_supports_long_bias() {
             | perptrix::models::signal::SignalDirection::Neutral
     ));
 }
+
+#[test]
+fn default_strategy_changes_direction_over_trending_series() {
+    let strategy = Strategy::default();
+    assert_eq!(strategy.name, "Default RSI+MACD Long");
+    assert_eq!(strategy.symbol, "BTC");
+
+    // Rally, then a sharp sell-off
+    let mut candles = create_uptrend_candles(150);
+    let last_close = candles.last().unwrap().close;
+    for i in 0..100 {
+        let base = last_close - (i as f64 * 1.5);
+        let time = Utc::now() + Duration::minutes(150 + i as i64);
+        candles.push(Candle::new(base, base + 0.2, base - 0.3, base - 0.1, 1000.0, time));
+    }
+    assert_eq!(candles.len(), 250);
+
+    let directions: Vec<_> = (50..=candles.len())
+        .filter_map(|end| SignalEngine::evaluate(&candles[..end], &strategy))
+        .map(|signal| signal.direction)
+        .collect();
+    assert!(!directions.is_empty());
+
+    let changes = directions.windows(2).filter(|w| w[0] != w[1]).count();
+    assert!(changes >= 1, "Expected at least one direction change");
+}
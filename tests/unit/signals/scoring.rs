@@ -0,0 +1,24 @@
+//! Unit tests for continuous indicator scoring.
+
+use perptrix::signals::scoring::{normalize_open_interest, open_interest_confirmation};
+
+#[test]
+fn open_interest_above_average_scores_positive() {
+    assert!((normalize_open_interest(110.0, 100.0) - 0.1).abs() < 1e-12);
+    assert!(normalize_open_interest(90.0, 100.0) < 0.0);
+}
+
+#[test]
+fn open_interest_score_is_clamped() {
+    assert_eq!(normalize_open_interest(500.0, 100.0), 1.0);
+    assert_eq!(normalize_open_interest(-500.0, 100.0), -1.0);
+    assert_eq!(normalize_open_interest(100.0, 0.0), 0.0);
+    assert_eq!(normalize_open_interest(f64::NAN, 100.0), 0.0);
+}
+
+#[test]
+fn confirmation_is_positive_when_oi_rises_with_price() {
+    assert!(open_interest_confirmation(0.5, 3.0) > 0.0);
+    assert!(open_interest_confirmation(0.5, -3.0) < 0.0);
+    assert_eq!(open_interest_confirmation(0.5, 0.0), 0.0);
+}
@@ -0,0 +1,1487 @@
+//! Unit tests for strategy condition evaluation and candle checks.
+
+use crate::test_utils::trending_candles;
+use chrono::{Duration, TimeZone, Utc};
+use perptrix::indicators::momentum::macd::{MACDSignal, MACD};
+use perptrix::indicators::momentum::rsi::{RSI, RSI_DEFAULT_PERIOD};
+use perptrix::indicators::volatility::atr::VolatilityRegime;
+use perptrix::metrics::Metrics;
+use perptrix::models::indicators::Candle;
+use perptrix::models::signal::{SignalDirection, SignalOutput};
+use perptrix::models::strategy::{
+    AggregationConfig, AggregationFilter, AggregationMethod, Comparison, Condition,
+    EvaluationTrace, IndicatorType, PositionSizeModel, Rule, RuleResult, RuleType, SlTpModel,
+    Strategy, TimeWindow,
+};
+use perptrix::strategies::evaluator::{IndicatorValues, LiveMarketData, StrategyEvaluator};
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+fn rsi_oversold_condition(params: HashMap<String, serde_json::Value>) -> Condition {
+    Condition {
+        indicator: IndicatorType::RSI,
+        indicator_params: params,
+        comparison: Comparison::SignalState,
+        threshold: None,
+        signal_state: Some("Oversold".to_string()),
+    }
+}
+
+fn values_with_rsi(rsi: f64) -> IndicatorValues {
+    let mut values = IndicatorValues::new(100.0);
+    values.rsi_value = Some(rsi);
+    values
+}
+
+#[test]
+fn rsi_oversold_uses_custom_threshold() {
+    let condition = rsi_oversold_condition(HashMap::from([(
+        "oversold_threshold".to_string(),
+        json!(20.0),
+    )]));
+
+    assert!(!StrategyEvaluator::evaluate_condition(
+        &condition,
+        &values_with_rsi(28.0)
+    ));
+    assert!(StrategyEvaluator::evaluate_condition(
+        &condition,
+        &values_with_rsi(18.0)
+    ));
+}
+
+#[test]
+fn rsi_oversold_defaults_to_thirty() {
+    let condition = rsi_oversold_condition(HashMap::new());
+    assert!(StrategyEvaluator::evaluate_condition(
+        &condition,
+        &values_with_rsi(28.0)
+    ));
+    assert!(!StrategyEvaluator::evaluate_condition(
+        &condition,
+        &values_with_rsi(32.0)
+    ));
+}
+
+#[test]
+fn rsi_divergence_state_overrides_oversold() {
+    use perptrix::indicators::momentum::rsi::DivergenceType;
+
+    let mut condition = rsi_oversold_condition(HashMap::new());
+    condition.signal_state = Some("BullishDivergence".to_string());
+    let mut values = values_with_rsi(45.0);
+    assert!(!StrategyEvaluator::evaluate_condition(&condition, &values));
+
+    values.rsi_divergence = Some(DivergenceType::Bullish);
+    assert!(StrategyEvaluator::evaluate_condition(&condition, &values));
+
+    // A divergence is reported instead of the oversold zone
+    values.rsi_value = Some(25.0);
+    condition.signal_state = Some("Oversold".to_string());
+    assert!(!StrategyEvaluator::evaluate_condition(&condition, &values));
+}
+
+fn steady_candles(count: usize) -> Vec<Candle> {
+    let start = Utc::now() - Duration::minutes(count as i64);
+    (0..count)
+        .map(|i| {
+            let price = 100.0 + (i as f64 * 0.7).sin();
+            Candle::new(
+                price,
+                price + 0.5,
+                price - 0.5,
+                price,
+                1_000.0,
+                start + Duration::minutes(i as i64),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn critical_candle_anomalies_skip_evaluation() {
+    let strategy = Strategy::default();
+    let candles = steady_candles(100);
+    assert!(StrategyEvaluator::evaluate_strategy(&strategy, &candles).is_some());
+
+    // Warnings alone still evaluate
+    let mut quiet = candles.clone();
+    quiet[40].volume = 0.0;
+    assert!(StrategyEvaluator::evaluate_strategy(&strategy, &quiet).is_some());
+
+    let corruptions: [fn(&mut Vec<Candle>); 4] = [
+        |candles| candles[60].close = candles[60].high + 1.0,
+        |candles| candles[60].low = candles[60].close + 0.1,
+        |candles| candles[60].open = -candles[60].open,
+        |candles| candles[60].timestamp = candles[59].timestamp,
+    ];
+    for (i, corrupt) in corruptions.iter().enumerate() {
+        let mut corrupted = candles.clone();
+        corrupt(&mut corrupted);
+        assert!(
+            StrategyEvaluator::evaluate_strategy(&strategy, &corrupted).is_none(),
+            "corruption {} was evaluated",
+            i
+        );
+    }
+}
+
+fn spread_below(bps: f64) -> Condition {
+    Condition {
+        indicator: IndicatorType::Spread,
+        indicator_params: HashMap::new(),
+        comparison: Comparison::LessThan,
+        threshold: Some(bps),
+        signal_state: None,
+    }
+}
+
+#[test]
+fn spread_condition_compares_basis_points() {
+    let condition = spread_below(5.0);
+    let mut values = IndicatorValues::new(100.0);
+    // No order book data: the condition cannot pass
+    assert!(!StrategyEvaluator::evaluate_condition(&condition, &values));
+
+    // 0.02 wide around a 100.01 mid is ~2 bps
+    values.set_spread(Some((100.0, 100.02)));
+    assert!((values.bid_ask_spread_pct.unwrap() - 0.02 / 100.01 * 100.0).abs() < 1e-12);
+    assert!(StrategyEvaluator::evaluate_condition(&condition, &values));
+
+    values.set_spread(Some((100.0, 100.1)));
+    assert!(!StrategyEvaluator::evaluate_condition(&condition, &values));
+
+    // A crossed book has no valid spread
+    values.set_spread(Some((100.1, 100.0)));
+    assert_eq!(values.bid_ask_spread_pct, None);
+}
+
+#[test]
+fn spread_rule_uses_top_of_book() {
+    let mut strategy = Strategy::default();
+    strategy.config.rules = vec![Rule {
+        id: "tight_spread".to_string(),
+        rule_type: RuleType::Condition,
+        weight: Some(1.0),
+        operator: None,
+        condition: Some(spread_below(5.0)),
+        children: None,
+    }];
+    strategy.config.aggregation.thresholds.long_min = 1;
+    strategy.config.aggregation.thresholds.short_max = -1;
+    let candles = steady_candles(100);
+
+    let tight =
+        StrategyEvaluator::evaluate_strategy_with_spread(&strategy, &candles, Some((100.0, 100.01)))
+            .unwrap();
+    assert_eq!(tight.direction, SignalDirection::Long);
+
+    let wide =
+        StrategyEvaluator::evaluate_strategy_with_spread(&strategy, &candles, Some((100.0, 100.5)))
+            .unwrap();
+    assert_eq!(wide.direction, SignalDirection::Short);
+
+    // Without order book data the condition fails like any missing indicator
+    let unknown = StrategyEvaluator::evaluate_strategy(&strategy, &candles).unwrap();
+    assert_eq!(unknown.direction, SignalDirection::Short);
+}
+
+#[test]
+fn trace_and_indicator_evaluations_use_the_given_market_data_and_time() {
+    let time_of_day_rule = Rule {
+        id: "afternoon".to_string(),
+        rule_type: RuleType::Condition,
+        weight: Some(1.0),
+        operator: None,
+        condition: Some(Condition {
+            indicator: IndicatorType::TimeOfDay,
+            indicator_params: HashMap::new(),
+            comparison: Comparison::GreaterEqual,
+            threshold: Some(12.0),
+            signal_state: None,
+        }),
+        children: None,
+    };
+    let mut strategy = Strategy::default();
+    strategy.config.rules = vec![
+        Rule {
+            id: "tight_spread".to_string(),
+            rule_type: RuleType::Condition,
+            weight: Some(1.0),
+            operator: None,
+            condition: Some(spread_below(5.0)),
+            children: None,
+        },
+        time_of_day_rule,
+    ];
+    strategy.config.aggregation.thresholds.long_min = 2;
+    let candles = steady_candles(100);
+    let tight = LiveMarketData {
+        top_of_book: Some((100.0, 100.01)),
+        ..LiveMarketData::default()
+    };
+    let morning = Utc.with_ymd_and_hms(2024, 1, 1, 9, 0, 0).unwrap();
+    let afternoon = Utc.with_ymd_and_hms(2024, 1, 1, 15, 0, 0).unwrap();
+
+    let (signal, trace) =
+        StrategyEvaluator::evaluate_with_trace(&strategy, &candles, &tight, afternoon).unwrap();
+    assert_eq!(signal.direction, SignalDirection::Long);
+    assert!(trace.rules.iter().all(|rule| rule.passed));
+
+    let (_, trace) =
+        StrategyEvaluator::evaluate_with_trace(&strategy, &candles, &tight, morning).unwrap();
+    let passed: Vec<bool> = trace.rules.iter().map(|rule| rule.passed).collect();
+    assert_eq!(passed, [true, false]);
+
+    let (signal, values) =
+        StrategyEvaluator::evaluate_with_indicators(&strategy, &candles, &tight, afternoon)
+            .unwrap();
+    assert_eq!(signal.direction, SignalDirection::Long);
+    assert!(values.bid_ask_spread_pct.is_some());
+
+    let (signal, values) = StrategyEvaluator::evaluate_with_indicators(
+        &strategy,
+        &candles,
+        &LiveMarketData::default(),
+        afternoon,
+    )
+    .unwrap();
+    assert_ne!(signal.direction, SignalDirection::Long);
+    assert_eq!(values.bid_ask_spread_pct, None);
+}
+
+#[test]
+fn fibonacci_condition_compares_the_retracement_ratio() {
+    let deep_pullback = Condition {
+        indicator: IndicatorType::Fibonacci,
+        indicator_params: HashMap::new(),
+        comparison: Comparison::GreaterThan,
+        threshold: Some(0.618),
+        signal_state: None,
+    };
+    let mut values = IndicatorValues::new(100.0);
+    // No confirmed swing yet
+    assert!(!StrategyEvaluator::evaluate_condition(&deep_pullback, &values));
+
+    values.fib_position_ratio = Some(0.7);
+    assert!(StrategyEvaluator::evaluate_condition(&deep_pullback, &values));
+    values.fib_position_ratio = Some(0.382);
+    assert!(!StrategyEvaluator::evaluate_condition(&deep_pullback, &values));
+}
+
+fn buy_sell_ratio_condition(comparison: Comparison, threshold: f64) -> Condition {
+    Condition {
+        indicator: IndicatorType::BuySellRatio,
+        indicator_params: HashMap::new(),
+        comparison,
+        threshold: Some(threshold),
+        signal_state: None,
+    }
+}
+
+#[test]
+fn buy_sell_ratio_condition_compares_the_ratio() {
+    let buyers = buy_sell_ratio_condition(Comparison::GreaterThan, 1.5);
+    let sellers = buy_sell_ratio_condition(Comparison::LessThan, 0.67);
+    let mut values = IndicatorValues::new(100.0);
+    // No trade data: neither condition can pass
+    assert!(!StrategyEvaluator::evaluate_condition(&buyers, &values));
+    assert!(!StrategyEvaluator::evaluate_condition(&sellers, &values));
+
+    values.buy_sell_ratio = Some(2.0);
+    assert!(StrategyEvaluator::evaluate_condition(&buyers, &values));
+    assert!(!StrategyEvaluator::evaluate_condition(&sellers, &values));
+
+    values.buy_sell_ratio = Some(0.5);
+    assert!(!StrategyEvaluator::evaluate_condition(&buyers, &values));
+    assert!(StrategyEvaluator::evaluate_condition(&sellers, &values));
+}
+
+#[test]
+fn buy_sell_ratio_rule_uses_live_market_data() {
+    let mut strategy = Strategy::default();
+    strategy.config.rules = vec![Rule {
+        id: "buyers_in_control".to_string(),
+        rule_type: RuleType::Condition,
+        weight: Some(1.0),
+        operator: None,
+        condition: Some(buy_sell_ratio_condition(Comparison::GreaterThan, 1.5)),
+        children: None,
+    }];
+    strategy.config.aggregation.thresholds.long_min = 1;
+    strategy.config.aggregation.thresholds.short_max = -1;
+    let candles = steady_candles(100);
+    let market_data = |ratio| LiveMarketData {
+        top_of_book: None,
+        buy_sell_ratio: Some(ratio),
+    };
+
+    let buying = StrategyEvaluator::evaluate_strategy_with_market_data(
+        &strategy,
+        &candles,
+        market_data(3.0),
+        None,
+    )
+    .unwrap();
+    assert_eq!(buying.direction, SignalDirection::Long);
+
+    let selling = StrategyEvaluator::evaluate_strategy_with_market_data(
+        &strategy,
+        &candles,
+        market_data(0.8),
+        None,
+    )
+    .unwrap();
+    assert_eq!(selling.direction, SignalDirection::Short);
+}
+
+fn rsi_rule(id: &str, comparison: Comparison, threshold: f64, weight: f64) -> Rule {
+    Rule {
+        id: id.to_string(),
+        rule_type: RuleType::Condition,
+        weight: Some(weight),
+        operator: None,
+        condition: Some(Condition {
+            indicator: IndicatorType::RSI,
+            indicator_params: HashMap::new(),
+            comparison,
+            threshold: Some(threshold),
+            signal_state: None,
+        }),
+        children: None,
+    }
+}
+
+/// Trace `strategy` as of now, without live market data
+fn trace_now(strategy: &Strategy, candles: &[Candle]) -> Option<(SignalOutput, EvaluationTrace)> {
+    StrategyEvaluator::evaluate_with_trace(
+        strategy,
+        candles,
+        &LiveMarketData::default(),
+        Utc::now(),
+    )
+}
+
+#[test]
+fn trace_reports_each_rule_decision() {
+    let mut strategy = Strategy::default();
+    // RSI is always within [0, 100]
+    strategy.config.rules = vec![
+        rsi_rule("rsi_above_zero", Comparison::GreaterThan, 0.0, 2.0),
+        rsi_rule("rsi_below_zero", Comparison::LessThan, 0.0, 1.0),
+    ];
+    strategy.config.aggregation.method = AggregationMethod::WeightedSum;
+    let candles = steady_candles(100);
+
+    let (signal, trace) = trace_now(&strategy, &candles).unwrap();
+    let plain = StrategyEvaluator::evaluate_strategy(&strategy, &candles).unwrap();
+    assert_eq!(signal.direction, plain.direction);
+    assert_eq!(signal.confidence, plain.confidence);
+
+    let [pass, fail] = trace.rules.as_slice() else {
+        panic!("expected two rule results, got {:?}", trace.rules);
+    };
+    assert_eq!(pass.rule_id, "rsi_above_zero");
+    assert!(pass.passed);
+    assert!(!fail.passed);
+    let rsi = pass.indicator_value.unwrap();
+    assert!((0.0..=100.0).contains(&rsi));
+    assert_eq!(fail.indicator_value, Some(rsi));
+
+    // Weighted sum counts each score (±weight) times the weight again
+    assert_eq!(pass.contribution, 4);
+    assert_eq!(fail.contribution, -1);
+    assert_eq!(trace.total_score, 3);
+
+    assert!(trace_now(&strategy, &candles[..10]).is_none());
+}
+
+#[test]
+fn signals_recommend_a_position_size() {
+    let mut strategy = Strategy::default();
+    strategy.config.rules = vec![rsi_rule("rsi_above_zero", Comparison::GreaterThan, 0.0, 2.0)];
+    let candles = steady_candles(100);
+
+    // Full confidence: the Kelly fraction is 1, capped at the default 0.25
+    let signal = StrategyEvaluator::evaluate_strategy(&strategy, &candles).unwrap();
+    assert_eq!(signal.direction, SignalDirection::Long);
+    assert_eq!(signal.confidence, 1.0);
+    assert!(signal.recommended_sl_pct > 0.0);
+    assert_eq!(signal.recommended_position_size_pct, Some(25.0));
+
+    strategy.config.position_size_model = PositionSizeModel::HalfKelly;
+    strategy.config.max_kelly_fraction = 1.0;
+    let signal = StrategyEvaluator::evaluate_strategy(&strategy, &candles).unwrap();
+    assert_eq!(signal.recommended_position_size_pct, Some(50.0));
+
+    strategy.config.position_size_model = PositionSizeModel::Fixed(0.02);
+    let signal = StrategyEvaluator::evaluate_strategy(&strategy, &candles).unwrap();
+    assert_eq!(signal.recommended_position_size_pct, Some(2.0));
+
+    // Neutral signals recommend no position
+    strategy.config.aggregation.thresholds.long_min = 3;
+    let signal = StrategyEvaluator::evaluate_strategy(&strategy, &candles).unwrap();
+    assert_eq!(signal.direction, SignalDirection::Neutral);
+    assert_eq!(signal.recommended_position_size_pct, None);
+}
+
+#[test]
+fn signals_use_the_strategy_sl_tp_model() {
+    let mut strategy = Strategy::default();
+    strategy.config.rules = vec![rsi_rule("rsi_above_zero", Comparison::GreaterThan, 0.0, 2.0)];
+    strategy.config.sl_tp_model = SlTpModel::RiskReward {
+        sl_pct: 1.5,
+        rr_ratio: 2.0,
+    };
+    let signal = StrategyEvaluator::evaluate_strategy(&strategy, &steady_candles(100)).unwrap();
+    assert_eq!(signal.recommended_sl_pct, 1.5);
+    assert_eq!(signal.recommended_tp_pct, 3.0);
+}
+
+/// Candles fed before `update` first reports a reading
+fn first_reading(candles: &[Candle], update: impl FnMut(&Candle) -> bool) -> Option<usize> {
+    candles.iter().position(update).map(|index| index + 1)
+}
+
+#[test]
+fn warm_up_lookbacks_match_the_first_reading() {
+    use perptrix::indicators::momentum::{cci, cmf, mfi, stochastic_rsi, williams_r};
+    use perptrix::indicators::structure::donchian;
+    use perptrix::indicators::trend::{adx, ema, kama, parabolic_sar};
+    use perptrix::indicators::volatility::{atr_pct, keltner};
+
+    let candles = steady_candles(200);
+    let first = |update: &mut dyn FnMut(&Candle) -> bool| first_reading(&candles, update);
+
+    let mut ribbon = ema::EmaRibbon::default();
+    let lookback = ribbon.lookback();
+    assert_eq!(
+        first(&mut |c| ribbon.update(c.close).is_some()),
+        Some(lookback)
+    );
+    let mut kama = kama::KAMA::default();
+    let lookback = kama.lookback();
+    assert_eq!(
+        first(&mut |c| kama.update(c.close).is_some()),
+        Some(lookback)
+    );
+    let mut sar = parabolic_sar::ParabolicSar::default();
+    let lookback = sar.lookback();
+    assert_eq!(
+        first(&mut |c| sar.update(c.high, c.low, c.close).is_some()),
+        Some(lookback)
+    );
+    let mut adx = adx::ADX::default();
+    let lookback = adx.lookback();
+    assert_eq!(
+        first(&mut |c| adx.update(c.high, c.low, c.close).is_some()),
+        Some(lookback)
+    );
+    let mut rsi = RSI::new(RSI_DEFAULT_PERIOD);
+    let lookback = rsi.lookback();
+    assert_eq!(
+        first(&mut |c| rsi.update(c.close).is_some()),
+        Some(lookback)
+    );
+
+    let mut source = RSI::new(RSI_DEFAULT_PERIOD);
+    let mut stoch_rsi = stochastic_rsi::StochasticRsi::default();
+    let lookback = source.lookback() + stoch_rsi.lookback() - 1;
+    assert_eq!(
+        first(&mut |c| source
+            .update(c.close)
+            .is_some_and(|value| stoch_rsi.update(value).is_some())),
+        Some(lookback)
+    );
+
+    let mut williams_r = williams_r::WilliamsR::default();
+    let lookback = williams_r.lookback();
+    assert_eq!(
+        first(&mut |c| williams_r.update(c.high, c.low, c.close).is_some()),
+        Some(lookback)
+    );
+    let mut cci = cci::CCI::default();
+    let lookback = cci.lookback();
+    assert_eq!(
+        first(&mut |c| cci.update(c.high, c.low, c.close).is_some()),
+        Some(lookback)
+    );
+    let mut mfi = mfi::MFI::default();
+    let lookback = mfi.lookback();
+    assert_eq!(
+        first(&mut |c| mfi.update(c.high, c.low, c.close, c.volume).is_some()),
+        Some(lookback)
+    );
+    let mut cmf = cmf::CMF::default();
+    let lookback = cmf.lookback();
+    assert_eq!(
+        first(&mut |c| cmf.update(c.high, c.low, c.close, c.volume).is_some()),
+        Some(lookback)
+    );
+    let mut keltner = keltner::KeltnerChannel::default();
+    let lookback = keltner.lookback();
+    assert_eq!(
+        first(&mut |c| keltner.update(c.high, c.low, c.close, None).is_some()),
+        Some(lookback)
+    );
+    let mut donchian = donchian::DonchianChannel::default();
+    let lookback = donchian.lookback();
+    assert_eq!(
+        first(&mut |c| donchian.update(c.high, c.low, c.close).is_some()),
+        Some(lookback)
+    );
+    let mut atr_pct = atr_pct::AtrPct::default();
+    let lookback = atr_pct.lookback();
+    assert_eq!(
+        first(&mut |c| atr_pct.update(c.high, c.low, c.close).is_some()),
+        Some(lookback)
+    );
+}
+
+#[test]
+fn indicator_computations_are_recorded() {
+    let strategy = Strategy::default();
+    let metrics = Metrics::default();
+    let evaluate = |candles: &[Candle]| {
+        StrategyEvaluator::evaluate_strategy_with_market_data(
+            &strategy,
+            candles,
+            LiveMarketData::default(),
+            Some(&metrics),
+        )
+    };
+    let computed = |name| {
+        metrics
+            .indicator_computations_total
+            .with_label_values(&[name])
+            .get()
+    };
+
+    // The 89-period EMA ribbon and 52-candle Ichimoku are still warming up
+    let candles = steady_candles(50);
+    evaluate(&candles).unwrap();
+    let (_, values) = StrategyEvaluator::evaluate_with_indicators(
+        &strategy,
+        &candles,
+        &LiveMarketData::default(),
+        Utc::now(),
+    ).unwrap();
+    let missing: Vec<&str> = values
+        .computations
+        .iter()
+        .filter(|c| !c.computed)
+        .map(|c| c.indicator)
+        .collect();
+    assert_eq!(missing, vec!["ema_ribbon", "ichimoku"]);
+    assert!(values.computations.iter().all(|c| !c.is_failure()));
+    assert_eq!(computed("rsi"), 1.0);
+    assert_eq!(
+        metrics.indicator_computations_none_ratio.get(),
+        2.0 / values.computations.len() as f64
+    );
+
+    evaluate(&steady_candles(100)).unwrap();
+    assert_eq!(computed("rsi"), 2.0);
+    assert_eq!(metrics.indicator_computations_none_ratio.get(), 0.0);
+    for computation in &values.computations {
+        assert_eq!(
+            metrics
+                .indicator_computation_failures_total
+                .with_label_values(&[computation.indicator])
+                .get(),
+            0.0
+        );
+    }
+
+    // Too few candles to evaluate records nothing
+    assert!(evaluate(&steady_candles(10)).is_none());
+    assert_eq!(computed("rsi"), 2.0);
+}
+
+/// Latest reading of a standalone RSI over `candles`
+fn standalone_rsi(period: usize, candles: &[Candle]) -> f64 {
+    let mut rsi = RSI::new(period);
+    candles.iter().filter_map(|c| rsi.update(c.close)).last().unwrap()
+}
+
+#[test]
+fn rsi_period_comes_from_condition_params() {
+    let candles = steady_candles(100);
+    let rsi_reading = |params: HashMap<String, serde_json::Value>| {
+        let mut rule = rsi_rule("rsi_above_zero", Comparison::GreaterThan, 0.0, 1.0);
+        rule.condition.as_mut().unwrap().indicator_params = params;
+        let mut strategy = Strategy::default();
+        strategy.config.rules = vec![rule];
+        let (_, trace) = trace_now(&strategy, &candles).unwrap();
+        trace.rules[0].indicator_value.unwrap()
+    };
+
+    let fast = rsi_reading(HashMap::from([("period".to_string(), json!(9))]));
+    let slow = rsi_reading(HashMap::from([("period".to_string(), json!(21))]));
+    let default = rsi_reading(HashMap::new());
+
+    assert!((fast - standalone_rsi(9, &candles)).abs() < 1e-9);
+    assert!((slow - standalone_rsi(21, &candles)).abs() < 1e-9);
+    assert!((default - standalone_rsi(RSI_DEFAULT_PERIOD, &candles)).abs() < 1e-9);
+    assert!((fast - slow).abs() > 1e-6, "{} == {}", fast, slow);
+}
+
+#[test]
+fn rsi_conditions_with_different_periods_read_their_own_rsi() {
+    let candles = steady_candles(100);
+    let rule = |id: &str, period: Option<u32>| {
+        let mut rule = rsi_rule(id, Comparison::GreaterThan, 0.0, 1.0);
+        if let Some(period) = period {
+            rule.condition.as_mut().unwrap().indicator_params =
+                HashMap::from([("period".to_string(), json!(period))]);
+        }
+        rule
+    };
+    let mut strategy = Strategy::default();
+    strategy.config.rules = vec![
+        rule("rsi_9", Some(9)),
+        rule("rsi_21", Some(21)),
+        rule("rsi_default", None),
+    ];
+
+    let (_, trace) = trace_now(&strategy, &candles).unwrap();
+    let readings: Vec<f64> = trace
+        .rules
+        .iter()
+        .map(|rule| rule.indicator_value.unwrap())
+        .collect();
+    for (reading, period) in readings.iter().zip([9, 21, RSI_DEFAULT_PERIOD]) {
+        let expected = standalone_rsi(period, &candles);
+        assert!(
+            (reading - expected).abs() < 1e-9,
+            "RSI({}) {} != {}",
+            period,
+            reading,
+            expected
+        );
+    }
+}
+
+#[test]
+fn bollinger_percent_b_and_bandwidth_are_comparable() {
+    let mut values = IndicatorValues::new(100.0);
+    values.bb_percent_b = Some(0.9);
+    values.bb_bandwidth = Some(1.5);
+    let condition = |indicator, comparison, threshold| Condition {
+        indicator,
+        indicator_params: HashMap::new(),
+        comparison,
+        threshold: Some(threshold),
+        signal_state: None,
+    };
+
+    assert!(StrategyEvaluator::evaluate_condition(
+        &condition(IndicatorType::BollingerPctB, Comparison::GreaterThan, 0.8),
+        &values
+    ));
+    assert!(StrategyEvaluator::evaluate_condition(
+        &condition(IndicatorType::BollingerBandwidth, Comparison::LessThan, 2.0),
+        &values
+    ));
+    assert!(!StrategyEvaluator::evaluate_condition(
+        &condition(IndicatorType::BollingerBandwidth, Comparison::LessThan, 1.0),
+        &values
+    ));
+    assert!(serde_json::from_value::<IndicatorType>(json!("BollingerPctB")).is_ok());
+}
+
+fn supertrend_bullish_rule(id: &str, atr_period: usize, multiplier: f64) -> Rule {
+    Rule {
+        id: id.to_string(),
+        rule_type: RuleType::Condition,
+        weight: Some(1.0),
+        operator: None,
+        condition: Some(Condition {
+            indicator: IndicatorType::SuperTrend,
+            indicator_params: HashMap::from([
+                ("atr_period".to_string(), json!(atr_period)),
+                ("multiplier".to_string(), json!(multiplier)),
+            ]),
+            comparison: Comparison::SignalState,
+            threshold: None,
+            signal_state: Some("Bullish".to_string()),
+        }),
+        children: None,
+    }
+}
+
+#[test]
+fn supertrend_multipliers_get_separate_calculators() {
+    // A steady climb, then a pullback of 6 that only the tight bands catch
+    let start = Utc::now() - Duration::minutes(64);
+    let closes = (0..60)
+        .map(|i| 100.0 + i as f64)
+        .chain((1..=4).map(|i| 159.0 - 1.5 * i as f64));
+    let candles: Vec<Candle> = closes
+        .enumerate()
+        .map(|(i, close)| {
+            Candle::new(close, close + 1.0, close - 1.0, close, 1_000.0, start + Duration::minutes(i as i64))
+        })
+        .collect();
+
+    let mut strategy = Strategy::default();
+    strategy.config.rules = vec![
+        supertrend_bullish_rule("tight", 10, 1.0),
+        supertrend_bullish_rule("loose", 10, 5.0),
+        // Same settings as "tight", so it shares its calculator
+        Rule {
+            id: "tight_again".to_string(),
+            ..supertrend_bullish_rule("", 10, 1.0)
+        },
+    ];
+    let (_, trace) = trace_now(&strategy, &candles).unwrap();
+
+    let passed: Vec<(&str, bool)> = trace.rules.iter().map(|r| (r.rule_id.as_str(), r.passed)).collect();
+    assert_eq!(passed, [("tight", false), ("loose", true), ("tight_again", false)]);
+
+    // Before the pullback both agree
+    let (_, trace) = trace_now(&strategy, &candles[..60]).unwrap();
+    assert!(trace.rules.iter().all(|r| r.passed));
+}
+
+const MACD_STATES: [&str; 4] = [
+    "BullishCross",
+    "BearishCross",
+    "BullishMomentum",
+    "BearishMomentum",
+];
+
+fn macd_state_rule(state: &str, (fast, slow, signal): (u32, u32, u32)) -> Rule {
+    Rule {
+        id: format!("macd_{}_{}_{}_{}", fast, slow, signal, state),
+        rule_type: RuleType::Condition,
+        weight: Some(1.0),
+        operator: None,
+        condition: Some(Condition {
+            indicator: IndicatorType::MACD,
+            indicator_params: HashMap::from([
+                ("fast_period".to_string(), json!(fast)),
+                ("slow_period".to_string(), json!(slow)),
+                ("signal_period".to_string(), json!(signal)),
+            ]),
+            comparison: Comparison::SignalState,
+            threshold: None,
+            signal_state: Some(state.to_string()),
+        }),
+        children: None,
+    }
+}
+
+/// MACD line and signal state after each candle of a standalone MACD
+fn standalone_macd(periods: (u32, u32, u32), candles: &[Candle]) -> Vec<(f64, Option<String>)> {
+    let mut macd = MACD::new(periods.0 as usize, periods.1 as usize, periods.2 as usize);
+    candles
+        .iter()
+        .map(|c| {
+            let (value, _, _, signal) = macd.update(c.close);
+            let state = (signal != MACDSignal::Neutral).then(|| format!("{:?}", signal));
+            (value, state)
+        })
+        .collect()
+}
+
+#[test]
+fn macd_periods_come_from_condition_params() {
+    let candles = steady_candles(120);
+    let standard = (12, 26, 9);
+    let fast = (8, 21, 5);
+    let mut strategy = Strategy::default();
+    strategy.config.rules = [standard, fast]
+        .iter()
+        .flat_map(|&periods| {
+            MACD_STATES
+                .iter()
+                .map(move |state| macd_state_rule(state, periods))
+        })
+        .collect();
+
+    let expected_standard = standalone_macd(standard, &candles);
+    let expected_fast = standalone_macd(fast, &candles);
+    let mut states_differ = false;
+    for len in 60..=candles.len() {
+        let (_, trace) =
+            trace_now(&strategy, &candles[..len]).unwrap();
+        // Each half of the rules reads the calculator for its own periods
+        for (rules, expected) in [
+            (&trace.rules[..4], &expected_standard[len - 1]),
+            (&trace.rules[4..], &expected_fast[len - 1]),
+        ] {
+            assert!((rules[0].indicator_value.unwrap() - expected.0).abs() < 1e-9);
+            let state = MACD_STATES
+                .iter()
+                .zip(rules)
+                .find(|(_, rule)| rule.passed)
+                .map(|(state, _)| state.to_string());
+            assert_eq!(state, expected.1, "after {} candles", len);
+        }
+        states_differ |= expected_standard[len - 1].1 != expected_fast[len - 1].1;
+    }
+    assert!(states_differ, "(12, 26, 9) and (8, 21, 5) never disagreed");
+
+    // Without params the standard periods are used
+    strategy.config.rules[0]
+        .condition
+        .as_mut()
+        .unwrap()
+        .indicator_params = HashMap::new();
+    let (_, trace) = trace_now(&strategy, &candles).unwrap();
+    let expected = expected_standard.last().unwrap();
+    assert!((trace.rules[0].indicator_value.unwrap() - expected.0).abs() < 1e-9);
+}
+
+fn candlestick_rule(state: &str) -> Rule {
+    Rule {
+        id: format!("candlestick_{}", state),
+        rule_type: RuleType::Condition,
+        weight: Some(1.0),
+        operator: None,
+        condition: Some(Condition {
+            indicator: IndicatorType::CandlestickPattern,
+            indicator_params: HashMap::new(),
+            comparison: Comparison::SignalState,
+            threshold: None,
+            signal_state: Some(state.to_string()),
+        }),
+        children: None,
+    }
+}
+
+#[test]
+fn candlestick_pattern_on_the_latest_candle_matches_signal_states() {
+    let mut candles = steady_candles(60);
+    let start = candles.last().unwrap().timestamp;
+    // Morning star: long bearish candle, small body, bullish reversal
+    for (i, (open, high, low, close)) in [
+        (102.0, 102.1, 99.9, 100.0),
+        (99.8, 100.0, 99.5, 99.7),
+        (99.9, 101.6, 99.8, 101.5),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let timestamp = start + Duration::minutes(i as i64 + 1);
+        candles.push(Candle::new(open, high, low, close, 1_000.0, timestamp));
+    }
+
+    let mut strategy = Strategy::default();
+    strategy.config.rules = [
+        "MorningStar",
+        "Bullish",
+        "Bearish",
+        "BullishEngulfing",
+        "Hammer",
+    ]
+    .into_iter()
+    .map(candlestick_rule)
+    .collect();
+    let (_, trace) = trace_now(&strategy, &candles).unwrap();
+
+    let passed: Vec<bool> = trace.rules.iter().map(|r| r.passed).collect();
+    assert_eq!(passed, [true, true, false, false, false]);
+    // Numeric comparisons see the strength, signed by direction
+    assert_eq!(trace.rules[0].indicator_value, Some(3.0));
+
+    // Without a pattern on the last candle every state fails
+    candles.pop();
+    let (_, trace) = trace_now(&strategy, &candles).unwrap();
+    assert!(trace.rules.iter().all(|r| !r.passed));
+}
+
+/// Values computed at `hour:minute` UTC on 2024-01-01
+fn values_at(hour: u32, minute: u32) -> IndicatorValues {
+    let mut values = IndicatorValues::new(100.0);
+    values.evaluated_at = Utc.with_ymd_and_hms(2024, 1, 1, hour, minute, 0).unwrap();
+    values
+}
+
+fn time_of_day_condition(comparison: Comparison, hour: f64) -> Condition {
+    Condition {
+        indicator: IndicatorType::TimeOfDay,
+        indicator_params: HashMap::new(),
+        comparison,
+        threshold: Some(hour),
+        signal_state: None,
+    }
+}
+
+#[test]
+fn time_of_day_is_the_utc_hour_across_midnight() {
+    let late = time_of_day_condition(Comparison::GreaterEqual, 22.0);
+    let early = time_of_day_condition(Comparison::LessEqual, 1.0);
+
+    let before_midnight = values_at(23, 59);
+    assert!(StrategyEvaluator::evaluate_condition(
+        &late,
+        &before_midnight
+    ));
+    assert!(!StrategyEvaluator::evaluate_condition(
+        &early,
+        &before_midnight
+    ));
+
+    let after_midnight = values_at(0, 1);
+    assert!(!StrategyEvaluator::evaluate_condition(
+        &late,
+        &after_midnight
+    ));
+    assert!(StrategyEvaluator::evaluate_condition(
+        &early,
+        &after_midnight
+    ));
+}
+
+#[test]
+fn time_filter_scales_the_score_across_midnight() {
+    let mut strategy = Strategy::default();
+    strategy.config.time_filter = Some(vec![
+        TimeWindow {
+            start_utc_hour: 22,
+            end_utc_hour: 0,
+            weight_multiplier: 0.5,
+        },
+        TimeWindow {
+            start_utc_hour: 0,
+            end_utc_hour: 8,
+            weight_multiplier: 0.0,
+        },
+    ]);
+    let results = vec![
+        RuleResult::new("a".to_string(), true, 1, 1.0),
+        RuleResult::new("b".to_string(), true, 1, 1.0),
+        RuleResult::new("c".to_string(), true, 2, 1.0),
+    ];
+    let score = |hour, minute| {
+        StrategyEvaluator::strategy_score(&results, &strategy.config, &values_at(hour, minute))
+    };
+
+    assert_eq!(score(21, 59), 4);
+    assert_eq!(score(23, 59), 2);
+    assert_eq!(score(0, 1), 0);
+    assert_eq!(score(8, 0), 4);
+}
+
+fn kama_rule(
+    id: &str,
+    comparison: Comparison,
+    threshold: Option<f64>,
+    state: Option<&str>,
+) -> Rule {
+    Rule {
+        id: id.to_string(),
+        rule_type: RuleType::Condition,
+        weight: Some(1.0),
+        operator: None,
+        condition: Some(Condition {
+            indicator: IndicatorType::KAMA,
+            indicator_params: HashMap::new(),
+            comparison,
+            threshold,
+            signal_state: state.map(str::to_string),
+        }),
+        children: None,
+    }
+}
+
+#[test]
+fn kama_conditions_read_the_adaptive_average() {
+    let start = Utc::now() - Duration::minutes(60);
+    let candles: Vec<Candle> = (0..60)
+        .map(|i| {
+            let timestamp = start + Duration::minutes(i);
+            Candle::new(100.0, 100.5, 99.5, 100.0, 1_000.0, timestamp)
+        })
+        .collect();
+
+    let mut strategy = Strategy::default();
+    strategy.config.rules = vec![
+        kama_rule("flat", Comparison::SignalState, None, Some("Flat")),
+        kama_rule("trending", Comparison::SignalState, None, Some("Trending")),
+        kama_rule("at_price", Comparison::GreaterEqual, Some(100.0), None),
+    ];
+    let (_, trace) = trace_now(&strategy, &candles).unwrap();
+
+    let passed: Vec<bool> = trace.rules.iter().map(|r| r.passed).collect();
+    assert_eq!(passed, [true, false, true]);
+    assert_eq!(trace.rules[2].indicator_value, Some(100.0));
+}
+
+fn obv_state_rule(state: &str) -> Rule {
+    Rule {
+        id: format!("obv_{}", state),
+        rule_type: RuleType::Condition,
+        weight: Some(1.0),
+        operator: None,
+        condition: Some(Condition {
+            indicator: IndicatorType::OBV,
+            indicator_params: HashMap::new(),
+            comparison: Comparison::SignalState,
+            threshold: None,
+            signal_state: Some(state.to_string()),
+        }),
+        children: None,
+    }
+}
+
+/// Zig-zag drifting by `drift` per pair of candles, with heavy volume on up
+/// candles when `heavy_up` and on down candles otherwise
+fn diverging_candles(drift: f64, heavy_up: bool) -> Vec<Candle> {
+    let start = Utc::now() - Duration::minutes(100);
+    (0..100)
+        .map(|i| {
+            let up = i % 2 == 1;
+            let price = 100.0 + (i / 2) as f64 * drift + if up { 1.0 } else { 0.0 };
+            let volume = if up == heavy_up { 1_000.0 } else { 100.0 };
+            Candle::new(
+                price,
+                price + 0.5,
+                price - 0.5,
+                price,
+                volume,
+                start + Duration::minutes(i),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn obv_divergence_signal_states_follow_price_and_volume() {
+    let mut strategy = Strategy::default();
+    strategy.config.rules = vec![
+        obv_state_rule("BullishDivergence"),
+        obv_state_rule("BearishDivergence"),
+    ];
+
+    let passed = |candles: &[Candle]| -> Vec<bool> {
+        let (_, trace) = trace_now(&strategy, candles).unwrap();
+        trace.rules.iter().map(|rule| rule.passed).collect()
+    };
+
+    // Falling price, buying volume
+    assert_eq!(passed(&diverging_candles(-0.4, true)), [true, false]);
+    // Rising price, selling volume
+    assert_eq!(passed(&diverging_candles(0.4, false)), [false, true]);
+    // Volume agrees with price
+    assert_eq!(passed(&diverging_candles(0.4, true)), [false, false]);
+}
+
+/// Strategy that always scores long (RSI is never below zero) unless `filter` suppresses it
+fn filtered_strategy(filter: AggregationFilter) -> Strategy {
+    let mut strategy = Strategy::default();
+    strategy.config.rules = vec![rsi_rule(
+        "rsi_above_zero",
+        Comparison::GreaterThan,
+        0.0,
+        2.0,
+    )];
+    strategy.config.aggregation.filter = Some(filter);
+    strategy
+}
+
+#[test]
+fn min_adx_filter_suppresses_ranging_markets() {
+    let strategy = filtered_strategy(AggregationFilter::MinAdx { threshold: 25.0 });
+
+    let (signal, trace) = trace_now(&strategy, &steady_candles(100)).unwrap();
+    assert_eq!(signal.direction, SignalDirection::Neutral);
+    assert_eq!(trace.total_score, 0);
+    assert!(trace
+        .rules
+        .iter()
+        .all(|rule| rule.passed && rule.contribution == 0));
+
+    let trending =
+        StrategyEvaluator::evaluate_strategy(&strategy, &trending_candles(100, 100.0, 1.0))
+            .unwrap();
+    assert_eq!(trending.direction, SignalDirection::Long);
+}
+
+#[test]
+fn max_volatility_regime_filter_sits_out_volatility_spikes() {
+    let strategy = filtered_strategy(AggregationFilter::MaxVolatilityRegime {
+        regime: VolatilityRegime::Elevated,
+    });
+
+    let mut candles = steady_candles(100);
+    let calm = StrategyEvaluator::evaluate_strategy(&strategy, &candles).unwrap();
+    assert_eq!(calm.direction, SignalDirection::Long);
+
+    // A range 30× the usual pushes ATR far above its recent average
+    let last = candles.last_mut().unwrap();
+    last.high += 15.0;
+    last.low -= 15.0;
+    let spike = StrategyEvaluator::evaluate_strategy(&strategy, &candles).unwrap();
+    assert_eq!(spike.direction, SignalDirection::Neutral);
+}
+
+#[test]
+fn aggregation_filter_deserializes_from_json() {
+    let aggregation: AggregationConfig = serde_json::from_value(json!({
+        "method": "Sum",
+        "thresholds": { "long_min": 1, "short_max": -1 },
+        "filter": { "MaxVolatilityRegime": { "regime": "High" } }
+    }))
+    .unwrap();
+    assert_eq!(
+        aggregation.filter,
+        Some(AggregationFilter::MaxVolatilityRegime {
+            regime: VolatilityRegime::High
+        })
+    );
+
+    let aggregation: AggregationConfig = serde_json::from_value(json!({
+        "method": "Sum",
+        "thresholds": { "long_min": 1, "short_max": -1 },
+        "filter": { "MinAdx": { "threshold": 20.0 } }
+    }))
+    .unwrap();
+    assert_eq!(
+        aggregation.filter,
+        Some(AggregationFilter::MinAdx { threshold: 20.0 })
+    );
+
+    // Existing configs without a filter still load and serialize without one
+    let aggregation: AggregationConfig = serde_json::from_value(json!({
+        "method": "Sum",
+        "thresholds": { "long_min": 1, "short_max": -1 }
+    }))
+    .unwrap();
+    assert_eq!(aggregation.filter, None);
+    assert!(serde_json::to_value(&aggregation)
+        .unwrap()
+        .get("filter")
+        .is_none());
+}
+
+/// Strategies with distinct ids that always score long on `steady_candles`
+fn numbered_strategies(count: usize) -> Vec<Strategy> {
+    (0..count)
+        .map(|i| {
+            let mut strategy = Strategy {
+                id: Some(i as i64),
+                ..Strategy::default()
+            };
+            strategy.config.rules = vec![
+                rsi_rule("rsi_above_zero", Comparison::GreaterThan, 0.0, 2.0),
+                rsi_rule("rsi_below_hundred", Comparison::LessThan, 100.0, 1.0),
+            ];
+            strategy
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn concurrent_evaluation_is_bounded_and_keeps_strategy_order() {
+    let strategies = numbered_strategies(10);
+    let active = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+
+    let results = {
+        let (active, peak) = (active.clone(), peak.clone());
+        StrategyEvaluator::evaluate_concurrent(
+            &strategies,
+            3,
+            std::time::Duration::from_secs(5),
+            move |strategy| {
+                let running = active.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(running, Ordering::SeqCst);
+                // Later strategies finish first
+                let id = strategy.id.unwrap();
+                std::thread::sleep(std::time::Duration::from_millis(30 - 2 * id as u64));
+                active.fetch_sub(1, Ordering::SeqCst);
+                id
+            },
+        )
+        .await
+    };
+
+    let ids: Vec<i64> = results.into_iter().map(Result::unwrap).collect();
+    assert_eq!(ids, (0..10).collect::<Vec<_>>());
+    assert!(peak.load(Ordering::SeqCst) <= 3);
+}
+
+#[tokio::test]
+async fn concurrent_evaluation_times_out_slow_strategies() {
+    let strategies = numbered_strategies(3);
+    let start = Instant::now();
+
+    let results = StrategyEvaluator::evaluate_concurrent(
+        &strategies,
+        3,
+        std::time::Duration::from_millis(200),
+        |strategy| {
+            if strategy.id == Some(1) {
+                std::thread::sleep(std::time::Duration::from_secs(2));
+            }
+            strategy.id
+        },
+    )
+    .await;
+
+    assert!(start.elapsed() < std::time::Duration::from_secs(1));
+    assert_eq!(results[0].as_ref().ok(), Some(&Some(0)));
+    assert!(results[1].is_err());
+    assert_eq!(results[2].as_ref().ok(), Some(&Some(2)));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn concurrent_evaluation_matches_sequential_and_scales_with_cores() {
+    let strategies = numbered_strategies(10);
+    let candles = steady_candles(250);
+
+    let time_sequential = || {
+        let start = Instant::now();
+        let signals: Vec<_> = strategies
+            .iter()
+            .filter_map(|strategy| StrategyEvaluator::evaluate_strategy(strategy, &candles))
+            .collect();
+        (start.elapsed(), signals)
+    };
+    let (mut sequential, expected) = time_sequential();
+    let mut concurrent = std::time::Duration::MAX;
+    for _ in 0..3 {
+        sequential = sequential.min(time_sequential().0);
+        let start = Instant::now();
+        let signals = StrategyEvaluator::evaluate_all_concurrent(&strategies, &candles).await;
+        concurrent = concurrent.min(start.elapsed());
+
+        assert_eq!(signals.len(), expected.len());
+        for (signal, expected) in signals.iter().zip(&expected) {
+            assert_eq!(signal.direction, expected.direction);
+            assert_eq!(signal.confidence, expected.confidence);
+        }
+    }
+
+    // Ten strategies spread over four or more cores should take well under
+    // the sequential time
+    let cores = std::thread::available_parallelism().map_or(1, |n| n.get());
+    if cores >= 4 {
+        assert!(
+            concurrent * 3 < sequential * 2,
+            "concurrent {:?} vs sequential {:?} on {} cores",
+            concurrent,
+            sequential,
+            cores
+        );
+    }
+}
+
+fn greater_than_rule(
+    id: &str,
+    indicator: IndicatorType,
+    params: HashMap<String, serde_json::Value>,
+    threshold: f64,
+) -> Rule {
+    Rule {
+        id: id.to_string(),
+        rule_type: RuleType::Condition,
+        weight: Some(1.0),
+        operator: None,
+        condition: Some(Condition {
+            indicator,
+            indicator_params: params,
+            comparison: Comparison::GreaterThan,
+            threshold: Some(threshold),
+            signal_state: None,
+        }),
+        children: None,
+    }
+}
+
+/// 200 candles closing at 100, then `jump_candles` closing at 110
+fn candles_with_price_jump(jump_candles: i64) -> Vec<Candle> {
+    let start = Utc::now() - Duration::minutes(200 + jump_candles);
+    (0..200 + jump_candles)
+        .map(|i| {
+            let close = if i < 200 { 100.0 } else { 110.0 };
+            let timestamp = start + Duration::minutes(i);
+            Candle::new(close, close + 0.5, close - 0.5, close, 1_000.0, timestamp)
+        })
+        .collect()
+}
+
+#[test]
+fn sma_conditions_compare_the_simple_average() {
+    let candles = candles_with_price_jump(5);
+
+    let mut strategy = Strategy::default();
+    strategy.config.rules = vec![
+        greater_than_rule("sma20", IndicatorType::SMA20, HashMap::new(), 102.0),
+        greater_than_rule("sma50", IndicatorType::SMA50, HashMap::new(), 102.0),
+        greater_than_rule("sma200", IndicatorType::SMA200, HashMap::new(), 100.0),
+        greater_than_rule(
+            "sma10",
+            IndicatorType::SMA,
+            HashMap::from([("period".to_string(), json!(10))]),
+            104.0,
+        ),
+    ];
+    let (_, trace) = trace_now(&strategy, &candles).unwrap();
+
+    let values: Vec<Option<f64>> = trace.rules.iter().map(|r| r.indicator_value).collect();
+    let expected = [
+        (15.0 * 100.0 + 5.0 * 110.0) / 20.0,
+        (45.0 * 100.0 + 5.0 * 110.0) / 50.0,
+        (195.0 * 100.0 + 5.0 * 110.0) / 200.0,
+        (5.0 * 100.0 + 5.0 * 110.0) / 10.0,
+    ];
+    for (value, expected) in values.iter().zip(expected) {
+        assert!((value.unwrap() - expected).abs() < 1e-9, "{:?}", values);
+    }
+    let passed: Vec<bool> = trace.rules.iter().map(|r| r.passed).collect();
+    assert_eq!(passed, [true, false, true, true]);
+}
+
+#[test]
+fn sma_without_a_period_param_is_the_20_period_average() {
+    let candles = candles_with_price_jump(5);
+
+    let mut strategy = Strategy::default();
+    strategy.config.rules = vec![
+        greater_than_rule("sma", IndicatorType::SMA, HashMap::new(), 0.0),
+        greater_than_rule("sma20", IndicatorType::SMA20, HashMap::new(), 0.0),
+    ];
+    let (_, trace) = trace_now(&strategy, &candles).unwrap();
+
+    assert_eq!(trace.rules[0].indicator_value, trace.rules[1].indicator_value);
+}
+
+#[test]
+fn sma_longer_than_the_series_has_no_value() {
+    let candles = candles_with_price_jump(5);
+
+    let mut strategy = Strategy::default();
+    strategy.config.rules = vec![greater_than_rule(
+        "sma500",
+        IndicatorType::SMA,
+        HashMap::from([("period".to_string(), json!(500))]),
+        0.0,
+    )];
+    let (_, trace) = trace_now(&strategy, &candles).unwrap();
+
+    assert_eq!(trace.rules[0].indicator_value, None);
+    assert!(!trace.rules[0].passed);
+}
+
+#[test]
+fn ema_reacts_to_a_price_jump_faster_than_the_sma() {
+    let candles = candles_with_price_jump(5);
+    let period = HashMap::from([
+        ("fast_period".to_string(), json!(20)),
+        ("slow_period".to_string(), json!(50)),
+    ]);
+
+    let mut strategy = Strategy::default();
+    strategy.config.rules = vec![
+        greater_than_rule("sma20", IndicatorType::SMA20, HashMap::new(), 103.0),
+        greater_than_rule("ema20", IndicatorType::EMA, period, 103.0),
+    ];
+    let (_, trace) = trace_now(&strategy, &candles).unwrap();
+
+    let sma = trace.rules[0].indicator_value.unwrap();
+    let ema = trace.rules[1].indicator_value.unwrap();
+    assert!(ema > sma, "EMA {} should lead SMA {}", ema, sma);
+    assert!(ema < 110.0);
+    let passed: Vec<bool> = trace.rules.iter().map(|r| r.passed).collect();
+    assert_eq!(passed, [false, true]);
+}
+
+#[test]
+fn volume_profile_conditions_read_the_poc_and_value_area() {
+    // Most volume trades at 1000, with lighter volume at the levels around it
+    let start = Utc::now() - Duration::minutes(60);
+    let candles: Vec<Candle> = (0..60)
+        .map(|i| {
+            let (close, volume) = match i % 4 {
+                0 | 2 => (1_000.0, 500.0),
+                1 => (990.0, 300.0),
+                _ => (1_010.0, 300.0),
+            };
+            let timestamp = start + Duration::minutes(i);
+            Candle::new(close, close + 1.0, close - 1.0, close, volume, timestamp)
+        })
+        .collect();
+
+    let mut strategy = Strategy::default();
+    strategy.config.rules = vec![
+        greater_than_rule("poc", IndicatorType::VolumeProfilePOC, HashMap::new(), 999.0),
+        greater_than_rule("vah", IndicatorType::VolumeProfileVAH, HashMap::new(), 1_005.0),
+        greater_than_rule("val", IndicatorType::VolumeProfileVAL, HashMap::new(), 995.0),
+    ];
+    let (_, trace) = trace_now(&strategy, &candles).unwrap();
+
+    let values: Vec<Option<f64>> = trace.rules.iter().map(|r| r.indicator_value).collect();
+    assert_eq!(values, [Some(1_000.0), Some(1_010.0), Some(990.0)]);
+    let passed: Vec<bool> = trace.rules.iter().map(|r| r.passed).collect();
+    assert_eq!(passed, [true, true, false]);
+}
+
+#[test]
+fn heikin_ashi_strategies_compute_indicators_on_transformed_candles() {
+    use perptrix::indicators::structure::heikin_ashi::transform_to_heikin_ashi;
+
+    // Choppy uptrend so Heikin-Ashi closes differ from the raw ones
+    let start = Utc::now() - Duration::minutes(100);
+    let mut price = 100.0;
+    let candles: Vec<Candle> = (0..100)
+        .map(|i| {
+            let open = price;
+            price += if i % 2 == 0 { 3.0 } else { -2.0 };
+            let timestamp = start + Duration::minutes(i);
+            Candle::new(open, open.max(price) + 0.5, open.min(price) - 0.5, price, 10.0, timestamp)
+        })
+        .collect();
+    let rsi_reading = |use_heikin_ashi: bool| {
+        let mut strategy = Strategy::default();
+        strategy.config.rules = vec![rsi_rule("rsi_above_zero", Comparison::GreaterThan, 0.0, 1.0)];
+        strategy.config.use_heikin_ashi = use_heikin_ashi;
+        let (_, trace) = trace_now(&strategy, &candles).unwrap();
+        trace.rules[0].indicator_value.unwrap()
+    };
+
+    let raw = rsi_reading(false);
+    let smoothed = rsi_reading(true);
+    assert!((raw - standalone_rsi(RSI_DEFAULT_PERIOD, &candles)).abs() < 1e-9);
+    let ha_candles = transform_to_heikin_ashi(&candles);
+    assert!((smoothed - standalone_rsi(RSI_DEFAULT_PERIOD, &ha_candles)).abs() < 1e-9);
+    assert!((raw - smoothed).abs() > 1e-6);
+}
+
+#[test]
+fn evaluation_time_can_be_set_for_historical_replays() {
+    let time_of_day_rule = Rule {
+        id: "hour".to_string(),
+        rule_type: RuleType::Condition,
+        weight: Some(1.0),
+        operator: None,
+        condition: Some(Condition {
+            indicator: IndicatorType::TimeOfDay,
+            indicator_params: HashMap::new(),
+            comparison: Comparison::GreaterEqual,
+            threshold: Some(12.0),
+            signal_state: None,
+        }),
+        children: None,
+    };
+    let mut strategy = Strategy::default();
+    strategy.config.rules = vec![time_of_day_rule];
+    strategy.config.aggregation.thresholds.long_min = 1;
+    let candles = steady_candles(100);
+
+    let morning = Utc.with_ymd_and_hms(2024, 1, 1, 9, 0, 0).unwrap();
+    let afternoon = Utc.with_ymd_and_hms(2024, 1, 1, 15, 0, 0).unwrap();
+    let at = |time| {
+        StrategyEvaluator::evaluate_strategy_at(&strategy, &candles, time)
+            .unwrap()
+            .direction
+    };
+    assert_eq!(at(morning), SignalDirection::Neutral);
+    assert_eq!(at(afternoon), SignalDirection::Long);
+}
@@ -0,0 +1,115 @@
+//! Unit tests for multi-timeframe strategy evaluation.
+
+use crate::test_utils::trending_candles;
+use perptrix::models::indicators::Candle;
+use perptrix::models::signal::SignalDirection;
+use perptrix::models::strategy::{
+    AggregationConfig, AggregationMethod, Comparison, Condition, IndicatorType, PositionSizeModel,
+    Rule, RuleType, SignalThresholds, SlTpModel, Strategy, StrategyConfig,
+    DEFAULT_MAX_KELLY_FRACTION,
+};
+use perptrix::strategies::{MultiTimeframeEvaluator, StrategyEvaluator};
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+fn rsi_above_50_strategy(timeframe_weights: &[(&str, f64)]) -> Strategy {
+    Strategy {
+        config: StrategyConfig {
+            rules: vec![Rule {
+                id: "rsi_above_50".to_string(),
+                rule_type: RuleType::Condition,
+                weight: Some(1.0),
+                operator: None,
+                condition: Some(Condition {
+                    indicator: IndicatorType::RSI,
+                    indicator_params: HashMap::new(),
+                    comparison: Comparison::GreaterThan,
+                    threshold: Some(50.0),
+                    signal_state: None,
+                }),
+                children: None,
+            }],
+            aggregation: AggregationConfig {
+                method: AggregationMethod::Sum,
+                thresholds: SignalThresholds {
+                    long_min: 1,
+                    short_max: -1,
+                },
+                filter: None,
+            },
+            category_weights: None,
+            timeframe_weights: Some(
+                timeframe_weights
+                    .iter()
+                    .map(|(interval, weight)| (interval.to_string(), *weight))
+                    .collect(),
+            ),
+            decay_model: None,
+            position_size_model: PositionSizeModel::default(),
+            max_kelly_fraction: DEFAULT_MAX_KELLY_FRACTION,
+            sl_tp_model: SlTpModel::default(),
+            time_filter: None,
+            use_heikin_ashi: false,
+        },
+        ..Strategy::default()
+    }
+}
+
+fn contradicting_timeframes() -> HashMap<String, Vec<Candle>> {
+    HashMap::from([
+        ("1m".to_string(), trending_candles(100, 100.0, 1.0)),
+        ("1h".to_string(), trending_candles(100, 300.0, -1.0)),
+    ])
+}
+
+#[test]
+fn heavier_timeframe_wins_when_timeframes_contradict() {
+    let candles = contradicting_timeframes();
+
+    let favour_minute = rsi_above_50_strategy(&[("1m", 3.0), ("1h", 1.0)]);
+    let signal =
+        StrategyEvaluator::evaluate_multi_timeframe(&favour_minute, &candles).expect("signal");
+    assert_eq!(signal.direction, SignalDirection::Long);
+    assert_eq!(signal.price, candles["1m"].last().unwrap().close);
+
+    let favour_hour = rsi_above_50_strategy(&[("1m", 1.0), ("1h", 3.0)]);
+    let signal =
+        StrategyEvaluator::evaluate_multi_timeframe(&favour_hour, &candles).expect("signal");
+    assert_eq!(signal.direction, SignalDirection::Short);
+    assert_eq!(signal.price, candles["1h"].last().unwrap().close);
+}
+
+#[test]
+fn short_or_zero_weight_timeframes_are_ignored() {
+    let mut candles = contradicting_timeframes();
+    candles.insert("5m".to_string(), trending_candles(10, 500.0, -1.0));
+
+    let strategy = rsi_above_50_strategy(&[("1h", 0.0)]);
+    let signal = StrategyEvaluator::evaluate_multi_timeframe(&strategy, &candles).expect("signal");
+    assert_eq!(signal.direction, SignalDirection::Long);
+
+    assert!(StrategyEvaluator::evaluate_multi_timeframe(&strategy, &HashMap::new()).is_none());
+}
+
+#[test]
+fn missing_timeframe_is_aggregated_from_finer_series() {
+    // 300 one-minute candles starting on a 5-minute boundary make 60 5m candles
+    let minutes = trending_candles(300, 100.0, 1.0);
+    let candles = HashMap::from([("1m".to_string(), minutes)]);
+
+    let strategy = rsi_above_50_strategy(&[("1m", 0.0), ("5m", 1.0)]);
+    let filled = MultiTimeframeEvaluator::with_aggregated_timeframes(&strategy, &candles);
+    assert_eq!(filled["5m"].len(), 60);
+    assert_eq!(filled["1m"].len(), 300);
+
+    let signal = StrategyEvaluator::evaluate_multi_timeframe(&strategy, &candles).expect("signal");
+    assert_eq!(signal.direction, SignalDirection::Long);
+    assert_eq!(signal.price, candles["1m"].last().unwrap().close);
+
+    // Fetched series are used as-is
+    let unweighted = rsi_above_50_strategy(&[("1m", 1.0)]);
+    assert!(matches!(
+        MultiTimeframeEvaluator::with_aggregated_timeframes(&unweighted, &candles),
+        Cow::Borrowed(_)
+    ));
+}
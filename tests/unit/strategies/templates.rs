@@ -0,0 +1,119 @@
+//! Unit tests for the strategy template library.
+
+use crate::test_utils::trending_candles;
+use perptrix::models::indicators::Candle;
+use perptrix::models::signal::SignalDirection;
+use perptrix::models::strategy::{Strategy, StrategyConfig};
+use perptrix::strategies::{validate_strategy_config, StrategyEvaluator, Templates};
+
+fn templates() -> Vec<(&'static str, StrategyConfig)> {
+    vec![
+        ("macd_rsi_basic", Templates::macd_rsi_basic()),
+        (
+            "trend_following_ema_supertrend",
+            Templates::trend_following_ema_supertrend(),
+        ),
+        (
+            "mean_reversion_bollinger_rsi",
+            Templates::mean_reversion_bollinger_rsi(),
+        ),
+        (
+            "funding_rate_contrarian",
+            Templates::funding_rate_contrarian(),
+        ),
+        (
+            "trend_following_adx_filtered",
+            Templates::trend_following_adx_filtered(),
+        ),
+        (
+            "mean_reversion_calm_markets",
+            Templates::mean_reversion_calm_markets(),
+        ),
+    ]
+}
+
+/// Steady trend with small pullbacks; funding follows the crowd
+fn trending_with_funding(count: usize, start_price: f64, step: f64) -> Vec<Candle> {
+    trending_candles(count, start_price, step)
+        .into_iter()
+        .map(|candle| {
+            candle
+                .with_funding_rate(0.0001 * step.signum())
+                .with_open_interest(10_000.0)
+        })
+        .collect()
+}
+
+fn evaluate(config: StrategyConfig, candles: &[Candle]) -> Option<SignalDirection> {
+    let strategy = Strategy {
+        config,
+        ..Strategy::default()
+    };
+    StrategyEvaluator::evaluate_strategy(&strategy, candles).map(|signal| signal.direction)
+}
+
+#[test]
+fn every_template_emits_a_signal_on_trending_data() {
+    let uptrend = trending_with_funding(250, 100.0, 1.0);
+    let downtrend = trending_with_funding(250, 400.0, -1.0);
+
+    for (name, config) in templates() {
+        assert!(
+            evaluate(config.clone(), &uptrend).is_some(),
+            "{} should emit a signal on an uptrend",
+            name
+        );
+        assert!(
+            evaluate(config, &downtrend).is_some(),
+            "{} should emit a signal on a downtrend",
+            name
+        );
+    }
+}
+
+#[test]
+fn trend_templates_follow_the_trend() {
+    let uptrend = trending_with_funding(250, 100.0, 1.0);
+    let downtrend = trending_with_funding(250, 400.0, -1.0);
+
+    for config in [
+        Templates::macd_rsi_basic(),
+        Templates::trend_following_ema_supertrend(),
+        Templates::trend_following_adx_filtered(),
+    ] {
+        assert_eq!(
+            evaluate(config.clone(), &uptrend),
+            Some(SignalDirection::Long)
+        );
+        assert_eq!(evaluate(config, &downtrend), Some(SignalDirection::Short));
+    }
+
+    // Contrarian templates lean against a crowded uptrend
+    assert_eq!(
+        evaluate(Templates::funding_rate_contrarian(), &uptrend),
+        Some(SignalDirection::Short)
+    );
+    assert_eq!(
+        evaluate(Templates::funding_rate_contrarian(), &downtrend),
+        Some(SignalDirection::Long)
+    );
+}
+
+#[test]
+fn templates_are_valid_and_listed_by_name() {
+    let listed = Templates::list();
+    assert_eq!(listed.len(), templates().len());
+
+    for (name, config) in templates() {
+        assert_eq!(validate_strategy_config(&config), Vec::new());
+        assert!(listed
+            .iter()
+            .any(|t| t.name == name && !t.description.is_empty()));
+        let by_name = Templates::get(name).expect("template by name");
+        assert_eq!(
+            serde_json::to_value(&by_name).unwrap(),
+            serde_json::to_value(&config).unwrap()
+        );
+    }
+    assert!(Templates::get("buy_the_top").is_none());
+}
@@ -0,0 +1,333 @@
+//! Unit tests for strategy config validation.
+
+use perptrix::models::strategy::{
+    Comparison, Condition, DecayConfig, IndicatorType, PositionSizeModel, Rule, RuleType,
+    SlTpModel, StrategyConfig, TimeWindow,
+};
+use perptrix::strategies::{validate_strategy_config, ValidationError};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+fn config_with_params(indicator: IndicatorType, params: &[(&str, Value)]) -> StrategyConfig {
+    StrategyConfig {
+        rules: vec![Rule {
+            id: "param_rule".to_string(),
+            rule_type: RuleType::Condition,
+            weight: Some(1.0),
+            operator: None,
+            condition: Some(Condition {
+                indicator,
+                indicator_params: params
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.clone()))
+                    .collect::<HashMap<_, _>>(),
+                comparison: Comparison::SignalState,
+                threshold: None,
+                signal_state: Some("Oversold".to_string()),
+            }),
+            children: None,
+        }],
+        ..StrategyConfig::default()
+    }
+}
+
+#[test]
+fn rsi_thresholds_must_be_ordered_and_in_range() {
+    let valid = config_with_params(
+        IndicatorType::RSI,
+        &[
+            ("overbought_threshold", json!(80.0)),
+            ("oversold_threshold", json!(20.0)),
+        ],
+    );
+    assert!(validate_strategy_config(&valid).is_empty());
+
+    let inverted = config_with_params(
+        IndicatorType::RSI,
+        &[
+            ("overbought_threshold", json!(25.0)),
+            ("oversold_threshold", json!(40.0)),
+        ],
+    );
+    assert!(!validate_strategy_config(&inverted).is_empty());
+
+    let out_of_range =
+        config_with_params(IndicatorType::RSI, &[("oversold_threshold", json!(0.0))]);
+    assert!(!validate_strategy_config(&out_of_range).is_empty());
+}
+
+#[test]
+fn ema_fast_period_must_be_below_slow_period() {
+    let valid = config_with_params(
+        IndicatorType::EMA,
+        &[("fast_period", json!(9)), ("slow_period", json!(21))],
+    );
+    assert!(validate_strategy_config(&valid).is_empty());
+
+    let inverted = config_with_params(
+        IndicatorType::EMA,
+        &[("fast_period", json!(50)), ("slow_period", json!(20))],
+    );
+    assert!(!validate_strategy_config(&inverted).is_empty());
+}
+
+#[test]
+fn macd_fast_period_must_be_below_slow_period() {
+    let valid = config_with_params(
+        IndicatorType::MACD,
+        &[
+            ("fast_period", json!(8)),
+            ("slow_period", json!(21)),
+            ("signal_period", json!(5)),
+        ],
+    );
+    assert!(validate_strategy_config(&valid).is_empty());
+
+    let inverted = config_with_params(
+        IndicatorType::MACD,
+        &[("fast_period", json!(26)), ("slow_period", json!(12))],
+    );
+    assert!(!validate_strategy_config(&inverted).is_empty());
+
+    // Checked against the default slow period of 26 when only one is set
+    let above_default_slow = config_with_params(IndicatorType::MACD, &[("fast_period", json!(30))]);
+    assert!(!validate_strategy_config(&above_default_slow).is_empty());
+}
+
+#[test]
+fn indicator_params_must_match_the_indicator_schema() {
+    let custom_period = config_with_params(IndicatorType::RSI, &[("period", json!(9))]);
+    assert!(validate_strategy_config(&custom_period).is_empty());
+
+    let fractional = config_with_params(IndicatorType::RSI, &[("period", json!(9.5))]);
+    assert!(!validate_strategy_config(&fractional).is_empty());
+
+    let unknown = config_with_params(IndicatorType::RSI, &[("lookback", json!(9))]);
+    assert!(!validate_strategy_config(&unknown).is_empty());
+}
+
+#[test]
+fn sma_accepts_a_custom_period() {
+    let custom_period = config_with_params(IndicatorType::SMA, &[("period", json!(100))]);
+    assert!(validate_strategy_config(&custom_period).is_empty());
+
+    let zero = config_with_params(IndicatorType::SMA, &[("period", json!(0))]);
+    assert!(!validate_strategy_config(&zero).is_empty());
+}
+
+#[test]
+fn decay_model_needs_positive_half_life_and_unit_threshold() {
+    let with_decay = |half_life_seconds, min_display_threshold| StrategyConfig {
+        decay_model: Some(DecayConfig {
+            half_life_seconds,
+            min_display_threshold,
+        }),
+        ..StrategyConfig::default()
+    };
+
+    assert!(validate_strategy_config(&with_decay(3600, 0.2)).is_empty());
+    assert!(!validate_strategy_config(&with_decay(0, 0.2)).is_empty());
+    assert!(!validate_strategy_config(&with_decay(3600, 1.5)).is_empty());
+    assert!(!validate_strategy_config(&with_decay(3600, f64::NAN)).is_empty());
+}
+
+fn condition_rule(id: &str, weight: f64) -> Rule {
+    let mut rule = config_with_params(IndicatorType::RSI, &[]).rules.remove(0);
+    rule.id = id.to_string();
+    rule.weight = Some(weight);
+    rule
+}
+
+fn group_rule(id: &str, children: Vec<Rule>) -> Rule {
+    Rule {
+        id: id.to_string(),
+        rule_type: RuleType::Group,
+        weight: None,
+        operator: None,
+        condition: None,
+        children: Some(children),
+    }
+}
+
+fn config_with_rules(rules: Vec<Rule>) -> StrategyConfig {
+    StrategyConfig {
+        rules,
+        ..StrategyConfig::default()
+    }
+}
+
+#[test]
+fn default_config_has_no_errors() {
+    assert_eq!(
+        validate_strategy_config(&StrategyConfig::default()),
+        Vec::new()
+    );
+}
+
+#[test]
+fn rule_ids_must_be_unique() {
+    let config = config_with_rules(vec![
+        condition_rule("rsi", 1.0),
+        group_rule("nested", vec![condition_rule("rsi", 1.0)]),
+        condition_rule("rsi", 1.0),
+    ]);
+    assert_eq!(
+        validate_strategy_config(&config),
+        vec![ValidationError::DuplicateRuleId("rsi".to_string())]
+    );
+}
+
+#[test]
+fn groups_may_not_contain_their_own_id() {
+    let config = config_with_rules(vec![group_rule(
+        "outer",
+        vec![group_rule(
+            "inner",
+            vec![condition_rule("outer", 1.0), condition_rule("leaf", 1.0)],
+        )],
+    )]);
+    assert_eq!(
+        validate_strategy_config(&config),
+        vec![ValidationError::RuleCycle("outer".to_string())]
+    );
+}
+
+#[test]
+fn long_threshold_must_be_above_short_threshold() {
+    let mut config = StrategyConfig::default();
+    config.aggregation.thresholds.long_min = 1;
+    config.aggregation.thresholds.short_max = 1;
+    assert_eq!(
+        validate_strategy_config(&config),
+        vec![ValidationError::ThresholdsOverlap {
+            long_min: 1,
+            short_max: 1,
+        }]
+    );
+
+    config.aggregation.thresholds.short_max = 0;
+    assert!(validate_strategy_config(&config).is_empty());
+}
+
+#[test]
+fn out_of_range_params_name_the_rule() {
+    let config = config_with_params(IndicatorType::RSI, &[("oversold_threshold", json!(0.0))]);
+    let errors = validate_strategy_config(&config);
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0],
+        ValidationError::InvalidParams { rule_id, .. } if rule_id == "param_rule"
+    ));
+    assert!(errors[0].to_string().starts_with("Rule 'param_rule': "));
+}
+
+#[test]
+fn at_least_one_rule_must_move_the_score() {
+    assert_eq!(
+        validate_strategy_config(&config_with_rules(Vec::new())),
+        vec![ValidationError::NoDirectionalRule]
+    );
+    // Scores truncate to whole numbers, so fractional weights are neutral
+    assert_eq!(
+        validate_strategy_config(&config_with_rules(vec![group_rule(
+            "group",
+            vec![condition_rule("a", 0.5), condition_rule("b", -0.9)],
+        )])),
+        vec![ValidationError::NoDirectionalRule]
+    );
+    assert!(validate_strategy_config(&config_with_rules(vec![group_rule(
+        "group",
+        vec![condition_rule("a", 0.5), condition_rule("b", -2.0)],
+    )]))
+    .is_empty());
+}
+
+#[test]
+fn every_problem_is_reported() {
+    let mut config =
+        config_with_rules(vec![condition_rule("dup", 0.0), condition_rule("dup", 0.0)]);
+    config.aggregation.thresholds.long_min = -5;
+    let errors = validate_strategy_config(&config);
+    assert_eq!(
+        errors,
+        vec![
+            ValidationError::DuplicateRuleId("dup".to_string()),
+            ValidationError::ThresholdsOverlap {
+                long_min: -5,
+                short_max: -2,
+            },
+            ValidationError::NoDirectionalRule,
+        ]
+    );
+}
+
+#[test]
+fn position_sizes_must_be_fractions_of_equity() {
+    let mut config = StrategyConfig {
+        max_kelly_fraction: 0.0,
+        position_size_model: PositionSizeModel::Fixed(1.5),
+        ..StrategyConfig::default()
+    };
+    let errors = validate_strategy_config(&config);
+    assert_eq!(errors.len(), 2);
+    assert!(errors
+        .iter()
+        .all(|e| matches!(e, ValidationError::InvalidPositionSize(_))));
+
+    config.max_kelly_fraction = 1.0;
+    config.position_size_model = PositionSizeModel::Fixed(0.02);
+    assert!(validate_strategy_config(&config).is_empty());
+}
+
+#[test]
+fn time_filter_windows_need_valid_hours_and_multipliers() {
+    let window = |start_utc_hour, end_utc_hour, weight_multiplier| TimeWindow {
+        start_utc_hour,
+        end_utc_hour,
+        weight_multiplier,
+    };
+    let mut config = StrategyConfig {
+        time_filter: Some(vec![
+            window(22, 24, 1.0),
+            window(8, 8, 0.5),
+            window(0, 8, -1.0),
+        ]),
+        ..StrategyConfig::default()
+    };
+    let errors = validate_strategy_config(&config);
+    assert_eq!(errors.len(), 3);
+    assert!(errors
+        .iter()
+        .all(|e| matches!(e, ValidationError::InvalidTimeFilter(_))));
+
+    config.time_filter = Some(vec![window(22, 2, 0.0), window(13, 21, 1.5)]);
+    assert!(validate_strategy_config(&config).is_empty());
+}
+
+#[test]
+fn sl_tp_models_need_finite_non_negative_values() {
+    let mut config = StrategyConfig {
+        sl_tp_model: SlTpModel::AtrBased {
+            sl_multiplier: -1.2,
+            tp_multiplier: f64::NAN,
+        },
+        ..StrategyConfig::default()
+    };
+    let errors = validate_strategy_config(&config);
+    assert_eq!(errors.len(), 2);
+    assert!(errors
+        .iter()
+        .all(|e| matches!(e, ValidationError::InvalidSlTp(_))));
+
+    config.sl_tp_model = SlTpModel::RiskReward {
+        sl_pct: 1.0,
+        rr_ratio: f64::INFINITY,
+    };
+    assert_eq!(validate_strategy_config(&config).len(), 1);
+
+    config.sl_tp_model = SlTpModel::FixedPct {
+        sl_pct: 1.0,
+        tp_pct: 2.5,
+    };
+    assert!(validate_strategy_config(&config).is_empty());
+}
@@ -0,0 +1,37 @@
+//! Shared helpers for the unit tests.
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use perptrix::models::indicators::Candle;
+
+/// 2023-11-14 22:15:00 UTC, on a 15-minute boundary
+const ALIGNED_START: i64 = 1_700_000_100;
+
+/// `i` minutes after [`ALIGNED_START`]
+pub fn minute(i: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp(ALIGNED_START, 0).unwrap() + Duration::minutes(i)
+}
+
+/// Start of the candle series: midnight UTC, so every interval boundary lines up
+fn series_start() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()
+}
+
+/// `count` one-minute candles from `series_start` moving `step` per candle
+/// from `start_price`, with a half-step pullback on every third candle to keep
+/// RSI off its rails. Series of 3n or 3n + 2 candles end on the trend.
+pub fn trending_candles(count: usize, start_price: f64, step: f64) -> Vec<Candle> {
+    (0..count)
+        .map(|i| {
+            let wiggle = if i % 3 == 1 { -step * 0.5 } else { 0.0 };
+            let price = start_price + i as f64 * step + wiggle;
+            Candle::new(
+                price,
+                price + 1.0,
+                price - 1.0,
+                price,
+                1_000.0,
+                series_start() + Duration::minutes(i as i64),
+            )
+        })
+        .collect()
+}